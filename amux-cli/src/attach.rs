@@ -11,11 +11,9 @@ use std::io::{self, Write};
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::ReceiverStream;
 
-#[allow(dead_code)]
 type Result<T> = std::result::Result<T, AttachError>;
 
 /// Attach to a session
-#[allow(dead_code)]
 pub async fn attach(client: &mut Client, session_id: &str) -> Result<()> {
     // Get terminal size
     let (cols, rows) = size().map_err(AttachError::Terminal)?;
@@ -109,7 +107,6 @@ pub async fn attach(client: &mut Client, session_id: &str) -> Result<()> {
 }
 
 /// Convert a key event to bytes to send to PTY
-#[allow(dead_code)]
 fn key_to_bytes(key: &crossterm::event::KeyEvent) -> Vec<u8> {
     use KeyCode::*;
 
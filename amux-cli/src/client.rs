@@ -4,6 +4,7 @@ use crate::error::ClientError;
 use amux_proto::daemon::ccm_daemon_client::CcmDaemonClient;
 use amux_proto::daemon::*;
 use hyper_util::rt::TokioIo;
+use std::os::unix::process::CommandExt;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::time::Duration;
@@ -14,35 +15,42 @@ use tower::service_fn;
 type Result<T> = std::result::Result<T, ClientError>;
 
 /// Amux daemon client
+#[derive(Clone)]
 pub struct Client {
     inner: CcmDaemonClient<Channel>,
 }
 
 impl Client {
-    /// Connect to the daemon via Unix socket, auto-starting if needed
-    pub async fn connect() -> Result<Self> {
+    /// Connect to the daemon via Unix socket
+    ///
+    /// With `auto_start`, a failed connection spawns the daemon and retries
+    /// once it listens; if that fails too, the first connection error is
+    /// returned.
+    pub async fn connect(auto_start: bool) -> Result<Self> {
         let socket_path = Self::socket_path()?;
-
-        // If socket doesn't exist, start daemon
-        if !socket_path.exists() {
-            Self::start_daemon()?;
-            Self::wait_for_daemon(&socket_path).await?;
-        }
-
-        // Try to connect
-        match Self::try_connect(&socket_path).await {
+        match Self::connect_to(&socket_path).await {
             Ok(client) => Ok(client),
-            Err(_) => {
-                // Connection failed, possibly stale socket - clean up and retry
-                let _ = std::fs::remove_file(&socket_path);
-                Self::start_daemon()?;
-                Self::wait_for_daemon(&socket_path).await?;
-                Self::try_connect(&socket_path).await
-            }
+            Err(e) if !auto_start => Err(e),
+            Err(e) => match Self::start_and_connect(&socket_path).await {
+                Ok(client) => Ok(client),
+                Err(start_err) => {
+                    tracing::warn!("Failed to auto-start daemon: {}", start_err);
+                    Err(e)
+                }
+            },
         }
     }
 
-    /// Start the daemon process in background
+    /// Spawn the daemon and connect once its socket appears
+    async fn start_and_connect(socket_path: &Path) -> Result<Self> {
+        // Nothing accepted the connection, so any socket left is stale
+        let _ = std::fs::remove_file(socket_path);
+        Self::start_daemon()?;
+        Self::wait_for_daemon(socket_path).await?;
+        Self::connect_to(socket_path).await
+    }
+
+    /// Start the daemon process in background, detached from the terminal
     fn start_daemon() -> Result<()> {
         // Try to find amux-daemon in the same directory as current executable
         let daemon_path = std::env::current_exe()
@@ -53,6 +61,8 @@ impl Client {
         let daemon_cmd = daemon_path.as_deref().unwrap_or(Path::new("amux-daemon"));
 
         Command::new(daemon_cmd)
+            // Own process group, so Ctrl-C in this terminal doesn't reach it
+            .process_group(0)
             .stdin(Stdio::null())
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -75,8 +85,8 @@ impl Client {
         Err(ClientError::DaemonTimeout)
     }
 
-    /// Try to connect to daemon
-    async fn try_connect(socket_path: &Path) -> Result<Self> {
+    /// Connect to the daemon listening on `socket_path`
+    pub async fn connect_to(socket_path: &Path) -> Result<Self> {
         let path = socket_path.to_path_buf();
         let channel = Endpoint::try_from("http://[::]:50051")
             .map_err(ClientError::ConnectionFailed)?
@@ -102,6 +112,13 @@ impl Client {
             .join("daemon.sock"))
     }
 
+    // ============ Daemon ============
+
+    pub async fn get_status(&mut self) -> Result<DaemonStatus> {
+        let response = self.inner.get_status(Empty {}).await?;
+        Ok(response.into_inner())
+    }
+
     // ============ Provider ============
 
     pub async fn list_providers(&mut self) -> Result<Vec<ProviderInfo>> {
@@ -126,14 +143,67 @@ impl Client {
         Ok(response.into_inner().repos)
     }
 
-    #[allow(dead_code)]
-    pub async fn remove_repo(&mut self, id: &str) -> Result<()> {
+    /// Unregister a repo; `detach_sessions` keeps its running sessions alive
+    pub async fn remove_repo(&mut self, id: &str, detach_sessions: bool) -> Result<()> {
+        self.inner
+            .remove_repo(RemoveRepoRequest {
+                id: id.to_string(),
+                detach_sessions,
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Persist a new repo display order
+    pub async fn reorder_repos(&mut self, repo_ids: Vec<String>) -> Result<()> {
+        self.inner
+            .reorder_repos(ReorderReposRequest { repo_ids })
+            .await?;
+        Ok(())
+    }
+
+    /// Turn worktree checkpoints for new sessions on or off
+    pub async fn set_repo_checkpoints(&mut self, repo_id: &str, enabled: bool) -> Result<()> {
+        self.inner
+            .set_repo_checkpoints(SetRepoCheckpointsRequest {
+                repo_id: repo_id.to_string(),
+                enabled,
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Set (`Some` value) or unset an environment variable of a repo's
+    /// sessions, or only of the sessions in `branch`
+    pub async fn set_repo_env(
+        &mut self,
+        repo_id: &str,
+        branch: Option<&str>,
+        name: &str,
+        value: Option<&str>,
+    ) -> Result<()> {
         self.inner
-            .remove_repo(RemoveRepoRequest { id: id.to_string() })
+            .set_repo_env(SetRepoEnvRequest {
+                repo_id: repo_id.to_string(),
+                branch: branch.map(String::from),
+                name: name.to_string(),
+                value: value.map(String::from),
+            })
             .await?;
         Ok(())
     }
 
+    /// Environment variables of a repo and its worktrees
+    pub async fn list_repo_env(&mut self, repo_id: &str) -> Result<Vec<EnvVar>> {
+        let response = self
+            .inner
+            .list_repo_env(ListRepoEnvRequest {
+                repo_id: repo_id.to_string(),
+            })
+            .await?;
+        Ok(response.into_inner().vars)
+    }
+
     // ============ Worktree ============
 
     pub async fn list_worktrees(&mut self, repo_id: &str) -> Result<Vec<WorktreeInfo>> {
@@ -183,6 +253,72 @@ impl Client {
         Ok(())
     }
 
+    /// Rename a branch; returns the worktree path after the rename
+    pub async fn rename_branch(
+        &mut self,
+        repo_id: &str,
+        old_branch: &str,
+        new_branch: &str,
+    ) -> Result<Option<String>> {
+        let response = self
+            .inner
+            .rename_branch(RenameBranchRequest {
+                repo_id: repo_id.to_string(),
+                old_branch: old_branch.to_string(),
+                new_branch: new_branch.to_string(),
+            })
+            .await?;
+        Ok(response.into_inner().worktree_path)
+    }
+
+    /// Set or clear (`None`) a worktree's default session delete action
+    pub async fn set_worktree_delete_action(
+        &mut self,
+        repo_id: &str,
+        branch: &str,
+        action: Option<DeleteAction>,
+    ) -> Result<()> {
+        self.inner
+            .set_worktree_delete_action(SetWorktreeDeleteActionRequest {
+                repo_id: repo_id.to_string(),
+                branch: branch.to_string(),
+                action: action.map(|a| a as i32),
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Entries of `path` (relative to the worktree root) in a worktree
+    pub async fn list_directory(
+        &mut self,
+        repo_id: &str,
+        branch: &str,
+        path: &str,
+        respect_gitignore: bool,
+    ) -> Result<Vec<DirectoryEntry>> {
+        let response = self
+            .inner
+            .list_directory(ListDirectoryRequest {
+                repo_id: repo_id.to_string(),
+                branch: branch.to_string(),
+                path: path.to_string(),
+                respect_gitignore,
+            })
+            .await?;
+        Ok(response.into_inner().entries)
+    }
+
+    /// Prune worktrees whose directory is gone and drop their sessions
+    pub async fn cleanup_worktrees(&mut self, repo_id: &str) -> Result<CleanupResult> {
+        let response = self
+            .inner
+            .cleanup_worktrees(CleanupWorktreesRequest {
+                repo_id: repo_id.to_string(),
+            })
+            .await?;
+        Ok(response.into_inner())
+    }
+
     // ============ Session ============
 
     pub async fn list_sessions(
@@ -195,11 +331,102 @@ impl Client {
             .list_sessions(ListSessionsRequest {
                 repo_id: repo_id.map(String::from),
                 branch: branch.map(String::from),
+                query: None,
+                ..Default::default()
             })
             .await?;
         Ok(response.into_inner().sessions)
     }
 
+    /// List up to `page_size` sessions from `page_token` (empty for the first page)
+    ///
+    /// Returns the sessions and the token of the next page, empty on the last.
+    pub async fn list_sessions_page(
+        &mut self,
+        repo_id: &str,
+        branch: &str,
+        page_size: u32,
+        page_token: &str,
+    ) -> Result<(Vec<SessionInfo>, String)> {
+        let response = self
+            .inner
+            .list_sessions(ListSessionsRequest {
+                repo_id: Some(repo_id.to_string()),
+                branch: Some(branch.to_string()),
+                query: None,
+                page_size,
+                page_token: page_token.to_string(),
+            })
+            .await?
+            .into_inner();
+        Ok((response.sessions, response.next_page_token))
+    }
+
+    /// Find sessions whose name or transcript contains `query`
+    pub async fn search_sessions(&mut self, query: &str) -> Result<Vec<SessionInfo>> {
+        let response = self
+            .inner
+            .list_sessions(ListSessionsRequest {
+                repo_id: None,
+                branch: None,
+                query: Some(query.to_string()),
+                ..Default::default()
+            })
+            .await?;
+        Ok(response.into_inner().sessions)
+    }
+
+    /// Stream matches of `query` in the output history of a repo's sessions
+    ///
+    /// Dropping the stream cancels the search on the daemon.
+    pub async fn search_session_history(
+        &mut self,
+        repo_id: Option<&str>,
+        query: &str,
+        regex: bool,
+    ) -> Result<tonic::Streaming<HistoryMatch>> {
+        let response = self
+            .inner
+            .search_session_history(SearchSessionHistoryRequest {
+                repo_id: repo_id.map(String::from),
+                query: query.to_string(),
+                regex,
+                limit: 0,
+            })
+            .await?;
+        Ok(response.into_inner())
+    }
+
+    /// Export a session's transcript
+    pub async fn export_session_transcript(
+        &mut self,
+        session_id: &str,
+        format: TranscriptFormat,
+    ) -> Result<SessionTranscript> {
+        let response = self
+            .inner
+            .export_session_transcript(ExportSessionTranscriptRequest {
+                session_id: session_id.to_string(),
+                format: format as i32,
+            })
+            .await?;
+        Ok(response.into_inner())
+    }
+
+    /// Read the file a session's provider keeps its conversation in
+    pub async fn get_provider_session_file(
+        &mut self,
+        session_id: &str,
+    ) -> Result<ProviderSessionFile> {
+        let response = self
+            .inner
+            .get_provider_session_file(GetProviderSessionFileRequest {
+                session_id: session_id.to_string(),
+            })
+            .await?;
+        Ok(response.into_inner())
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn create_session(
         &mut self,
@@ -209,7 +436,10 @@ impl Client {
         is_shell: Option<bool>,
         model: Option<&str>,
         prompt: Option<&str>,
+        system_prompt: Option<&str>,
         provider: Option<&str>,
+        command: &[String],
+        cwd: Option<&str>,
         rows: Option<u32>,
         cols: Option<u32>,
     ) -> Result<SessionInfo> {
@@ -225,6 +455,9 @@ impl Client {
                 provider: provider.map(String::from),
                 rows,
                 cols,
+                system_prompt: system_prompt.map(String::from),
+                command: command.to_vec(),
+                cwd: cwd.map(String::from),
             })
             .await?;
         Ok(response.into_inner())
@@ -245,15 +478,56 @@ impl Client {
         Ok(response.into_inner())
     }
 
+    /// Pin/unpin a session and/or move it to `sort_key` within its worktree
+    pub async fn update_session_meta(
+        &mut self,
+        session_id: &str,
+        pinned: Option<bool>,
+        sort_key: Option<u32>,
+    ) -> Result<SessionInfo> {
+        let response = self
+            .inner
+            .update_session_meta(UpdateSessionMetaRequest {
+                session_id: session_id.to_string(),
+                pinned,
+                sort_key,
+            })
+            .await?;
+        Ok(response.into_inner())
+    }
+
     pub async fn destroy_session(&mut self, session_id: &str) -> Result<()> {
         self.inner
             .destroy_session(DestroySessionRequest {
                 session_id: session_id.to_string(),
+                trash: false,
             })
             .await?;
         Ok(())
     }
 
+    /// Destroy a session, keeping it restorable for a while
+    pub async fn trash_session(&mut self, session_id: &str) -> Result<()> {
+        self.inner
+            .destroy_session(DestroySessionRequest {
+                session_id: session_id.to_string(),
+                trash: true,
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Restore a trashed session
+    pub async fn restore_session(&mut self, session_id: &str) -> Result<SessionInfo> {
+        let response = self
+            .inner
+            .restore_session(RestoreSessionRequest {
+                session_id: session_id.to_string(),
+            })
+            .await?;
+        Ok(response.into_inner())
+    }
+
     pub async fn stop_session(&mut self, session_id: &str) -> Result<()> {
         self.inner
             .stop_session(StopSessionRequest {
@@ -269,17 +543,35 @@ impl Client {
         &mut self.inner
     }
 
+    /// Write `data` to a session's PTY without staying attached
+    pub async fn send_input(&mut self, session_id: &str, data: Vec<u8>) -> Result<()> {
+        let input = AttachInput {
+            session_id: session_id.to_string(),
+            data,
+            rows: None,
+            cols: None,
+        };
+        self.inner
+            .attach_session(tokio_stream::iter([input]))
+            .await?;
+        Ok(())
+    }
+
     // ============ Events ============
 
     /// Subscribe to events from the daemon
+    ///
+    /// Session activity events are only sent with `include_activity`.
     pub async fn subscribe_events(
         &mut self,
         repo_id: Option<&str>,
+        include_activity: bool,
     ) -> Result<tonic::Streaming<Event>> {
         let response = self
             .inner
             .subscribe_events(SubscribeEventsRequest {
                 repo_id: repo_id.map(String::from),
+                include_activity,
             })
             .await?;
         Ok(response.into_inner())
@@ -292,23 +584,52 @@ impl Client {
         &mut self,
         repo_id: &str,
         branch: &str,
+        revision: Option<&str>,
+        base_ref: Option<&str>,
     ) -> Result<Vec<DiffFileInfo>> {
         let response = self
             .inner
             .get_diff_files(GetDiffFilesRequest {
                 repo_id: repo_id.to_string(),
                 branch: branch.to_string(),
+                revision: revision.map(String::from),
+                base_ref: base_ref.map(String::from),
             })
             .await?;
         Ok(response.into_inner().files)
     }
 
+    /// Get the files a branch changed since its merge base with `base_ref`
+    /// (`None` = upstream, else main/master)
+    pub async fn get_branch_diff(
+        &mut self,
+        repo_id: &str,
+        branch: &str,
+        base_ref: Option<&str>,
+    ) -> Result<GetBranchDiffResponse> {
+        let response = self
+            .inner
+            .get_branch_diff(GetBranchDiffRequest {
+                repo_id: repo_id.to_string(),
+                branch: branch.to_string(),
+                base_ref: base_ref.map(String::from),
+            })
+            .await?;
+        Ok(response.into_inner())
+    }
+
     /// Get diff content for a specific file
+    ///
+    /// With `branch_diff`, the file's changes since the merge base with
+    /// `base_ref` are returned instead of its worktree changes.
     pub async fn get_file_diff(
         &mut self,
         repo_id: &str,
         branch: &str,
         file_path: &str,
+        revision: Option<&str>,
+        base_ref: Option<&str>,
+        branch_diff: bool,
     ) -> Result<GetFileDiffResponse> {
         let response = self
             .inner
@@ -316,6 +637,63 @@ impl Client {
                 repo_id: repo_id.to_string(),
                 branch: branch.to_string(),
                 file_path: file_path.to_string(),
+                revision: revision.map(String::from),
+                base_ref: base_ref.map(String::from),
+                branch_diff,
+            })
+            .await?;
+        Ok(response.into_inner())
+    }
+
+    /// Get `count` lines of a file from `start` (1-based), as in the worktree
+    /// or in `revision`
+    pub async fn get_file_lines(
+        &mut self,
+        repo_id: &str,
+        branch: &str,
+        file_path: &str,
+        revision: Option<&str>,
+        start: i32,
+        count: i32,
+    ) -> Result<Vec<String>> {
+        let response = self
+            .inner
+            .get_file_lines(GetFileLinesRequest {
+                repo_id: repo_id.to_string(),
+                branch: branch.to_string(),
+                file_path: file_path.to_string(),
+                revision: revision.map(String::from),
+                start,
+                count,
+            })
+            .await?;
+        Ok(response.into_inner().lines)
+    }
+
+    /// Blame a file as committed at the worktree's HEAD
+    pub async fn get_blame(
+        &mut self,
+        repo_id: &str,
+        branch: &str,
+        file_path: &str,
+    ) -> Result<GetBlameResponse> {
+        let response = self
+            .inner
+            .get_blame(GetBlameRequest {
+                repo_id: repo_id.to_string(),
+                branch: branch.to_string(),
+                file_path: file_path.to_string(),
+            })
+            .await?;
+        Ok(response.into_inner())
+    }
+
+    /// List branches and tags of a repo (for picking a diff base)
+    pub async fn list_refs(&mut self, repo_id: &str) -> Result<ListRefsResponse> {
+        let response = self
+            .inner
+            .list_refs(ListRefsRequest {
+                repo_id: repo_id.to_string(),
             })
             .await?;
         Ok(response.into_inner())
@@ -375,6 +753,20 @@ impl Client {
         Ok(())
     }
 
+    /// Restore a deleted line comment
+    pub async fn restore_line_comment(
+        &mut self,
+        comment: LineCommentInfo,
+    ) -> Result<LineCommentInfo> {
+        let response = self
+            .inner
+            .restore_line_comment(RestoreLineCommentRequest {
+                comment: Some(comment),
+            })
+            .await?;
+        Ok(response.into_inner())
+    }
+
     /// List line comments
     pub async fn list_line_comments(
         &mut self,
@@ -393,19 +785,72 @@ impl Client {
         Ok(response.into_inner().comments)
     }
 
+    /// Save the worktree's comments as a review
+    pub async fn save_review(
+        &mut self,
+        repo_id: &str,
+        branch: &str,
+        base_ref: &str,
+        title: Option<String>,
+    ) -> Result<ReviewInfo> {
+        let response = self
+            .inner
+            .save_review(SaveReviewRequest {
+                repo_id: repo_id.to_string(),
+                branch: branch.to_string(),
+                base_ref: base_ref.to_string(),
+                title,
+            })
+            .await?;
+        Ok(response.into_inner())
+    }
+
+    /// List past reviews of a worktree, newest first
+    pub async fn list_reviews(&mut self, repo_id: &str, branch: &str) -> Result<Vec<ReviewInfo>> {
+        let response = self
+            .inner
+            .list_reviews(ListReviewsRequest {
+                repo_id: repo_id.to_string(),
+                branch: branch.to_string(),
+            })
+            .await?;
+        Ok(response.into_inner().reviews)
+    }
+
+    /// Put a past review's comments back, returning the worktree's comments
+    pub async fn open_review(
+        &mut self,
+        repo_id: &str,
+        branch: &str,
+        review_id: &str,
+    ) -> Result<Vec<LineCommentInfo>> {
+        let response = self
+            .inner
+            .open_review(OpenReviewRequest {
+                repo_id: repo_id.to_string(),
+                branch: branch.to_string(),
+                review_id: review_id.to_string(),
+            })
+            .await?;
+        Ok(response.into_inner().comments)
+    }
+
     // ============ Git Status ============
 
-    /// Get git status for a worktree (staged/unstaged/untracked files)
+    /// Get git status for a worktree (staged/unstaged/untracked files, submodules
+    /// and optionally ignored files)
     pub async fn get_git_status(
         &mut self,
         repo_id: &str,
         branch: &str,
+        include_ignored: bool,
     ) -> Result<GetGitStatusResponse> {
         let response = self
             .inner
             .get_git_status(GetGitStatusRequest {
                 repo_id: repo_id.to_string(),
                 branch: branch.to_string(),
+                include_ignored,
             })
             .await?;
         Ok(response.into_inner())
@@ -474,13 +919,183 @@ impl Client {
         Ok(response.into_inner())
     }
 
-    /// Pull from remote (fetch + rebase)
-    pub async fn git_pull(&mut self, repo_id: &str, branch: &str) -> Result<GitPullResponse> {
+    /// Fetch all remotes of a repository
+    pub async fn fetch(&mut self, repo_id: &str) -> Result<FetchResponse> {
+        let response = self
+            .inner
+            .fetch(FetchRequest {
+                repo_id: repo_id.to_string(),
+            })
+            .await?;
+        Ok(response.into_inner())
+    }
+
+    /// Fetch and integrate upstream changes into a worktree
+    pub async fn pull_worktree(
+        &mut self,
+        repo_id: &str,
+        branch: &str,
+        strategy: PullStrategy,
+        stash_first: bool,
+    ) -> Result<PullWorktreeResponse> {
+        let response = self
+            .inner
+            .pull_worktree(PullWorktreeRequest {
+                repo_id: repo_id.to_string(),
+                branch: branch.to_string(),
+                strategy: strategy as i32,
+                stash_first,
+            })
+            .await?;
+        Ok(response.into_inner())
+    }
+
+    /// Stash local changes of a worktree
+    pub async fn stash_save(
+        &mut self,
+        repo_id: &str,
+        branch: &str,
+        message: &str,
+        include_untracked: bool,
+    ) -> Result<StashEntry> {
+        let response = self
+            .inner
+            .stash_save(StashSaveRequest {
+                repo_id: repo_id.to_string(),
+                branch: branch.to_string(),
+                message: message.to_string(),
+                include_untracked,
+            })
+            .await?;
+        Ok(response.into_inner())
+    }
+
+    /// List stashes made on a worktree's branch
+    pub async fn stash_list(&mut self, repo_id: &str, branch: &str) -> Result<Vec<StashEntry>> {
+        let response = self
+            .inner
+            .stash_list(StashListRequest {
+                repo_id: repo_id.to_string(),
+                branch: branch.to_string(),
+            })
+            .await?;
+        Ok(response.into_inner().stashes)
+    }
+
+    /// Apply a stash and drop it
+    pub async fn stash_pop(
+        &mut self,
+        repo_id: &str,
+        branch: &str,
+        index: u32,
+    ) -> Result<StashApplyResponse> {
+        let response = self
+            .inner
+            .stash_pop(stash_ref(repo_id, branch, index))
+            .await?;
+        Ok(response.into_inner())
+    }
+
+    /// Stage all, commit and push a worktree in one daemon-side pipeline
+    pub async fn commit_and_push(
+        &mut self,
+        repo_id: &str,
+        branch: &str,
+        message: &str,
+        push_remote: Option<&str>,
+    ) -> Result<CommitAndPushResponse> {
         let response = self
             .inner
-            .git_pull(GitPullRequest {
+            .commit_and_push(CommitAndPushRequest {
                 repo_id: repo_id.to_string(),
                 branch: branch.to_string(),
+                message: message.to_string(),
+                push_remote: push_remote.unwrap_or_default().to_string(),
+            })
+            .await?;
+        Ok(response.into_inner())
+    }
+
+    // ============ Conflicts ============
+
+    /// List files of a worktree with unresolved merge conflicts
+    pub async fn list_conflicts(
+        &mut self,
+        repo_id: &str,
+        branch: &str,
+    ) -> Result<Vec<ConflictFile>> {
+        let response = self
+            .inner
+            .list_conflicts(ListConflictsRequest {
+                repo_id: repo_id.to_string(),
+                branch: branch.to_string(),
+            })
+            .await?;
+        Ok(response.into_inner().files)
+    }
+
+    /// Get the unchanged regions and conflict hunks of a file
+    pub async fn get_file_conflicts(
+        &mut self,
+        repo_id: &str,
+        branch: &str,
+        path: &str,
+    ) -> Result<Vec<ConflictRegion>> {
+        let response = self
+            .inner
+            .get_file_conflicts(GetFileConflictsRequest {
+                repo_id: repo_id.to_string(),
+                branch: branch.to_string(),
+                path: path.to_string(),
+            })
+            .await?;
+        Ok(response.into_inner().regions)
+    }
+
+    /// Resolve every hunk of a file and stage it; returns the files still conflicted
+    pub async fn resolve_conflicts(
+        &mut self,
+        repo_id: &str,
+        branch: &str,
+        path: &str,
+        resolutions: &[ConflictResolution],
+    ) -> Result<Vec<ConflictFile>> {
+        let response = self
+            .inner
+            .resolve_conflicts(ResolveConflictsRequest {
+                repo_id: repo_id.to_string(),
+                branch: branch.to_string(),
+                path: path.to_string(),
+                resolutions: resolutions.iter().map(|r| *r as i32).collect(),
+            })
+            .await?;
+        Ok(response.into_inner().remaining)
+    }
+
+    // ============ Checkpoints ============
+
+    /// List a repo's session checkpoints, newest first
+    pub async fn list_checkpoints(
+        &mut self,
+        repo_id: &str,
+        branch: Option<&str>,
+    ) -> Result<Vec<CheckpointInfo>> {
+        let response = self
+            .inner
+            .list_checkpoints(ListCheckpointsRequest {
+                repo_id: repo_id.to_string(),
+                branch: branch.map(|b| b.to_string()),
+            })
+            .await?;
+        Ok(response.into_inner().checkpoints)
+    }
+
+    /// Put a session's worktree back to how it was when the session started
+    pub async fn restore_checkpoint(&mut self, session_id: &str) -> Result<CheckpointInfo> {
+        let response = self
+            .inner
+            .restore_checkpoint(RestoreCheckpointRequest {
+                session_id: session_id.to_string(),
             })
             .await?;
         Ok(response.into_inner())
@@ -531,13 +1146,30 @@ impl Client {
     }
 
     /// Delete a TODO item
-    pub async fn delete_todo(&mut self, todo_id: &str) -> Result<()> {
-        self.inner
+    pub async fn delete_todo(&mut self, todo_id: &str) -> Result<Vec<TodoItem>> {
+        let response = self
+            .inner
             .delete_todo(DeleteTodoRequest {
                 todo_id: todo_id.to_string(),
             })
             .await?;
-        Ok(())
+        Ok(response.into_inner().deleted)
+    }
+
+    /// Restore deleted TODO items
+    pub async fn restore_todos(
+        &mut self,
+        repo_id: &str,
+        items: Vec<TodoItem>,
+    ) -> Result<Vec<TodoItem>> {
+        let response = self
+            .inner
+            .restore_todos(RestoreTodosRequest {
+                repo_id: repo_id.to_string(),
+                items,
+            })
+            .await?;
+        Ok(response.into_inner().items)
     }
 
     /// List TODO items for a repository
@@ -585,3 +1217,11 @@ impl Client {
         Ok(response.into_inner())
     }
 }
+
+fn stash_ref(repo_id: &str, branch: &str, index: u32) -> StashRefRequest {
+    StashRefRequest {
+        repo_id: repo_id.to_string(),
+        branch: branch.to_string(),
+        index,
+    }
+}
@@ -2,6 +2,9 @@
 
 use thiserror::Error;
 
+/// How to bring the daemon back, shown with errors that mean it is gone
+pub const START_DAEMON_HINT: &str = "start it with `amux-daemon`";
+
 /// Errors that can occur when connecting to the daemon
 #[derive(Debug, Error)]
 pub enum ClientError {
@@ -14,13 +17,49 @@ pub enum ClientError {
     #[error("failed to connect to daemon: {0}")]
     ConnectionFailed(#[source] tonic::transport::Error),
 
+    /// The connection to a running daemon was lost (`Code::Unavailable`)
+    #[error("daemon not running, {START_DAEMON_HINT} ({})", .0.message())]
+    DaemonUnavailable(tonic::Status),
+
+    /// The daemon handled the request and rejected it
     #[error("rpc error: {0}")]
-    Rpc(#[from] tonic::Status),
+    Rpc(tonic::Status),
 
     #[error("cannot find home directory")]
     NoHomeDir,
 }
 
+impl From<tonic::Status> for ClientError {
+    fn from(status: tonic::Status) -> Self {
+        if status.code() == tonic::Code::Unavailable {
+            ClientError::DaemonUnavailable(status)
+        } else {
+            ClientError::Rpc(status)
+        }
+    }
+}
+
+impl ClientError {
+    /// Whether the daemon can't be reached, as opposed to a rejected request
+    pub fn is_daemon_unavailable(&self) -> bool {
+        matches!(
+            self,
+            ClientError::DaemonStartFailed(_)
+                | ClientError::DaemonTimeout
+                | ClientError::ConnectionFailed(_)
+                | ClientError::DaemonUnavailable(_)
+        )
+    }
+
+    /// Status code of a rejected request
+    pub fn code(&self) -> Option<tonic::Code> {
+        match self {
+            ClientError::Rpc(status) => Some(status.code()),
+            _ => None,
+        }
+    }
+}
+
 /// Errors that can occur in attach mode
 #[derive(Debug, Error)]
 pub enum AttachError {
@@ -58,8 +97,38 @@ pub enum TuiError {
     #[error("channel send error")]
     ChannelSend,
 
-    #[error("config error: {0}")]
-    Config(String),
+    #[error("failed to load config: {0}")]
+    Config(#[source] amux_config::ConfigError),
+
+    #[error("failed to build keybind map: {0}")]
+    Keybinds(#[source] amux_config::ConfigError),
+}
+
+impl TuiError {
+    /// Whether the daemon can't be reached, as opposed to a rejected request
+    pub fn is_daemon_unavailable(&self) -> bool {
+        match self {
+            TuiError::Client(e) => e.is_daemon_unavailable(),
+            TuiError::Rpc(status) => status.code() == tonic::Code::Unavailable,
+            _ => false,
+        }
+    }
+
+    /// Text for the notification area
+    pub fn user_message(&self) -> String {
+        match self {
+            TuiError::Rpc(status) if status.code() == tonic::Code::Unavailable => {
+                format!("Daemon not running, {}", START_DAEMON_HINT)
+            }
+            TuiError::Client(e) if e.is_daemon_unavailable() => {
+                format!("Daemon not running, {}", START_DAEMON_HINT)
+            }
+            TuiError::Client(ClientError::Rpc(status)) | TuiError::Rpc(status) => {
+                status.message().to_string()
+            }
+            e => e.to_string(),
+        }
+    }
 }
 
 /// Top-level CLI error type
@@ -71,9 +140,34 @@ pub enum CliError {
     #[error(transparent)]
     Tui(#[from] TuiError),
 
+    #[error(transparent)]
+    Attach(#[from] AttachError),
+
+    #[error("config error: {0}")]
+    Config(#[from] amux_config::ConfigError),
+
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("not a repository added to amux: {}", .0.display())]
+    UnknownRepo(std::path::PathBuf),
 }
 
-/// Result type alias for CLI operations
-pub type Result<T> = std::result::Result<T, CliError>;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unavailable_status_is_daemon_unavailable() {
+        let e = ClientError::from(tonic::Status::unavailable("transport error"));
+        assert!(e.is_daemon_unavailable());
+        assert_eq!(e.code(), None);
+        assert!(e.to_string().contains(START_DAEMON_HINT));
+        assert!(TuiError::from(e).user_message().contains(START_DAEMON_HINT));
+
+        let e = ClientError::from(tonic::Status::not_found("no such session"));
+        assert!(!e.is_daemon_unavailable());
+        assert_eq!(e.code(), Some(tonic::Code::NotFound));
+        assert_eq!(TuiError::from(e).user_message(), "no such session");
+    }
+}
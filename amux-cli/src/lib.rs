@@ -0,0 +1,7 @@
+//! Amux CLI - client for the Amux daemon
+
+// Allow large error types - tonic::Status is large but boxing it would add complexity
+#![allow(clippy::result_large_err)]
+
+pub mod client;
+pub mod error;
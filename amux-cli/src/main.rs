@@ -4,10 +4,12 @@
 #![allow(clippy::result_large_err)]
 
 mod attach;
-mod client;
-pub mod error;
+mod status;
 mod tui;
 
+use amux_cli::{client, error};
+use amux_proto::daemon::TranscriptFormat;
+use clap::{Parser, Subcommand};
 use client::Client;
 use error::CliError;
 use futures::stream::StreamExt;
@@ -50,6 +52,147 @@ fn find_main_repo_path(path: &Path) -> Option<PathBuf> {
     }
 }
 
+/// Amux - manage AI coding sessions across git worktrees
+#[derive(Parser)]
+#[command(name = "amux", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Print the effective configuration as JSON and exit
+    #[cfg(feature = "json")]
+    #[arg(long)]
+    print_config_json: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Attach this terminal directly to a session (detach with Ctrl+])
+    Attach {
+        /// ID of the session to attach to
+        session_id: String,
+    },
+    /// Session operations
+    Session {
+        #[command(subcommand)]
+        command: SessionCommand,
+    },
+    /// Repository operations
+    Repo {
+        #[command(subcommand)]
+        command: RepoCommand,
+    },
+    /// Daemon operations
+    Daemon {
+        #[command(subcommand)]
+        command: DaemonCommand,
+    },
+    /// Configuration file tooling
+    #[cfg(feature = "json")]
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+#[cfg(feature = "json")]
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Print the JSON Schema of config.toml, for editor completion
+    Schema,
+}
+
+#[derive(Subcommand)]
+enum SessionCommand {
+    /// Export a session's transcript (Markdown unless --plain)
+    Export {
+        /// ID of the session to export
+        session_id: String,
+        /// File to write instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Plain text with escape sequences stripped
+        #[arg(long)]
+        plain: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum DaemonCommand {
+    /// Summary of the daemon, its repos and sessions, with a consistency check
+    Status {
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum RepoCommand {
+    /// Environment variables set for the repo's sessions
+    Env {
+        #[command(subcommand)]
+        command: EnvCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum EnvCommand {
+    /// Set a variable; `${VAR}` in the value expands from the daemon's environment
+    Set {
+        name: String,
+        value: String,
+        /// Only for sessions of this worktree
+        #[arg(short, long)]
+        branch: Option<String>,
+        /// Repository path (default: the current directory)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+    },
+    /// Remove a variable
+    Unset {
+        name: String,
+        /// Remove it from this worktree's variables
+        #[arg(short, long)]
+        branch: Option<String>,
+        /// Repository path (default: the current directory)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+    },
+    /// List the variables of the repo and its worktrees
+    List {
+        /// Repository path (default: the current directory)
+        #[arg(long)]
+        repo: Option<PathBuf>,
+    },
+}
+
+/// ID of the added repo at `path` (or containing the current directory)
+async fn repo_id_at(client: &mut Client, path: Option<PathBuf>) -> Result<String, CliError> {
+    let path = match path {
+        Some(path) => path,
+        None => std::env::current_dir()?,
+    };
+    let main_path = find_main_repo_path(&path)
+        .and_then(|p| p.canonicalize().ok())
+        .ok_or_else(|| CliError::UnknownRepo(path.clone()))?;
+    let main_path = main_path.to_string_lossy();
+    client
+        .list_repos()
+        .await?
+        .into_iter()
+        .find(|r| r.path == main_path)
+        .map(|r| r.id)
+        .ok_or(CliError::UnknownRepo(path))
+}
+
+/// Whether to spawn the daemon when it isn't running (`auto_start_daemon`)
+///
+/// An unreadable config counts as off; the TUI reports it once connected.
+fn auto_start_daemon() -> bool {
+    amux_config::Config::load_or_default().is_ok_and(|c| c.options.auto_start_daemon)
+}
+
 fn init_logging() {
     // TUI takes over stdout/stderr, so log to file if Amux_LOG is set
     // Usage: Amux_LOG=debug ccm
@@ -80,19 +223,120 @@ fn init_logging() {
 
 #[tokio::main]
 async fn main() -> Result<(), CliError> {
+    let cli = Cli::parse();
     init_logging();
     debug!("Amux CLI starting");
 
+    #[cfg(feature = "json")]
+    if cli.print_config_json {
+        let json = amux_config::Config::load_or_default()?.to_json_string()?;
+        println!("{}", json);
+        return Ok(());
+    }
+
+    match cli.command {
+        Some(Command::Attach { session_id }) => {
+            let mut client = Client::connect(auto_start_daemon()).await?;
+            attach::attach(&mut client, &session_id).await?;
+            return Ok(());
+        }
+        Some(Command::Session {
+            command:
+                SessionCommand::Export {
+                    session_id,
+                    output,
+                    plain,
+                },
+        }) => {
+            let mut client = Client::connect(auto_start_daemon()).await?;
+            let format = if plain {
+                TranscriptFormat::Plain
+            } else {
+                TranscriptFormat::Markdown
+            };
+            let transcript = client
+                .export_session_transcript(&session_id, format)
+                .await?;
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, transcript.content)?;
+                    eprintln!("Exported transcript to {}", path.display());
+                }
+                None => print!("{}", transcript.content),
+            }
+            return Ok(());
+        }
+        Some(Command::Repo {
+            command: RepoCommand::Env { command },
+        }) => {
+            let mut client = Client::connect(auto_start_daemon()).await?;
+            match command {
+                EnvCommand::Set {
+                    name,
+                    value,
+                    branch,
+                    repo,
+                } => {
+                    let repo_id = repo_id_at(&mut client, repo).await?;
+                    client
+                        .set_repo_env(&repo_id, branch.as_deref(), &name, Some(&value))
+                        .await?;
+                }
+                EnvCommand::Unset { name, branch, repo } => {
+                    let repo_id = repo_id_at(&mut client, repo).await?;
+                    client
+                        .set_repo_env(&repo_id, branch.as_deref(), &name, None)
+                        .await?;
+                }
+                EnvCommand::List { repo } => {
+                    let repo_id = repo_id_at(&mut client, repo).await?;
+                    for var in client.list_repo_env(&repo_id).await? {
+                        match var.branch {
+                            Some(branch) => println!("[{}] {}={}", branch, var.name, var.value),
+                            None => println!("{}={}", var.name, var.value),
+                        }
+                    }
+                }
+            }
+            return Ok(());
+        }
+        Some(Command::Daemon {
+            command: DaemonCommand::Status { json },
+        }) => {
+            let mut client = Client::connect(auto_start_daemon()).await?;
+            let daemon_status = client.get_status().await?;
+            if json {
+                println!("{:#}", status::status_json(&daemon_status));
+            } else {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map_or(0, |d| d.as_secs());
+                print!("{}", status::format_status(&daemon_status, now));
+            }
+            return Ok(());
+        }
+        #[cfg(feature = "json")]
+        Some(Command::Config {
+            command: ConfigCommand::Schema,
+        }) => {
+            println!("{:#}", amux_config::Config::json_schema());
+            return Ok(());
+        }
+        None => {}
+    }
+
     // Setup signal handlers for graceful shutdown
     let should_exit = Arc::new(AtomicBool::new(false));
     let should_exit_signal = should_exit.clone();
+    let should_suspend = Arc::new(AtomicBool::new(false));
+    let should_suspend_signal = should_suspend.clone();
 
     let mut signals = Signals::new([SIGINT, SIGTERM, SIGTSTP])?;
     let signals_handle = signals.handle();
 
     // Spawn signal handler task
     // SIGINT/SIGTERM: trigger graceful shutdown
-    // SIGTSTP: ignore (prevent suspension)
+    // SIGTSTP: suspend once the TUI has given the terminal back
     tokio::spawn(async move {
         while let Some(signal) = signals.next().await {
             match signal {
@@ -102,8 +346,8 @@ async fn main() -> Result<(), CliError> {
                     break;
                 }
                 SIGTSTP => {
-                    // Ignore Ctrl+Z - don't suspend the TUI
-                    debug!("Received SIGTSTP, ignoring");
+                    debug!("Received SIGTSTP, suspending");
+                    should_suspend_signal.store(true, Ordering::Relaxed);
                 }
                 _ => {}
             }
@@ -111,11 +355,20 @@ async fn main() -> Result<(), CliError> {
     });
 
     // Connect to daemon
-    let client = match Client::connect().await {
+    let client = match Client::connect(auto_start_daemon()).await {
         Ok(c) => c,
+        Err(e) if e.is_daemon_unavailable() => {
+            eprintln!("Error: {}", e);
+            eprintln!("amux-daemon is not running, {}.", error::START_DAEMON_HINT);
+            if !auto_start_daemon() {
+                eprintln!(
+                    "Set `auto_start_daemon = true` under [options] to start it automatically."
+                );
+            }
+            std::process::exit(1);
+        }
         Err(e) => {
             eprintln!("Error: {}", e);
-            eprintln!("Make sure amux-daemon is running.");
             std::process::exit(1);
         }
     };
@@ -157,7 +410,7 @@ async fn main() -> Result<(), CliError> {
     }
 
     // Run TUI with signal exit flag
-    tui::run_with_client(app, should_exit).await?;
+    tui::run_with_client(app, should_exit, should_suspend).await?;
 
     // Cleanup signal handler
     signals_handle.close();
@@ -0,0 +1,125 @@
+//! `amux daemon status` output
+
+use crate::tui::views::sidebar::render::format_memory;
+use crate::tui::views::tab_bar::clock::format_duration;
+use amux_proto::daemon::DaemonStatus;
+use serde_json::{json, Value};
+
+/// Status as a table of labelled lines, `now` being the current unix time
+pub fn format_status(status: &DaemonStatus, now: u64) -> String {
+    let rows = [
+        ("Version", status.version.clone()),
+        (
+            "Uptime",
+            format_duration(now.saturating_sub(status.started_at)),
+        ),
+        ("Socket", status.socket_path.clone()),
+        ("Repos", status.repo_count.to_string()),
+        ("Worktrees", status.worktree_count.to_string()),
+        (
+            "Sessions",
+            format!(
+                "{} running, {} stopped, {} trashed",
+                status.running_sessions, status.stopped_sessions, status.trashed_sessions
+            ),
+        ),
+        ("History", format_memory(status.history_bytes / 1024)),
+        ("Subscribers", status.event_subscribers.to_string()),
+    ];
+    let mut out: String = rows
+        .iter()
+        .map(|(label, value)| format!("{:<12} {}\n", label, value))
+        .collect();
+
+    if status.exited_sessions.is_empty() {
+        out.push_str(&format!("{:<12} ok\n", "Consistency"));
+    } else {
+        out.push_str(&format!(
+            "{:<12} {} session(s) exited but are still shown running:\n",
+            "Consistency",
+            status.exited_sessions.len()
+        ));
+        for session in &status.exited_sessions {
+            let id: String = session.id.chars().take(8).collect();
+            out.push_str(&format!(
+                "  {}  {} ({})\n",
+                id, session.name, session.branch
+            ));
+        }
+    }
+    out
+}
+
+/// Status as JSON, for scripts
+pub fn status_json(status: &DaemonStatus) -> Value {
+    let exited: Vec<Value> = status
+        .exited_sessions
+        .iter()
+        .map(|s| json!({ "id": s.id, "name": s.name, "branch": s.branch }))
+        .collect();
+    json!({
+        "version": status.version,
+        "started_at": status.started_at,
+        "socket_path": status.socket_path,
+        "repos": status.repo_count,
+        "worktrees": status.worktree_count,
+        "sessions": {
+            "running": status.running_sessions,
+            "stopped": status.stopped_sessions,
+            "trashed": status.trashed_sessions,
+        },
+        "history_bytes": status.history_bytes,
+        "event_subscribers": status.event_subscribers,
+        "exited_sessions": exited,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amux_proto::daemon::ExitedSession;
+
+    fn status() -> DaemonStatus {
+        DaemonStatus {
+            version: "0.1.0".to_string(),
+            started_at: 1000,
+            socket_path: "/tmp/amux.sock".to_string(),
+            repo_count: 2,
+            worktree_count: 5,
+            running_sessions: 1,
+            stopped_sessions: 3,
+            trashed_sessions: 0,
+            history_bytes: 3 * 1024 * 1024,
+            event_subscribers: 1,
+            exited_sessions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_format_status() {
+        let mut status = status();
+        let out = format_status(&status, 1000 + 3660);
+        assert!(out.contains("Uptime       1h1m\n"));
+        assert!(out.contains("Sessions     1 running, 3 stopped, 0 trashed\n"));
+        assert!(out.contains("History      3M\n"));
+        assert!(out.ends_with("Consistency  ok\n"));
+
+        status.exited_sessions.push(ExitedSession {
+            id: "0123456789".to_string(),
+            name: "fix-tests".to_string(),
+            branch: "main".to_string(),
+        });
+        let out = format_status(&status, 1000);
+        assert!(out.ends_with(
+            "Consistency  1 session(s) exited but are still shown running:\n  01234567  fix-tests (main)\n"
+        ));
+    }
+
+    #[test]
+    fn test_status_json() {
+        let json = status_json(&status());
+        assert_eq!(json["sessions"]["stopped"], 3);
+        assert_eq!(json["history_bytes"], 3 * 1024 * 1024);
+        assert_eq!(json["exited_sessions"], json!([]));
+    }
+}
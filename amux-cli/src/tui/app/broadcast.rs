@@ -0,0 +1,112 @@
+//! Typing one line into all sessions of a worktree at once
+
+use super::super::state::InputMode;
+use super::App;
+use crate::error::TuiError;
+use amux_proto::daemon::{SessionInfo, SessionStatus};
+use std::collections::HashSet;
+
+type Result<T> = std::result::Result<T, TuiError>;
+
+impl App {
+    /// Sessions of the current worktree, each listed once
+    fn worktree_sessions(&self) -> Vec<&SessionInfo> {
+        let (Some(repo), Some(worktree)) = (self.current_repo(), self.current_worktree()) else {
+            return Vec::new();
+        };
+        let mut seen = HashSet::new();
+        repo.sessions
+            .iter()
+            .chain(repo.sessions_by_worktree.values().flatten())
+            .filter(|s| s.branch == worktree.branch && seen.insert(s.id.as_str()))
+            .collect()
+    }
+
+    /// Open the prompt for a line to type into the worktree's sessions
+    pub fn start_broadcast_input(&mut self) {
+        if !self.config.options.allow_broadcast {
+            self.error_message =
+                Some("Broadcasting is off, set allow_broadcast = true to enable it".to_string());
+            return;
+        }
+        if self.current_worktree().is_none() {
+            self.error_message = Some("No worktree selected".to_string());
+            return;
+        }
+
+        self.save_focus();
+        self.input_mode = InputMode::BroadcastInput;
+        self.text_input.clear();
+    }
+
+    /// Ask before sending the entered line to the running sessions
+    pub fn submit_broadcast_input(&mut self) {
+        let text = self.text_input.content();
+        let count = self
+            .worktree_sessions()
+            .iter()
+            .filter(|s| s.status == SessionStatus::Running as i32)
+            .count();
+        self.text_input.clear();
+        if count == 0 {
+            self.cancel_input();
+            self.error_message = Some("No running sessions in this worktree".to_string());
+            return;
+        }
+        self.input_mode = InputMode::ConfirmBroadcast { text, count };
+    }
+
+    /// Send the confirmed line, followed by Enter, to the running sessions
+    pub async fn confirm_broadcast(&mut self) -> Result<()> {
+        let InputMode::ConfirmBroadcast { text, .. } =
+            std::mem::replace(&mut self.input_mode, InputMode::Normal)
+        else {
+            return Ok(());
+        };
+        self.restore_focus();
+
+        let mut data = text.into_bytes();
+        data.push(b'\r');
+        self.broadcast_to_all_sessions(&data).await
+    }
+
+    /// Write `data` to every running session of the current worktree
+    ///
+    /// Stopped sessions, and those the daemon can't reach, are skipped and
+    /// counted in the resulting message.
+    pub async fn broadcast_to_all_sessions(&mut self, data: &[u8]) -> Result<()> {
+        let sessions: Vec<(String, bool)> = self
+            .worktree_sessions()
+            .iter()
+            .map(|s| (s.id.clone(), s.status == SessionStatus::Running as i32))
+            .collect();
+        let total = sessions.len();
+
+        let mut reached = 0;
+        for (session_id, running) in sessions {
+            if !running {
+                continue;
+            }
+            match self.client.send_input(&session_id, data.to_vec()).await {
+                Ok(()) => reached += 1,
+                Err(e) => tracing::warn!("Failed to send input to {}: {}", session_id, e),
+            }
+        }
+
+        if reached == total {
+            self.status_message = Some(format!(
+                "Sent to {} session{}",
+                total,
+                if total == 1 { "" } else { "s" }
+            ));
+        } else {
+            self.error_message = Some(format!(
+                "{} of {} sessions reached ({} have no stream)",
+                reached,
+                total,
+                total - reached
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+//! Worktree checkpoints taken when sessions start
+
+use super::super::state::{InputMode, SidebarItem};
+use super::super::App;
+use crate::error::{ClientError, TuiError};
+
+type Result<T> = std::result::Result<T, TuiError>;
+
+impl App {
+    /// Turn checkpoints for new sessions on or off in the current repo
+    pub async fn toggle_checkpoints(&mut self) -> Result<()> {
+        let Some((repo_id, enabled)) = self
+            .current_repo()
+            .map(|r| (r.info.id.clone(), !r.info.checkpoints))
+        else {
+            return Ok(());
+        };
+
+        match self.client.set_repo_checkpoints(&repo_id, enabled).await {
+            Ok(()) => {
+                if let Some(repo) = self.current_repo_mut() {
+                    repo.info.checkpoints = enabled;
+                }
+                self.status_message = Some(if enabled {
+                    "Checkpoints on: new sessions snapshot their worktree".to_string()
+                } else {
+                    "Checkpoints off".to_string()
+                });
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to set checkpoints: {}", e));
+            }
+        }
+        Ok(())
+    }
+
+    /// Ask to restore the selected session's worktree to its checkpoint
+    pub async fn prepare_restore_checkpoint(&mut self) -> Result<()> {
+        let SidebarItem::Session(wt_idx, s_idx) = self.current_sidebar_item() else {
+            self.error_message = Some("No session selected".to_string());
+            return Ok(());
+        };
+        let Some(session) = self
+            .current_repo()
+            .and_then(|r| r.sessions_by_worktree.get(&wt_idx))
+            .and_then(|sessions| sessions.get(s_idx))
+            .cloned()
+        else {
+            return Ok(());
+        };
+
+        let checkpoints = match self
+            .client
+            .list_checkpoints(&session.repo_id, Some(&session.branch))
+            .await
+        {
+            Ok(checkpoints) => checkpoints,
+            Err(e) => {
+                self.error_message = Some(format!("Failed to list checkpoints: {}", e));
+                return Ok(());
+            }
+        };
+        let Some(checkpoint) = checkpoints.into_iter().find(|c| c.session_id == session.id) else {
+            self.error_message = Some(format!("Session {} has no checkpoint", session.name));
+            return Ok(());
+        };
+
+        self.save_focus();
+        self.input_mode = InputMode::ConfirmRestoreCheckpoint {
+            session_id: session.id,
+            session_name: session.name,
+            branch: checkpoint.branch,
+            created_at: checkpoint.created_at,
+        };
+        self.text_input.clear();
+        Ok(())
+    }
+
+    /// Restore the confirmed checkpoint, discarding work done since
+    pub async fn restore_checkpoint(&mut self) -> Result<()> {
+        let (session_id, session_name) = match &self.input_mode {
+            InputMode::ConfirmRestoreCheckpoint {
+                session_id,
+                session_name,
+                ..
+            } => (session_id.clone(), session_name.clone()),
+            _ => return Ok(()),
+        };
+        self.input_mode = InputMode::Normal;
+        self.text_input.clear();
+        self.restore_focus();
+
+        match self.client.restore_checkpoint(&session_id).await {
+            Ok(checkpoint) => {
+                self.status_message = Some(format!(
+                    "Restored {} to the start of {}",
+                    checkpoint.branch, session_name
+                ));
+            }
+            Err(ClientError::Rpc(status)) => {
+                self.error_message = Some(format!("Cannot restore: {}", status.message()));
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        self.load_git_status().await?;
+        self.refresh_worktree_status().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,263 @@
+//! Clipboard operations (copy branch names, paths, SHAs)
+
+use super::super::state::{DiffItem, Focus, SidebarItem};
+use super::super::widgets::helpers::truncate_to_width;
+use super::super::App;
+use amux_config::CopyTarget;
+use amux_proto::daemon::SessionInfo;
+use arboard::Clipboard;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use std::fmt;
+use std::io::Write;
+use std::sync::Mutex;
+
+/// Maximum display width of the copied value shown in the status bar
+const COPY_PREVIEW_LEN: usize = 40;
+
+impl App {
+    /// Copy a value from the current selection to the system clipboard
+    pub fn copy_to_clipboard(&mut self, target: CopyTarget) {
+        let target = self.resolve_copy_target(target);
+        let Some(value) = self.copy_value(target) else {
+            self.error_message = Some(format!("Nothing to copy ({})", copy_target_label(target)));
+            return;
+        };
+
+        let method = match write_clipboard(&value) {
+            Ok(method) => method,
+            Err(e) => {
+                self.error_message = Some(e.to_string());
+                return;
+            }
+        };
+
+        let preview = truncate_to_width(value.trim_end(), COPY_PREVIEW_LEN);
+        self.status_message = Some(match method {
+            CopyMethod::System => format!("Copied: {}", preview),
+            CopyMethod::Osc52 => format!("Copied via OSC 52: {}", preview),
+        });
+    }
+
+    /// Pick a concrete target for `CopyTarget::Auto` from the focused view
+    fn resolve_copy_target(&self, target: CopyTarget) -> CopyTarget {
+        if target != CopyTarget::Auto {
+            return target;
+        }
+
+        match self.focus {
+            Focus::Sidebar => match self.current_sidebar_item() {
+                SidebarItem::Session(..) => CopyTarget::SessionName,
+                _ => CopyTarget::BranchName,
+            },
+            Focus::DiffFiles => match self.current_diff_item() {
+                DiffItem::Line(..) => CopyTarget::DiffLineContent,
+                _ => CopyTarget::FilePath,
+            },
+            Focus::GitStatus if self.current_git_file_path().is_some() => CopyTarget::FilePath,
+            Focus::GitStatus => CopyTarget::BranchName,
+            Focus::Conflicts => CopyTarget::FilePath,
+            Focus::Terminal if self.terminal.selection.is_some() => CopyTarget::TerminalSelection,
+            Focus::Terminal => CopyTarget::SessionName,
+        }
+    }
+
+    fn copy_value(&self, target: CopyTarget) -> Option<String> {
+        match target {
+            CopyTarget::Auto => None,
+            CopyTarget::BranchName => self.copy_worktree().map(|w| w.branch.clone()),
+            CopyTarget::CommitSha => self
+                .copy_worktree()
+                .map(|w| w.head_sha.clone())
+                .filter(|sha| !sha.is_empty()),
+            CopyTarget::SessionId => self.copy_session().map(|s| s.id.clone()),
+            CopyTarget::SessionName => self.copy_session().map(|s| s.name.clone()),
+            CopyTarget::FilePath => match self.focus {
+                Focus::GitStatus => self.current_git_file_path(),
+                Focus::Conflicts => self.conflicts()?.current_file().map(|f| f.path.clone()),
+                _ => match self.current_diff_item() {
+                    DiffItem::File(idx) | DiffItem::Line(idx, _) => {
+                        self.diff()?.files.get(idx).map(|f| f.path.clone())
+                    }
+                    DiffItem::None => None,
+                },
+            },
+            CopyTarget::DiffLineContent => match self.current_diff_item() {
+                DiffItem::Line(file_idx, line_idx) => self
+                    .diff()?
+                    .file_lines
+                    .get(&file_idx)?
+                    .get(line_idx)
+                    .map(|l| l.content.clone()),
+                _ => None,
+            },
+            CopyTarget::TerminalSelection => self.terminal_selection_text(),
+        }
+    }
+
+    /// Worktree under the sidebar cursor, or the current one elsewhere
+    fn copy_worktree(&self) -> Option<&amux_proto::daemon::WorktreeInfo> {
+        let repo = self.current_repo()?;
+        match self.current_sidebar_item() {
+            SidebarItem::Worktree(idx) | SidebarItem::Session(idx, _)
+                if self.focus == Focus::Sidebar =>
+            {
+                repo.worktrees.get(idx)
+            }
+            _ => repo.current_worktree(),
+        }
+    }
+
+    /// Session under the sidebar cursor, or the one attached to the terminal
+    fn copy_session(&self) -> Option<&SessionInfo> {
+        let repo = self.current_repo()?;
+        if self.focus == Focus::Sidebar {
+            if let SidebarItem::Session(wt_idx, s_idx) = self.current_sidebar_item() {
+                return repo.sessions_by_worktree.get(&wt_idx)?.get(s_idx);
+            }
+        }
+
+        self.active_session()
+    }
+}
+
+fn copy_target_label(target: CopyTarget) -> &'static str {
+    match target {
+        CopyTarget::Auto => "selection",
+        CopyTarget::BranchName => "branch name",
+        CopyTarget::SessionId => "session id",
+        CopyTarget::SessionName => "session name",
+        CopyTarget::FilePath => "file path",
+        CopyTarget::CommitSha => "commit SHA",
+        CopyTarget::DiffLineContent => "diff line",
+        CopyTarget::TerminalSelection => "terminal selection",
+    }
+}
+
+/// How copied text reached a clipboard
+#[derive(Debug, PartialEq, Eq)]
+enum CopyMethod {
+    /// The system clipboard
+    System,
+    /// An OSC 52 sequence to the outer terminal, which may ignore it
+    Osc52,
+}
+
+#[derive(Debug)]
+enum CopyError {
+    /// No system clipboard, e.g. on a headless machine
+    Unavailable,
+    Io(std::io::Error),
+}
+
+impl fmt::Display for CopyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CopyError::Unavailable => write!(f, "Clipboard not available. Use OSC 52 or pipe."),
+            CopyError::Io(e) => write!(f, "Failed to copy: {}", e),
+        }
+    }
+}
+
+/// Write text to the system clipboard
+///
+/// Over SSH the text is sent to the outer terminal as OSC 52 instead, as
+/// the system clipboard there is the remote machine's.
+fn write_clipboard(text: &str) -> Result<CopyMethod, CopyError> {
+    let remote =
+        std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some();
+    write_clipboard_with(
+        text,
+        remote,
+        std::env::var_os("TMUX").is_some(),
+        write_system_clipboard,
+        &mut std::io::stdout(),
+    )
+}
+
+fn write_clipboard_with(
+    text: &str,
+    remote: bool,
+    in_tmux: bool,
+    system: impl FnOnce(&str) -> Result<(), arboard::Error>,
+    terminal: &mut impl Write,
+) -> Result<CopyMethod, CopyError> {
+    if !remote {
+        return match system(text) {
+            Ok(()) => Ok(CopyMethod::System),
+            Err(e) => {
+                tracing::debug!("System clipboard unavailable: {}", e);
+                Err(CopyError::Unavailable)
+            }
+        };
+    }
+
+    terminal
+        .write_all(osc52_sequence(text, in_tmux).as_bytes())
+        .and_then(|()| terminal.flush())
+        .map_err(CopyError::Io)?;
+    Ok(CopyMethod::Osc52)
+}
+
+fn write_system_clipboard(text: &str) -> Result<(), arboard::Error> {
+    // Kept open: on X11 the copied text is served by the process that set it
+    static CLIPBOARD: Mutex<Option<Clipboard>> = Mutex::new(None);
+
+    let mut clipboard = CLIPBOARD.lock().unwrap_or_else(|e| e.into_inner());
+    let clipboard = match clipboard.as_mut() {
+        Some(clipboard) => clipboard,
+        None => clipboard.insert(Clipboard::new()?),
+    };
+    clipboard.set_text(text)
+}
+
+/// OSC 52 setting the terminal's clipboard, wrapped for tmux to pass on
+fn osc52_sequence(text: &str, in_tmux: bool) -> String {
+    let encoded = BASE64.encode(text);
+    if in_tmux {
+        format!("\x1bPtmux;\x1b\x1b]52;c;{}\x07\x1b\\", encoded)
+    } else {
+        format!("\x1b]52;c;{}\x07", encoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_clipboard_paths() {
+        let mut terminal = Vec::new();
+        let method = write_clipboard_with("hi", false, false, |_| Ok(()), &mut terminal);
+        assert_eq!(method.unwrap(), CopyMethod::System);
+        assert!(terminal.is_empty());
+
+        // No system clipboard: nothing is claimed to be copied
+        let err = write_clipboard_with(
+            "hi",
+            false,
+            false,
+            |_| Err(arboard::Error::ClipboardNotSupported),
+            &mut terminal,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Clipboard not available. Use OSC 52 or pipe."
+        );
+        assert!(terminal.is_empty());
+
+        let method = write_clipboard_with("hi", true, false, |_| unreachable!(), &mut terminal);
+        assert_eq!(method.unwrap(), CopyMethod::Osc52);
+        assert_eq!(terminal, b"\x1b]52;c;aGk=\x07");
+    }
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(osc52_sequence("hi", false), "\x1b]52;c;aGk=\x07");
+        assert_eq!(
+            osc52_sequence("hi", true),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+    }
+}
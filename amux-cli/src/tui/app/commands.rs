@@ -0,0 +1,220 @@
+//! Runtime commands typed at the `:` prompt
+
+use super::super::state::{InputMode, ProviderChoice};
+use super::App;
+use crate::error::TuiError;
+use amux_config::{AvailableProvider, ProviderCommand, RuntimeCommand};
+use amux_proto::daemon::ProviderInfo;
+
+type Result<T> = std::result::Result<T, TuiError>;
+
+impl App {
+    /// Open the `:` prompt in the status bar
+    pub fn start_command_line(&mut self) {
+        self.save_focus();
+        self.input_mode = InputMode::CommandLine;
+        self.text_input.clear();
+    }
+
+    /// Run the command typed at the prompt
+    ///
+    /// Commands that don't parse or fail show in the error message.
+    pub async fn submit_command_line(&mut self) -> Result<()> {
+        let input = self.text_input.content();
+        self.cancel_input();
+
+        match RuntimeCommand::parse(&input) {
+            Ok(RuntimeCommand::Set { option, value }) => {
+                self.set_option(&option, &value);
+                Ok(())
+            }
+            Ok(RuntimeCommand::Provider(command)) => self.run_provider_command(command).await,
+            Ok(_) => {
+                self.error_message = Some(format!("Not available in the TUI: {}", input.trim()));
+                Ok(())
+            }
+            Err(e) => {
+                self.error_message = Some(e.to_string());
+                Ok(())
+            }
+        }
+    }
+
+    /// Apply `:set` to the options of this run; the config file is unchanged
+    fn set_option(&mut self, option: &str, value: &str) {
+        match self.config.options.set(option, value) {
+            Ok(()) => self.status_message = Some(format!("{} = {}", option, value)),
+            Err(e) => self.error_message = Some(e.to_string()),
+        }
+    }
+
+    /// List the providers, or change and save the provider settings
+    async fn run_provider_command(&mut self, command: ProviderCommand) -> Result<()> {
+        let infos = match self.client.list_providers().await {
+            Ok(infos) => infos,
+            Err(e) => {
+                self.error_message = Some(format!("Failed to fetch providers: {}", e));
+                return Ok(());
+            }
+        };
+        let models: Vec<Vec<&str>> = infos
+            .iter()
+            .map(|p| p.models.iter().map(String::as_str).collect())
+            .collect();
+        let available: Vec<AvailableProvider> = infos
+            .iter()
+            .zip(&models)
+            .map(|(p, models)| (p.name.as_str(), models.as_slice()))
+            .collect();
+
+        let providers = &mut self.config.providers;
+        let changed = match &command {
+            ProviderCommand::List => {
+                let lines = providers.list_lines(&available);
+                let lines: Vec<&str> = lines.iter().map(|line| line.trim()).collect();
+                self.status_message = Some(format!("Providers: {}", lines.join(", ")));
+                return Ok(());
+            }
+            ProviderCommand::Default { name } => providers
+                .set_default(name, &available)
+                .map(|()| format!("Default provider: {}", name)),
+            ProviderCommand::Model { provider, model } => providers
+                .set_model(provider, model, &available)
+                .map(|()| format!("Model of {}: {}", provider, model)),
+        };
+        match changed.and_then(|message| self.config.save().map(|()| message)) {
+            Ok(message) => self.status_message = Some(message),
+            Err(e) => self.error_message = Some(e.to_string()),
+        }
+        Ok(())
+    }
+
+    /// Provider and model of the `providers` config, if the daemon offers it
+    ///
+    /// Its model is used if the provider has it, the provider's default
+    /// model otherwise.
+    pub(super) async fn configured_provider(&mut self) -> Option<ProviderChoice> {
+        let infos = self.client.list_providers().await.ok()?;
+        let providers = &self.config.providers;
+        let info: &ProviderInfo = infos.iter().find(|p| p.name == providers.default)?;
+        let model = providers
+            .model(&info.name)
+            .filter(|model| info.models.iter().any(|m| m == model))
+            .unwrap_or(&info.default_model);
+        Some(ProviderChoice {
+            provider: info.name.clone(),
+            model: model.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::input::handle_input_sync;
+    use super::*;
+    use amux_daemon::testing::TestDaemon;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn app(daemon: &TestDaemon) -> App {
+        let config = amux_config::defaults::default_config();
+        let keybinds = config.to_keybind_map().unwrap();
+        App::with_config(daemon.client.clone(), config, keybinds)
+    }
+
+    /// Press the prefix and `:`, type `line` and run it
+    async fn run_command(app: &mut App, line: &str) {
+        let press =
+            |app: &mut App, code, modifiers| handle_input_sync(app, KeyEvent::new(code, modifiers));
+        press(app, KeyCode::Char('s'), KeyModifiers::CONTROL);
+        press(app, KeyCode::Char(':'), KeyModifiers::NONE);
+        assert_eq!(app.input_mode, InputMode::CommandLine);
+        for c in line.chars() {
+            press(app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        let action = press(app, KeyCode::Enter, KeyModifiers::NONE).unwrap();
+        app.execute_async_action(action).await.unwrap();
+        assert_eq!(app.input_mode, InputMode::Normal);
+    }
+
+    #[tokio::test]
+    async fn test_provider_commands() {
+        let daemon = TestDaemon::start().await;
+        let mut app = app(&daemon);
+
+        run_command(&mut app, "provider list").await;
+        let status = app.status_message.take().unwrap();
+        assert!(
+            status.contains("* claude (available, model sonnet)"),
+            "{}",
+            status
+        );
+
+        run_command(&mut app, "provider default gemini").await;
+        let error = app.error_message.take().unwrap();
+        assert!(
+            error.starts_with("Invalid option: Provider 'gemini' not found"),
+            "{}",
+            error
+        );
+        assert_eq!(app.config.providers.default, "claude");
+
+        run_command(&mut app, "provider model claude gpt-4").await;
+        assert!(app.error_message.take().is_some());
+        assert_eq!(app.config.providers.claude.model, "sonnet");
+
+        run_command(&mut app, "bind j move-down").await;
+        assert_eq!(
+            app.error_message.take().as_deref(),
+            Some("Not available in the TUI: bind j move-down")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_command() {
+        let daemon = TestDaemon::start().await;
+        let mut app = app(&daemon);
+
+        run_command(&mut app, "set mouse_enabeld off").await;
+        assert_eq!(
+            app.error_message.take().as_deref(),
+            Some("Invalid option: Unknown option 'mouse_enabeld'. Did you mean 'mouse_enabled'?")
+        );
+        assert!(app.config.options.mouse_enabled);
+
+        run_command(&mut app, "set mouse_enabled maybe").await;
+        assert_eq!(
+            app.error_message.take().as_deref(),
+            Some("Invalid option: Invalid value 'maybe' for mouse_enabled")
+        );
+
+        run_command(&mut app, "set mouse_enabled off").await;
+        assert_eq!(app.error_message, None);
+        assert_eq!(app.status_message.as_deref(), Some("mouse_enabled = off"));
+        assert!(!app.config.options.mouse_enabled);
+    }
+
+    #[tokio::test]
+    async fn test_configured_provider() {
+        let daemon = TestDaemon::start().await;
+        let mut app = app(&daemon);
+        app.config.providers.default = "codex".to_string();
+        app.config.providers.codex.model = "gpt-4".to_string();
+        assert_eq!(
+            app.configured_provider().await,
+            Some(ProviderChoice {
+                provider: "codex".to_string(),
+                model: "gpt-4".to_string(),
+            })
+        );
+
+        // Models the provider doesn't have fall back to its default
+        app.config.providers.codex.model = "gpt-5".to_string();
+        assert_eq!(
+            app.configured_provider().await.map(|choice| choice.model),
+            Some("o4-mini".to_string())
+        );
+
+        app.config.providers.default = "gemini".to_string();
+        assert_eq!(app.configured_provider().await, None);
+    }
+}
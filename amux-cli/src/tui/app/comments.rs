@@ -1,38 +1,59 @@
 //! Line comment operations
 
-use super::super::state::{DiffItem, InputMode};
+use super::super::state::{DiffItem, InputMode, UndoEntry};
+use super::super::views::diff::collapse;
 use super::super::App;
 use crate::error::TuiError;
-use amux_proto::daemon::LineCommentInfo;
+use amux_config::FILE_COMMENT_LINE;
+use amux_proto::daemon::{LineCommentInfo, LineType};
 
 type Result<T> = std::result::Result<T, TuiError>;
 
 impl App {
-    /// Start adding a line comment (only works when cursor is on a diff line)
-    pub fn start_add_line_comment(&mut self) {
-        if let DiffItem::Line(file_idx, line_idx) = self.current_diff_item() {
-            let Some(diff) = self.diff() else { return };
-            if let (Some(file), Some(lines)) = (
-                diff.files.get(file_idx).cloned(),
-                diff.file_lines.get(&file_idx),
-            ) {
-                if let Some(diff_line) = lines.get(line_idx).cloned() {
-                    // Get actual line number from diff info
-                    let line_number = diff_line
-                        .new_lineno
-                        .unwrap_or(diff_line.old_lineno.unwrap_or(line_idx as i32));
-
-                    self.save_focus();
-                    self.input_mode = InputMode::AddLineComment {
-                        file_path: file.path.clone(),
-                        line_number,
-                        line_type: diff_line.line_type,
-                    };
-                    self.text_input.clear();
-                }
+    /// File and line a comment at the diff cursor attaches to, with the line type
+    ///
+    /// On a file header the comment covers the whole file (`FILE_COMMENT_LINE`).
+    fn comment_target(&self) -> Option<(String, i32, i32)> {
+        let diff = self.diff()?;
+        match self.current_diff_item() {
+            DiffItem::File(file_idx) => {
+                let file = diff.files.get(file_idx)?;
+                Some((
+                    file.path.clone(),
+                    FILE_COMMENT_LINE,
+                    LineType::Unspecified as i32,
+                ))
             }
+            DiffItem::Line(file_idx, line_idx) => {
+                let file = diff.files.get(file_idx)?;
+                let diff_line = diff
+                    .file_lines
+                    .get(&file_idx)?
+                    .get(line_idx)
+                    .filter(|line| !collapse::is_collapsed(line))?;
+                // Get actual line number from diff info
+                let line_number = diff_line
+                    .new_lineno
+                    .unwrap_or(diff_line.old_lineno.unwrap_or(line_idx as i32));
+                Some((file.path.clone(), line_number, diff_line.line_type))
+            }
+            DiffItem::None => None,
+        }
+    }
+
+    /// Start adding a comment on the diff line or file under the cursor
+    pub fn start_add_line_comment(&mut self) {
+        if let Some((file_path, line_number, line_type)) = self.comment_target() {
+            self.save_focus();
+            self.input_mode = InputMode::AddLineComment {
+                file_path,
+                line_number,
+                line_type,
+            };
+            self.text_input.clear();
         } else {
-            self.status_message = Some("Move cursor to a diff line to add comment".to_string());
+            self.status_message =
+                Some("Move cursor to a file or diff line to add comment".to_string());
         }
     }
 
@@ -47,7 +68,7 @@ impl App {
             _ => return Ok(()),
         };
 
-        let comment_text = self.text_input.trim().to_string();
+        let comment_text = self.text_input.trim();
         self.input_mode = InputMode::Normal;
         self.text_input.clear();
         self.restore_focus();
@@ -140,43 +161,22 @@ impl App {
             .count()
     }
 
-    /// Start editing an existing comment on current line
+    /// Start editing the comment on the current line or file
     pub fn start_edit_line_comment(&mut self) {
-        // Extract needed data first to avoid borrow conflicts
-        let edit_info: Option<(String, String, i32, String)> = {
-            if let DiffItem::Line(file_idx, line_idx) = self.current_diff_item() {
-                let diff = self.diff();
-                if let (Some(file), Some(lines)) = (
-                    diff.and_then(|d| d.files.get(file_idx)),
-                    diff.and_then(|d| d.file_lines.get(&file_idx)),
-                ) {
-                    if let Some(diff_line) = lines.get(line_idx) {
-                        let line_number = diff_line
-                            .new_lineno
-                            .unwrap_or(diff_line.old_lineno.unwrap_or(line_idx as i32));
-
-                        // Check if there's a comment on this line
-                        self.get_line_comment(&file.path, line_number)
-                            .map(|comment| {
-                                (
-                                    comment.id.clone(),
-                                    file.path.clone(),
-                                    line_number,
-                                    comment.comment.clone(),
-                                )
-                            })
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        };
+        let edit_info = self
+            .comment_target()
+            .and_then(|(file_path, line_number, _)| {
+                self.get_line_comment(&file_path, line_number)
+                    .map(|comment| {
+                        (
+                            comment.id.clone(),
+                            file_path.clone(),
+                            line_number,
+                            comment.comment.clone(),
+                        )
+                    })
+            });
 
-        // Now mutate self
         if let Some((comment_id, file_path, line_number, comment_text)) = edit_info {
             self.save_focus();
             self.input_mode = InputMode::EditLineComment {
@@ -186,7 +186,7 @@ impl App {
             };
             self.text_input.set_content(comment_text);
         } else {
-            self.status_message = Some("No comment on this line to edit".to_string());
+            self.status_message = Some("No comment here to edit".to_string());
         }
     }
 
@@ -197,7 +197,7 @@ impl App {
             _ => return Ok(()),
         };
 
-        let comment_text = self.text_input.trim().to_string();
+        let comment_text = self.text_input.trim();
         self.input_mode = InputMode::Normal;
         self.text_input.clear();
         self.restore_focus();
@@ -231,170 +231,112 @@ impl App {
         Ok(())
     }
 
-    /// Delete comment on current line
+    /// Delete the comment on the current line or file
     pub async fn delete_current_line_comment(&mut self) -> Result<()> {
-        if let DiffItem::Line(file_idx, line_idx) = self.current_diff_item() {
-            let Some(diff) = self.diff() else {
-                return Ok(());
-            };
-            if let (Some(file), Some(lines)) =
-                (diff.files.get(file_idx), diff.file_lines.get(&file_idx))
-            {
-                if let Some(diff_line) = lines.get(line_idx) {
-                    let line_number = diff_line
-                        .new_lineno
-                        .unwrap_or(diff_line.old_lineno.unwrap_or(line_idx as i32));
+        let comment = self
+            .comment_target()
+            .and_then(|(file_path, line_number, _)| {
+                self.get_line_comment(&file_path, line_number).cloned()
+            });
+        let Some(comment) = comment else {
+            self.status_message = Some("No comment here to delete".to_string());
+            return Ok(());
+        };
 
-                    if let Some(comment) = self
-                        .line_comments()
-                        .iter()
-                        .find(|c| c.file_path == file.path && c.line_number == line_number)
-                    {
-                        let comment_id = comment.id.clone();
-
-                        match self.client.delete_line_comment(&comment_id).await {
-                            Ok(_) => {
-                                if let Some(repo) = self.current_repo_mut() {
-                                    repo.line_comments.retain(|c| c.id != comment_id);
-                                }
-                                self.status_message = Some("Comment deleted".to_string());
-                            }
-                            Err(e) => {
-                                self.error_message =
-                                    Some(format!("Failed to delete comment: {}", e));
-                            }
-                        }
-                        return Ok(());
-                    }
+        let comment_id = comment.id.clone();
+        match self.client.delete_line_comment(&comment_id).await {
+            Ok(_) => {
+                if let Some(repo) = self.current_repo_mut() {
+                    repo.line_comments.retain(|c| c.id != comment_id);
                 }
+                self.push_undo(UndoEntry::Comment(comment), "Comment deleted");
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to delete comment: {}", e));
             }
         }
-        self.status_message = Some("No comment on this line to delete".to_string());
         Ok(())
     }
 
-    /// Jump to next line with a comment
-    pub fn jump_to_next_comment(&mut self) {
-        let current = self.current_diff_item();
-        let (current_file_idx, current_line_idx) = match current {
-            DiffItem::File(f) => (f, 0),
-            DiffItem::Line(f, l) => (f, l),
-            DiffItem::None => return,
+    /// Diff cursor positions holding a comment, with the commented line number
+    ///
+    /// File headers count when the file has a file-level comment; lines only
+    /// while their file is expanded.
+    fn comment_positions(&self) -> Vec<(usize, i32)> {
+        let Some(diff) = self.diff() else {
+            return Vec::new();
         };
-
-        // Build a flat list of (file_idx, line_idx, line_number, file_path)
-        let all_lines: Vec<(usize, usize, i32, String)> = {
-            let Some(diff) = self.diff() else { return };
-            let mut lines = Vec::new();
-            for (file_idx, file) in diff.files.iter().enumerate() {
-                if diff.expanded.contains(&file_idx) {
-                    if let Some(diff_lines) = diff.file_lines.get(&file_idx) {
-                        for (line_idx, diff_line) in diff_lines.iter().enumerate() {
-                            let line_number = diff_line
-                                .new_lineno
-                                .unwrap_or(diff_line.old_lineno.unwrap_or(line_idx as i32));
-                            lines.push((file_idx, line_idx, line_number, file.path.clone()));
-                        }
-                    }
-                }
+        let mut positions = Vec::new();
+        let mut cursor = 0;
+        for (file_idx, file) in diff.files.iter().enumerate() {
+            if self.has_line_comment(&file.path, FILE_COMMENT_LINE) {
+                positions.push((cursor, FILE_COMMENT_LINE));
             }
-            lines
-        };
-
-        // Find current position in flat list
-        let current_pos = all_lines
-            .iter()
-            .position(|(f, l, _, _)| *f == current_file_idx && *l >= current_line_idx)
-            .unwrap_or(0);
-
-        // Find next comment after current position
-        for (file_idx, line_idx, line_number, file_path) in all_lines.iter().skip(current_pos + 1) {
-            if self.has_line_comment(file_path, *line_number) {
-                let cursor = self.calculate_cursor_for_line(*file_idx, *line_idx);
-                if let Some(diff) = self.diff_mut() {
-                    diff.cursor = cursor;
-                }
-                self.status_message = Some(format!("Jumped to comment at line {}", line_number));
-                return;
+            cursor += 1; // File header
+            if !diff.expanded.contains(&file_idx) {
+                continue;
             }
-        }
-
-        // Wrap around - search from beginning
-        for (file_idx, line_idx, line_number, file_path) in all_lines.iter().take(current_pos + 1) {
-            if self.has_line_comment(file_path, *line_number) {
-                let cursor = self.calculate_cursor_for_line(*file_idx, *line_idx);
-                if let Some(diff) = self.diff_mut() {
-                    diff.cursor = cursor;
+            if let Some(diff_lines) = diff.file_lines.get(&file_idx) {
+                for (line_idx, diff_line) in diff_lines.iter().enumerate() {
+                    let line_number = diff_line
+                        .new_lineno
+                        .unwrap_or(diff_line.old_lineno.unwrap_or(line_idx as i32));
+                    if !collapse::is_collapsed(diff_line)
+                        && self.has_line_comment(&file.path, line_number)
+                    {
+                        positions.push((cursor, line_number));
+                    }
+                    cursor += 1;
                 }
-                self.status_message = Some(format!("Jumped to comment at line {}", line_number));
-                return;
             }
         }
-
-        self.status_message = Some("No comments to jump to".to_string());
+        positions
     }
 
-    /// Jump to previous line with a comment
-    pub fn jump_to_prev_comment(&mut self) {
-        let current = self.current_diff_item();
-        let (current_file_idx, current_line_idx) = match current {
-            DiffItem::File(f) => (f, 0),
-            DiffItem::Line(f, l) => (f, l),
-            DiffItem::None => return,
+    /// Jump to next line or file with a comment
+    pub fn jump_to_next_comment(&mut self) {
+        let Some(current) = self.diff().map(|d| d.cursor) else {
+            return;
         };
+        let positions = self.comment_positions();
+        // Wrap around to the first comment
+        let target = positions
+            .iter()
+            .find(|(cursor, _)| *cursor > current)
+            .or(positions.first())
+            .copied();
+        self.jump_to_comment(target);
+    }
 
-        // Build a flat list of (file_idx, line_idx, line_number, file_path)
-        let all_lines: Vec<(usize, usize, i32, String)> = {
-            let Some(diff) = self.diff() else { return };
-            let mut lines = Vec::new();
-            for (file_idx, file) in diff.files.iter().enumerate() {
-                if diff.expanded.contains(&file_idx) {
-                    if let Some(diff_lines) = diff.file_lines.get(&file_idx) {
-                        for (line_idx, diff_line) in diff_lines.iter().enumerate() {
-                            let line_number = diff_line
-                                .new_lineno
-                                .unwrap_or(diff_line.old_lineno.unwrap_or(line_idx as i32));
-                            lines.push((file_idx, line_idx, line_number, file.path.clone()));
-                        }
-                    }
-                }
-            }
-            lines
+    /// Jump to previous line or file with a comment
+    pub fn jump_to_prev_comment(&mut self) {
+        let Some(current) = self.diff().map(|d| d.cursor) else {
+            return;
         };
-
-        // Find current position in flat list
-        let current_pos = all_lines
+        let positions = self.comment_positions();
+        // Wrap around to the last comment
+        let target = positions
             .iter()
-            .position(|(f, l, _, _)| *f == current_file_idx && *l >= current_line_idx)
-            .unwrap_or(all_lines.len());
-
-        // Find previous comment before current position
-        for (file_idx, line_idx, line_number, file_path) in all_lines.iter().take(current_pos).rev()
-        {
-            if self.has_line_comment(file_path, *line_number) {
-                let cursor = self.calculate_cursor_for_line(*file_idx, *line_idx);
-                if let Some(diff) = self.diff_mut() {
-                    diff.cursor = cursor;
-                }
-                self.status_message = Some(format!("Jumped to comment at line {}", line_number));
-                return;
-            }
-        }
+            .rev()
+            .find(|(cursor, _)| *cursor < current)
+            .or(positions.last())
+            .copied();
+        self.jump_to_comment(target);
+    }
 
-        // Wrap around - search from end
-        for (file_idx, line_idx, line_number, file_path) in all_lines.iter().skip(current_pos).rev()
-        {
-            if self.has_line_comment(file_path, *line_number) {
-                let cursor = self.calculate_cursor_for_line(*file_idx, *line_idx);
-                if let Some(diff) = self.diff_mut() {
-                    diff.cursor = cursor;
-                }
-                self.status_message = Some(format!("Jumped to comment at line {}", line_number));
-                return;
-            }
+    fn jump_to_comment(&mut self, target: Option<(usize, i32)>) {
+        let Some((cursor, line_number)) = target else {
+            self.status_message = Some("No comments to jump to".to_string());
+            return;
+        };
+        if let Some(diff) = self.diff_mut() {
+            diff.cursor = cursor;
         }
-
-        self.status_message = Some("No comments to jump to".to_string());
+        self.status_message = Some(if line_number == FILE_COMMENT_LINE {
+            "Jumped to file comment".to_string()
+        } else {
+            format!("Jumped to comment at line {}", line_number)
+        });
     }
 
     /// Submit all comments as a review to Claude
@@ -417,10 +359,17 @@ impl App {
                 .push(comment);
         }
 
-        for (file_path, comments) in by_file {
+        for (file_path, mut comments) in by_file {
             prompt.push_str(&format!("## File: {}\n\n", file_path));
 
+            // File-level comments (line FILE_COMMENT_LINE) come first
+            comments.sort_by_key(|c| c.line_number);
             for comment in comments {
+                if comment.line_number == FILE_COMMENT_LINE {
+                    prompt.push_str("## File-level comment\n");
+                    prompt.push_str(&format!("Comment: {}\n\n", comment.comment));
+                    continue;
+                }
                 let line_type_str = match comment.line_type {
                     3 => "+", // Addition
                     4 => "-", // Deletion
@@ -448,10 +397,96 @@ impl App {
         if self.terminal_stream.is_some() {
             self.send_to_terminal(prompt.into_bytes()).await?;
             self.status_message = Some("Review sent to Claude".to_string());
+            self.save_review().await;
         } else {
             self.error_message = Some("No active session to send review".to_string());
         }
 
         Ok(())
     }
+
+    /// Keep the submitted comments as a review so it can be reopened later
+    async fn save_review(&mut self) {
+        let ids = self
+            .current_repo()
+            .map(|r| r.info.id.clone())
+            .zip(self.current_worktree().map(|w| w.branch.clone()));
+        let Some((repo_id, branch)) = ids else { return };
+        let base_ref = self
+            .diff()
+            .and_then(|d| d.base_ref.clone())
+            .unwrap_or_else(|| "HEAD".to_string());
+
+        if let Err(e) = self
+            .client
+            .save_review(&repo_id, &branch, &base_ref, None)
+            .await
+        {
+            tracing::warn!("Failed to save review: {}", e);
+        }
+    }
+
+    /// Open the list of past reviews of the current worktree
+    pub async fn show_reviews(&mut self) -> Result<()> {
+        let ids = self
+            .current_repo()
+            .map(|r| r.info.id.clone())
+            .zip(self.current_worktree().map(|w| w.branch.clone()));
+        let Some((repo_id, branch)) = ids else {
+            return Ok(());
+        };
+
+        match self.client.list_reviews(&repo_id, &branch).await {
+            Ok(reviews) if reviews.is_empty() => {
+                self.status_message = Some("No saved reviews".to_string());
+            }
+            Ok(reviews) => {
+                self.save_focus();
+                self.input_mode = InputMode::SelectReview {
+                    reviews,
+                    selected_index: 0,
+                };
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to list reviews: {}", e));
+            }
+        }
+        Ok(())
+    }
+
+    /// Put the selected review's comments back onto the diff
+    pub async fn open_review(&mut self) -> Result<()> {
+        let InputMode::SelectReview {
+            reviews,
+            selected_index,
+        } = &self.input_mode
+        else {
+            return Ok(());
+        };
+        let Some(review) = reviews.get(*selected_index) else {
+            return Ok(());
+        };
+        let (repo_id, branch, review_id) = (
+            review.repo_id.clone(),
+            review.branch.clone(),
+            review.id.clone(),
+        );
+
+        self.input_mode = InputMode::Normal;
+        self.restore_focus();
+
+        match self.client.open_review(&repo_id, &branch, &review_id).await {
+            Ok(comments) => {
+                let count = comments.len();
+                if let Some(repo) = self.current_repo_mut() {
+                    repo.line_comments = comments;
+                }
+                self.status_message = Some(format!("Reopened review ({} comments)", count));
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to open review: {}", e));
+            }
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,197 @@
+//! Merge conflict view operations
+
+use super::super::state::{AsyncAction, Focus, RightPanelView};
+use super::super::App;
+use crate::error::TuiError;
+use amux_proto::daemon::ConflictResolution;
+
+type Result<T> = std::result::Result<T, TuiError>;
+
+impl App {
+    /// Open the conflict view for the current worktree
+    pub async fn show_conflicts(&mut self) -> Result<()> {
+        let ids = self
+            .current_repo()
+            .map(|r| r.info.id.clone())
+            .zip(self.current_worktree().map(|w| w.branch.clone()));
+        let Some((repo_id, branch)) = ids else {
+            return Ok(());
+        };
+
+        let files = self.client.list_conflicts(&repo_id, &branch).await?;
+        if files.is_empty() {
+            self.status_message = Some("No merge conflicts".to_string());
+            if self.right_panel_view == RightPanelView::Conflicts {
+                self.close_conflicts_view();
+            }
+            return Ok(());
+        }
+
+        // Keep showing the same file when refreshing
+        let shown = self
+            .conflicts()
+            .and_then(|c| c.current_file())
+            .map(|f| f.path.clone());
+        if let Some(conflicts) = self.conflicts_mut() {
+            conflicts.file_idx = shown
+                .and_then(|path| files.iter().position(|f| f.path == path))
+                .unwrap_or(0);
+            conflicts.files = files;
+        }
+
+        // The diff view already saved the focus to return to
+        if self.right_panel_view == RightPanelView::Terminal {
+            self.save_focus();
+        }
+        self.right_panel_view = RightPanelView::Conflicts;
+        self.focus = Focus::Conflicts;
+        self.load_conflict_file().await
+    }
+
+    /// Load the hunks of the shown conflicted file, clearing any choices
+    pub async fn load_conflict_file(&mut self) -> Result<()> {
+        let ids = self
+            .current_repo()
+            .map(|r| r.info.id.clone())
+            .zip(self.current_worktree().map(|w| w.branch.clone()));
+        let path = self
+            .conflicts()
+            .and_then(|c| c.current_file())
+            .map(|f| f.path.clone());
+        let (Some((repo_id, branch)), Some(path)) = (ids, path) else {
+            return Ok(());
+        };
+
+        match self
+            .client
+            .get_file_conflicts(&repo_id, &branch, &path)
+            .await
+        {
+            Ok(regions) => {
+                if let Some(conflicts) = self.conflicts_mut() {
+                    let hunks = regions.iter().filter(|r| r.is_conflict).count();
+                    conflicts.regions = regions;
+                    conflicts.choices = vec![None; hunks];
+                    conflicts.selected = 0;
+                }
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to load conflicts: {}", e));
+            }
+        }
+        Ok(())
+    }
+
+    /// Select the previous or next conflict hunk
+    pub fn conflict_move(&mut self, down: bool) {
+        if let Some(conflicts) = self.conflicts_mut() {
+            let last = conflicts.choices.len().saturating_sub(1);
+            conflicts.selected = if down {
+                (conflicts.selected + 1).min(last)
+            } else {
+                conflicts.selected.saturating_sub(1)
+            };
+        }
+    }
+
+    /// Show the previous (negative) or next (positive) conflicted file
+    ///
+    /// Choices made for the current file are discarded.
+    pub fn conflict_switch_file(&mut self, delta: isize) -> Option<AsyncAction> {
+        let conflicts = self.conflicts_mut()?;
+        if conflicts.files.len() < 2 {
+            return None;
+        }
+        let len = conflicts.files.len() as isize;
+        conflicts.file_idx = (conflicts.file_idx as isize + delta).rem_euclid(len) as usize;
+        Some(AsyncAction::LoadConflictFile)
+    }
+
+    /// Resolve the selected hunk and move on to the next unresolved one
+    pub fn choose_conflict_resolution(&mut self, resolution: ConflictResolution) {
+        let Some(conflicts) = self.conflicts_mut() else {
+            return;
+        };
+        let selected = conflicts.selected;
+        let Some(choice) = conflicts.choices.get_mut(selected) else {
+            return;
+        };
+        *choice = Some(resolution);
+
+        let count = conflicts.choices.len();
+        if let Some(next) = (1..count)
+            .map(|offset| (selected + offset) % count)
+            .find(|&idx| conflicts.choices[idx].is_none())
+        {
+            conflicts.selected = next;
+        }
+    }
+
+    /// Write the shown file with the chosen resolutions and stage it
+    pub async fn write_conflict_resolution(&mut self) -> Result<()> {
+        let ids = self
+            .current_repo()
+            .map(|r| r.info.id.clone())
+            .zip(self.current_worktree().map(|w| w.branch.clone()));
+        let Some((repo_id, branch)) = ids else {
+            return Ok(());
+        };
+        let Some(conflicts) = self.conflicts() else {
+            return Ok(());
+        };
+        let Some(path) = conflicts.current_file().map(|f| f.path.clone()) else {
+            return Ok(());
+        };
+        let unresolved = conflicts.unresolved();
+        if unresolved > 0 {
+            self.error_message = Some(format!(
+                "{} conflict(s) in {} still need a choice",
+                unresolved, path
+            ));
+            return Ok(());
+        }
+        let resolutions: Vec<_> = conflicts.choices.iter().flatten().copied().collect();
+
+        let remaining = match self
+            .client
+            .resolve_conflicts(&repo_id, &branch, &path, &resolutions)
+            .await
+        {
+            Ok(remaining) => remaining,
+            Err(e) => {
+                self.error_message = Some(format!("Failed to resolve {}: {}", path, e));
+                return Ok(());
+            }
+        };
+
+        if remaining.is_empty() {
+            self.status_message = Some("All conflicts resolved; ready to commit".to_string());
+            self.close_conflicts_view();
+        } else {
+            self.status_message = Some(format!(
+                "Resolved {}; {} conflicted file(s) left",
+                path,
+                remaining.len()
+            ));
+            if let Some(conflicts) = self.conflicts_mut() {
+                conflicts.file_idx = conflicts.file_idx.min(remaining.len() - 1);
+                conflicts.files = remaining;
+            }
+            self.load_conflict_file().await?;
+        }
+
+        self.load_git_status().await?;
+        Ok(())
+    }
+
+    /// Leave the conflict view (restores focus)
+    pub fn close_conflicts_view(&mut self) {
+        self.right_panel_view = RightPanelView::Terminal;
+        if !self.restore_focus() {
+            self.focus = Focus::Sidebar;
+        }
+        if let Some(conflicts) = self.conflicts_mut() {
+            *conflicts = Default::default();
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//! Right-click context menu for sidebar rows
+
+use super::super::state::{InputMode, SidebarItem};
+use super::super::App;
+use amux_config::{Action, CopyTarget};
+
+impl App {
+    /// Open the actions for the selected sidebar row at a screen position
+    pub fn open_context_menu(&mut self, column: u16, row: u16) {
+        let items = match self.current_sidebar_item() {
+            SidebarItem::Session(_, _) => vec![
+                Action::RenameSession,
+                Action::ShowSessionInfo,
+                Action::ViewSessionFile,
+                Action::TogglePinSession,
+                Action::OpenInExternalTerminal,
+                Action::ExportTranscript,
+                Action::RestoreCheckpoint,
+                Action::CopyToClipboard(CopyTarget::SessionName),
+                Action::DeleteCurrent,
+            ],
+            SidebarItem::Worktree(_) => vec![
+                Action::CreateSession,
+                Action::SelectProviderAndCreate,
+                Action::RunCommand,
+                Action::ShellInDirectory,
+                Action::RenameBranch,
+                Action::CycleDeleteAction,
+                Action::CopyToClipboard(CopyTarget::BranchName),
+                Action::DeleteCurrent,
+            ],
+            SidebarItem::LoadMore(_) | SidebarItem::None => return,
+        };
+
+        self.save_focus();
+        self.input_mode = InputMode::ContextMenu {
+            items,
+            selected_index: 0,
+            column,
+            row,
+        };
+    }
+
+    /// Move the context menu selection up or down
+    pub fn move_context_menu_selection(&mut self, down: bool) {
+        if let InputMode::ContextMenu {
+            items,
+            selected_index,
+            ..
+        } = &mut self.input_mode
+        {
+            if down && *selected_index + 1 < items.len() {
+                *selected_index += 1;
+            } else if !down && *selected_index > 0 {
+                *selected_index -= 1;
+            }
+        }
+    }
+
+    /// Close the context menu, returning the chosen item's action
+    pub fn take_context_menu_item(&mut self, index: usize) -> Option<Action> {
+        let action = match &self.input_mode {
+            InputMode::ContextMenu { items, .. } => items.get(index).cloned(),
+            _ => return None,
+        };
+        self.cancel_input();
+        action
+    }
+}
@@ -1,36 +1,135 @@
 //! Diff view operations
 
-use super::super::state::{AsyncAction, DiffItem, Focus, RightPanelView};
+use super::super::highlight::parse_gitattributes;
+use super::super::state::{
+    AsyncAction, BlameState, DiffItem, DiffSnapshot, DiffViewMode, Focus, InputMode, RightPanelView,
+};
+use super::super::views::diff::collapse;
+use super::super::widgets::helpers::get_highlighter;
 use super::super::widgets::VirtualList;
 use super::super::App;
-use crate::error::TuiError;
+use crate::error::{ClientError, TuiError};
+use amux_proto::daemon::DiffFileInfo;
+use std::collections::HashSet;
+use std::path::Path;
 
 type Result<T> = std::result::Result<T, TuiError>;
 
 impl App {
     /// Switch to diff view
+    ///
+    /// Returning to a worktree's diff restores its expanded files, cursor
+    /// and base, unless its git status changed meanwhile.
     pub async fn switch_to_diff_view(&mut self) -> Result<()> {
+        self.show_worktree_diff();
         self.right_panel_view = RightPanelView::Diff;
         self.focus = Focus::DiffFiles;
+        let snapshot = self.take_diff_snapshot();
         self.load_diff_files().await?;
+        if let Some(snapshot) = snapshot {
+            self.restore_diff_snapshot(&snapshot).await?;
+        }
         self.load_comments().await?;
         Ok(())
     }
 
     /// Switch back to previous view (restores focus)
+    ///
+    /// The diff is cleared, its review position kept for the worktree.
     pub fn switch_to_terminal_view(&mut self) {
+        self.leave_diff_view();
+        if let Some(repo) = self.current_repo_mut() {
+            if let (Some(branch), Some(snapshot)) =
+                (repo.diff.loaded_branch.clone(), repo.diff.snapshot())
+            {
+                repo.diff_snapshots.insert(branch, snapshot);
+            }
+            let diff = &mut repo.diff;
+            diff.files.clear();
+            diff.expanded.clear();
+            diff.file_lines.clear();
+            diff.cursor = 0;
+            diff.scroll_offset = 0;
+            diff.revision = None;
+            diff.loaded_branch = None;
+        }
+    }
+
+    /// Swap between terminal and diff, keeping the diff's files, cursor and expansion
+    ///
+    /// The diff is only reloaded if it was loaded for another worktree, shows
+    /// a stash, or its git status changed since.
+    pub fn toggle_right_panel(&mut self) -> Option<AsyncAction> {
+        if self.right_panel_view == RightPanelView::Diff {
+            self.leave_diff_view();
+            return None;
+        }
+
+        self.save_focus();
+        if self.focus == Focus::Terminal {
+            self.exit_terminal();
+        }
+        let branch = self.current_worktree().map(|w| w.branch.clone());
+        let current = self.diff().is_some_and(|d| {
+            !d.stale
+                && d.revision.is_none()
+                && d.loaded_branch.is_some()
+                && d.loaded_branch == branch
+        });
+        if !current {
+            return Some(AsyncAction::SwitchToDiffView);
+        }
+        self.right_panel_view = RightPanelView::Diff;
+        self.focus = Focus::DiffFiles;
+        None
+    }
+
+    /// Show the terminal again and restore the focus held before the diff
+    fn leave_diff_view(&mut self) {
         self.right_panel_view = RightPanelView::Terminal;
         // Restore focus to where user was before entering diff
         if !self.restore_focus() {
             // Fallback to sidebar if stack was empty
             self.focus = Focus::Sidebar;
         }
+    }
+
+    /// Take the current worktree's kept review position, applying its base
+    ///
+    /// Kept positions give way to a file opened from the git panel.
+    fn take_diff_snapshot(&mut self) -> Option<DiffSnapshot> {
+        if self.git().is_some_and(|g| g.pending_diff_file.is_some()) {
+            return None;
+        }
+        let branch = self.current_worktree()?.branch.clone();
+        let repo = self.current_repo_mut()?;
+        let snapshot = repo.diff_snapshots.remove(&branch)?;
+        repo.diff.base_ref = snapshot.base_ref.clone();
+        repo.diff.mode = snapshot.mode;
+        repo.diff.branch_base = snapshot.branch_base.clone();
+        Some(snapshot)
+    }
+
+    /// Expand the snapshot's files again and put the cursor back
+    async fn restore_diff_snapshot(&mut self, snapshot: &DiffSnapshot) -> Result<()> {
+        let Some(diff) = self.diff_mut() else {
+            return Ok(());
+        };
+        diff.restore_expanded(snapshot);
+        // Each call loads the lines of one expanded file
+        for _ in 0..diff.expanded.len() {
+            self.load_file_diff().await?;
+        }
         if let Some(diff) = self.diff_mut() {
-            diff.files.clear();
-            diff.expanded.clear();
-            diff.file_lines.clear();
-            diff.cursor = 0;
-            diff.scroll_offset = 0;
+            diff.restore_cursor(snapshot);
+        }
+        Ok(())
+    }
+
+    /// Make the diff view show worktree changes rather than a stash
+    pub fn show_worktree_diff(&mut self) {
+        if let Some(diff) = self.diff_mut() {
+            diff.revision = None;
         }
     }
 
@@ -42,39 +141,13 @@ impl App {
             .map(|r| r.info.id.clone())
             .zip(self.current_worktree().map(|w| w.branch.clone()));
 
-        if let Some((repo_id, branch)) = ids {
-            match self.client.get_diff_files(&repo_id, &branch).await {
-                Ok(files) => {
-                    // Get pending file before modifying state
-                    let pending_file = self.git_mut().and_then(|g| g.pending_diff_file.take());
-
-                    if let Some(diff) = self.diff_mut() {
-                        diff.files = files;
-                        diff.expanded.clear();
-                        diff.file_lines.clear();
-                        diff.cursor = 0;
-                        diff.scroll_offset = 0;
-
-                        // If there's a pending file to expand, find and expand it
-                        if let Some(pending_file) = pending_file {
-                            if let Some(idx) =
-                                diff.files.iter().position(|f| f.path == pending_file)
-                            {
-                                diff.cursor = idx;
-                                diff.expanded.insert(idx);
-                            }
-                        }
-                    }
+        let revision = self.diff().and_then(|d| d.revision.clone());
+        let base_ref = self.diff().and_then(|d| d.picked_base());
 
-                    // Load the file's diff content if we just expanded one
-                    self.load_file_diff().await?;
-                }
-                Err(e) => {
-                    self.error_message = Some(format!("Failed to load diff: {}", e));
-                }
-            }
+        self.load_highlight_attributes();
 
-            // Also load comments for this branch
+        if let Some((repo_id, branch)) = ids {
+            // Load comments first: lines carrying one aren't collapsed
             match self
                 .client
                 .list_line_comments(&repo_id, &branch, None)
@@ -89,10 +162,126 @@ impl App {
                     tracing::warn!("Failed to load comments: {}", e);
                 }
             }
+
+            match self
+                .fetch_diff_files(&repo_id, &branch, revision.as_deref(), base_ref.as_deref())
+                .await
+            {
+                Ok(files) => {
+                    if let Some(diff) = self.diff_mut() {
+                        diff.loaded_branch = Some(branch);
+                        diff.stale = false;
+                    }
+                    self.show_diff_files(files).await?
+                }
+                Err(e) => {
+                    self.error_message = Some(format!("Failed to load diff: {}", e));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Fetch the files the diff view lists in its current mode
+    ///
+    /// `base_ref` is the worktree's base, or the branch's in `Branch` mode. A
+    /// branch diff that fails leaves `Branch` mode.
+    async fn fetch_diff_files(
+        &mut self,
+        repo_id: &str,
+        branch: &str,
+        revision: Option<&str>,
+        base_ref: Option<&str>,
+    ) -> std::result::Result<Vec<DiffFileInfo>, ClientError> {
+        let mode = self.diff().map(|d| d.mode).unwrap_or_default();
+        if revision.is_some() || mode == DiffViewMode::WorkingTree {
+            return self
+                .client
+                .get_diff_files(repo_id, branch, revision, base_ref)
+                .await;
+        }
+
+        let response = self.client.get_branch_diff(repo_id, branch, base_ref).await;
+        if let Some(diff) = self.diff_mut() {
+            match &response {
+                Ok(response) => diff.branch_base_used = Some(response.base_ref.clone()),
+                Err(_) => diff.mode = DiffViewMode::WorkingTree,
+            }
+        }
+        response.map(|r| r.files)
+    }
+
+    /// Show the branch's changes since its base instead of the worktree's,
+    /// or back
+    pub async fn toggle_diff_mode(&mut self) -> Result<()> {
+        let Some(diff) = self.diff_mut() else {
+            return Ok(());
+        };
+        diff.revision = None;
+        diff.mode = match diff.mode {
+            DiffViewMode::WorkingTree => DiffViewMode::Branch,
+            DiffViewMode::Branch => DiffViewMode::WorkingTree,
+        };
+        let mode = diff.mode;
+
+        self.load_diff_files().await?;
+        let Some(diff) = self.diff_mut() else {
+            return Ok(());
+        };
+        if diff.mode != mode {
+            return Ok(());
         }
+        let message = match mode {
+            DiffViewMode::WorkingTree => "Showing working tree changes".to_string(),
+            DiffViewMode::Branch => format!(
+                "Showing branch changes since {}",
+                diff.branch_base_used.as_deref().unwrap_or_default()
+            ),
+        };
+        self.status_message = Some(message);
         Ok(())
     }
 
+    /// Give the highlighter the current repo's `.gitattributes` languages
+    ///
+    /// The file is read from the repo's worktree once per repo.
+    fn load_highlight_attributes(&mut self) {
+        let Some(repo) = self.current_repo_mut() else {
+            return;
+        };
+        let attributes = repo.highlight_attributes.get_or_insert_with(|| {
+            std::fs::read_to_string(Path::new(&repo.info.path).join(".gitattributes"))
+                .map(|content| parse_gitattributes(&content))
+                .unwrap_or_default()
+        });
+        get_highlighter().set_attributes(attributes.clone());
+    }
+
+    /// Replace the listed files, expanding the pending file if any
+    async fn show_diff_files(&mut self, files: Vec<DiffFileInfo>) -> Result<()> {
+        // Get pending file before modifying state
+        let pending_file = self.git_mut().and_then(|g| g.pending_diff_file.take());
+
+        if let Some(diff) = self.diff_mut() {
+            diff.files = files;
+            diff.expanded.clear();
+            diff.file_lines.clear();
+            diff.cursor = 0;
+            diff.scroll_offset = 0;
+
+            // If there's a pending file to expand, find and expand it
+            if let Some(pending_file) = pending_file {
+                if let Some(idx) = diff.files.iter().position(|f| f.path == pending_file) {
+                    diff.cursor = idx;
+                    diff.expanded.insert(idx);
+                }
+            }
+        }
+
+        // Load the file's diff content if we just expanded one
+        self.load_file_diff().await
+    }
+
     /// Load diff content for the file that is being expanded
     pub async fn load_file_diff(&mut self) -> Result<()> {
         // Find which file needs loading (the one that's expanded but has no lines)
@@ -109,15 +298,54 @@ impl App {
             .map(|r| r.info.id.clone())
             .zip(self.current_worktree().map(|w| w.branch.clone()));
 
+        let revision = self.diff().and_then(|d| d.revision.clone());
+        let (base_ref, branch_diff) = self.diff().map(|d| d.file_diff_base()).unwrap_or_default();
+        let new_side = self.diff().and_then(|d| d.new_side_revision());
+
         if let (Some((file_idx, file_path)), Some((repo_id, branch))) = (file_info, ids) {
             match self
                 .client
-                .get_file_diff(&repo_id, &branch, &file_path)
+                .get_file_diff(
+                    &repo_id,
+                    &branch,
+                    &file_path,
+                    revision.as_deref(),
+                    base_ref.as_deref(),
+                    branch_diff,
+                )
                 .await
             {
                 Ok(response) => {
+                    // Lines with comments are never left hidden
+                    let commented = self.commented_lines(&file_path);
+                    let mut lines = collapse::collapse_context(
+                        response.lines,
+                        self.config.options.diff_collapse_context,
+                        &commented,
+                    );
+                    for idx in (0..lines.len()).rev() {
+                        if collapse::is_collapsed(&lines[idx])
+                            && collapse::hides_comment(&lines[idx], &commented)
+                        {
+                            let shown = self
+                                .client
+                                .get_file_lines(
+                                    &repo_id,
+                                    &branch,
+                                    &file_path,
+                                    new_side.as_deref(),
+                                    lines[idx].new_lineno.unwrap_or(1),
+                                    lines[idx].hidden_lines,
+                                )
+                                .await;
+                            if let Ok(contents) = shown {
+                                let expanded = collapse::expand(&lines[idx], contents);
+                                lines.splice(idx..=idx, expanded);
+                            }
+                        }
+                    }
                     if let Some(diff) = self.diff_mut() {
-                        diff.file_lines.insert(file_idx, response.lines);
+                        diff.file_lines.insert(file_idx, lines);
                     }
                 }
                 Err(e) => {
@@ -131,6 +359,281 @@ impl App {
         Ok(())
     }
 
+    /// Show the unchanged lines of the collapsed row under the cursor
+    ///
+    /// They replace the row in place, so the cursor lands on the first one.
+    pub async fn expand_diff_context(&mut self) -> Result<()> {
+        let DiffItem::Line(file_idx, line_idx) = self.current_diff_item() else {
+            return Ok(());
+        };
+        let Some(diff) = self.diff() else {
+            return Ok(());
+        };
+        let Some(row) = diff
+            .file_lines
+            .get(&file_idx)
+            .and_then(|lines| lines.get(line_idx))
+            .filter(|line| collapse::is_collapsed(line))
+            .cloned()
+        else {
+            return Ok(());
+        };
+        let Some(file_path) = diff.files.get(file_idx).map(|f| f.path.clone()) else {
+            return Ok(());
+        };
+        let revision = diff.new_side_revision();
+
+        let ids = self
+            .current_repo()
+            .map(|r| r.info.id.clone())
+            .zip(self.current_worktree().map(|w| w.branch.clone()));
+        let Some((repo_id, branch)) = ids else {
+            return Ok(());
+        };
+
+        let contents = match self
+            .client
+            .get_file_lines(
+                &repo_id,
+                &branch,
+                &file_path,
+                revision.as_deref(),
+                row.new_lineno.unwrap_or(1),
+                row.hidden_lines,
+            )
+            .await
+        {
+            Ok(contents) => contents,
+            Err(e) => {
+                self.error_message = Some(format!("Failed to load lines: {}", e));
+                return Ok(());
+            }
+        };
+
+        if let Some(lines) = self
+            .diff_mut()
+            .and_then(|d| d.file_lines.get_mut(&file_idx))
+        {
+            // Only splice if the row is still where it was
+            if lines.get(line_idx) == Some(&row) {
+                lines.splice(line_idx..=line_idx, collapse::expand(&row, contents));
+            }
+        }
+        Ok(())
+    }
+
+    /// Line numbers of a file carrying a comment
+    fn commented_lines(&self, file_path: &str) -> HashSet<i32> {
+        self.line_comments()
+            .iter()
+            .filter(|c| c.file_path == file_path)
+            .map(|c| c.line_number)
+            .collect()
+    }
+
+    /// Open the diff base picker with the repo's branches and tags
+    pub async fn start_select_diff_base(&mut self) -> Result<()> {
+        if self.diff().is_some_and(|d| d.revision.is_some()) {
+            self.error_message = Some("A stash is shown; its base can't be changed".to_string());
+            return Ok(());
+        }
+        let Some(repo_id) = self.current_repo().map(|r| r.info.id.clone()) else {
+            return Ok(());
+        };
+
+        let response = match self.client.list_refs(&repo_id).await {
+            Ok(response) => response,
+            Err(e) => {
+                self.error_message = Some(format!("Failed to list refs: {}", e));
+                return Ok(());
+            }
+        };
+        let mut refs = vec!["HEAD".to_string()];
+        refs.extend(response.branches);
+        refs.extend(response.tags);
+
+        let current = self
+            .diff()
+            .and_then(|d| d.file_diff_base().0)
+            .unwrap_or_else(|| "HEAD".to_string());
+        let selected_index = refs.iter().position(|r| *r == current).unwrap_or(0);
+
+        self.save_focus();
+        self.text_input.clear();
+        self.input_mode = InputMode::SelectDiffBase {
+            refs,
+            selected_index,
+        };
+        Ok(())
+    }
+
+    /// Refs of the base picker containing the typed text (case-insensitive)
+    pub fn diff_base_matches(&self) -> Vec<&str> {
+        let InputMode::SelectDiffBase { refs, .. } = &self.input_mode else {
+            return Vec::new();
+        };
+        let filter = self.text_input.content().trim().to_lowercase();
+        refs.iter()
+            .map(String::as_str)
+            .filter(|r| r.to_lowercase().contains(&filter))
+            .collect()
+    }
+
+    /// Move the base picker selection up or down
+    pub fn move_diff_base_selection(&mut self, down: bool) {
+        let matches = self.diff_base_matches().len();
+        if let InputMode::SelectDiffBase { selected_index, .. } = &mut self.input_mode {
+            if down && *selected_index + 1 < matches {
+                *selected_index += 1;
+            } else if !down && *selected_index > 0 {
+                *selected_index -= 1;
+            }
+        }
+    }
+
+    /// Close the base picker and diff against the selected ref
+    ///
+    /// Text matching no listed ref (e.g. a SHA) is used as typed.
+    pub async fn submit_diff_base(&mut self) -> Result<()> {
+        let InputMode::SelectDiffBase { selected_index, .. } = self.input_mode else {
+            return Ok(());
+        };
+        let typed = self.text_input.content().trim().to_string();
+        let chosen = self
+            .diff_base_matches()
+            .get(selected_index)
+            .map(|r| r.to_string())
+            .unwrap_or(typed);
+
+        self.input_mode = InputMode::Normal;
+        self.text_input.clear();
+        self.restore_focus();
+
+        if chosen.is_empty() {
+            return Ok(());
+        }
+        let base_ref = (chosen != "HEAD").then_some(chosen);
+        self.set_diff_base(base_ref).await
+    }
+
+    /// Diff the worktree against `base_ref` (`None` = HEAD), or in `Branch`
+    /// mode the branch (`None` = its default base)
+    ///
+    /// The files are fetched first, so an invalid ref leaves the view as is.
+    pub async fn set_diff_base(&mut self, base_ref: Option<String>) -> Result<()> {
+        let ids = self
+            .current_repo()
+            .map(|r| r.info.id.clone())
+            .zip(self.current_worktree().map(|w| w.branch.clone()));
+        let Some((repo_id, branch)) = ids else {
+            return Ok(());
+        };
+
+        let files = match self
+            .fetch_diff_files(&repo_id, &branch, None, base_ref.as_deref())
+            .await
+        {
+            Ok(files) => files,
+            Err(e) => {
+                self.error_message = Some(format!("Failed to change diff base: {}", e));
+                return Ok(());
+            }
+        };
+
+        // Keep the file under the cursor expanded if it still differs
+        let shown_file = match self.current_diff_item() {
+            DiffItem::File(idx) | DiffItem::Line(idx, _) => self
+                .diff()
+                .filter(|d| d.expanded.contains(&idx))
+                .and_then(|d| d.files.get(idx))
+                .map(|f| f.path.clone()),
+            DiffItem::None => None,
+        };
+        if let Some(git) = self.git_mut() {
+            git.pending_diff_file = shown_file;
+        }
+
+        let message = match self.diff_mut() {
+            Some(diff) if diff.mode == DiffViewMode::Branch => {
+                diff.branch_base = base_ref;
+                format!(
+                    "Showing branch changes since {}",
+                    diff.branch_base_used.as_deref().unwrap_or_default()
+                )
+            }
+            Some(diff) => {
+                let message = format!("Diffing against {}", base_ref.as_deref().unwrap_or("HEAD"));
+                diff.base_ref = base_ref;
+                message
+            }
+            None => return Ok(()),
+        };
+        self.status_message = Some(message);
+        self.show_diff_files(files).await
+    }
+
+    /// Open the blame overlay for the file under the diff cursor
+    ///
+    /// Starts at the line under the cursor (its old-side number, which is
+    /// the HEAD line when diffing against HEAD).
+    pub async fn show_blame(&mut self) -> Result<()> {
+        if self.diff().is_some_and(|d| d.revision.is_some()) {
+            self.error_message = Some("A stash is shown; blame covers worktree files".to_string());
+            return Ok(());
+        }
+        let (file_idx, line_idx) = match self.current_diff_item() {
+            DiffItem::File(idx) => (idx, None),
+            DiffItem::Line(idx, line) => (idx, Some(line)),
+            DiffItem::None => return Ok(()),
+        };
+        let Some(diff) = self.diff() else {
+            return Ok(());
+        };
+        let Some(file_path) = diff.files.get(file_idx).map(|f| f.path.clone()) else {
+            return Ok(());
+        };
+        let start_line = line_idx
+            .and_then(|idx| diff.file_lines.get(&file_idx)?.get(idx))
+            .and_then(|line| line.old_lineno.or(line.new_lineno));
+
+        let ids = self
+            .current_repo()
+            .map(|r| r.info.id.clone())
+            .zip(self.current_worktree().map(|w| w.branch.clone()));
+        let Some((repo_id, branch)) = ids else {
+            return Ok(());
+        };
+
+        let response = match self.client.get_blame(&repo_id, &branch, &file_path).await {
+            Ok(response) => response,
+            Err(e) => {
+                self.error_message = Some(format!("Failed to blame {}: {}", file_path, e));
+                return Ok(());
+            }
+        };
+
+        let cursor = start_line
+            .map(|n| (n.max(1) - 1) as usize)
+            .unwrap_or(0)
+            .min(response.lines.len().saturating_sub(1));
+        self.blame = BlameState {
+            file_path,
+            head: response.head,
+            lines: response.lines,
+            cursor,
+        };
+        self.save_focus();
+        self.input_mode = InputMode::BlamePopup;
+        Ok(())
+    }
+
+    /// Close the blame overlay (restores focus)
+    pub fn close_blame(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.blame = BlameState::default();
+        self.restore_focus();
+    }
+
     /// Get current item at cursor position
     pub fn current_diff_item(&self) -> DiffItem {
         let Some(diff) = self.diff() else {
@@ -183,6 +686,14 @@ impl App {
         }
     }
 
+    /// Move the cursor to a position (mouse click)
+    pub fn diff_select(&mut self, pos: usize) {
+        if let Some(diff) = self.diff_mut() {
+            diff.set_cursor(pos);
+            self.dirty.sidebar = true;
+        }
+    }
+
     /// Jump to previous file
     pub fn diff_prev_file(&mut self) {
         let Some(diff) = self.diff_mut() else { return };
@@ -225,8 +736,15 @@ impl App {
         }
     }
 
-    /// Toggle expansion of current file (only works when cursor is on a file)
+    /// Toggle expansion of current file, or show the lines of a collapsed row
     pub fn toggle_diff_expand(&mut self) -> Option<AsyncAction> {
+        if let DiffItem::Line(file_idx, line_idx) = self.current_diff_item() {
+            let on_collapsed = self
+                .diff()
+                .and_then(|d| d.file_lines.get(&file_idx)?.get(line_idx))
+                .is_some_and(collapse::is_collapsed);
+            return on_collapsed.then_some(AsyncAction::ExpandDiffContext);
+        }
         if let DiffItem::File(file_idx) = self.current_diff_item() {
             let diff = self.diff_mut()?;
             if diff.expanded.contains(&file_idx) {
@@ -250,27 +768,4 @@ impl App {
             diff.fullscreen = !diff.fullscreen;
         }
     }
-
-    /// Calculate cursor position for a specific file and line
-    pub(super) fn calculate_cursor_for_line(
-        &self,
-        target_file_idx: usize,
-        target_line_idx: usize,
-    ) -> usize {
-        let Some(diff) = self.diff() else { return 0 };
-        let mut cursor = 0;
-        for (file_idx, _) in diff.files.iter().enumerate() {
-            if file_idx == target_file_idx {
-                // Found the file, add the line offset
-                return cursor + 1 + target_line_idx; // +1 for file header
-            }
-            cursor += 1; // File header
-            if diff.expanded.contains(&file_idx) {
-                if let Some(lines) = diff.file_lines.get(&file_idx) {
-                    cursor += lines.len();
-                }
-            }
-        }
-        cursor
-    }
 }
@@ -0,0 +1,286 @@
+//! Searching the lines of all diff files
+//!
+//! The search bar at the bottom of the diff view searches as the query is
+//! typed. With no file expanded, the files with matches are expanded for it;
+//! otherwise n/N load the diffs of collapsed files as the search reaches
+//! them. A file loaded that way is collapsed again when it has no match, so
+//! only files with hits stay open.
+
+use super::super::state::{AsyncAction, DiffItem, DiffSearch, DiffState, InputMode};
+use super::super::views::diff::collapse;
+use super::super::widgets::helpers::find_matches;
+use super::App;
+use crate::error::TuiError;
+
+type Result<T> = std::result::Result<T, TuiError>;
+
+impl App {
+    /// Open the search bar, searching again for the last query
+    pub fn start_diff_search(&mut self) -> Option<AsyncAction> {
+        let origin = self.current_diff_item();
+        let query = self
+            .diff()?
+            .search
+            .as_ref()
+            .map(|s| s.query.clone())
+            .unwrap_or_default();
+        self.save_focus();
+        self.input_mode = InputMode::SearchDiff { origin };
+        let searching = !query.is_empty();
+        self.text_input.set_content(query);
+        searching.then_some(AsyncAction::UpdateDiffSearch)
+    }
+
+    /// Search for the text typed so far, moving to its first match from
+    /// where the search started
+    pub async fn update_diff_search(&mut self) -> Result<()> {
+        let InputMode::SearchDiff { origin } = self.input_mode.clone() else {
+            return Ok(());
+        };
+        let query = self.text_input.content();
+        if query.is_empty() {
+            if let Some(diff) = self.diff_mut() {
+                diff.search = None;
+                diff.cursor = item_position(diff, &origin);
+            }
+            return Ok(());
+        }
+        if self.diff().is_some_and(|d| d.expanded.is_empty()) {
+            self.expand_matching_files(&query).await?;
+        }
+
+        let Some(diff) = self.diff_mut() else {
+            return Ok(());
+        };
+        let start = item_position(diff, &origin);
+        let matches = all_matches(diff, &query);
+        let current = matches
+            .iter()
+            .position(|&(file_idx, line_idx)| line_position(diff, file_idx, line_idx) >= start)
+            .unwrap_or(0);
+        diff.cursor = match matches.get(current) {
+            Some(&(file_idx, line_idx)) => line_position(diff, file_idx, line_idx),
+            None => start,
+        };
+        diff.search = Some(DiffSearch {
+            query,
+            matches,
+            current,
+        });
+        self.dirty.sidebar = true;
+        Ok(())
+    }
+
+    /// Close the search bar, staying at the match and keeping n/N on it
+    pub fn submit_diff_search(&mut self) {
+        let query = self.text_input.content();
+        self.cancel_input();
+        if query.is_empty() {
+            if let Some(diff) = self.diff_mut() {
+                diff.search = None;
+            }
+        }
+    }
+
+    /// Close the search bar, back where the search started
+    pub fn cancel_diff_search(&mut self) {
+        if let InputMode::SearchDiff { origin } = self.input_mode.clone() {
+            if let Some(diff) = self.diff_mut() {
+                diff.search = None;
+                diff.cursor = item_position(diff, &origin);
+            }
+        }
+        self.cancel_input();
+    }
+
+    /// Whether n/N cycle through diff search matches
+    pub fn diff_search_active(&self) -> bool {
+        self.diff().is_some_and(|d| d.search.is_some())
+    }
+
+    /// The match under the cursor (counting from 1) and the number of matches
+    pub fn diff_search_count(&self) -> Option<(Option<usize>, usize)> {
+        let diff = self.diff()?;
+        let search = diff.search.as_ref()?;
+        let on_current = search
+            .matches
+            .get(search.current)
+            .is_some_and(|&(file_idx, line_idx)| {
+                line_position(diff, file_idx, line_idx) == diff.cursor
+            });
+        Some((
+            on_current.then_some(search.current + 1),
+            search.matches.len(),
+        ))
+    }
+
+    /// Move the cursor to the next (or previous) line matching the search
+    pub async fn diff_search_step(&mut self, forward: bool) -> Result<()> {
+        let Some(query) = self
+            .diff()
+            .and_then(|d| d.search.as_ref())
+            .map(|s| s.query.clone())
+        else {
+            return Ok(());
+        };
+        let (current_file, current_line) = match self.current_diff_item() {
+            DiffItem::File(idx) => (idx, -1),
+            DiffItem::Line(idx, line_idx) => (idx, line_idx as isize),
+            DiffItem::None => (0, -1),
+        };
+        let count = self.diff().map_or(0, |d| d.files.len());
+        if count == 0 {
+            self.status_message = Some("No changes to search".to_string());
+            return Ok(());
+        }
+
+        // Every file once, then the current one again for the lines on the
+        // other side of the cursor
+        for step in 0..=count {
+            let file_idx = if forward {
+                (current_file + step) % count
+            } else {
+                (current_file + count - step % count) % count
+            };
+            let (first, last) = match (forward, step) {
+                (true, 0) => (current_line + 1, isize::MAX),
+                (true, _) if step == count => (0, current_line),
+                (false, 0) => (0, current_line - 1),
+                (false, _) if step == count => (current_line, isize::MAX),
+                _ => (0, isize::MAX),
+            };
+            if first > last {
+                continue;
+            }
+
+            let loaded_here = self.load_for_search(file_idx).await?;
+            let Some(diff) = self.diff_mut() else {
+                return Ok(());
+            };
+            let mut hits = matching_lines(diff, file_idx, &query)
+                .into_iter()
+                .filter(|&l| (first..=last).contains(&(l as isize)));
+            let hit = if forward {
+                hits.next()
+            } else {
+                hits.next_back()
+            };
+
+            match hit {
+                Some(line_idx) => {
+                    diff.cursor = line_position(diff, file_idx, line_idx);
+                    // Files loaded on the way may have added matches
+                    let matches = all_matches(diff, &query);
+                    let current = matches
+                        .iter()
+                        .position(|&m| m == (file_idx, line_idx))
+                        .unwrap_or(0);
+                    let message = format!("Match {}/{}", current + 1, matches.len());
+                    diff.search = Some(DiffSearch {
+                        query,
+                        matches,
+                        current,
+                    });
+                    self.status_message = Some(message);
+                    self.dirty.sidebar = true;
+                    return Ok(());
+                }
+                None if loaded_here && matching_lines(diff, file_idx, &query).is_empty() => {
+                    diff.expanded.remove(&file_idx);
+                    diff.file_lines.remove(&file_idx);
+                }
+                None => {}
+            }
+        }
+
+        self.status_message = Some(format!("No matches for '{}'", query));
+        Ok(())
+    }
+
+    /// Expand the files whose lines match `query`, loading every file
+    async fn expand_matching_files(&mut self, query: &str) -> Result<()> {
+        let count = self.diff().map_or(0, |d| d.files.len());
+        for file_idx in 0..count {
+            let loaded_here = self.load_for_search(file_idx).await?;
+            if let Some(diff) = self.diff_mut() {
+                if loaded_here && matching_lines(diff, file_idx, query).is_empty() {
+                    diff.expanded.remove(&file_idx);
+                    diff.file_lines.remove(&file_idx);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Expand a file and load its lines unless it is open already
+    ///
+    /// Returns whether the file was expanded for the search.
+    async fn load_for_search(&mut self, file_idx: usize) -> Result<bool> {
+        let Some(diff) = self.diff_mut() else {
+            return Ok(false);
+        };
+        if diff.expanded.contains(&file_idx) {
+            return Ok(false);
+        }
+        diff.expanded.insert(file_idx);
+        self.load_file_diff().await?;
+        Ok(true)
+    }
+}
+
+/// Matching `(file_idx, line_idx)` of all expanded files
+fn all_matches(diff: &DiffState, query: &str) -> Vec<(usize, usize)> {
+    (0..diff.files.len())
+        .flat_map(|file_idx| {
+            matching_lines(diff, file_idx, query)
+                .into_iter()
+                .map(move |line_idx| (file_idx, line_idx))
+        })
+        .collect()
+}
+
+/// Indices of the lines of an expanded file matching `query`
+fn matching_lines(diff: &DiffState, file_idx: usize, query: &str) -> Vec<usize> {
+    if !diff.expanded.contains(&file_idx) {
+        return Vec::new();
+    }
+    diff.file_lines
+        .get(&file_idx)
+        .map_or_else(Vec::new, |lines| {
+            lines
+                .iter()
+                .enumerate()
+                .filter(|(_, line)| {
+                    !collapse::is_collapsed(line) && !find_matches(&line.content, query).is_empty()
+                })
+                .map(|(idx, _)| idx)
+                .collect()
+        })
+}
+
+/// Cursor position of a line of an expanded file
+fn line_position(diff: &DiffState, file_idx: usize, line_idx: usize) -> usize {
+    let before: usize = (0..file_idx)
+        .map(|idx| {
+            let lines = if diff.expanded.contains(&idx) {
+                diff.file_lines.get(&idx).map_or(0, |l| l.len())
+            } else {
+                0
+            };
+            1 + lines
+        })
+        .sum();
+    before + 1 + line_idx
+}
+
+/// Cursor position of a file or line, wherever files were expanded since
+fn item_position(diff: &DiffState, item: &DiffItem) -> usize {
+    match *item {
+        DiffItem::File(file_idx) => line_position(diff, file_idx, 0) - 1,
+        DiffItem::Line(file_idx, line_idx) if diff.expanded.contains(&file_idx) => {
+            line_position(diff, file_idx, line_idx)
+        }
+        DiffItem::Line(file_idx, _) => line_position(diff, file_idx, 0) - 1,
+        DiffItem::None => 0,
+    }
+}
@@ -0,0 +1,117 @@
+//! Opening the focused file in the user's editor
+//!
+//! The action only records an [`EditorRequest`]; the main loop hands the
+//! terminal to the editor and calls [`App::editor_closed`] once it has it
+//! back.
+
+use super::super::state::{DiffItem, Focus, RightPanelView};
+use super::App;
+use crate::error::TuiError;
+use amux_proto::daemon::DiffLine;
+use std::path::PathBuf;
+use std::process::ExitStatus;
+
+type Result<T> = std::result::Result<T, TuiError>;
+
+/// An editor to run once the TUI has let go of the terminal
+pub struct EditorRequest {
+    /// Shell command line with the placeholders filled in
+    pub command: String,
+    /// Worktree the command runs in
+    pub cwd: PathBuf,
+    /// The edited file, relative to the worktree
+    pub file: String,
+}
+
+impl App {
+    /// Open the file under the cursor of the diff view or git panel
+    pub fn open_in_editor(&mut self) {
+        let Some(cwd) = self.current_worktree().map(|w| PathBuf::from(&w.path)) else {
+            self.error_message = Some("No worktree selected".to_string());
+            return;
+        };
+        let Some((file, line)) = self.editor_target() else {
+            self.error_message = Some("No file selected".to_string());
+            return;
+        };
+
+        let template = match self.config.options.editor_command.trim() {
+            "" => default_editor_command(),
+            template => template.to_string(),
+        };
+        let command = template
+            .replace("{line}", &line.to_string())
+            .replace("{file}", &shell_words::quote(&file));
+        self.editor_request = Some(EditorRequest { command, cwd, file });
+    }
+
+    /// File and line under the cursor of the focused view
+    fn editor_target(&self) -> Option<(String, u32)> {
+        match self.focus {
+            Focus::GitStatus => self.current_git_file_path().map(|path| (path, 1)),
+            Focus::DiffFiles => {
+                let diff = self.diff()?;
+                match self.current_diff_item() {
+                    DiffItem::File(idx) => Some((diff.files.get(idx)?.path.clone(), 1)),
+                    DiffItem::Line(idx, line_idx) => {
+                        let line = diff
+                            .file_lines
+                            .get(&idx)
+                            .map_or(1, |lines| new_line_number(lines, line_idx));
+                        Some((diff.files.get(idx)?.path.clone(), line))
+                    }
+                    DiffItem::None => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Report how the editor went and reload what it may have changed
+    pub async fn editor_closed(
+        &mut self,
+        request: EditorRequest,
+        status: std::io::Result<ExitStatus>,
+    ) -> Result<()> {
+        match status {
+            Ok(status) if status.success() => {}
+            Ok(status) => self.error_message = Some(format!("Editor exited with {}", status)),
+            Err(e) => self.error_message = Some(format!("Failed to run editor: {}", e)),
+        }
+
+        if self.sidebar.git_panel_enabled {
+            self.load_git_status().await?;
+        }
+        if self.right_panel_view == RightPanelView::Diff {
+            // Reopen the edited file once the diff is reloaded
+            if let Some(git) = self.git_mut() {
+                git.pending_diff_file = Some(request.file);
+            }
+            self.load_diff_files().await?;
+        }
+        Ok(())
+    }
+}
+
+/// `$VISUAL`, else `$EDITOR`, else `vi`, jumping to the line
+fn default_editor_command() -> String {
+    let editor = ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+    format!("{} +{{line}} {{file}}", editor)
+}
+
+/// Line of the new file at `idx`, or the nearest one after or before it
+/// (deleted lines and hunk headers have none)
+fn new_line_number(lines: &[DiffLine], idx: usize) -> u32 {
+    let after = lines.iter().skip(idx).find_map(|l| l.new_lineno);
+    let before = || {
+        lines[..idx.min(lines.len())]
+            .iter()
+            .rev()
+            .find_map(|l| l.new_lineno)
+    };
+    after.or_else(before).map_or(1, |n| n.max(1) as u32)
+}
@@ -1,6 +1,7 @@
 //! Event handling and async action execution
 
-use super::super::state::AsyncAction;
+use super::super::state::{AsyncAction, TerminalMode};
+use super::super::views::sidebar::format_memory;
 use super::super::App;
 use crate::error::TuiError;
 use amux_proto::daemon::{event as daemon_event, Event as DaemonEvent};
@@ -8,6 +9,9 @@ use tracing::debug;
 
 type Result<T> = std::result::Result<T, TuiError>;
 
+/// Memory use above which a session triggers a warning (1 GB)
+const HIGH_MEMORY_KB: u64 = 1024 * 1024;
+
 impl App {
     /// Check if event subscription needs to be restored
     pub fn needs_resubscribe(&self) -> bool {
@@ -45,6 +49,7 @@ impl App {
             }
             Some(daemon_event::Event::SessionDestroyed(e)) => {
                 debug!("Event: SessionDestroyed {}", e.session_id);
+                self.sidebar.unread.remove(&e.session_id);
                 if let Some(repo) = self.current_repo_mut() {
                     let old_len = repo.sessions.len();
                     // Remove session from list
@@ -142,6 +147,127 @@ impl App {
                 }
                 None
             }
+            Some(daemon_event::Event::SessionTitleChanged(e)) => {
+                debug!("Event: SessionTitleChanged {} {:?}", e.session_id, e.title);
+                let title = Some(e.title).filter(|t| !t.is_empty());
+                let mut changed = false;
+
+                if let Some(repo) = self.current_repo_mut() {
+                    for session in repo
+                        .sessions
+                        .iter_mut()
+                        .chain(repo.sessions_by_worktree.values_mut().flatten())
+                        .filter(|s| s.id == e.session_id)
+                    {
+                        if session.title != title {
+                            session.title = title.clone();
+                            changed = true;
+                        }
+                    }
+                }
+
+                if changed {
+                    self.dirty.sidebar = true;
+                }
+                None
+            }
+            Some(daemon_event::Event::SessionAttachChanged(e)) => {
+                debug!(
+                    "Event: SessionAttachChanged {} {}",
+                    e.session_id, e.attach_count
+                );
+                let was_external = self.attached_externally();
+
+                if let Some(repo) = self.current_repo_mut() {
+                    for session in repo
+                        .sessions
+                        .iter_mut()
+                        .chain(repo.sessions_by_worktree.values_mut().flatten())
+                        .filter(|s| s.id == e.session_id)
+                    {
+                        session.attach_count = e.attach_count;
+                    }
+                }
+
+                let is_external = self.attached_externally();
+                if is_external == was_external {
+                    return None;
+                }
+                self.dirty.sidebar = true;
+                if is_external {
+                    // Input belongs to the external window until it closes
+                    self.terminal.mode = TerminalMode::Normal;
+                    self.status_message =
+                        Some("Session attached externally, preview is read-only".to_string());
+                    None
+                } else {
+                    // The external window resized the PTY, take it back
+                    self.status_message = Some("External terminal detached".to_string());
+                    let (cols, rows) = self.screen_size();
+                    Some(AsyncAction::ResizeTerminal { rows, cols })
+                }
+            }
+            Some(daemon_event::Event::SessionActivity(e)) => {
+                // Keep the status bar's idle time current between refreshes
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map_or(0, |d| d.as_secs());
+                if let Some(repo) = self.repo_states.get_mut(&e.repo_id) {
+                    for session in repo
+                        .sessions
+                        .iter_mut()
+                        .chain(repo.sessions_by_worktree.values_mut().flatten())
+                        .filter(|s| s.id == e.session_id)
+                    {
+                        session.last_output_at = now;
+                    }
+                }
+
+                // Output of the session being viewed counts as read
+                if self.terminal.active_session_id.as_deref() == Some(e.session_id.as_str()) {
+                    if self.sidebar.unread.remove(&e.session_id).is_some() {
+                        self.dirty.sidebar = true;
+                    }
+                    return None;
+                }
+                if self
+                    .sidebar
+                    .unread
+                    .insert(e.session_id, e.repo_id)
+                    .is_none()
+                {
+                    self.dirty.sidebar = true;
+                }
+                None
+            }
+            Some(daemon_event::Event::SessionUsageUpdated(e)) => {
+                let mut crossed_limit = None;
+
+                if let Some(repo) = self.current_repo_mut() {
+                    for session in repo
+                        .sessions
+                        .iter_mut()
+                        .chain(repo.sessions_by_worktree.values_mut().flatten())
+                        .filter(|s| s.id == e.session_id)
+                    {
+                        if session.memory_kb < HIGH_MEMORY_KB && e.memory_kb >= HIGH_MEMORY_KB {
+                            crossed_limit = Some(session.name.clone());
+                        }
+                        session.memory_kb = e.memory_kb;
+                        session.cpu_percent = e.cpu_percent;
+                    }
+                }
+
+                if let Some(name) = crossed_limit {
+                    self.status_message = Some(format!(
+                        "Warning: session {} is using {} of memory",
+                        name,
+                        format_memory(e.memory_kb)
+                    ));
+                }
+                self.dirty.sidebar = true;
+                None
+            }
             Some(daemon_event::Event::WorktreeAdded(e)) => {
                 debug!(
                     "Event: WorktreeAdded {:?}",
@@ -184,6 +310,7 @@ impl App {
                                 repo.sidebar_cursor = 0;
                                 repo.expanded_worktrees.clear();
                                 repo.sessions_by_worktree.clear();
+                                repo.session_page_tokens.clear();
                             } else {
                                 // Clamp branch index
                                 if repo.branch_idx >= repo.worktrees.len() {
@@ -191,6 +318,7 @@ impl App {
                                 }
                                 // Clear session caches (indices may have shifted)
                                 repo.sessions_by_worktree.clear();
+                                repo.session_page_tokens.clear();
                                 repo.expanded_worktrees.clear();
                             }
 
@@ -210,28 +338,45 @@ impl App {
             Some(daemon_event::Event::GitStatusChanged(e)) => {
                 debug!("Event: GitStatusChanged {}/{}", e.repo_id, e.branch);
 
-                // Only refresh if event is for current worktree
-                if let (Some(repo), Some(worktree)) = (self.current_repo(), self.current_worktree())
-                {
-                    if e.repo_id == repo.info.id && e.branch == worktree.branch {
-                        debug!("Auto-refreshing git status for {}/{}", e.repo_id, e.branch);
+                // A kept diff of the branch is reloaded when next shown, and
+                // its review position forgotten
+                if let Some(repo) = self.repo_states.get_mut(&e.repo_id) {
+                    if repo.diff.loaded_branch.as_deref() == Some(e.branch.as_str()) {
+                        repo.diff.stale = true;
+                    }
+                    repo.diff_snapshots.remove(&e.branch);
+                }
 
-                        // Client-side debounce: avoid refreshing too frequently
-                        if let Some(last) = self.last_git_refresh {
-                            if last.elapsed() < std::time::Duration::from_millis(500) {
-                                debug!(
-                                    "Skipping refresh: debounced (last refresh was {}ms ago)",
-                                    last.elapsed().as_millis()
-                                );
-                                return None; // Skip if refreshed <500ms ago
-                            }
-                        }
+                // Only refresh if event is for current repo
+                let (Some(repo), Some(worktree)) = (self.current_repo(), self.current_worktree())
+                else {
+                    return None;
+                };
+                if e.repo_id != repo.info.id {
+                    return None;
+                }
+                if e.branch != worktree.branch {
+                    // Other worktrees only need their sidebar indicators updated
+                    return Some(AsyncAction::RefreshWorktreeStatus { branch: e.branch });
+                }
 
-                        self.last_git_refresh = Some(std::time::Instant::now());
-                        return Some(AsyncAction::LoadGitStatus);
+                debug!("Auto-refreshing git status for {}/{}", e.repo_id, e.branch);
+
+                // Client-side debounce: avoid refreshing too frequently
+                let debounce =
+                    std::time::Duration::from_millis(self.config.options.git_refresh_debounce_ms);
+                if let Some(last) = self.last_git_refresh {
+                    if last.elapsed() < debounce {
+                        debug!(
+                            "Skipping refresh: debounced (last refresh was {}ms ago)",
+                            last.elapsed().as_millis()
+                        );
+                        return None;
                     }
                 }
-                None
+
+                self.last_git_refresh = Some(std::time::Instant::now());
+                Some(AsyncAction::RefreshWorktreeStatus { branch: e.branch })
             }
             None => None,
         }
@@ -249,6 +394,21 @@ impl App {
             AsyncAction::RefreshBranches => {
                 let _ = self.refresh_branches().await;
             }
+            AsyncAction::AddRepo { path } => {
+                self.add_repo(&path).await?;
+            }
+            AsyncAction::CleanupWorktrees => {
+                self.cleanup_worktrees().await?;
+            }
+            AsyncAction::RemoveRepo { repo_id, sessions } => {
+                self.remove_repo(&repo_id, sessions).await?;
+            }
+            AsyncAction::SaveRepoOrder => {
+                self.save_repo_order().await?;
+            }
+            AsyncAction::SaveSidebarWidth => {
+                self.save_sidebar_width().await?;
+            }
             AsyncAction::CreateSession => {
                 self.create_new().await?;
             }
@@ -258,6 +418,45 @@ impl App {
             AsyncAction::SubmitRenameSession => {
                 self.submit_rename_session().await?;
             }
+            AsyncAction::SubmitRenameBranch => {
+                self.submit_rename_branch().await?;
+            }
+            AsyncAction::CycleWorktreeDeleteAction => {
+                self.cycle_worktree_delete_action().await?;
+            }
+            AsyncAction::TogglePinSession => {
+                self.toggle_pin_session().await?;
+            }
+            AsyncAction::ExportTranscript => {
+                self.export_transcript().await?;
+            }
+            AsyncAction::ViewSessionFile => {
+                self.view_session_file().await?;
+            }
+            AsyncAction::ReplayMacro { register } => {
+                self.replay_macro(register).await?;
+            }
+            AsyncAction::ToggleCheckpoints => {
+                self.toggle_checkpoints().await?;
+            }
+            AsyncAction::PrepareRestoreCheckpoint => {
+                self.prepare_restore_checkpoint().await?;
+            }
+            AsyncAction::RestoreCheckpoint => {
+                self.restore_checkpoint().await?;
+            }
+            AsyncAction::MoveSession { down } => {
+                self.move_session(down).await?;
+            }
+            AsyncAction::ShowConflicts => {
+                self.show_conflicts().await?;
+            }
+            AsyncAction::LoadConflictFile => {
+                self.load_conflict_file().await?;
+            }
+            AsyncAction::WriteConflictResolution => {
+                self.write_conflict_resolution().await?;
+            }
             AsyncAction::SubmitAddWorktree => {
                 self.submit_add_worktree().await?;
             }
@@ -311,6 +510,9 @@ impl App {
             AsyncAction::LoadFileDiff => {
                 self.load_file_diff().await?;
             }
+            AsyncAction::ExpandDiffContext => {
+                self.expand_diff_context().await?;
+            }
             AsyncAction::LoadComments => {
                 self.load_comments().await?;
             }
@@ -326,12 +528,27 @@ impl App {
             AsyncAction::SubmitReviewToClaude => {
                 self.submit_review_to_claude().await?;
             }
+            AsyncAction::ShowReviews => {
+                self.show_reviews().await?;
+            }
+            AsyncAction::OpenReview => {
+                self.open_review().await?;
+            }
             AsyncAction::LoadWorktreeSessions { wt_idx } => {
                 self.load_worktree_sessions(wt_idx).await?;
             }
+            AsyncAction::LoadMoreSessions { wt_idx } => {
+                self.load_more_sessions(wt_idx).await?;
+            }
             AsyncAction::LoadGitStatus => {
                 self.load_git_status().await?;
             }
+            AsyncAction::RefreshWorktreeStatus { branch } => {
+                self.refresh_worktree_status().await?;
+                if self.current_worktree().map(|w| w.branch == branch) == Some(true) {
+                    self.load_git_status().await?;
+                }
+            }
             AsyncAction::StageFile { file_path } => {
                 self.stage_file(&file_path).await?;
             }
@@ -350,12 +567,36 @@ impl App {
             AsyncAction::GitPull => {
                 self.git_pull().await?;
             }
+            AsyncAction::GitFetch => {
+                self.git_fetch().await?;
+            }
+            AsyncAction::ConfirmPullStash => {
+                self.confirm_pull_stash().await?;
+            }
+            AsyncAction::StashSave => {
+                self.stash_save().await?;
+            }
+            AsyncAction::StashPop => {
+                self.stash_pop().await?;
+            }
+            AsyncAction::ViewStashDiff => {
+                self.view_stash_diff().await?;
+            }
+            AsyncAction::StartQuickShip => {
+                self.start_quick_ship().await?;
+            }
+            AsyncAction::SubmitQuickShip => {
+                self.submit_quick_ship().await?;
+            }
             AsyncAction::SwitchToShell => {
                 self.switch_to_shell_session().await?;
             }
             AsyncAction::LoadTodos => {
                 self.load_todos().await?;
             }
+            AsyncAction::Undo => {
+                self.undo().await?;
+            }
             AsyncAction::CreateTodo {
                 title,
                 description,
@@ -387,12 +628,72 @@ impl App {
             AsyncAction::FetchProviders { repo_id, branch } => {
                 self.fetch_providers(&repo_id, &branch).await?;
             }
+            AsyncAction::SearchSessions { query } => {
+                self.search_sessions(&query).await?;
+            }
+            AsyncAction::SubmitSearchSessions => {
+                self.submit_search_sessions().await?;
+            }
+            AsyncAction::SearchHistory { query } => {
+                self.search_history(&query).await?;
+            }
+            AsyncAction::SubmitSearchHistory => {
+                self.submit_search_history().await?;
+            }
+            AsyncAction::StartSelectDiffBase => {
+                self.start_select_diff_base().await?;
+            }
+            AsyncAction::SubmitDiffBase => {
+                self.submit_diff_base().await?;
+            }
+            AsyncAction::ToggleDiffMode => {
+                self.toggle_diff_mode().await?;
+            }
+            AsyncAction::ShowBlame => {
+                self.show_blame().await?;
+            }
             AsyncAction::SubmitProviderSelection => {
                 self.submit_provider_selection().await?;
             }
             AsyncAction::SubmitCreateSessionInput => {
                 self.submit_create_session_input().await?;
             }
+            AsyncAction::SubmitSessionSystemPrompt => {
+                self.submit_session_system_prompt().await?;
+            }
+            AsyncAction::SubmitRunCommand => {
+                self.submit_run_command().await?;
+            }
+            AsyncAction::SubmitCommandLine => {
+                self.submit_command_line().await?;
+            }
+            AsyncAction::ConfirmBroadcast => {
+                self.confirm_broadcast().await?;
+            }
+            AsyncAction::UpdateDiffSearch => {
+                self.update_diff_search().await?;
+            }
+            AsyncAction::DiffSearch { forward } => {
+                self.diff_search_step(forward).await?;
+            }
+            AsyncAction::CompleteShellDirectory => {
+                self.complete_shell_directory().await?;
+            }
+            AsyncAction::SubmitShellDirectory => {
+                self.submit_shell_directory().await?;
+            }
+            AsyncAction::RequestQuit => {
+                self.request_quit().await?;
+            }
+            AsyncAction::StopAllAndQuit => {
+                self.stop_all_and_quit().await;
+            }
+            AsyncAction::RunStartupAction {
+                repo_id,
+                sidebar_cursor,
+            } => {
+                self.run_startup_action(repo_id, sidebar_cursor).await?;
+            }
         }
         Ok(())
     }
@@ -0,0 +1,76 @@
+//! Session transcript export
+
+use super::super::state::SidebarItem;
+use super::super::App;
+use crate::error::TuiError;
+use amux_proto::daemon::TranscriptFormat;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+type Result<T> = std::result::Result<T, TuiError>;
+
+impl App {
+    /// Write the selected session's transcript as Markdown to ~/.amux/exports/
+    pub async fn export_transcript(&mut self) -> Result<()> {
+        let SidebarItem::Session(wt_idx, s_idx) = self.current_sidebar_item() else {
+            self.error_message = Some("No session selected".to_string());
+            return Ok(());
+        };
+        let Some(session) = self
+            .current_repo()
+            .and_then(|r| r.sessions_by_worktree.get(&wt_idx))
+            .and_then(|sessions| sessions.get(s_idx))
+            .cloned()
+        else {
+            return Ok(());
+        };
+
+        let transcript = match self
+            .client
+            .export_session_transcript(&session.id, TranscriptFormat::Markdown)
+            .await
+        {
+            Ok(transcript) => transcript,
+            Err(e) => {
+                self.error_message = Some(format!("Failed to export transcript: {}", e));
+                return Ok(());
+            }
+        };
+
+        let Some(dir) = dirs::home_dir().map(|home| home.join(".amux").join("exports")) else {
+            self.error_message = Some("Failed to export transcript: no home directory".to_string());
+            return Ok(());
+        };
+        let path = export_path(dir, &session.name);
+        let written = std::fs::create_dir_all(path.parent().unwrap_or(&path))
+            .and_then(|_| std::fs::write(&path, transcript.content));
+        match written {
+            Ok(()) => {
+                self.status_message = Some(format!("Exported transcript to {}", path.display()));
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to export transcript: {}", e));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `<dir>/<session name>-<unix time>.md`, with the name made filename-safe
+fn export_path(dir: PathBuf, session_name: &str) -> PathBuf {
+    let name: String = session_name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    dir.join(format!("{}-{}.md", name, secs))
+}
@@ -1,9 +1,12 @@
 //! Git status operations
 
-use super::super::state::{GitPanelItem, GitSection, GitStatusFile};
+use super::super::state::{
+    AsyncAction, Focus, GitPanelItem, GitSection, GitStatusFile, InputMode, RightPanelView,
+};
 use super::super::widgets::VirtualList;
 use super::super::App;
-use crate::error::TuiError;
+use crate::error::{ClientError, TuiError};
+use amux_proto::daemon::{PipelineStep, PullOutcome, PullStrategy, StashEntry, StepFailure};
 
 type Result<T> = std::result::Result<T, TuiError>;
 
@@ -23,7 +26,11 @@ impl App {
             }
         };
 
-        let response = self.client.get_git_status(&repo_id, &branch).await?;
+        let include_ignored = self.config.options.show_ignored;
+        let response = self
+            .client
+            .get_git_status(&repo_id, &branch, include_ignored)
+            .await?;
 
         // Update git state in repo
         if let Some(repo) = self.current_repo_mut() {
@@ -50,11 +57,36 @@ impl App {
                     section: GitSection::Untracked,
                 });
             }
+            for f in response.submodules {
+                repo.git.files.push(GitStatusFile {
+                    path: f.path,
+                    status: f.status,
+                    section: GitSection::Submodules,
+                });
+            }
+            for f in response.ignored {
+                repo.git.files.push(GitStatusFile {
+                    path: f.path,
+                    status: f.status,
+                    section: GitSection::Ignored,
+                });
+            }
 
             repo.git.cursor = 0;
         }
         self.dirty.sidebar = true;
 
+        match self.client.stash_list(&repo_id, &branch).await {
+            Ok(stashes) => {
+                if let Some(repo) = self.current_repo_mut() {
+                    repo.git.stashes = stashes;
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Failed to load stashes: {}", e);
+            }
+        }
+
         // Also load comments for this branch
         match self
             .client
@@ -143,13 +175,7 @@ impl App {
         };
 
         let mut pos = 0;
-        let sections = [
-            GitSection::Staged,
-            GitSection::Unstaged,
-            GitSection::Untracked,
-        ];
-
-        for section in sections {
+        for section in GitSection::FILE_SECTIONS {
             let files: Vec<_> = git
                 .files
                 .iter()
@@ -178,6 +204,20 @@ impl App {
             }
         }
 
+        if !git.stashes.is_empty() {
+            if pos == git.cursor {
+                return GitPanelItem::Section(GitSection::Stashes);
+            }
+            pos += 1;
+            if git.expanded_sections.contains(&GitSection::Stashes) {
+                if let Some(idx) = git.cursor.checked_sub(pos) {
+                    if idx < git.stashes.len() {
+                        return GitPanelItem::Stash(idx);
+                    }
+                }
+            }
+        }
+
         GitPanelItem::None
     }
 
@@ -239,6 +279,26 @@ impl App {
         }
     }
 
+    /// Section of the currently selected git status file
+    pub fn current_git_file_section(&self) -> Option<GitSection> {
+        if let GitPanelItem::File(idx) = self.current_git_panel_item() {
+            self.git()?.files.get(idx).map(|f| f.section)
+        } else {
+            None
+        }
+    }
+
+    /// Show or hide files matched by .gitignore (not saved to the config)
+    pub fn toggle_show_ignored(&mut self) {
+        let show = !self.config.options.show_ignored;
+        self.config.options.show_ignored = show;
+        self.status_message = Some(if show {
+            "Showing ignored files".to_string()
+        } else {
+            "Hiding ignored files".to_string()
+        });
+    }
+
     /// Check if current git item is staged
     pub fn is_current_git_item_staged(&self) -> bool {
         if let GitPanelItem::File(idx) = self.current_git_panel_item() {
@@ -264,6 +324,8 @@ impl App {
             let response = self.client.git_push(&repo_id, &branch).await?;
             if response.success {
                 self.status_message = Some(response.message);
+                // Update ahead/behind indicators
+                self.refresh_worktree_status().await?;
             } else {
                 self.error_message = Some(response.message);
             }
@@ -271,7 +333,31 @@ impl App {
         Ok(())
     }
 
-    /// Pull from remote (fetch + rebase)
+    /// Show a progress message and run an action once it has been drawn
+    pub fn defer_with_status(&mut self, message: &str, action: AsyncAction) -> Option<AsyncAction> {
+        self.status_message = Some(message.to_string());
+        self.deferred_action = Some(action);
+        None
+    }
+
+    /// Fetch all remotes of the current repo
+    pub async fn git_fetch(&mut self) -> Result<()> {
+        let Some(repo_id) = self.current_repo().map(|r| r.info.id.clone()) else {
+            return Ok(());
+        };
+
+        let response = self.client.fetch(&repo_id).await?;
+        if response.success {
+            self.status_message = Some(response.message);
+            // Update ahead/behind indicators
+            self.refresh_worktree_status().await?;
+        } else {
+            self.error_message = Some(response.message);
+        }
+        Ok(())
+    }
+
+    /// Pull the current worktree using the configured strategy
     pub async fn git_pull(&mut self) -> Result<()> {
         let ids = self
             .current_repo()
@@ -279,15 +365,296 @@ impl App {
             .zip(self.current_worktree().map(|w| w.branch.clone()));
 
         if let Some((repo_id, branch)) = ids {
-            let response = self.client.git_pull(&repo_id, &branch).await?;
-            if response.success {
+            self.pull_worktree(repo_id, branch, false).await?;
+        }
+        Ok(())
+    }
+
+    /// Pull after the user agreed to stash local changes
+    pub async fn confirm_pull_stash(&mut self) -> Result<()> {
+        let (repo_id, branch) = match &self.input_mode {
+            InputMode::ConfirmPullStash { repo_id, branch } => (repo_id.clone(), branch.clone()),
+            _ => return Ok(()),
+        };
+        self.input_mode = InputMode::Normal;
+        self.restore_focus();
+        self.pull_worktree(repo_id, branch, true).await
+    }
+
+    async fn pull_worktree(
+        &mut self,
+        repo_id: String,
+        branch: String,
+        stash_first: bool,
+    ) -> Result<()> {
+        let strategy = match self.config.options.pull_strategy {
+            amux_config::PullStrategy::Merge => PullStrategy::Merge,
+            amux_config::PullStrategy::Rebase => PullStrategy::Rebase,
+            amux_config::PullStrategy::FfOnly => PullStrategy::FfOnly,
+        };
+        let response = self
+            .client
+            .pull_worktree(&repo_id, &branch, strategy, stash_first)
+            .await?;
+
+        match PullOutcome::try_from(response.outcome) {
+            Ok(PullOutcome::Dirty) => {
+                self.status_message = None;
+                self.save_focus();
+                self.input_mode = InputMode::ConfirmPullStash { repo_id, branch };
+                return Ok(());
+            }
+            Ok(PullOutcome::Conflicts) => {
+                self.error_message = Some(format!(
+                    "{} ({} conflicted, resolve and commit)",
+                    response.message,
+                    response.conflicted_files.len()
+                ));
+                self.show_conflicts().await?;
+            }
+            Ok(PullOutcome::Updated) | Ok(PullOutcome::UpToDate) => {
                 self.status_message = Some(response.message);
-                // Refresh git status after pull
-                self.load_git_status().await?;
-            } else {
+            }
+            _ => {
+                self.status_message = None;
                 self.error_message = Some(response.message);
             }
         }
+
+        // Refresh git status and ahead/behind indicators after pull
+        self.load_git_status().await?;
+        self.refresh_worktree_status().await?;
+        Ok(())
+    }
+
+    /// Get the stash under the git panel cursor
+    pub fn current_git_stash(&self) -> Option<&StashEntry> {
+        match self.current_git_panel_item() {
+            GitPanelItem::Stash(idx) => self.git()?.stashes.get(idx),
+            _ => None,
+        }
+    }
+
+    /// Open the stash message prompt for the current worktree
+    pub fn start_stash_save(&mut self) {
+        let ids = self
+            .current_repo()
+            .map(|r| r.info.id.clone())
+            .zip(self.current_worktree().map(|w| w.branch.clone()));
+        let Some((repo_id, branch)) = ids else {
+            return;
+        };
+        if self.git().is_some_and(|g| g.files.is_empty()) {
+            self.status_message = Some(format!("Nothing to stash in {}", branch));
+            return;
+        }
+
+        self.save_focus();
+        self.input_mode = InputMode::StashMessage { repo_id, branch };
+        self.text_input.clear();
+    }
+
+    /// Stash all local changes of the worktree, untracked files included
+    ///
+    /// An empty message lets git generate the usual "WIP on <branch>" one.
+    pub async fn stash_save(&mut self) -> Result<()> {
+        let (repo_id, branch) = match &self.input_mode {
+            InputMode::StashMessage { repo_id, branch } => (repo_id.clone(), branch.clone()),
+            _ => return Ok(()),
+        };
+
+        let message = self.text_input.trim();
+        self.input_mode = InputMode::Normal;
+        self.text_input.clear();
+        self.restore_focus();
+
+        match self
+            .client
+            .stash_save(&repo_id, &branch, &message, true)
+            .await
+        {
+            Ok(entry) => {
+                self.status_message = Some(format!("Stashed: {}", entry.message));
+            }
+            Err(ClientError::Rpc(status)) => {
+                self.status_message = None;
+                self.error_message = Some(format!("Cannot stash: {}", status.message()));
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        self.load_git_status().await?;
+        self.refresh_worktree_status().await?;
+        Ok(())
+    }
+
+    /// Pop the stash under the cursor, or the newest stash of the worktree
+    ///
+    /// A stash that doesn't apply cleanly is kept, and its conflicting files
+    /// are shown in the diff view.
+    pub async fn stash_pop(&mut self) -> Result<()> {
+        let ids = self
+            .current_repo()
+            .map(|r| r.info.id.clone())
+            .zip(self.current_worktree().map(|w| w.branch.clone()));
+        let Some((repo_id, branch)) = ids else {
+            return Ok(());
+        };
+        let index = self
+            .current_git_stash()
+            .or_else(|| self.git().and_then(|g| g.stashes.first()))
+            .map(|s| s.index);
+        let Some(index) = index else {
+            self.status_message = Some(format!("No stashes for {}", branch));
+            return Ok(());
+        };
+
+        match self.client.stash_pop(&repo_id, &branch, index).await {
+            Ok(response) if response.success => {
+                self.status_message = Some(response.message);
+            }
+            Ok(response) => {
+                self.status_message = None;
+                self.error_message = Some(format!(
+                    "{}: {}",
+                    response.message,
+                    response.conflicted_files.join(", ")
+                ));
+                self.show_conflicts().await?;
+            }
+            Err(ClientError::Rpc(status)) => {
+                self.status_message = None;
+                self.error_message = Some(format!("Cannot pop stash: {}", status.message()));
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        self.load_git_status().await?;
+        self.refresh_worktree_status().await?;
+        Ok(())
+    }
+
+    /// Show the changes saved in the stash under the cursor
+    pub async fn view_stash_diff(&mut self) -> Result<()> {
+        let Some(stash) = self.current_git_stash().cloned() else {
+            return Ok(());
+        };
+
+        self.status_message = Some(format!("Stash: {}", stash.message));
+        if let Some(diff) = self.diff_mut() {
+            diff.revision = Some(stash.oid);
+        }
+        if self.right_panel_view != RightPanelView::Diff {
+            self.save_focus();
+        }
+        self.right_panel_view = RightPanelView::Diff;
+        self.focus = Focus::DiffFiles;
+        self.load_diff_files().await
+    }
+
+    /// Open the quick commit-and-push prompt for the current worktree
+    ///
+    /// The message defaults to the first open TODO title, then the name of
+    /// the worktree's session.
+    pub async fn start_quick_ship(&mut self) -> Result<()> {
+        let ids = self
+            .current_repo()
+            .map(|r| r.info.id.clone())
+            .zip(self.current_worktree().map(|w| w.branch.clone()));
+        let Some((repo_id, branch)) = ids else {
+            self.error_message = Some("No worktree selected".to_string());
+            return Ok(());
+        };
+
+        let conflicts = self.client.list_conflicts(&repo_id, &branch).await?;
+        if !conflicts.is_empty() {
+            self.error_message = Some(format!(
+                "Resolve {} conflicted file(s) before committing",
+                conflicts.len()
+            ));
+            return Ok(());
+        }
+
+        let todo_title = self
+            .client
+            .list_todos(&repo_id, false)
+            .await
+            .ok()
+            .and_then(|todos| {
+                todos
+                    .into_iter()
+                    .filter(|t| !t.completed && t.parent_id.is_none())
+                    .min_by_key(|t| t.order)
+            })
+            .map(|t| t.title);
+        let session_name = || {
+            let sessions: Vec<_> = self
+                .sessions()
+                .iter()
+                .filter(|s| s.branch == branch)
+                .collect();
+            sessions
+                .iter()
+                .find(|s| self.terminal.active_session_id.as_deref() == Some(s.id.as_str()))
+                .or(sessions.first())
+                .map(|s| s.name.clone())
+        };
+        let message = todo_title.or_else(session_name).unwrap_or_default();
+
+        self.save_focus();
+        self.input_mode = InputMode::QuickShip { repo_id, branch };
+        self.text_input.set_content(message);
+        Ok(())
+    }
+
+    /// Submit the quick commit-and-push prompt
+    pub async fn submit_quick_ship(&mut self) -> Result<()> {
+        let (repo_id, branch) = match &self.input_mode {
+            InputMode::QuickShip { repo_id, branch } => (repo_id.clone(), branch.clone()),
+            _ => return Ok(()),
+        };
+
+        let message = self.text_input.trim();
+        self.input_mode = InputMode::Normal;
+        self.text_input.clear();
+        self.restore_focus();
+
+        if message.is_empty() {
+            self.error_message = Some("Commit message cannot be empty".to_string());
+            return Ok(());
+        }
+
+        let response = self
+            .client
+            .commit_and_push(&repo_id, &branch, &message, None)
+            .await?;
+
+        if response.success {
+            self.status_message = Some(format!(
+                "Committed {:.7} and pushed {}",
+                response.commit_id, branch
+            ));
+        } else if let Some(step) = response.steps.iter().find(|s| !s.success) {
+            let step_name = match PipelineStep::try_from(step.step) {
+                Ok(PipelineStep::Stage) => "Stage",
+                Ok(PipelineStep::Commit) => "Commit",
+                Ok(PipelineStep::Push) => "Push",
+                _ => "Ship",
+            };
+            let reason = match StepFailure::try_from(step.failure) {
+                Ok(StepFailure::Auth) => " (auth)",
+                Ok(StepFailure::NonFastForward) => " (non-fast-forward, pull first)",
+                Ok(StepFailure::Hook) => " (hook)",
+                Ok(StepFailure::Conflicts) => " (resolve conflicts first)",
+                _ => "",
+            };
+            self.error_message = Some(format!("{} failed{}: {}", step_name, reason, step.message));
+        }
+
+        // Refresh regardless: the commit may have landed even if push failed
+        self.load_git_status().await?;
+        self.refresh_worktree_status().await?;
         Ok(())
     }
 }
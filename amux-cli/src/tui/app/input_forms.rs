@@ -1,16 +1,32 @@
 //! Input form handling
 
+use super::super::branch_name::{self, BranchNameError};
+use super::super::persistence;
+use super::super::sidebar_order;
 use super::super::state::{
-    AsyncAction, DeleteTarget, ExitCleanupAction, Focus, InputMode, SavedFocusState, SidebarItem,
+    AsyncAction, DeleteTarget, ExitCleanupAction, Focus, InputMode, PrefixMode, ProviderChoice,
+    SavedFocusState, SidebarItem, UndoEntry,
 };
 use super::super::widgets::VirtualList;
 use super::super::App;
 use crate::error::TuiError;
-use amux_config::{DEFAULT_SCROLLBACK, DEFAULT_TERMINAL_COLS, DEFAULT_TERMINAL_ROWS};
+use amux_config::{
+    Confirmation, SidebarSort, DEFAULT_SCROLLBACK, DEFAULT_TERMINAL_COLS, DEFAULT_TERMINAL_ROWS,
+};
+use amux_proto::daemon::{ProviderInfo, SessionInfo};
 use std::sync::{Arc, Mutex};
 
 type Result<T> = std::result::Result<T, TuiError>;
 
+/// Index of a provider's default model in its model list
+fn default_model_index(provider: &ProviderInfo) -> usize {
+    provider
+        .models
+        .iter()
+        .position(|m| *m == provider.default_model)
+        .unwrap_or(0)
+}
+
 impl App {
     /// Save current focus before opening a dialog/popup
     pub fn save_focus(&mut self) {
@@ -46,12 +62,34 @@ impl App {
     pub fn cancel_input(&mut self) {
         self.input_mode = InputMode::Normal;
         self.text_input.clear();
+        self.completion.close();
         self.status_message = None;
 
         // Restore focus when canceling
         self.restore_focus();
     }
 
+    /// Wait for a command key after the prefix key
+    pub fn enter_prefix_mode(&mut self) {
+        self.prefix_mode = PrefixMode::WaitingForCommand;
+        self.prefix_entered_at = Some(std::time::Instant::now());
+    }
+
+    /// Leave prefix mode once `prefix_timeout_ms` passes without a command key
+    pub fn expire_prefix_mode(&mut self) {
+        let timeout = self.config.options.prefix_timeout_ms;
+        if self.prefix_mode != PrefixMode::WaitingForCommand || timeout == 0 {
+            return;
+        }
+        if self
+            .prefix_entered_at
+            .is_some_and(|at| at.elapsed().as_millis() >= timeout as u128)
+        {
+            self.prefix_mode = PrefixMode::None;
+            self.prefix_entered_at = None;
+        }
+    }
+
     /// Start add worktree mode
     pub fn start_add_worktree(&mut self) {
         // Get current selected branch as base (None = use HEAD)
@@ -70,26 +108,46 @@ impl App {
             branch,
             providers: vec![],
             selected_index: 0,
+            model_index: 0,
             loading: true,
         };
     }
 
     /// Fetch available providers from daemon
-    pub async fn fetch_providers(&mut self, _repo_id: &str, _branch: &str) -> Result<()> {
+    ///
+    /// The provider and model last picked for the repo are selected if the
+    /// daemon still offers them.
+    pub async fn fetch_providers(&mut self, repo_id: &str, _branch: &str) -> Result<()> {
         match self.client.list_providers().await {
             Ok(provider_infos) => {
-                let provider_names: Vec<String> =
-                    provider_infos.iter().map(|p| p.name.clone()).collect();
-                let provider_count = provider_names.len();
+                let provider_count = provider_infos.len();
+                let (provider_idx, model_idx) =
+                    match self.provider_choices.get(repo_id).and_then(|choice| {
+                        let p_idx = provider_infos
+                            .iter()
+                            .position(|p| p.name == choice.provider)?;
+                        let m_idx = provider_infos[p_idx]
+                            .models
+                            .iter()
+                            .position(|m| *m == choice.model)?;
+                        Some((p_idx, m_idx))
+                    }) {
+                        Some(indices) => indices,
+                        None => (0, provider_infos.first().map_or(0, default_model_index)),
+                    };
 
                 // Update the input mode with fetched providers
                 if let InputMode::SelectProvider {
                     ref mut providers,
+                    ref mut selected_index,
+                    ref mut model_index,
                     ref mut loading,
                     ..
                 } = &mut self.input_mode
                 {
-                    *providers = provider_names;
+                    *providers = provider_infos;
+                    *selected_index = provider_idx;
+                    *model_index = model_idx;
                     *loading = false;
 
                     if providers.is_empty() {
@@ -110,128 +168,137 @@ impl App {
         Ok(())
     }
 
+    /// Move the provider selection, starting at the new provider's default model
+    pub fn move_provider_selection(&mut self, down: bool) {
+        if let InputMode::SelectProvider {
+            providers,
+            selected_index,
+            model_index,
+            loading: false,
+            ..
+        } = &mut self.input_mode
+        {
+            let next = if down {
+                (*selected_index + 1).min(providers.len().saturating_sub(1))
+            } else {
+                selected_index.saturating_sub(1)
+            };
+            if next != *selected_index {
+                *selected_index = next;
+                *model_index = default_model_index(&providers[next]);
+            }
+        }
+    }
+
+    /// Cycle through the selected provider's models
+    pub fn cycle_provider_model(&mut self, forward: bool) {
+        if let InputMode::SelectProvider {
+            providers,
+            selected_index,
+            model_index,
+            loading: false,
+            ..
+        } = &mut self.input_mode
+        {
+            let count = providers.get(*selected_index).map_or(0, |p| p.models.len());
+            if count > 0 {
+                *model_index = if forward {
+                    (*model_index + 1) % count
+                } else {
+                    (*model_index + count - 1) % count
+                };
+            }
+        }
+    }
+
+    /// Provider and model selected in the provider picker
+    pub fn selected_provider_choice(&self) -> Option<ProviderChoice> {
+        let InputMode::SelectProvider {
+            providers,
+            selected_index,
+            model_index,
+            loading: false,
+            ..
+        } = &self.input_mode
+        else {
+            return None;
+        };
+        let provider = providers.get(*selected_index)?;
+        Some(ProviderChoice {
+            provider: provider.name.clone(),
+            model: provider.models.get(*model_index)?.clone(),
+        })
+    }
+
     /// Submit provider selection and create session
     pub async fn submit_provider_selection(&mut self) -> Result<()> {
-        let (repo_id, branch, provider) = match &self.input_mode {
+        let (repo_id, branch) = match &self.input_mode {
             InputMode::SelectProvider {
                 repo_id,
                 branch,
-                providers,
-                selected_index,
                 loading,
+                ..
             } => {
                 if *loading {
                     return Ok(()); // Still loading, ignore submit
                 }
-
-                if providers.is_empty() {
-                    self.status_message = Some("No provider selected".to_string());
-                    return Ok(());
-                }
-
-                let provider = providers.get(*selected_index).cloned().unwrap_or_else(|| {
-                    // Should not happen if providers is not empty
-                    providers.first().cloned().unwrap_or_default()
-                });
-
-                if provider.is_empty() {
-                    self.status_message = Some("Invalid provider selected".to_string());
-                    return Ok(());
-                }
-
-                (repo_id.clone(), branch.clone(), provider)
+                (repo_id.clone(), branch.clone())
             }
             _ => return Ok(()),
         };
+        let Some(choice) = self.selected_provider_choice() else {
+            self.status_message = Some("No provider selected".to_string());
+            return Ok(());
+        };
 
         self.input_mode = InputMode::Normal;
         self.restore_focus();
 
         // Update status message to show which provider was selected
-        self.status_message = Some(format!("Creating session with {}...", provider));
-
-        // Get terminal size for PTY creation
-        let (inner_rows, inner_cols) = self.get_inner_terminal_size();
+        self.status_message = Some(format!(
+            "Creating session with {} ({})...",
+            choice.provider, choice.model
+        ));
 
-        // Create session with selected provider
-        match self
-            .client
-            .create_session(
-                &repo_id,
-                &branch,
-                None,
-                None,
-                None,
-                None,
-                Some(&provider),
-                Some(inner_rows as u32),
-                Some(inner_cols as u32),
-            )
+        self.create_and_enter_session(&repo_id, &branch, None, Some(&choice), None, &[])
             .await
-        {
-            Ok(session) => {
-                // Refresh sessions for this worktree
-                let b_idx = self.branch_idx();
-                self.load_worktree_sessions(b_idx).await?;
-                // Expand worktree
-                if let Some(repo) = self.current_repo_mut() {
-                    repo.expanded_worktrees.insert(b_idx);
-                }
-                self.update_sidebar_total_items();
-
-                // Update sidebar cursor to point to the new session
-                if let Some(repo) = self.current_repo_mut() {
-                    let session_idx = repo
-                        .sessions_by_worktree
-                        .get(&b_idx)
-                        .and_then(|sessions| sessions.iter().position(|s| s.id == session.id));
-
-                    if let Some(s_idx) = session_idx {
-                        let mut cursor_pos = 0;
-                        for wt_idx in 0..b_idx {
-                            cursor_pos += 1;
-                            if repo.expanded_worktrees.contains(&wt_idx) {
-                                if let Some(sessions) = repo.sessions_by_worktree.get(&wt_idx) {
-                                    cursor_pos += sessions.len();
-                                }
-                            }
-                        }
-                        cursor_pos += 1;
-                        cursor_pos += s_idx;
-                        repo.sidebar_cursor = cursor_pos;
-                    }
-                }
-
-                // Disconnect current stream
-                self.disconnect_stream();
+    }
 
-                // Save current parser if there was an active session
-                if let Some(old_id) = &self.terminal.active_session_id {
-                    self.terminal
-                        .session_parsers
-                        .insert(old_id.clone(), self.terminal.parser.clone());
+    /// Keep the provider and model picked for a repo as its default (or
+    /// forget it, with `None`)
+    fn remember_provider_choice(&mut self, repo_id: &str, choice: Option<ProviderChoice>) {
+        match choice {
+            Some(choice) => {
+                if self.provider_choices.get(repo_id) == Some(&choice) {
+                    return;
                 }
-
-                // Create new parser for the new session
-                self.terminal.parser = Arc::new(Mutex::new(vt100::Parser::new(
-                    DEFAULT_TERMINAL_ROWS,
-                    DEFAULT_TERMINAL_COLS,
-                    DEFAULT_SCROLLBACK,
-                )));
-                self.terminal
-                    .session_parsers
-                    .insert(session.id.clone(), self.terminal.parser.clone());
-                self.terminal.scroll_offset = 0;
-                self.terminal.active_session_id = Some(session.id.clone());
-
-                self.enter_terminal().await?;
+                self.provider_choices.insert(repo_id.to_string(), choice);
             }
-            Err(e) => {
-                self.error_message = Some(e.to_string());
+            None => {
+                if self.provider_choices.remove(repo_id).is_none() {
+                    return;
+                }
             }
         }
+        if let Err(e) = persistence::save_provider_choices(&self.provider_choices) {
+            tracing::warn!("Failed to save provider choices: {}", e);
+        }
+    }
 
-        Ok(())
+    /// Show the details of the session under the sidebar cursor
+    pub fn show_session_info(&mut self) {
+        let SidebarItem::Session(wt_idx, s_idx) = self.current_sidebar_item() else {
+            return;
+        };
+        let Some(session_id) = self
+            .current_repo()
+            .and_then(|repo| repo.sessions_by_worktree.get(&wt_idx)?.get(s_idx))
+            .map(|s| s.id.clone())
+        else {
+            return;
+        };
+        self.save_focus();
+        self.input_mode = InputMode::SessionInfo(session_id);
     }
 
     /// Start rename session mode
@@ -294,7 +361,7 @@ impl App {
             _ => return Ok(()),
         };
 
-        let new_name = self.text_input.trim().to_string();
+        let new_name = self.text_input.trim();
         self.input_mode = InputMode::Normal;
         self.text_input.clear();
         self.restore_focus();
@@ -320,6 +387,272 @@ impl App {
         Ok(())
     }
 
+    /// Start renaming the branch of the selected worktree
+    pub fn start_rename_branch(&mut self) {
+        let wt_idx = match self.current_sidebar_item() {
+            SidebarItem::Worktree(idx)
+            | SidebarItem::Session(idx, _)
+            | SidebarItem::LoadMore(idx) => idx,
+            SidebarItem::None => {
+                self.error_message = Some("No worktree selected".to_string());
+                return;
+            }
+        };
+        let Some(worktree) = self.worktrees().get(wt_idx).cloned() else {
+            return;
+        };
+        if worktree.is_main {
+            self.error_message = Some("Cannot rename the main worktree's branch".to_string());
+            return;
+        }
+
+        self.save_focus();
+        self.input_mode = InputMode::RenameBranch {
+            branch: worktree.branch.clone(),
+        };
+        self.text_input.set_content(worktree.branch);
+    }
+
+    /// Submit rename branch
+    pub async fn submit_rename_branch(&mut self) -> Result<()> {
+        let old_branch = match &self.input_mode {
+            InputMode::RenameBranch { branch } => branch.clone(),
+            _ => return Ok(()),
+        };
+
+        let new_branch = self.text_input.trim();
+        self.input_mode = InputMode::Normal;
+        self.text_input.clear();
+        self.restore_focus();
+
+        if new_branch.is_empty() {
+            self.error_message = Some("Branch name cannot be empty".to_string());
+            return Ok(());
+        }
+        if new_branch == old_branch {
+            return Ok(());
+        }
+        let Some(repo_id) = self.current_repo_id.clone() else {
+            return Ok(());
+        };
+
+        match self
+            .client
+            .rename_branch(&repo_id, &old_branch, &new_branch)
+            .await
+        {
+            Ok(_) => {
+                self.status_message = Some(format!("Renamed {} to {}", old_branch, new_branch));
+                // Worktree paths and session branches changed on the daemon
+                self.refresh_branches().await?;
+                self.refresh_sessions().await?;
+                self.load_worktree_sessions(self.branch_idx()).await?;
+            }
+            Err(e) => {
+                self.error_message = Some(e.to_string());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Default delete action set on the worktree at `wt_idx`, if any
+    pub fn worktree_delete_action(&self, wt_idx: usize) -> Option<ExitCleanupAction> {
+        self.worktrees()
+            .get(wt_idx)
+            .and_then(|wt| ExitCleanupAction::from_proto(wt.default_delete_action))
+    }
+
+    /// Cycle the selected worktree's default delete action: unset, Destroy, Stop
+    pub async fn cycle_worktree_delete_action(&mut self) -> Result<()> {
+        let wt_idx = match self.current_sidebar_item() {
+            SidebarItem::Worktree(idx)
+            | SidebarItem::Session(idx, _)
+            | SidebarItem::LoadMore(idx) => idx,
+            SidebarItem::None => {
+                self.error_message = Some("No worktree selected".to_string());
+                return Ok(());
+            }
+        };
+        let (Some(repo_id), Some(branch)) = (
+            self.current_repo_id.clone(),
+            self.worktrees().get(wt_idx).map(|wt| wt.branch.clone()),
+        ) else {
+            return Ok(());
+        };
+
+        let next = match self.worktree_delete_action(wt_idx) {
+            None => Some(ExitCleanupAction::Destroy),
+            Some(ExitCleanupAction::Destroy) => Some(ExitCleanupAction::Stop),
+            Some(ExitCleanupAction::Stop) => None,
+        };
+
+        match self
+            .client
+            .set_worktree_delete_action(&repo_id, &branch, next.map(ExitCleanupAction::to_proto))
+            .await
+        {
+            Ok(()) => {
+                if let Some(wt) = self
+                    .current_repo_mut()
+                    .and_then(|r| r.worktrees.get_mut(wt_idx))
+                {
+                    wt.default_delete_action = next.map(|a| a.to_proto() as i32);
+                }
+                self.status_message = Some(match next {
+                    Some(action) => format!("Deleting sessions in {}: {}", branch, action.label()),
+                    None => format!(
+                        "Deleting sessions in {}: {} (global default)",
+                        branch,
+                        ExitCleanupAction::default().label()
+                    ),
+                });
+            }
+            Err(e) => {
+                self.error_message = Some(e.to_string());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pin or unpin the session under the sidebar cursor
+    pub async fn toggle_pin_session(&mut self) -> Result<()> {
+        let SidebarItem::Session(wt_idx, s_idx) = self.current_sidebar_item() else {
+            self.error_message = Some("No session selected".to_string());
+            return Ok(());
+        };
+        let Some(session) = self
+            .current_repo()
+            .and_then(|r| r.sessions_by_worktree.get(&wt_idx))
+            .and_then(|sessions| sessions.get(s_idx))
+            .cloned()
+        else {
+            return Ok(());
+        };
+
+        let pinned = !session.pinned;
+        match self
+            .client
+            .update_session_meta(&session.id, Some(pinned), None)
+            .await
+        {
+            Ok(_) => {
+                self.status_message = Some(format!(
+                    "{} {}",
+                    if pinned { "Pinned" } else { "Unpinned" },
+                    session.name
+                ));
+                self.reload_session_order(wt_idx, &session.id).await?;
+            }
+            Err(e) => {
+                self.error_message = Some(e.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Move the session under the sidebar cursor up or down within its worktree
+    ///
+    /// Pinned sessions stay above unpinned ones. Sorted sidebars can't be
+    /// reordered by hand.
+    pub async fn move_session(&mut self, down: bool) -> Result<()> {
+        let SidebarItem::Session(wt_idx, s_idx) = self.current_sidebar_item() else {
+            return Ok(());
+        };
+        if self.config.options.sidebar_sort != SidebarSort::None {
+            self.error_message =
+                Some("Sessions are sorted; set sidebar_sort to none to reorder them".to_string());
+            return Ok(());
+        }
+        let Some(sessions) = self
+            .current_repo()
+            .and_then(|r| r.sessions_by_worktree.get(&wt_idx))
+        else {
+            return Ok(());
+        };
+        let neighbor_idx = if down {
+            s_idx + 1
+        } else if let Some(idx) = s_idx.checked_sub(1) {
+            idx
+        } else {
+            return Ok(());
+        };
+        let (Some(session), Some(neighbor)) = (sessions.get(s_idx), sessions.get(neighbor_idx))
+        else {
+            return Ok(());
+        };
+        if session.pinned != neighbor.pinned {
+            return Ok(());
+        }
+        let (session_id, sort_key) = (session.id.clone(), neighbor.sort_key);
+
+        match self
+            .client
+            .update_session_meta(&session_id, None, Some(sort_key))
+            .await
+        {
+            Ok(_) => self.reload_session_order(wt_idx, &session_id).await?,
+            Err(e) => {
+                self.error_message = Some(e.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Reload a worktree's sessions, keeping the sidebar cursor on `session_id`
+    async fn reload_session_order(&mut self, wt_idx: usize, session_id: &str) -> Result<()> {
+        self.load_worktree_sessions(wt_idx).await?;
+
+        let new_idx = self
+            .current_repo()
+            .and_then(|r| r.sessions_by_worktree.get(&wt_idx))
+            .and_then(|sessions| sessions.iter().position(|s| s.id == session_id));
+        self.move_sidebar_cursor_to_session(wt_idx, session_id);
+
+        if wt_idx == self.branch_idx() {
+            if let Some(new_idx) = new_idx {
+                self.set_session_idx(new_idx);
+            }
+            self.refresh_sessions().await?;
+        }
+        Ok(())
+    }
+
+    /// Names of the branches without a worktree
+    fn available_branch_names(&self) -> Vec<String> {
+        self.available_branches()
+            .iter()
+            .map(|b| b.branch.clone())
+            .collect()
+    }
+
+    /// Offer the branches without a worktree starting with the typed name
+    pub fn complete_branch_name(&mut self) {
+        let names = self.available_branch_names();
+        if !self.completion.open(&mut self.text_input, 0, names) {
+            self.status_message = Some("No matching branch".to_string());
+        }
+    }
+
+    /// Show the branches without a worktree fuzzily matching the typed name
+    pub fn suggest_branch_names(&mut self) {
+        if self.text_input.is_empty() {
+            self.completion.close();
+            return;
+        }
+        let names = self.available_branch_names();
+        self.completion.suggest(&self.text_input, 0, names);
+    }
+
+    /// Why the typed new branch name is invalid (None while nothing is typed)
+    pub fn typed_branch_error(&self) -> Option<BranchNameError> {
+        if self.text_input.is_empty() {
+            return None;
+        }
+        branch_name::validate(&self.text_input.content()).err()
+    }
+
     /// Submit add worktree (create worktree for selected or new branch)
     pub async fn submit_add_worktree(&mut self) -> Result<()> {
         // Get base_branch from input mode before clearing
@@ -332,7 +665,7 @@ impl App {
         // Only use base_branch when creating a NEW branch (typing in input)
         let (branch_name, use_base) = if !self.text_input.is_empty() {
             // Creating new branch - use base_branch
-            (self.text_input.trim().to_string(), true)
+            (self.text_input.trim(), true)
         } else if let Some(branch) = self.available_branches().get(self.add_worktree_idx()) {
             // Selecting existing branch - no need for base
             (branch.branch.clone(), false)
@@ -374,8 +707,9 @@ impl App {
                     .position(|w| w.branch == branch_name)
                 {
                     self.set_branch_idx(idx);
-                    if let Some(repo) = self.current_repo_mut() {
-                        repo.sidebar_cursor = idx;
+                    let row = self.sidebar_row_of(&SidebarItem::Worktree(idx));
+                    if let (Some(row), Some(repo)) = (row, self.current_repo_mut()) {
+                        repo.sidebar_cursor = row;
                     }
                     self.refresh_sessions().await?;
                 }
@@ -394,7 +728,7 @@ impl App {
             return Ok(());
         }
 
-        let branch_name = self.text_input.trim().to_string();
+        let branch_name = self.text_input.trim();
         self.input_mode = InputMode::Normal;
         self.text_input.clear();
         self.status_message = None;
@@ -417,6 +751,9 @@ impl App {
                     None,
                     None,
                     None,
+                    None,
+                    &[],
+                    None,
                     Some(inner_rows as u32),
                     Some(inner_cols as u32),
                 )
@@ -491,7 +828,7 @@ impl App {
 
         // Get name from input (None if empty for default name)
         let name = {
-            let trimmed = self.text_input.trim().to_string();
+            let trimmed = self.text_input.trim();
             if trimmed.is_empty() {
                 None
             } else {
@@ -503,81 +840,282 @@ impl App {
         self.text_input.clear();
         self.restore_focus();
 
-        // Get terminal size for PTY creation
-        let (inner_rows, inner_cols) = self.get_inner_terminal_size();
+        self.create_and_enter_session(
+            &repo_id,
+            &branch,
+            name.as_deref(),
+            provider.as_ref(),
+            None,
+            &[],
+        )
+        .await
+    }
 
-        // Create session with optional name and provider
+    /// Ask for a system prompt for the session being set up
+    ///
+    /// Continues from the provider picker or the session name prompt, keeping
+    /// the choices made there.
+    pub fn start_session_system_prompt(&mut self) {
+        let (repo_id, branch, name, provider) = match &self.input_mode {
+            InputMode::SelectProvider {
+                repo_id, branch, ..
+            } => (
+                repo_id.clone(),
+                branch.clone(),
+                None,
+                self.selected_provider_choice(),
+            ),
+            InputMode::CreateSessionInput {
+                repo_id,
+                branch,
+                provider,
+            } => {
+                let name = self.text_input.trim();
+                (
+                    repo_id.clone(),
+                    branch.clone(),
+                    (!name.is_empty()).then_some(name),
+                    provider.clone(),
+                )
+            }
+            _ => return,
+        };
+
+        self.input_mode = InputMode::SessionSystemPrompt {
+            repo_id,
+            branch,
+            name,
+            provider,
+        };
+        self.text_input.clear();
+    }
+
+    /// Create the session with the entered system prompt (none if left empty)
+    pub async fn submit_session_system_prompt(&mut self) -> Result<()> {
+        let InputMode::SessionSystemPrompt {
+            repo_id,
+            branch,
+            name,
+            provider,
+        } = std::mem::replace(&mut self.input_mode, InputMode::Normal)
+        else {
+            return Ok(());
+        };
+        let system_prompt = self.text_input.trim();
+        self.text_input.clear();
+        self.restore_focus();
+
+        self.create_and_enter_session(
+            &repo_id,
+            &branch,
+            name.as_deref(),
+            provider.as_ref(),
+            (!system_prompt.is_empty()).then_some(system_prompt.as_str()),
+            &[],
+        )
+        .await
+    }
+
+    /// Open the prompt for a command line to run as a session
+    pub fn start_run_command(&mut self) {
+        let (repo_id, branch) = match (
+            self.current_repo().map(|r| r.info.id.clone()),
+            self.current_worktree().map(|w| w.branch.clone()),
+        ) {
+            (Some(repo_id), Some(branch)) => (repo_id, branch),
+            _ => {
+                self.error_message = Some("No worktree selected".to_string());
+                return;
+            }
+        };
+
+        self.save_focus();
+        self.input_mode = InputMode::RunCommand { repo_id, branch };
+        self.text_input.clear();
+    }
+
+    /// Split the entered command line like a shell would and run it as a session
+    pub async fn submit_run_command(&mut self) -> Result<()> {
+        let InputMode::RunCommand { repo_id, branch } = &self.input_mode else {
+            return Ok(());
+        };
+        let (repo_id, branch) = (repo_id.clone(), branch.clone());
+
+        let argv = match shell_words::split(&self.text_input.trim()) {
+            Ok(argv) if !argv.is_empty() => argv,
+            Ok(_) => {
+                self.error_message = Some("Command cannot be empty".to_string());
+                return Ok(());
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Invalid command: {}", e));
+                return Ok(());
+            }
+        };
+
+        self.input_mode = InputMode::Normal;
+        self.text_input.clear();
+        self.restore_focus();
+
+        self.create_and_enter_session(&repo_id, &branch, None, None, None, &argv)
+            .await
+    }
+
+    /// Open the prompt for a subdirectory of the worktree to start a new shell in
+    pub fn start_shell_directory(&mut self) {
+        let (repo_id, branch) = match (
+            self.current_repo().map(|r| r.info.id.clone()),
+            self.current_worktree().map(|w| w.branch.clone()),
+        ) {
+            (Some(repo_id), Some(branch)) => (repo_id, branch),
+            _ => {
+                self.error_message = Some("No worktree selected".to_string());
+                return;
+            }
+        };
+
+        self.save_focus();
+        self.input_mode = InputMode::ShellDirectory { repo_id, branch };
+        self.text_input.clear();
+        self.completion.close();
+    }
+
+    /// Offer the directories matching the last component of the entered path
+    ///
+    /// Hidden directories are offered once a `.` is typed.
+    pub async fn complete_shell_directory(&mut self) -> Result<()> {
+        let InputMode::ShellDirectory { repo_id, branch } = &self.input_mode else {
+            return Ok(());
+        };
+        let (repo_id, branch) = (repo_id.clone(), branch.clone());
+
+        let input = self.text_input.content();
+        let start = input.rfind('/').map_or(0, |i| i + 1);
+        let (parent, partial) = input.split_at(start);
+        let entries = match self
+            .client
+            .list_directory(&repo_id, &branch, parent, true)
+            .await
+        {
+            Ok(entries) => entries,
+            Err(e) => {
+                self.error_message = Some(format!("Failed to list directories: {}", e));
+                return Ok(());
+            }
+        };
+        let dirs = entries
+            .into_iter()
+            .filter(|entry| entry.is_dir)
+            .filter(|entry| partial.starts_with('.') || !entry.name.starts_with('.'))
+            .map(|entry| format!("{}/", entry.name))
+            .collect();
+
+        if !self.completion.open(&mut self.text_input, start, dirs) {
+            self.status_message = Some("No matching directory".to_string());
+        }
+        Ok(())
+    }
+
+    /// Create a shell session in the entered directory and enter it
+    pub async fn submit_shell_directory(&mut self) -> Result<()> {
+        let InputMode::ShellDirectory {
+            repo_id, branch, ..
+        } = &self.input_mode
+        else {
+            return Ok(());
+        };
+        let (repo_id, branch) = (repo_id.clone(), branch.clone());
+        let dir = self.text_input.trim().trim_end_matches('/').to_string();
+
+        self.input_mode = InputMode::Normal;
+        self.text_input.clear();
+        self.completion.close();
+        self.restore_focus();
+
+        let (inner_rows, inner_cols) = self.get_inner_terminal_size();
+        let name = if dir.is_empty() { "shell" } else { &dir };
         match self
             .client
             .create_session(
                 &repo_id,
                 &branch,
-                name.as_deref(),
+                Some(name),
+                Some(true),
+                None,
                 None,
                 None,
                 None,
-                provider.as_deref(),
+                &[],
+                Some(&dir),
                 Some(inner_rows as u32),
                 Some(inner_cols as u32),
             )
             .await
         {
-            Ok(session) => {
-                // Refresh sessions for this worktree
-                let b_idx = self.branch_idx();
-                self.load_worktree_sessions(b_idx).await?;
-                // Expand worktree
-                if let Some(repo) = self.current_repo_mut() {
-                    repo.expanded_worktrees.insert(b_idx);
-                }
-                self.update_sidebar_total_items();
-
-                // Update sidebar cursor to point to the new session
-                if let Some(repo) = self.current_repo_mut() {
-                    let session_idx = repo
-                        .sessions_by_worktree
-                        .get(&b_idx)
-                        .and_then(|sessions| sessions.iter().position(|s| s.id == session.id));
-
-                    if let Some(s_idx) = session_idx {
-                        let mut cursor_pos = 0;
-                        for wt_idx in 0..b_idx {
-                            cursor_pos += 1;
-                            if repo.expanded_worktrees.contains(&wt_idx) {
-                                if let Some(sessions) = repo.sessions_by_worktree.get(&wt_idx) {
-                                    cursor_pos += sessions.len();
-                                }
-                            }
-                        }
-                        cursor_pos += 1;
-                        cursor_pos += s_idx;
-                        repo.sidebar_cursor = cursor_pos;
-                    }
-                }
+            Ok(session) => self.enter_new_session(&session).await?,
+            Err(e) => {
+                self.error_message = Some(format!("Failed to create shell session: {}", e));
+            }
+        }
+        Ok(())
+    }
 
-                // Disconnect current stream
-                self.disconnect_stream();
+    /// Create a session in a worktree, select it and enter its terminal
+    ///
+    /// A provider picked here becomes the repo's default; without one the
+    /// remembered one is used, and forgotten if the daemon no longer has it.
+    async fn create_and_enter_session(
+        &mut self,
+        repo_id: &str,
+        branch: &str,
+        name: Option<&str>,
+        provider: Option<&ProviderChoice>,
+        system_prompt: Option<&str>,
+        command: &[String],
+    ) -> Result<()> {
+        // Get terminal size for PTY creation
+        let (inner_rows, inner_cols) = self.get_inner_terminal_size();
 
-                // Save current parser if there was an active session
-                if let Some(old_id) = &self.terminal.active_session_id {
-                    self.terminal
-                        .session_parsers
-                        .insert(old_id.clone(), self.terminal.parser.clone());
-                }
+        // The repo's last pick, or else the configured default provider
+        let remembered = match provider {
+            None if command.is_empty() => match self.provider_choices.get(repo_id).cloned() {
+                Some(choice) => Some(choice),
+                None => self.configured_provider().await,
+            },
+            _ => None,
+        };
+        let choice = provider.or(remembered.as_ref());
 
-                // Create new parser for the new session
-                self.terminal.parser = Arc::new(Mutex::new(vt100::Parser::new(
-                    DEFAULT_TERMINAL_ROWS,
-                    DEFAULT_TERMINAL_COLS,
-                    DEFAULT_SCROLLBACK,
-                )));
-                self.terminal
-                    .session_parsers
-                    .insert(session.id.clone(), self.terminal.parser.clone());
-                self.terminal.scroll_offset = 0;
-                self.terminal.active_session_id = Some(session.id.clone());
-
-                self.enter_terminal().await?;
+        // Create session with optional name and provider
+        match self
+            .client
+            .create_session(
+                repo_id,
+                branch,
+                name,
+                None,
+                choice.map(|c| c.model.as_str()),
+                None,
+                system_prompt,
+                choice.map(|c| c.provider.as_str()),
+                command,
+                None,
+                Some(inner_rows as u32),
+                Some(inner_cols as u32),
+            )
+            .await
+        {
+            Ok(session) => {
+                if provider.is_some() {
+                    self.remember_provider_choice(repo_id, provider.cloned());
+                }
+                self.enter_new_session(&session).await?
+            }
+            // The daemon rejects providers and models it doesn't have
+            Err(e) if remembered.is_some() && e.code() == Some(tonic::Code::InvalidArgument) => {
+                self.remember_provider_choice(repo_id, None);
+                self.error_message =
+                    Some(format!("{} (the repo's remembered provider was reset)", e));
             }
             Err(e) => {
                 self.error_message = Some(e.to_string());
@@ -587,108 +1125,79 @@ impl App {
         Ok(())
     }
 
+    /// Select a just-created session in the sidebar and enter its terminal
+    async fn enter_new_session(&mut self, session: &SessionInfo) -> Result<()> {
+        // Refresh sessions for this worktree
+        let b_idx = self.branch_idx();
+        self.load_worktree_sessions(b_idx).await?;
+        // Expand worktree
+        if let Some(repo) = self.current_repo_mut() {
+            repo.expanded_worktrees.insert(b_idx);
+        }
+        self.update_sidebar_total_items();
+
+        // Update sidebar cursor to point to the new session
+        self.move_sidebar_cursor_to_session(b_idx, &session.id);
+
+        // Disconnect current stream
+        self.disconnect_stream();
+
+        // Save current parser if there was an active session
+        if let Some(old_id) = &self.terminal.active_session_id {
+            self.terminal
+                .session_parsers
+                .insert(old_id.clone(), self.terminal.parser.clone());
+        }
+
+        // Create new parser for the new session
+        self.terminal.parser = Arc::new(Mutex::new(vt100::Parser::new(
+            DEFAULT_TERMINAL_ROWS,
+            DEFAULT_TERMINAL_COLS,
+            DEFAULT_SCROLLBACK,
+        )));
+        self.terminal
+            .session_parsers
+            .insert(session.id.clone(), self.terminal.parser.clone());
+        self.terminal.scroll_offset = 0;
+        self.terminal.active_session_id = Some(session.id.clone());
+
+        self.enter_terminal().await?;
+        Ok(())
+    }
+
     /// Create new session and enter interactive mode
+    ///
+    /// Uses the provider last picked for the repo, if any.
     pub async fn create_new(&mut self) -> Result<()> {
         match self.focus {
             Focus::Sidebar => {
                 // In tree view: create session for currently selected worktree
-                if let (Some(repo), Some(branch)) = (
-                    self.current_repo().map(|r| r.info.clone()),
-                    self.current_worktree().cloned(),
+                if let (Some(repo_id), Some(branch)) = (
+                    self.current_repo().map(|r| r.info.id.clone()),
+                    self.current_worktree().map(|w| w.branch.clone()),
                 ) {
-                    let (inner_rows, inner_cols) = self.get_inner_terminal_size();
-                    match self
-                        .client
-                        .create_session(
-                            &repo.id,
-                            &branch.branch,
-                            None,
-                            None,
-                            None,
-                            None,
-                            None,
-                            Some(inner_rows as u32),
-                            Some(inner_cols as u32),
-                        )
-                        .await
-                    {
-                        Ok(session) => {
-                            // Refresh sessions for this worktree
-                            let b_idx = self.branch_idx();
-                            self.load_worktree_sessions(b_idx).await?;
-                            // Expand worktree
-                            if let Some(repo) = self.current_repo_mut() {
-                                repo.expanded_worktrees.insert(b_idx);
-                            }
-                            self.update_sidebar_total_items();
-
-                            // Update sidebar cursor to point to the new session
-                            // Calculate position: worktree position + 1 + session index within worktree
-                            if let Some(repo) = self.current_repo_mut() {
-                                // Find the new session's index in sessions_by_worktree
-                                let session_idx =
-                                    repo.sessions_by_worktree.get(&b_idx).and_then(|sessions| {
-                                        sessions.iter().position(|s| s.id == session.id)
-                                    });
-
-                                if let Some(s_idx) = session_idx {
-                                    // Calculate cursor position
-                                    let mut cursor_pos = 0;
-                                    for wt_idx in 0..b_idx {
-                                        cursor_pos += 1; // worktree itself
-                                        if repo.expanded_worktrees.contains(&wt_idx) {
-                                            if let Some(sessions) =
-                                                repo.sessions_by_worktree.get(&wt_idx)
-                                            {
-                                                cursor_pos += sessions.len();
-                                            }
-                                        }
-                                    }
-                                    cursor_pos += 1; // current worktree
-                                    cursor_pos += s_idx; // session position within worktree
-
-                                    repo.sidebar_cursor = cursor_pos;
-                                }
-                            }
-
-                            // Disconnect current stream
-                            self.disconnect_stream();
-
-                            // Save current parser if there was an active session
-                            if let Some(old_id) = &self.terminal.active_session_id {
-                                self.terminal
-                                    .session_parsers
-                                    .insert(old_id.clone(), self.terminal.parser.clone());
-                            }
-
-                            // Create new parser for the new session
-                            self.terminal.parser = Arc::new(Mutex::new(vt100::Parser::new(
-                                DEFAULT_TERMINAL_ROWS,
-                                DEFAULT_TERMINAL_COLS,
-                                DEFAULT_SCROLLBACK,
-                            )));
-                            self.terminal
-                                .session_parsers
-                                .insert(session.id.clone(), self.terminal.parser.clone());
-                            self.terminal.scroll_offset = 0;
-                            self.terminal.active_session_id = Some(session.id.clone());
-
-                            self.enter_terminal().await?;
-                        }
-                        Err(e) => {
-                            self.error_message = Some(e.to_string());
-                        }
-                    }
+                    self.create_and_enter_session(&repo_id, &branch, None, None, None, &[])
+                        .await?;
                 }
             }
-            Focus::Terminal | Focus::DiffFiles | Focus::GitStatus => {}
+            Focus::Terminal | Focus::DiffFiles | Focus::GitStatus | Focus::Conflicts => {}
         }
         Ok(())
     }
 
+    /// Stop showing a confirmation dialog (its `[a] always` choice) and save the config
+    pub fn stop_confirming(&mut self, confirmation: Confirmation) {
+        self.config.options.set_confirms(confirmation, false);
+        if let Err(e) = amux_config::writer::save_default(&self.config) {
+            self.error_message = Some(format!("Failed to save config: {}", e));
+        }
+    }
+
     /// Request deletion (enters confirm mode)
+    ///
+    /// Returns the deletion itself when its confirmation is turned off.
     /// Note: Caller should call save_focus() before this if needed
-    pub fn request_delete(&mut self) {
+    pub fn request_delete(&mut self) -> Option<AsyncAction> {
         match self.focus {
             Focus::Sidebar => {
                 // In tree view: delete based on current selection
@@ -719,23 +1228,33 @@ impl App {
                         }
                     }
                     SidebarItem::Session(wt_idx, s_idx) => {
+                        // The worktree's own default wins over the global one
+                        self.session_delete_action =
+                            self.worktree_delete_action(wt_idx).unwrap_or_default();
                         if let Some(repo) = self.current_repo() {
                             if let Some(sessions) = repo.sessions_by_worktree.get(&wt_idx) {
                                 if let Some(session) = sessions.get(s_idx) {
-                                    self.input_mode =
-                                        InputMode::ConfirmDelete(DeleteTarget::Session {
-                                            session_id: session.id.clone(),
-                                            name: session.name.clone(),
+                                    let target = DeleteTarget::Session {
+                                        session_id: session.id.clone(),
+                                        name: session.name.clone(),
+                                    };
+                                    if !self.config.options.confirms(Confirmation::DeleteSession) {
+                                        return Some(AsyncAction::ConfirmDelete {
+                                            target,
+                                            action: self.session_delete_action,
                                         });
+                                    }
+                                    self.input_mode = InputMode::ConfirmDelete(target);
                                 }
                             }
                         }
                     }
-                    SidebarItem::None => {}
+                    SidebarItem::LoadMore(_) | SidebarItem::None => {}
                 }
             }
-            Focus::Terminal | Focus::DiffFiles | Focus::GitStatus => {}
+            Focus::Terminal | Focus::DiffFiles | Focus::GitStatus | Focus::Conflicts => {}
         }
+        None
     }
 
     /// Confirm and execute deletion
@@ -754,6 +1273,9 @@ impl App {
                         // Don't restore focus yet - we're chaining to another dialog
                         self.input_mode = InputMode::ConfirmDeleteBranch(branch);
                         self.refresh_branches().await?;
+                        if !self.config.options.confirms(Confirmation::DeleteBranch) {
+                            self.confirm_delete_branch().await?;
+                        }
                     }
                     Err(e) => {
                         self.error_message = Some(e.to_string());
@@ -769,21 +1291,21 @@ impl App {
 
                 // Execute action based on user selection
                 let result = match action {
-                    ExitCleanupAction::Destroy => self
-                        .client
-                        .destroy_session(&session_id)
-                        .await
-                        .map(|_| format!("Destroyed session: {}", name)),
-                    ExitCleanupAction::Stop => self
-                        .client
-                        .stop_session(&session_id)
-                        .await
-                        .map(|_| format!("Stopped session: {}", name)),
+                    ExitCleanupAction::Destroy => {
+                        self.client.trash_session(&session_id).await.map(|_| {
+                            let msg = format!("Destroyed session: {}", name);
+                            self.push_undo(UndoEntry::Session { session_id, name }, &msg);
+                        })
+                    }
+                    ExitCleanupAction::Stop => {
+                        self.client.stop_session(&session_id).await.map(|_| {
+                            self.status_message = Some(format!("Stopped session: {}", name));
+                        })
+                    }
                 };
 
                 match result {
-                    Ok(msg) => {
-                        self.status_message = Some(msg);
+                    Ok(()) => {
                         self.refresh_sessions().await?;
                         // Also refresh worktree sessions for tree view
                         self.load_worktree_sessions(self.branch_idx()).await?;
@@ -867,31 +1389,38 @@ impl App {
 
     // ========== Sidebar Navigation ==========
 
+    /// Rows of the current repo's sidebar, in the configured order
+    pub fn sidebar_rows(&self) -> Vec<SidebarItem> {
+        let options = &self.config.options;
+        self.current_repo()
+            .map(|repo| {
+                sidebar_order::sidebar_rows(repo, options.sidebar_sort, options.sidebar_pin_main)
+            })
+            .unwrap_or_default()
+    }
+
+    /// Sidebar cursor position of an item's row
+    pub fn sidebar_row_of(&self, item: &SidebarItem) -> Option<usize> {
+        self.sidebar_rows().iter().position(|row| row == item)
+    }
+
+    /// Put the sidebar cursor on a session's row, if it is listed
+    fn move_sidebar_cursor_to_session(&mut self, wt_idx: usize, session_id: &str) {
+        let row = self.sidebar_position_of(wt_idx, session_id);
+        if let (Some(row), Some(repo)) = (row, self.current_repo_mut()) {
+            repo.sidebar_cursor = row;
+        }
+    }
+
     /// Get the current sidebar item at cursor position
     pub fn current_sidebar_item(&self) -> SidebarItem {
-        let Some(repo) = self.current_repo() else {
+        let Some(cursor) = self.current_repo().map(|r| r.sidebar_cursor) else {
             return SidebarItem::None;
         };
-
-        let cursor = repo.sidebar_cursor;
-        let mut pos = 0;
-        for (wt_idx, _wt) in repo.worktrees.iter().enumerate() {
-            if pos == cursor {
-                return SidebarItem::Worktree(wt_idx);
-            }
-            pos += 1;
-            if repo.expanded_worktrees.contains(&wt_idx) {
-                if let Some(sessions) = repo.sessions_by_worktree.get(&wt_idx) {
-                    for (s_idx, _session) in sessions.iter().enumerate() {
-                        if pos == cursor {
-                            return SidebarItem::Session(wt_idx, s_idx);
-                        }
-                        pos += 1;
-                    }
-                }
-            }
-        }
-        SidebarItem::None
+        self.sidebar_rows()
+            .get(cursor)
+            .cloned()
+            .unwrap_or(SidebarItem::None)
     }
 
     /// Toggle expansion of current worktree
@@ -946,6 +1475,24 @@ impl App {
         None
     }
 
+    /// Move cursor to a row of the sidebar tree view (mouse click)
+    pub fn sidebar_select_row(&mut self, row: usize) -> Option<AsyncAction> {
+        let moved = self.current_repo_mut().is_some_and(|r| {
+            if row >= r.calculate_sidebar_total() || row == r.sidebar_cursor {
+                return false;
+            }
+            r.sidebar_cursor = row;
+            true
+        });
+        if moved {
+            self.dirty.sidebar = true;
+            if self.update_selection_from_sidebar() {
+                return Some(AsyncAction::LoadGitStatus);
+            }
+        }
+        None
+    }
+
     /// Update branch_idx and session_idx based on sidebar cursor
     /// Returns true if the worktree changed (needs git status refresh)
     pub(super) fn update_selection_from_sidebar(&mut self) -> bool {
@@ -998,6 +1545,9 @@ impl App {
                     }
                 }
             }
+            SidebarItem::LoadMore(wt_idx) => {
+                self.set_branch_idx(wt_idx);
+            }
             SidebarItem::None => {}
         }
 
@@ -1020,6 +1570,9 @@ impl App {
             Focus::DiffFiles => {
                 self.diff_move_up();
             }
+            Focus::Conflicts => {
+                self.conflict_move(false);
+            }
         }
         None
     }
@@ -1037,6 +1590,9 @@ impl App {
             Focus::DiffFiles => {
                 self.diff_move_down();
             }
+            Focus::Conflicts => {
+                self.conflict_move(true);
+            }
         }
         None
     }
@@ -0,0 +1,90 @@
+//! Sidebar width and pane sizes
+
+use super::super::state::AsyncAction;
+use super::super::App;
+use crate::error::TuiError;
+use amux_config::{DEFAULT_TERMINAL_COLS, DEFAULT_TERMINAL_ROWS, SIDEBAR_WIDTH_PERCENT_RANGE};
+use crossterm::terminal::size;
+
+type Result<T> = std::result::Result<T, TuiError>;
+
+/// Percentage points added or removed per grow/shrink keypress
+const SIDEBAR_STEP_PERCENT: i16 = 5;
+
+impl App {
+    /// Grow (positive) or shrink (negative) the sidebar by one step
+    pub fn resize_sidebar_sync(&mut self, direction: i16) -> Option<AsyncAction> {
+        let percent = self.sidebar.width_percent as i16 + direction * SIDEBAR_STEP_PERCENT;
+        self.set_sidebar_width_sync(percent.max(0) as u16)
+    }
+
+    /// Set the sidebar width, clamped to the allowed range
+    ///
+    /// Returns the action that resizes the PTY and persists the width, or
+    /// `None` if the width didn't change.
+    pub fn set_sidebar_width_sync(&mut self, percent: u16) -> Option<AsyncAction> {
+        let percent = percent.clamp(
+            *SIDEBAR_WIDTH_PERCENT_RANGE.start(),
+            *SIDEBAR_WIDTH_PERCENT_RANGE.end(),
+        );
+        if percent == self.sidebar.width_percent {
+            return None;
+        }
+
+        self.sidebar.width_percent = percent;
+        self.status_message = Some(format!("Sidebar width: {}%", percent));
+        self.dirty.sidebar = true;
+        Some(AsyncAction::SaveSidebarWidth)
+    }
+
+    /// Hide or show the sidebar
+    ///
+    /// Returns the action that resizes the PTY to the new terminal width.
+    pub fn toggle_sidebar_hidden(&mut self) -> Option<AsyncAction> {
+        self.sidebar.hidden = !self.sidebar.hidden;
+        self.sidebar.drag_column = None;
+        self.status_message = Some(
+            if self.sidebar.hidden {
+                "Sidebar hidden"
+            } else {
+                "Sidebar shown"
+            }
+            .to_string(),
+        );
+        self.dirty.sidebar = true;
+        let (cols, rows) = self.screen_size();
+        Some(AsyncAction::ResizeTerminal { rows, cols })
+    }
+
+    /// Resize the PTY to the new layout and persist the sidebar width
+    pub async fn save_sidebar_width(&mut self) -> Result<()> {
+        let (cols, rows) = self.screen_size();
+        self.resize_terminal(rows, cols).await?;
+
+        self.config.ui.sidebar_width_percent = self.sidebar.width_percent;
+        if let Err(e) = amux_config::writer::save_default(&self.config) {
+            self.error_message = Some(format!("Failed to save sidebar width: {}", e));
+        }
+        Ok(())
+    }
+
+    /// Screen size (cols, rows), from the last resize event if any
+    pub fn screen_size(&self) -> (u16, u16) {
+        match (self.terminal.cols, self.terminal.rows) {
+            (Some(cols), Some(rows)) => (cols, rows),
+            _ => size().unwrap_or((DEFAULT_TERMINAL_COLS, DEFAULT_TERMINAL_ROWS)),
+        }
+    }
+
+    /// Inner terminal pane size (rows, cols) for a screen of the given size
+    ///
+    /// Layout: tab bar (3) + main content + status bar (3); main content is
+    /// the sidebar followed by the terminal, which has a 1-cell border.
+    pub fn inner_terminal_size(&self, full_cols: u16, full_rows: u16) -> (u16, u16) {
+        let main_height = full_rows.saturating_sub(6); // tab + status bars
+        let terminal_width = full_cols.saturating_sub(self.sidebar.width_cols(full_cols));
+        let inner_rows = main_height.saturating_sub(2); // borders
+        let inner_cols = terminal_width.saturating_sub(2); // borders
+        (inner_rows, inner_cols)
+    }
+}
@@ -0,0 +1,172 @@
+//! Keyboard macros: recording resolved actions into registers and replaying them
+
+use super::super::input::resolver::{detect_context, key_event_to_pattern_string};
+use super::super::input::{handle_input_sync, utils};
+use super::super::macros;
+use super::super::state::{AsyncAction, MacroRecording, MacroRegisterFor, PrefixMode};
+use super::super::App;
+use crate::error::TuiError;
+use amux_config::macros::{is_register, save_macros};
+use amux_config::{BindingContext, MacroStep};
+use crossterm::event::{KeyCode, KeyEvent};
+
+type Result<T> = std::result::Result<T, TuiError>;
+
+impl App {
+    /// Stop recording, or wait for the register to record into
+    pub fn toggle_macro_recording(&mut self) -> Option<AsyncAction> {
+        if self.macro_recording.is_some() {
+            self.stop_macro_recording();
+        } else {
+            self.wait_for_macro_register(MacroRegisterFor::Record);
+        }
+        None
+    }
+
+    /// Wait for the register key after a macro command
+    pub fn wait_for_macro_register(&mut self, purpose: MacroRegisterFor) {
+        self.prefix_mode = PrefixMode::WaitingForRegister(purpose);
+        self.status_message = Some(match purpose {
+            MacroRegisterFor::Record => "Record macro into register (a-z, 0-9)".to_string(),
+            MacroRegisterFor::Replay => "Replay macro from register (a-z, 0-9)".to_string(),
+        });
+    }
+
+    /// Take the register key; Esc cancels
+    pub fn handle_macro_register_key(
+        &mut self,
+        purpose: MacroRegisterFor,
+        key: KeyEvent,
+    ) -> Option<AsyncAction> {
+        self.prefix_mode = PrefixMode::None;
+        self.status_message = None;
+        let register = match key.code {
+            KeyCode::Esc => return None,
+            KeyCode::Char(c) if is_register(c) => c,
+            _ => {
+                self.error_message = Some("Macro registers are a-z and 0-9".to_string());
+                return None;
+            }
+        };
+        match purpose {
+            MacroRegisterFor::Record => {
+                self.macro_recording = Some(MacroRecording {
+                    register,
+                    steps: Vec::new(),
+                });
+                None
+            }
+            MacroRegisterFor::Replay => Some(AsyncAction::ReplayMacro { register }),
+        }
+    }
+
+    /// Add the action `key` resolves to, if any, to the macro being recorded
+    ///
+    /// Called before the key is handled, so the context is the one it is
+    /// resolved in. Keys typed into the terminal resolve to nothing.
+    pub fn record_macro_key(&mut self, key: KeyEvent) {
+        let context = if self.prefix_mode == PrefixMode::WaitingForCommand {
+            // Esc always cancels prefix mode
+            if key.code == KeyCode::Esc {
+                return;
+            }
+            BindingContext::Prefix
+        } else {
+            detect_context(self)
+        };
+        let Some(action) = key_event_to_pattern_string(key)
+            .and_then(|pattern| self.keybinds.resolve(&pattern, context))
+        else {
+            return;
+        };
+        if !macros::is_recordable(context, &action) {
+            return;
+        }
+        if let Some(recording) = self.macro_recording.as_mut() {
+            recording.steps.push(MacroStep { context, action });
+        }
+    }
+
+    /// Keep the recorded macro in its register and save the registers
+    fn stop_macro_recording(&mut self) {
+        let Some(recording) = self.macro_recording.take() else {
+            return;
+        };
+        let count = recording.steps.len();
+        self.macros.insert(recording.register, recording.steps);
+        if let Err(e) = save_macros(&self.macros) {
+            self.error_message = Some(format!("Failed to save macros: {}", e));
+            return;
+        }
+        self.status_message = Some(format!(
+            "Recorded {} step(s) into @{}",
+            count, recording.register
+        ));
+    }
+
+    /// Run the steps of a register in order, stopping at the first that fails
+    pub async fn replay_macro(&mut self, register: char) -> Result<()> {
+        let Some(steps) = self.macros.get(&register).cloned() else {
+            self.error_message = Some(format!("Register @{} is empty", register));
+            return Ok(());
+        };
+        for (idx, step) in steps.iter().enumerate() {
+            if let Err(reason) = self.replay_macro_step(step).await {
+                self.error_message = Some(format!(
+                    "Macro @{} stopped at step {} ({}): {}",
+                    register,
+                    idx + 1,
+                    step,
+                    reason
+                ));
+                return Ok(());
+            }
+        }
+        self.status_message = Some(format!(
+            "Replayed {} step(s) from @{}",
+            steps.len(),
+            register
+        ));
+        Ok(())
+    }
+
+    /// Press the keys of a step and await the action they queue
+    async fn replay_macro_step(&mut self, step: &MacroStep) -> std::result::Result<(), String> {
+        let in_context = if step.context == BindingContext::Prefix {
+            !utils::is_text_input_mode(self)
+        } else {
+            detect_context(self) == step.context
+        };
+        if !in_context {
+            return Err(format!("not in {}", step.context.name()));
+        }
+        if !macros::is_recordable(step.context, &step.action) {
+            return Err("can't be replayed".to_string());
+        }
+        let keys = macros::step_keys(&self.keybinds, step)
+            .ok_or_else(|| format!("no key bound to {}", step.action.name()))?;
+
+        self.error_message = None;
+        let mut action = None;
+        for key in keys {
+            action = handle_input_sync(self, key);
+        }
+        if let Some(action) = action {
+            self.run_macro_action(action).await?;
+        }
+        if let Some(action) = self.deferred_action.take() {
+            self.run_macro_action(action).await?;
+        }
+        match self.error_message.take() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    async fn run_macro_action(&mut self, action: AsyncAction) -> std::result::Result<(), String> {
+        // Boxed, as execute_async_action is what replays the macro
+        Box::pin(self.execute_async_action(action))
+            .await
+            .map_err(|e| e.user_message())
+    }
+}
@@ -2,67 +2,151 @@
 //!
 //! Split into functional submodules:
 //! - repo.rs: Repository access and refresh operations
+//! - clipboard.rs: Copy selection to the system clipboard
 //! - terminal.rs: Terminal operations and stream management
+//! - layout.rs: Sidebar width and pane sizes
+//! - export.rs: Session transcript export
+//! - checkpoints.rs: Worktree checkpoints taken when sessions start
+//! - search.rs: Session and history search, navigation to results
+//! - recent.rs: Recently shown sessions
+//! - broadcast.rs: Typing one line into all sessions of a worktree
+//! - commands.rs: Runtime commands typed at the `:` prompt
+//! - context_menu.rs: Right-click context menu for sidebar rows
 //! - git_ops.rs: Git status operations
+//! - editor.rs: Opening files in the user's editor
+//! - suspend.rs: Leaving the TUI for a shell, and job-control suspension
 //! - diff.rs: Diff view operations
+//! - diff_search.rs: Searching the lines of all diff files
 //! - comments.rs: Line comment operations
 //! - todo.rs: TODO operations
+//! - undo.rs: Undo of destroyed sessions and deleted comments/TODOs
+//! - macros.rs: Keyboard macro recording and replay
 //! - input_forms.rs: Input form handling
 //! - events.rs: Event handling and async action execution
 
+mod broadcast;
+mod checkpoints;
+mod clipboard;
+mod commands;
 mod comments;
+mod conflicts;
+mod context_menu;
 mod diff;
+mod diff_search;
+mod editor;
 mod events;
+mod export;
 mod git_ops;
 mod input_forms;
+mod layout;
+mod macros;
+mod quit;
+mod recent;
 mod repo;
+mod search;
+mod session_file;
+mod startup;
+mod suspend;
 mod terminal;
 mod todo;
+mod undo;
 
+pub use editor::EditorRequest;
+pub use search::HistorySearch;
 pub use terminal::TerminalStream;
 
 use crate::client::Client;
 use crate::error::TuiError;
-use amux_config::{Config, KeybindMap};
+use amux_config::{Config, ImeIntegration, KeybindMap, Macros, QuitBehavior};
 use amux_proto::daemon::Event as DaemonEvent;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event,
+    },
     execute,
     terminal::{
-        disable_raw_mode, enable_raw_mode, BeginSynchronizedUpdate, EndSynchronizedUpdate,
+        disable_raw_mode, enable_raw_mode, size, BeginSynchronizedUpdate, EndSynchronizedUpdate,
         EnterAlternateScreen, LeaveAlternateScreen,
     },
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::collections::HashMap;
-use std::io;
+use signal_hook::consts::SIGTSTP;
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, Stdout};
+use std::path::PathBuf;
+use std::process::{Command, ExitStatus};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::sync::mpsc;
 
 type Result<T> = std::result::Result<T, TuiError>;
 
+use super::color::{self, ColorSupport};
+use super::hyperlink::process_with_hyperlinks;
 use super::icons::StatusIcons;
-use super::input::{handle_input_sync, handle_mouse_sync, TextInput};
+use super::input::{handle_input_sync, handle_mouse_sync, handle_paste_sync, TextInput};
 use super::layout::draw;
+use super::persistence;
+use super::restore::{self, TerminalGuard};
 use super::state::{
-    AsyncAction, DirtyFlags, ExitCleanupAction, Focus, InputMode, PrefixMode, RepoState,
-    RightPanelView, SavedFocusState, SidebarState, TerminalState, TodoState,
+    AsyncAction, BlameState, DirtyFlags, ExitCleanupAction, Focus, InputMode, MacroRecording,
+    PrefixMode, ProviderChoice, RecentSession, RepoState, RightPanelView, SavedFocusState,
+    SidebarState, TerminalState, TodoState, UndoStack,
 };
-use super::theme::CyberpunkTheme;
-
-/// Deactivate fcitx5 input method
-fn deactivate_ime() {
-    let _ = std::process::Command::new("fcitx5-remote")
-        .arg("-c")
-        .output();
+use super::theme::Theme;
+use super::widgets::helpers::get_highlighter;
+use super::widgets::Completion;
+
+/// ibus engine that types plain keyboard input
+const IBUS_PLAIN_ENGINE: &str = "xkb:us::eng";
+
+/// Switch the input method off so keys reach the TUI as typed
+///
+/// Returns the ibus engine to switch back to at exit.
+fn deactivate_ime(ime: ImeIntegration) -> Option<String> {
+    match ime {
+        ImeIntegration::None => None,
+        ImeIntegration::Fcitx5 => {
+            let _ = std::process::Command::new("fcitx5-remote")
+                .arg("-c")
+                .output();
+            None
+        }
+        ImeIntegration::Ibus => {
+            let engine = std::process::Command::new("ibus")
+                .arg("engine")
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+                .filter(|engine| !engine.is_empty() && engine != IBUS_PLAIN_ENGINE)?;
+            let _ = std::process::Command::new("ibus")
+                .args(["engine", IBUS_PLAIN_ENGINE])
+                .output();
+            Some(engine)
+        }
+    }
 }
 
-/// Activate fcitx5 input method
-fn activate_ime() {
-    let _ = std::process::Command::new("fcitx5-remote")
-        .arg("-o")
-        .output();
+/// Switch the input method back on
+fn activate_ime(ime: ImeIntegration, ibus_engine: Option<String>) {
+    match ime {
+        ImeIntegration::None => {}
+        ImeIntegration::Fcitx5 => {
+            let _ = std::process::Command::new("fcitx5-remote")
+                .arg("-o")
+                .output();
+        }
+        ImeIntegration::Ibus => {
+            if let Some(engine) = ibus_engine {
+                let _ = std::process::Command::new("ibus")
+                    .args(["engine", &engine])
+                    .output();
+            }
+        }
+    }
 }
 
 /// Application state
@@ -93,6 +177,12 @@ pub struct App {
     // ============ TODO State (global) ============
     pub todo: TodoState,
 
+    // ============ Blame State (global) ============
+    pub blame: BlameState,
+
+    // ============ Undo State (global) ============
+    pub undo: UndoStack,
+
     // ============ View State ============
     /// Right panel view mode (shared between terminal and diff)
     pub right_panel_view: RightPanelView,
@@ -103,19 +193,45 @@ pub struct App {
     pub status_message: Option<String>,
     pub input_mode: InputMode,
     pub text_input: TextInput,
+    /// Tab completion popup of the open text input
+    pub completion: Completion,
+    /// Destroy/Stop choice in the session cleanup dialogs
     pub session_delete_action: ExitCleanupAction,
+    /// Action to run after the next frame, so its progress message is drawn first
+    pub deferred_action: Option<AsyncAction>,
+    /// Editor to hand the terminal to before the next frame
+    pub editor_request: Option<EditorRequest>,
+    /// Worktree to open a login shell in before the next frame
+    pub shell_request: Option<PathBuf>,
+    /// Session handling chosen in the quit dialog, instead of `quit_behavior`
+    pub quit_behavior_override: Option<QuitBehavior>,
 
     // ============ Event Subscription ============
     pub event_rx: Option<mpsc::Receiver<DaemonEvent>>,
 
+    // ============ History Search ============
+    pub history_search: Option<HistorySearch>,
+
     // ============ Debounce ============
     pub last_git_refresh: Option<std::time::Instant>,
 
+    // ============ Recent Sessions ============
+    /// Sessions shown in the terminal, most recent first
+    pub recent_sessions: VecDeque<RecentSession>,
+    /// Provider and model last picked for each repo (by ID)
+    pub provider_choices: HashMap<String, ProviderChoice>,
+
     // ============ Prefix Key Mode ============
     pub prefix_mode: PrefixMode,
+    pub prefix_entered_at: Option<std::time::Instant>,
+
+    // ============ Keyboard Macros ============
+    /// Recorded macros by register, kept in ~/.amux/macros.toml
+    pub macros: Macros,
+    /// Macro being recorded, if any
+    pub macro_recording: Option<MacroRecording>,
 
     // ============ Configuration ============
-    #[allow(dead_code)]
     pub config: Config,
     pub keybinds: KeybindMap,
 
@@ -123,20 +239,49 @@ pub struct App {
     pub dirty: DirtyFlags,
 
     // ============ Theme & Icons ============
-    pub theme: CyberpunkTheme,
+    pub theme: Theme,
+    /// `theme` option value the current theme was loaded from
+    pub theme_option: String,
+    /// Colors the terminal was detected to show
+    pub color_support: ColorSupport,
     pub icons: StatusIcons,
 }
 
 impl App {
     pub async fn new(client: Client) -> Result<Self> {
         // Load configuration and build keybind map
-        let config = Config::load_or_default()
-            .map_err(|e| TuiError::Config(format!("Failed to load config: {}", e)))?;
-        let keybinds = config
-            .to_keybind_map()
-            .map_err(|e| TuiError::Config(format!("Failed to build keybind map: {}", e)))?;
+        let config = Config::load_or_default().map_err(TuiError::Config)?;
+        let keybinds = config.to_keybind_map().map_err(TuiError::Keybinds)?;
+
+        get_highlighter().set_extension_overrides(config.highlight.extensions.clone());
 
-        let mut app = Self {
+        let mut app = Self::with_config(client, config, keybinds);
+        app.recent_sessions = persistence::load_recent_sessions();
+        app.provider_choices = persistence::load_provider_choices();
+        app.macros = amux_config::macros::load_macros().unwrap_or_else(|e| {
+            tracing::warn!("Failed to load macros: {}", e);
+            Macros::new()
+        });
+
+        app.apply_theme_option();
+
+        // Load initial data
+        app.refresh_all().await?;
+
+        // Load git status for current worktree
+        let _ = app.load_git_status().await;
+
+        // Subscribe to events (don't fail if subscription fails)
+        app.subscribe_events().await;
+
+        app.queue_startup_action();
+
+        Ok(app)
+    }
+
+    /// An app with nothing loaded yet, and nothing remembered from earlier runs
+    fn with_config(client: Client, config: Config, keybinds: KeybindMap) -> Self {
+        Self {
             client,
             // Repo management
             repo_states: HashMap::new(),
@@ -149,9 +294,16 @@ impl App {
             terminal: TerminalState::default(),
             terminal_stream: None,
             // Sidebar (global parts)
-            sidebar: SidebarState::default(),
+            sidebar: SidebarState {
+                width_percent: config.ui.sidebar_width_percent,
+                ..SidebarState::default()
+            },
             // TODO
             todo: TodoState::new(),
+            // Blame
+            blame: BlameState::default(),
+            // Undo
+            undo: UndoStack::default(),
             // View
             right_panel_view: RightPanelView::Terminal,
             // UI state
@@ -160,56 +312,69 @@ impl App {
             status_message: None,
             input_mode: InputMode::Normal,
             text_input: TextInput::new(),
-            session_delete_action: ExitCleanupAction::Destroy,
+            completion: Completion::new(),
+            session_delete_action: ExitCleanupAction::default(),
+            deferred_action: None,
+            editor_request: None,
+            shell_request: None,
+            quit_behavior_override: None,
             // Event subscription
             event_rx: None,
+            // History search
+            history_search: None,
             // Debounce
             last_git_refresh: None,
+            // Recent sessions
+            recent_sessions: VecDeque::new(),
+            provider_choices: HashMap::new(),
             // Prefix mode
             prefix_mode: PrefixMode::None,
+            prefix_entered_at: None,
+            // Macros
+            macros: Macros::new(),
+            macro_recording: None,
             // Config
             config,
             keybinds,
             // Dirty flags
             dirty: DirtyFlags::default(),
             // Theme & Icons
-            theme: CyberpunkTheme::default(),
+            theme: Theme::default(),
+            theme_option: String::new(),
+            color_support: ColorSupport::detect(),
             icons: StatusIcons::default(),
-        };
-
-        // Load initial data
-        app.refresh_all().await?;
-
-        // Load git status for current worktree
-        let _ = app.load_git_status().await;
-
-        // Subscribe to events (don't fail if subscription fails)
-        app.subscribe_events().await;
+        }
+    }
 
-        Ok(app)
+    /// Colors to draw with: the detected ones, or true color if forced
+    pub fn colors(&self) -> ColorSupport {
+        if self.config.options.force_truecolor {
+            ColorSupport::TrueColor
+        } else {
+            self.color_support
+        }
     }
-}
 
-// Drop trait for automatic cleanup on abnormal exit
-impl Drop for App {
-    fn drop(&mut self) {
-        // Only cleanup on abnormal exit (should_quit = false means unexpected termination)
-        if !self.should_quit && !self.sessions().is_empty() {
-            let running_ids: Vec<String> = self
-                .sessions()
-                .iter()
-                .filter(|s| s.status == 1) // SESSION_STATUS_RUNNING
-                .map(|s| s.id.clone())
-                .collect();
-
-            if !running_ids.is_empty() {
-                // Drop cannot be async, so create a sync runtime
-                if let Ok(runtime) = tokio::runtime::Runtime::new() {
-                    for session_id in &running_ids {
-                        let _ = runtime.block_on(self.client.stop_session(session_id));
-                    }
+    /// Load the theme named by the `theme` option if it changed
+    ///
+    /// Runs before every frame, so `:set theme` shows on the next one. A
+    /// theme that fails to load keeps the current colors until the option
+    /// changes again.
+    pub fn apply_theme_option(&mut self) {
+        if self.theme_option == self.config.options.theme {
+            return;
+        }
+        self.theme_option = self.config.options.theme.clone();
+        match Theme::load(&self.theme_option) {
+            Ok(theme) => {
+                if !get_highlighter().set_theme(&theme.syntax_theme) {
+                    self.error_message =
+                        Some(format!("Unknown syntax theme: {}", theme.syntax_theme));
                 }
+                self.theme = theme;
+                self.dirty.sidebar = true;
             }
+            Err(e) => self.error_message = Some(e),
         }
     }
 }
@@ -220,35 +385,151 @@ pub enum RunResult {
     Quit,
 }
 
+/// How long the input reader waits for an event before checking for a pause
+const INPUT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Set while another program (editor, shell) reads the terminal
+///
+/// The reader holds the lock while it polls, so once a pause is set it
+/// reads nothing more until it is cleared.
+type InputPause = Arc<Mutex<bool>>;
+
+fn set_input_paused(pause: &InputPause, paused: bool) {
+    *pause.lock().unwrap_or_else(|e| e.into_inner()) = paused;
+}
+
 /// Spawn a thread to read crossterm events (blocking I/O)
-fn spawn_input_reader() -> mpsc::Receiver<Event> {
+fn spawn_input_reader(pause: InputPause) -> mpsc::Receiver<Event> {
     let (tx, rx) = mpsc::channel(32);
 
-    std::thread::spawn(move || {
-        while let Ok(event) = event::read() {
-            if tx.blocking_send(event).is_err() {
-                break; // Receiver dropped
+    std::thread::spawn(move || loop {
+        let event = {
+            let paused = pause.lock().unwrap_or_else(|e| e.into_inner());
+            if *paused {
+                None
+            } else {
+                match event::poll(INPUT_POLL_INTERVAL) {
+                    Ok(true) => match event::read() {
+                        Ok(event) => Some(event),
+                        Err(_) => break,
+                    },
+                    Ok(false) => continue,
+                    Err(_) => break,
+                }
             }
+        };
+        match event {
+            Some(event) => {
+                if tx.blocking_send(event).is_err() {
+                    break; // Receiver dropped
+                }
+            }
+            None => std::thread::sleep(INPUT_POLL_INTERVAL),
         }
     });
 
     rx
 }
 
+/// Give the terminal back to the shell: no raw mode or alternate screen
+fn suspend_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        DisableBracketedPaste
+    )?;
+    terminal.show_cursor()
+}
+
+/// Take the terminal over again after `suspend_terminal`
+fn resume_terminal(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    mouse_enabled: bool,
+) -> io::Result<()> {
+    enable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        EnableBracketedPaste
+    )?;
+    if mouse_enabled {
+        execute!(terminal.backend_mut(), EnableMouseCapture)?;
+    }
+    // The screen was left, draw everything again
+    terminal.clear()
+}
+
+/// Run a program in the terminal and take the terminal back when it exits
+///
+/// The TUI is restored whether the program ran, failed to start or crashed.
+fn run_in_terminal(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    pause: &InputPause,
+    mut command: Command,
+    mouse_enabled: bool,
+) -> Result<io::Result<ExitStatus>> {
+    set_input_paused(pause, true);
+    let status =
+        suspend_terminal(terminal).and_then(|()| tokio::task::block_in_place(|| command.status()));
+    let resumed = resume_terminal(terminal, mouse_enabled);
+    set_input_paused(pause, false);
+    resumed.map_err(TuiError::TerminalInit)?;
+    Ok(status)
+}
+
+/// Stop the process as SIGTSTP would, with the terminal given back
+///
+/// Returns once the shell continues the process (`fg`, SIGCONT), with the
+/// terminal taken over again.
+fn suspend_process(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    pause: &InputPause,
+    mouse_enabled: bool,
+) -> Result<()> {
+    set_input_paused(pause, true);
+    let stopped = suspend_terminal(terminal)
+        .and_then(|()| signal_hook::low_level::emulate_default_handler(SIGTSTP));
+    if let Err(e) = stopped {
+        tracing::warn!("Failed to suspend: {}", e);
+    }
+    let resumed = resume_terminal(terminal, mouse_enabled);
+    set_input_paused(pause, false);
+    resumed.map_err(TuiError::TerminalInit)
+}
+
 /// Run the TUI application
-pub async fn run_with_client(mut app: App, should_exit: Arc<AtomicBool>) -> Result<RunResult> {
+///
+/// `should_exit` and `should_suspend` are set by the signal handler on
+/// SIGINT/SIGTERM and SIGTSTP.
+pub async fn run_with_client(
+    mut app: App,
+    should_exit: Arc<AtomicBool>,
+    should_suspend: Arc<AtomicBool>,
+) -> Result<RunResult> {
     // Deactivate IME at startup
-    deactivate_ime();
+    let ime = app.config.options.ime_integration;
+    let ibus_engine = deactivate_ime(ime);
 
-    // Setup terminal
+    // Setup terminal, restored by the guard if anything below fails
+    restore::install_panic_hook();
+    let guard = TerminalGuard::stdout();
     enable_raw_mode().map_err(TuiError::TerminalInit)?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture).map_err(TuiError::TerminalInit)?;
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste).map_err(TuiError::TerminalInit)?;
+    // Without capture the host terminal keeps its own selection and scrolling
+    if app.config.options.mouse_enabled {
+        execute!(stdout, EnableMouseCapture).map_err(TuiError::TerminalInit)?;
+    } else {
+        execute!(stdout, DisableMouseCapture).map_err(TuiError::TerminalInit)?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend).map_err(TuiError::TerminalInit)?;
 
     // Spawn input reader thread (crossterm events are blocking)
-    let mut input_rx = spawn_input_reader();
+    let input_pause = InputPause::default();
+    let mut input_rx = spawn_input_reader(input_pause.clone());
 
     // Fixed 16ms render interval (~60fps) - always render on every tick (tuitest pattern)
     let mut render_interval = tokio::time::interval(std::time::Duration::from_millis(16));
@@ -290,11 +571,24 @@ pub async fn run_with_client(mut app: App, should_exit: Arc<AtomicBool>) -> Resu
                             pending_action = Some(action);
                         }
                     }
+                    Event::Paste(text) => {
+                        if let Some(action) = handle_paste_sync(&mut app, text) {
+                            if let Some(old_action) = pending_action.take() {
+                                let _ = app.execute_async_action(old_action).await;
+                            }
+                            pending_action = Some(action);
+                        }
+                    }
                     Event::Resize(cols, rows) => {
                         let _ = app.resize_terminal(rows, cols).await;
                     }
-                    Event::Mouse(mouse) => {
-                        handle_mouse_sync(&mut app, mouse);
+                    Event::Mouse(mouse) if app.config.options.mouse_enabled => {
+                        if let Some(action) = handle_mouse_sync(&mut app, mouse) {
+                            if let Some(old_action) = pending_action.take() {
+                                let _ = app.execute_async_action(old_action).await;
+                            }
+                            pending_action = Some(action);
+                        }
                     }
                     _ => {}
                 }
@@ -330,7 +624,17 @@ pub async fn run_with_client(mut app: App, should_exit: Arc<AtomicBool>) -> Resu
                 }
             }
 
-            // 4. Render tick - ALWAYS RENDER (tuitest pattern)
+            // 4. History search matches as the daemon streams them
+            found = async {
+                match app.history_search.as_mut() {
+                    Some(search) => search.rx.recv().await,
+                    None => std::future::pending().await,
+                }
+            } => {
+                app.on_history_match(found);
+            }
+
+            // 5. Render tick - ALWAYS RENDER (tuitest pattern)
             _ = render_interval.tick() => {
                 // Drain any remaining data from channel into buffer
                 if let Some(stream) = app.terminal_stream.as_mut() {
@@ -367,11 +671,21 @@ pub async fn run_with_client(mut app: App, should_exit: Arc<AtomicBool>) -> Resu
 
                         if let Ok(mut parser) = app.terminal.parser.lock() {
                             let scroll_offset = parser.screen().scrollback();
-                            parser.process(&data);
+                            if app.config.options.open_links {
+                                process_with_hyperlinks(
+                                    &mut parser,
+                                    &data,
+                                    &mut app.terminal.hyperlinks,
+                                    &mut app.terminal.pending_hyperlink,
+                                );
+                            } else {
+                                parser.process(&data);
+                            }
                             if scroll_offset > 0 {
                                 parser.screen_mut().set_scrollback(scroll_offset);
                             }
                         }
+                        app.apply_history_jump(&data);
                     }
                     last_pty_time = None;
                 }
@@ -379,10 +693,58 @@ pub async fn run_with_client(mut app: App, should_exit: Arc<AtomicBool>) -> Resu
                 // Execute pending async action
                 if let Some(action) = pending_action.take() {
                     if let Err(e) = app.execute_async_action(action).await {
-                        app.error_message = Some(format!("{}", e));
+                        app.error_message = Some(e.user_message());
+                    }
+                }
+
+                if let Some(request) = app.editor_request.take() {
+                    let mut command = Command::new("sh");
+                    command.arg("-c").arg(&request.command).current_dir(&request.cwd);
+                    let status = run_in_terminal(
+                        &mut terminal,
+                        &input_pause,
+                        command,
+                        app.config.options.mouse_enabled,
+                    )?;
+                    // Resizes while the editor ran went unseen
+                    if let Ok((cols, rows)) = size() {
+                        let _ = app.resize_terminal(rows, cols).await;
+                    }
+                    if let Err(e) = app.editor_closed(request, status).await {
+                        app.error_message = Some(e.user_message());
+                    }
+                }
+
+                if let Some(cwd) = app.shell_request.take() {
+                    let mut command = Command::new(suspend::login_shell());
+                    command.current_dir(cwd);
+                    let status = run_in_terminal(
+                        &mut terminal,
+                        &input_pause,
+                        command,
+                        app.config.options.mouse_enabled,
+                    )?;
+                    if let Ok((cols, rows)) = size() {
+                        let _ = app.resize_terminal(rows, cols).await;
+                    }
+                    if let Err(e) = app.shell_closed(status).await {
+                        app.error_message = Some(e.user_message());
                     }
                 }
 
+                if should_suspend.swap(false, Ordering::Relaxed) {
+                    suspend_process(&mut terminal, &input_pause, app.config.options.mouse_enabled)?;
+                    // The terminal may have been resized while stopped
+                    if let Ok((cols, rows)) = size() {
+                        let _ = app.resize_terminal(rows, cols).await;
+                    }
+                    if let Err(e) = app.resumed().await {
+                        app.error_message = Some(e.user_message());
+                    }
+                }
+
+                app.expire_prefix_mode();
+
                 // Check if we need to resubscribe (event channel disconnected)
                 if app.needs_resubscribe() {
                     // Fallback: Periodic session refresh while disconnected
@@ -398,13 +760,25 @@ pub async fn run_with_client(mut app: App, should_exit: Arc<AtomicBool>) -> Resu
                     }
                 }
 
+                app.apply_theme_option();
+
                 // Always render - no dirty checks needed (tuitest pattern)
                 // Use synchronized update to prevent flicker
                 execute!(terminal.backend_mut(), BeginSynchronizedUpdate)
                     .map_err(TuiError::Render)?;
-                terminal.draw(|f| draw(f, &app)).map_err(TuiError::Render)?;
+                terminal
+                    .draw(|f| {
+                        draw(f, &app);
+                        color::quantize_buffer(f.buffer_mut(), app.colors());
+                    })
+                    .map_err(TuiError::Render)?;
                 execute!(terminal.backend_mut(), EndSynchronizedUpdate)
                     .map_err(TuiError::Render)?;
+
+                // Slow actions (fetch/pull) start once their progress message is on screen
+                if pending_action.is_none() {
+                    pending_action = app.deferred_action.take();
+                }
             }
         }
 
@@ -416,20 +790,13 @@ pub async fn run_with_client(mut app: App, should_exit: Arc<AtomicBool>) -> Resu
     }
 
     // Cleanup
-    app.disconnect_stream();
+    app.apply_quit_behavior().await;
 
     // Restore terminal
-    disable_raw_mode().map_err(TuiError::TerminalRestore)?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )
-    .map_err(TuiError::TerminalRestore)?;
-    terminal.show_cursor().map_err(TuiError::TerminalRestore)?;
+    guard.restore().map_err(TuiError::TerminalRestore)?;
 
     // Activate IME at exit
-    activate_ime();
+    activate_ime(ime, ibus_engine);
 
     Ok(RunResult::Quit)
 }
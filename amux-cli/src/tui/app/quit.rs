@@ -0,0 +1,150 @@
+//! Asking before quitting while work is in progress
+//!
+//! With running sessions (`confirm_quit_running_sessions`) or uncommitted
+//! changes (`confirm_quit_dirty`), quitting first shows what is at stake.
+//! Keeping the sessions running skips `quit_behavior`; stopping them covers
+//! every running session of the daemon, not just the loaded ones. When the
+//! daemon can't list its sessions, none are known to run and quitting
+//! isn't held up by them.
+
+use super::super::state::InputMode;
+use super::App;
+use crate::error::{ClientError, TuiError};
+use amux_config::QuitBehavior;
+use amux_proto::daemon::{SessionInfo, SessionStatus};
+use std::collections::HashSet;
+
+type Result<T> = std::result::Result<T, TuiError>;
+
+impl App {
+    /// Quit, or ask first when sessions are running or worktrees are dirty
+    pub async fn request_quit(&mut self) -> Result<()> {
+        let options = &self.config.options;
+        let (check_running, check_dirty) = (
+            options.confirm_quit_running_sessions,
+            options.confirm_quit_dirty,
+        );
+
+        let running = if check_running {
+            running_sessions(self.client.list_sessions(None, None).await)
+        } else {
+            Vec::new()
+        };
+        let dirty_worktrees = if check_dirty {
+            self.repo_states
+                .values()
+                .flat_map(|r| r.worktrees.iter())
+                .filter(|w| w.status_known && w.dirty)
+                .count()
+        } else {
+            0
+        };
+
+        match confirm_quit_mode(running, dirty_worktrees) {
+            Some(mode) => {
+                self.save_focus();
+                self.input_mode = mode;
+            }
+            None => self.should_quit = true,
+        }
+        Ok(())
+    }
+
+    /// Quit and leave every session running, whatever `quit_behavior` says
+    pub fn quit_keeping_sessions(&mut self) {
+        self.input_mode = InputMode::Normal;
+        self.quit_behavior_override = Some(QuitBehavior::Detach);
+        self.should_quit = true;
+    }
+
+    /// Stop the running sessions listed in the dialog, then quit
+    ///
+    /// Sessions the daemon fails to stop are left as they are, as when
+    /// `quit_behavior` stops them.
+    pub async fn stop_all_and_quit(&mut self) {
+        let InputMode::ConfirmQuit { session_ids, .. } =
+            std::mem::replace(&mut self.input_mode, InputMode::Normal)
+        else {
+            return;
+        };
+        for session_id in session_ids {
+            let _ = self.client.stop_session(&session_id).await;
+        }
+        self.quit_behavior_override = Some(QuitBehavior::Stop);
+        self.should_quit = true;
+    }
+}
+
+/// Running sessions of the daemon, none if it couldn't list them
+fn running_sessions(
+    listed: std::result::Result<Vec<SessionInfo>, ClientError>,
+) -> Vec<SessionInfo> {
+    match listed {
+        Ok(sessions) => sessions
+            .into_iter()
+            .filter(|s| s.status == SessionStatus::Running as i32)
+            .collect(),
+        Err(e) => {
+            tracing::warn!("Failed to list sessions before quitting: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Dialog to confirm quitting with, or None to quit right away
+fn confirm_quit_mode(running: Vec<SessionInfo>, dirty_worktrees: usize) -> Option<InputMode> {
+    if running.is_empty() && dirty_worktrees == 0 {
+        return None;
+    }
+    let worktrees = running
+        .iter()
+        .map(|s| (s.repo_id.as_str(), s.branch.as_str()))
+        .collect::<HashSet<_>>()
+        .len();
+    Some(InputMode::ConfirmQuit {
+        session_ids: running.into_iter().map(|s| s.id).collect(),
+        worktrees,
+        dirty_worktrees,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(id: &str, branch: &str, status: SessionStatus) -> SessionInfo {
+        SessionInfo {
+            id: id.to_string(),
+            repo_id: "repo".to_string(),
+            branch: branch.to_string(),
+            status: status as i32,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_quit_when_daemon_unavailable() {
+        let listed = Err(ClientError::from(tonic::Status::unavailable("gone")));
+        let running = running_sessions(listed);
+        assert!(running.is_empty());
+        assert_eq!(confirm_quit_mode(running, 0), None);
+    }
+
+    #[test]
+    fn test_confirm_quit_mode() {
+        let running = running_sessions(Ok(vec![
+            session("a", "main", SessionStatus::Running),
+            session("b", "main", SessionStatus::Running),
+            session("c", "fix", SessionStatus::Stopped),
+        ]));
+        assert_eq!(
+            confirm_quit_mode(running, 2),
+            Some(InputMode::ConfirmQuit {
+                session_ids: vec!["a".to_string(), "b".to_string()],
+                worktrees: 1,
+                dirty_worktrees: 2,
+            })
+        );
+        assert!(confirm_quit_mode(Vec::new(), 1).is_some());
+    }
+}
@@ -0,0 +1,74 @@
+//! Recently shown sessions, to switch back to them
+
+use super::super::persistence;
+use super::super::state::{InputMode, RecentSession, RECENT_SESSIONS_CAPACITY};
+use super::App;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+impl App {
+    /// Put the session shown in the terminal at the front of the recent list
+    pub(super) fn note_recent_session(&mut self) {
+        let Some(repo_id) = self.current_repo_id.clone() else {
+            return;
+        };
+        let Some(session_id) = self.terminal.active_session_id.clone() else {
+            return;
+        };
+
+        self.recent_sessions.retain(|r| r.session_id != session_id);
+        self.recent_sessions.push_front(RecentSession {
+            repo_id,
+            session_id,
+            shown_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or(0),
+        });
+        self.recent_sessions.truncate(RECENT_SESSIONS_CAPACITY);
+
+        if let Err(e) = persistence::save_recent_sessions(&self.recent_sessions) {
+            tracing::warn!("Failed to save recent sessions: {}", e);
+        }
+    }
+
+    /// Recent sessions other than the one shown, most recent first
+    pub fn recent_switch_targets(&self) -> Vec<&RecentSession> {
+        let active = self.terminal.active_session_id.as_deref();
+        self.recent_sessions
+            .iter()
+            .filter(|r| Some(r.session_id.as_str()) != active)
+            .collect()
+    }
+
+    /// Switch to the `index`th of `recent_switch_targets`
+    pub fn switch_to_recent_session(&mut self, index: usize) {
+        let Some(session_id) = self
+            .recent_switch_targets()
+            .get(index)
+            .map(|r| r.session_id.clone())
+        else {
+            self.error_message = Some(format!("No recent session {}", index + 1));
+            return;
+        };
+        self.jump_to_session(&session_id);
+    }
+
+    /// Open the list of recent sessions
+    pub fn start_recent_sessions(&mut self) {
+        if self.recent_switch_targets().is_empty() {
+            self.status_message = Some("No recent sessions".to_string());
+            return;
+        }
+        self.save_focus();
+        self.input_mode = InputMode::RecentSessions { selected_index: 0 };
+    }
+
+    /// Switch to the session picked in the recent sessions list
+    pub fn open_recent_session(&mut self) {
+        let InputMode::RecentSessions { selected_index } = self.input_mode else {
+            return;
+        };
+        self.cancel_input();
+        self.switch_to_recent_session(selected_index);
+    }
+}
@@ -1,10 +1,11 @@
 //! Repository access and refresh operations
 
-use super::super::state::{AsyncAction, RepoState};
+use super::super::state::{AsyncAction, InputMode, RepoState};
 use super::super::App;
-use crate::error::TuiError;
-use amux_config::{DEFAULT_SCROLLBACK, DEFAULT_TERMINAL_COLS, DEFAULT_TERMINAL_ROWS};
-use amux_proto::daemon::{LineCommentInfo, RepoInfo, SessionInfo, WorktreeInfo};
+use crate::error::{ClientError, TuiError};
+use amux_config::{QuitBehavior, DEFAULT_SCROLLBACK, DEFAULT_TERMINAL_COLS, DEFAULT_TERMINAL_ROWS};
+use amux_proto::daemon::{LineCommentInfo, SessionInfo, WorktreeInfo};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use tracing::debug;
 
@@ -22,9 +23,10 @@ impl App {
 
     /// Get current repo state (mutable)
     pub fn current_repo_mut(&mut self) -> Option<&mut RepoState> {
-        // Need to clone the id to avoid borrow issues
-        let id = self.current_repo_id.clone()?;
-        self.repo_states.get_mut(&id)
+        // Disjoint fields, so the id is borrowed rather than cloned
+        self.current_repo_id
+            .as_ref()
+            .and_then(|id| self.repo_states.get_mut(id))
     }
 
     /// Get current repo index in repo_order
@@ -42,11 +44,6 @@ impl App {
             .filter_map(|id| self.repo_states.get(id))
     }
 
-    /// Get repos as RepoInfo list (for compatibility)
-    pub fn repos(&self) -> Vec<RepoInfo> {
-        self.repos_ordered().map(|r| r.info.clone()).collect()
-    }
-
     /// Get current worktrees (convenience)
     pub fn worktrees(&self) -> &[WorktreeInfo] {
         self.current_repo()
@@ -83,6 +80,16 @@ impl App {
         self.current_repo().and_then(|r| r.current_session())
     }
 
+    /// Get the session attached to the terminal pane
+    pub fn active_session(&self) -> Option<&SessionInfo> {
+        let repo = self.current_repo()?;
+        let active = self.terminal.active_session_id.as_deref()?;
+        repo.sessions
+            .iter()
+            .chain(repo.sessions_by_worktree.values().flatten())
+            .find(|s| s.id == active)
+    }
+
     /// Get current line comments (convenience)
     pub fn line_comments(&self) -> &[LineCommentInfo] {
         self.current_repo()
@@ -110,6 +117,16 @@ impl App {
         self.current_repo_mut().map(|r| &mut r.diff)
     }
 
+    /// Get current conflict view state (convenience)
+    pub fn conflicts(&self) -> Option<&super::super::state::ConflictState> {
+        self.current_repo().map(|r| &r.conflicts)
+    }
+
+    /// Get current conflict view state (mutable, convenience)
+    pub fn conflicts_mut(&mut self) -> Option<&mut super::super::state::ConflictState> {
+        self.current_repo_mut().map(|r| &mut r.conflicts)
+    }
+
     /// Get add_worktree_idx (convenience)
     pub fn add_worktree_idx(&self) -> usize {
         self.current_repo().map(|r| r.add_worktree_idx).unwrap_or(0)
@@ -263,13 +280,43 @@ impl App {
         Ok(())
     }
 
+    /// Refresh dirty/ahead/behind indicators and diff stats for current repo's worktrees
+    ///
+    /// Lighter than `refresh_branches`: only the indicator fields are updated
+    /// in place, so cursors and expanded sessions are untouched.
+    pub async fn refresh_worktree_status(&mut self) -> Result<()> {
+        let Some(repo_id) = self.current_repo_id.clone() else {
+            return Ok(());
+        };
+
+        let latest = self.client.list_worktrees(&repo_id).await?;
+        if let Some(repo) = self.repo_states.get_mut(&repo_id) {
+            for wt in repo.worktrees.iter_mut() {
+                if let Some(new) = latest.iter().find(|n| n.branch == wt.branch) {
+                    wt.dirty = new.dirty;
+                    wt.ahead = new.ahead;
+                    wt.behind = new.behind;
+                    wt.status_known = new.status_known;
+                    wt.diff_stats = new.diff_stats;
+                    wt.head_sha = new.head_sha.clone();
+                }
+            }
+        }
+        self.dirty.sidebar = true;
+        Ok(())
+    }
+
     /// Refresh sessions for current branch
     pub async fn refresh_sessions(&mut self) -> Result<()> {
         // Get repo_id and branch info first to avoid borrow issues
-        let (repo_id, branch_name) = {
+        let (repo_id, branch_name, page_size) = {
             if let Some(repo) = self.current_repo() {
                 if let Some(wt) = repo.current_worktree() {
-                    (repo.info.id.clone(), wt.branch.clone())
+                    (
+                        repo.info.id.clone(),
+                        wt.branch.clone(),
+                        self.session_page_size(repo.branch_idx),
+                    )
                 } else {
                     return Ok(());
                 }
@@ -278,10 +325,10 @@ impl App {
             }
         };
 
-        // Fetch sessions from daemon
-        let sessions = self
+        // Fetch the first page (or as many as the tree lists) from daemon
+        let (sessions, _) = self
             .client
-            .list_sessions(Some(&repo_id), Some(&branch_name))
+            .list_sessions_page(&repo_id, &branch_name, page_size, "")
             .await?;
 
         // Update repo state
@@ -342,6 +389,7 @@ impl App {
 
             self.terminal.scroll_offset = 0;
             self.terminal.active_session_id = new_session_id;
+            self.note_recent_session();
 
             // Auto-connect for preview if there's a session
             if self.terminal.active_session_id.is_some() {
@@ -371,20 +419,74 @@ impl App {
             }
         };
 
-        let sessions = self
+        let page_size = self.session_page_size(wt_idx);
+        let (sessions, next_page_token) = self
             .client
-            .list_sessions(Some(&repo_id), Some(&branch))
+            .list_sessions_page(&repo_id, &branch, page_size, "")
             .await?;
 
         // Store sessions in repo state
         if let Some(repo) = self.current_repo_mut() {
             repo.sessions_by_worktree.insert(wt_idx, sessions);
+            if next_page_token.is_empty() {
+                repo.session_page_tokens.remove(&wt_idx);
+            } else {
+                repo.session_page_tokens.insert(wt_idx, next_page_token);
+            }
         }
         self.update_sidebar_total_items();
         self.dirty.sidebar = true;
         Ok(())
     }
 
+    /// Append the next page of a worktree's sessions (the "Load more" row)
+    pub async fn load_more_sessions(&mut self, wt_idx: usize) -> Result<()> {
+        let Some((repo_id, branch, page_token)) = self.current_repo().and_then(|repo| {
+            Some((
+                repo.info.id.clone(),
+                repo.worktrees.get(wt_idx)?.branch.clone(),
+                repo.session_page_tokens.get(&wt_idx)?.clone(),
+            ))
+        }) else {
+            return Ok(());
+        };
+
+        let page_size = self.config.options.session_page_size;
+        let (sessions, next_page_token) = self
+            .client
+            .list_sessions_page(&repo_id, &branch, page_size, &page_token)
+            .await?;
+
+        if let Some(repo) = self.current_repo_mut() {
+            if repo.branch_idx == wt_idx {
+                repo.sessions.extend(sessions.iter().cloned());
+            }
+            repo.sessions_by_worktree
+                .entry(wt_idx)
+                .or_default()
+                .extend(sessions);
+            if next_page_token.is_empty() {
+                repo.session_page_tokens.remove(&wt_idx);
+            } else {
+                repo.session_page_tokens.insert(wt_idx, next_page_token);
+            }
+        }
+        // The cursor stays on the row, now the first newly loaded session
+        self.update_selection_from_sidebar();
+        self.dirty.sidebar = true;
+        Ok(())
+    }
+
+    /// Sessions to fetch for a worktree: a page, or all already listed so a
+    /// refresh keeps the pages loaded with "Load more"
+    fn session_page_size(&self, wt_idx: usize) -> u32 {
+        let listed = self
+            .current_repo()
+            .and_then(|r| r.sessions_by_worktree.get(&wt_idx))
+            .map_or(0, |s| s.len() as u32);
+        self.config.options.session_page_size.max(listed)
+    }
+
     /// Switch to repo by index (sync version)
     /// Saves current repo's view state and restores the target repo's state
     pub fn switch_repo_sync(&mut self, idx: usize) -> Option<AsyncAction> {
@@ -408,13 +510,203 @@ impl App {
         None
     }
 
+    /// Move the current repo one tab left (negative) or right (positive)
+    ///
+    /// Number shortcuts follow the new order; it is persisted by the daemon.
+    pub fn move_repo_sync(&mut self, delta: isize) -> Option<AsyncAction> {
+        let id = self.current_repo_id.as_ref()?;
+        let idx = self.repo_order.iter().position(|r| r == id)?;
+        let target = idx.checked_add_signed(delta)?;
+        if target >= self.repo_order.len() {
+            return None;
+        }
+
+        self.repo_order.swap(idx, target);
+        self.dirty.sidebar = true;
+        Some(AsyncAction::SaveRepoOrder)
+    }
+
+    /// Persist the current repo order
+    pub async fn save_repo_order(&mut self) -> Result<()> {
+        self.client.reorder_repos(self.repo_order.clone()).await?;
+        Ok(())
+    }
+
+    /// Open the add-repository prompt
+    pub fn start_add_repo(&mut self) {
+        self.save_focus();
+        self.text_input.clear();
+        self.input_mode = InputMode::AddRepo;
+    }
+
+    /// Add a repository by path and select it
+    ///
+    /// `~` and relative paths are resolved here since the daemon runs with a
+    /// different working directory. A repo that is already known is selected.
+    pub async fn add_repo(&mut self, path: &str) -> Result<()> {
+        let path = resolve_user_path(path.trim());
+        if !path.exists() {
+            self.error_message = Some(format!("Path does not exist: {}", path.display()));
+            return Ok(());
+        }
+
+        let added = match self.client.add_repo(&path.to_string_lossy()).await {
+            Ok(info) => Some(info),
+            Err(ClientError::Rpc(status)) if status.code() == tonic::Code::AlreadyExists => None,
+            Err(ClientError::Rpc(status)) => {
+                self.error_message = Some(format!("Cannot add repo: {}", status.message()));
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        self.refresh_all().await?;
+        let repo_id = match added {
+            Some(info) => {
+                self.status_message = Some(format!("Added repo {}", info.name));
+                Some(info.id)
+            }
+            None => {
+                // Same lookup as the startup auto-add: match the main repo path
+                let repo_path = crate::find_main_repo_path(&path)
+                    .and_then(|p| p.canonicalize().ok())
+                    .unwrap_or(path);
+                let path_str = repo_path.to_string_lossy();
+                let existing = self
+                    .repos_ordered()
+                    .find(|r| r.info.path == path_str)
+                    .map(|r| (r.info.id.clone(), r.info.name.clone()));
+                if let Some((_, name)) = &existing {
+                    self.status_message = Some(format!("Repo {} already added", name));
+                }
+                existing.map(|(id, _)| id)
+            }
+        };
+
+        if let Some(id) = repo_id.filter(|id| self.repo_order.contains(id)) {
+            self.current_repo_id = Some(id);
+            self.update_sidebar_total_items();
+            self.dirty.sidebar = true;
+            self.refresh_branches().await?;
+        }
+        Ok(())
+    }
+
+    /// Ask for confirmation before unregistering the current repo
+    ///
+    /// The session choice starts from `quit_behavior`.
+    pub fn start_remove_repo(&mut self) {
+        let Some(repo) = self.current_repo() else {
+            self.error_message = Some("No repo selected".to_string());
+            return;
+        };
+        let (repo_id, name, session_count) = (
+            repo.info.id.clone(),
+            repo.info.name.clone(),
+            repo.sessions.len(),
+        );
+
+        self.save_focus();
+        self.input_mode = InputMode::ConfirmRemoveRepo {
+            repo_id,
+            name,
+            session_count,
+            sessions: self.config.options.quit_behavior,
+        };
+    }
+
+    /// Unregister a repo from the daemon and select an adjacent one
+    ///
+    /// The repo's sessions are destroyed or stopped first, or detached: left
+    /// running but no longer listed under any repo. Its git directory is left
+    /// untouched.
+    pub async fn remove_repo(&mut self, repo_id: &str, sessions: QuitBehavior) -> Result<()> {
+        self.input_mode = InputMode::Normal;
+        self.restore_focus();
+
+        let name = self
+            .repo_states
+            .get(repo_id)
+            .map(|r| r.info.name.clone())
+            .unwrap_or_else(|| repo_id.to_string());
+
+        for session in self.client.list_sessions(Some(repo_id), None).await? {
+            if self.terminal.active_session_id.as_deref() == Some(session.id.as_str()) {
+                self.disconnect_stream();
+                self.terminal.active_session_id = None;
+            }
+            let result = match sessions {
+                QuitBehavior::Destroy => self.client.destroy_session(&session.id).await,
+                QuitBehavior::Stop if session.status == 1 => {
+                    // SESSION_STATUS_RUNNING
+                    self.client.stop_session(&session.id).await
+                }
+                QuitBehavior::Stop | QuitBehavior::Detach => Ok(()),
+            };
+            if let Err(e) = result {
+                self.error_message = Some(format!("Cannot clean up {}: {}", session.name, e));
+                return Ok(());
+            }
+        }
+
+        let detach = sessions == QuitBehavior::Detach;
+        match self.client.remove_repo(repo_id, detach).await {
+            Ok(()) => {}
+            Err(ClientError::Rpc(status)) => {
+                self.error_message = Some(format!("Cannot remove repo: {}", status.message()));
+                return Ok(());
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        // Select the repo that takes the removed one's place, or the new last one
+        if let Some(idx) = self.repo_order.iter().position(|id| id == repo_id) {
+            self.repo_order.remove(idx);
+            self.repo_states.remove(repo_id);
+            if self.current_repo_id.as_deref() == Some(repo_id) {
+                let next = idx.min(self.repo_order.len().saturating_sub(1));
+                self.current_repo_id = self.repo_order.get(next).cloned();
+            }
+        }
+
+        self.refresh_all().await?;
+        self.update_sidebar_total_items();
+        self.status_message = Some(format!("Removed repo {} (files left on disk)", name));
+        Ok(())
+    }
+
+    /// Prune worktrees deleted outside amux and their sessions
+    pub async fn cleanup_worktrees(&mut self) -> Result<()> {
+        let Some(repo_id) = self.current_repo_id.clone() else {
+            return Ok(());
+        };
+
+        let result = match self.client.cleanup_worktrees(&repo_id).await {
+            Ok(result) => result,
+            Err(e) => {
+                self.error_message = Some(format!("Worktree cleanup failed: {}", e));
+                return Ok(());
+            }
+        };
+
+        self.refresh_all().await?;
+        self.update_sidebar_total_items();
+        let removed = format!("Removed {} stale worktree entries", result.removed_count);
+        if result.errors.is_empty() {
+            self.status_message = Some(removed);
+        } else {
+            self.error_message = Some(format!("{}; {}", removed, result.errors.join("; ")));
+        }
+        Ok(())
+    }
+
     /// Subscribe to daemon events
     pub(super) async fn subscribe_events(&mut self) {
         use tokio::sync::mpsc;
         use tokio_stream::StreamExt;
 
         debug!("Subscribing to daemon events");
-        match self.client.subscribe_events(None).await {
+        match self.client.subscribe_events(None, true).await {
             Ok(mut stream) => {
                 debug!("Event subscription successful");
                 let (tx, rx) = mpsc::channel(64);
@@ -438,3 +730,20 @@ impl App {
         }
     }
 }
+
+/// Expand a leading `~` and make a path absolute against the current directory
+fn resolve_user_path(input: &str) -> PathBuf {
+    let path = match input.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => dirs::home_dir()
+            .map(|home| home.join(rest.trim_start_matches('/')))
+            .unwrap_or_else(|| PathBuf::from(input)),
+        _ => PathBuf::from(input),
+    };
+    if path.is_absolute() {
+        path
+    } else {
+        std::env::current_dir()
+            .map(|cwd| cwd.join(&path))
+            .unwrap_or(path)
+    }
+}
@@ -0,0 +1,342 @@
+//! Session search (names and transcripts) and history search
+
+use super::super::state::{AsyncAction, Focus, InputMode, SidebarItem};
+use super::super::App;
+use crate::error::TuiError;
+use amux_proto::daemon::{HistoryMatch, SessionInfo};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_stream::StreamExt;
+
+type Result<T> = std::result::Result<T, TuiError>;
+
+/// A running history search; dropping it cancels the search on the daemon
+pub struct HistorySearch {
+    pub rx: mpsc::Receiver<HistoryMatch>,
+    task: JoinHandle<()>,
+}
+
+impl Drop for HistorySearch {
+    fn drop(&mut self) {
+        // Dropping the response stream tells the daemon to stop scanning
+        self.task.abort();
+    }
+}
+
+impl App {
+    /// Open the session search prompt
+    pub fn start_search_sessions(&mut self) {
+        self.save_focus();
+        self.text_input.clear();
+        self.input_mode = InputMode::SearchSessions {
+            query: String::new(),
+            results: Vec::new(),
+            selected_index: 0,
+        };
+    }
+
+    /// Search all sessions whose name or transcript contains `query`
+    ///
+    /// Results are shown only while the search prompt is still open.
+    pub async fn search_sessions(&mut self, query: &str) -> Result<()> {
+        let found = if query.trim().is_empty() {
+            Vec::new()
+        } else {
+            self.client.search_sessions(query).await?
+        };
+
+        if let InputMode::SearchSessions {
+            query: shown,
+            results,
+            selected_index,
+        } = &mut self.input_mode
+        {
+            *shown = query.to_string();
+            *results = found;
+            *selected_index = 0;
+        }
+        Ok(())
+    }
+
+    /// Close the search prompt and navigate to the selected result
+    pub async fn submit_search_sessions(&mut self) -> Result<()> {
+        let session = match &self.input_mode {
+            InputMode::SearchSessions {
+                results,
+                selected_index,
+                ..
+            } => results.get(*selected_index).cloned(),
+            _ => return Ok(()),
+        };
+
+        self.input_mode = InputMode::Normal;
+        self.text_input.clear();
+        self.restore_focus();
+
+        match session {
+            Some(session) => self.navigate_to_session(&session).await,
+            None => {
+                self.status_message = Some("No matching session".to_string());
+                Ok(())
+            }
+        }
+    }
+
+    /// Select a session in the sidebar, switching repo and worktree if needed
+    pub async fn navigate_to_session(&mut self, session: &SessionInfo) -> Result<()> {
+        if self.current_repo_id.as_deref() != Some(session.repo_id.as_str()) {
+            if !self.repo_order.contains(&session.repo_id) {
+                self.error_message = Some("Session's repository is no longer loaded".to_string());
+                return Ok(());
+            }
+            self.current_repo_id = Some(session.repo_id.clone());
+            self.refresh_branches().await?;
+        }
+
+        let Some(wt_idx) = self
+            .worktrees()
+            .iter()
+            .position(|w| w.branch == session.branch)
+        else {
+            self.error_message = Some(format!("Worktree not found: {}", session.branch));
+            return Ok(());
+        };
+
+        if let Some(repo) = self.current_repo_mut() {
+            repo.expanded_worktrees.insert(wt_idx);
+        }
+        self.load_worktree_sessions(wt_idx).await?;
+
+        let Some(cursor) = self.sidebar_position_of(wt_idx, &session.id) else {
+            self.error_message = Some(format!("Session not found: {}", session.name));
+            return Ok(());
+        };
+        if let Some(repo) = self.current_repo_mut() {
+            repo.sidebar_cursor = cursor;
+        }
+
+        self.focus = Focus::Sidebar;
+        self.dirty.sidebar = true;
+        if self.update_selection_from_sidebar() {
+            self.load_git_status().await?;
+        }
+        self.status_message = Some(format!("Jumped to {}", session.name));
+        Ok(())
+    }
+
+    /// Select a session by ID in whichever repo holds it and attach its terminal
+    ///
+    /// Only sessions already loaded into a repo's state are found; returns
+    /// false, with an error message, for any other ID.
+    pub fn jump_to_session(&mut self, session_id: &str) -> bool {
+        let found = self.repo_states.iter().find_map(|(repo_id, repo)| {
+            let session = repo
+                .sessions
+                .iter()
+                .chain(repo.sessions_by_worktree.values().flatten())
+                .find(|s| s.id == session_id)?;
+            let wt_idx = repo
+                .worktrees
+                .iter()
+                .position(|w| w.branch == session.branch)?;
+            Some((repo_id.clone(), wt_idx, session.branch.clone()))
+        });
+        let Some((repo_id, wt_idx, branch)) = found else {
+            self.error_message = Some(format!("Session not found: {}", session_id));
+            return false;
+        };
+
+        self.current_repo_id = Some(repo_id);
+        if let Some(repo) = self.current_repo_mut() {
+            repo.expanded_worktrees.insert(wt_idx);
+            // The tree lists sessions from the per-worktree cache
+            if !repo.sessions_by_worktree.contains_key(&wt_idx) {
+                let sessions = repo
+                    .sessions
+                    .iter()
+                    .filter(|s| s.branch == branch)
+                    .cloned()
+                    .collect();
+                repo.sessions_by_worktree.insert(wt_idx, sessions);
+            }
+        }
+        self.update_sidebar_total_items();
+        self.dirty.sidebar = true;
+
+        let Some(cursor) = self.sidebar_position_of(wt_idx, session_id) else {
+            self.error_message = Some(format!("Session not found: {}", session_id));
+            return false;
+        };
+        if let Some(repo) = self.current_repo_mut() {
+            repo.sidebar_cursor = cursor;
+        }
+        self.update_selection_from_sidebar();
+        self.deferred_action = Some(AsyncAction::ConnectStream);
+        true
+    }
+
+    /// Sidebar cursor position of a session row
+    pub(super) fn sidebar_position_of(&self, wt_idx: usize, session_id: &str) -> Option<usize> {
+        let s_idx = self
+            .current_repo()?
+            .sessions_by_worktree
+            .get(&wt_idx)?
+            .iter()
+            .position(|s| s.id == session_id)?;
+        self.sidebar_row_of(&SidebarItem::Session(wt_idx, s_idx))
+    }
+
+    /// Move the search result selection up or down
+    pub fn move_search_selection(&mut self, down: bool) {
+        if let InputMode::SearchSessions {
+            results,
+            selected_index,
+            ..
+        } = &mut self.input_mode
+        {
+            if down && *selected_index + 1 < results.len() {
+                *selected_index += 1;
+            } else if !down && *selected_index > 0 {
+                *selected_index -= 1;
+            }
+        }
+    }
+
+    /// Open the history search prompt
+    pub fn start_search_history(&mut self) {
+        self.save_focus();
+        self.text_input.clear();
+        self.history_search = None;
+        self.input_mode = InputMode::SearchHistory {
+            query: String::new(),
+            regex: false,
+            results: Vec::new(),
+            selected_index: 0,
+            searching: false,
+        };
+    }
+
+    /// Restart the history search of the current repo's sessions for `query`
+    ///
+    /// Matches arrive through `history_search` and are added by
+    /// `on_history_match` while the prompt stays open.
+    pub async fn search_history(&mut self, query: &str) -> Result<()> {
+        self.history_search = None;
+        let InputMode::SearchHistory {
+            query: shown,
+            regex,
+            results,
+            selected_index,
+            searching,
+        } = &mut self.input_mode
+        else {
+            return Ok(());
+        };
+        *shown = query.to_string();
+        results.clear();
+        *selected_index = 0;
+        *searching = !query.trim().is_empty();
+        if !*searching {
+            return Ok(());
+        }
+        let regex = *regex;
+
+        let repo_id = self.current_repo_id.clone();
+        match self
+            .client
+            .search_session_history(repo_id.as_deref(), query, regex)
+            .await
+        {
+            Ok(mut stream) => {
+                let (tx, rx) = mpsc::channel(64);
+                let task = tokio::spawn(async move {
+                    while let Some(Ok(found)) = stream.next().await {
+                        if tx.send(found).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+                self.history_search = Some(HistorySearch { rx, task });
+            }
+            Err(e) => {
+                if let InputMode::SearchHistory { searching, .. } = &mut self.input_mode {
+                    *searching = false;
+                }
+                self.error_message = Some(format!("History search failed: {}", e));
+            }
+        }
+        Ok(())
+    }
+
+    /// Add a streamed history match (`None` once the search is over)
+    pub fn on_history_match(&mut self, found: Option<HistoryMatch>) {
+        let InputMode::SearchHistory {
+            results, searching, ..
+        } = &mut self.input_mode
+        else {
+            self.history_search = None;
+            return;
+        };
+        match found {
+            Some(found) => results.push(found),
+            None => {
+                *searching = false;
+                self.history_search = None;
+            }
+        }
+    }
+
+    /// Close the history search and show the selected match in its session
+    pub async fn submit_search_history(&mut self) -> Result<()> {
+        let found = match &self.input_mode {
+            InputMode::SearchHistory {
+                results,
+                selected_index,
+                ..
+            } => results.get(*selected_index).cloned(),
+            _ => return Ok(()),
+        };
+
+        self.history_search = None;
+        self.input_mode = InputMode::Normal;
+        self.text_input.clear();
+        self.restore_focus();
+
+        let Some(found) = found else {
+            self.status_message = Some("No matching output".to_string());
+            return Ok(());
+        };
+        let session = self
+            .repo_states
+            .values()
+            .flat_map(|r| r.sessions.iter())
+            .find(|s| s.id == found.session_id)
+            .cloned();
+        let Some(session) = session else {
+            self.error_message = Some(format!("Session not found: {}", found.session_name));
+            return Ok(());
+        };
+
+        self.navigate_to_session(&session).await?;
+        if self.terminal.active_session_id.as_deref() == Some(session.id.as_str()) {
+            self.jump_to_history(found.byte_offset).await?;
+        }
+        Ok(())
+    }
+
+    /// Move the history match selection up or down
+    pub fn move_history_selection(&mut self, down: bool) {
+        if let InputMode::SearchHistory {
+            results,
+            selected_index,
+            ..
+        } = &mut self.input_mode
+        {
+            if down && *selected_index + 1 < results.len() {
+                *selected_index += 1;
+            } else if !down && *selected_index > 0 {
+                *selected_index -= 1;
+            }
+        }
+    }
+}
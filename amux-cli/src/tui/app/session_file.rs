@@ -0,0 +1,43 @@
+//! Viewer for the provider's own file of a session
+
+use super::super::session_file::pretty_lines;
+use super::super::state::{InputMode, SidebarItem};
+use super::super::App;
+use crate::error::TuiError;
+
+type Result<T> = std::result::Result<T, TuiError>;
+
+impl App {
+    /// Open the selected session's provider file (Claude's JSONL) read-only
+    pub async fn view_session_file(&mut self) -> Result<()> {
+        let SidebarItem::Session(wt_idx, s_idx) = self.current_sidebar_item() else {
+            self.error_message = Some("No session selected".to_string());
+            return Ok(());
+        };
+        let Some(session_id) = self
+            .current_repo()
+            .and_then(|r| r.sessions_by_worktree.get(&wt_idx))
+            .and_then(|sessions| sessions.get(s_idx))
+            .map(|s| s.id.clone())
+        else {
+            return Ok(());
+        };
+
+        let file = match self.client.get_provider_session_file(&session_id).await {
+            Ok(file) => file,
+            Err(e) => {
+                self.error_message = Some(format!("Failed to read session file: {}", e));
+                return Ok(());
+            }
+        };
+
+        self.save_focus();
+        self.input_mode = InputMode::SessionFile {
+            path: file.path,
+            lines: pretty_lines(&file.content),
+            scroll: 0,
+            truncated: file.truncated,
+        };
+        Ok(())
+    }
+}
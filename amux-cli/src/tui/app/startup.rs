@@ -0,0 +1,103 @@
+//! What the TUI does once loaded (`startup_action`)
+//!
+//! The action is queued by `App::new` and runs after the first frame is
+//! drawn. It is dropped if the user has already moved the cursor, switched
+//! repo or opened a prompt, and a session that no longer exists only leaves
+//! a status message.
+
+use super::super::state::{AsyncAction, Focus, InputMode};
+use super::App;
+use crate::error::TuiError;
+use amux_config::StartupAction;
+use amux_proto::daemon::SessionStatus;
+
+type Result<T> = std::result::Result<T, TuiError>;
+
+impl App {
+    /// Queue `startup_action` to run once the first frame is drawn
+    pub(super) fn queue_startup_action(&mut self) {
+        if self.config.options.startup_action == StartupAction::None {
+            return;
+        }
+        self.deferred_action = Some(AsyncAction::RunStartupAction {
+            repo_id: self.current_repo_id.clone(),
+            sidebar_cursor: self.sidebar_cursor(),
+        });
+    }
+
+    /// Run `startup_action` unless the user has navigated since it was queued
+    pub async fn run_startup_action(
+        &mut self,
+        repo_id: Option<String>,
+        sidebar_cursor: usize,
+    ) -> Result<()> {
+        let untouched = self.current_repo_id == repo_id
+            && self.sidebar_cursor() == sidebar_cursor
+            && self.input_mode == InputMode::Normal
+            && self.focus == Focus::Sidebar
+            && self.terminal.active_session_id.is_none();
+        if !untouched {
+            return Ok(());
+        }
+
+        match self.config.options.startup_action.clone() {
+            StartupAction::None => Ok(()),
+            StartupAction::LastSession => {
+                let Some(recent) = self.recent_sessions.front() else {
+                    return Ok(());
+                };
+                let session_id = recent.session_id.clone();
+                self.open_startup_session(&session_id).await
+            }
+            StartupAction::FirstRunningSession => {
+                self.open_first_running_session();
+                Ok(())
+            }
+            StartupAction::SpecificSession(session) => self.open_startup_session(&session).await,
+            StartupAction::NewSession => self.create_new().await,
+        }
+    }
+
+    fn sidebar_cursor(&self) -> usize {
+        self.current_repo().map_or(0, |r| r.sidebar_cursor)
+    }
+
+    /// Open the session with this ID or name, wherever it is
+    async fn open_startup_session(&mut self, id_or_name: &str) -> Result<()> {
+        let session = self
+            .client
+            .list_sessions(None, None)
+            .await?
+            .into_iter()
+            .filter(|s| s.status != SessionStatus::Trashed as i32)
+            .find(|s| s.id == id_or_name || s.name == id_or_name);
+        let Some(session) = session else {
+            self.status_message = Some(format!("Startup session not found: {}", id_or_name));
+            return Ok(());
+        };
+
+        self.navigate_to_session(&session).await?;
+        if self.terminal.active_session_id.as_deref() == Some(session.id.as_str()) {
+            self.enter_terminal().await?;
+        }
+        Ok(())
+    }
+
+    /// Open the first running session, in sidebar order of the repos
+    fn open_first_running_session(&mut self) {
+        let running = self.repo_order.iter().find_map(|repo_id| {
+            let repo = self.repo_states.get(repo_id)?;
+            repo.sessions
+                .iter()
+                .chain(repo.sessions_by_worktree.values().flatten())
+                .find(|s| s.status == SessionStatus::Running as i32)
+                .map(|s| s.id.clone())
+        });
+        match running {
+            Some(session_id) => {
+                self.jump_to_session(&session_id);
+            }
+            None => self.status_message = Some("No running session".to_string()),
+        }
+    }
+}
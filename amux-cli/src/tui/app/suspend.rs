@@ -0,0 +1,56 @@
+//! Leaving the TUI for a shell, and coming back
+//!
+//! Like the editor, the shell only leaves a request behind; the main loop
+//! hands it the terminal. Job-control suspension (`Ctrl-z`, SIGTSTP) is
+//! handled by the main loop too, and both end in [`App::resumed`].
+
+use super::super::state::RightPanelView;
+use super::App;
+use crate::error::TuiError;
+use std::path::PathBuf;
+use std::process::ExitStatus;
+
+type Result<T> = std::result::Result<T, TuiError>;
+
+impl App {
+    /// Drop to a login shell in the current worktree, without a session
+    pub fn suspend_to_shell(&mut self) {
+        let Some(cwd) = self.current_worktree().map(|w| PathBuf::from(&w.path)) else {
+            self.error_message = Some("No worktree selected".to_string());
+            return;
+        };
+        self.shell_request = Some(cwd);
+    }
+
+    /// Refresh what the shell may have changed, reporting if it didn't start
+    ///
+    /// A shell exits with the status of its last command, which says
+    /// nothing about the shell itself.
+    pub async fn shell_closed(&mut self, status: std::io::Result<ExitStatus>) -> Result<()> {
+        let refreshed = self.resumed().await;
+        if let Err(e) = status {
+            self.error_message = Some(format!("Failed to run shell: {}", e));
+        }
+        refreshed
+    }
+
+    /// Reload everything once the terminal is back, anything may have changed
+    pub async fn resumed(&mut self) -> Result<()> {
+        self.refresh_all().await?;
+        if self.sidebar.git_panel_enabled {
+            self.load_git_status().await?;
+        }
+        if self.right_panel_view == RightPanelView::Diff {
+            self.load_diff_files().await?;
+        }
+        Ok(())
+    }
+}
+
+/// The user's login shell, else `sh`
+pub fn login_shell() -> String {
+    std::env::var("SHELL")
+        .ok()
+        .filter(|shell| !shell.trim().is_empty())
+        .unwrap_or_else(|| "sh".to_string())
+}
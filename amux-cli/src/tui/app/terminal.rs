@@ -1,9 +1,11 @@
 //! Terminal operations and stream management
 
+use super::super::hyperlink::open_uri;
+use super::super::selection;
 use super::super::state::{Focus, RightPanelView, TerminalMode};
 use super::super::App;
 use crate::error::TuiError;
-use amux_config::{DEFAULT_SCROLLBACK, DEFAULT_TERMINAL_COLS, DEFAULT_TERMINAL_ROWS};
+use amux_config::{QuitBehavior, DEFAULT_SCROLLBACK, DEFAULT_TERMINAL_COLS, DEFAULT_TERMINAL_ROWS};
 use amux_proto::daemon::AttachInput;
 use crossterm::terminal::size;
 use std::sync::{Arc, Mutex};
@@ -42,16 +44,83 @@ impl App {
             self.disconnect_stream();
             self.connect_stream().await?;
         }
+        self.refuse_insert_when_external();
 
         Ok(())
     }
 
     /// Enter Insert mode (from Normal mode)
     pub fn enter_insert_mode(&mut self) {
+        if self.refuse_insert_when_external() {
+            return;
+        }
         self.terminal.mode = TerminalMode::Insert;
         self.scroll_to_bottom();
     }
 
+    /// Whether another client (e.g. an external window) is attached to the
+    /// active session, making the preview read-only
+    pub fn attached_externally(&self) -> bool {
+        let Some(session) = self.active_session() else {
+            return false;
+        };
+        let own = self
+            .terminal_stream
+            .as_ref()
+            .is_some_and(|s| s.session_id == session.id);
+        session.attach_count > u32::from(own)
+    }
+
+    /// Keep Normal mode while attached externally; returns true if refused
+    fn refuse_insert_when_external(&mut self) -> bool {
+        if !self.attached_externally() {
+            return false;
+        }
+        self.terminal.mode = TerminalMode::Normal;
+        self.status_message =
+            Some("Session is attached externally, preview is read-only".to_string());
+        true
+    }
+
+    /// Attach the active session from a new terminal window
+    ///
+    /// Runs `external_terminal_command` through `sh -c`, with `{session_id}`
+    /// replaced by the session's id.
+    pub fn open_in_external_terminal(&mut self) {
+        let template = self.config.options.external_terminal_command.trim();
+        if template.is_empty() {
+            self.error_message = Some(
+                "Set options.external_terminal_command, e.g. \"wezterm start -- amux attach {session_id}\""
+                    .to_string(),
+            );
+            return;
+        }
+        let Some(session) = self.active_session() else {
+            self.error_message = Some("No session selected".to_string());
+            return;
+        };
+
+        let command = template.replace("{session_id}", &session.id);
+        let name = session.name.clone();
+        let spawned = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(&command)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn();
+        match spawned {
+            Ok(mut child) => {
+                // Reap the launcher whenever it exits
+                std::thread::spawn(move || child.wait());
+                self.status_message = Some(format!("Opened {} in external terminal", name));
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to run external terminal: {}", e));
+            }
+        }
+    }
+
     /// Exit terminal mode (back to sidebar)
     pub fn exit_terminal(&mut self) {
         if self.terminal.fullscreen {
@@ -104,6 +173,85 @@ impl App {
         }
     }
 
+    /// Open the hyperlink under the terminal cursor
+    pub fn open_hyperlink_at_cursor(&mut self) {
+        let Ok(parser) = self.terminal.parser.lock() else {
+            return;
+        };
+        let (row, col) = parser.screen().cursor_position();
+        let uri = self
+            .terminal
+            .hyperlinks
+            .iter()
+            .find(|span| span.contains(row, col) && span.is_visible(parser.screen()))
+            .map(|span| span.uri.clone());
+        drop(parser);
+
+        self.open_hyperlink(uri);
+    }
+
+    /// Open the hyperlink at a cell of the terminal screen (mouse click)
+    pub fn open_hyperlink_at(&mut self, row: u16, col: u16) -> bool {
+        let uri = {
+            let Ok(parser) = self.terminal.parser.lock() else {
+                return false;
+            };
+            self.terminal
+                .hyperlinks
+                .iter()
+                .find(|span| span.contains(row, col) && span.is_visible(parser.screen()))
+                .map(|span| span.uri.clone())
+        };
+        if uri.is_none() {
+            return false;
+        }
+        self.open_hyperlink(uri);
+        true
+    }
+
+    /// Select the word at a cell of the terminal screen (double click)
+    pub fn select_word_at(&mut self, row: u16, col: u16) {
+        let Ok(parser) = self.terminal.parser.lock() else {
+            return;
+        };
+        let cells = selection::row_cells(parser.screen(), row);
+        drop(parser);
+        self.terminal.selection =
+            selection::word_bounds(&cells, col as usize, &self.config.options.word_delimiters)
+                .map(|(start, end)| (row, start as u16, row, end as u16));
+    }
+
+    /// Select a whole row of the terminal screen (triple click)
+    pub fn select_line_at(&mut self, row: u16) {
+        let (rows, cols) = self
+            .terminal
+            .parser
+            .lock()
+            .map_or((0, 0), |parser| parser.screen().size());
+        self.terminal.selection = (row < rows && cols > 0).then(|| (row, 0, row, cols - 1));
+    }
+
+    /// Text of the terminal selection
+    pub fn terminal_selection_text(&self) -> Option<String> {
+        let selection = self.terminal.selection?;
+        let parser = self.terminal.parser.lock().ok()?;
+        Some(selection::selected_text(parser.screen(), selection)).filter(|t| !t.is_empty())
+    }
+
+    fn open_hyperlink(&mut self, uri: Option<String>) {
+        if !self.config.options.open_links {
+            self.status_message = Some("Opening links is disabled (open_links)".to_string());
+            return;
+        }
+        match uri {
+            Some(uri) => match open_uri(&uri) {
+                Ok(()) => self.status_message = Some(format!("Opened {}", uri)),
+                Err(e) => self.error_message = Some(format!("Failed to open link: {}", e)),
+            },
+            None => self.status_message = Some("No link under cursor".to_string()),
+        }
+    }
+
     /// Connect to session stream for preview/interaction
     pub async fn connect_stream(&mut self) -> Result<()> {
         let session_id = match &self.terminal.active_session_id {
@@ -112,14 +260,8 @@ impl App {
         };
 
         // Get terminal size and calculate inner area
-        // Layout: Tab bar (3) + Main content + Status bar (3)
-        // Main content: Sidebar (38 fixed) + Terminal (remaining)
-        // Terminal has borders (2 lines, 2 cols)
         let (full_cols, full_rows) = size().map_err(TuiError::TerminalInit)?;
-        let main_height = full_rows.saturating_sub(6); // tab + status bars
-        let terminal_width = full_cols.saturating_sub(38); // screen - sidebar
-        let inner_rows = main_height.saturating_sub(2); // borders
-        let inner_cols = terminal_width.saturating_sub(2); // borders
+        let (inner_rows, inner_cols) = self.inner_terminal_size(full_cols, full_rows);
 
         // Resize vt100 parser to match
         if let Ok(mut parser) = self.terminal.parser.lock() {
@@ -161,6 +303,11 @@ impl App {
             }
         });
 
+        // Whatever the session printed meanwhile is on screen now
+        if self.sidebar.unread.remove(&session_id).is_some() {
+            self.dirty.sidebar = true;
+        }
+
         self.terminal_stream = Some(TerminalStream {
             session_id,
             input_tx,
@@ -170,9 +317,92 @@ impl App {
         Ok(())
     }
 
+    /// Show the active session's output scrolled to a history offset
+    ///
+    /// The history is replayed onto a fresh screen so the offset (from a
+    /// history search match) maps onto its lines.
+    pub async fn jump_to_history(&mut self, offset: u64) -> Result<()> {
+        let Some(session_id) = self.terminal.active_session_id.clone() else {
+            return Ok(());
+        };
+
+        self.disconnect_stream();
+        self.terminal.parser = Arc::new(Mutex::new(vt100::Parser::new(
+            DEFAULT_TERMINAL_ROWS,
+            DEFAULT_TERMINAL_COLS,
+            DEFAULT_SCROLLBACK,
+        )));
+        self.terminal
+            .session_parsers
+            .insert(session_id, self.terminal.parser.clone());
+        self.terminal.scroll_offset = 0;
+        self.terminal.history_jump = Some(offset);
+
+        self.focus = Focus::Terminal;
+        self.right_panel_view = RightPanelView::Terminal;
+        self.terminal.mode = TerminalMode::Normal;
+        self.connect_stream().await
+    }
+
+    /// Scroll to the pending history jump once its replay has been processed
+    pub fn apply_history_jump(&mut self, replay: &[u8]) {
+        let Some(offset) = self.terminal.history_jump.take() else {
+            return;
+        };
+        if let Ok(mut parser) = self.terminal.parser.lock() {
+            // Lines printed after the match, less half a screen to center it
+            let start = usize::try_from(offset).unwrap_or(usize::MAX);
+            let lines_after = replay
+                .get(start..)
+                .map_or(0, |rest| rest.iter().filter(|&&b| b == b'\n').count());
+            let rows = parser.screen().size().0 as usize;
+            parser
+                .screen_mut()
+                .set_scrollback(lines_after.saturating_sub(rows / 2));
+            self.terminal.scroll_offset = parser.screen().scrollback();
+        }
+    }
+
     /// Disconnect from session stream
     pub fn disconnect_stream(&mut self) {
         self.terminal_stream = None;
+        // Link positions belong to the screen of the stream being dropped
+        self.terminal.hyperlinks.clear();
+        self.terminal.pending_hyperlink = None;
+        self.terminal.selection = None;
+    }
+
+    /// Sessions affected by `quit_behavior`, as (session_id, running)
+    fn quit_targets(&self) -> Vec<(String, bool)> {
+        self.repo_states
+            .values()
+            .flat_map(|r| r.sessions.iter())
+            .map(|s| (s.id.clone(), s.status == 1)) // SESSION_STATUS_RUNNING
+            .collect()
+    }
+
+    /// Apply `quit_behavior` to known sessions and disconnect the stream
+    ///
+    /// Detach leaves daemon sessions alive so they can be reattached later.
+    /// Only a normal quit applies it; after a crash sessions keep running.
+    pub async fn apply_quit_behavior(&mut self) {
+        self.disconnect_stream();
+
+        let behavior = self
+            .quit_behavior_override
+            .unwrap_or(self.config.options.quit_behavior);
+        for (session_id, running) in self.quit_targets() {
+            match behavior {
+                QuitBehavior::Detach => {}
+                QuitBehavior::Stop if running => {
+                    let _ = self.client.stop_session(&session_id).await;
+                }
+                QuitBehavior::Stop => {}
+                QuitBehavior::Destroy => {
+                    let _ = self.client.destroy_session(&session_id).await;
+                }
+            }
+        }
     }
 
     /// Send data to terminal
@@ -199,10 +429,7 @@ impl App {
         self.terminal.rows = Some(rows);
 
         // Calculate inner area (same as connect_stream)
-        let main_height = rows.saturating_sub(6);
-        let terminal_width = cols.saturating_sub(38); // screen - sidebar
-        let inner_rows = main_height.saturating_sub(2);
-        let inner_cols = terminal_width.saturating_sub(2);
+        let (inner_rows, inner_cols) = self.inner_terminal_size(cols, rows);
 
         // Resize parser
         if let Ok(mut parser) = self.terminal.parser.lock() {
@@ -369,7 +596,10 @@ impl App {
                         Some(true),
                         None, // no model for shell sessions
                         None, // no prompt for shell sessions
+                        None, // no system prompt for shell sessions
                         None, // no provider for shell sessions
+                        &[],
+                        None,
                         Some(inner_rows as u32),
                         Some(inner_cols as u32),
                     )
@@ -423,10 +653,6 @@ impl App {
     pub fn get_inner_terminal_size(&self) -> (u16, u16) {
         let (full_cols, full_rows) =
             size().unwrap_or((DEFAULT_TERMINAL_COLS, DEFAULT_TERMINAL_ROWS));
-        let main_height = full_rows.saturating_sub(6); // tab + status bars
-        let terminal_width = full_cols.saturating_sub(38); // screen - sidebar
-        let inner_rows = main_height.saturating_sub(2); // borders
-        let inner_cols = terminal_width.saturating_sub(2); // borders
-        (inner_rows, inner_cols)
+        self.inner_terminal_size(full_cols, full_rows)
     }
 }
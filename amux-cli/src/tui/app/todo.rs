@@ -1,5 +1,6 @@
 //! TODO operations
 
+use super::super::state::{InputMode, UndoEntry};
 use super::super::App;
 use crate::error::TuiError;
 use amux_proto::daemon::TodoItem;
@@ -84,7 +85,21 @@ impl App {
 
     /// Delete a TODO item
     pub async fn delete_todo(&mut self, todo_id: &str) -> Result<()> {
-        self.client.delete_todo(todo_id).await?;
+        let deleted = self.client.delete_todo(todo_id).await?;
+        if let Some(repo_id) = deleted.first().map(|t| t.repo_id.clone()) {
+            let msg = format!("Deleted {} TODO item(s)", deleted.len());
+            self.push_undo(
+                UndoEntry::Todos {
+                    repo_id,
+                    items: deleted,
+                },
+                &msg,
+            );
+        }
+        if matches!(self.input_mode, InputMode::ConfirmDeleteTodo { .. }) {
+            self.input_mode = InputMode::TodoPopup;
+            self.restore_focus();
+        }
         self.load_todos().await?;
         Ok(())
     }
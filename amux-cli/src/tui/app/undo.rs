@@ -0,0 +1,66 @@
+//! Undo of destroyed sessions and deleted comments/TODOs
+
+use super::super::state::UndoEntry;
+use super::super::App;
+use crate::error::TuiError;
+use std::time::Duration;
+
+type Result<T> = std::result::Result<T, TuiError>;
+
+impl App {
+    /// Remember a destructive action so `undo` can revert it
+    pub fn push_undo(&mut self, entry: UndoEntry, done: &str) {
+        self.undo.push(entry);
+        self.status_message = Some(format!(
+            "{} — press u to undo ({}s)",
+            done, self.config.options.trash_ttl_secs
+        ));
+    }
+
+    /// Revert the most recent destructive action that hasn't expired
+    pub async fn undo(&mut self) -> Result<()> {
+        let ttl = Duration::from_secs(self.config.options.trash_ttl_secs);
+        let Some(entry) = self.undo.pop(ttl) else {
+            self.status_message = Some("Nothing to undo".to_string());
+            return Ok(());
+        };
+
+        match entry {
+            UndoEntry::Session { session_id, name } => {
+                match self.client.restore_session(&session_id).await {
+                    Ok(_) => {
+                        self.refresh_sessions().await?;
+                        self.load_worktree_sessions(self.branch_idx()).await?;
+                        self.status_message = Some(format!("Restored session: {}", name));
+                    }
+                    Err(e) => {
+                        self.error_message = Some(format!("Failed to restore session: {}", e));
+                    }
+                }
+            }
+            UndoEntry::Comment(comment) => match self.client.restore_line_comment(comment).await {
+                Ok(_) => {
+                    self.load_comments().await?;
+                    self.status_message = Some("Comment restored".to_string());
+                }
+                Err(e) => {
+                    self.error_message = Some(format!("Failed to restore comment: {}", e));
+                }
+            },
+            UndoEntry::Todos { repo_id, items } => {
+                match self.client.restore_todos(&repo_id, items).await {
+                    Ok(restored) => {
+                        self.load_todos().await?;
+                        self.status_message =
+                            Some(format!("Restored {} TODO item(s)", restored.len()));
+                    }
+                    Err(e) => {
+                        self.error_message = Some(format!("Failed to restore TODO: {}", e));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+//! Branch name validation, following `git check-ref-format --branch`
+//!
+//! Checked while a name is typed so the offending part can be highlighted
+//! before the daemon gets to reject it.
+
+use std::ops::Range;
+
+/// Characters git never allows in a ref name
+const FORBIDDEN_CHARS: &[char] = &[' ', '~', '^', ':', '?', '*', '[', '\\'];
+
+/// Sequences git never allows in a ref name
+const FORBIDDEN_SEQUENCES: &[&str] = &["..", "@{", "//"];
+
+/// Why a branch name is invalid, and where
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchNameError {
+    pub reason: String,
+    /// Byte range of the offending part of the name
+    pub range: Range<usize>,
+}
+
+impl BranchNameError {
+    fn new(reason: impl Into<String>, range: Range<usize>) -> Self {
+        Self {
+            reason: reason.into(),
+            range,
+        }
+    }
+}
+
+/// Check `name` against git's rules for branch names
+pub fn validate(name: &str) -> Result<(), BranchNameError> {
+    if name.is_empty() {
+        return Err(BranchNameError::new("Branch name is empty", 0..0));
+    }
+    if name.starts_with('-') {
+        return Err(BranchNameError::new("Cannot start with '-'", 0..1));
+    }
+    if name == "@" || name == "HEAD" {
+        return Err(BranchNameError::new(
+            format!("'{}' is reserved", name),
+            0..name.len(),
+        ));
+    }
+
+    if let Some((i, c)) = name
+        .char_indices()
+        .find(|&(_, c)| c.is_ascii_control() || FORBIDDEN_CHARS.contains(&c))
+    {
+        let reason = match c {
+            ' ' => "Spaces are not allowed".to_string(),
+            c if c.is_ascii_control() => "Control characters are not allowed".to_string(),
+            c => format!("'{}' is not allowed", c),
+        };
+        return Err(BranchNameError::new(reason, i..i + c.len_utf8()));
+    }
+    for sequence in FORBIDDEN_SEQUENCES {
+        if let Some(i) = name.find(sequence) {
+            return Err(BranchNameError::new(
+                format!("'{}' is not allowed", sequence),
+                i..i + sequence.len(),
+            ));
+        }
+    }
+
+    if name.starts_with('/') {
+        return Err(BranchNameError::new("Cannot start with '/'", 0..1));
+    }
+    if name.ends_with('/') || name.ends_with('.') {
+        let last = name.len() - 1;
+        return Err(BranchNameError::new(
+            format!("Cannot end with '{}'", &name[last..]),
+            last..name.len(),
+        ));
+    }
+
+    let mut start = 0;
+    for component in name.split('/') {
+        if component.starts_with('.') {
+            return Err(BranchNameError::new(
+                "Parts cannot start with '.'",
+                start..start + 1,
+            ));
+        }
+        if component.ends_with(".lock") {
+            let end = start + component.len();
+            return Err(BranchNameError::new(
+                "Parts cannot end with '.lock'",
+                end - ".lock".len()..end,
+            ));
+        }
+        start += component.len() + 1;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range_of(name: &str) -> Range<usize> {
+        validate(name).unwrap_err().range
+    }
+
+    #[test]
+    fn test_valid_names() {
+        for name in [
+            "main",
+            "feature/login",
+            "fix-123",
+            "v1.2",
+            "修复/中文",
+            "a@b",
+        ] {
+            assert!(validate(name).is_ok(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_invalid_characters() {
+        assert_eq!(range_of("my branch"), 2..3);
+        assert_eq!(
+            validate("my branch").unwrap_err().reason,
+            "Spaces are not allowed"
+        );
+        assert_eq!(range_of("a~1"), 1..2);
+        assert_eq!(range_of("what?"), 4..5);
+        assert_eq!(range_of("a:b"), 1..2);
+        assert_eq!(range_of("tab\there"), 3..4);
+        assert_eq!(range_of("中文 x"), 6..7);
+    }
+
+    #[test]
+    fn test_invalid_sequences() {
+        assert_eq!(range_of("a..b"), 1..3);
+        assert_eq!(range_of("a@{1}"), 1..3);
+        assert_eq!(range_of("feature//x"), 7..9);
+    }
+
+    #[test]
+    fn test_invalid_positions() {
+        assert_eq!(range_of("-x"), 0..1);
+        assert_eq!(range_of("/x"), 0..1);
+        assert_eq!(range_of("x/"), 1..2);
+        assert_eq!(range_of("x."), 1..2);
+        assert_eq!(range_of("HEAD"), 0..4);
+        assert_eq!(range_of("@"), 0..1);
+        assert_eq!(range_of(""), 0..0);
+    }
+
+    #[test]
+    fn test_invalid_components() {
+        assert_eq!(range_of(".hidden"), 0..1);
+        assert_eq!(range_of("feature/.x"), 8..9);
+        assert_eq!(range_of("refs.lock"), 4..9);
+        assert_eq!(range_of("a.lock/b"), 1..6);
+    }
+}
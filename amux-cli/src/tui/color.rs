@@ -0,0 +1,219 @@
+//! Terminal color support
+//!
+//! Themes, syntax highlighting and diff tints are all RGB. On terminals
+//! without true color every drawn frame has its RGB colors replaced by the
+//! nearest of the 256 or 16 colors the terminal has, rather than letting
+//! the terminal pick one.
+
+use ratatui::buffer::Buffer;
+use ratatui::style::Color;
+
+/// Colors a terminal can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSupport {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+}
+
+/// Terminal programs with true color that don't set `COLORTERM`
+const TRUECOLOR_PROGRAMS: &[&str] = &["iTerm.app", "WezTerm", "vscode", "ghostty"];
+
+impl ColorSupport {
+    /// Support of the terminal amux runs in, from its environment
+    pub fn detect() -> Self {
+        Self::from_env(|name| std::env::var(name).ok())
+    }
+
+    /// Support told by `COLORTERM`, `TERM_PROGRAM` and `TERM`
+    ///
+    /// `COLORTERM=truecolor` (or `24bit`) and `-direct` terminfo names mean
+    /// true color, `-256color` ones 256 colors; anything else gets 16.
+    pub fn from_env(var: impl Fn(&str) -> Option<String>) -> Self {
+        let colorterm = var("COLORTERM").unwrap_or_default().to_lowercase();
+        let term = var("TERM").unwrap_or_default().to_lowercase();
+        let program = var("TERM_PROGRAM").unwrap_or_default();
+
+        if matches!(colorterm.as_str(), "truecolor" | "24bit")
+            || term.ends_with("-direct")
+            || term.contains("truecolor")
+            || TRUECOLOR_PROGRAMS.contains(&program.as_str())
+        {
+            ColorSupport::TrueColor
+        } else if term.contains("256color") {
+            ColorSupport::Ansi256
+        } else {
+            ColorSupport::Ansi16
+        }
+    }
+}
+
+/// The closest color to `color` the terminal can show
+pub fn quantize(color: Color, support: ColorSupport) -> Color {
+    match (support, color) {
+        (ColorSupport::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_256(r, g, b)),
+        (ColorSupport::Ansi16, Color::Rgb(r, g, b)) => nearest_16(r, g, b),
+        (ColorSupport::Ansi16, Color::Indexed(idx)) if idx >= 16 => {
+            let (r, g, b) = indexed_rgb(idx);
+            nearest_16(r, g, b)
+        }
+        _ => color,
+    }
+}
+
+/// Replace the colors of a drawn frame by ones the terminal can show
+pub fn quantize_buffer(buffer: &mut Buffer, support: ColorSupport) {
+    if support == ColorSupport::TrueColor {
+        return;
+    }
+    for cell in buffer.content.iter_mut() {
+        cell.fg = quantize(cell.fg, support);
+        cell.bg = quantize(cell.bg, support);
+    }
+}
+
+/// Channel values of the 6x6x6 color cube (indices 16 to 231)
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The standard 16 colors, as xterm draws them by default
+const ANSI_16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+/// Index of the closest color of the cube or the gray ramp (232 to 255)
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |v: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - v as i32).abs())
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+    let cube_idx = 16 + 36 * ri + 6 * gi + bi;
+
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_step = (average.saturating_sub(8) / 10).min(23);
+    let gray = (8 + 10 * gray_step) as u8;
+
+    if distance((gray, gray, gray), (r, g, b)) < distance(cube, (r, g, b)) {
+        232 + gray_step as u8
+    } else {
+        cube_idx as u8
+    }
+}
+
+/// Closest of the standard 16 colors
+fn nearest_16(r: u8, g: u8, b: u8) -> Color {
+    ANSI_16
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb, (r, g, b)))
+        .map_or(Color::Reset, |(color, _)| *color)
+}
+
+/// RGB value of a color of the 256-color palette
+fn indexed_rgb(idx: u8) -> (u8, u8, u8) {
+    match idx {
+        0..=15 => ANSI_16[idx as usize].1,
+        16..=231 => {
+            let idx = (idx - 16) as usize;
+            (
+                CUBE_LEVELS[idx / 36],
+                CUBE_LEVELS[idx / 6 % 6],
+                CUBE_LEVELS[idx % 6],
+            )
+        }
+        _ => {
+            let gray = 8 + 10 * (idx - 232);
+            (gray, gray, gray)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn detect(vars: &[(&str, &str)]) -> ColorSupport {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        ColorSupport::from_env(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(
+            detect(&[("COLORTERM", "truecolor"), ("TERM", "xterm")]),
+            ColorSupport::TrueColor
+        );
+        assert_eq!(detect(&[("TERM", "xterm-direct")]), ColorSupport::TrueColor);
+        assert_eq!(
+            detect(&[("TERM", "xterm-256color"), ("TERM_PROGRAM", "WezTerm")]),
+            ColorSupport::TrueColor
+        );
+        assert_eq!(
+            detect(&[("TERM", "screen-256color")]),
+            ColorSupport::Ansi256
+        );
+        assert_eq!(detect(&[("TERM", "xterm")]), ColorSupport::Ansi16);
+        assert_eq!(detect(&[]), ColorSupport::Ansi16);
+    }
+
+    #[test]
+    fn test_quantize_256() {
+        let q = |r, g, b| quantize(Color::Rgb(r, g, b), ColorSupport::Ansi256);
+        assert_eq!(q(255, 0, 0), Color::Indexed(196));
+        assert_eq!(q(0, 0, 0), Color::Indexed(16));
+        // Grays land on the ramp rather than the cube
+        assert_eq!(q(128, 128, 128), Color::Indexed(244));
+        assert_eq!(q(0x89, 0xb4, 0xfa), Color::Indexed(111));
+        // Colors that aren't RGB are left alone
+        assert_eq!(
+            quantize(Color::Indexed(42), ColorSupport::Ansi256),
+            Color::Indexed(42)
+        );
+    }
+
+    #[test]
+    fn test_quantize_16() {
+        let q = |color| quantize(color, ColorSupport::Ansi16);
+        assert_eq!(q(Color::Rgb(250, 10, 10)), Color::LightRed);
+        assert_eq!(q(Color::Rgb(30, 30, 46)), Color::Black);
+        assert_eq!(q(Color::Rgb(0, 190, 200)), Color::Cyan);
+        assert_eq!(q(Color::Indexed(196)), Color::LightRed);
+        assert_eq!(q(Color::Indexed(3)), Color::Indexed(3));
+        assert_eq!(q(Color::Reset), Color::Reset);
+    }
+
+    #[test]
+    fn test_truecolor_keeps_rgb() {
+        let mut buffer = Buffer::empty(ratatui::layout::Rect::new(0, 0, 2, 1));
+        buffer.content[0].fg = Color::Rgb(1, 2, 3);
+        quantize_buffer(&mut buffer, ColorSupport::TrueColor);
+        assert_eq!(buffer.content[0].fg, Color::Rgb(1, 2, 3));
+        quantize_buffer(&mut buffer, ColorSupport::Ansi256);
+        assert_eq!(buffer.content[0].fg, Color::Indexed(16));
+    }
+}
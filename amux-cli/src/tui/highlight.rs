@@ -1,15 +1,45 @@
 //! Syntax highlighting module using syntect
+//!
+//! A file's syntax is resolved once per path, in order: `.gitattributes`
+//! `linguist-language`, `[highlight.extensions]` overrides, extension (see
+//! [`EXTENSION_SYNTAXES`] for the ones mapped differently), file name, then
+//! a shebang first line. Syntaxes are syntect's defaults plus two-face's
+//! extra ones (TypeScript, Kotlin, Swift, TOML, ...). Files with no syntax
+//! are returned unstyled without running syntect.
 
 use ratatui::style::{Color, Modifier, Style};
+use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Mutex;
 use syntect::easy::HighlightLines;
 use syntect::highlighting::{Style as SyntectStyle, ThemeSet};
-use syntect::parsing::SyntaxSet;
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+
+/// Syntect theme used when the UI theme names one that doesn't exist
+const DEFAULT_THEME: &str = "base16-eighties.dark";
+
+/// Syntaxes for extensions the bundled syntaxes map to a less likely
+/// language (`.h` to Objective-C)
+const EXTENSION_SYNTAXES: &[(&str, &str)] = &[("h", "C")];
 
 /// Syntax highlighter using syntect
 pub struct Highlighter {
     syntax_set: SyntaxSet,
     theme_set: ThemeSet,
+    /// Name of the syntect theme in `theme_set` to highlight with
+    theme_name: Mutex<String>,
+    languages: Mutex<Languages>,
+}
+
+/// Language overrides and the syntax resolved for each file
+#[derive(Default)]
+struct Languages {
+    /// Syntax name by extension or file name, from the config
+    extensions: HashMap<String, String>,
+    /// `(pattern, language)` from the repo's `.gitattributes`, in file order
+    attributes: Vec<(String, String)>,
+    /// Index into the syntax set by file path (`None` = plain text)
+    resolved: HashMap<String, Option<usize>>,
 }
 
 impl Default for Highlighter {
@@ -22,28 +52,59 @@ impl Highlighter {
     /// Create a new highlighter with default syntaxes and themes
     pub fn new() -> Self {
         Self {
-            syntax_set: SyntaxSet::load_defaults_newlines(),
+            syntax_set: two_face::syntax::extra_newlines(),
             theme_set: ThemeSet::load_defaults(),
+            theme_name: Mutex::new(DEFAULT_THEME.to_string()),
+            languages: Mutex::new(Languages::default()),
         }
     }
 
-    /// Highlight a line of code based on file extension
+    /// Highlight with the syntect theme `name`, if it exists
+    ///
+    /// Returns false, keeping the current theme, for unknown names.
+    pub fn set_theme(&self, name: &str) -> bool {
+        if !self.theme_set.themes.contains_key(name) {
+            return false;
+        }
+        if let Ok(mut theme_name) = self.theme_name.lock() {
+            *theme_name = name.to_string();
+        }
+        true
+    }
+
+    /// Set the config's syntax names by extension or file name
+    pub fn set_extension_overrides(&self, extensions: HashMap<String, String>) {
+        if let Ok(mut languages) = self.languages.lock() {
+            if languages.extensions != extensions {
+                languages.extensions = extensions;
+                languages.resolved.clear();
+            }
+        }
+    }
+
+    /// Set the current repo's `.gitattributes` language rules
+    pub fn set_attributes(&self, attributes: Vec<(String, String)>) {
+        if let Ok(mut languages) = self.languages.lock() {
+            if languages.attributes != attributes {
+                languages.attributes = attributes;
+                languages.resolved.clear();
+            }
+        }
+    }
+
+    /// Highlight a line of code based on the file's resolved syntax
     /// Returns a vector of (style, text) pairs
     pub fn highlight_line<'a>(&self, line: &'a str, file_path: &str) -> Vec<(Style, &'a str)> {
-        // Get syntax from file extension
-        let extension = Path::new(file_path)
-            .extension()
-            .and_then(|e| e.to_str())
-            .unwrap_or("");
-
-        let syntax = self
-            .syntax_set
-            .find_syntax_by_extension(extension)
-            .or_else(|| self.syntax_set.find_syntax_by_first_line(line))
-            .unwrap_or_else(|| self.syntax_set.find_syntax_plain_text());
+        let Some(syntax) = self.syntax_for(file_path, line) else {
+            return vec![(Style::default(), line)];
+        };
 
-        // Use base16-eighties.dark theme (good for terminals)
-        let theme = &self.theme_set.themes["base16-eighties.dark"];
+        let theme = self
+            .theme_name
+            .lock()
+            .ok()
+            .and_then(|name| self.theme_set.themes.get(name.as_str()))
+            .unwrap_or(&self.theme_set.themes[DEFAULT_THEME]);
 
         let mut highlighter = HighlightLines::new(syntax, theme);
 
@@ -56,6 +117,87 @@ impl Highlighter {
         }
     }
 
+    /// Cached syntax of `file_path`
+    ///
+    /// A path without one is retried only on a shebang line, which can only
+    /// be the file's first line.
+    fn syntax_for(&self, file_path: &str, line: &str) -> Option<&SyntaxReference> {
+        let mut languages = self.languages.lock().ok()?;
+        let index = match languages.resolved.get(file_path) {
+            Some(None) if line.starts_with("#!") => {
+                let index = self.find_by_first_line(line);
+                languages.resolved.insert(file_path.to_string(), index);
+                index
+            }
+            Some(index) => *index,
+            None => {
+                let index = self.resolve(&languages, file_path).or_else(|| {
+                    line.starts_with("#!")
+                        .then(|| self.find_by_first_line(line))
+                        .flatten()
+                });
+                languages.resolved.insert(file_path.to_string(), index);
+                index
+            }
+        };
+        index.and_then(|i| self.syntax_set.syntaxes().get(i))
+    }
+
+    /// Resolve a syntax from overrides, extension and file name
+    fn resolve(&self, languages: &Languages, file_path: &str) -> Option<usize> {
+        let path = Path::new(file_path);
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+
+        let attribute = languages
+            .attributes
+            .iter()
+            .rev()
+            .find(|(pattern, _)| attribute_matches(pattern, file_path))
+            .map(|(_, language)| language);
+        let overridden = attribute
+            .or_else(|| languages.extensions.get(extension))
+            .or_else(|| languages.extensions.get(name));
+        if let Some(language) = overridden {
+            if let Some(index) = self.find_by_language(language) {
+                return Some(index);
+            }
+        }
+
+        if let Some((_, language)) = EXTENSION_SYNTAXES.iter().find(|(e, _)| *e == extension) {
+            return self.find_by_language(language);
+        }
+        [extension, name]
+            .into_iter()
+            .filter(|token| !token.is_empty())
+            .find_map(|token| self.syntax_set.find_syntax_by_extension(token))
+            .and_then(|syntax| self.index_of(syntax))
+    }
+
+    /// Syntax by name (e.g. "Rust") or by one of its extensions
+    fn find_by_language(&self, language: &str) -> Option<usize> {
+        let syntax = self
+            .syntax_set
+            .find_syntax_by_name(language)
+            .or_else(|| self.syntax_set.find_syntax_by_token(language))?;
+        self.index_of(syntax)
+    }
+
+    fn find_by_first_line(&self, line: &str) -> Option<usize> {
+        let syntax = self.syntax_set.find_syntax_by_first_line(line)?;
+        self.index_of(syntax)
+    }
+
+    fn index_of(&self, syntax: &SyntaxReference) -> Option<usize> {
+        if syntax.name == self.syntax_set.find_syntax_plain_text().name {
+            return None;
+        }
+        self.syntax_set
+            .syntaxes()
+            .iter()
+            .position(|s| std::ptr::eq(s, syntax))
+    }
+
     /// Convert syntect style to ratatui style
     fn syntect_to_ratatui(&self, style: SyntectStyle) -> Style {
         let fg = style.foreground;
@@ -135,6 +277,48 @@ impl Highlighter {
     }
 }
 
+/// `linguist-language` rules of a `.gitattributes` file as `(pattern, language)`
+pub fn parse_gitattributes(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let pattern = fields.next().filter(|p| !p.starts_with('#'))?;
+            let language = fields.find_map(|attr| attr.strip_prefix("linguist-language="))?;
+            Some((pattern.to_string(), language.to_string()))
+        })
+        .collect()
+}
+
+/// Whether a `.gitattributes` pattern matches a repo-relative path
+///
+/// A pattern without a slash matches the file name in any directory.
+fn attribute_matches(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.strip_prefix('/').unwrap_or(pattern);
+    if pattern.contains('/') {
+        glob_match(pattern.as_bytes(), path.as_bytes())
+    } else {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        glob_match(pattern.as_bytes(), name.as_bytes())
+    }
+}
+
+/// Match `*` (within a path segment), `**` (across segments) and `?`
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        [b'*', b'*', rest @ ..] => {
+            let rest = rest.strip_prefix(b"/").unwrap_or(rest);
+            (0..=text.len()).any(|i| glob_match(rest, &text[i..]))
+        }
+        [b'*', rest @ ..] => (0..=text.len())
+            .take_while(|&i| i == 0 || text[i - 1] != b'/')
+            .any(|i| glob_match(rest, &text[i..])),
+        [b'?', rest @ ..] => matches!(text, [c, ..] if *c != b'/') && glob_match(rest, &text[1..]),
+        [c, rest @ ..] => text.first() == Some(c) && glob_match(rest, &text[1..]),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,4 +341,72 @@ mod tests {
         let result = highlighter.highlight_line("some text", "file.xyz");
         assert!(!result.is_empty());
     }
+
+    #[test]
+    fn test_resolve_common_extensions() {
+        let highlighter = Highlighter::new();
+        for extension in [
+            "rs", "py", "go", "ts", "tsx", "jsx", "java", "kt", "swift", "rb", "php", "c", "cpp",
+            "h", "sh", "yaml", "json", "toml", "sql", "md",
+        ] {
+            let path = format!("src/file.{}", extension);
+            assert!(highlighter.syntax_for(&path, "").is_some(), "{}", path);
+        }
+        for (path, language) in [
+            ("include/a.h", "C"),
+            ("src/app.ts", "TypeScript"),
+            ("src/App.tsx", "TypeScriptReact"),
+            ("build.gradle.kts", "Kotlin"),
+            ("Sources/App.swift", "Swift"),
+            ("Cargo.toml", "TOML"),
+        ] {
+            let syntax = highlighter.syntax_for(path, "");
+            assert_eq!(syntax.map(|s| s.name.as_str()), Some(language), "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_resolve_by_name_and_shebang() {
+        let highlighter = Highlighter::new();
+        assert!(highlighter.syntax_for("src/Makefile", "all:").is_some());
+        assert!(highlighter.syntax_for("bin/run", "set -e").is_none());
+        let syntax = highlighter.syntax_for("bin/run", "#!/usr/bin/env python3");
+        assert_eq!(syntax.map(|s| s.name.as_str()), Some("Python"));
+    }
+
+    #[test]
+    fn test_language_overrides() {
+        let highlighter = Highlighter::new();
+        highlighter
+            .set_extension_overrides(HashMap::from([("tf".to_string(), "Ruby".to_string())]));
+        let syntax = highlighter.syntax_for("main.tf", "");
+        assert_eq!(syntax.map(|s| s.name.as_str()), Some("Ruby"));
+
+        highlighter.set_attributes(parse_gitattributes("*.tf linguist-language=Python\n"));
+        let syntax = highlighter.syntax_for("main.tf", "");
+        assert_eq!(syntax.map(|s| s.name.as_str()), Some("Python"));
+    }
+
+    #[test]
+    fn test_parse_gitattributes() {
+        let content = "# comment\n*.h linguist-language=C++ diff\n*.txt text\n/vendor/** -linguist-language linguist-language=Go\n";
+        assert_eq!(
+            parse_gitattributes(content),
+            vec![
+                ("*.h".to_string(), "C++".to_string()),
+                ("/vendor/**".to_string(), "Go".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_attribute_matches() {
+        assert!(attribute_matches("*.h", "include/a.h"));
+        assert!(attribute_matches("Justfile", "tools/Justfile"));
+        assert!(attribute_matches("/vendor/**", "vendor/x/y.go"));
+        assert!(attribute_matches("src/*.rs", "src/main.rs"));
+        assert!(!attribute_matches("src/*.rs", "src/a/main.rs"));
+        assert!(attribute_matches("docs/**/*.md", "docs/a/b/c.md"));
+        assert!(!attribute_matches("*.h", "a.hpp"));
+    }
 }
@@ -0,0 +1,311 @@
+//! OSC 8 hyperlink tracking for terminal output
+//!
+//! vt100 silently drops OSC 8 sequences, so PTY output is split at each
+//! `ESC ] 8 ; params ; URI ST` marker before being fed to the parser. The
+//! cursor position at the opening and closing markers gives the screen cells
+//! covered by the link text.
+
+use ratatui::{style::Modifier, text::Span};
+use std::borrow::Cow;
+
+/// Maximum number of spans kept per terminal
+const MAX_HYPERLINK_SPANS: usize = 256;
+
+/// A hyperlink covering a run of cells on a single screen row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HyperlinkSpan {
+    pub row: u16,
+    pub col_start: u16,
+    /// Exclusive end column
+    pub col_end: u16,
+    pub uri: String,
+    /// Screen text at creation, used to detect spans that scrolled away
+    pub text: String,
+}
+
+impl HyperlinkSpan {
+    /// Check if the span covers a cell
+    pub fn contains(&self, row: u16, col: u16) -> bool {
+        self.row == row && col >= self.col_start && col < self.col_end
+    }
+
+    /// Check if the span still matches what is on screen
+    pub fn is_visible(&self, screen: &vt100::Screen) -> bool {
+        screen.contents_between(self.row, self.col_start, self.row, self.col_end) == self.text
+    }
+}
+
+/// Link opened by an OSC 8 sequence that has not been closed yet
+#[derive(Debug, Clone)]
+pub struct PendingHyperlink {
+    row: u16,
+    col: u16,
+    uri: String,
+}
+
+/// Feed PTY output to the parser, recording OSC 8 hyperlinks as they close
+pub fn process_with_hyperlinks(
+    parser: &mut vt100::Parser,
+    data: &[u8],
+    spans: &mut Vec<HyperlinkSpan>,
+    pending: &mut Option<PendingHyperlink>,
+) {
+    const OSC8: &[u8] = b"\x1b]8;";
+
+    let mut rest = data;
+    while let Some(start) = find(rest, OSC8) {
+        parser.process(&rest[..start]);
+
+        let body_start = start + OSC8.len();
+        let Some((body_len, term_len)) = find_terminator(&rest[body_start..]) else {
+            // Sequence split across reads - let vt100 swallow it
+            parser.process(&rest[start..]);
+            return;
+        };
+
+        let body = &rest[body_start..body_start + body_len];
+        // body is "params;URI" - params may be empty
+        let uri = body
+            .iter()
+            .position(|&b| b == b';')
+            .map(|i| &body[i + 1..])
+            .and_then(|u| std::str::from_utf8(u).ok())
+            .unwrap_or("");
+
+        let (row, col) = parser.screen().cursor_position();
+        if let Some(open) = pending.take() {
+            close_link(parser.screen(), open, row, col, spans);
+        }
+        if is_openable(uri) {
+            *pending = Some(PendingHyperlink {
+                row,
+                col,
+                uri: uri.to_string(),
+            });
+        }
+
+        rest = &rest[body_start + body_len + term_len..];
+    }
+    parser.process(rest);
+}
+
+/// Turn a closed link into per-row spans, replacing any spans it overwrites
+fn close_link(
+    screen: &vt100::Screen,
+    open: PendingHyperlink,
+    end_row: u16,
+    end_col: u16,
+    spans: &mut Vec<HyperlinkSpan>,
+) {
+    if end_row < open.row || (end_row == open.row && end_col <= open.col) {
+        // Screen scrolled or cursor moved backwards - position is unreliable
+        return;
+    }
+
+    let (_, cols) = screen.size();
+    for row in open.row..=end_row {
+        let col_start = if row == open.row { open.col } else { 0 };
+        let col_end = if row == end_row { end_col } else { cols };
+        if col_end <= col_start {
+            continue;
+        }
+
+        spans.retain(|s| s.row != row || s.col_end <= col_start || s.col_start >= col_end);
+        spans.push(HyperlinkSpan {
+            row,
+            col_start,
+            col_end,
+            uri: open.uri.clone(),
+            text: screen.contents_between(row, col_start, row, col_end),
+        });
+    }
+
+    if spans.len() > MAX_HYPERLINK_SPANS {
+        let excess = spans.len() - MAX_HYPERLINK_SPANS;
+        spans.drain(..excess);
+    }
+}
+
+/// Check if a URI should be tracked and opened
+///
+/// Malformed URIs and `file://` links are ignored.
+pub fn is_openable(uri: &str) -> bool {
+    let Some((scheme, rest)) = uri.split_once(':') else {
+        return false;
+    };
+    if rest.is_empty() || uri.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return false;
+    }
+    let scheme = scheme.to_ascii_lowercase();
+    matches!(scheme.as_str(), "http" | "https" | "mailto")
+        && (scheme == "mailto" || (rest.starts_with("//") && rest.len() > 2))
+}
+
+/// Open a URI with the system handler
+pub fn open_uri(uri: &str) -> Result<(), opener::OpenError> {
+    opener::open(uri)
+}
+
+/// Find byte ranges of plain http(s) URLs in text
+pub fn find_urls(text: &str) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut offset = 0;
+
+    while let Some(pos) = ["https://", "http://"]
+        .iter()
+        .filter_map(|p| text[offset..].find(p))
+        .min()
+    {
+        let start = offset + pos;
+        let len = text[start..]
+            .find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '<' | '>' | '`'))
+            .unwrap_or(text.len() - start);
+        // Trailing punctuation usually belongs to the surrounding prose
+        let end = start
+            + text[start..start + len]
+                .trim_end_matches(['.', ',', ';', ':', ')', ']', '}'])
+                .len();
+
+        if is_openable(&text[start..end]) {
+            ranges.push((start, end));
+        }
+        offset = start + len.max(1);
+    }
+
+    ranges
+}
+
+/// Underline any URLs found across a line of styled spans
+pub fn underline_urls(spans: Vec<Span<'_>>) -> Vec<Span<'_>> {
+    let text: String = spans.iter().map(|s| s.content.as_ref()).collect();
+    let urls = find_urls(&text);
+    if urls.is_empty() {
+        return spans;
+    }
+
+    let mut result = Vec::with_capacity(spans.len() + urls.len() * 2);
+    let mut span_start = 0;
+    for span in spans {
+        let span_end = span_start + span.content.len();
+        let mut cut = span_start;
+
+        for &(url_start, url_end) in &urls {
+            let start = url_start.clamp(span_start, span_end);
+            let end = url_end.clamp(span_start, span_end);
+            if start >= end {
+                continue;
+            }
+            if start > cut {
+                result.push(slice_span(
+                    &span,
+                    cut - span_start,
+                    start - span_start,
+                    None,
+                ));
+            }
+            result.push(slice_span(
+                &span,
+                start - span_start,
+                end - span_start,
+                Some(Modifier::UNDERLINED),
+            ));
+            cut = end;
+        }
+
+        if cut == span_start {
+            result.push(span);
+        } else if cut < span_end {
+            result.push(slice_span(
+                &span,
+                cut - span_start,
+                span_end - span_start,
+                None,
+            ));
+        }
+        span_start = span_end;
+    }
+
+    result
+}
+
+fn slice_span<'a>(
+    span: &Span<'_>,
+    start: usize,
+    end: usize,
+    modifier: Option<Modifier>,
+) -> Span<'a> {
+    let style = match modifier {
+        Some(m) => span.style.add_modifier(m),
+        None => span.style,
+    };
+    Span::styled(Cow::Owned(span.content[start..end].to_string()), style)
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+/// Find an OSC terminator (BEL or ESC \), returning (body length, terminator length)
+fn find_terminator(data: &[u8]) -> Option<(usize, usize)> {
+    data.iter().enumerate().find_map(|(i, &b)| match b {
+        0x07 => Some((i, 1)),
+        0x1b if data.get(i + 1) == Some(&b'\\') => Some((i, 2)),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(data: &[u8]) -> (vt100::Parser, Vec<HyperlinkSpan>) {
+        let mut parser = vt100::Parser::new(24, 80, 0);
+        let mut spans = Vec::new();
+        let mut pending = None;
+        process_with_hyperlinks(&mut parser, data, &mut spans, &mut pending);
+        (parser, spans)
+    }
+
+    #[test]
+    fn test_osc8_link_recorded() {
+        let (parser, spans) =
+            process(b"see \x1b]8;;https://example.com\x1b\\example\x1b]8;;\x1b\\ now");
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].row, 0);
+        assert_eq!(spans[0].col_start, 4);
+        assert_eq!(spans[0].col_end, 11);
+        assert_eq!(spans[0].uri, "https://example.com");
+        assert!(spans[0].is_visible(parser.screen()));
+        assert_eq!(parser.screen().contents(), "see example now");
+    }
+
+    #[test]
+    fn test_osc8_bel_terminator_and_params() {
+        let (_, spans) = process(b"\x1b]8;id=1;http://a.test\x07link\x1b]8;;\x07");
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].uri, "http://a.test");
+    }
+
+    #[test]
+    fn test_file_and_malformed_uris_skipped() {
+        let (_, spans) = process(b"\x1b]8;;file:///etc/passwd\x1b\\x\x1b]8;;\x1b\\");
+        assert!(spans.is_empty());
+        let (_, spans) = process(b"\x1b]8;;not a uri\x1b\\x\x1b]8;;\x1b\\");
+        assert!(spans.is_empty());
+    }
+
+    #[test]
+    fn test_find_urls() {
+        let text = "docs at https://example.com/a, or (http://b.test).";
+        let urls: Vec<&str> = find_urls(text).iter().map(|&(s, e)| &text[s..e]).collect();
+        assert_eq!(urls, vec!["https://example.com/a", "http://b.test"]);
+    }
+
+    #[test]
+    fn test_underline_urls_splits_spans() {
+        let spans = underline_urls(vec![Span::raw("go https://x.test now")]);
+        assert_eq!(spans.len(), 3);
+        assert_eq!(spans[1].content, "https://x.test");
+        assert!(spans[1].style.add_modifier.contains(Modifier::UNDERLINED));
+    }
+}
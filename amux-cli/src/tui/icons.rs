@@ -11,6 +11,8 @@ pub mod nerd {
     // Status icons
     pub const CHECK: &str = "\u{f00c}"; //
     pub const WARNING: &str = "\u{f071}"; //
+    pub const ARCHIVE: &str = "\u{f187}"; //
+    pub const PIN: &str = "\u{f08d}"; //
 
     // Control icons
     pub const PLAY: &str = "\u{f04b}"; //
@@ -37,9 +39,12 @@ pub mod unicode {
     // Shapes
     pub const DIAMOND_FILLED: &str = "◆";
     pub const DIAMOND_EMPTY: &str = "◇";
+    pub const TRIPLE_BAR: &str = "≡";
     pub const CIRCLE_FILLED: &str = "●";
     pub const CIRCLE_EMPTY: &str = "○";
     pub const TRIANGLE_RIGHT: &str = "▸";
+    pub const ARROW_UP: &str = "↑";
+    pub const ARROW_DOWN: &str = "↓";
 }
 
 /// Box Drawing characters for borders and frames
@@ -137,6 +142,18 @@ impl StatusIcons {
         }
     }
 
+    pub fn worktree_dirty(&self) -> &'static str {
+        unicode::CIRCLE_FILLED
+    }
+
+    pub fn ahead(&self) -> &'static str {
+        unicode::ARROW_UP
+    }
+
+    pub fn behind(&self) -> &'static str {
+        unicode::ARROW_DOWN
+    }
+
     // ===== Git status =====
 
     pub fn git_added(&self) -> &'static str {
@@ -159,6 +176,22 @@ impl StatusIcons {
         "?"
     }
 
+    pub fn git_conflicted(&self) -> &'static str {
+        "U"
+    }
+
+    pub fn git_ignored(&self) -> &'static str {
+        "!"
+    }
+
+    pub fn git_submodule_modified(&self) -> &'static str {
+        "M"
+    }
+
+    pub fn git_submodule_uninitialized(&self) -> &'static str {
+        "-"
+    }
+
     pub fn staged_indicator(&self) -> &'static str {
         if self.use_nerd_fonts {
             nerd::CHECK
@@ -179,6 +212,36 @@ impl StatusIcons {
         "?"
     }
 
+    pub fn submodule_indicator(&self) -> &'static str {
+        unicode::TRIANGLE_RIGHT
+    }
+
+    pub fn ignored_indicator(&self) -> &'static str {
+        unicode::CIRCLE_EMPTY
+    }
+
+    pub fn stash_indicator(&self) -> &'static str {
+        if self.use_nerd_fonts {
+            nerd::ARCHIVE
+        } else {
+            unicode::TRIPLE_BAR
+        }
+    }
+
+    // ===== Sessions =====
+
+    pub fn unread(&self) -> &'static str {
+        unicode::CIRCLE_FILLED
+    }
+
+    pub fn pinned(&self) -> &'static str {
+        if self.use_nerd_fonts {
+            nerd::PIN
+        } else {
+            "📌"
+        }
+    }
+
     // ===== Comments =====
 
     pub fn comment(&self) -> &'static str {
@@ -10,11 +10,14 @@
 //! - `prefix`: Prefix key command handling (Ctrl+s + ?)
 //! - `resolver`: Key-to-action resolution
 //! - `mouse`: Mouse event handling
+//! - `handle_paste_sync`: Bracketed paste into text inputs and the terminal
 //!
 //! Input handlers for specific views are in their respective view modules:
 //! - `views::sidebar::input` - Sidebar navigation
 //! - `views::terminal::input` - Terminal mode input
 //! - `views::diff::input` - Diff view input
+//! - `views::blame::input` - Blame overlay input
+//! - `views::conflicts::input` - Conflict view input
 //! - `views::git_status::input` - Git status panel input
 //! - `views::todo::input` - TODO popup input
 //! - `overlays::input` - Dialogs and confirmation overlays
@@ -26,31 +29,91 @@ pub mod utils;
 
 use crate::tui::app::App;
 use crate::tui::overlays::input as overlay_input;
-use crate::tui::state::{AsyncAction, Focus, InputMode, PrefixMode, TerminalMode};
-use crate::tui::views::{diff, git_status, sidebar, terminal, todo};
+use crate::tui::state::{
+    AsyncAction, Focus, InputMode, MacroRegisterFor, PrefixMode, TerminalMode,
+};
+use crate::tui::views::{blame, conflicts, diff, git_status, sidebar, terminal, todo};
+use amux_config::Action;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 // Re-export for external use
 pub use super::widgets::TextInput;
 pub use mouse::handle_mouse_sync;
 
+/// Handle pasted text (sync version - returns async action if needed)
+///
+/// Text inputs take the paste as one edit, so its newlines can't submit
+/// them. In terminal insert mode the paste goes to the PTY, bracketed when
+/// the program there enabled bracketed paste.
+pub fn handle_paste_sync(app: &mut App, text: String) -> Option<AsyncAction> {
+    if utils::is_text_input_mode(app) {
+        app.text_input
+            .set_multiline(app.input_mode.allows_newline());
+        app.text_input.paste(&text);
+        let query = app.text_input.content();
+        return match app.input_mode {
+            InputMode::SearchSessions { .. } => Some(AsyncAction::SearchSessions { query }),
+            InputMode::SearchHistory { .. } => Some(AsyncAction::SearchHistory { query }),
+            _ => None,
+        };
+    }
+
+    if app.input_mode != InputMode::Normal
+        || app.focus != Focus::Terminal
+        || app.terminal.mode != TerminalMode::Insert
+    {
+        return None;
+    }
+    let bracketed = app
+        .terminal
+        .parser
+        .lock()
+        .is_ok_and(|parser| parser.screen().bracketed_paste());
+    let data = if bracketed {
+        format!("\x1b[200~{}\x1b[201~", text).into_bytes()
+    } else {
+        text.into_bytes()
+    };
+    Some(AsyncAction::SendToTerminal { data })
+}
+
 /// Handle keyboard input (sync version - returns async action if needed)
 pub fn handle_input_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
-    // Ignore Ctrl+C and Ctrl+Z at TUI level (prevent accidental exit/suspend)
+    // Ignore Ctrl+C at TUI level (prevent accidental exit). Raw mode keeps
+    // Ctrl+Z from reaching the terminal driver, so raise its SIGTSTP here;
+    // the main loop suspends once it sees the signal.
     if key.modifiers.contains(KeyModifiers::CONTROL) {
         match key.code {
-            KeyCode::Char('c') | KeyCode::Char('z') => return None,
+            KeyCode::Char('c') => return None,
+            KeyCode::Char('z') => {
+                let _ = signal_hook::low_level::raise(signal_hook::consts::SIGTSTP);
+                return None;
+            }
             _ => {}
         }
     }
 
+    // Commit messages, comments and descriptions take newlines on Enter
+    app.text_input
+        .set_multiline(app.input_mode.allows_newline());
+
     // Check for prefix key - works in any context except text input
     // Use the configured prefix key from keybind map instead of hardcoded
     if resolver::is_key_the_prefix(key, &app.keybinds) && !utils::is_text_input_mode(app) {
-        app.prefix_mode = PrefixMode::WaitingForCommand;
+        app.enter_prefix_mode();
         return None;
     }
 
+    // Register key after a macro command
+    if let PrefixMode::WaitingForRegister(purpose) = app.prefix_mode {
+        return app.handle_macro_register_key(purpose, key);
+    }
+
+    // Record the key's action before a handler acts on it
+    if app.macro_recording.is_some() {
+        app.record_macro_key(key);
+    }
+
     // Handle prefix mode commands
     if app.prefix_mode == PrefixMode::WaitingForCommand {
         return prefix::handle_prefix_command_sync(app, key);
@@ -71,6 +134,64 @@ pub fn handle_input_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
         return overlay_input::handle_rename_session_mode_sync(app, key);
     }
 
+    if matches!(app.input_mode, InputMode::RenameBranch { .. }) {
+        return overlay_input::handle_rename_branch_mode_sync(app, key);
+    }
+
+    // Handle quick commit-and-push message
+    if matches!(app.input_mode, InputMode::StashMessage { .. }) {
+        return overlay_input::handle_stash_message_mode_sync(app, key);
+    }
+
+    // Handle typed confirmation of a checkpoint restore
+    if matches!(app.input_mode, InputMode::ConfirmRestoreCheckpoint { .. }) {
+        return overlay_input::handle_confirm_restore_checkpoint_sync(app, key);
+    }
+
+    if matches!(app.input_mode, InputMode::QuickShip { .. }) {
+        return overlay_input::handle_quick_ship_mode_sync(app, key);
+    }
+
+    // Handle add repo mode
+    if matches!(app.input_mode, InputMode::AddRepo) {
+        return overlay_input::handle_add_repo_mode_sync(app, key);
+    }
+
+    // Handle confirm remove repo mode
+    if matches!(app.input_mode, InputMode::ConfirmRemoveRepo { .. }) {
+        return overlay_input::handle_confirm_remove_repo_sync(app, key);
+    }
+
+    // Handle session search mode
+    if matches!(app.input_mode, InputMode::SearchSessions { .. }) {
+        return overlay_input::handle_search_sessions_mode_sync(app, key);
+    }
+
+    // Handle history search mode
+    if matches!(app.input_mode, InputMode::SearchHistory { .. }) {
+        return overlay_input::handle_search_history_mode_sync(app, key);
+    }
+
+    // Handle diff base picker
+    if matches!(app.input_mode, InputMode::SelectDiffBase { .. }) {
+        return overlay_input::handle_select_diff_base_mode_sync(app, key);
+    }
+
+    // Handle confirm stash-before-pull mode
+    if matches!(app.input_mode, InputMode::ConfirmPullStash { .. }) {
+        return overlay_input::handle_confirm_pull_stash_sync(app, key);
+    }
+
+    // Handle confirm broadcast mode
+    if matches!(app.input_mode, InputMode::ConfirmBroadcast { .. }) {
+        return overlay_input::handle_confirm_broadcast_sync(app, key);
+    }
+
+    // Handle confirm quit mode
+    if matches!(app.input_mode, InputMode::ConfirmQuit { .. }) {
+        return overlay_input::handle_confirm_quit_sync(app, key);
+    }
+
     // Handle confirm delete mode
     if matches!(app.input_mode, InputMode::ConfirmDelete(_)) {
         return overlay_input::handle_confirm_delete_sync(app, key);
@@ -94,11 +215,56 @@ pub fn handle_input_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
         return overlay_input::handle_select_provider_mode_sync(app, key);
     }
 
+    // Handle review picker
+    if matches!(app.input_mode, InputMode::SelectReview { .. }) {
+        return overlay_input::handle_select_review_mode_sync(app, key);
+    }
+
+    if matches!(app.input_mode, InputMode::RecentSessions { .. }) {
+        return overlay_input::handle_recent_sessions_mode_sync(app, key);
+    }
+
+    if matches!(app.input_mode, InputMode::ContextMenu { .. }) {
+        return overlay_input::handle_context_menu_mode_sync(app, key);
+    }
+
+    if matches!(app.input_mode, InputMode::SessionInfo(_)) {
+        return overlay_input::handle_session_info_mode_sync(app, key);
+    }
+
+    if matches!(app.input_mode, InputMode::SessionFile { .. }) {
+        return overlay_input::handle_session_file_mode_sync(app, key);
+    }
+
     // Handle create session input mode (name input in status bar)
     if matches!(app.input_mode, InputMode::CreateSessionInput { .. }) {
         return overlay_input::handle_create_session_input_mode_sync(app, key);
     }
 
+    if matches!(app.input_mode, InputMode::SessionSystemPrompt { .. }) {
+        return overlay_input::handle_session_system_prompt_mode_sync(app, key);
+    }
+
+    if matches!(app.input_mode, InputMode::RunCommand { .. }) {
+        return overlay_input::handle_run_command_mode_sync(app, key);
+    }
+
+    if matches!(app.input_mode, InputMode::BroadcastInput) {
+        return overlay_input::handle_broadcast_input_mode_sync(app, key);
+    }
+
+    if matches!(app.input_mode, InputMode::CommandLine) {
+        return overlay_input::handle_command_line_mode_sync(app, key);
+    }
+
+    if matches!(app.input_mode, InputMode::SearchDiff { .. }) {
+        return overlay_input::handle_search_diff_mode_sync(app, key);
+    }
+
+    if matches!(app.input_mode, InputMode::ShellDirectory { .. }) {
+        return overlay_input::handle_shell_directory_mode_sync(app, key);
+    }
+
     // Handle add line comment mode
     if matches!(app.input_mode, InputMode::AddLineComment { .. }) {
         return overlay_input::handle_add_line_comment_mode_sync(app, key);
@@ -109,6 +275,11 @@ pub fn handle_input_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
         return overlay_input::handle_edit_line_comment_mode_sync(app, key);
     }
 
+    // Handle blame overlay
+    if app.input_mode == InputMode::BlamePopup {
+        return blame::handle_blame_popup_sync(app, key);
+    }
+
     // Handle TODO modes
     if app.input_mode == InputMode::TodoPopup {
         return todo::handle_todo_popup_sync(app, key);
@@ -130,6 +301,27 @@ pub fn handle_input_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
         return todo::handle_confirm_delete_todo_sync(app, key);
     }
 
+    // Copy resolves its target from focus, while send text, the right panel
+    // toggle and macros work from any view, so handle them before view dispatch
+    match resolver::resolve_action(app, key, &app.keybinds) {
+        Some(Action::CopyToClipboard(target)) => {
+            app.copy_to_clipboard(target);
+            return None;
+        }
+        Some(Action::SendText(text)) => {
+            return Some(AsyncAction::SendToTerminal {
+                data: text.into_bytes(),
+            });
+        }
+        Some(Action::ToggleRightPanel) => return app.toggle_right_panel(),
+        Some(Action::RecordMacro) => return app.toggle_macro_recording(),
+        Some(Action::ReplayMacro) => {
+            app.wait_for_macro_register(MacroRegisterFor::Replay);
+            return None;
+        }
+        _ => {}
+    }
+
     // Handle terminal modes when focused on terminal
     if app.focus == Focus::Terminal {
         return match app.terminal.mode {
@@ -146,6 +338,11 @@ pub fn handle_input_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
         return diff::handle_diff_files_mode_sync(app, key);
     }
 
+    // Handle merge conflict view
+    if app.focus == Focus::Conflicts {
+        return conflicts::handle_conflicts_mode_sync(app, key);
+    }
+
     // Handle git status panel
     if app.focus == Focus::GitStatus {
         return git_status::handle_git_status_input_sync(app, key);
@@ -1,77 +1,227 @@
 //! Mouse event handling
 
 use super::super::app::App;
-use super::super::state::{Focus, RightPanelView};
-use crossterm::event::{MouseEvent, MouseEventKind};
+use super::super::overlays::dialogs::context_menu_area;
+use super::super::overlays::input::run_context_menu_item;
+use super::super::state::{AsyncAction, Focus, InputMode, RightPanelView};
+use super::super::views::diff::render::position_at_row;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use std::time::Instant;
+
+/// Lines moved per scroll wheel step
+const SCROLL_LINES: usize = 3;
 
 /// Handle mouse events (sync version)
 /// Uses mouse position to determine which area to scroll
-pub fn handle_mouse_sync(app: &mut App, mouse: MouseEvent) {
-    // Determine which area the mouse is over based on x position
-    // Layout: fixed 38 char sidebar (left), remaining = main content (right)
-    let sidebar_width = 38u16;
+pub fn handle_mouse_sync(app: &mut App, mouse: MouseEvent) -> Option<AsyncAction> {
+    let (screen_cols, screen_rows) = app.screen_size();
+
+    // An open context menu takes all mouse input
+    let menu = match &app.input_mode {
+        InputMode::ContextMenu {
+            items, column, row, ..
+        } => Some(context_menu_area(
+            Rect::new(0, 0, screen_cols, screen_rows),
+            app,
+            items,
+            *column,
+            *row,
+        )),
+        InputMode::Normal => None,
+        // Other dialogs are keyboard only
+        _ => return None,
+    };
+    if let Some(menu) = menu {
+        return handle_context_menu_mouse(app, mouse, menu);
+    }
+
+    // Layout: tab bar (3) + main content + status bar (3); main content is the
+    // sidebar (left, configurable width, absent in fullscreen) and the right panel
+    let fullscreen = (app.terminal.fullscreen && app.focus == Focus::Terminal)
+        || (app.diff().is_some_and(|d| d.fullscreen) && app.focus == Focus::DiffFiles);
+    let sidebar_width = if fullscreen {
+        0
+    } else {
+        app.sidebar.width_cols(screen_cols)
+    };
+    let main = Rect::new(0, 3, screen_cols, screen_rows.saturating_sub(6));
     let in_sidebar = mouse.column < sidebar_width;
+    let tree = sidebar_tree_area(app, Rect::new(0, main.y, sidebar_width, main.height));
+    // Row of the list inside a bordered panel, if the mouse is on one
+    let row_in = |area: Rect| {
+        (mouse.row > area.y && mouse.row + 1 < area.bottom())
+            .then(|| (mouse.row - area.y - 1) as usize)
+    };
+
+    // The sidebar's right border doubles as a drag handle (below the tab
+    // bar, above the status bar)
+    let on_divider = sidebar_width > 0
+        && mouse.column + 1 == sidebar_width
+        && mouse.row >= main.y
+        && mouse.row < main.bottom();
 
     match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) if on_divider => {
+            app.sidebar.drag_column = Some(mouse.column);
+        }
+        MouseEventKind::Drag(MouseButton::Left) if app.sidebar.drag_column.is_some() => {
+            app.sidebar.drag_column = Some(mouse.column);
+            app.status_message = Some(format!(
+                "Sidebar width: {}%",
+                drag_percent(mouse.column, screen_cols)
+            ));
+        }
+        MouseEventKind::Up(MouseButton::Left) => {
+            if let Some(column) = app.sidebar.drag_column.take() {
+                return app.set_sidebar_width_sync(drag_percent(column, screen_cols));
+            }
+        }
         MouseEventKind::ScrollUp => {
             if in_sidebar {
                 // Scroll sidebar
-                for _ in 0..3 {
-                    let _ = app.sidebar_move_up();
+                let mut action = None;
+                for _ in 0..SCROLL_LINES {
+                    action = app.sidebar_move_up().or(action);
                 }
                 app.dirty.sidebar = true;
+                return action;
             } else {
                 // Scroll main content area (terminal or diff)
                 match app.right_panel_view {
                     RightPanelView::Terminal => {
-                        app.scroll_up(3);
+                        app.scroll_up(SCROLL_LINES);
                     }
                     RightPanelView::Diff => {
-                        for _ in 0..3 {
+                        for _ in 0..SCROLL_LINES {
                             app.diff_move_up();
                         }
                     }
+                    RightPanelView::Conflicts => app.conflict_move(false),
                 }
             }
         }
         MouseEventKind::ScrollDown => {
             if in_sidebar {
                 // Scroll sidebar
-                for _ in 0..3 {
-                    let _ = app.sidebar_move_down();
+                let mut action = None;
+                for _ in 0..SCROLL_LINES {
+                    action = app.sidebar_move_down().or(action);
                 }
                 app.dirty.sidebar = true;
+                return action;
             } else {
                 // Scroll main content area
                 match app.right_panel_view {
                     RightPanelView::Terminal => {
-                        app.scroll_down(3);
+                        app.scroll_down(SCROLL_LINES);
                     }
                     RightPanelView::Diff => {
-                        for _ in 0..3 {
+                        for _ in 0..SCROLL_LINES {
                             app.diff_move_down();
                         }
                     }
+                    RightPanelView::Conflicts => app.conflict_move(true),
                 }
             }
         }
-        MouseEventKind::Down(crossterm::event::MouseButton::Left) => {
+        MouseEventKind::Down(MouseButton::Right) if in_sidebar => {
+            // Select the row under the mouse and show its actions
+            let row = row_in(tree)?;
+            app.focus = Focus::Sidebar;
+            app.dirty.sidebar = true;
+            let action = app.sidebar_select_row(row);
+            app.open_context_menu(mouse.column, mouse.row);
+            return action;
+        }
+        MouseEventKind::Down(MouseButton::Left) => {
             // Click to focus: left side = sidebar, right side = terminal/diff
             if in_sidebar {
+                app.dirty.sidebar = true;
+                if mouse.row >= tree.bottom() {
+                    app.focus = Focus::GitStatus;
+                    return None;
+                }
                 app.focus = Focus::Sidebar;
-            } else {
-                // Click on right panel
-                match app.right_panel_view {
-                    RightPanelView::Terminal => {
-                        app.focus = Focus::Terminal;
+                return row_in(tree).and_then(|row| app.sidebar_select_row(row));
+            }
+
+            // Click on right panel (inner area is inside its border)
+            let inner_left = sidebar_width + 1;
+            let inner_top = main.y + 1;
+            match app.right_panel_view {
+                RightPanelView::Terminal => {
+                    app.focus = Focus::Terminal;
+                    if mouse.column >= inner_left && mouse.row >= inner_top {
+                        let (row, col) = (mouse.row - inner_top, mouse.column - inner_left);
+                        match app.terminal.clicks.click(row, col, Instant::now()) {
+                            2 => app.select_word_at(row, col),
+                            3 => app.select_line_at(row),
+                            // Click on a hyperlink opens it
+                            _ => {
+                                app.terminal.selection = None;
+                                app.open_hyperlink_at(row, col);
+                            }
+                        }
                     }
-                    RightPanelView::Diff => {
-                        app.focus = Focus::DiffFiles;
+                }
+                RightPanelView::Diff => {
+                    app.focus = Focus::DiffFiles;
+                    let visible_height = main.height.saturating_sub(2) as usize;
+                    if let Some(pos) =
+                        row_in(main).and_then(|row| position_at_row(app, row, visible_height))
+                    {
+                        app.diff_select(pos);
                     }
                 }
+                RightPanelView::Conflicts => {
+                    app.focus = Focus::Conflicts;
+                }
             }
             app.dirty.sidebar = true;
         }
         _ => {}
     }
+    None
+}
+
+/// Mouse input while the context menu is open
+///
+/// Clicking an item runs it; clicking elsewhere closes the menu.
+fn handle_context_menu_mouse(app: &mut App, mouse: MouseEvent, menu: Rect) -> Option<AsyncAction> {
+    let on_item = mouse.column >= menu.x
+        && mouse.column < menu.right()
+        && mouse.row > menu.y
+        && mouse.row + 1 < menu.bottom();
+
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) if on_item => {
+            return run_context_menu_item(app, (mouse.row - menu.y - 1) as usize);
+        }
+        MouseEventKind::Down(_) => app.cancel_input(),
+        MouseEventKind::ScrollUp => app.move_context_menu_selection(false),
+        MouseEventKind::ScrollDown => app.move_context_menu_selection(true),
+        _ => {}
+    }
+    None
+}
+
+/// Area of the worktree tree within the sidebar (the git panel takes the rest)
+fn sidebar_tree_area(app: &App, sidebar: Rect) -> Rect {
+    if !app.sidebar.git_panel_enabled {
+        return sidebar;
+    }
+    // Same split as draw_sidebar
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(sidebar)[0]
+}
+
+/// Sidebar width percentage that puts its right border at `column`
+fn drag_percent(column: u16, screen_cols: u16) -> u16 {
+    if screen_cols == 0 {
+        return 0;
+    }
+    ((column as u32 + 1) * 100 / screen_cols as u32) as u16
 }
@@ -2,7 +2,7 @@
 
 use super::super::app::App;
 use super::super::state::{
-    AsyncAction, Focus, InputMode, PrefixMode, RightPanelView, TerminalMode,
+    AsyncAction, Focus, InputMode, MacroRegisterFor, PrefixMode, RightPanelView, TerminalMode,
 };
 use super::resolver;
 use amux_config::Action;
@@ -12,8 +12,9 @@ use crossterm::event::{KeyCode, KeyEvent};
 pub fn handle_prefix_command_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
     // Reset prefix mode first
     app.prefix_mode = PrefixMode::None;
+    app.prefix_entered_at = None;
 
-    // Esc cancels prefix mode
+    // Esc always cancels prefix mode, even when bound to a prefix command
     if key.code == KeyCode::Esc {
         return None;
     }
@@ -56,6 +57,16 @@ fn execute_prefix_action(app: &mut App, action: Action) -> Option<AsyncAction> {
             Some(AsyncAction::CreateSession)
         }
 
+        Action::ShellInDirectory => {
+            app.start_shell_directory();
+            None
+        }
+
+        Action::SuspendToShell => {
+            app.suspend_to_shell();
+            None
+        }
+
         Action::SelectProviderAndCreate => {
             app.save_focus();
             if app.focus == Focus::Terminal {
@@ -94,12 +105,25 @@ fn execute_prefix_action(app: &mut App, action: Action) -> Option<AsyncAction> {
             if app.focus == Focus::Terminal {
                 app.exit_terminal();
             }
-            app.request_delete();
+            app.request_delete()
+        }
+
+        Action::Undo => Some(AsyncAction::Undo),
+
+        Action::RecordMacro => app.toggle_macro_recording(),
+
+        Action::ReplayMacro => {
+            app.wait_for_macro_register(MacroRegisterFor::Replay);
             None
         }
 
         Action::RefreshAll => Some(AsyncAction::RefreshAll),
 
+        Action::EnterCommandMode => {
+            app.start_command_line();
+            None
+        }
+
         Action::ToggleFullscreen => {
             if app.focus == Focus::Terminal || app.terminal.active_session_id.is_some() {
                 app.toggle_fullscreen();
@@ -123,6 +147,8 @@ fn execute_prefix_action(app: &mut App, action: Action) -> Option<AsyncAction> {
             Some(AsyncAction::LoadGitStatus)
         }
 
+        Action::ToggleRightPanel => app.toggle_right_panel(),
+
         Action::FocusDiff => {
             app.save_focus();
             if app.focus == Focus::Terminal {
@@ -142,6 +168,10 @@ fn execute_prefix_action(app: &mut App, action: Action) -> Option<AsyncAction> {
             Some(AsyncAction::LoadTodos)
         }
 
+        Action::SendText(text) => Some(AsyncAction::SendToTerminal {
+            data: text.into_bytes(),
+        }),
+
         Action::SwitchRepo(idx) => {
             if app.focus == Focus::Terminal {
                 app.exit_terminal();
@@ -149,11 +179,64 @@ fn execute_prefix_action(app: &mut App, action: Action) -> Option<AsyncAction> {
             app.switch_repo_sync(idx)
         }
 
-        Action::Quit => {
-            app.should_quit = true;
+        Action::RecentSessions => {
+            if app.focus == Focus::Terminal {
+                app.exit_terminal();
+            }
+            app.start_recent_sessions();
             None
         }
 
+        Action::SwitchToRecentSession(index) => {
+            if app.focus == Focus::Terminal {
+                app.exit_terminal();
+            }
+            app.switch_to_recent_session(index);
+            None
+        }
+
+        Action::AddRepo => {
+            if app.focus == Focus::Terminal {
+                app.exit_terminal();
+            }
+            app.start_add_repo();
+            None
+        }
+
+        Action::RemoveRepo => {
+            if app.focus == Focus::Terminal {
+                app.exit_terminal();
+            }
+            app.start_remove_repo();
+            None
+        }
+
+        Action::CleanupWorktrees => Some(AsyncAction::CleanupWorktrees),
+
+        Action::SearchSessions => {
+            if app.focus == Focus::Terminal {
+                app.exit_terminal();
+            }
+            app.start_search_sessions();
+            None
+        }
+
+        Action::SearchHistory => {
+            if app.focus == Focus::Terminal {
+                app.exit_terminal();
+            }
+            app.start_search_history();
+            None
+        }
+
+        Action::MoveRepoLeft => app.move_repo_sync(-1),
+        Action::MoveRepoRight => app.move_repo_sync(1),
+        Action::GrowSidebar => app.resize_sidebar_sync(1),
+        Action::ShrinkSidebar => app.resize_sidebar_sync(-1),
+        Action::ToggleSidebar => app.toggle_sidebar_hidden(),
+
+        Action::Quit => Some(AsyncAction::RequestQuit),
+
         // Unknown or unhandled action in prefix context
         _ => {
             app.status_message = Some(
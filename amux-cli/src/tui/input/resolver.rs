@@ -1,50 +1,29 @@
 //! Adapter layer between ccm-config types and TUI input handling
 //!
 //! This module provides utilities to:
-//! 1. Convert crossterm KeyEvent to ccm-config KeyPattern
+//! 1. Convert between crossterm KeyEvent and ccm-config KeyPattern
 //! 2. Detect the current BindingContext from app state
 //! 3. Resolve keys to actions using the keybind map
 
 #![allow(dead_code)] // Functions will be used by refactored handlers
 
-use amux_config::{Action, BindingContext, KeybindMap};
+use amux_config::{Action, BindingContext, KeyPattern, KeybindMap};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use super::super::app::App;
 use super::super::state::Focus;
 
-/// Convert a crossterm KeyEvent to a ccm-config KeyPattern string
-pub fn key_event_to_pattern_string(key: KeyEvent) -> Option<String> {
-    let mut modifiers = Vec::new();
-
-    // Check if this is an uppercase letter (Shift is implicit in the character)
-    let is_uppercase_letter = matches!(key.code, KeyCode::Char(c) if c.is_ascii_uppercase());
-
-    // Handle modifiers in the order: Shift, Control, Alt, Meta (like tmux)
-    // Don't add Shift modifier for uppercase letters - it's already in the character
-    if key.modifiers.contains(KeyModifiers::SHIFT) && !is_uppercase_letter {
-        modifiers.push("S");
-    }
-    if key.modifiers.contains(KeyModifiers::CONTROL) {
-        modifiers.push("C");
-    }
-    if key.modifiers.contains(KeyModifiers::ALT) {
-        modifiers.push("A");
-    }
-
+/// Convert a crossterm KeyEvent to the canonical KeyPattern for it
+///
+/// Meta counts as Alt, BackTab is `S-Tab`, and Shift is dropped for
+/// characters that already carry it (`A`, `?`), the same way
+/// [`KeyPattern::parse`] treats config keys. Super and Hyper are ignored.
+pub fn key_event_to_pattern(key: KeyEvent) -> Option<KeyPattern> {
     let key_str = match key.code {
-        KeyCode::Char(c) => {
-            // For printable chars, uppercase modifiers matter
-            // "s" is different from "S", "j" is different from "J"
-            match c {
-                'a'..='z' | 'A'..='Z' | '0'..='9' => c.to_string(),
-                '!' | '@' | '#' | '$' | '%' | '^' | '&' | '*' | '(' | ')' | '-' | '=' | '['
-                | ']' | '{' | '}' | ';' | ':' | '\'' | '"' | ',' | '.' | '/' | '\\' | '|' | '?'
-                | '`' | '~' | '<' | '>' => c.to_string(),
-                ' ' => "Space".to_string(),
-                _ => return None,
-            }
-        }
+        KeyCode::Char(' ') => "Space".to_string(),
+        // "s" is different from "S", "j" is different from "J"
+        KeyCode::Char(c) if c.is_ascii_graphic() => c.to_string(),
+        KeyCode::Char(_) => return None,
         KeyCode::Enter => "Enter".to_string(),
         KeyCode::Tab => "Tab".to_string(),
         KeyCode::BackTab => "BackTab".to_string(),
@@ -73,12 +52,66 @@ pub fn key_event_to_pattern_string(key: KeyEvent) -> Option<String> {
         | KeyCode::Modifier(_) => return None,
     };
 
-    // Build the pattern string
-    if modifiers.is_empty() {
-        Some(key_str)
-    } else {
-        Some(format!("{}-{}", modifiers.join("-"), key_str))
+    let modifiers = key.modifiers;
+    KeyPattern::new(
+        modifiers.contains(KeyModifiers::CONTROL),
+        modifiers.intersects(KeyModifiers::ALT | KeyModifiers::META),
+        modifiers.contains(KeyModifiers::SHIFT),
+        &key_str,
+    )
+    .ok()
+}
+
+/// Convert a crossterm KeyEvent to a canonical KeyPattern string
+pub fn key_event_to_pattern_string(key: KeyEvent) -> Option<String> {
+    key_event_to_pattern(key).map(|pattern| pattern.to_string())
+}
+
+/// The KeyEvent crossterm reports when the keys of `pattern` are pressed
+///
+/// Inverse of [`key_event_to_pattern`]: `S-Tab` arrives as BackTab and
+/// uppercase letters carry Shift.
+pub fn pattern_to_key_event(pattern: &KeyPattern) -> Option<KeyEvent> {
+    let mut modifiers = KeyModifiers::empty();
+    if pattern.ctrl() {
+        modifiers |= KeyModifiers::CONTROL;
+    }
+    if pattern.alt() {
+        modifiers |= KeyModifiers::ALT;
     }
+    if pattern.shift() {
+        modifiers |= KeyModifiers::SHIFT;
+    }
+
+    let mut chars = pattern.key.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) => {
+            if c.is_ascii_uppercase() {
+                modifiers |= KeyModifiers::SHIFT;
+            }
+            KeyCode::Char(c)
+        }
+        _ => match pattern.key.as_str() {
+            "Space" => KeyCode::Char(' '),
+            "Enter" => KeyCode::Enter,
+            "Tab" if pattern.shift() => KeyCode::BackTab,
+            "Tab" => KeyCode::Tab,
+            "Esc" => KeyCode::Esc,
+            "Backspace" => KeyCode::Backspace,
+            "Delete" => KeyCode::Delete,
+            "Insert" => KeyCode::Insert,
+            "Home" => KeyCode::Home,
+            "End" => KeyCode::End,
+            "PageUp" => KeyCode::PageUp,
+            "PageDown" => KeyCode::PageDown,
+            "Up" => KeyCode::Up,
+            "Down" => KeyCode::Down,
+            "Left" => KeyCode::Left,
+            "Right" => KeyCode::Right,
+            k => KeyCode::F(k.strip_prefix('F')?.parse().ok()?),
+        },
+    };
+    Some(KeyEvent::new(code, modifiers))
 }
 
 /// Detect the current binding context based on app state
@@ -90,21 +123,47 @@ pub fn detect_context(app: &App) -> BindingContext {
         InputMode::NewBranch
         | InputMode::AddWorktree { .. }
         | InputMode::RenameSession { .. }
+        | InputMode::RenameBranch { .. }
+        | InputMode::QuickShip { .. }
+        | InputMode::StashMessage { .. }
+        | InputMode::ConfirmRestoreCheckpoint { .. }
+        | InputMode::SearchSessions { .. }
+        | InputMode::SearchHistory { .. }
+        | InputMode::SelectDiffBase { .. }
+        | InputMode::AddRepo
         | InputMode::AddLineComment { .. }
         | InputMode::EditLineComment { .. }
         | InputMode::AddTodo { .. }
         | InputMode::EditTodo { .. }
         | InputMode::EditTodoDescription { .. }
-        | InputMode::CreateSessionInput { .. } => return BindingContext::DialogText,
+        | InputMode::CreateSessionInput { .. }
+        | InputMode::SessionSystemPrompt { .. }
+        | InputMode::RunCommand { .. }
+        | InputMode::BroadcastInput
+        | InputMode::CommandLine
+        | InputMode::SearchDiff { .. }
+        | InputMode::ShellDirectory { .. } => return BindingContext::DialogText,
 
         InputMode::ConfirmDelete(_)
         | InputMode::ConfirmDeleteBranch(_)
         | InputMode::ConfirmDeleteWorktreeSessions { .. }
         | InputMode::ConfirmDeleteTodo { .. }
-        | InputMode::SelectProvider { .. } => return BindingContext::DialogConfirm,
+        | InputMode::ConfirmPullStash { .. }
+        | InputMode::ConfirmBroadcast { .. }
+        | InputMode::ConfirmQuit { .. }
+        | InputMode::ConfirmRemoveRepo { .. }
+        | InputMode::SelectProvider { .. }
+        | InputMode::SelectReview { .. }
+        | InputMode::RecentSessions { .. }
+        | InputMode::ContextMenu { .. }
+        | InputMode::SessionInfo(_)
+        | InputMode::SessionFile { .. } => return BindingContext::DialogConfirm,
 
         InputMode::TodoPopup => return BindingContext::Todo,
 
+        // Navigated with the diff view's keys
+        InputMode::BlamePopup => return BindingContext::Diff,
+
         InputMode::Normal => {}
     }
 
@@ -119,6 +178,7 @@ pub fn detect_context(app: &App) -> BindingContext {
     // Other focus-based contexts
     match app.focus {
         Focus::DiffFiles => BindingContext::Diff,
+        Focus::Conflicts => BindingContext::Conflicts,
         Focus::GitStatus => BindingContext::GitStatus,
         _ => BindingContext::Sidebar, // Default for any sidebar-related focus
     }
@@ -181,10 +241,85 @@ mod tests {
 
     #[test]
     fn test_key_event_to_pattern_string_shift_ctrl_s() {
-        let key = KeyEvent::new(
-            KeyCode::Char('s'),
-            KeyModifiers::SHIFT | KeyModifiers::CONTROL,
+        // Terminals differ on whether Ctrl+Shift+s reports 's' or 'S'
+        for c in ['s', 'S'] {
+            let key = KeyEvent::new(
+                KeyCode::Char(c),
+                KeyModifiers::SHIFT | KeyModifiers::CONTROL,
+            );
+            assert_eq!(key_event_to_pattern_string(key), Some("C-S".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_patterns_round_trip_key_events() {
+        let none = KeyModifiers::empty();
+        let ctrl = KeyModifiers::CONTROL;
+        let alt = KeyModifiers::ALT;
+        let shift = KeyModifiers::SHIFT;
+        // (config spelling, event as crossterm reports it, canonical pattern)
+        let cases = [
+            ("j", KeyCode::Char('j'), none, "j"),
+            ("C-s", KeyCode::Char('s'), ctrl, "C-s"),
+            ("C-S-s", KeyCode::Char('S'), ctrl | shift, "C-S"),
+            ("S-C-Left", KeyCode::Left, ctrl | shift, "C-S-Left"),
+            ("C-S-Left", KeyCode::Left, ctrl | shift, "C-S-Left"),
+            ("M-Enter", KeyCode::Enter, alt, "A-Enter"),
+            ("A-Enter", KeyCode::Enter, alt, "A-Enter"),
+            ("F12", KeyCode::F(12), none, "F12"),
+            ("C-F12", KeyCode::F(12), ctrl, "C-F12"),
+            ("?", KeyCode::Char('?'), none, "?"),
+            ("S-?", KeyCode::Char('?'), none, "?"),
+            ("A", KeyCode::Char('A'), shift, "A"),
+            ("S-a", KeyCode::Char('A'), shift, "A"),
+            ("Tab", KeyCode::Tab, none, "Tab"),
+            ("S-Tab", KeyCode::BackTab, shift, "S-Tab"),
+            ("BackTab", KeyCode::BackTab, shift, "S-Tab"),
+            ("Space", KeyCode::Char(' '), none, "Space"),
+            ("Return", KeyCode::Enter, none, "Enter"),
+            ("C--", KeyCode::Char('-'), ctrl, "C--"),
+        ];
+        for (spelling, code, modifiers, canonical) in cases {
+            let pattern = KeyPattern::parse(spelling).unwrap();
+            let event = KeyEvent::new(code, modifiers);
+            assert_eq!(pattern.to_string(), canonical, "parsing {}", spelling);
+            assert_eq!(pattern_to_key_event(&pattern), Some(event), "{}", spelling);
+            assert_eq!(
+                key_event_to_pattern(event),
+                Some(pattern),
+                "{:?} {:?}",
+                code,
+                modifiers
+            );
+        }
+
+        // Other ways terminals report the same keys
+        let variants = [
+            (KeyCode::Tab, shift, "S-Tab"),
+            (KeyCode::BackTab, none, "S-Tab"),
+            (KeyCode::Char('?'), shift, "?"),
+            (KeyCode::Char('x'), KeyModifiers::META, "A-x"),
+            (KeyCode::Char('x'), KeyModifiers::SUPER, "x"),
+        ];
+        for (code, modifiers, canonical) in variants {
+            let key = KeyEvent::new(code, modifiers);
+            assert_eq!(key_event_to_pattern_string(key).as_deref(), Some(canonical));
+        }
+        assert_eq!(
+            key_event_to_pattern(KeyEvent::new(KeyCode::Char('é'), none)),
+            None
         );
-        assert_eq!(key_event_to_pattern_string(key), Some("S-C-s".to_string()));
+    }
+
+    #[test]
+    fn test_is_key_the_prefix_any_spelling() {
+        let bindings = amux_config::Bindings::default();
+        let keybinds = KeybindMap::from_bindings(&bindings, "Ctrl-Shift-Left").unwrap();
+        let key = KeyEvent::new(KeyCode::Left, KeyModifiers::SHIFT | KeyModifiers::CONTROL);
+        assert!(is_key_the_prefix(key, &keybinds));
+        assert!(!is_key_the_prefix(
+            KeyEvent::new(KeyCode::Left, KeyModifiers::CONTROL),
+            &keybinds
+        ));
     }
 }
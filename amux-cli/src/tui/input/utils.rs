@@ -19,9 +19,26 @@ pub fn is_text_input_mode(app: &App) -> bool {
         InputMode::NewBranch
             | InputMode::AddWorktree { .. }
             | InputMode::RenameSession { .. }
+            | InputMode::RenameBranch { .. }
+            | InputMode::QuickShip { .. }
+            | InputMode::StashMessage { .. }
+            | InputMode::ConfirmRestoreCheckpoint { .. }
+            | InputMode::SearchSessions { .. }
+            | InputMode::SearchHistory { .. }
+            | InputMode::SelectDiffBase { .. }
+            | InputMode::AddRepo
             | InputMode::AddTodo { .. }
             | InputMode::EditTodo { .. }
             | InputMode::EditTodoDescription { .. }
+            | InputMode::AddLineComment { .. }
+            | InputMode::EditLineComment { .. }
+            | InputMode::CreateSessionInput { .. }
+            | InputMode::SessionSystemPrompt { .. }
+            | InputMode::RunCommand { .. }
+            | InputMode::BroadcastInput
+            | InputMode::CommandLine
+            | InputMode::SearchDiff { .. }
+            | InputMode::ShellDirectory { .. }
     )
 }
 
@@ -84,7 +101,8 @@ pub fn key_to_bytes(key: &KeyEvent) -> Vec<u8> {
 ///
 /// This is a convenience wrapper for simple text input dialogs.
 /// - on_cancel: called when Esc is pressed
-/// - on_submit: called when Enter is pressed, returns the AsyncAction to perform
+/// - on_submit: called when Enter (Alt+Enter in multi-line modes) is pressed,
+///   returns the AsyncAction to perform
 pub fn handle_text_input_with_actions<F, G>(
     app: &mut App,
     key: &KeyEvent,
@@ -7,10 +7,18 @@
 
 use crate::tui::app::App;
 use crate::tui::overlays::dialogs::{
-    draw_add_line_comment_overlay, draw_add_worktree_overlay, draw_confirm_delete_branch_overlay,
-    draw_confirm_delete_overlay, draw_confirm_delete_worktree_sessions_overlay,
-    draw_edit_line_comment_overlay, draw_input_overlay, draw_rename_session_overlay,
-    draw_select_provider_overlay,
+    draw_add_line_comment_overlay, draw_add_repo_overlay, draw_add_worktree_overlay,
+    draw_broadcast_input_overlay, draw_confirm_broadcast_overlay,
+    draw_confirm_delete_branch_overlay, draw_confirm_delete_overlay,
+    draw_confirm_delete_worktree_sessions_overlay, draw_confirm_pull_stash_overlay,
+    draw_confirm_quit_overlay, draw_confirm_remove_repo_overlay,
+    draw_confirm_restore_checkpoint_overlay, draw_context_menu_overlay,
+    draw_edit_line_comment_overlay, draw_input_overlay, draw_quick_ship_overlay,
+    draw_recent_sessions_overlay, draw_rename_branch_overlay, draw_rename_session_overlay,
+    draw_run_command_overlay, draw_search_history_overlay, draw_search_sessions_overlay,
+    draw_select_diff_base_overlay, draw_select_provider_overlay, draw_select_review_overlay,
+    draw_session_file_overlay, draw_session_info_overlay, draw_session_system_prompt_overlay,
+    draw_shell_directory_overlay, draw_stash_message_overlay,
 };
 use crate::tui::state::{Focus, InputMode, RightPanelView};
 use crate::tui::views::tab_bar::{draw_status_bar, draw_tab_bar};
@@ -18,7 +26,7 @@ use crate::tui::views::todo::{
     draw_add_todo_overlay, draw_confirm_delete_todo_overlay, draw_edit_todo_description_overlay,
     draw_edit_todo_overlay, draw_todo_popup,
 };
-use crate::tui::views::{diff, sidebar, terminal};
+use crate::tui::views::{blame, conflicts, diff, sidebar, terminal};
 use ratatui::{
     layout::{Constraint, Direction, Layout},
     Frame,
@@ -39,6 +47,17 @@ pub fn draw(f: &mut Frame, app: &App) {
     draw_tab_bar(f, chunks[0], app);
     draw_main_content(f, chunks[1], app);
     draw_status_bar(f, chunks[2], app);
+
+    // The context menu sits over the view it was opened on
+    if let InputMode::ContextMenu {
+        ref items,
+        selected_index,
+        column,
+        row,
+    } = app.input_mode
+    {
+        draw_context_menu_overlay(f, app, items, selected_index, column, row);
+    }
 }
 
 /// Draw main content: Sidebar + Terminal/Diff with overlay handling
@@ -61,6 +80,141 @@ fn draw_main_content(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
         return;
     }
 
+    // Check for rename branch overlay
+    if matches!(app.input_mode, InputMode::RenameBranch { .. }) {
+        draw_rename_branch_overlay(f, area, app);
+        return;
+    }
+
+    // Check for quick commit-and-push overlay
+    if matches!(app.input_mode, InputMode::QuickShip { .. }) {
+        draw_quick_ship_overlay(f, area, app);
+        return;
+    }
+
+    // Check for new session system prompt overlay
+    if let InputMode::SessionSystemPrompt { ref provider, .. } = app.input_mode {
+        draw_session_system_prompt_overlay(f, area, app, provider.as_ref());
+        return;
+    }
+
+    // Check for session info overlay
+    if let InputMode::SessionInfo(ref session_id) = app.input_mode {
+        draw_session_info_overlay(f, area, app, session_id);
+        return;
+    }
+
+    if let InputMode::SessionFile {
+        ref path,
+        ref lines,
+        scroll,
+        truncated,
+    } = app.input_mode
+    {
+        draw_session_file_overlay(f, area, app, path, lines, scroll, truncated);
+        return;
+    }
+
+    // Check for stash message overlay
+    if matches!(app.input_mode, InputMode::StashMessage { .. }) {
+        draw_stash_message_overlay(f, area, app);
+        return;
+    }
+
+    // Check for confirm checkpoint restore overlay
+    if let InputMode::ConfirmRestoreCheckpoint {
+        ref session_name,
+        ref branch,
+        created_at,
+        ..
+    } = app.input_mode
+    {
+        draw_confirm_restore_checkpoint_overlay(f, area, app, session_name, branch, created_at);
+        return;
+    }
+
+    // Check for add repo overlay
+    if matches!(app.input_mode, InputMode::AddRepo) {
+        draw_add_repo_overlay(f, area, app);
+        return;
+    }
+
+    // Check for run command overlay
+    if matches!(app.input_mode, InputMode::RunCommand { .. }) {
+        draw_run_command_overlay(f, area, app);
+        return;
+    }
+
+    // Check for broadcast input overlay
+    if matches!(app.input_mode, InputMode::BroadcastInput) {
+        draw_broadcast_input_overlay(f, area, app);
+        return;
+    }
+
+    // Check for shell directory overlay
+    if matches!(app.input_mode, InputMode::ShellDirectory { .. }) {
+        draw_shell_directory_overlay(f, area, app);
+        return;
+    }
+
+    // Check for session search overlay
+    if let InputMode::SearchSessions {
+        ref query,
+        ref results,
+        selected_index,
+    } = app.input_mode
+    {
+        draw_search_sessions_overlay(f, area, app, query, results, selected_index);
+        return;
+    }
+
+    // Check for history search overlay
+    if matches!(app.input_mode, InputMode::SearchHistory { .. }) {
+        draw_search_history_overlay(f, area, app);
+        return;
+    }
+
+    // Check for diff base picker
+    if let InputMode::SelectDiffBase { selected_index, .. } = app.input_mode {
+        draw_select_diff_base_overlay(f, area, app, selected_index);
+        return;
+    }
+
+    // Check for confirm remove repo overlay
+    if let InputMode::ConfirmRemoveRepo {
+        ref name,
+        session_count,
+        sessions,
+        ..
+    } = app.input_mode
+    {
+        draw_confirm_remove_repo_overlay(f, area, app, name, session_count, sessions);
+        return;
+    }
+
+    // Check for confirm stash-before-pull overlay
+    if let InputMode::ConfirmPullStash { ref branch, .. } = app.input_mode {
+        draw_confirm_pull_stash_overlay(f, area, app, branch);
+        return;
+    }
+
+    // Check for confirm broadcast overlay
+    if let InputMode::ConfirmBroadcast { ref text, count } = app.input_mode {
+        draw_confirm_broadcast_overlay(f, area, app, text, count);
+        return;
+    }
+
+    // Check for confirm quit overlay
+    if let InputMode::ConfirmQuit {
+        ref session_ids,
+        worktrees,
+        dirty_worktrees,
+    } = app.input_mode
+    {
+        draw_confirm_quit_overlay(f, area, app, session_ids.len(), worktrees, dirty_worktrees);
+        return;
+    }
+
     // Check for confirm delete overlay
     if let InputMode::ConfirmDelete(ref target) = app.input_mode {
         draw_confirm_delete_overlay(f, area, app, target);
@@ -88,11 +242,36 @@ fn draw_main_content(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
     if let InputMode::SelectProvider {
         ref providers,
         selected_index,
+        model_index,
         loading,
         ..
     } = app.input_mode
     {
-        draw_select_provider_overlay(f, area, app, providers, selected_index, loading);
+        draw_select_provider_overlay(
+            f,
+            area,
+            app,
+            providers,
+            selected_index,
+            model_index,
+            loading,
+        );
+        return;
+    }
+
+    // Check for review picker
+    if let InputMode::SelectReview {
+        ref reviews,
+        selected_index,
+    } = app.input_mode
+    {
+        draw_select_review_overlay(f, area, app, reviews, selected_index);
+        return;
+    }
+
+    // Check for recent sessions picker
+    if let InputMode::RecentSessions { selected_index } = app.input_mode {
+        draw_recent_sessions_overlay(f, area, app, selected_index);
         return;
     }
 
@@ -118,6 +297,12 @@ fn draw_main_content(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
         return;
     }
 
+    // Check for blame overlay
+    if app.input_mode == InputMode::BlamePopup {
+        blame::draw_blame_popup(f, area, app);
+        return;
+    }
+
     // Check for TODO popup
     if app.input_mode == InputMode::TodoPopup {
         draw_todo_popup(f, area, app);
@@ -144,7 +329,7 @@ fn draw_main_content(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
 
     // Check for confirm delete TODO overlay
     if let InputMode::ConfirmDeleteTodo { ref title, .. } = app.input_mode {
-        draw_confirm_delete_todo_overlay(f, area, title);
+        draw_confirm_delete_todo_overlay(f, area, app, title);
         return;
     }
 
@@ -160,20 +345,30 @@ fn draw_main_content(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
         return;
     }
 
+    // Hidden sidebar: the right panel takes the full width
+    if app.sidebar.hidden {
+        draw_right_panel(f, area, app);
+        return;
+    }
+
     // Split into sidebar and main content
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
-            Constraint::Length(38), // Sidebar - fixed width for file paths
-            Constraint::Min(0),     // Main content (Terminal or Diff) - take remaining
+            Constraint::Length(app.sidebar.width_cols(area.width)), // Sidebar
+            Constraint::Min(0), // Main content (Terminal or Diff) - take remaining
         ])
         .split(area);
 
     sidebar::draw_sidebar(f, chunks[0], app);
+    draw_right_panel(f, chunks[1], app);
+}
 
-    // Draw right panel based on view mode
+/// Draw the right panel based on view mode
+fn draw_right_panel(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
     match app.right_panel_view {
-        RightPanelView::Terminal => terminal::draw_terminal(f, chunks[1], app),
-        RightPanelView::Diff => diff::draw_diff_view(f, chunks[1], app),
+        RightPanelView::Terminal => terminal::draw_terminal(f, area, app),
+        RightPanelView::Diff => diff::draw_diff_view(f, area, app),
+        RightPanelView::Conflicts => conflicts::draw_conflicts_view(f, area, app),
     }
 }
@@ -0,0 +1,84 @@
+//! What keyboard macros record, and the keys that replay a step
+//!
+//! Macros hold actions rather than keys, so replaying a step looks up a key
+//! bound to its action with the current bindings; prefix steps press the
+//! prefix key first. Keys resolving to no action (text typed into the
+//! terminal, dialog fields) are never recorded.
+
+use super::input::resolver::pattern_to_key_event;
+use amux_config::{Action, BindingContext, KeyPattern, KeybindMap, MacroStep};
+use crossterm::event::KeyEvent;
+
+/// Whether an action resolved in `context` goes into the macro being recorded
+///
+/// Dialogs mostly read keys directly, so their steps wouldn't replay.
+pub fn is_recordable(context: BindingContext, action: &Action) -> bool {
+    !matches!(
+        context,
+        BindingContext::DialogText | BindingContext::DialogConfirm
+    ) && !matches!(
+        action,
+        Action::RecordMacro | Action::ReplayMacro | Action::Noop | Action::SendToTerminal
+    )
+}
+
+/// Keys replaying `step`, or None if no key resolves to its action now
+pub fn step_keys(keybinds: &KeybindMap, step: &MacroStep) -> Option<Vec<KeyEvent>> {
+    let key_str = keybinds.key_for_action(step.action.clone(), step.context)?;
+    // A global key may be taken by another action in the step's context
+    if keybinds.resolve(&key_str, step.context).as_ref() != Some(&step.action) {
+        return None;
+    }
+    let key = pattern_to_key_event(&KeyPattern::parse(&key_str).ok()?)?;
+    if step.context == BindingContext::Prefix {
+        Some(vec![pattern_to_key_event(keybinds.prefix_key())?, key])
+    } else {
+        Some(vec![key])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn keybinds() -> KeybindMap {
+        amux_config::defaults::default_config()
+            .to_keybind_map()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_is_recordable() {
+        assert!(is_recordable(BindingContext::Sidebar, &Action::MoveDown));
+        assert!(is_recordable(BindingContext::Prefix, &Action::FocusDiff));
+        assert!(!is_recordable(BindingContext::DialogText, &Action::Submit));
+        assert!(!is_recordable(BindingContext::Prefix, &Action::RecordMacro));
+        assert!(!is_recordable(
+            BindingContext::TerminalInsert,
+            &Action::SendToTerminal
+        ));
+    }
+
+    #[test]
+    fn test_step_keys() {
+        let keybinds = keybinds();
+        let step = MacroStep {
+            context: BindingContext::Prefix,
+            action: Action::FocusDiff,
+        };
+        assert_eq!(
+            step_keys(&keybinds, &step),
+            Some(vec![
+                KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL),
+                KeyEvent::new(KeyCode::Char('v'), KeyModifiers::NONE),
+            ])
+        );
+
+        let step = MacroStep {
+            context: BindingContext::Sidebar,
+            action: Action::ToggleSidebar,
+        };
+        assert_eq!(step_keys(&keybinds, &step), None);
+    }
+}
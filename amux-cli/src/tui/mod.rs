@@ -1,11 +1,20 @@
 //! TUI module
 
 mod app;
+pub mod branch_name;
+pub mod color;
 pub mod highlight;
+pub mod hyperlink;
 pub mod icons;
 mod input;
 mod layout;
+pub mod macros;
 pub mod overlays;
+pub mod persistence;
+pub mod restore;
+pub mod selection;
+pub mod session_file;
+pub mod sidebar_order;
 pub mod state;
 pub mod theme;
 pub mod views;
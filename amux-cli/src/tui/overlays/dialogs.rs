@@ -4,14 +4,21 @@
 //! TODO-related overlays are in views/todo/render.rs
 
 use crate::tui::app::App;
-use crate::tui::state::{DeleteTarget, ExitCleanupAction};
+use crate::tui::state::{DeleteTarget, ExitCleanupAction, InputMode, ProviderChoice, SidebarItem};
+use crate::tui::views::diff::render::comment_location;
+use crate::tui::views::git_status::render::format_age;
+use crate::tui::views::sidebar::render::format_memory;
+use crate::tui::widgets::helpers::{get_highlighter, truncate_start_to_width, truncate_to_width};
+use amux_config::{Action, BindingContext, QuitBehavior};
+use amux_proto::daemon::{ProviderInfo, ReviewInfo, SessionInfo, SessionStatus};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, List, ListItem, Paragraph},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
     Frame,
 };
+use unicode_width::UnicodeWidthStr;
 
 /// Draw input overlay for new branch
 pub fn draw_input_overlay(f: &mut Frame, area: Rect, app: &App) {
@@ -24,7 +31,384 @@ pub fn draw_input_overlay(f: &mut Frame, area: Rect, app: &App) {
     let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
     let popup_area = Rect::new(x, y, popup_width, popup_height);
 
+    let caption = match app.typed_branch_error() {
+        Some(error) => Line::styled(error.reason, Style::default().fg(theme.error)),
+        None => Line::styled(
+            "Will create from HEAD",
+            Style::default().fg(theme.text_tertiary),
+        ),
+    };
+    let text = vec![
+        Line::from(branch_name_spans(app, &app.text_input.content())),
+        Line::from(""),
+        caption,
+    ];
+
     // Draw input box with background to cover underlying content
+    let input = Paragraph::new(text)
+        .style(Style::default().fg(theme.neon_yellow).bg(theme.bg_level0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.neon_cyan).bg(theme.bg_level0))
+                .style(Style::default().bg(theme.bg_level0))
+                .title(" New Branch (Enter=create, Esc=cancel) "),
+        );
+    f.render_widget(input, popup_area);
+
+    // Show cursor
+    f.set_cursor_position((
+        popup_area.x + app.text_input.cursor_display_offset() as u16 + 1,
+        popup_area.y + 1,
+    ));
+}
+
+/// Draw rename session overlay
+pub fn draw_rename_session_overlay(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+
+    // Center the input box
+    let popup_width = 60.min(area.width.saturating_sub(4));
+    let popup_height = 5;
+    let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    // Draw input box with background to cover underlying content
+    let input = Paragraph::new(app.text_input.content())
+        .style(Style::default().fg(theme.neon_yellow).bg(theme.bg_level0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.success).bg(theme.bg_level0))
+                .style(Style::default().bg(theme.bg_level0))
+                .title(" Rename Session (Enter=save, Esc=cancel) "),
+        );
+    f.render_widget(input, popup_area);
+
+    // Show cursor
+    f.set_cursor_position((
+        popup_area.x + app.text_input.cursor_display_offset() as u16 + 1,
+        popup_area.y + 1,
+    ));
+}
+
+/// Draw rename branch overlay
+pub fn draw_rename_branch_overlay(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+
+    let popup_width = 60.min(area.width.saturating_sub(4));
+    let popup_height = 5;
+    let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    let input = Paragraph::new(app.text_input.content())
+        .style(Style::default().fg(theme.neon_yellow).bg(theme.bg_level0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.success).bg(theme.bg_level0))
+                .style(Style::default().bg(theme.bg_level0))
+                .title(" Rename Branch (Enter=save, Esc=cancel) "),
+        );
+    f.render_widget(input, popup_area);
+
+    f.set_cursor_position((
+        popup_area.x + app.text_input.cursor_display_offset() as u16 + 1,
+        popup_area.y + 1,
+    ));
+}
+
+/// Draw quick commit-and-push message overlay
+pub fn draw_quick_ship_overlay(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+
+    // Grow with the wrapped message, up to 10 lines
+    let popup_width = 70.min(area.width.saturating_sub(4));
+    let input_rows = app
+        .text_input
+        .wrap(popup_width.saturating_sub(2) as usize)
+        .lines
+        .len()
+        .min(10) as u16;
+    let popup_height = (input_rows + 4).min(area.height.saturating_sub(2));
+    let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    f.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.success).bg(theme.bg_level0))
+        .style(Style::default().bg(theme.bg_level0))
+        .title(" Commit all & Push (Alt+Enter=ship, Enter=newline, Esc=cancel) ");
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    app.text_input.render_wrapped(
+        f,
+        inner,
+        Style::default().fg(theme.neon_yellow).bg(theme.bg_level0),
+    );
+}
+
+/// Draw the system prompt overlay of a new session
+pub fn draw_session_system_prompt_overlay(
+    f: &mut Frame,
+    area: Rect,
+    app: &App,
+    provider: Option<&ProviderChoice>,
+) {
+    let theme = &app.theme;
+
+    // Grow with the wrapped prompt, up to 12 lines
+    let popup_width = 70.min(area.width.saturating_sub(4));
+    let input_rows = app
+        .text_input
+        .wrap(popup_width.saturating_sub(2) as usize)
+        .lines
+        .len()
+        .clamp(3, 12) as u16;
+    let popup_height = (input_rows + 2).min(area.height.saturating_sub(2));
+    let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    f.render_widget(Clear, popup_area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.neon_cyan).bg(theme.bg_level0))
+        .style(Style::default().bg(theme.bg_level0))
+        .title(format!(
+            " System Prompt for {} (Alt+Enter=create, Enter=newline, Esc=cancel) ",
+            provider.map_or("new session".to_string(), |c| format!(
+                "{} ({})",
+                c.provider, c.model
+            ))
+        ));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    app.text_input.render_wrapped(
+        f,
+        inner,
+        Style::default().fg(theme.neon_yellow).bg(theme.bg_level0),
+    );
+}
+
+/// Draw the details of a session
+pub fn draw_session_info_overlay(f: &mut Frame, area: Rect, app: &App, session_id: &str) {
+    let theme = &app.theme;
+    let Some(session) = app.current_repo().and_then(|repo| {
+        repo.sessions_by_worktree
+            .values()
+            .flatten()
+            .find(|s| s.id == session_id)
+    }) else {
+        return;
+    };
+
+    let status = match SessionStatus::try_from(session.status) {
+        Ok(SessionStatus::Running) => "running",
+        Ok(SessionStatus::Stopped) => "stopped",
+        Ok(SessionStatus::Trashed) => "trashed",
+        _ => "unknown",
+    };
+    let provider = if session.is_shell() {
+        "shell".to_string()
+    } else {
+        let provider = session.provider.as_deref().unwrap_or("claude");
+        match &session.model {
+            Some(model) => format!("{} ({})", provider, model),
+            None => provider.to_string(),
+        }
+    };
+
+    let mut rows: Vec<(&str, String)> = vec![
+        ("Name", session.name.clone()),
+        ("ID", session.id.chars().take(8).collect()),
+        ("Status", status.to_string()),
+        ("Provider", provider),
+        ("Branch", session.branch.clone()),
+    ];
+    if !session.command.is_empty() {
+        rows.push(("Command", shell_words::join(&session.command)));
+    }
+    if let Some(cwd) = &session.cwd {
+        rows.push(("Directory", cwd.clone()));
+    }
+    if session.created_at > 0 {
+        rows.push((
+            "Created",
+            format!("{} ago", format_age(session.created_at as i64)),
+        ));
+    }
+    if let Some(title) = &session.title {
+        rows.push(("Title", title.clone()));
+    }
+    if session.memory_kb > 0 {
+        rows.push((
+            "Usage",
+            format!(
+                "{} memory, {:.0}% CPU",
+                format_memory(session.memory_kb),
+                session.cpu_percent
+            ),
+        ));
+    }
+    if session.attach_count > 0 {
+        rows.push(("Attached", session.attach_count.to_string()));
+    }
+    if let Some(prompt) = session.system_prompt.as_deref().filter(|p| !p.is_empty()) {
+        let mut lines = prompt.lines();
+        let first = lines.next().unwrap_or_default();
+        let more = if lines.next().is_some() { " …" } else { "" };
+        rows.push(("System", format!("{}{}", first, more)));
+    }
+
+    let text: Vec<Line> = rows
+        .into_iter()
+        .map(|(label, value)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{:<10}", label),
+                    Style::default().fg(theme.text_tertiary),
+                ),
+                Span::styled(value, Style::default().fg(theme.text_primary)),
+            ])
+        })
+        .collect();
+
+    // Values longer than the popup are cut at its border
+    let popup_width = 64.min(area.width.saturating_sub(4));
+    let popup_height = (text.len() as u16 + 2).min(area.height.saturating_sub(2));
+    let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    f.render_widget(Clear, popup_area);
+    let info = Paragraph::new(text)
+        .style(Style::default().bg(theme.bg_level0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.neon_cyan).bg(theme.bg_level0))
+                .style(Style::default().bg(theme.bg_level0))
+                .title(" Session Info (any key to close) "),
+        );
+    f.render_widget(info, popup_area);
+}
+
+/// Draw the read-only session file viewer
+pub fn draw_session_file_overlay(
+    f: &mut Frame,
+    area: Rect,
+    app: &App,
+    path: &str,
+    lines: &[String],
+    scroll: usize,
+    truncated: bool,
+) {
+    let theme = &app.theme;
+
+    let popup_width = area.width.saturating_sub(8);
+    let popup_height = area.height.saturating_sub(4);
+    let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    // Keep the last page full rather than scrolling past the end
+    let visible = popup_height.saturating_sub(2) as usize;
+    let scroll = scroll.min(lines.len().saturating_sub(visible));
+    let highlighter = get_highlighter();
+    let text: Vec<Line> = lines
+        .iter()
+        .skip(scroll)
+        .take(visible)
+        .map(|line| {
+            Line::from(
+                highlighter
+                    .highlight_line(line, "session.json")
+                    .into_iter()
+                    .map(|(style, text)| Span::styled(text.to_string(), style))
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect();
+
+    let title_width = popup_width.saturating_sub(24) as usize;
+    let title = format!(" {} ", truncate_start_to_width(path, title_width));
+    let mut position = format!(
+        " {}-{}/{} ",
+        (scroll + 1).min(lines.len()),
+        scroll + text.len(),
+        lines.len()
+    );
+    if truncated {
+        position = format!(" truncated,{}", position);
+    }
+
+    f.render_widget(Clear, popup_area);
+    let viewer = Paragraph::new(text)
+        .style(Style::default().bg(theme.bg_level0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.neon_cyan).bg(theme.bg_level0))
+                .style(Style::default().bg(theme.bg_level0))
+                .title(title)
+                .title_bottom(Line::from(" j/k scroll, g/G top/bottom, q close ").left_aligned())
+                .title_bottom(Line::from(position).right_aligned()),
+        );
+    f.render_widget(viewer, popup_area);
+}
+
+/// Draw stash message overlay
+pub fn draw_stash_message_overlay(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+
+    let popup_width = 70.min(area.width.saturating_sub(4));
+    let popup_height = 5;
+    let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    let input = Paragraph::new(app.text_input.content())
+        .style(Style::default().fg(theme.neon_yellow).bg(theme.bg_level0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.success).bg(theme.bg_level0))
+                .style(Style::default().bg(theme.bg_level0))
+                .title(" Stash message, optional (Enter=stash, Esc=cancel) "),
+        );
+    f.render_widget(input, popup_area);
+
+    f.set_cursor_position((
+        popup_area.x + app.text_input.cursor_display_offset() as u16 + 1,
+        popup_area.y + 1,
+    ));
+}
+
+/// Draw add repository overlay
+pub fn draw_add_repo_overlay(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+
+    let popup_width = 70.min(area.width.saturating_sub(4));
+    let popup_height = 5;
+    let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
     let input = Paragraph::new(app.text_input.content())
         .style(Style::default().fg(theme.neon_yellow).bg(theme.bg_level0))
         .block(
@@ -33,48 +417,522 @@ pub fn draw_input_overlay(f: &mut Frame, area: Rect, app: &App) {
                 .border_type(BorderType::Rounded)
                 .border_style(Style::default().fg(theme.neon_cyan).bg(theme.bg_level0))
                 .style(Style::default().bg(theme.bg_level0))
-                .title(" New Branch (Enter=create, Esc=cancel) "),
-        );
-    f.render_widget(input, popup_area);
+                .title(" Add Repository Path (Enter=add, Esc=cancel) "),
+        );
+    f.render_widget(input, popup_area);
+
+    f.set_cursor_position((
+        popup_area.x + app.text_input.cursor_display_offset() as u16 + 1,
+        popup_area.y + 1,
+    ));
+}
+
+/// Draw the command line input for a new command session
+pub fn draw_run_command_overlay(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+
+    let popup_width = 70.min(area.width.saturating_sub(4));
+    let popup_height = 5;
+    let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    let input = Paragraph::new(app.text_input.content())
+        .style(Style::default().fg(theme.neon_yellow).bg(theme.bg_level0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.neon_cyan).bg(theme.bg_level0))
+                .style(Style::default().bg(theme.bg_level0))
+                .title(" Run Command (Enter=run, Esc=cancel) "),
+        );
+    f.render_widget(input, popup_area);
+
+    f.set_cursor_position((
+        popup_area.x + app.text_input.cursor_display_offset() as u16 + 1,
+        popup_area.y + 1,
+    ));
+}
+
+/// Draw the line input for broadcasting to the worktree's sessions
+pub fn draw_broadcast_input_overlay(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+
+    let popup_width = 70.min(area.width.saturating_sub(4));
+    let popup_height = 3;
+    let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    f.render_widget(Clear, popup_area);
+    let input = Paragraph::new(app.text_input.content())
+        .style(Style::default().fg(theme.neon_yellow).bg(theme.bg_level0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.warning).bg(theme.bg_level0))
+                .style(Style::default().bg(theme.bg_level0))
+                .title(" Broadcast to Running Sessions (Enter=send, Esc=cancel) "),
+        );
+    f.render_widget(input, popup_area);
+
+    f.set_cursor_position((
+        popup_area.x + app.text_input.cursor_display_offset() as u16 + 1,
+        popup_area.y + 1,
+    ));
+}
+
+/// Draw the directory input for a new shell
+pub fn draw_shell_directory_overlay(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+
+    let popup_width = 70.min(area.width.saturating_sub(4));
+    let popup_height = 3;
+    let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    let title = if app.completion.is_open() {
+        " Shell in Directory (Tab=next, Enter=accept, Esc=close list) "
+    } else {
+        " Shell in Directory (Tab=complete, Enter=open, Esc=cancel) "
+    };
+    f.render_widget(Clear, popup_area);
+    let input = Paragraph::new(app.text_input.content())
+        .style(Style::default().fg(theme.neon_yellow).bg(theme.bg_level0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.neon_cyan).bg(theme.bg_level0))
+                .style(Style::default().bg(theme.bg_level0))
+                .title(title),
+        );
+    f.render_widget(input, popup_area);
+
+    let input_row = Rect::new(popup_area.x + 1, popup_area.y + 1, popup_width - 2, 1);
+    app.completion.render(f, input_row, theme);
+
+    f.set_cursor_position((
+        popup_area.x + app.text_input.cursor_display_offset() as u16 + 1,
+        popup_area.y + 1,
+    ));
+}
+
+/// Draw session search overlay (query input + result list)
+pub fn draw_search_sessions_overlay(
+    f: &mut Frame,
+    area: Rect,
+    app: &App,
+    query: &str,
+    results: &[SessionInfo],
+    selected_index: usize,
+) {
+    let theme = &app.theme;
+
+    let popup_width = 80.min(area.width.saturating_sub(4));
+    let popup_height = (results.len() as u16 * 2 + 5).clamp(8, 22).min(area.height);
+    let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.neon_cyan).bg(theme.bg_level0))
+        .style(Style::default().bg(theme.bg_level0))
+        .title(" Search Sessions (Up/Down=select, Enter=jump, Esc=cancel) ");
+    f.render_widget(block, popup_area);
+
+    let inner = popup_area.inner(ratatui::layout::Margin::new(1, 1));
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Query
+            Constraint::Length(1), // Separator / status
+            Constraint::Min(1),    // Results
+        ])
+        .split(inner);
+
+    let prompt = "/ ";
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled(prompt, Style::default().fg(theme.text_tertiary)),
+        Span::styled(
+            app.text_input.content(),
+            Style::default().fg(theme.neon_yellow),
+        ),
+    ]))
+    .style(Style::default().bg(theme.bg_level0));
+    f.render_widget(input, chunks[0]);
+
+    let status = if query.is_empty() {
+        "Type to search names and transcripts".to_string()
+    } else {
+        match results.len() {
+            0 => "No matches".to_string(),
+            1 => "1 match".to_string(),
+            n => format!("{} matches", n),
+        }
+    };
+    f.render_widget(
+        Paragraph::new(status).style(Style::default().fg(theme.text_tertiary).bg(theme.bg_level0)),
+        chunks[1],
+    );
+
+    let highlight = Style::default()
+        .fg(theme.bg_level0)
+        .bg(theme.neon_yellow)
+        .add_modifier(Modifier::BOLD);
+    let items: Vec<ListItem> = results
+        .iter()
+        .enumerate()
+        .map(|(i, session)| {
+            let is_selected = i == selected_index;
+            let name_style = if is_selected {
+                Style::default()
+                    .fg(theme.neon_cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text_primary)
+            };
+            let prefix = if is_selected { "> " } else { "  " };
+
+            let mut title = vec![Span::styled(prefix, name_style)];
+            title.extend(highlight_match(&session.name, query, name_style, highlight));
+            title.push(Span::styled(
+                format!("  {}", session.branch),
+                Style::default().fg(theme.text_tertiary),
+            ));
+
+            let context = session.match_context.as_deref().unwrap_or("");
+            let mut detail = vec![Span::raw("    ")];
+            detail.extend(highlight_match(
+                context,
+                query,
+                Style::default().fg(theme.text_secondary),
+                highlight,
+            ));
+
+            ListItem::new(vec![Line::from(title), Line::from(detail)])
+                .style(Style::default().bg(theme.bg_level0))
+        })
+        .collect();
+    f.render_widget(
+        List::new(items).style(Style::default().bg(theme.bg_level0)),
+        chunks[2],
+    );
 
-    // Show cursor
     f.set_cursor_position((
-        popup_area.x + app.text_input.cursor_display_offset() as u16 + 1,
-        popup_area.y + 1,
+        chunks[0].x + prompt.width() as u16 + app.text_input.cursor_display_offset() as u16,
+        chunks[0].y,
     ));
 }
 
-/// Draw rename session overlay
-pub fn draw_rename_session_overlay(f: &mut Frame, area: Rect, app: &App) {
+/// Draw history search overlay (query input + matches grouped by session)
+pub fn draw_search_history_overlay(f: &mut Frame, area: Rect, app: &App) {
+    let InputMode::SearchHistory {
+        query,
+        regex,
+        results,
+        selected_index,
+        searching,
+    } = &app.input_mode
+    else {
+        return;
+    };
     let theme = &app.theme;
 
-    // Center the input box
+    let popup_width = 90.min(area.width.saturating_sub(4));
+    let popup_height = 24.min(area.height);
+    let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.neon_cyan).bg(theme.bg_level0))
+        .style(Style::default().bg(theme.bg_level0))
+        .title(" Search History (Up/Down=select, Ctrl-r=regex, Enter=show, Esc=cancel) ");
+    f.render_widget(block, popup_area);
+
+    let inner = popup_area.inner(ratatui::layout::Margin::new(1, 1));
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Query
+            Constraint::Length(1), // Status
+            Constraint::Min(1),    // Matches
+        ])
+        .split(inner);
+
+    let prompt = if *regex { "re/ " } else { "/ " };
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled(prompt, Style::default().fg(theme.text_tertiary)),
+        Span::styled(
+            app.text_input.content(),
+            Style::default().fg(theme.neon_yellow),
+        ),
+    ]))
+    .style(Style::default().bg(theme.bg_level0));
+    f.render_widget(input, chunks[0]);
+
+    let status = if query.trim().is_empty() {
+        "Type to search the output of this repo's sessions".to_string()
+    } else {
+        let count = match results.len() {
+            0 if *searching => String::new(),
+            0 => "No matches".to_string(),
+            1 => "1 match".to_string(),
+            n => format!("{} matches", n),
+        };
+        if *searching {
+            format!("Searching... {}", count)
+        } else {
+            count
+        }
+    };
+    f.render_widget(
+        Paragraph::new(status).style(Style::default().fg(theme.text_tertiary).bg(theme.bg_level0)),
+        chunks[1],
+    );
+
+    // Regex matches can't be highlighted by the literal matcher
+    let literal = if *regex { "" } else { query.as_str() };
+    let highlight = Style::default()
+        .fg(theme.bg_level0)
+        .bg(theme.neon_yellow)
+        .add_modifier(Modifier::BOLD);
+    let context_style = Style::default().fg(theme.text_tertiary);
+    let items: Vec<ListItem> = results
+        .iter()
+        .enumerate()
+        .map(|(i, found)| {
+            let is_selected = i == *selected_index;
+            let mut lines = Vec::new();
+
+            // Session header at the start of each group
+            if i == 0 || results[i - 1].session_id != found.session_id {
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        found.session_name.as_str(),
+                        Style::default()
+                            .fg(theme.neon_cyan)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(format!("  {}", found.branch), context_style),
+                ]));
+            }
+
+            // The selected match shows its surrounding lines
+            if is_selected {
+                for line in &found.context_before {
+                    lines.push(Line::styled(format!("    {}", line), context_style));
+                }
+            }
+            let line_style = if is_selected {
+                Style::default()
+                    .fg(theme.text_primary)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text_secondary)
+            };
+            let prefix = if is_selected { "  > " } else { "    " };
+            let mut spans = vec![Span::styled(prefix, line_style)];
+            spans.extend(highlight_match(&found.line, literal, line_style, highlight));
+            lines.push(Line::from(spans));
+            if is_selected {
+                for line in &found.context_after {
+                    lines.push(Line::styled(format!("    {}", line), context_style));
+                }
+            }
+
+            ListItem::new(lines).style(Style::default().bg(theme.bg_level0))
+        })
+        .collect();
+    let mut list_state = ListState::default().with_selected(Some(*selected_index));
+    f.render_stateful_widget(
+        List::new(items).style(Style::default().bg(theme.bg_level0)),
+        chunks[2],
+        &mut list_state,
+    );
+
+    f.set_cursor_position((
+        chunks[0].x + prompt.width() as u16 + app.text_input.cursor_display_offset() as u16,
+        chunks[0].y,
+    ));
+}
+
+/// Draw the diff base picker (refs filtered by the typed text)
+pub fn draw_select_diff_base_overlay(f: &mut Frame, area: Rect, app: &App, selected_index: usize) {
+    let theme = &app.theme;
+    let matches = app.diff_base_matches();
+    let filter = app.text_input.trim();
+
     let popup_width = 60.min(area.width.saturating_sub(4));
-    let popup_height = 5;
+    let popup_height = (matches.len() as u16 + 5).clamp(8, 20).min(area.height);
     let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
     let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
     let popup_area = Rect::new(x, y, popup_width, popup_height);
 
-    // Draw input box with background to cover underlying content
-    let input = Paragraph::new(app.text_input.content())
-        .style(Style::default().fg(theme.neon_yellow).bg(theme.bg_level0))
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(theme.success).bg(theme.bg_level0))
-                .style(Style::default().bg(theme.bg_level0))
-                .title(" Rename Session (Enter=save, Esc=cancel) "),
-        );
-    f.render_widget(input, popup_area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.neon_cyan).bg(theme.bg_level0))
+        .style(Style::default().bg(theme.bg_level0))
+        .title(" Diff Against (Up/Down=select, Enter=apply, Esc=cancel) ");
+    f.render_widget(block, popup_area);
+
+    let inner = popup_area.inner(ratatui::layout::Margin::new(1, 1));
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Filter
+            Constraint::Length(1), // Status
+            Constraint::Min(1),    // Refs
+        ])
+        .split(inner);
+
+    let prompt = "ref: ";
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled(prompt, Style::default().fg(theme.text_tertiary)),
+        Span::styled(
+            app.text_input.content(),
+            Style::default().fg(theme.neon_yellow),
+        ),
+    ]))
+    .style(Style::default().bg(theme.bg_level0));
+    f.render_widget(input, chunks[0]);
+
+    let status = if matches.is_empty() {
+        format!("No matching ref; Enter diffs against '{}'", filter)
+    } else {
+        "Branches and tags; type to filter or enter a SHA".to_string()
+    };
+    f.render_widget(
+        Paragraph::new(status).style(Style::default().fg(theme.text_tertiary).bg(theme.bg_level0)),
+        chunks[1],
+    );
+
+    // Scroll so the selection stays visible
+    let visible = chunks[2].height as usize;
+    let skip = (selected_index + 1).saturating_sub(visible);
+    let highlight = Style::default()
+        .fg(theme.bg_level0)
+        .bg(theme.neon_yellow)
+        .add_modifier(Modifier::BOLD);
+    let items: Vec<ListItem> = matches
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .take(visible)
+        .map(|(i, name)| {
+            let is_selected = i == selected_index;
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.neon_cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text_primary)
+            };
+            let prefix = if is_selected { "> " } else { "  " };
+            let mut spans = vec![Span::styled(prefix, style)];
+            spans.extend(highlight_match(name, &filter, style, highlight));
+            ListItem::new(Line::from(spans)).style(Style::default().bg(theme.bg_level0))
+        })
+        .collect();
+    f.render_widget(
+        List::new(items).style(Style::default().bg(theme.bg_level0)),
+        chunks[2],
+    );
 
-    // Show cursor
     f.set_cursor_position((
-        popup_area.x + app.text_input.cursor_display_offset() as u16 + 1,
-        popup_area.y + 1,
+        chunks[0].x + prompt.width() as u16 + app.text_input.cursor_display_offset() as u16,
+        chunks[0].y,
     ));
 }
 
+/// Split text into spans, styling the first case-insensitive match of `query`
+fn highlight_match<'a>(text: &'a str, query: &str, base: Style, matched: Style) -> Vec<Span<'a>> {
+    let query: Vec<char> = query.chars().collect();
+    if query.is_empty() {
+        return vec![Span::styled(text, base)];
+    }
+
+    let found = text.char_indices().find_map(|(start, _)| {
+        let mut chars = text[start..].char_indices();
+        for q in &query {
+            let (_, c) = chars.next()?;
+            if !c.to_lowercase().eq(q.to_lowercase()) {
+                return None;
+            }
+        }
+        let end = chars.next().map_or(text.len(), |(i, _)| start + i);
+        Some((start, end))
+    });
+
+    match found {
+        Some((start, end)) => vec![
+            Span::styled(&text[..start], base),
+            Span::styled(&text[start..end], matched),
+            Span::styled(&text[end..], base),
+        ],
+        None => vec![Span::styled(text, base)],
+    }
+}
+
+/// Destroy/Stop choice lines for session cleanup, marking `app.session_delete_action`
+fn cleanup_option_lines(app: &App) -> Vec<Line<'static>> {
+    let theme = &app.theme;
+    let mut lines = Vec::new();
+
+    // Option 1: Destroy
+    let destroy_indicator = if app.session_delete_action == ExitCleanupAction::Destroy {
+        "▸ "
+    } else {
+        "  "
+    };
+    lines.push(Line::from(vec![
+        Span::raw(destroy_indicator),
+        Span::styled(
+            "[d] Destroy",
+            if app.session_delete_action == ExitCleanupAction::Destroy {
+                Style::default()
+                    .fg(theme.neon_yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.error)
+            },
+        ),
+        Span::raw(" (delete all data)"),
+    ]));
+
+    // Option 2: Stop
+    let stop_indicator = if app.session_delete_action == ExitCleanupAction::Stop {
+        "▸ "
+    } else {
+        "  "
+    };
+    lines.push(Line::from(vec![
+        Span::raw(stop_indicator),
+        Span::styled(
+            "[s] Stop",
+            if app.session_delete_action == ExitCleanupAction::Stop {
+                Style::default()
+                    .fg(theme.neon_yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.neon_cyan)
+            },
+        ),
+        Span::raw(" (stop PTY, keep metadata)"),
+    ]));
+
+    lines
+}
+
 /// Draw confirm delete overlay
 pub fn draw_confirm_delete_overlay(f: &mut Frame, area: Rect, app: &App, target: &DeleteTarget) {
     let theme = &app.theme;
@@ -87,52 +945,42 @@ pub fn draw_confirm_delete_overlay(f: &mut Frame, area: Rect, app: &App, target:
                 Line::from(""),
             ];
 
-            // Option 1: Destroy
-            let destroy_indicator = if app.session_delete_action == ExitCleanupAction::Destroy {
-                "▸ "
-            } else {
-                "  "
+            lines.extend(cleanup_option_lines(app));
+
+            // Where the selected action comes from
+            let worktree_default = match app.current_sidebar_item() {
+                SidebarItem::Session(wt_idx, _) => app.worktree_delete_action(wt_idx),
+                _ => None,
             };
+            let selected = app.session_delete_action;
+            let source = match worktree_default {
+                Some(action) if action == selected => "worktree default",
+                None if selected == ExitCleanupAction::default() => "default",
+                _ => "this time only",
+            };
+            lines.push(Line::from(""));
             lines.push(Line::from(vec![
-                Span::raw(destroy_indicator),
+                Span::raw("Will "),
                 Span::styled(
-                    "[d] Destroy",
-                    if app.session_delete_action == ExitCleanupAction::Destroy {
-                        Style::default()
-                            .fg(theme.neon_yellow)
-                            .add_modifier(Modifier::BOLD)
-                    } else {
-                        Style::default().fg(theme.error)
-                    },
+                    selected.label().to_lowercase(),
+                    Style::default()
+                        .fg(theme.neon_yellow)
+                        .add_modifier(Modifier::BOLD),
                 ),
-                Span::raw(" (delete all data)"),
-            ]));
-
-            // Option 2: Stop
-            let stop_indicator = if app.session_delete_action == ExitCleanupAction::Stop {
-                "▸ "
-            } else {
-                "  "
-            };
-            lines.push(Line::from(vec![
-                Span::raw(stop_indicator),
                 Span::styled(
-                    "[s] Stop",
-                    if app.session_delete_action == ExitCleanupAction::Stop {
-                        Style::default()
-                            .fg(theme.neon_yellow)
-                            .add_modifier(Modifier::BOLD)
-                    } else {
-                        Style::default().fg(theme.neon_cyan)
-                    },
+                    format!(" ({})", source),
+                    Style::default().fg(theme.text_secondary),
                 ),
-                Span::raw(" (stop PTY, keep metadata)"),
             ]));
 
             lines.push(Line::from(""));
             lines.push(Line::from(vec![
+                Span::styled("[Tab]", Style::default().fg(theme.neon_cyan)),
+                Span::raw(" Switch  "),
                 Span::styled("[Enter]", Style::default().fg(theme.success)),
                 Span::raw(" Confirm  "),
+                Span::styled("[a]", Style::default().fg(theme.neon_yellow)),
+                Span::raw(" Always  "),
                 Span::styled("[Esc/n]", Style::default().fg(theme.error)),
                 Span::raw(" Cancel"),
             ]));
@@ -206,7 +1054,11 @@ pub fn draw_add_worktree_overlay(f: &mut Frame, area: Rect, app: &App, base_bran
         .borders(Borders::ALL)
         .border_style(Style::default().fg(theme.neon_cyan).bg(theme.bg_level0))
         .style(Style::default().bg(theme.bg_level0))
-        .title(" Add Worktree (j/k=select, Enter=add, Esc=cancel) ");
+        .title(if app.completion.is_open() {
+            " Add Worktree (Tab=next, Enter=accept, Esc=close list) "
+        } else {
+            " Add Worktree (j/k=select, Tab=complete, Enter=add, Esc=cancel) "
+        });
     f.render_widget(block, popup_area);
 
     // Instructions
@@ -214,17 +1066,23 @@ pub fn draw_add_worktree_overlay(f: &mut Frame, area: Rect, app: &App, base_bran
         .style(Style::default().fg(theme.text_tertiary).bg(theme.bg_level0));
     f.render_widget(instructions, chunks[0]);
 
-    // Base branch info (only shown when typing new branch name)
-    let base_info = match base_branch {
-        Some(branch) => format!("Base: {} (new branch will be created from here)", branch),
-        None => "Base: HEAD (new branch will be created from HEAD)".to_string(),
-    };
-    let base_style = if !app.text_input.is_empty() {
-        Style::default().fg(theme.success).bg(theme.bg_level0)
+    // What the typed name will do: create from the base, reuse a branch, or nothing
+    let base = base_branch.unwrap_or("HEAD");
+    let typed = app.text_input.content();
+    let (caption, caption_color) = if let Some(error) = app.typed_branch_error() {
+        (error.reason, theme.error)
+    } else if typed.is_empty() {
+        (
+            format!("Base: {} (new branches are created from here)", base),
+            theme.text_tertiary,
+        )
+    } else if app.available_branches().iter().any(|b| b.branch == typed) {
+        ("Will use the existing branch".to_string(), theme.success)
     } else {
-        Style::default().fg(theme.text_tertiary).bg(theme.bg_level0)
+        (format!("Will create from {}", base), theme.success)
     };
-    let base_paragraph = Paragraph::new(base_info).style(base_style);
+    let base_paragraph =
+        Paragraph::new(caption).style(Style::default().fg(caption_color).bg(theme.bg_level0));
     f.render_widget(base_paragraph, chunks[1]);
 
     // Branch list
@@ -261,47 +1119,226 @@ pub fn draw_add_worktree_overlay(f: &mut Frame, area: Rect, app: &App, base_bran
     } else {
         Style::default().fg(theme.text_tertiary).bg(theme.bg_level0)
     };
-    let input_text = if app.text_input.is_empty() {
-        "New branch: (type to create new)"
-    } else {
-        app.text_input.content()
-    };
-    let prefix = if !app.text_input.is_empty() {
-        "> "
+    let line = if app.text_input.is_empty() {
+        Line::from("  New: New branch: (type to create new)")
     } else {
-        "  "
+        let mut spans = vec![Span::raw("> New: ")];
+        spans.extend(branch_name_spans(app, &typed));
+        Line::from(spans)
     };
-    let input = Paragraph::new(format!("{}New: {}", prefix, input_text)).style(input_style);
+    let input = Paragraph::new(line).style(input_style);
     f.render_widget(input, chunks[4]);
 
-    // Show cursor if typing
-    if !app.text_input.is_empty() {
-        f.set_cursor_position((
-            chunks[4].x + 7 + app.text_input.cursor_display_offset() as u16, // 7 = "> New: ".len()
-            chunks[4].y,
+    // Show cursor if typing
+    if !app.text_input.is_empty() {
+        f.set_cursor_position((
+            chunks[4].x + 7 + app.text_input.cursor_display_offset() as u16, // 7 = "> New: ".len()
+            chunks[4].y,
+        ));
+    }
+
+    // Branch name completion (Tab)
+    let name_area = Rect {
+        x: chunks[4].x + 7,
+        width: chunks[4].width.saturating_sub(7),
+        ..chunks[4]
+    };
+    app.completion.render(f, name_area, theme);
+}
+
+/// A typed branch name with the part making it invalid highlighted
+fn branch_name_spans(app: &App, name: &str) -> Vec<Span<'static>> {
+    let Some(error) = app.typed_branch_error() else {
+        return vec![Span::raw(name.to_string())];
+    };
+    let range = error.range;
+    // An empty range (nothing wrong in particular) marks nothing
+    let bad = match &name[range.clone()] {
+        "" => Span::raw(""),
+        bad => Span::styled(
+            bad.to_string(),
+            Style::default().fg(app.theme.bg_level0).bg(app.theme.error),
+        ),
+    };
+    vec![
+        Span::raw(name[..range.start].to_string()),
+        bad,
+        Span::raw(name[range.end..].to_string()),
+    ]
+}
+
+/// Draw confirm delete branch overlay (after worktree deletion)
+pub fn draw_confirm_delete_branch_overlay(f: &mut Frame, area: Rect, app: &App, branch: &str) {
+    let theme = &app.theme;
+
+    // Center the confirm box
+    let popup_width = 55.min(area.width.saturating_sub(4));
+    let popup_height = 6;
+    let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    let text = vec![
+        Line::from(format!("Worktree deleted. Delete branch '{}'?", branch)),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("[y]", Style::default().fg(theme.error)),
+            Span::raw(" Yes  "),
+            Span::styled("[a]", Style::default().fg(theme.neon_yellow)),
+            Span::raw(" Always delete  "),
+            Span::styled("[n/Esc]", Style::default().fg(theme.success)),
+            Span::raw(" No, keep branch"),
+        ]),
+    ];
+
+    let confirm = Paragraph::new(text)
+        .alignment(ratatui::layout::Alignment::Center)
+        .style(Style::default().bg(theme.bg_level0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.warning).bg(theme.bg_level0))
+                .style(Style::default().bg(theme.bg_level0))
+                .title(" Delete Branch? "),
+        );
+    f.render_widget(confirm, popup_area);
+}
+
+/// Draw confirm stash-before-pull overlay
+pub fn draw_confirm_pull_stash_overlay(f: &mut Frame, area: Rect, app: &App, branch: &str) {
+    let theme = &app.theme;
+
+    // Center the confirm box
+    let popup_width = 60.min(area.width.saturating_sub(4));
+    let popup_height = 7;
+    let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    let text = vec![
+        Line::from(format!("Worktree '{}' has uncommitted changes.", branch)),
+        Line::from("Stash them, pull, and re-apply afterwards?"),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("[y/Enter]", Style::default().fg(theme.warning)),
+            Span::raw(" Stash & pull  "),
+            Span::styled("[n/Esc]", Style::default().fg(theme.success)),
+            Span::raw(" Cancel"),
+        ]),
+    ];
+
+    let confirm = Paragraph::new(text)
+        .alignment(ratatui::layout::Alignment::Center)
+        .style(Style::default().bg(theme.bg_level0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.warning).bg(theme.bg_level0))
+                .style(Style::default().bg(theme.bg_level0))
+                .title(" Pull Dirty Worktree? "),
+        );
+    f.render_widget(confirm, popup_area);
+}
+
+/// Draw the summary shown when quitting with work in progress
+pub fn draw_confirm_quit_overlay(
+    f: &mut Frame,
+    area: Rect,
+    app: &App,
+    running: usize,
+    worktrees: usize,
+    dirty_worktrees: usize,
+) {
+    let theme = &app.theme;
+
+    let popup_width = 64.min(area.width.saturating_sub(4));
+    let popup_height = 7;
+    let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    let plural = |n: usize, word: &str| format!("{} {}{}", n, word, if n == 1 { "" } else { "s" });
+    let mut summary = Vec::new();
+    if running > 0 {
+        summary.push(format!(
+            "{} running in {}",
+            plural(running, "session"),
+            plural(worktrees, "worktree")
+        ));
+    }
+    if dirty_worktrees > 0 {
+        summary.push(format!(
+            "{} with uncommitted changes",
+            plural(dirty_worktrees, "worktree")
         ));
     }
+
+    let mut keys = vec![
+        Span::styled("[q]", Style::default().fg(theme.success)),
+        Span::raw(" Quit, keep running  "),
+    ];
+    if running > 0 {
+        keys.push(Span::styled("[s]", Style::default().fg(theme.warning)));
+        keys.push(Span::raw(" Stop all & quit  "));
+    }
+    keys.push(Span::styled("[Esc]", Style::default().fg(theme.success)));
+    keys.push(Span::raw(" Cancel"));
+
+    let text = vec![
+        Line::from(summary.join(", ")),
+        Line::from(""),
+        Line::from(keys),
+    ];
+
+    let confirm = Paragraph::new(text)
+        .alignment(ratatui::layout::Alignment::Center)
+        .style(Style::default().bg(theme.bg_level0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.warning).bg(theme.bg_level0))
+                .style(Style::default().bg(theme.bg_level0))
+                .title(" Quit? "),
+        );
+    f.render_widget(confirm, popup_area);
 }
 
-/// Draw confirm delete branch overlay (after worktree deletion)
-pub fn draw_confirm_delete_branch_overlay(f: &mut Frame, area: Rect, app: &App, branch: &str) {
+/// Draw the confirmation before broadcasting a line
+pub fn draw_confirm_broadcast_overlay(
+    f: &mut Frame,
+    area: Rect,
+    app: &App,
+    text: &str,
+    count: usize,
+) {
     let theme = &app.theme;
 
-    // Center the confirm box
-    let popup_width = 55.min(area.width.saturating_sub(4));
-    let popup_height = 6;
+    let popup_width = 60.min(area.width.saturating_sub(4));
+    let popup_height = 7;
     let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
     let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
     let popup_area = Rect::new(x, y, popup_width, popup_height);
 
+    let shown = truncate_to_width(text, popup_width.saturating_sub(6) as usize);
     let text = vec![
-        Line::from(format!("Worktree deleted. Delete branch '{}'?", branch)),
+        Line::from(format!(
+            "Send to {} session{}?",
+            count,
+            if count == 1 { "" } else { "s" }
+        )),
+        Line::from(Span::styled(
+            format!("'{}'", shown),
+            Style::default().fg(theme.neon_yellow),
+        )),
         Line::from(""),
         Line::from(vec![
-            Span::styled("[y]", Style::default().fg(theme.error)),
-            Span::raw(" Yes, delete branch  "),
+            Span::styled("[y/Enter]", Style::default().fg(theme.warning)),
+            Span::raw(" Send  "),
             Span::styled("[n/Esc]", Style::default().fg(theme.success)),
-            Span::raw(" No, keep branch"),
+            Span::raw(" Cancel"),
         ]),
     ];
 
@@ -314,7 +1351,155 @@ pub fn draw_confirm_delete_branch_overlay(f: &mut Frame, area: Rect, app: &App,
                 .border_type(BorderType::Rounded)
                 .border_style(Style::default().fg(theme.warning).bg(theme.bg_level0))
                 .style(Style::default().bg(theme.bg_level0))
-                .title(" Delete Branch? "),
+                .title(" Broadcast Input? "),
+        );
+    f.render_widget(confirm, popup_area);
+}
+
+/// Draw the typed confirmation for restoring a session checkpoint
+pub fn draw_confirm_restore_checkpoint_overlay(
+    f: &mut Frame,
+    area: Rect,
+    app: &App,
+    session_name: &str,
+    branch: &str,
+    created_at: i64,
+) {
+    let theme = &app.theme;
+
+    let popup_width = 64.min(area.width.saturating_sub(4));
+    let popup_height = 8;
+    let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    let text = vec![
+        Line::from(format!(
+            "Restore '{}' to the start of session '{}' ({} ago)?",
+            branch,
+            session_name,
+            format_age(created_at)
+        )),
+        Line::from(Span::styled(
+            "Commits and changes made since are discarded.",
+            Style::default().fg(theme.warning),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Type restore: ", Style::default().fg(theme.text_tertiary)),
+            Span::styled(
+                app.text_input.content(),
+                Style::default().fg(theme.neon_yellow),
+            ),
+        ]),
+    ];
+
+    let confirm = Paragraph::new(text)
+        .style(Style::default().bg(theme.bg_level0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.error).bg(theme.bg_level0))
+                .style(Style::default().bg(theme.bg_level0))
+                .title(" Restore Checkpoint (Enter=restore, Esc=cancel) "),
+        );
+    f.render_widget(confirm, popup_area);
+
+    f.set_cursor_position((
+        popup_area.x
+            + 1
+            + "Type restore: ".width() as u16
+            + app.text_input.cursor_display_offset() as u16,
+        popup_area.y + 4,
+    ));
+}
+
+/// Draw confirm remove repo overlay
+pub fn draw_confirm_remove_repo_overlay(
+    f: &mut Frame,
+    area: Rect,
+    app: &App,
+    name: &str,
+    session_count: usize,
+    sessions: QuitBehavior,
+) {
+    let theme = &app.theme;
+
+    let mut lines = vec![
+        Line::from(format!("Remove repo '{}' from amux?", name)),
+        Line::from(Span::styled(
+            "Files on disk are not touched.",
+            Style::default().fg(theme.text_tertiary),
+        )),
+        Line::from(""),
+    ];
+    if session_count > 0 {
+        lines.push(Line::from(format!(
+            "Its {} session(s) will be:",
+            session_count
+        )));
+        let choices = [
+            (
+                QuitBehavior::Destroy,
+                "[d] Destroy",
+                theme.error,
+                " (delete all data)",
+            ),
+            (
+                QuitBehavior::Stop,
+                "[s] Stop",
+                theme.neon_cyan,
+                " (stop PTY, keep metadata)",
+            ),
+            (
+                QuitBehavior::Detach,
+                "[t] Detach",
+                theme.success,
+                " (keep running, listed under no repo)",
+            ),
+        ];
+        for (choice, label, color, note) in choices {
+            let (indicator, style) = if choice == sessions {
+                (
+                    "▸ ",
+                    Style::default()
+                        .fg(theme.neon_yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                ("  ", Style::default().fg(color))
+            };
+            lines.push(Line::from(vec![
+                Span::raw(indicator),
+                Span::styled(label, style),
+                Span::raw(note),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(vec![
+        Span::styled("[y/Enter]", Style::default().fg(theme.warning)),
+        Span::raw(" Remove  "),
+        Span::styled("[n/Esc]", Style::default().fg(theme.success)),
+        Span::raw(" Cancel"),
+    ]));
+
+    let popup_width = 60.min(area.width.saturating_sub(4));
+    let popup_height = (lines.len() as u16 + 2).min(area.height.saturating_sub(4));
+    let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    let confirm = Paragraph::new(lines)
+        .style(Style::default().bg(theme.bg_level0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.warning).bg(theme.bg_level0))
+                .style(Style::default().bg(theme.bg_level0))
+                .title(" Remove Repository "),
         );
     f.render_widget(confirm, popup_area);
 }
@@ -380,25 +1565,20 @@ pub fn draw_add_line_comment_overlay(
 ) {
     let theme = &app.theme;
 
-    // Calculate input lines for dynamic height
-    let input_lines: Vec<&str> = app.text_input.content().lines().collect();
-    let input_line_count = input_lines.len().max(1);
+    // Grow with the wrapped comment; 4 = borders + "> "
+    let popup_width = 70.min(area.width.saturating_sub(4));
+    let input_width = popup_width.saturating_sub(4);
+    let input_rows = app.text_input.wrap(input_width as usize).lines.len() as u16;
 
     // Center the input box with dynamic height
-    let popup_width = 70.min(area.width.saturating_sub(4));
-    let popup_height = (6 + input_line_count as u16).min(area.height.saturating_sub(4));
+    let popup_height = (6 + input_rows).min(area.height.saturating_sub(4));
     let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
     let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
     let popup_area = Rect::new(x, y, popup_width, popup_height);
 
-    // Truncate file path if too long
-    // Only truncate if max_path_len > 3 to ensure room for "..." plus at least one character
+    // Truncate file path if too long, keeping its end
     let max_path_len = (popup_width as usize).saturating_sub(20);
-    let display_path = if file_path.len() > max_path_len && max_path_len > 3 {
-        format!("...{}", &file_path[file_path.len() - max_path_len + 3..])
-    } else {
-        file_path.to_string()
-    };
+    let display_path = truncate_start_to_width(file_path, max_path_len);
 
     // Build text with multiline input support
     let mut text = vec![
@@ -409,28 +1589,17 @@ pub fn draw_add_line_comment_overlay(
         Line::from(vec![
             Span::styled("Line: ", Style::default().fg(theme.text_tertiary)),
             Span::styled(
-                format!("{}", line_number),
+                comment_location(line_number),
                 Style::default().fg(theme.neon_cyan),
             ),
         ]),
         Line::from(""),
     ];
 
-    // Add input lines
-    for (i, line) in input_lines.iter().enumerate() {
-        let prefix = if i == 0 { "> " } else { "  " };
-        text.push(Line::from(vec![
-            Span::styled(prefix, Style::default().fg(theme.neon_yellow)),
-            Span::styled(*line, Style::default().fg(theme.neon_yellow)),
-        ]));
-    }
-    // Handle empty input
-    if input_lines.is_empty() {
-        text.push(Line::from(vec![Span::styled(
-            "> ",
-            Style::default().fg(theme.neon_yellow),
-        )]));
-    }
+    text.push(Line::from(Span::styled(
+        "> ",
+        Style::default().fg(theme.neon_yellow),
+    )));
 
     let input = Paragraph::new(text)
         .style(Style::default().bg(theme.bg_level0))
@@ -440,14 +1609,22 @@ pub fn draw_add_line_comment_overlay(
                 .border_type(BorderType::Rounded)
                 .border_style(Style::default().fg(theme.neon_cyan).bg(theme.bg_level0))
                 .style(Style::default().bg(theme.bg_level0))
-                .title(" Add Comment (Enter=save, Shift+Enter=newline, Esc=cancel) "),
+                .title(" Add Comment (Alt+Enter=save, Enter=newline, Esc=cancel) "),
         );
     f.render_widget(input, popup_area);
 
-    // Calculate cursor position for multiline input
-    let cursor_x = popup_area.x + 3 + app.text_input.cursor_display_offset() as u16; // 3 = "> " + border
-    let cursor_y = popup_area.y + 4 + (input_line_count.saturating_sub(1)) as u16;
-    f.set_cursor_position((cursor_x, cursor_y));
+    // Comment text goes right of the "> " prompt
+    let input_area = Rect::new(
+        popup_area.x + 3,
+        popup_area.y + 4,
+        input_width,
+        popup_area.height.saturating_sub(6).max(1),
+    );
+    app.text_input.render_wrapped(
+        f,
+        input_area,
+        Style::default().fg(theme.neon_yellow).bg(theme.bg_level0),
+    );
 }
 
 /// Draw select provider overlay for new session
@@ -455,15 +1632,16 @@ pub fn draw_select_provider_overlay(
     f: &mut Frame,
     area: Rect,
     app: &App,
-    providers: &[String],
+    providers: &[ProviderInfo],
     selected_index: usize,
+    model_index: usize,
     loading: bool,
 ) {
     let theme = &app.theme;
 
     // Calculate popup size based on content
     let popup_height = (providers.len() + 4).min(15) as u16; // +4 for borders, title, instructions
-    let popup_width = 50.min(area.width.saturating_sub(4));
+    let popup_width = 70.min(area.width.saturating_sub(4));
     let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
     let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
     let popup_area = Rect::new(x, y, popup_width, popup_height);
@@ -483,20 +1661,27 @@ pub fn draw_select_provider_overlay(
         .borders(Borders::ALL)
         .border_style(Style::default().fg(theme.neon_cyan).bg(theme.bg_level0))
         .style(Style::default().bg(theme.bg_level0))
-        .title(" Select Provider (j/k=select, Enter=create, Esc=cancel) ");
+        .title(
+            " Select Provider (j/k=provider, h/l=model, Enter=create, Shift+Enter=system prompt) ",
+        );
     f.render_widget(block, popup_area);
 
     // Instructions
     let instructions_text = if loading {
         "Loading providers...".to_string()
     } else {
-        "Select AI provider for new session:".to_string()
+        "Select AI provider and model for new session:".to_string()
     };
     let instructions = Paragraph::new(instructions_text)
         .style(Style::default().fg(theme.text_tertiary).bg(theme.bg_level0));
     f.render_widget(instructions, chunks[0]);
 
-    // Provider list
+    // Provider list, with the selected provider's model between arrows
+    let name_width = providers
+        .iter()
+        .map(|p| p.display_name.width())
+        .max()
+        .unwrap_or(0);
     let items: Vec<ListItem> = providers
         .iter()
         .enumerate()
@@ -511,13 +1696,229 @@ pub fn draw_select_provider_overlay(
                 Style::default().fg(theme.text_primary).bg(theme.bg_level0)
             };
             let prefix = if is_selected { "> " } else { "  " };
-            ListItem::new(format!("{}{}", prefix, provider)).style(style)
+            let name = format!(
+                "{}{}{}",
+                prefix,
+                provider.display_name,
+                " ".repeat(name_width - provider.display_name.width() + 2)
+            );
+            let model = if is_selected {
+                let model = provider.models.get(model_index).map_or("", String::as_str);
+                Span::styled(format!("\u{2039} {} \u{203a}", model), style)
+            } else {
+                Span::styled(
+                    provider.default_model.clone(),
+                    Style::default().fg(theme.text_tertiary).bg(theme.bg_level0),
+                )
+            };
+            ListItem::new(Line::from(vec![Span::styled(name, style), model]))
+        })
+        .collect();
+    let list = List::new(items).style(Style::default().bg(theme.bg_level0));
+    f.render_widget(list, chunks[1]);
+}
+
+/// Draw the picker of past reviews
+pub fn draw_select_review_overlay(
+    f: &mut Frame,
+    area: Rect,
+    app: &App,
+    reviews: &[ReviewInfo],
+    selected_index: usize,
+) {
+    let theme = &app.theme;
+
+    let popup_height = (reviews.len() + 4).min(15) as u16;
+    let popup_width = 70.min(area.width.saturating_sub(4));
+    let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(x, y, popup_width, popup_height);
+
+    let inner = popup_area.inner(ratatui::layout::Margin::new(1, 1));
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Instructions
+            Constraint::Min(1),    // Review list
+        ])
+        .split(inner);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.neon_cyan).bg(theme.bg_level0))
+        .style(Style::default().bg(theme.bg_level0))
+        .title(" Reviews (j/k=select, Enter=reopen, Esc=cancel) ");
+    f.render_widget(block, popup_area);
+
+    let instructions = Paragraph::new("Reopen a review to put its comments back:")
+        .style(Style::default().fg(theme.text_tertiary).bg(theme.bg_level0));
+    f.render_widget(instructions, chunks[0]);
+
+    // Keep the selection visible when there are more reviews than rows
+    let visible = chunks[1].height as usize;
+    let offset = (selected_index + 1).saturating_sub(visible);
+    let items: Vec<ListItem> = reviews
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(i, review)| {
+            let is_selected = i == selected_index;
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.neon_yellow)
+                    .bg(theme.bg_level0)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text_primary).bg(theme.bg_level0)
+            };
+            let prefix = if is_selected { "> " } else { "  " };
+            let title = review
+                .title
+                .clone()
+                .unwrap_or_else(|| format!("Review of {}", review.base_ref));
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{}{}", prefix, title), style),
+                Span::styled(
+                    format!(
+                        "  {} comment(s), {} ago",
+                        review.comments.len(),
+                        format_age(review.created_at)
+                    ),
+                    Style::default().fg(theme.text_tertiary).bg(theme.bg_level0),
+                ),
+            ]))
         })
         .collect();
     let list = List::new(items).style(Style::default().bg(theme.bg_level0));
     f.render_widget(list, chunks[1]);
 }
 
+/// Draw the recent sessions picker
+pub fn draw_recent_sessions_overlay(f: &mut Frame, area: Rect, app: &App, selected_index: usize) {
+    let theme = &app.theme;
+    let targets = app.recent_switch_targets();
+
+    let popup_height = (targets.len() + 2) as u16;
+    let popup_width = 70.min(area.width.saturating_sub(4));
+    let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(x, y, popup_width, popup_height.min(area.height));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.neon_cyan).bg(theme.bg_level0))
+        .style(Style::default().bg(theme.bg_level0))
+        .title(" Recent Sessions (1-9/Enter=switch, Esc=cancel) ");
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let items: Vec<ListItem> = targets
+        .iter()
+        .enumerate()
+        .map(|(i, recent)| {
+            let is_selected = i == selected_index;
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.neon_yellow)
+                    .bg(theme.bg_level0)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text_primary).bg(theme.bg_level0)
+            };
+            let prefix = if is_selected { ">" } else { " " };
+            let repo = app.repo_states.get(&recent.repo_id);
+            let name = repo
+                .and_then(|r| {
+                    r.sessions
+                        .iter()
+                        .chain(r.sessions_by_worktree.values().flatten())
+                        .find(|s| s.id == recent.session_id)
+                })
+                .map(|s| s.name.clone())
+                .unwrap_or_else(|| recent.session_id.chars().take(8).collect());
+            let repo_name = repo.map(|r| r.info.name.as_str()).unwrap_or("?");
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{}{} {}", prefix, i + 1, name), style),
+                Span::styled(
+                    format!("  {}, {} ago", repo_name, format_age(recent.shown_at)),
+                    Style::default().fg(theme.text_tertiary).bg(theme.bg_level0),
+                ),
+            ]))
+        })
+        .collect();
+    let list = List::new(items).style(Style::default().bg(theme.bg_level0));
+    f.render_widget(list, inner);
+}
+
+/// Screen area of the context menu opened at `column`/`row`, kept on `screen`
+pub fn context_menu_area(screen: Rect, app: &App, items: &[Action], column: u16, row: u16) -> Rect {
+    let label_width = items
+        .iter()
+        .map(|a| context_menu_label(app, a).width())
+        .max()
+        .unwrap_or(0);
+    let width = (label_width as u16 + 4).min(screen.width);
+    let height = (items.len() as u16 + 2).min(screen.height);
+    let x = column.min(screen.right().saturating_sub(width));
+    let y = row.min(screen.bottom().saturating_sub(height));
+    Rect::new(x, y, width, height)
+}
+
+/// Menu entry: action name and its sidebar key, if bound
+fn context_menu_label(app: &App, action: &Action) -> String {
+    let key = app
+        .keybinds
+        .key_display(action.clone(), BindingContext::Sidebar);
+    if key.is_empty() {
+        action.display_name().to_string()
+    } else {
+        format!("{}  {}", action.display_name(), key)
+    }
+}
+
+/// Draw the context menu over the current view
+pub fn draw_context_menu_overlay(
+    f: &mut Frame,
+    app: &App,
+    items: &[Action],
+    selected_index: usize,
+    column: u16,
+    row: u16,
+) {
+    let theme = &app.theme;
+    let menu_area = context_menu_area(f.area(), app, items, column, row);
+
+    let list_items: Vec<ListItem> = items
+        .iter()
+        .enumerate()
+        .map(|(i, action)| {
+            let style = if i == selected_index {
+                Style::default()
+                    .fg(theme.neon_yellow)
+                    .bg(theme.bg_level0)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text_primary).bg(theme.bg_level0)
+            };
+            ListItem::new(Span::styled(
+                format!(" {} ", context_menu_label(app, action)),
+                style,
+            ))
+        })
+        .collect();
+
+    let list = List::new(list_items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.neon_cyan).bg(theme.bg_level0))
+            .style(Style::default().bg(theme.bg_level0)),
+    );
+    f.render_widget(Clear, menu_area);
+    f.render_widget(list, menu_area);
+}
+
 /// Draw edit line comment overlay
 pub fn draw_edit_line_comment_overlay(
     f: &mut Frame,
@@ -528,25 +1929,20 @@ pub fn draw_edit_line_comment_overlay(
 ) {
     let theme = &app.theme;
 
-    // Calculate input lines for dynamic height
-    let input_lines: Vec<&str> = app.text_input.content().lines().collect();
-    let input_line_count = input_lines.len().max(1);
+    // Grow with the wrapped comment; 4 = borders + "> "
+    let popup_width = 70.min(area.width.saturating_sub(4));
+    let input_width = popup_width.saturating_sub(4);
+    let input_rows = app.text_input.wrap(input_width as usize).lines.len() as u16;
 
     // Center the input box with dynamic height
-    let popup_width = 70.min(area.width.saturating_sub(4));
-    let popup_height = (6 + input_line_count as u16).min(area.height.saturating_sub(4));
+    let popup_height = (6 + input_rows).min(area.height.saturating_sub(4));
     let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
     let y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
     let popup_area = Rect::new(x, y, popup_width, popup_height);
 
-    // Truncate file path if too long
-    // Only truncate if max_path_len > 3 to ensure room for "..." plus at least one character
+    // Truncate file path if too long, keeping its end
     let max_path_len = (popup_width as usize).saturating_sub(20);
-    let display_path = if file_path.len() > max_path_len && max_path_len > 3 {
-        format!("...{}", &file_path[file_path.len() - max_path_len + 3..])
-    } else {
-        file_path.to_string()
-    };
+    let display_path = truncate_start_to_width(file_path, max_path_len);
 
     // Build text with multiline input support
     let mut text = vec![
@@ -557,28 +1953,17 @@ pub fn draw_edit_line_comment_overlay(
         Line::from(vec![
             Span::styled("Line: ", Style::default().fg(theme.text_tertiary)),
             Span::styled(
-                format!("{}", line_number),
+                comment_location(line_number),
                 Style::default().fg(theme.neon_cyan),
             ),
         ]),
         Line::from(""),
     ];
 
-    // Add input lines
-    for (i, line) in input_lines.iter().enumerate() {
-        let prefix = if i == 0 { "> " } else { "  " };
-        text.push(Line::from(vec![
-            Span::styled(prefix, Style::default().fg(theme.neon_yellow)),
-            Span::styled(*line, Style::default().fg(theme.neon_yellow)),
-        ]));
-    }
-    // Handle empty input
-    if input_lines.is_empty() {
-        text.push(Line::from(vec![Span::styled(
-            "> ",
-            Style::default().fg(theme.neon_yellow),
-        )]));
-    }
+    text.push(Line::from(Span::styled(
+        "> ",
+        Style::default().fg(theme.neon_yellow),
+    )));
 
     let input = Paragraph::new(text)
         .style(Style::default().bg(theme.bg_level0))
@@ -588,12 +1973,20 @@ pub fn draw_edit_line_comment_overlay(
                 .border_type(BorderType::Rounded)
                 .border_style(Style::default().fg(theme.success).bg(theme.bg_level0))
                 .style(Style::default().bg(theme.bg_level0))
-                .title(" Edit Comment (Enter=save, Shift+Enter=newline, Esc=cancel) "),
+                .title(" Edit Comment (Alt+Enter=save, Enter=newline, Esc=cancel) "),
         );
     f.render_widget(input, popup_area);
 
-    // Calculate cursor position for multiline input
-    let cursor_x = popup_area.x + 3 + app.text_input.cursor_display_offset() as u16; // 3 = "> " + border
-    let cursor_y = popup_area.y + 4 + (input_line_count.saturating_sub(1)) as u16;
-    f.set_cursor_position((cursor_x, cursor_y));
+    // Comment text goes right of the "> " prompt
+    let input_area = Rect::new(
+        popup_area.x + 3,
+        popup_area.y + 4,
+        input_width,
+        popup_area.height.saturating_sub(6).max(1),
+    );
+    app.text_input.render_wrapped(
+        f,
+        input_area,
+        Style::default().fg(theme.neon_yellow).bg(theme.bg_level0),
+    );
 }
@@ -4,9 +4,12 @@
 
 use crate::tui::app::App;
 use crate::tui::input::utils::{
-    handle_confirmation, handle_confirmation_with_enter, handle_text_input_with_actions,
+    handle_confirmation, handle_confirmation_with_enter, handle_text_input,
+    handle_text_input_with_actions, TextInputResult,
 };
 use crate::tui::state::{AsyncAction, InputMode};
+use crate::tui::views::sidebar;
+use amux_config::{Action, Confirmation, QuitBehavior};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 /// Handle input when in confirm delete mode
@@ -37,37 +40,19 @@ pub fn handle_confirm_delete_sync(app: &mut App, key: KeyEvent) -> Option<AsyncA
     }
 
     // Session deletion: support navigation and selection
+    if select_cleanup_action(app, &key) {
+        return None;
+    }
     match key.code {
-        // j/k or up/down navigate options
-        KeyCode::Char('j') | KeyCode::Down => {
-            app.session_delete_action = match app.session_delete_action {
-                ExitCleanupAction::Destroy => ExitCleanupAction::Stop,
-                ExitCleanupAction::Stop => ExitCleanupAction::Stop,
-            };
-            None
-        }
-        KeyCode::Char('k') | KeyCode::Up => {
-            app.session_delete_action = match app.session_delete_action {
-                ExitCleanupAction::Destroy => ExitCleanupAction::Destroy,
-                ExitCleanupAction::Stop => ExitCleanupAction::Destroy,
-            };
-            None
-        }
-        // Shortcuts
-        KeyCode::Char('d') => {
-            app.session_delete_action = ExitCleanupAction::Destroy;
-            None
-        }
-        KeyCode::Char('s') => {
-            app.session_delete_action = ExitCleanupAction::Stop;
-            None
-        }
-        // Enter confirms
-        KeyCode::Enter => {
+        // Enter confirms; `a` confirms and stops asking
+        KeyCode::Enter | KeyCode::Char('a') => {
             let target = match &app.input_mode {
                 InputMode::ConfirmDelete(t) => t.clone(),
                 _ => return None,
             };
+            if key.code == KeyCode::Char('a') {
+                app.stop_confirming(Confirmation::DeleteSession);
+            }
             Some(AsyncAction::ConfirmDelete {
                 target,
                 action: app.session_delete_action,
@@ -82,8 +67,74 @@ pub fn handle_confirm_delete_sync(app: &mut App, key: KeyEvent) -> Option<AsyncA
     }
 }
 
+/// Move the Destroy/Stop choice with j/k, arrows, Tab or the d/s shortcuts
+///
+/// Returns true if the key was consumed.
+fn select_cleanup_action(app: &mut App, key: &KeyEvent) -> bool {
+    use crate::tui::state::ExitCleanupAction;
+
+    app.session_delete_action = match key.code {
+        KeyCode::Char('j') | KeyCode::Down | KeyCode::Char('s') => ExitCleanupAction::Stop,
+        KeyCode::Char('k') | KeyCode::Up | KeyCode::Char('d') => ExitCleanupAction::Destroy,
+        KeyCode::Tab => app.session_delete_action.toggled(),
+        _ => return false,
+    };
+    true
+}
+
+/// Move the Destroy/Stop/Detach choice with j/k, arrows, Tab or the d/s/t shortcuts
+fn remove_repo_sessions_choice(current: QuitBehavior, key: &KeyEvent) -> Option<QuitBehavior> {
+    const CHOICES: [QuitBehavior; 3] = [
+        QuitBehavior::Destroy,
+        QuitBehavior::Stop,
+        QuitBehavior::Detach,
+    ];
+    let idx = CHOICES.iter().position(|&c| c == current).unwrap_or(0);
+    match key.code {
+        KeyCode::Char('d') => Some(QuitBehavior::Destroy),
+        KeyCode::Char('s') => Some(QuitBehavior::Stop),
+        KeyCode::Char('t') => Some(QuitBehavior::Detach),
+        KeyCode::Char('j') | KeyCode::Down | KeyCode::Tab => Some(CHOICES[(idx + 1) % 3]),
+        KeyCode::Char('k') | KeyCode::Up => Some(CHOICES[(idx + 2) % 3]),
+        _ => None,
+    }
+}
+
+/// Handle input when confirming removal of a repo
+pub fn handle_confirm_remove_repo_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    let (repo_id, session_count, sessions) = match &app.input_mode {
+        InputMode::ConfirmRemoveRepo {
+            repo_id,
+            session_count,
+            sessions,
+            ..
+        } => (repo_id.clone(), *session_count, *sessions),
+        _ => return None,
+    };
+
+    if session_count > 0 {
+        if let Some(choice) = remove_repo_sessions_choice(sessions, &key) {
+            if let InputMode::ConfirmRemoveRepo { sessions, .. } = &mut app.input_mode {
+                *sessions = choice;
+            }
+            return None;
+        }
+    }
+    handle_confirmation_with_enter(
+        app,
+        &key,
+        |a| a.cancel_input(),
+        AsyncAction::RemoveRepo { repo_id, sessions },
+    )
+    .and_then(|action| app.defer_with_status("Removing repo…", action))
+}
+
 /// Handle input when in confirm delete branch mode (after worktree deletion)
 pub fn handle_confirm_delete_branch_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    if key.code == KeyCode::Char('a') {
+        app.stop_confirming(Confirmation::DeleteBranch);
+        return Some(AsyncAction::ConfirmDeleteBranch);
+    }
     handle_confirmation(
         app,
         &key,
@@ -92,6 +143,17 @@ pub fn handle_confirm_delete_branch_sync(app: &mut App, key: KeyEvent) -> Option
     )
 }
 
+/// Handle input when confirming a stash before pulling a dirty worktree
+pub fn handle_confirm_pull_stash_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    handle_confirmation_with_enter(
+        app,
+        &key,
+        |a| a.cancel_input(),
+        AsyncAction::ConfirmPullStash,
+    )
+    .and_then(|action| app.defer_with_status("Stashing and pulling…", action))
+}
+
 /// Handle input when in confirm delete worktree sessions mode
 pub fn handle_confirm_delete_worktree_sessions_sync(
     app: &mut App,
@@ -107,6 +169,18 @@ pub fn handle_confirm_delete_worktree_sessions_sync(
 
 /// Handle input when in add worktree mode
 pub fn handle_add_worktree_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    if app.completion.handle_key(&key, &mut app.text_input) {
+        return None;
+    }
+    let typed = app.text_input.content();
+    let action = handle_add_worktree_key(app, key);
+    if app.text_input.content() != typed {
+        app.suggest_branch_names();
+    }
+    action
+}
+
+fn handle_add_worktree_key(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
     // Shift+Enter: insert newline (when typing new branch name)
     if key.code == KeyCode::Enter && key.modifiers.contains(KeyModifiers::SHIFT) {
         app.text_input.insert('\n');
@@ -119,8 +193,14 @@ pub fn handle_add_worktree_mode_sync(app: &mut App, key: KeyEvent) -> Option<Asy
             app.cancel_input();
             None
         }
-        // Confirm selection
+        // Confirm selection, unless the typed name is invalid (shown in the overlay)
+        KeyCode::Enter if app.typed_branch_error().is_some() => None,
         KeyCode::Enter => Some(AsyncAction::SubmitAddWorktree),
+        // Complete the new branch name from existing branches
+        KeyCode::Tab => {
+            app.complete_branch_name();
+            None
+        }
         // Navigate up in branch list (clear input buffer if typing)
         KeyCode::Up | KeyCode::Char('k') if app.text_input.is_empty() => {
             if app.add_worktree_idx() > 0 {
@@ -159,6 +239,185 @@ pub fn handle_rename_session_mode_sync(app: &mut App, key: KeyEvent) -> Option<A
     )
 }
 
+/// Handle input when in rename branch mode
+pub fn handle_rename_branch_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    handle_text_input_with_actions(
+        app,
+        &key,
+        |a| a.cancel_input(),
+        |_| Some(AsyncAction::SubmitRenameBranch),
+    )
+}
+
+/// Handle input when entering a stash message
+pub fn handle_stash_message_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    handle_text_input_with_actions(
+        app,
+        &key,
+        |a| a.cancel_input(),
+        |_| Some(AsyncAction::StashSave),
+    )
+}
+
+/// Handle input when confirming a checkpoint restore
+///
+/// Only Enter on the typed word "restore" confirms; the restore discards work.
+pub fn handle_confirm_restore_checkpoint_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    handle_text_input_with_actions(
+        app,
+        &key,
+        |a| a.cancel_input(),
+        |a| {
+            if a.text_input.trim() == "restore" {
+                Some(AsyncAction::RestoreCheckpoint)
+            } else {
+                a.error_message = Some("Type \"restore\" to confirm".to_string());
+                None
+            }
+        },
+    )
+}
+
+/// Handle input when entering the quick commit-and-push message
+pub fn handle_quick_ship_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    handle_text_input_with_actions(
+        app,
+        &key,
+        |a| a.cancel_input(),
+        |_| Some(AsyncAction::SubmitQuickShip),
+    )
+}
+
+/// Handle input when entering a repository path
+pub fn handle_add_repo_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    handle_text_input_with_actions(
+        app,
+        &key,
+        |a| a.cancel_input(),
+        |a| {
+            let path = a.text_input.trim();
+            a.input_mode = InputMode::Normal;
+            a.text_input.clear();
+            a.restore_focus();
+            if path.is_empty() {
+                None
+            } else {
+                let message = format!("Adding {}…", path);
+                a.defer_with_status(&message, AsyncAction::AddRepo { path })
+            }
+        },
+    )
+}
+
+/// Handle input when searching sessions
+///
+/// Every edit re-runs the search; Up/Down move through the results.
+pub fn handle_search_sessions_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Up => return move_search(app, false),
+        KeyCode::Down => return move_search(app, true),
+        KeyCode::Char('p') if ctrl => return move_search(app, false),
+        KeyCode::Char('n') if ctrl => return move_search(app, true),
+        _ => {}
+    }
+
+    let before = app.text_input.content();
+    match handle_text_input(&key, &mut app.text_input) {
+        TextInputResult::Cancel => {
+            app.cancel_input();
+            None
+        }
+        TextInputResult::Submit => Some(AsyncAction::SubmitSearchSessions),
+        _ if app.text_input.content() != before => Some(AsyncAction::SearchSessions {
+            query: app.text_input.content(),
+        }),
+        _ => None,
+    }
+}
+
+fn move_search(app: &mut App, down: bool) -> Option<AsyncAction> {
+    app.move_search_selection(down);
+    None
+}
+
+/// Handle input when searching session history
+///
+/// Every edit restarts the search; Ctrl-r toggles regex matching.
+pub fn handle_search_history_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Up => return move_history_search(app, false),
+        KeyCode::Down => return move_history_search(app, true),
+        KeyCode::Char('p') if ctrl => return move_history_search(app, false),
+        KeyCode::Char('n') if ctrl => return move_history_search(app, true),
+        KeyCode::Char('r') if ctrl => {
+            if let InputMode::SearchHistory { regex, .. } = &mut app.input_mode {
+                *regex = !*regex;
+            }
+            return Some(AsyncAction::SearchHistory {
+                query: app.text_input.content(),
+            });
+        }
+        _ => {}
+    }
+
+    let before = app.text_input.content();
+    match handle_text_input(&key, &mut app.text_input) {
+        TextInputResult::Cancel => {
+            app.history_search = None;
+            app.cancel_input();
+            None
+        }
+        TextInputResult::Submit => Some(AsyncAction::SubmitSearchHistory),
+        _ if app.text_input.content() != before => Some(AsyncAction::SearchHistory {
+            query: app.text_input.content(),
+        }),
+        _ => None,
+    }
+}
+
+fn move_history_search(app: &mut App, down: bool) -> Option<AsyncAction> {
+    app.move_history_selection(down);
+    None
+}
+
+/// Handle input when picking the diff base
+///
+/// Typing filters the refs; Enter with no match uses the text itself (a SHA).
+pub fn handle_select_diff_base_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Up => return move_diff_base(app, false),
+        KeyCode::Down => return move_diff_base(app, true),
+        KeyCode::Char('p') if ctrl => return move_diff_base(app, false),
+        KeyCode::Char('n') if ctrl => return move_diff_base(app, true),
+        _ => {}
+    }
+
+    let before = app.text_input.content();
+    match handle_text_input(&key, &mut app.text_input) {
+        TextInputResult::Cancel => {
+            app.cancel_input();
+            None
+        }
+        TextInputResult::Submit => Some(AsyncAction::SubmitDiffBase),
+        _ => {
+            if app.text_input.content() != before {
+                if let InputMode::SelectDiffBase { selected_index, .. } = &mut app.input_mode {
+                    *selected_index = 0;
+                }
+            }
+            None
+        }
+    }
+}
+
+fn move_diff_base(app: &mut App, down: bool) -> Option<AsyncAction> {
+    app.move_diff_base_selection(down);
+    None
+}
+
 /// Handle input when adding a line comment
 pub fn handle_add_line_comment_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
     handle_text_input_with_actions(
@@ -185,12 +444,22 @@ pub fn handle_input_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncActio
         app,
         &key,
         |a| a.cancel_input(),
-        |_| Some(AsyncAction::SubmitInput),
+        |a| {
+            a.typed_branch_error()
+                .is_none()
+                .then_some(AsyncAction::SubmitInput)
+        },
     )
 }
 
 /// Handle input when in create session input mode (name input in status bar)
+///
+/// Shift+Enter asks for a system prompt before creating the session.
 pub fn handle_create_session_input_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    if key.code == KeyCode::Enter && key.modifiers.contains(KeyModifiers::SHIFT) {
+        app.start_session_system_prompt();
+        return None;
+    }
     handle_text_input_with_actions(
         app,
         &key,
@@ -199,6 +468,112 @@ pub fn handle_create_session_input_mode_sync(app: &mut App, key: KeyEvent) -> Op
     )
 }
 
+/// Handle input when entering the system prompt of a new session
+pub fn handle_session_system_prompt_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    handle_text_input_with_actions(
+        app,
+        &key,
+        |a| a.cancel_input(),
+        |_| Some(AsyncAction::SubmitSessionSystemPrompt),
+    )
+}
+
+/// Handle input when entering a command line to run as a session
+pub fn handle_run_command_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    handle_text_input_with_actions(
+        app,
+        &key,
+        |a| a.cancel_input(),
+        |_| Some(AsyncAction::SubmitRunCommand),
+    )
+}
+
+/// Handle input when entering a line to broadcast to the worktree's sessions
+pub fn handle_broadcast_input_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    handle_text_input_with_actions(
+        app,
+        &key,
+        |a| a.cancel_input(),
+        |a| {
+            a.submit_broadcast_input();
+            None
+        },
+    )
+}
+
+/// Handle input when typing a runtime command after `:`
+pub fn handle_command_line_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    handle_text_input_with_actions(
+        app,
+        &key,
+        |a| a.cancel_input(),
+        |_| Some(AsyncAction::SubmitCommandLine),
+    )
+}
+
+/// Handle input in the diff search bar, searching again on every edit
+pub fn handle_search_diff_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    let before = app.text_input.content();
+    match handle_text_input(&key, &mut app.text_input) {
+        TextInputResult::Cancel => {
+            app.cancel_diff_search();
+            None
+        }
+        TextInputResult::Submit => {
+            app.submit_diff_search();
+            None
+        }
+        _ if app.text_input.content() != before => Some(AsyncAction::UpdateDiffSearch),
+        _ => None,
+    }
+}
+
+/// Handle input when confirming a broadcast to the worktree's sessions
+pub fn handle_confirm_broadcast_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    handle_confirmation_with_enter(
+        app,
+        &key,
+        |a| a.cancel_input(),
+        AsyncAction::ConfirmBroadcast,
+    )
+}
+
+/// Handle input when confirming a quit with sessions running
+pub fn handle_confirm_quit_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    match key.code {
+        KeyCode::Char('q') | KeyCode::Char('Q') => {
+            app.quit_keeping_sessions();
+            None
+        }
+        KeyCode::Char('s') | KeyCode::Char('S') => {
+            app.defer_with_status("Stopping sessions…", AsyncAction::StopAllAndQuit)
+        }
+        KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => {
+            app.cancel_input();
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Handle input when entering the directory of a new shell (Tab completes)
+pub fn handle_shell_directory_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    if app.completion.handle_key(&key, &mut app.text_input) {
+        return None;
+    }
+    if key.code == KeyCode::Tab {
+        return Some(AsyncAction::CompleteShellDirectory);
+    }
+    let action = handle_text_input_with_actions(
+        app,
+        &key,
+        |a| a.cancel_input(),
+        |_| Some(AsyncAction::SubmitShellDirectory),
+    );
+    app.completion.refilter(&app.text_input);
+    action
+}
+
 /// Handle input when in select provider mode
 pub fn handle_select_provider_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
     match key.code {
@@ -207,41 +582,172 @@ pub fn handle_select_provider_mode_sync(app: &mut App, key: KeyEvent) -> Option<
             app.cancel_input();
             None
         }
-        // Confirm selection (unless loading)
+        // Confirm selection (unless loading); Shift+Enter adds a system prompt first
         KeyCode::Enter => {
             if let InputMode::SelectProvider { loading, .. } = &app.input_mode {
                 if *loading {
                     return None; // Ignore Enter while loading
                 }
             }
+            if key.modifiers.contains(KeyModifiers::SHIFT) {
+                app.start_session_system_prompt();
+                return None;
+            }
             Some(AsyncAction::SubmitProviderSelection)
         }
-        // Navigate up in provider list (unless loading)
+        // Navigate the provider list (unless loading)
         KeyCode::Up | KeyCode::Char('k') => {
-            if let InputMode::SelectProvider {
-                selected_index,
-                loading,
-                ..
-            } = &mut app.input_mode
-            {
-                if !*loading && *selected_index > 0 {
-                    *selected_index -= 1;
-                }
+            app.move_provider_selection(false);
+            None
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.move_provider_selection(true);
+            None
+        }
+        // Cycle the selected provider's models
+        KeyCode::Left | KeyCode::Char('h') => {
+            app.cycle_provider_model(false);
+            None
+        }
+        KeyCode::Right | KeyCode::Char('l') => {
+            app.cycle_provider_model(true);
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Handle input in the sidebar context menu
+pub fn handle_context_menu_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    let InputMode::ContextMenu { selected_index, .. } = app.input_mode else {
+        return None;
+    };
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.cancel_input();
+            None
+        }
+        KeyCode::Enter => run_context_menu_item(app, selected_index),
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.move_context_menu_selection(false);
+            None
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.move_context_menu_selection(true);
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Handle input in the session info overlay: any key closes it
+pub fn handle_session_info_mode_sync(app: &mut App, _key: KeyEvent) -> Option<AsyncAction> {
+    app.cancel_input();
+    None
+}
+
+/// Lines moved by a page of the session file viewer
+const SESSION_FILE_PAGE: usize = 20;
+
+/// Handle input while viewing a session file
+pub fn handle_session_file_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    let InputMode::SessionFile { lines, scroll, .. } = &mut app.input_mode else {
+        return None;
+    };
+    let last = lines.len().saturating_sub(1);
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.cancel_input();
+            return None;
+        }
+        KeyCode::Char('j') | KeyCode::Down => *scroll += 1,
+        KeyCode::Char('k') | KeyCode::Up => *scroll = scroll.saturating_sub(1),
+        KeyCode::PageDown => *scroll += SESSION_FILE_PAGE,
+        KeyCode::Char('d') if ctrl => *scroll += SESSION_FILE_PAGE,
+        KeyCode::PageUp => *scroll = scroll.saturating_sub(SESSION_FILE_PAGE),
+        KeyCode::Char('u') if ctrl => *scroll = scroll.saturating_sub(SESSION_FILE_PAGE),
+        KeyCode::Char('g') | KeyCode::Home => *scroll = 0,
+        KeyCode::Char('G') | KeyCode::End => *scroll = last,
+        _ => {}
+    }
+    *scroll = (*scroll).min(last);
+    None
+}
+
+/// Close the context menu and run an item as if its key was pressed in the sidebar
+pub fn run_context_menu_item(app: &mut App, index: usize) -> Option<AsyncAction> {
+    match app.take_context_menu_item(index)? {
+        Action::CopyToClipboard(target) => {
+            app.copy_to_clipboard(target);
+            None
+        }
+        action => sidebar::input::execute_sidebar_action(app, action),
+    }
+}
+
+/// Handle input when picking a past review
+pub fn handle_select_review_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    let InputMode::SelectReview {
+        reviews,
+        selected_index,
+    } = &mut app.input_mode
+    else {
+        return None;
+    };
+
+    match key.code {
+        KeyCode::Esc => {
+            app.cancel_input();
+            None
+        }
+        KeyCode::Enter => Some(AsyncAction::OpenReview),
+        KeyCode::Up | KeyCode::Char('k') => {
+            *selected_index = selected_index.saturating_sub(1);
+            None
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            if *selected_index + 1 < reviews.len() {
+                *selected_index += 1;
             }
             None
         }
-        // Navigate down in provider list (unless loading)
+        _ => None,
+    }
+}
+
+/// Handle input when picking a recent session
+pub fn handle_recent_sessions_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    let count = app.recent_switch_targets().len();
+    let InputMode::RecentSessions { selected_index } = &mut app.input_mode else {
+        return None;
+    };
+
+    match key.code {
+        KeyCode::Esc => {
+            app.cancel_input();
+            None
+        }
+        KeyCode::Enter => {
+            app.open_recent_session();
+            None
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            *selected_index = selected_index.saturating_sub(1);
+            None
+        }
         KeyCode::Down | KeyCode::Char('j') => {
-            if let InputMode::SelectProvider {
-                selected_index,
-                providers,
-                loading,
-                ..
-            } = &mut app.input_mode
-            {
-                if !*loading && *selected_index + 1 < providers.len() {
-                    *selected_index += 1;
-                }
+            if *selected_index + 1 < count {
+                *selected_index += 1;
+            }
+            None
+        }
+        KeyCode::Char(c @ '1'..='9') => {
+            let index = c as usize - '1' as usize;
+            if index < count {
+                *selected_index = index;
+                app.open_recent_session();
             }
             None
         }
@@ -0,0 +1,159 @@
+//! TUI state kept between runs in ~/.amux
+//!
+//! Recent sessions are stored as plain text, one
+//! `<unix seconds> <repo id> <session id>` line per session, most recent
+//! first. The provider picked for each repo is kept the same way, one
+//! `<repo id> <provider> <model>` line per repo.
+
+use super::state::{ProviderChoice, RecentSession, RECENT_SESSIONS_CAPACITY};
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+
+fn recent_sessions_file() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".amux").join("recent_sessions"))
+}
+
+fn provider_choices_file() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".amux").join("provider_choices"))
+}
+
+/// Write the recent sessions list
+pub fn save_recent_sessions(queue: &VecDeque<RecentSession>) -> std::io::Result<()> {
+    let Some(path) = recent_sessions_file() else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, format_recent_sessions(queue))
+}
+
+/// Read the recent sessions list (empty if missing or unreadable)
+pub fn load_recent_sessions() -> VecDeque<RecentSession> {
+    recent_sessions_file()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|content| parse_recent_sessions(&content))
+        .unwrap_or_default()
+}
+
+fn format_recent_sessions(queue: &VecDeque<RecentSession>) -> String {
+    queue
+        .iter()
+        .map(|r| format!("{} {} {}\n", r.shown_at, r.repo_id, r.session_id))
+        .collect()
+}
+
+/// Parse the stored list, skipping malformed lines
+fn parse_recent_sessions(content: &str) -> VecDeque<RecentSession> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let shown_at = fields.next()?.parse().ok()?;
+            let repo_id = fields.next()?.to_string();
+            let session_id = fields.next()?.to_string();
+            Some(RecentSession {
+                repo_id,
+                session_id,
+                shown_at,
+            })
+        })
+        .take(RECENT_SESSIONS_CAPACITY)
+        .collect()
+}
+
+/// Write the provider picked for each repo
+pub fn save_provider_choices(choices: &HashMap<String, ProviderChoice>) -> std::io::Result<()> {
+    let Some(path) = provider_choices_file() else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, format_provider_choices(choices))
+}
+
+/// Read the provider picked for each repo (empty if missing or unreadable)
+pub fn load_provider_choices() -> HashMap<String, ProviderChoice> {
+    provider_choices_file()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|content| parse_provider_choices(&content))
+        .unwrap_or_default()
+}
+
+fn format_provider_choices(choices: &HashMap<String, ProviderChoice>) -> String {
+    let mut repo_ids: Vec<_> = choices.keys().collect();
+    repo_ids.sort();
+    repo_ids
+        .into_iter()
+        .map(|repo_id| {
+            let choice = &choices[repo_id];
+            format!("{} {} {}\n", repo_id, choice.provider, choice.model)
+        })
+        .collect()
+}
+
+/// Parse the stored choices, skipping malformed lines
+fn parse_provider_choices(content: &str) -> HashMap<String, ProviderChoice> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let repo_id = fields.next()?.to_string();
+            let provider = fields.next()?.to_string();
+            let model = fields.next()?.to_string();
+            Some((repo_id, ProviderChoice { provider, model }))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recent(session_id: &str, shown_at: i64) -> RecentSession {
+        RecentSession {
+            repo_id: "repo".to_string(),
+            session_id: session_id.to_string(),
+            shown_at,
+        }
+    }
+
+    #[test]
+    fn test_recent_sessions_round_trip() {
+        let queue: VecDeque<_> = [recent("b", 200), recent("a", 100)].into();
+        let content = format_recent_sessions(&queue);
+        assert_eq!(content, "200 repo b\n100 repo a\n");
+        assert_eq!(parse_recent_sessions(&content), queue);
+    }
+
+    #[test]
+    fn test_parse_skips_malformed_lines() {
+        let parsed = parse_recent_sessions("x repo a\n100 repo\n\n100 repo b\n");
+        assert_eq!(parsed, VecDeque::from([recent("b", 100)]));
+
+        let many: String = (0..20).map(|i| format!("{} repo s{}\n", i, i)).collect();
+        assert_eq!(parse_recent_sessions(&many).len(), RECENT_SESSIONS_CAPACITY);
+    }
+
+    #[test]
+    fn test_provider_choices_round_trip() {
+        let choice = |provider: &str, model: &str| ProviderChoice {
+            provider: provider.to_string(),
+            model: model.to_string(),
+        };
+        let choices = HashMap::from([
+            ("r2".to_string(), choice("codex", "o3")),
+            ("r1".to_string(), choice("claude", "opus")),
+        ]);
+        let content = format_provider_choices(&choices);
+        assert_eq!(content, "r1 claude opus\nr2 codex o3\n");
+        assert_eq!(parse_provider_choices(&content), choices);
+
+        let parsed = parse_provider_choices("r1 claude\n\nr2 codex o3\n");
+        assert_eq!(
+            parsed,
+            HashMap::from([("r2".to_string(), choice("codex", "o3"))])
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! Giving the terminal back to the shell, however the TUI exits
+//!
+//! The terminal is restored by a [`TerminalGuard`] held while the TUI runs,
+//! so errors returned early with `?` can't leave it in raw mode on the
+//! alternate screen, and by a panic hook before the panic is printed.
+
+use crossterm::{
+    cursor::Show,
+    event::{DisableBracketedPaste, DisableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, LeaveAlternateScreen},
+};
+use std::io;
+
+/// Leave raw mode, the alternate screen, mouse capture and bracketed paste,
+/// and show the cursor
+pub fn restore_terminal() -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        DisableBracketedPaste,
+        Show
+    )
+}
+
+/// Restores the terminal when dropped, unless restored already
+pub struct TerminalGuard {
+    restore: Option<Box<dyn FnOnce() -> io::Result<()> + Send>>,
+}
+
+impl TerminalGuard {
+    /// Guard stdout, the terminal the TUI is about to take over
+    pub fn stdout() -> Self {
+        Self::with_restore(restore_terminal)
+    }
+
+    fn with_restore(restore: impl FnOnce() -> io::Result<()> + Send + 'static) -> Self {
+        Self {
+            restore: Some(Box::new(restore)),
+        }
+    }
+
+    /// Restore the terminal now, reporting whether it worked
+    pub fn restore(mut self) -> io::Result<()> {
+        self.restore.take().map_or(Ok(()), |restore| restore())
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        if let Some(restore) = self.restore.take() {
+            // Best effort: there's no one left to report a failure to
+            let _ = restore();
+        }
+    }
+}
+
+/// Restore the terminal before a panic is printed
+///
+/// Without this the message would land on the alternate screen and leave
+/// the shell in raw mode. Sessions belong to the daemon and outlive the
+/// crash.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal();
+        default_hook(info);
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    fn counting_guard() -> (TerminalGuard, Arc<AtomicU32>) {
+        let restored = Arc::new(AtomicU32::new(0));
+        let count = restored.clone();
+        let guard = TerminalGuard::with_restore(move || {
+            count.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        });
+        (guard, restored)
+    }
+
+    #[test]
+    fn test_restores_on_error_path() {
+        let (guard, restored) = counting_guard();
+        let draw = || Err(io::Error::other("draw failed"));
+        let run = move || -> io::Result<()> {
+            let _guard = guard;
+            draw()?;
+            Ok(())
+        };
+        assert!(run().is_err());
+        assert_eq!(restored.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_restores_once() {
+        let (guard, restored) = counting_guard();
+        guard.restore().unwrap();
+        assert_eq!(restored.load(Ordering::Relaxed), 1);
+    }
+}
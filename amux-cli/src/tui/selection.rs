@@ -0,0 +1,149 @@
+//! Mouse selection of terminal text
+//!
+//! A double click selects the word under the mouse, a triple click its whole
+//! row. Selections are `(start_row, start_col, end_row, end_col)` in screen
+//! cells, both ends included.
+
+use std::time::{Duration, Instant};
+
+/// Longest pause between the clicks of a double or triple click
+const MULTI_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+/// Selected screen cells, `(start_row, start_col, end_row, end_col)`
+pub type Selection = (u16, u16, u16, u16);
+
+/// Counts quick successive clicks on the same cell
+#[derive(Debug, Default)]
+pub struct ClickCounter {
+    last: Option<(Instant, u16, u16)>,
+    count: u8,
+}
+
+impl ClickCounter {
+    /// Register a click, returning 1, 2 or 3 for a single, double or
+    /// triple click (a fourth click starts over)
+    pub fn click(&mut self, row: u16, col: u16, now: Instant) -> u8 {
+        let repeated = self.last.is_some_and(|(at, r, c)| {
+            r == row && c == col && now.duration_since(at) <= MULTI_CLICK_INTERVAL
+        });
+        self.count = if repeated { self.count % 3 + 1 } else { 1 };
+        self.last = Some((now, row, col));
+        self.count
+    }
+}
+
+/// Whether `selection` covers a cell
+pub fn contains(selection: Selection, row: u16, col: u16) -> bool {
+    let (start_row, start_col, end_row, end_col) = selection;
+    (row, col) >= (start_row, start_col) && (row, col) <= (end_row, end_col)
+}
+
+/// Columns of the word around `col` in a row of cell contents
+///
+/// Words end at characters in `delimiters` and at empty cells. Clicking a
+/// delimiter selects just that cell.
+pub fn word_bounds(cells: &[String], col: usize, delimiters: &str) -> Option<(usize, usize)> {
+    let is_delimiter = |cell: &String| {
+        cell.chars()
+            .next()
+            .is_none_or(|c| c.is_whitespace() || delimiters.contains(c))
+    };
+    if is_delimiter(cells.get(col)?) {
+        return Some((col, col));
+    }
+    let start = cells[..col]
+        .iter()
+        .rposition(is_delimiter)
+        .map_or(0, |i| i + 1);
+    let end = cells[col..]
+        .iter()
+        .position(is_delimiter)
+        .map_or(cells.len() - 1, |i| col + i - 1);
+    Some((start, end))
+}
+
+/// Contents of each cell of a screen row
+///
+/// The right half of a wide character repeats it, so it belongs to the same
+/// word.
+pub fn row_cells(screen: &vt100::Screen, row: u16) -> Vec<String> {
+    let (_, cols) = screen.size();
+    let mut cells: Vec<String> = Vec::with_capacity(cols as usize);
+    for col in 0..cols {
+        let contents = match screen.cell(row, col) {
+            Some(cell) if cell.is_wide_continuation() => cells.last().cloned().unwrap_or_default(),
+            Some(cell) => cell.contents().to_string(),
+            None => String::new(),
+        };
+        cells.push(contents);
+    }
+    cells
+}
+
+/// Text of the selected cells
+pub fn selected_text(screen: &vt100::Screen, selection: Selection) -> String {
+    let (start_row, start_col, end_row, end_col) = selection;
+    screen.contents_between(start_row, start_col, end_row, end_col + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(text: &str) -> Vec<String> {
+        text.chars().map(|c| c.to_string()).collect()
+    }
+
+    const DELIMITERS: &str = " \t\n\"'`()[]{}|&;:<>";
+
+    #[test]
+    fn test_click_counter() {
+        let mut clicks = ClickCounter::default();
+        let now = Instant::now();
+        assert_eq!(clicks.click(1, 2, now), 1);
+        assert_eq!(clicks.click(1, 2, now + Duration::from_millis(100)), 2);
+        assert_eq!(clicks.click(1, 2, now + Duration::from_millis(200)), 3);
+        assert_eq!(clicks.click(1, 2, now + Duration::from_millis(300)), 1);
+
+        // Too slow, or on another cell
+        assert_eq!(clicks.click(1, 2, now + Duration::from_secs(2)), 1);
+        assert_eq!(clicks.click(1, 3, now + Duration::from_secs(2)), 1);
+    }
+
+    #[test]
+    fn test_word_bounds() {
+        let row = cells("ls (src/main.rs) done");
+        assert_eq!(word_bounds(&row, 6, DELIMITERS), Some((4, 14)));
+        assert_eq!(word_bounds(&row, 0, DELIMITERS), Some((0, 1)));
+        assert_eq!(word_bounds(&row, 20, DELIMITERS), Some((17, 20)));
+        // A delimiter selects itself
+        assert_eq!(word_bounds(&row, 3, DELIMITERS), Some((3, 3)));
+        assert_eq!(word_bounds(&row, 30, DELIMITERS), None);
+
+        // Fewer delimiters make longer words
+        assert_eq!(word_bounds(&row, 6, " "), Some((3, 15)));
+    }
+
+    #[test]
+    fn test_word_bounds_with_wide_characters() {
+        let mut parser = vt100::Parser::new(1, 10, 0);
+        parser.process("a 中文 b".as_bytes());
+        let row = row_cells(parser.screen(), 0);
+        assert_eq!(word_bounds(&row, 3, DELIMITERS), Some((2, 5)));
+        // Empty cells past the text separate words too
+        assert_eq!(word_bounds(&row, 9, DELIMITERS), Some((9, 9)));
+    }
+
+    #[test]
+    fn test_selected_text() {
+        let mut parser = vt100::Parser::new(2, 10, 0);
+        parser.process(b"hello you\r\nthere");
+        let screen = parser.screen();
+        assert_eq!(selected_text(screen, (0, 6, 0, 8)), "you");
+        assert_eq!(selected_text(screen, (0, 6, 1, 4)), "you\nthere");
+        assert!(contains((0, 6, 1, 4), 0, 9));
+        assert!(contains((0, 6, 1, 4), 1, 0));
+        assert!(!contains((0, 6, 1, 4), 1, 5));
+        assert!(!contains((0, 6, 1, 4), 0, 5));
+    }
+}
@@ -0,0 +1,65 @@
+//! Lines of a provider's session file, as shown in the session file viewer
+//!
+//! Claude keeps a session as JSON Lines, one entry per line. Each entry is
+//! pretty-printed on its own, with a blank line between entries; a file
+//! holding a single JSON document is printed as one. Lines that aren't JSON
+//! are kept as they are.
+
+use serde_json::Value;
+
+/// Pretty-printed lines of a session file
+pub fn pretty_lines(content: &str) -> Vec<String> {
+    if let Ok(value) = serde_json::from_str::<Value>(content) {
+        return pretty(&value).lines().map(str::to_string).collect();
+    }
+
+    let mut lines = Vec::new();
+    for entry in content.lines().filter(|line| !line.trim().is_empty()) {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        match serde_json::from_str::<Value>(entry) {
+            Ok(value) => lines.extend(pretty(&value).lines().map(str::to_string)),
+            Err(_) => lines.push(entry.to_string()),
+        }
+    }
+    lines
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_lines() {
+        let content = "{\"type\":\"user\",\"n\":1}\n\nnot json\n[1]\n";
+        assert_eq!(
+            pretty_lines(content),
+            [
+                "{",
+                "  \"n\": 1,",
+                "  \"type\": \"user\"",
+                "}",
+                "",
+                "not json",
+                "",
+                "[",
+                "  1",
+                "]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_single_document() {
+        assert_eq!(
+            pretty_lines("{\n\"a\": {\"b\": null}\n}"),
+            ["{", "  \"a\": {", "    \"b\": null", "  }", "}"]
+        );
+        assert!(pretty_lines("").is_empty());
+    }
+}
@@ -0,0 +1,193 @@
+//! Order of the sidebar rows
+//!
+//! `RepoState` keeps worktrees and sessions in the daemon's order, and
+//! everything else refers to them by those indices. Only the rows are
+//! sorted (`sidebar_sort`), so drawing and cursor math both go through
+//! [`sidebar_rows`]. Worktrees are ranked by their loaded sessions; ones
+//! never expanded count as having none.
+
+use super::state::{RepoState, SidebarItem};
+use amux_config::SidebarSort;
+use amux_proto::daemon::{SessionInfo, SessionStatus, WorktreeInfo};
+use std::cmp::Reverse;
+
+/// Rows of the sidebar tree, top to bottom
+pub fn sidebar_rows(repo: &RepoState, sort: SidebarSort, pin_main: bool) -> Vec<SidebarItem> {
+    let mut rows = Vec::new();
+    for wt_idx in worktree_order(repo, sort, pin_main) {
+        rows.push(SidebarItem::Worktree(wt_idx));
+        if !repo.expanded_worktrees.contains(&wt_idx) {
+            continue;
+        }
+        if let Some(sessions) = repo.sessions_by_worktree.get(&wt_idx) {
+            rows.extend(
+                session_order(sessions, sort)
+                    .into_iter()
+                    .map(|s_idx| SidebarItem::Session(wt_idx, s_idx)),
+            );
+        }
+        if repo.session_page_tokens.contains_key(&wt_idx) {
+            rows.push(SidebarItem::LoadMore(wt_idx));
+        }
+    }
+    rows
+}
+
+/// Indices of the worktrees, in the order they are listed
+pub fn worktree_order(repo: &RepoState, sort: SidebarSort, pin_main: bool) -> Vec<usize> {
+    let worktrees = &repo.worktrees;
+    let sessions = |idx: usize| {
+        repo.sessions_by_worktree
+            .get(&idx)
+            .map_or(&[][..], Vec::as_slice)
+    };
+
+    let mut order: Vec<usize> = (0..worktrees.len()).collect();
+    match sort {
+        SidebarSort::None => {}
+        SidebarSort::Name => order.sort_by_cached_key(|&i| worktrees[i].branch.to_lowercase()),
+        SidebarSort::Recent => order.sort_by_key(|&i| Reverse(newest_session(sessions(i)))),
+        SidebarSort::Status => order.sort_by_key(|&i| worktree_rank(&worktrees[i], sessions(i))),
+    }
+    if pin_main {
+        // Stable, so the others keep their order
+        order.sort_by_key(|&i| !worktrees[i].is_main);
+    }
+    order
+}
+
+/// Indices of a worktree's sessions, in the order they are listed
+pub fn session_order(sessions: &[SessionInfo], sort: SidebarSort) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..sessions.len()).collect();
+    match sort {
+        SidebarSort::None => return order,
+        SidebarSort::Name => order.sort_by_cached_key(|&i| sessions[i].name.to_lowercase()),
+        SidebarSort::Recent => order.sort_by_key(|&i| Reverse(sessions[i].created_at)),
+        SidebarSort::Status => order.sort_by_key(|&i| !is_running(&sessions[i])),
+    }
+    order.sort_by_key(|&i| !sessions[i].pinned);
+    order
+}
+
+fn is_running(session: &SessionInfo) -> bool {
+    session.status == SessionStatus::Running as i32
+}
+
+fn newest_session(sessions: &[SessionInfo]) -> u64 {
+    sessions.iter().map(|s| s.created_at).max().unwrap_or(0)
+}
+
+/// Worktrees running a session first, then dirty ones
+fn worktree_rank(worktree: &WorktreeInfo, sessions: &[SessionInfo]) -> (bool, bool) {
+    let running = sessions.iter().any(is_running);
+    let dirty = worktree.status_known && worktree.dirty;
+    (!running, !dirty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amux_proto::daemon::RepoInfo;
+
+    fn worktree(branch: &str, is_main: bool, dirty: bool) -> WorktreeInfo {
+        WorktreeInfo {
+            branch: branch.to_string(),
+            is_main,
+            dirty,
+            status_known: true,
+            ..Default::default()
+        }
+    }
+
+    fn session(name: &str, running: bool, created_at: u64) -> SessionInfo {
+        let status = if running {
+            SessionStatus::Running
+        } else {
+            SessionStatus::Stopped
+        };
+        SessionInfo {
+            id: name.to_string(),
+            name: name.to_string(),
+            status: status as i32,
+            created_at,
+            ..Default::default()
+        }
+    }
+
+    fn repo() -> RepoState {
+        let mut repo = RepoState::new(RepoInfo::default());
+        repo.worktrees = vec![
+            worktree("main", true, false),
+            worktree("zeta", false, true),
+            worktree("Alpha", false, false),
+            worktree("mid", false, false),
+        ];
+        repo.sessions_by_worktree
+            .insert(3, vec![session("b", false, 10), session("a", true, 30)]);
+        repo
+    }
+
+    #[test]
+    fn test_worktree_order() {
+        let repo = repo();
+        assert_eq!(
+            worktree_order(&repo, SidebarSort::None, false),
+            [0, 1, 2, 3]
+        );
+        assert_eq!(
+            worktree_order(&repo, SidebarSort::Name, false),
+            [2, 0, 3, 1]
+        );
+        assert_eq!(worktree_order(&repo, SidebarSort::Name, true), [0, 2, 3, 1]);
+        // Only "mid" has sessions loaded
+        assert_eq!(
+            worktree_order(&repo, SidebarSort::Recent, true),
+            [0, 3, 1, 2]
+        );
+        // Running a session beats being dirty
+        assert_eq!(
+            worktree_order(&repo, SidebarSort::Status, false),
+            [3, 1, 0, 2]
+        );
+    }
+
+    #[test]
+    fn test_session_order() {
+        let mut sessions = vec![
+            session("b", false, 10),
+            session("c", true, 20),
+            session("a", false, 30),
+        ];
+        assert_eq!(session_order(&sessions, SidebarSort::None), [0, 1, 2]);
+        assert_eq!(session_order(&sessions, SidebarSort::Name), [2, 0, 1]);
+        assert_eq!(session_order(&sessions, SidebarSort::Recent), [2, 1, 0]);
+        assert_eq!(session_order(&sessions, SidebarSort::Status), [1, 0, 2]);
+
+        // Pinned sessions stay on top
+        sessions[0].pinned = true;
+        assert_eq!(session_order(&sessions, SidebarSort::Name), [0, 2, 1]);
+    }
+
+    #[test]
+    fn test_sidebar_rows() {
+        let mut repo = repo();
+        repo.expanded_worktrees.insert(3);
+        repo.session_page_tokens.insert(3, "next".to_string());
+        assert_eq!(
+            sidebar_rows(&repo, SidebarSort::Status, true),
+            [
+                SidebarItem::Worktree(0),
+                SidebarItem::Worktree(3),
+                SidebarItem::Session(3, 1),
+                SidebarItem::Session(3, 0),
+                SidebarItem::LoadMore(3),
+                SidebarItem::Worktree(1),
+                SidebarItem::Worktree(2),
+            ]
+        );
+        assert_eq!(
+            sidebar_rows(&repo, SidebarSort::None, false).len(),
+            repo.calculate_sidebar_total()
+        );
+    }
+}
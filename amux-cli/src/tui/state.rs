@@ -13,6 +13,7 @@ pub enum Focus {
     GitStatus, // Git status panel
     Terminal,  // Terminal interaction area
     DiffFiles, // Diff file list (with inline expansion)
+    Conflicts, // Merge conflict resolution view
 }
 
 /// Sidebar item in tree view
@@ -20,6 +21,7 @@ pub enum Focus {
 pub enum SidebarItem {
     Worktree(usize),       // Worktree at index
     Session(usize, usize), // (worktree_idx, session_idx within worktree)
+    LoadMore(usize),       // "Load more" row below a worktree's listed sessions
     None,
 }
 
@@ -29,6 +31,20 @@ pub enum GitSection {
     Staged,
     Unstaged,
     Untracked,
+    Submodules,
+    Ignored,
+    Stashes,
+}
+
+impl GitSection {
+    /// Sections listing files, in display order
+    pub const FILE_SECTIONS: [GitSection; 5] = [
+        GitSection::Staged,
+        GitSection::Unstaged,
+        GitSection::Untracked,
+        GitSection::Submodules,
+        GitSection::Ignored,
+    ];
 }
 
 /// A file with its git status (client-side representation)
@@ -44,6 +60,7 @@ pub struct GitStatusFile {
 pub enum GitPanelItem {
     Section(GitSection), // Section header
     File(usize),         // File at index in git_status_files
+    Stash(usize),        // Stash at index in stashes
     None,
 }
 
@@ -53,6 +70,7 @@ pub enum RightPanelView {
     #[default]
     Terminal,
     Diff,
+    Conflicts,
 }
 
 /// Current item in diff view (for unified navigation)
@@ -71,10 +89,44 @@ pub enum DeleteTarget {
 }
 
 /// Exit cleanup action for sessions
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum ExitCleanupAction {
+    #[default]
     Destroy, // Destroy all sessions (delete data)
-    Stop,    // Stop sessions (kill PTY, keep metadata)
+    Stop, // Stop sessions (kill PTY, keep metadata)
+}
+
+impl ExitCleanupAction {
+    /// The other action (Tab in the delete dialog)
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::Destroy => Self::Stop,
+            Self::Stop => Self::Destroy,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Destroy => "Destroy",
+            Self::Stop => "Stop",
+        }
+    }
+
+    /// A worktree's `default_delete_action`; unset or unspecified means none
+    pub fn from_proto(value: Option<i32>) -> Option<Self> {
+        match value.and_then(|v| DeleteAction::try_from(v).ok())? {
+            DeleteAction::Destroy => Some(Self::Destroy),
+            DeleteAction::Stop => Some(Self::Stop),
+            DeleteAction::Unspecified => None,
+        }
+    }
+
+    pub fn to_proto(self) -> DeleteAction {
+        match self {
+            Self::Destroy => DeleteAction::Destroy,
+            Self::Stop => DeleteAction::Stop,
+        }
+    }
 }
 
 /// Input mode for text entry
@@ -88,6 +140,9 @@ pub enum InputMode {
     RenameSession {
         session_id: String,
     }, // Renaming a session
+    RenameBranch {
+        branch: String,
+    }, // Renaming a worktree's branch
     ConfirmDelete(DeleteTarget), // Confirm deletion
     ConfirmDeleteBranch(String), // Confirm deleting branch after worktree (branch name)
     ConfirmDeleteWorktreeSessions {
@@ -123,22 +178,153 @@ pub enum InputMode {
         todo_id: String,
         title: String,
     },
-    // Provider selection for new session (with loading state)
+    // Provider and model selection for new session (with loading state)
     SelectProvider {
         repo_id: String,
         branch: String,
-        providers: Vec<String>,
+        providers: Vec<ProviderInfo>,
         selected_index: usize,
+        // Index into the selected provider's models
+        model_index: usize,
         loading: bool,
     },
+    // Commit message for quick commit-and-push
+    QuickShip {
+        repo_id: String,
+        branch: String,
+    },
+    // Optional message for a new stash
+    StashMessage {
+        repo_id: String,
+        branch: String,
+    },
+    // Entering a repository path to add
+    AddRepo,
+    // Confirm unregistering a repo (files on disk are kept)
+    ConfirmRemoveRepo {
+        repo_id: String,
+        name: String,
+        session_count: usize,
+        sessions: amux_config::QuitBehavior, // What happens to the repo's sessions
+    },
+    // Searching session names and transcripts (query = text the results match)
+    SearchSessions {
+        query: String,
+        results: Vec<SessionInfo>,
+        selected_index: usize,
+    },
+    // Searching session output history (results stream in from App::history_search)
+    SearchHistory {
+        query: String,
+        regex: bool,
+        results: Vec<HistoryMatch>,
+        selected_index: usize,
+        searching: bool,
+    },
+    // Blame of a diff file (state in App::blame)
+    BlamePopup,
+    // Picking the diff base (refs filtered by the typed text; index into the matches)
+    SelectDiffBase {
+        refs: Vec<String>,
+        selected_index: usize,
+    },
+    // Picking a past review to reopen
+    SelectReview {
+        reviews: Vec<ReviewInfo>,
+        selected_index: usize,
+    },
+    // Picking a recently shown session (index into App::recent_switch_targets)
+    RecentSessions {
+        selected_index: usize,
+    },
+    // Actions for the right-clicked sidebar row, drawn at the click
+    ContextMenu {
+        items: Vec<Action>,
+        selected_index: usize,
+        column: u16,
+        row: u16,
+    },
+    // Worktree is dirty, confirm stashing before pull
+    ConfirmPullStash {
+        repo_id: String,
+        branch: String,
+    },
+    // Typing "restore" to roll a worktree back to a session's checkpoint
+    ConfirmRestoreCheckpoint {
+        session_id: String,
+        session_name: String,
+        branch: String,
+        created_at: i64,
+    },
+    // Details of a session (by ID); any key closes it
+    SessionInfo(String),
+    // Read-only view of the provider's session file, pretty-printed
+    SessionFile {
+        path: String,
+        lines: Vec<String>,
+        scroll: usize,
+        truncated: bool,
+    },
     // Creating new session with name input in status bar
     CreateSessionInput {
         repo_id: String,
         branch: String,
-        provider: Option<String>,
+        provider: Option<ProviderChoice>,
+    },
+    // Optional system prompt for the session being created
+    SessionSystemPrompt {
+        repo_id: String,
+        branch: String,
+        name: Option<String>,
+        provider: Option<ProviderChoice>,
+    },
+    // Command line to run as a new session
+    RunCommand {
+        repo_id: String,
+        branch: String,
+    },
+    // Line to type into every running session of a worktree
+    BroadcastInput,
+    // Runtime command typed after `:` in the status bar
+    CommandLine,
+    // Search bar of the diff view, searching as it is typed (`origin` = item to return to)
+    SearchDiff {
+        origin: DiffItem,
+    },
+    // Confirm typing `text` into `count` running sessions
+    ConfirmBroadcast {
+        text: String,
+        count: usize,
+    },
+    // Quitting with running sessions (in `worktrees` worktrees) or dirty worktrees
+    ConfirmQuit {
+        session_ids: Vec<String>,
+        worktrees: usize,
+        dirty_worktrees: usize,
+    },
+    // Subdirectory of the worktree to open a new shell in
+    ShellDirectory {
+        repo_id: String,
+        branch: String,
     },
 }
 
+impl InputMode {
+    /// Whether Enter inserts a newline in this mode's text input
+    ///
+    /// Such modes submit with Alt+Enter instead.
+    pub fn allows_newline(&self) -> bool {
+        matches!(
+            self,
+            InputMode::QuickShip { .. }
+                | InputMode::AddLineComment { .. }
+                | InputMode::EditLineComment { .. }
+                | InputMode::EditTodoDescription { .. }
+                | InputMode::SessionSystemPrompt { .. }
+        )
+    }
+}
+
 /// Terminal mode (vim-style)
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TerminalMode {
@@ -153,6 +339,25 @@ pub struct SavedFocusState {
     pub terminal_mode: Option<TerminalMode>,
 }
 
+/// Number of sessions kept in `App::recent_sessions`
+pub const RECENT_SESSIONS_CAPACITY: usize = 10;
+
+/// A session shown in the terminal, kept to switch back to it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecentSession {
+    pub repo_id: String,
+    pub session_id: String,
+    /// When it was last shown (unix seconds)
+    pub shown_at: i64,
+}
+
+/// Provider and model picked for new sessions of a repo
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderChoice {
+    pub provider: String,
+    pub model: String,
+}
+
 /// Prefix key mode state
 #[derive(Debug, Clone, PartialEq)]
 pub enum PrefixMode {
@@ -160,6 +365,22 @@ pub enum PrefixMode {
     None,
     /// Waiting for command after Ctrl+s prefix
     WaitingForCommand,
+    /// Waiting for the register after a macro command
+    WaitingForRegister(MacroRegisterFor),
+}
+
+/// What the register key after a macro command is for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MacroRegisterFor {
+    Record,
+    Replay,
+}
+
+/// Macro being recorded
+#[derive(Debug, Clone)]
+pub struct MacroRecording {
+    pub register: char,
+    pub steps: Vec<amux_config::MacroStep>,
 }
 
 /// Tracks which UI components need redrawing
@@ -229,9 +450,36 @@ pub enum AsyncAction {
     RefreshAll,
     RefreshSessions,
     RefreshBranches,
+    SaveRepoOrder,
+    SaveSidebarWidth,
+    AddRepo {
+        path: String,
+    },
+    CleanupWorktrees,
+    RemoveRepo {
+        repo_id: String,
+        sessions: amux_config::QuitBehavior,
+    },
     CreateSession,
     SubmitInput,
     SubmitRenameSession,
+    SubmitRenameBranch,
+    CycleWorktreeDeleteAction,
+    TogglePinSession,
+    ExportTranscript,
+    ViewSessionFile,
+    ReplayMacro {
+        register: char,
+    },
+    ToggleCheckpoints,
+    PrepareRestoreCheckpoint,
+    RestoreCheckpoint,
+    MoveSession {
+        down: bool,
+    },
+    ShowConflicts,
+    LoadConflictFile,
+    WriteConflictResolution,
     SubmitAddWorktree,
     ConfirmDelete {
         target: DeleteTarget,
@@ -242,6 +490,7 @@ pub enum AsyncAction {
     DestroySession {
         session_id: String,
     },
+    Undo,
     RenameSession {
         session_id: String,
         new_name: String,
@@ -258,18 +507,27 @@ pub enum AsyncAction {
     SwitchToDiffView,
     LoadDiffFiles,
     LoadFileDiff,
+    ExpandDiffContext,
     // Comment actions
     LoadComments,
     SubmitLineComment,
     UpdateLineComment,
     DeleteLineComment,
     SubmitReviewToClaude,
+    ShowReviews,
+    OpenReview,
     // Tree view actions
     LoadWorktreeSessions {
         wt_idx: usize,
     },
+    LoadMoreSessions {
+        wt_idx: usize,
+    },
     // Git status actions
     LoadGitStatus,
+    RefreshWorktreeStatus {
+        branch: String,
+    },
     StageFile {
         file_path: String,
     },
@@ -280,6 +538,13 @@ pub enum AsyncAction {
     UnstageAll,
     GitPush,
     GitPull,
+    GitFetch,
+    ConfirmPullStash,
+    StashSave,
+    StashPop,
+    ViewStashDiff,
+    StartQuickShip,
+    SubmitQuickShip,
     // Shell session action
     SwitchToShell,
     // TODO actions
@@ -311,8 +576,45 @@ pub enum AsyncAction {
         branch: String,
     },
     SubmitProviderSelection,
+    // Session search actions
+    SearchSessions {
+        query: String,
+    },
+    SubmitSearchSessions,
+    // History search actions
+    SearchHistory {
+        query: String,
+    },
+    SubmitSearchHistory,
+    // Diff base selection
+    StartSelectDiffBase,
+    SubmitDiffBase,
+    // Worktree / branch diff mode
+    ToggleDiffMode,
+    // Blame of the focused diff file
+    ShowBlame,
     // Session creation with name input
     SubmitCreateSessionInput,
+    SubmitSessionSystemPrompt,
+    SubmitRunCommand,
+    SubmitCommandLine,
+    ConfirmBroadcast,
+    // Search the diff for the typed text, and move to the next/previous match
+    UpdateDiffSearch,
+    DiffSearch {
+        forward: bool,
+    },
+    // Shell in a subdirectory
+    CompleteShellDirectory,
+    SubmitShellDirectory,
+    // Quit, asking first if sessions are running
+    RequestQuit,
+    StopAllAndQuit,
+    // `startup_action`, skipped if the sidebar moved since it was queued
+    RunStartupAction {
+        repo_id: Option<String>,
+        sidebar_cursor: usize,
+    },
 }
 
 /// Default expanded git sections
@@ -321,6 +623,9 @@ pub fn default_expanded_git_sections() -> HashSet<GitSection> {
     set.insert(GitSection::Staged);
     set.insert(GitSection::Unstaged);
     set.insert(GitSection::Untracked);
+    set.insert(GitSection::Submodules);
+    set.insert(GitSection::Ignored);
+    set.insert(GitSection::Stashes);
     set
 }
 
@@ -328,9 +633,13 @@ pub fn default_expanded_git_sections() -> HashSet<GitSection> {
 // These types group related fields from App for better organization.
 // They are designed to be used as embedded structs within App.
 
-use amux_config::{DEFAULT_SCROLLBACK, DEFAULT_TERMINAL_COLS, DEFAULT_TERMINAL_ROWS};
+use super::hyperlink::{HyperlinkSpan, PendingHyperlink};
+use super::selection::{ClickCounter, Selection};
+use amux_config::{Action, DEFAULT_SCROLLBACK, DEFAULT_TERMINAL_COLS, DEFAULT_TERMINAL_ROWS};
 use amux_proto::daemon::{
-    DiffFileInfo, DiffLine, LineCommentInfo, RepoInfo, SessionInfo, TodoItem, WorktreeInfo,
+    BlameLine, ConflictFile, ConflictRegion, ConflictResolution, DeleteAction, DiffFileInfo,
+    DiffLine, HistoryMatch, LineCommentInfo, ProviderInfo, RepoInfo, ReviewInfo, SessionInfo,
+    StashEntry, TodoItem, WorktreeInfo,
 };
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
@@ -365,14 +674,22 @@ pub struct RepoState {
     pub expanded_worktrees: HashSet<usize>,
     /// Sessions grouped by worktree index (cache for tree view)
     pub sessions_by_worktree: HashMap<usize, Vec<SessionInfo>>,
+    /// Token of the next session page by worktree index, while more remain
+    pub session_page_tokens: HashMap<usize, String>,
 
     // ============ View State ============
     /// Git status panel state
     pub git: GitState,
     /// Diff view state
     pub diff: DiffState,
+    /// Review positions of diffs left for the terminal, by branch
+    pub diff_snapshots: HashMap<String, DiffSnapshot>,
+    /// Merge conflict view state
+    pub conflicts: ConflictState,
     /// Line comments for current branch
     pub line_comments: Vec<LineCommentInfo>,
+    /// `linguist-language` rules from `.gitattributes` (read with the first diff)
+    pub highlight_attributes: Option<Vec<(String, String)>>,
 }
 
 impl RepoState {
@@ -389,9 +706,13 @@ impl RepoState {
             sidebar_cursor: 0,
             expanded_worktrees: HashSet::new(),
             sessions_by_worktree: HashMap::new(),
+            session_page_tokens: HashMap::new(),
             git: GitState::default(),
             diff: DiffState::default(),
+            conflicts: ConflictState::default(),
             line_comments: Vec::new(),
+            highlight_attributes: None,
+            diff_snapshots: HashMap::new(),
         }
     }
 
@@ -409,13 +730,20 @@ impl RepoState {
     pub fn calculate_sidebar_total(&self) -> usize {
         let mut count = self.worktrees.len();
         for &wt_idx in &self.expanded_worktrees {
-            if let Some(sessions) = self.sessions_by_worktree.get(&wt_idx) {
-                count += sessions.len();
-            }
+            count += self.session_rows(wt_idx);
         }
         count.max(1)
     }
 
+    /// Rows listed under an expanded worktree: its sessions and "Load more"
+    pub fn session_rows(&self, wt_idx: usize) -> usize {
+        let sessions = self
+            .sessions_by_worktree
+            .get(&wt_idx)
+            .map_or(0, |s| s.len());
+        sessions + usize::from(self.session_page_tokens.contains_key(&wt_idx))
+    }
+
     /// Clamp all indices to valid ranges
     pub fn clamp_indices(&mut self) {
         if self.worktrees.is_empty() {
@@ -461,6 +789,16 @@ pub struct TerminalState {
     pub cols: Option<u16>,
     /// Terminal rows
     pub rows: Option<u16>,
+    /// OSC 8 hyperlinks on the current screen
+    pub hyperlinks: Vec<HyperlinkSpan>,
+    /// Hyperlink opened but not yet closed by the PTY output
+    pub pending_hyperlink: Option<PendingHyperlink>,
+    /// History offset to scroll to once the attached session's replay arrives
+    pub history_jump: Option<u64>,
+    /// Cells selected by a double or triple click
+    pub selection: Option<Selection>,
+    /// Recent clicks, to tell double and triple clicks apart
+    pub clicks: ClickCounter,
 }
 
 impl Default for TerminalState {
@@ -480,10 +818,25 @@ impl Default for TerminalState {
             fullscreen: false,
             cols: None,
             rows: None,
+            hyperlinks: Vec::new(),
+            pending_hyperlink: None,
+            history_jump: None,
+            selection: None,
+            clicks: ClickCounter::default(),
         }
     }
 }
 
+/// What the diff view compares
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiffViewMode {
+    /// Worktree changes against HEAD or `base_ref`
+    #[default]
+    WorkingTree,
+    /// Commits of the branch since its merge base with `branch_base`
+    Branch,
+}
+
 /// Diff view state
 #[derive(Default)]
 pub struct DiffState {
@@ -499,12 +852,153 @@ pub struct DiffState {
     pub scroll_offset: usize,
     /// Whether diff is fullscreen
     pub fullscreen: bool,
+    /// Commit-ish whose changes are shown instead of the worktree (e.g. a stash)
+    pub revision: Option<String>,
+    /// Ref the worktree is compared against (`None` = HEAD)
+    pub base_ref: Option<String>,
+    /// Whether worktree or branch changes are shown
+    pub mode: DiffViewMode,
+    /// Base picked for `Branch` mode (`None` = the daemon's default)
+    pub branch_base: Option<String>,
+    /// Base the shown branch changes were compared against
+    pub branch_base_used: Option<String>,
+    /// Branch the files were loaded for
+    pub loaded_branch: Option<String>,
+    /// Git status of `loaded_branch` changed since the files were loaded
+    pub stale: bool,
+    /// Search of the diff lines, cycled with n/N
+    pub search: Option<DiffSearch>,
+}
+
+impl DiffState {
+    /// Base picked for the current mode
+    pub fn picked_base(&self) -> Option<String> {
+        match self.mode {
+            DiffViewMode::WorkingTree => self.base_ref.clone(),
+            DiffViewMode::Branch => self.branch_base.clone(),
+        }
+    }
+
+    /// Base ref of file diff requests, and whether it is a branch diff's
+    pub fn file_diff_base(&self) -> (Option<String>, bool) {
+        match self.mode {
+            DiffViewMode::WorkingTree => (self.base_ref.clone(), false),
+            DiffViewMode::Branch => (self.branch_base_used.clone(), true),
+        }
+    }
+
+    /// Revision holding the new side of the shown changes (`None` = worktree)
+    pub fn new_side_revision(&self) -> Option<String> {
+        match (&self.revision, self.mode) {
+            (Some(revision), _) => Some(revision.clone()),
+            (None, DiffViewMode::Branch) => Some("HEAD".to_string()),
+            (None, DiffViewMode::WorkingTree) => None,
+        }
+    }
+
+    /// Review position of the shown worktree changes
+    ///
+    /// `None` for a stash, and for files loaded before the last git status
+    /// change.
+    pub fn snapshot(&self) -> Option<DiffSnapshot> {
+        if self.stale || self.revision.is_some() || self.loaded_branch.is_none() {
+            return None;
+        }
+        Some(DiffSnapshot {
+            expanded: self
+                .expanded
+                .iter()
+                .filter_map(|&idx| self.files.get(idx))
+                .map(|f| f.path.clone())
+                .collect(),
+            cursor: self.cursor,
+            scroll_offset: self.scroll_offset,
+            base_ref: self.base_ref.clone(),
+            mode: self.mode,
+            branch_base: self.branch_base.clone(),
+        })
+    }
+
+    /// Expand the snapshot's files among the listed ones
+    ///
+    /// The cursor is restored by [`DiffState::restore_cursor`] once their
+    /// lines are loaded.
+    pub fn restore_expanded(&mut self, snapshot: &DiffSnapshot) {
+        self.expanded = self
+            .files
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| snapshot.expanded.contains(&f.path))
+            .map(|(idx, _)| idx)
+            .collect();
+    }
+
+    /// Put the cursor and scroll back where the snapshot had them
+    pub fn restore_cursor(&mut self, snapshot: &DiffSnapshot) {
+        self.cursor = snapshot.cursor;
+        self.scroll_offset = snapshot.scroll_offset;
+        self.clamp_cursor();
+    }
+}
+
+/// Review position of a worktree's diff, kept while the terminal is shown
+///
+/// Dropped when the worktree's git status changes, as the files and lines it
+/// points into may have moved.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiffSnapshot {
+    /// Paths of the expanded files
+    pub expanded: HashSet<String>,
+    pub cursor: usize,
+    pub scroll_offset: usize,
+    pub base_ref: Option<String>,
+    pub mode: DiffViewMode,
+    pub branch_base: Option<String>,
+}
+
+/// Text searched for in the diff lines
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiffSearch {
+    pub query: String,
+    /// Matching `(file_idx, line_idx)` of the expanded files, in order
+    pub matches: Vec<(usize, usize)>,
+    /// Index into `matches` of the one under the cursor
+    pub current: usize,
+}
+
+/// Merge conflict view state
+#[derive(Default)]
+pub struct ConflictState {
+    /// Files of the worktree with unresolved conflicts
+    pub files: Vec<ConflictFile>,
+    /// File shown (index into `files`)
+    pub file_idx: usize,
+    /// Unchanged text and conflict hunks of the shown file
+    pub regions: Vec<ConflictRegion>,
+    /// Chosen resolution per conflict hunk of the shown file
+    pub choices: Vec<Option<ConflictResolution>>,
+    /// Selected conflict hunk
+    pub selected: usize,
+}
+
+impl ConflictState {
+    /// The shown file
+    pub fn current_file(&self) -> Option<&ConflictFile> {
+        self.files.get(self.file_idx)
+    }
+
+    /// Hunks of the shown file still without a resolution
+    pub fn unresolved(&self) -> usize {
+        self.choices.iter().filter(|c| c.is_none()).count()
+    }
 }
 
 /// Git status panel state
 pub struct GitState {
     /// All files (staged + unstaged + untracked)
     pub files: Vec<GitStatusFile>,
+    /// Stashes made on the worktree's branch, newest first
+    pub stashes: Vec<StashEntry>,
     /// Cursor in virtual list
     pub cursor: usize,
     /// Scroll offset for rendering (reserved for future caching optimization)
@@ -520,6 +1014,7 @@ impl Default for GitState {
     fn default() -> Self {
         Self {
             files: Vec::new(),
+            stashes: Vec::new(),
             cursor: 0,
             scroll_offset: 0,
             expanded_sections: default_expanded_git_sections(),
@@ -532,14 +1027,178 @@ impl Default for GitState {
 pub struct SidebarState {
     /// Whether git panel is enabled
     pub git_panel_enabled: bool,
+    /// Sidebar share of the screen width, in percent
+    pub width_percent: u16,
+    /// Sidebar hidden, giving the right panel the full width (not persisted)
+    pub hidden: bool,
+    /// Column under the mouse while the sidebar border is being dragged
+    pub drag_column: Option<u16>,
+    /// Sessions with output since they were last active (session id -> repo id)
+    pub unread: HashMap<String, String>,
+}
+
+impl SidebarState {
+    /// Number of unread sessions in a repo
+    pub fn unread_in_repo(&self, repo_id: &str) -> usize {
+        self.unread.values().filter(|r| *r == repo_id).count()
+    }
+
+    /// Sidebar width in columns for a screen `total_cols` wide
+    ///
+    /// Shared by the layout and the PTY size calculation so they agree.
+    pub fn width_cols(&self, total_cols: u16) -> u16 {
+        if self.hidden {
+            return 0;
+        }
+        (total_cols as u32 * self.width_percent as u32 / 100) as u16
+    }
 }
 
 impl Default for SidebarState {
     fn default() -> Self {
         Self {
             git_panel_enabled: true,
+            width_percent: 25,
+            hidden: false,
+            drag_column: None,
+            unread: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod sidebar_state_tests {
+    use super::*;
+
+    #[test]
+    fn test_sidebar_width_cols() {
+        let mut sidebar = SidebarState::default();
+        assert_eq!(sidebar.width_cols(160), 40);
+        sidebar.width_percent = 60;
+        assert_eq!(sidebar.width_cols(99), 59);
+        assert_eq!(sidebar.width_cols(0), 0);
+        sidebar.hidden = true;
+        assert_eq!(sidebar.width_cols(160), 0);
+    }
+
+    #[test]
+    fn test_unread_in_repo() {
+        let mut sidebar = SidebarState::default();
+        sidebar.unread.insert("s1".to_string(), "a".to_string());
+        sidebar.unread.insert("s2".to_string(), "a".to_string());
+        sidebar.unread.insert("s3".to_string(), "b".to_string());
+        assert_eq!(sidebar.unread_in_repo("a"), 2);
+        assert_eq!(sidebar.unread_in_repo("c"), 0);
+    }
+}
+
+/// Blame overlay state
+#[derive(Default)]
+pub struct BlameState {
+    /// File being blamed
+    pub file_path: String,
+    /// HEAD commit the blame was computed at
+    pub head: String,
+    /// One entry per line of the file
+    pub lines: Vec<BlameLine>,
+    /// Selected line
+    pub cursor: usize,
+}
+
+impl BlameState {
+    /// Move the selection by `delta` lines, stopping at either end
+    pub fn move_by(&mut self, delta: isize) {
+        let last = self.lines.len().saturating_sub(1) as isize;
+        self.cursor = (self.cursor as isize).saturating_add(delta).clamp(0, last) as usize;
+    }
+}
+
+#[cfg(test)]
+mod blame_state_tests {
+    use super::*;
+
+    #[test]
+    fn test_blame_move_by_clamps() {
+        let mut blame = BlameState {
+            lines: vec![BlameLine::default(); 5],
+            ..Default::default()
+        };
+        blame.move_by(3);
+        assert_eq!(blame.cursor, 3);
+        blame.move_by(isize::MAX);
+        assert_eq!(blame.cursor, 4);
+        blame.move_by(-20);
+        assert_eq!(blame.cursor, 0);
+
+        let mut empty = BlameState::default();
+        empty.move_by(1);
+        assert_eq!(empty.cursor, 0);
+    }
+}
+
+/// Something a destructive action removed that undo can put back
+#[derive(Debug, Clone)]
+pub enum UndoEntry {
+    /// A session moved to the daemon's trash
+    Session { session_id: String, name: String },
+    /// A deleted line comment
+    Comment(LineCommentInfo),
+    /// A deleted TODO item with its descendants
+    Todos {
+        repo_id: String,
+        items: Vec<TodoItem>,
+    },
+}
+
+/// Recent destructive actions, newest last
+#[derive(Default)]
+pub struct UndoStack {
+    entries: Vec<(UndoEntry, std::time::Instant)>,
+}
+
+impl UndoStack {
+    pub fn push(&mut self, entry: UndoEntry) {
+        self.entries.push((entry, std::time::Instant::now()));
+    }
+
+    /// Take the newest entry, dropping everything older than `ttl`
+    pub fn pop(&mut self, ttl: std::time::Duration) -> Option<UndoEntry> {
+        self.entries.retain(|(_, at)| at.elapsed() < ttl);
+        self.entries.pop().map(|(entry, _)| entry)
+    }
+}
+
+#[cfg(test)]
+mod undo_stack_tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn session(id: &str) -> UndoEntry {
+        UndoEntry::Session {
+            session_id: id.to_string(),
+            name: id.to_string(),
         }
     }
+
+    #[test]
+    fn test_undo_stack_pops_newest_within_ttl() {
+        let mut undo = UndoStack::default();
+        undo.push(session("a"));
+        undo.push(session("b"));
+
+        let ttl = Duration::from_secs(30);
+        assert!(
+            matches!(undo.pop(ttl), Some(UndoEntry::Session { session_id, .. }) if session_id == "b")
+        );
+        assert!(
+            matches!(undo.pop(ttl), Some(UndoEntry::Session { session_id, .. }) if session_id == "a")
+        );
+        assert!(undo.pop(ttl).is_none());
+
+        undo.push(session("c"));
+        assert!(undo.pop(Duration::ZERO).is_none());
+        assert!(undo.pop(ttl).is_none());
+    }
 }
 
 /// TODO state
@@ -612,17 +1271,16 @@ impl VirtualList for DiffState {
 impl VirtualList for GitState {
     fn virtual_len(&self) -> usize {
         let mut count = 0;
-        for section in [
-            GitSection::Staged,
-            GitSection::Unstaged,
-            GitSection::Untracked,
-        ] {
+        for section in GitSection::FILE_SECTIONS {
             if self.expanded_sections.contains(&section) {
                 count += 1; // Section header
                             // Count files in this section
                 count += self.files.iter().filter(|f| f.section == section).count();
             }
         }
+        if !self.stashes.is_empty() && self.expanded_sections.contains(&GitSection::Stashes) {
+            count += 1 + self.stashes.len();
+        }
         count.max(1)
     }
 
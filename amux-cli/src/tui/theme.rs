@@ -1,15 +1,30 @@
-//! Catppuccin Mocha theme system for amux TUI
+//! Color themes for amux TUI
 //!
-//! Provides a centralized color theme using the Catppuccin Mocha palette,
-//! a soothing pastel theme that's easy on the eyes.
+//! Every color the views draw with is a named role of [`Theme`]. The
+//! built-in `dark` theme uses the Catppuccin Mocha palette and `light` the
+//! Catppuccin Latte one; any other `theme` option value names a TOML file
+//! that overrides roles of a base theme:
+//!
+//! ```toml
+//! base = "light"                   # optional, defaults to "dark"
+//! syntax_theme = "InspiredGitHub"  # optional syntect theme for code
+//! focus_border = "#1e66f5"
+//! diff_add = "#40a02b"
+//! ```
+//!
+//! A bare name is looked up as `~/.amux/themes/<name>.toml`.
 //!
 //! Palette reference: https://catppuccin.com/palette/
 
 use ratatui::style::{Color, Modifier, Style};
+use std::path::PathBuf;
+
+/// Color theme with a color per UI role
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    /// Syntect theme used to highlight code in diffs
+    pub syntax_theme: String,
 
-/// Catppuccin Mocha color theme - soothing pastel colors
-#[derive(Debug, Clone)]
-pub struct CyberpunkTheme {
     // Primary accent colors
     pub neon_cyan: Color,
     pub neon_magenta: Color,
@@ -59,16 +74,18 @@ pub struct CyberpunkTheme {
     pub comment_line_no: Color,
 }
 
-impl Default for CyberpunkTheme {
+impl Default for Theme {
     fn default() -> Self {
-        Self::cyberpunk()
+        Self::dark()
     }
 }
 
-impl CyberpunkTheme {
+impl Theme {
     /// Catppuccin Mocha theme - soothing pastel colors
-    pub fn cyberpunk() -> Self {
+    pub fn dark() -> Self {
         Self {
+            syntax_theme: "base16-eighties.dark".to_string(),
+
             // Catppuccin Mocha accent colors
             neon_cyan: Color::Rgb(148, 226, 213), // Teal #94e2d5
             neon_magenta: Color::Rgb(203, 166, 247), // Mauve #cba6f7
@@ -119,6 +136,140 @@ impl CyberpunkTheme {
         }
     }
 
+    /// Catppuccin Latte theme - for light terminal backgrounds
+    pub fn light() -> Self {
+        Self {
+            syntax_theme: "InspiredGitHub".to_string(),
+
+            // Catppuccin Latte accent colors
+            neon_cyan: Color::Rgb(23, 146, 153), // Teal #179299
+            neon_magenta: Color::Rgb(136, 57, 239), // Mauve #8839ef
+            neon_yellow: Color::Rgb(223, 142, 29), // Yellow #df8e1d
+            neon_green: Color::Rgb(64, 160, 43), // Green #40a02b
+
+            // UI semantic
+            focus_border: Color::Rgb(114, 135, 253), // Lavender #7287fd
+            unfocus_border: Color::Rgb(172, 176, 190), // Surface 2 #acb0be
+            selection_fg: Color::Rgb(76, 79, 105),   // Text #4c4f69
+
+            // Status
+            success: Color::Rgb(64, 160, 43),  // Green #40a02b
+            error: Color::Rgb(210, 15, 57),    // Red #d20f39
+            warning: Color::Rgb(223, 142, 29), // Yellow #df8e1d
+
+            // Git status
+            git_added: Color::Rgb(64, 160, 43), // Green #40a02b
+            git_modified: Color::Rgb(223, 142, 29), // Yellow #df8e1d
+            git_deleted: Color::Rgb(210, 15, 57), // Red #d20f39
+            git_renamed: Color::Rgb(23, 146, 153), // Teal #179299
+            git_untracked: Color::Rgb(136, 57, 239), // Mauve #8839ef
+            git_staged: Color::Rgb(64, 160, 43), // Green #40a02b
+            git_unstaged: Color::Rgb(254, 100, 11), // Peach #fe640b
+
+            // Terminal modes
+            terminal_insert: Color::Rgb(64, 160, 43), // Green #40a02b
+            terminal_normal: Color::Rgb(223, 142, 29), // Yellow #df8e1d
+
+            // Background (Catppuccin Latte base)
+            bg_level0: Color::Rgb(239, 241, 245), // Base #eff1f5
+
+            // Text hierarchy (Catppuccin Latte text colors)
+            text_primary: Color::Rgb(76, 79, 105), // Text #4c4f69
+            text_secondary: Color::Rgb(92, 95, 119), // Subtext 1 #5c5f77
+            text_tertiary: Color::Rgb(124, 127, 147), // Overlay 2 #7c7f93
+            text_disabled: Color::Rgb(140, 143, 161), // Overlay 1 #8c8fa1
+
+            // Diff colors
+            diff_add: Color::Rgb(64, 160, 43), // Green #40a02b
+            diff_del: Color::Rgb(210, 15, 57), // Red #d20f39
+            diff_hunk_header: Color::Rgb(30, 102, 245), // Blue #1e66f5
+
+            // Comment colors
+            comment_border: Color::Rgb(172, 176, 190), // Surface 2 #acb0be
+            comment_path: Color::Rgb(23, 146, 153),    // Teal #179299
+            comment_line_no: Color::Rgb(223, 142, 29), // Yellow #df8e1d
+        }
+    }
+
+    /// Resolve a `theme` option value: a built-in name or a theme file
+    pub fn load(name: &str) -> Result<Self, String> {
+        match name {
+            "dark" => Ok(Self::dark()),
+            "light" => Ok(Self::light()),
+            _ => {
+                let path = theme_path(name);
+                let content = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("Cannot read theme {}: {}", path.display(), e))?;
+                Self::from_toml(&content)
+                    .map_err(|e| format!("Invalid theme {}: {}", path.display(), e))
+            }
+        }
+    }
+
+    /// Parse a theme file: roles given as `"#rrggbb"` over its `base` theme
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let table: toml::Table = content.parse().map_err(|e| format!("{}", e))?;
+        let mut theme = match table.get("base") {
+            None => Self::dark(),
+            Some(toml::Value::String(base)) if base == "dark" => Self::dark(),
+            Some(toml::Value::String(base)) if base == "light" => Self::light(),
+            Some(base) => return Err(format!("unknown base theme {}", base)),
+        };
+
+        for (key, value) in &table {
+            match (key.as_str(), value) {
+                ("base", _) => {}
+                ("syntax_theme", toml::Value::String(name)) => theme.syntax_theme = name.clone(),
+                (role, toml::Value::String(hex)) => {
+                    let color = parse_hex(hex)
+                        .ok_or_else(|| format!("{}: expected \"#rrggbb\", got {:?}", role, hex))?;
+                    *theme
+                        .role_mut(role)
+                        .ok_or_else(|| format!("unknown color role '{}'", role))? = color;
+                }
+                (key, _) => return Err(format!("{}: expected a string", key)),
+            }
+        }
+        Ok(theme)
+    }
+
+    /// Color of a role by its field name
+    fn role_mut(&mut self, role: &str) -> Option<&mut Color> {
+        Some(match role {
+            "neon_cyan" => &mut self.neon_cyan,
+            "neon_magenta" => &mut self.neon_magenta,
+            "neon_yellow" => &mut self.neon_yellow,
+            "neon_green" => &mut self.neon_green,
+            "focus_border" => &mut self.focus_border,
+            "unfocus_border" => &mut self.unfocus_border,
+            "selection_fg" => &mut self.selection_fg,
+            "success" => &mut self.success,
+            "error" => &mut self.error,
+            "warning" => &mut self.warning,
+            "git_added" => &mut self.git_added,
+            "git_modified" => &mut self.git_modified,
+            "git_deleted" => &mut self.git_deleted,
+            "git_renamed" => &mut self.git_renamed,
+            "git_untracked" => &mut self.git_untracked,
+            "git_staged" => &mut self.git_staged,
+            "git_unstaged" => &mut self.git_unstaged,
+            "terminal_insert" => &mut self.terminal_insert,
+            "terminal_normal" => &mut self.terminal_normal,
+            "bg_level0" => &mut self.bg_level0,
+            "text_primary" => &mut self.text_primary,
+            "text_secondary" => &mut self.text_secondary,
+            "text_tertiary" => &mut self.text_tertiary,
+            "text_disabled" => &mut self.text_disabled,
+            "diff_add" => &mut self.diff_add,
+            "diff_del" => &mut self.diff_del,
+            "diff_hunk_header" => &mut self.diff_hunk_header,
+            "comment_border" => &mut self.comment_border,
+            "comment_path" => &mut self.comment_path,
+            "comment_line_no" => &mut self.comment_line_no,
+            _ => return None,
+        })
+    }
+
     // ========== Style Helpers ==========
 
     /// Style for focused panel border
@@ -160,6 +311,9 @@ impl CyberpunkTheme {
             GitFileStatus::Deleted => self.git_deleted,
             GitFileStatus::Renamed => self.git_renamed,
             GitFileStatus::Untracked => self.git_untracked,
+            GitFileStatus::Ignored => self.text_disabled,
+            GitFileStatus::SubmoduleModified => self.git_modified,
+            GitFileStatus::SubmoduleUninitialized => self.text_tertiary,
             GitFileStatus::Unknown => self.text_disabled,
         }
     }
@@ -170,6 +324,9 @@ impl CyberpunkTheme {
             GitSection::Staged => self.git_staged,
             GitSection::Unstaged => self.git_unstaged,
             GitSection::Untracked => self.git_untracked,
+            GitSection::Submodules => self.diff_hunk_header,
+            GitSection::Ignored => self.text_disabled,
+            GitSection::Stashes => self.neon_cyan,
         }
     }
 
@@ -206,6 +363,33 @@ impl CyberpunkTheme {
     }
 }
 
+/// Path of a theme file named by the `theme` option
+///
+/// Bare names are looked up in `~/.amux/themes`; paths may start with `~`.
+fn theme_path(name: &str) -> PathBuf {
+    let home = || dirs::home_dir().unwrap_or_default();
+    if let Some(rest) = name.strip_prefix("~/") {
+        home().join(rest)
+    } else if name.contains('/') || name.ends_with(".toml") {
+        PathBuf::from(name)
+    } else {
+        home()
+            .join(".amux")
+            .join("themes")
+            .join(format!("{}.toml", name))
+    }
+}
+
+/// Parse a `#rrggbb` color
+fn parse_hex(hex: &str) -> Option<Color> {
+    let digits = hex.strip_prefix('#')?;
+    if digits.len() != 6 || !digits.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).ok();
+    Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
 /// Git file status for color mapping
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GitFileStatus {
@@ -214,6 +398,9 @@ pub enum GitFileStatus {
     Deleted,
     Renamed,
     Untracked,
+    Ignored,
+    SubmoduleModified,
+    SubmoduleUninitialized,
     Unknown,
 }
 
@@ -223,6 +410,9 @@ pub enum GitSection {
     Staged,
     Unstaged,
     Untracked,
+    Submodules,
+    Ignored,
+    Stashes,
 }
 
 /// Terminal mode for border color
@@ -238,8 +428,48 @@ mod tests {
 
     #[test]
     fn test_theme_default() {
-        let theme = CyberpunkTheme::default();
+        let theme = Theme::default();
         // Catppuccin Mocha Teal #94e2d5
         assert_eq!(theme.neon_cyan, Color::Rgb(148, 226, 213));
+        assert_eq!(Theme::load("light").unwrap(), Theme::light());
+    }
+
+    #[test]
+    fn test_theme_from_toml() {
+        let theme = Theme::from_toml(
+            r##"
+            base = "light"
+            focus_border = "#FF0080"
+            syntax_theme = "Solarized (light)"
+            "##,
+        )
+        .unwrap();
+        assert_eq!(theme.focus_border, Color::Rgb(255, 0, 128));
+        assert_eq!(theme.syntax_theme, "Solarized (light)");
+        assert_eq!(theme.bg_level0, Theme::light().bg_level0);
+
+        assert_eq!(Theme::from_toml("").unwrap(), Theme::dark());
+        assert!(Theme::from_toml(r##"focus = "#ffffff""##).is_err());
+        assert!(Theme::from_toml(r#"error = "red""#).is_err());
+        assert!(Theme::from_toml(r#"base = "sepia""#).is_err());
+    }
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(parse_hex("#94e2d5"), Some(Color::Rgb(148, 226, 213)));
+        assert_eq!(parse_hex("94e2d5"), None);
+        assert_eq!(parse_hex("#94e2d"), None);
+        assert_eq!(parse_hex("#94e2dg"), None);
+    }
+
+    #[test]
+    fn test_theme_path() {
+        let home = dirs::home_dir().unwrap_or_default();
+        assert_eq!(
+            theme_path("solarized"),
+            home.join(".amux/themes/solarized.toml")
+        );
+        assert_eq!(theme_path("~/mine.toml"), home.join("mine.toml"));
+        assert_eq!(theme_path("./mine.toml"), PathBuf::from("./mine.toml"));
     }
 }
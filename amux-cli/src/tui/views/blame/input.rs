@@ -0,0 +1,50 @@
+//! Blame overlay input handling
+
+use crate::tui::app::App;
+use crate::tui::input::resolver;
+use crate::tui::state::AsyncAction;
+use amux_config::Action;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Lines moved by PageUp/PageDown and Ctrl-u/Ctrl-d
+const PAGE_LINES: isize = 20;
+
+/// Handle input in the blame overlay (navigated with the diff context keys)
+pub fn handle_blame_popup_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    if let Some(pattern_str) = resolver::key_event_to_pattern_string(key) {
+        if let Some(action) = app
+            .keybinds
+            .resolve(&pattern_str, amux_config::BindingContext::Diff)
+        {
+            match action {
+                Action::MoveDown => {
+                    app.blame.move_by(1);
+                    return None;
+                }
+                Action::MoveUp => {
+                    app.blame.move_by(-1);
+                    return None;
+                }
+                Action::ToggleBlame | Action::BackToTerminal | Action::ClosePopup => {
+                    app.close_blame();
+                    return None;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Fallback for keys not in the diff context
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.close_blame(),
+        KeyCode::Char('g') | KeyCode::Home => app.blame.move_by(isize::MIN),
+        KeyCode::Char('G') | KeyCode::End => app.blame.move_by(isize::MAX),
+        KeyCode::PageDown => app.blame.move_by(PAGE_LINES),
+        KeyCode::PageUp => app.blame.move_by(-PAGE_LINES),
+        KeyCode::Char('d') if ctrl => app.blame.move_by(PAGE_LINES),
+        KeyCode::Char('u') if ctrl => app.blame.move_by(-PAGE_LINES),
+        _ => {}
+    }
+    None
+}
@@ -0,0 +1,7 @@
+//! Blame overlay - who last touched each line of a diff file
+
+pub mod input;
+pub mod render;
+
+pub use input::handle_blame_popup_sync;
+pub use render::draw_blame_popup;
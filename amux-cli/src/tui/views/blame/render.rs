@@ -0,0 +1,152 @@
+//! Blame overlay rendering
+
+use crate::tui::app::App;
+use crate::tui::widgets::helpers::fit_to_width;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// Characters of a commit SHA shown per line
+const SHORT_SHA: usize = 8;
+/// Widest author column
+const MAX_AUTHOR_WIDTH: usize = 16;
+
+/// Draw the blame of a file, one line per file line prefixed with SHA and author
+pub fn draw_blame_popup(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let blame = &app.blame;
+
+    let popup_width = (area.width * 90) / 100;
+    let popup_height = (area.height * 85) / 100;
+    let popup_area = Rect::new(
+        area.x + (area.width - popup_width) / 2,
+        area.y + (area.height - popup_height) / 2,
+        popup_width,
+        popup_height,
+    );
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.neon_cyan).bg(theme.bg_level0))
+        .style(Style::default().bg(theme.bg_level0))
+        .title(format!(
+            " Blame: {} @ {} (line {}/{}, Esc=close) ",
+            blame.file_path,
+            short_sha(&blame.head),
+            blame.cursor + 1,
+            blame.lines.len()
+        ));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),    // Lines
+            Constraint::Length(1), // Selected commit
+        ])
+        .split(inner);
+
+    if blame.lines.is_empty() {
+        let placeholder = Paragraph::new("Empty file")
+            .style(Style::default().fg(theme.text_disabled))
+            .alignment(ratatui::layout::Alignment::Center);
+        f.render_widget(placeholder, chunks[0]);
+        return;
+    }
+
+    // Keep the selection in the middle of the view
+    let visible = chunks[0].height as usize;
+    let offset = blame
+        .cursor
+        .saturating_sub(visible / 2)
+        .min(blame.lines.len().saturating_sub(visible));
+
+    let author_width = blame
+        .lines
+        .iter()
+        .map(|l| l.author.width())
+        .max()
+        .unwrap_or(0)
+        .min(MAX_AUTHOR_WIDTH);
+    let number_width = blame.lines.len().to_string().len();
+
+    let lines: Vec<Line> = blame
+        .lines
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(idx, line)| {
+            // Only the first line of a run from the same commit shows SHA and author
+            let repeated = idx > 0 && blame.lines[idx - 1].commit_id == line.commit_id;
+            let (sha, author) = if repeated {
+                (String::new(), " ".repeat(author_width))
+            } else {
+                (
+                    short_sha(&line.commit_id),
+                    fit_to_width(&line.author, author_width),
+                )
+            };
+
+            let mut spans = vec![
+                Span::styled(
+                    format!("{:<width$} ", sha, width = SHORT_SHA),
+                    Style::default().fg(theme.neon_yellow),
+                ),
+                Span::styled(
+                    // Already padded to the column's display width
+                    format!("{} ", author),
+                    Style::default().fg(theme.neon_cyan),
+                ),
+                Span::styled(
+                    format!("{:>width$} ", idx + 1, width = number_width),
+                    Style::default().fg(theme.text_tertiary),
+                ),
+                Span::styled(
+                    line.content.clone(),
+                    Style::default().fg(theme.text_primary),
+                ),
+            ];
+            if idx == blame.cursor {
+                for span in &mut spans {
+                    span.style = span.style.patch(theme.selection_style());
+                }
+            }
+            Line::from(spans)
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), chunks[0]);
+
+    if let Some(line) = blame.lines.get(blame.cursor) {
+        let summary = if line.commit_id.is_empty() {
+            "Not committed".to_string()
+        } else {
+            format!(
+                "{} {}: {}",
+                short_sha(&line.commit_id),
+                line.author,
+                line.summary
+            )
+        };
+        f.render_widget(
+            Paragraph::new(summary).style(
+                Style::default()
+                    .fg(theme.text_secondary)
+                    .add_modifier(Modifier::ITALIC),
+            ),
+            chunks[1],
+        );
+    }
+}
+
+fn short_sha(sha: &str) -> String {
+    sha.chars().take(SHORT_SHA).collect()
+}
@@ -0,0 +1,73 @@
+//! Conflict view input handling
+
+use crate::tui::app::App;
+use crate::tui::input::resolver;
+use crate::tui::state::AsyncAction;
+use amux_config::Action;
+use amux_proto::daemon::ConflictResolution;
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// Handle input in Conflicts mode
+pub fn handle_conflicts_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAction> {
+    if let Some(pattern_str) = resolver::key_event_to_pattern_string(key) {
+        if let Some(action) = app
+            .keybinds
+            .resolve(&pattern_str, amux_config::BindingContext::Conflicts)
+        {
+            return execute_conflicts_action(app, action);
+        }
+    }
+
+    match key.code {
+        KeyCode::Esc => {
+            app.close_conflicts_view();
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Execute a conflict view action
+fn execute_conflicts_action(app: &mut App, action: Action) -> Option<AsyncAction> {
+    match action {
+        Action::MoveUp => {
+            app.conflict_move(false);
+            None
+        }
+
+        Action::MoveDown => {
+            app.conflict_move(true);
+            None
+        }
+
+        Action::PrevFile => app.conflict_switch_file(-1),
+
+        Action::NextFile => app.conflict_switch_file(1),
+
+        Action::ResolveOurs => {
+            app.choose_conflict_resolution(ConflictResolution::Ours);
+            None
+        }
+
+        Action::ResolveTheirs => {
+            app.choose_conflict_resolution(ConflictResolution::Theirs);
+            None
+        }
+
+        Action::ResolveBoth => {
+            app.choose_conflict_resolution(ConflictResolution::Both);
+            None
+        }
+
+        Action::WriteResolution => Some(AsyncAction::WriteConflictResolution),
+
+        Action::RefreshDiff => Some(AsyncAction::ShowConflicts),
+
+        Action::BackToTerminal | Action::ClosePopup => {
+            app.close_conflicts_view();
+            None
+        }
+
+        _ => None,
+    }
+}
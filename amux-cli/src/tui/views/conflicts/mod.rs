@@ -0,0 +1,7 @@
+//! Conflict view - pick ours/theirs/both per merge conflict hunk
+
+pub mod input;
+pub mod render;
+
+pub use input::handle_conflicts_mode_sync;
+pub use render::draw_conflicts_view;
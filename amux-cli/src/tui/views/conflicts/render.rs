@@ -0,0 +1,143 @@
+//! Conflict view rendering
+
+use crate::tui::app::App;
+use crate::tui::state::Focus;
+use amux_proto::daemon::ConflictResolution;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph},
+    Frame,
+};
+
+/// Draw the conflict hunks of the shown file
+pub fn draw_conflicts_view(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let is_focused = app.focus == Focus::Conflicts;
+
+    let border_style = if is_focused {
+        theme.focused_border_style()
+    } else {
+        theme.unfocused_border_style()
+    };
+
+    let title = match app
+        .conflicts()
+        .and_then(|c| c.current_file().map(|f| (c, f)))
+    {
+        Some((conflicts, file)) => format!(
+            " {} Conflicts: {} (file {}/{}, unresolved {}/{}){} ",
+            app.icons.git_conflicted(),
+            file.path,
+            conflicts.file_idx + 1,
+            conflicts.files.len(),
+            conflicts.unresolved(),
+            conflicts.choices.len(),
+            if is_focused { " [*]" } else { "" }
+        ),
+        None => " Conflicts ".to_string(),
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(border_style)
+        .title(title);
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let Some(conflicts) = app.conflicts().filter(|c| !c.regions.is_empty()) else {
+        let placeholder = Paragraph::new("No conflicts")
+            .style(Style::default().fg(theme.text_disabled))
+            .alignment(ratatui::layout::Alignment::Center);
+        f.render_widget(placeholder, inner);
+        return;
+    };
+
+    let mut lines: Vec<Line> = Vec::new();
+    let mut selected_line = 0;
+    let mut hunk_idx = 0;
+
+    for region in &conflicts.regions {
+        if !region.is_conflict {
+            lines.extend(region.lines.iter().map(|text| {
+                Line::from(Span::styled(
+                    format!("  {}", text),
+                    Style::default().fg(theme.text_secondary),
+                ))
+            }));
+            continue;
+        }
+
+        let is_selected = hunk_idx == conflicts.selected;
+        let choice = conflicts.choices.get(hunk_idx).copied().flatten();
+        if is_selected {
+            selected_line = lines.len();
+        }
+
+        let gutter = if is_selected { "▌ " } else { "  " };
+        let marker_style = if is_selected && is_focused {
+            theme.selection_style()
+        } else if is_selected {
+            theme.selection_unfocused_style()
+        } else {
+            Style::default().fg(theme.text_tertiary)
+        };
+        let marker = |text: String, tag: Option<Span<'static>>| {
+            let mut spans = vec![Span::styled(format!("{}{}", gutter, text), marker_style)];
+            spans.extend(tag);
+            Line::from(spans)
+        };
+        let side = |text: &String, color: Color, kept: bool| {
+            let style = if kept {
+                Style::default().fg(color)
+            } else {
+                Style::default().fg(theme.text_disabled)
+            };
+            Line::from(Span::styled(format!("{}{}", gutter, text), style))
+        };
+
+        let tag = match choice {
+            Some(ConflictResolution::Ours) => Span::styled(" [ours]", theme.diff_add_style()),
+            Some(ConflictResolution::Theirs) => Span::styled(" [theirs]", theme.diff_add_style()),
+            Some(ConflictResolution::Both) => Span::styled(" [both]", theme.diff_add_style()),
+            _ => Span::styled(" [unresolved]", Style::default().fg(theme.warning)),
+        };
+        let keep_ours = !matches!(choice, Some(ConflictResolution::Theirs));
+        let keep_theirs = !matches!(choice, Some(ConflictResolution::Ours));
+
+        lines.push(marker(format!("<<<<<<< {}", region.ours_label), Some(tag)));
+        lines.extend(
+            region
+                .ours
+                .iter()
+                .map(|text| side(text, theme.neon_cyan, keep_ours)),
+        );
+        lines.push(marker("=======".to_string(), None));
+        lines.extend(
+            region
+                .theirs
+                .iter()
+                .map(|text| side(text, theme.neon_magenta, keep_theirs)),
+        );
+        lines.push(marker(format!(">>>>>>> {}", region.theirs_label), None));
+
+        hunk_idx += 1;
+    }
+
+    // Keep the selected hunk a third of the way down the view
+    let visible_height = inner.height as usize;
+    let total_lines = lines.len();
+    let scroll_offset = selected_line
+        .saturating_sub(visible_height / 3)
+        .min(total_lines.saturating_sub(visible_height));
+
+    let visible_lines: Vec<Line> = lines
+        .into_iter()
+        .skip(scroll_offset)
+        .take(visible_height)
+        .collect();
+    f.render_widget(Paragraph::new(visible_lines), inner);
+}
@@ -0,0 +1,265 @@
+//! Collapsing unchanged lines of a file diff
+//!
+//! Context runs longer than the configured limit are replaced by a
+//! `LINE_TYPE_COLLAPSED` row, keeping a few lines next to each change, and
+//! the unchanged lines between hunks get such a row too. Expanding a row
+//! fetches its lines from the daemon; they are not kept client-side.
+
+use amux_proto::daemon::{DiffLine, LineType};
+use std::collections::HashSet;
+
+/// Context lines kept next to a change when the rest of a run is collapsed
+const KEEP_LINES: usize = 3;
+
+/// Whether `line` stands for hidden unchanged lines
+pub fn is_collapsed(line: &DiffLine) -> bool {
+    line.line_type == LineType::Collapsed as i32
+}
+
+/// Collapse context runs longer than `max_run` lines and the gaps between hunks
+///
+/// Runs holding a line in `commented` stay expanded. `max_run` 0 returns the
+/// lines as they are.
+pub fn collapse_context(
+    lines: Vec<DiffLine>,
+    max_run: usize,
+    commented: &HashSet<i32>,
+) -> Vec<DiffLine> {
+    if max_run == 0 {
+        return lines;
+    }
+
+    let mut out = Vec::with_capacity(lines.len());
+    let mut last_new = 0;
+    let mut i = 0;
+    while i < lines.len() {
+        let line_type = LineType::try_from(lines[i].line_type).unwrap_or(LineType::Context);
+        match line_type {
+            LineType::Header => {
+                if let Some((old_first, new_first)) = hunk_start(&lines[i].content) {
+                    let gap = new_first - last_new - 1;
+                    if gap > 0 {
+                        out.push(collapsed_row(old_first - gap, new_first - gap, gap));
+                    }
+                }
+                out.push(lines[i].clone());
+                i += 1;
+            }
+            LineType::Context => {
+                let end = lines[i..]
+                    .iter()
+                    .position(|l| l.line_type != LineType::Context as i32)
+                    .map_or(lines.len(), |len| i + len);
+                let run = &lines[i..end];
+                let top = if i > 0 && is_change(&lines[i - 1]) {
+                    KEEP_LINES
+                } else {
+                    0
+                };
+                let bottom = if end < lines.len() && is_change(&lines[end]) {
+                    KEEP_LINES
+                } else {
+                    0
+                };
+                let has_comment = run.iter().any(|l| {
+                    l.new_lineno
+                        .or(l.old_lineno)
+                        .is_some_and(|n| commented.contains(&n))
+                });
+
+                // Hiding a single line would save nothing
+                if run.len() > max_run && run.len() >= top + bottom + 2 && !has_comment {
+                    let hidden = &run[top..run.len() - bottom];
+                    out.extend_from_slice(&run[..top]);
+                    out.push(collapsed_row(
+                        hidden[0].old_lineno.unwrap_or(0),
+                        hidden[0].new_lineno.unwrap_or(0),
+                        hidden.len() as i32,
+                    ));
+                    out.extend_from_slice(&run[run.len() - bottom..]);
+                } else {
+                    out.extend_from_slice(run);
+                }
+                if let Some(n) = run.last().and_then(|l| l.new_lineno) {
+                    last_new = n;
+                }
+                i = end;
+            }
+            _ => {
+                if let Some(n) = lines[i].new_lineno {
+                    last_new = n;
+                }
+                out.push(lines[i].clone());
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Context lines replacing a collapsed row, from the file's new-side `contents`
+pub fn expand(row: &DiffLine, contents: Vec<String>) -> Vec<DiffLine> {
+    let old_start = row.old_lineno.unwrap_or(0);
+    let new_start = row.new_lineno.unwrap_or(0);
+    contents
+        .into_iter()
+        .enumerate()
+        .map(|(offset, content)| DiffLine {
+            line_type: LineType::Context as i32,
+            content,
+            old_lineno: Some(old_start + offset as i32),
+            new_lineno: Some(new_start + offset as i32),
+            hidden_lines: 0,
+        })
+        .collect()
+}
+
+/// Whether any line hidden by `row` is in `commented`
+pub fn hides_comment(row: &DiffLine, commented: &HashSet<i32>) -> bool {
+    let start = row.new_lineno.unwrap_or(0);
+    (start..start + row.hidden_lines).any(|n| commented.contains(&n))
+}
+
+fn collapsed_row(old_start: i32, new_start: i32, count: i32) -> DiffLine {
+    DiffLine {
+        line_type: LineType::Collapsed as i32,
+        content: String::new(),
+        old_lineno: Some(old_start),
+        new_lineno: Some(new_start),
+        hidden_lines: count,
+    }
+}
+
+fn is_change(line: &DiffLine) -> bool {
+    line.line_type == LineType::Addition as i32 || line.line_type == LineType::Deletion as i32
+}
+
+/// First old and new line of a hunk from its `@@ -a,b +c,d @@` header
+///
+/// An empty side (`,0`) starts after the line it names.
+fn hunk_start(header: &str) -> Option<(i32, i32)> {
+    let mut ranges = header.strip_prefix("@@ ")?.split_whitespace();
+    let side = |range: &str| -> Option<i32> {
+        let (start, len) = range.split_once(',').unwrap_or((range, "1"));
+        let start: i32 = start.parse().ok()?;
+        Some(if len == "0" { start + 1 } else { start })
+    };
+    let old = side(ranges.next()?.strip_prefix('-')?)?;
+    let new = side(ranges.next()?.strip_prefix('+')?)?;
+    Some((old, new))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(content: &str) -> DiffLine {
+        DiffLine {
+            line_type: LineType::Header as i32,
+            content: content.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn context(old: i32, new: i32) -> DiffLine {
+        DiffLine {
+            line_type: LineType::Context as i32,
+            content: format!("line {}", new),
+            old_lineno: Some(old),
+            new_lineno: Some(new),
+            hidden_lines: 0,
+        }
+    }
+
+    fn added(new: i32) -> DiffLine {
+        DiffLine {
+            line_type: LineType::Addition as i32,
+            content: "added".to_string(),
+            new_lineno: Some(new),
+            ..Default::default()
+        }
+    }
+
+    /// Hunk `@@ -11,21 +11,22 @@`: 10 context lines, an addition, 11 more
+    fn hunk() -> Vec<DiffLine> {
+        let mut lines = vec![header("@@ -11,21 +11,22 @@ fn main")];
+        lines.extend((11..21).map(|n| context(n, n)));
+        lines.push(added(21));
+        lines.extend((21..32).map(|n| context(n, n + 1)));
+        lines
+    }
+
+    #[test]
+    fn test_collapse_context() {
+        let lines = collapse_context(hunk(), 8, &HashSet::new());
+        // Gap before the hunk
+        assert!(is_collapsed(&lines[0]));
+        assert_eq!(
+            (
+                lines[0].old_lineno,
+                lines[0].new_lineno,
+                lines[0].hidden_lines
+            ),
+            (Some(1), Some(1), 10)
+        );
+        assert_eq!(lines[1].line_type, LineType::Header as i32);
+        // Leading run keeps the 3 lines before the change
+        assert!(is_collapsed(&lines[2]));
+        assert_eq!((lines[2].new_lineno, lines[2].hidden_lines), (Some(11), 7));
+        assert_eq!(lines[3].new_lineno, Some(18));
+        assert_eq!(lines[6].line_type, LineType::Addition as i32);
+        // Trailing run keeps the 3 lines after it
+        assert_eq!(lines[9].new_lineno, Some(24));
+        assert!(is_collapsed(&lines[10]));
+        assert_eq!(
+            (
+                lines[10].old_lineno,
+                lines[10].new_lineno,
+                lines[10].hidden_lines
+            ),
+            (Some(24), Some(25), 8)
+        );
+        assert_eq!(lines.len(), 11);
+
+        assert_eq!(collapse_context(hunk(), 0, &HashSet::new()), hunk());
+        assert_eq!(
+            collapse_context(hunk(), 11, &HashSet::new()).len(),
+            hunk().len() + 1
+        );
+    }
+
+    #[test]
+    fn test_commented_runs_stay_expanded() {
+        let lines = collapse_context(hunk(), 8, &HashSet::from([13]));
+        assert_eq!(lines.iter().filter(|l| is_collapsed(l)).count(), 2);
+        assert!(hides_comment(&lines[0], &HashSet::from([5])));
+        assert!(!hides_comment(&lines[0], &HashSet::from([11])));
+    }
+
+    #[test]
+    fn test_expand() {
+        let row = collapsed_row(24, 25, 2);
+        let lines = expand(&row, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(
+            lines,
+            vec![
+                DiffLine {
+                    content: "a".to_string(),
+                    ..context(24, 25)
+                },
+                DiffLine {
+                    content: "b".to_string(),
+                    ..context(25, 26)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hunk_start() {
+        assert_eq!(hunk_start("@@ -11,21 +11,22 @@ fn main"), Some((11, 11)));
+        assert_eq!(hunk_start("@@ -5 +5,0 @@"), Some((5, 6)));
+        assert_eq!(hunk_start("@@ -0,0 +1,3 @@ (new file)"), Some((1, 1)));
+        assert_eq!(hunk_start("not a header"), None);
+    }
+}
@@ -67,6 +67,17 @@ fn execute_diff_action(app: &mut App, action: Action) -> Option<AsyncAction> {
 
         Action::DeleteComment => Some(AsyncAction::DeleteLineComment),
 
+        Action::Undo => Some(AsyncAction::Undo),
+
+        // n/N go through search matches while searching, comments otherwise
+        Action::NextComment if app.diff_search_active() => {
+            Some(AsyncAction::DiffSearch { forward: true })
+        }
+
+        Action::PrevComment if app.diff_search_active() => {
+            Some(AsyncAction::DiffSearch { forward: false })
+        }
+
         Action::NextComment => {
             app.jump_to_next_comment();
             None
@@ -77,10 +88,24 @@ fn execute_diff_action(app: &mut App, action: Action) -> Option<AsyncAction> {
             None
         }
 
+        Action::SearchInDiff => app.start_diff_search(),
+
         Action::SubmitReviewClaude => Some(AsyncAction::SubmitReviewToClaude),
+        Action::ShowReviews => Some(AsyncAction::ShowReviews),
 
         Action::RefreshDiff => Some(AsyncAction::LoadDiffFiles),
 
+        Action::SelectDiffBase => Some(AsyncAction::StartSelectDiffBase),
+
+        Action::DiffAgainstBase => Some(AsyncAction::ToggleDiffMode),
+
+        Action::ToggleBlame => Some(AsyncAction::ShowBlame),
+
+        Action::OpenInEditor => {
+            app.open_in_editor();
+            None
+        }
+
         Action::ToggleFullscreen => {
             app.toggle_diff_fullscreen();
             None
@@ -1,5 +1,6 @@
 //! Diff view - file diffs with word-level highlighting
 
+pub mod collapse;
 pub mod input;
 pub mod render;
 
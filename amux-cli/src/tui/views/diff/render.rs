@@ -1,13 +1,16 @@
 //! Diff view rendering
 
 use crate::tui::app::App;
+use crate::tui::hyperlink::underline_urls;
 use crate::tui::icons::box_drawing;
-use crate::tui::state::{DiffItem, Focus};
+use crate::tui::state::{DiffItem, DiffState, DiffViewMode, Focus, InputMode};
 use crate::tui::theme::GitFileStatus;
 use crate::tui::widgets::helpers::{
-    find_paired_addition, find_paired_deletion, get_highlighter, render_word_diff_line,
+    find_paired_addition, find_paired_deletion, get_highlighter, highlight_matches,
+    render_word_diff_line, truncate_start_to_width, wrap_to_width,
 };
-use amux_proto::daemon::{FileStatus, LineType};
+use amux_config::FILE_COMMENT_LINE;
+use amux_proto::daemon::{FileStatus, LineCommentInfo, LineType};
 use ratatui::{
     layout::Rect,
     style::{Modifier, Style},
@@ -38,14 +41,42 @@ pub fn draw_diff_inline(f: &mut Frame, area: Rect, app: &App) {
         theme.unfocused_border_style()
     };
 
+    let label = match app.diff() {
+        Some(DiffState {
+            revision: Some(revision),
+            ..
+        }) => format!("Stash {:.7}", revision),
+        Some(d) if d.mode == DiffViewMode::Branch => format!(
+            "Changes [vs {}]",
+            d.branch_base_used.as_deref().unwrap_or("base")
+        ),
+        Some(DiffState {
+            base_ref: Some(base_ref),
+            ..
+        }) => format!("Changes vs {} [Working Tree]", base_ref),
+        _ => "Changes [Working Tree]".to_string(),
+    };
+    let search = app
+        .diff()
+        .and_then(|d| d.search.as_ref())
+        .map(|s| s.query.as_str());
+    let label = match (search, app.diff_search_count()) {
+        (Some(query), Some((Some(nth), total))) => {
+            format!("{} [/{} {}/{}]", label, query, nth, total)
+        }
+        (Some(query), Some((None, total))) => format!("{} [/{} {}]", label, query, total),
+        _ => label,
+    };
     let title = if is_focused {
         format!(
-            " Changes ({}) [*] ",
+            " {} ({}) [*] ",
+            label,
             app.diff().map(|d| d.files.len()).unwrap_or(0)
         )
     } else {
         format!(
-            " Changes ({}) ",
+            " {} ({}) ",
+            label,
             app.diff().map(|d| d.files.len()).unwrap_or(0)
         )
     };
@@ -56,9 +87,23 @@ pub fn draw_diff_inline(f: &mut Frame, area: Rect, app: &App) {
         .border_style(border_style)
         .title(title);
 
-    let inner = block.inner(area);
+    // Search matches stand out on top of the word diff and syntax colors
+    let match_style = Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD);
+    let highlight = |spans: Vec<Span<'static>>| match search {
+        Some(query) => highlight_matches(spans, query, match_style),
+        None => spans,
+    };
+
+    let mut inner = block.inner(area);
     f.render_widget(block, area);
 
+    // The search bar takes the bottom row while it is open
+    if matches!(app.input_mode, InputMode::SearchDiff { .. }) && inner.height > 1 {
+        inner.height -= 1;
+        let bar = Rect::new(inner.x, inner.y + inner.height, inner.width, 1);
+        draw_search_bar(f, app, bar);
+    }
+
     let Some(diff) = app.diff() else {
         let placeholder = Paragraph::new("No diff state")
             .style(Style::default().fg(theme.text_disabled))
@@ -101,6 +146,9 @@ pub fn draw_diff_inline(f: &mut Frame, area: Rect, app: &App) {
             FileStatus::Deleted => GitFileStatus::Deleted,
             FileStatus::Renamed => GitFileStatus::Renamed,
             FileStatus::Untracked => GitFileStatus::Untracked,
+            FileStatus::Ignored => GitFileStatus::Ignored,
+            FileStatus::SubmoduleModified => GitFileStatus::SubmoduleModified,
+            FileStatus::SubmoduleUninitialized => GitFileStatus::SubmoduleUninitialized,
             FileStatus::Unspecified => GitFileStatus::Unknown,
         };
 
@@ -110,9 +158,11 @@ pub fn draw_diff_inline(f: &mut Frame, area: Rect, app: &App) {
             GitFileStatus::Deleted => icons.git_deleted(),
             GitFileStatus::Renamed => icons.git_renamed(),
             GitFileStatus::Untracked => icons.git_untracked(),
+            GitFileStatus::Ignored => icons.git_ignored(),
+            GitFileStatus::SubmoduleModified => icons.git_submodule_modified(),
+            GitFileStatus::SubmoduleUninitialized => icons.git_submodule_uninitialized(),
             GitFileStatus::Unknown => "?",
         };
-
         let status_color = theme.git_status_color(file_status);
 
         // Expand/collapse indicator
@@ -165,6 +215,11 @@ pub fn draw_diff_inline(f: &mut Frame, area: Rect, app: &App) {
             comment_badge,
         ]));
 
+        // File-level comment sits under the file header
+        if let Some(comment) = app.get_line_comment(&file.path, FILE_COMMENT_LINE) {
+            lines.extend(comment_box_lines(app, &file.path, comment));
+        }
+
         // If this file is expanded, show diff lines
         if is_expanded {
             if let Some(file_lines) = diff.file_lines.get(&file_idx) {
@@ -178,7 +233,9 @@ pub fn draw_diff_inline(f: &mut Frame, area: Rect, app: &App) {
                     let line_number = diff_line
                         .new_lineno
                         .unwrap_or(diff_line.old_lineno.unwrap_or(line_idx as i32));
-                    let line_comment = app.get_line_comment(&file.path, line_number);
+                    let line_comment = app
+                        .get_line_comment(&file.path, line_number)
+                        .filter(|_| line_type != LineType::Collapsed);
                     let comment_marker = if line_comment.is_some() {
                         Span::styled(
                             " [*]",
@@ -229,6 +286,7 @@ pub fn draw_diff_inline(f: &mut Frame, area: Rect, app: &App) {
 
                             // Add word-diff highlighted content
                             let content_spans = render_word_diff_line(
+                                theme,
                                 &diff_line.content,
                                 paired_content,
                                 true,
@@ -236,7 +294,7 @@ pub fn draw_diff_inline(f: &mut Frame, area: Rect, app: &App) {
                                 is_focused,
                                 &file.path,
                             );
-                            line_spans.extend(content_spans);
+                            line_spans.extend(highlight(content_spans));
                         }
                         LineType::Deletion => {
                             // Find paired addition for word-level diff
@@ -252,6 +310,7 @@ pub fn draw_diff_inline(f: &mut Frame, area: Rect, app: &App) {
 
                             // Add word-diff highlighted content
                             let content_spans = render_word_diff_line(
+                                theme,
                                 &diff_line.content,
                                 paired_content,
                                 false,
@@ -259,7 +318,25 @@ pub fn draw_diff_inline(f: &mut Frame, area: Rect, app: &App) {
                                 is_focused,
                                 &file.path,
                             );
-                            line_spans.extend(content_spans);
+                            line_spans.extend(highlight(content_spans));
+                        }
+                        LineType::Collapsed => {
+                            let style = if is_line_selected && is_focused {
+                                Style::default()
+                                    .fg(theme.text_tertiary)
+                                    .add_modifier(Modifier::REVERSED)
+                            } else {
+                                Style::default().fg(theme.text_tertiary)
+                            };
+                            let count = diff_line.hidden_lines;
+                            line_spans.push(Span::styled(
+                                format!(
+                                    "  … {} unchanged line{} …",
+                                    count,
+                                    if count == 1 { "" } else { "s" }
+                                ),
+                                style,
+                            ));
                         }
                         LineType::Context | LineType::Unspecified => {
                             line_spans.push(Span::styled("  ", Style::default()));
@@ -267,83 +344,30 @@ pub fn draw_diff_inline(f: &mut Frame, area: Rect, app: &App) {
                             let highlighter = get_highlighter();
                             let syntax_spans =
                                 highlighter.highlight_line(&diff_line.content, &file.path);
-                            for (style, text) in syntax_spans {
-                                let final_style = if is_line_selected && is_focused {
-                                    style.add_modifier(Modifier::REVERSED)
-                                } else {
-                                    style
-                                };
-                                line_spans.push(Span::styled(text.to_string(), final_style));
-                            }
+                            let content_spans = syntax_spans
+                                .into_iter()
+                                .map(|(style, text)| {
+                                    let final_style = if is_line_selected && is_focused {
+                                        style.add_modifier(Modifier::REVERSED)
+                                    } else {
+                                        style
+                                    };
+                                    Span::styled(text.to_string(), final_style)
+                                })
+                                .collect();
+                            line_spans.extend(highlight(content_spans));
                         }
                     }
 
+                    if app.config.options.open_links {
+                        line_spans = underline_urls(line_spans);
+                    }
                     line_spans.push(comment_marker);
                     lines.push(Line::from(line_spans));
 
                     // If line has a comment, show comment box below
                     if let Some(comment) = line_comment {
-                        // Truncate file path for display
-                        let display_path = if file.path.len() > 30 {
-                            format!("...{}", &file.path[file.path.len() - 27..])
-                        } else {
-                            file.path.clone()
-                        };
-
-                        // Comment box top border with file info
-                        lines.push(Line::from(vec![
-                            Span::raw("     "),
-                            Span::styled(
-                                format!(
-                                    "{}{}[",
-                                    box_drawing::ROUND_TOP_LEFT,
-                                    box_drawing::HORIZONTAL
-                                ),
-                                Style::default().fg(theme.comment_border),
-                            ),
-                            Span::styled(display_path, Style::default().fg(theme.comment_path)),
-                            Span::styled(":", Style::default().fg(theme.comment_border)),
-                            Span::styled(
-                                format!("{}", line_number),
-                                Style::default().fg(theme.comment_line_no),
-                            ),
-                            Span::styled(
-                                format!("]{}", box_drawing::HORIZONTAL),
-                                Style::default().fg(theme.comment_border),
-                            ),
-                        ]));
-
-                        // Comment content (wrap if needed)
-                        let comment_text = &comment.comment;
-                        let max_width = 50;
-                        for chunk in comment_text
-                            .chars()
-                            .collect::<Vec<_>>()
-                            .chunks(max_width)
-                            .map(|c| c.iter().collect::<String>())
-                        {
-                            lines.push(Line::from(vec![
-                                Span::raw("     "),
-                                Span::styled(
-                                    format!("{} ", box_drawing::VERTICAL),
-                                    Style::default().fg(theme.comment_border),
-                                ),
-                                Span::styled(chunk, Style::default().fg(theme.text_primary)),
-                            ]));
-                        }
-
-                        // Comment box bottom border
-                        lines.push(Line::from(vec![
-                            Span::raw("     "),
-                            Span::styled(
-                                format!(
-                                    "{}{}",
-                                    box_drawing::ROUND_BOTTOM_LEFT,
-                                    box_drawing::HORIZONTAL.repeat(46)
-                                ),
-                                Style::default().fg(theme.comment_border),
-                            ),
-                        ]));
+                        lines.extend(comment_box_lines(app, &file.path, comment));
                     }
                 }
             }
@@ -354,16 +378,7 @@ pub fn draw_diff_inline(f: &mut Frame, area: Rect, app: &App) {
     let visible_height = inner.height as usize;
     let total_lines = lines.len();
     let cursor_line = diff.cursor;
-
-    // Calculate scroll offset to keep cursor visible
-    let scroll_offset = if cursor_line < diff.scroll_offset {
-        cursor_line
-    } else if cursor_line >= diff.scroll_offset + visible_height {
-        cursor_line.saturating_sub(visible_height / 2)
-    } else {
-        diff.scroll_offset
-    }
-    .min(total_lines.saturating_sub(visible_height));
+    let scroll_offset = scroll_offset(diff, visible_height, total_lines);
 
     // Render visible lines
     let visible_lines: Vec<Line> = lines
@@ -388,3 +403,153 @@ pub fn draw_diff_inline(f: &mut Frame, area: Rect, app: &App) {
         );
     }
 }
+
+/// The query being typed into the diff search bar, after a `/`
+fn draw_search_bar(f: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
+    let bar = Paragraph::new(Line::from(vec![
+        Span::styled("/", Style::default().fg(theme.neon_cyan)),
+        Span::styled(
+            app.text_input.content(),
+            Style::default().fg(theme.neon_yellow),
+        ),
+    ]))
+    .style(Style::default().bg(theme.bg_level0));
+    f.render_widget(bar, area);
+    f.set_cursor_position((
+        area.x + 1 + app.text_input.cursor_display_offset() as u16,
+        area.y,
+    ));
+}
+
+/// First drawn line that keeps the cursor visible
+fn scroll_offset(diff: &DiffState, visible_height: usize, total_lines: usize) -> usize {
+    let cursor_line = diff.cursor;
+    if cursor_line < diff.scroll_offset {
+        cursor_line
+    } else if cursor_line >= diff.scroll_offset + visible_height {
+        cursor_line.saturating_sub(visible_height / 2)
+    } else {
+        diff.scroll_offset
+    }
+    .min(total_lines.saturating_sub(visible_height))
+}
+
+/// Cursor position of the file or line drawn `row` lines below the top of a
+/// diff view `visible_height` lines tall
+///
+/// Rows of comment boxes have no position.
+pub fn position_at_row(app: &App, row: usize, visible_height: usize) -> Option<usize> {
+    let diff = app.diff()?;
+    let comment_rows = |path: &str, line_number: i32| match app.get_line_comment(path, line_number)
+    {
+        Some(comment) => comment_box_lines(app, path, comment).len(),
+        None => 0,
+    };
+
+    // Mirror the lines drawn by draw_diff_inline
+    let mut rows: Vec<Option<usize>> = Vec::new();
+    let mut pos = 0;
+    for (file_idx, file) in diff.files.iter().enumerate() {
+        rows.push(Some(pos));
+        pos += 1;
+        rows.extend(std::iter::repeat_n(
+            None,
+            comment_rows(&file.path, FILE_COMMENT_LINE),
+        ));
+
+        if !diff.expanded.contains(&file_idx) {
+            continue;
+        }
+        for (line_idx, line) in diff
+            .file_lines
+            .get(&file_idx)
+            .into_iter()
+            .flatten()
+            .enumerate()
+        {
+            rows.push(Some(pos));
+            pos += 1;
+            if line.line_type != LineType::Collapsed as i32 {
+                let line_number = line
+                    .new_lineno
+                    .unwrap_or(line.old_lineno.unwrap_or(line_idx as i32));
+                rows.extend(std::iter::repeat_n(
+                    None,
+                    comment_rows(&file.path, line_number),
+                ));
+            }
+        }
+    }
+
+    let offset = scroll_offset(diff, visible_height, rows.len());
+    rows.get(offset + row).copied().flatten()
+}
+
+/// Where a comment sits: its line number, or "file" for a file-level comment
+pub fn comment_location(line_number: i32) -> String {
+    if line_number == FILE_COMMENT_LINE {
+        "file".to_string()
+    } else {
+        line_number.to_string()
+    }
+}
+
+/// Comment box drawn below the commented line or file header
+fn comment_box_lines(app: &App, file_path: &str, comment: &LineCommentInfo) -> Vec<Line<'static>> {
+    let theme = &app.theme;
+    let mut lines = Vec::new();
+
+    // Truncate file path for display
+    let display_path = truncate_start_to_width(file_path, 30);
+
+    // Comment box top border with file info
+    lines.push(Line::from(vec![
+        Span::raw("     "),
+        Span::styled(
+            format!(
+                "{}{}[",
+                box_drawing::ROUND_TOP_LEFT,
+                box_drawing::HORIZONTAL
+            ),
+            Style::default().fg(theme.comment_border),
+        ),
+        Span::styled(display_path, Style::default().fg(theme.comment_path)),
+        Span::styled(":", Style::default().fg(theme.comment_border)),
+        Span::styled(
+            comment_location(comment.line_number),
+            Style::default().fg(theme.comment_line_no),
+        ),
+        Span::styled(
+            format!("]{}", box_drawing::HORIZONTAL),
+            Style::default().fg(theme.comment_border),
+        ),
+    ]));
+
+    // Comment content (wrap if needed)
+    for chunk in wrap_to_width(&comment.comment, 50) {
+        lines.push(Line::from(vec![
+            Span::raw("     "),
+            Span::styled(
+                format!("{} ", box_drawing::VERTICAL),
+                Style::default().fg(theme.comment_border),
+            ),
+            Span::styled(chunk, Style::default().fg(theme.text_primary)),
+        ]));
+    }
+
+    // Comment box bottom border
+    lines.push(Line::from(vec![
+        Span::raw("     "),
+        Span::styled(
+            format!(
+                "{}{}",
+                box_drawing::ROUND_BOTTOM_LEFT,
+                box_drawing::HORIZONTAL.repeat(46)
+            ),
+            Style::default().fg(theme.comment_border),
+        ),
+    ]));
+
+    lines
+}
@@ -2,7 +2,7 @@
 
 use crate::tui::app::App;
 use crate::tui::input::resolver;
-use crate::tui::state::{AsyncAction, Focus, RightPanelView};
+use crate::tui::state::{AsyncAction, Focus, GitSection, RightPanelView};
 use amux_config::Action;
 use crossterm::event::{KeyCode, KeyEvent};
 
@@ -31,6 +31,7 @@ pub fn handle_git_status_input_sync(app: &mut App, key: KeyEvent) -> Option<Asyn
             } else {
                 app.status_message = Some("Switching to Diff panel".to_string());
             }
+            app.show_worktree_diff();
             app.right_panel_view = RightPanelView::Diff;
             app.focus = Focus::DiffFiles;
             Some(AsyncAction::LoadDiffFiles)
@@ -72,6 +73,7 @@ fn execute_git_status_action(app: &mut App, action: Action) -> Option<AsyncActio
             // If on a file, open diff for that file
             if let Some(file_path) = app.current_git_file_path() {
                 app.status_message = Some(format!("Opening diff for: {}", file_path));
+                app.show_worktree_diff();
                 app.right_panel_view = RightPanelView::Diff;
                 app.focus = Focus::DiffFiles;
                 // Store the file path to expand after loading
@@ -80,12 +82,23 @@ fn execute_git_status_action(app: &mut App, action: Action) -> Option<AsyncActio
                 }
                 return Some(AsyncAction::LoadDiffFiles);
             }
+            // If on a stash, show its changes
+            if app.current_git_stash().is_some() {
+                return Some(AsyncAction::ViewStashDiff);
+            }
             // If on a section header, toggle expand/collapse
             app.toggle_git_section_expand();
             None
         }
 
         Action::StageFile => {
+            // Submodule checkouts and ignored paths are not staged from here
+            if matches!(
+                app.current_git_file_section(),
+                Some(GitSection::Submodules | GitSection::Ignored)
+            ) {
+                return None;
+            }
             if let Some(file_path) = app.current_git_file_path() {
                 if !app.is_current_git_item_staged() {
                     return Some(AsyncAction::StageFile { file_path });
@@ -107,7 +120,23 @@ fn execute_git_status_action(app: &mut App, action: Action) -> Option<AsyncActio
         Action::UnstageAll => Some(AsyncAction::UnstageAll),
 
         Action::GitPush => Some(AsyncAction::GitPush),
-        Action::GitPull => Some(AsyncAction::GitPull),
+        Action::GitPull => app.defer_with_status("Pulling…", AsyncAction::GitPull),
+        Action::GitFetch => app.defer_with_status("Fetching…", AsyncAction::GitFetch),
+        Action::QuickShip => Some(AsyncAction::StartQuickShip),
+        Action::StashSave => {
+            app.start_stash_save();
+            None
+        }
+        Action::StashPop => app.defer_with_status("Popping stash…", AsyncAction::StashPop),
+        Action::ShowConflicts => Some(AsyncAction::ShowConflicts),
+        Action::OpenInEditor => {
+            app.open_in_editor();
+            None
+        }
+        Action::ToggleIgnored => {
+            app.toggle_show_ignored();
+            Some(AsyncAction::LoadGitStatus)
+        }
 
         Action::RefreshStatus => Some(AsyncAction::LoadGitStatus),
 
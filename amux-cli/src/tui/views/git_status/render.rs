@@ -65,6 +65,18 @@ pub fn draw_git_status_panel(f: &mut Frame, area: Rect, app: &App) {
             icons.untracked_indicator(),
             "Untracked",
         ),
+        (
+            GitSection::Submodules,
+            ThemeGitSection::Submodules,
+            icons.submodule_indicator(),
+            "Submodules",
+        ),
+        (
+            GitSection::Ignored,
+            ThemeGitSection::Ignored,
+            icons.ignored_indicator(),
+            "Ignored",
+        ),
     ];
 
     for (section, theme_section, section_icon, section_name) in sections {
@@ -141,6 +153,9 @@ pub fn draw_git_status_panel(f: &mut Frame, area: Rect, app: &App) {
                         FileStatus::Deleted => GitFileStatus::Deleted,
                         FileStatus::Renamed => GitFileStatus::Renamed,
                         FileStatus::Untracked => GitFileStatus::Untracked,
+                        FileStatus::Ignored => GitFileStatus::Ignored,
+                        FileStatus::SubmoduleModified => GitFileStatus::SubmoduleModified,
+                        FileStatus::SubmoduleUninitialized => GitFileStatus::SubmoduleUninitialized,
                         FileStatus::Unspecified => GitFileStatus::Unknown,
                     };
 
@@ -150,6 +165,9 @@ pub fn draw_git_status_panel(f: &mut Frame, area: Rect, app: &App) {
                     GitFileStatus::Deleted => icons.git_deleted(),
                     GitFileStatus::Renamed => icons.git_renamed(),
                     GitFileStatus::Untracked => icons.git_untracked(),
+                    GitFileStatus::Ignored => icons.git_ignored(),
+                    GitFileStatus::SubmoduleModified => icons.git_submodule_modified(),
+                    GitFileStatus::SubmoduleUninitialized => icons.git_submodule_uninitialized(),
                     GitFileStatus::Unknown => "?",
                 };
 
@@ -190,6 +208,85 @@ pub fn draw_git_status_panel(f: &mut Frame, area: Rect, app: &App) {
         }
     }
 
+    // Stashes made on this branch
+    if !git.stashes.is_empty() {
+        let section_color = theme.git_section_color(ThemeGitSection::Stashes);
+        let is_expanded = git.expanded_sections.contains(&GitSection::Stashes);
+        let is_cursor = cursor_pos == git.cursor;
+
+        let section_style = if is_cursor && is_focused {
+            Style::default()
+                .fg(section_color)
+                .add_modifier(Modifier::BOLD)
+        } else if is_cursor {
+            theme.selection_unfocused_style()
+        } else {
+            theme.normal_style()
+        };
+
+        let expand_char = if is_expanded {
+            icons.collapse()
+        } else {
+            icons.expand()
+        };
+
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(
+                icons.cursor(),
+                if is_cursor {
+                    section_style
+                } else {
+                    Style::default()
+                },
+            ),
+            Span::styled(
+                format!(" {} ", expand_char),
+                Style::default().fg(theme.text_tertiary),
+            ),
+            Span::styled(
+                format!(
+                    "{} Stashes ({})",
+                    icons.stash_indicator(),
+                    git.stashes.len()
+                ),
+                section_style,
+            ),
+        ])));
+        cursor_pos += 1;
+
+        if is_expanded {
+            for stash in &git.stashes {
+                let is_stash_cursor = cursor_pos == git.cursor;
+
+                let stash_style = if is_stash_cursor && is_focused {
+                    theme.selection_style()
+                } else if is_stash_cursor {
+                    theme.selection_unfocused_style()
+                } else {
+                    theme.normal_style()
+                };
+
+                items.push(ListItem::new(Line::from(vec![
+                    Span::styled(
+                        icons.cursor(),
+                        if is_stash_cursor {
+                            stash_style
+                        } else {
+                            Style::default()
+                        },
+                    ),
+                    Span::raw("   "), // Indent
+                    Span::styled(
+                        format!("{:>3} ", format_age(stash.created_at)),
+                        Style::default().fg(section_color),
+                    ),
+                    Span::styled(&stash.message, stash_style),
+                ])));
+                cursor_pos += 1;
+            }
+        }
+    }
+
     // Show empty message if no files
     if items.is_empty() {
         items.push(ListItem::new(Line::from(vec![Span::styled(
@@ -243,3 +340,19 @@ pub fn draw_git_status_panel(f: &mut Frame, area: Rect, app: &App) {
         );
     }
 }
+
+/// Short age of a Unix timestamp (e.g. "5m", "3h", "2d")
+pub(crate) fn format_age(timestamp: i64) -> String {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(timestamp);
+    let secs = (now - timestamp).max(0);
+    match secs {
+        0..=59 => "now".to_string(),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86_399 => format!("{}h", secs / 3600),
+        86_400..=604_799 => format!("{}d", secs / 86_400),
+        _ => format!("{}w", secs / 604_800),
+    }
+}
@@ -1,3 +1,5 @@
+pub mod blame;
+pub mod conflicts;
 pub mod diff;
 pub mod git_status;
 pub mod sidebar;
@@ -52,7 +52,7 @@ pub fn handle_navigation_input_sync(app: &mut App, key: KeyEvent) -> Option<Asyn
                     // Go back to sidebar
                     app.focus = Focus::Sidebar;
                 }
-                Focus::Terminal | Focus::DiffFiles => {
+                Focus::Terminal | Focus::DiffFiles | Focus::Conflicts => {
                     // Handled in their respective modes
                 }
             }
@@ -71,6 +71,7 @@ pub fn handle_navigation_input_sync(app: &mut App, key: KeyEvent) -> Option<Asyn
                             None
                         }
                     }
+                    SidebarItem::LoadMore(wt_idx) => Some(AsyncAction::LoadMoreSessions { wt_idx }),
                     SidebarItem::None => None,
                 }
             } else {
@@ -83,12 +84,18 @@ pub fn handle_navigation_input_sync(app: &mut App, key: KeyEvent) -> Option<Asyn
 }
 
 /// Execute a sidebar action
-fn execute_sidebar_action(app: &mut App, action: Action) -> Option<AsyncAction> {
+pub fn execute_sidebar_action(app: &mut App, action: Action) -> Option<AsyncAction> {
     match action {
         Action::MoveUp => app.select_prev_sync(),
         Action::MoveDown => app.select_next_sync(),
 
         Action::SwitchRepo(idx) => app.switch_repo_sync(idx),
+        Action::SearchSessions => {
+            app.start_search_sessions();
+            None
+        }
+        Action::MoveRepoLeft => app.move_repo_sync(-1),
+        Action::MoveRepoRight => app.move_repo_sync(1),
 
         Action::Select => {
             // Select action: toggle expand for worktrees, enter terminal for sessions
@@ -102,6 +109,7 @@ fn execute_sidebar_action(app: &mut App, action: Action) -> Option<AsyncAction>
                             None
                         }
                     }
+                    SidebarItem::LoadMore(wt_idx) => Some(AsyncAction::LoadMoreSessions { wt_idx }),
                     SidebarItem::None => None,
                 }
             } else {
@@ -122,6 +130,21 @@ fn execute_sidebar_action(app: &mut App, action: Action) -> Option<AsyncAction>
             None
         }
 
+        Action::RunCommand => {
+            app.start_run_command();
+            None
+        }
+
+        Action::BroadcastInput => {
+            app.start_broadcast_input();
+            None
+        }
+
+        Action::ShellInDirectory => {
+            app.start_shell_directory();
+            None
+        }
+
         Action::SelectProviderAndCreate => {
             // Get current repo and branch
             let (repo_id, branch) = match (
@@ -155,10 +178,11 @@ fn execute_sidebar_action(app: &mut App, action: Action) -> Option<AsyncAction>
 
         Action::DeleteCurrent => {
             app.save_focus();
-            app.request_delete();
-            None
+            app.request_delete()
         }
 
+        Action::Undo => Some(AsyncAction::Undo),
+
         Action::RenameSession if app.focus == Focus::Sidebar => {
             tracing::debug!(
                 "RenameSession action triggered, current_sidebar_item = {:?}",
@@ -168,13 +192,58 @@ fn execute_sidebar_action(app: &mut App, action: Action) -> Option<AsyncAction>
             None
         }
 
-        Action::RefreshAll => Some(AsyncAction::RefreshAll),
+        Action::OpenInExternalTerminal => {
+            app.open_in_external_terminal();
+            None
+        }
+
+        Action::RenameBranch if app.focus == Focus::Sidebar => {
+            app.start_rename_branch();
+            None
+        }
+
+        Action::CycleDeleteAction if app.focus == Focus::Sidebar => {
+            Some(AsyncAction::CycleWorktreeDeleteAction)
+        }
+
+        Action::TogglePinSession if app.focus == Focus::Sidebar => {
+            Some(AsyncAction::TogglePinSession)
+        }
+        Action::MoveSessionUp if app.focus == Focus::Sidebar => {
+            Some(AsyncAction::MoveSession { down: false })
+        }
+        Action::MoveSessionDown if app.focus == Focus::Sidebar => {
+            Some(AsyncAction::MoveSession { down: true })
+        }
+
+        Action::ExportTranscript if app.focus == Focus::Sidebar => {
+            Some(AsyncAction::ExportTranscript)
+        }
 
-        Action::Quit => {
-            app.should_quit = true;
+        Action::ShowSessionInfo if app.focus == Focus::Sidebar => {
+            app.show_session_info();
             None
         }
 
+        Action::ViewSessionFile if app.focus == Focus::Sidebar => {
+            Some(AsyncAction::ViewSessionFile)
+        }
+
+        Action::ToggleCheckpoints if app.focus == Focus::Sidebar => {
+            Some(AsyncAction::ToggleCheckpoints)
+        }
+        Action::RestoreCheckpoint if app.focus == Focus::Sidebar => {
+            Some(AsyncAction::PrepareRestoreCheckpoint)
+        }
+
+        Action::RefreshAll => Some(AsyncAction::RefreshAll),
+
+        Action::QuickShip => Some(AsyncAction::StartQuickShip),
+        Action::GitFetch => app.defer_with_status("Fetching…", AsyncAction::GitFetch),
+        Action::GitPull => app.defer_with_status("Pulling…", AsyncAction::GitPull),
+
+        Action::Quit => Some(AsyncAction::RequestQuit),
+
         // Unhandled or context-inappropriate actions
         _ => None,
     }
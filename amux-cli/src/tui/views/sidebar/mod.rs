@@ -5,4 +5,4 @@ pub mod render;
 
 // Re-export commonly used items
 pub use input::handle_navigation_input_sync;
-pub use render::draw_sidebar;
+pub use render::{draw_sidebar, format_memory};
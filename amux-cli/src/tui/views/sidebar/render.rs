@@ -2,8 +2,9 @@
 
 use crate::tui::app::App;
 use crate::tui::icons::box_drawing;
-use crate::tui::state::Focus;
+use crate::tui::state::{Focus, SidebarItem};
 use crate::tui::views::git_status::draw_git_status_panel;
+use crate::tui::widgets::helpers::truncate_to_width;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::Style,
@@ -43,128 +44,240 @@ pub fn draw_sidebar_tree(f: &mut Frame, area: Rect, app: &App) {
     };
 
     let mut items: Vec<ListItem> = Vec::new();
-    let mut cursor_pos = 0;
+    // Names are cut so the indicators after them stay visible
+    let inner_width = area.width.saturating_sub(2) as usize;
+    let spans_width = |spans: &[Span]| spans.iter().map(Span::width).sum::<usize>();
 
     let repo = app.current_repo();
     let sidebar_cursor = repo.map(|r| r.sidebar_cursor).unwrap_or(0);
     let expanded_worktrees = repo.map(|r| &r.expanded_worktrees);
     let sessions_by_worktree = repo.map(|r| &r.sessions_by_worktree);
+    let has_more_sessions =
+        |wt_idx: usize| repo.is_some_and(|r| r.session_page_tokens.contains_key(&wt_idx));
 
-    for (wt_idx, wt) in app.worktrees().iter().enumerate() {
-        let is_expanded = expanded_worktrees
-            .map(|e| e.contains(&wt_idx))
-            .unwrap_or(false);
-        let is_cursor = cursor_pos == sidebar_cursor;
-
-        // Worktree row style
-        let wt_style = if is_cursor && is_focused {
-            theme.selection_style()
-        } else if is_cursor {
-            theme.selection_unfocused_style()
-        } else {
-            theme.normal_style()
-        };
-
-        // Expand indicator
-        let expand_char = if is_expanded {
-            icons.collapse()
-        } else {
-            icons.expand()
-        };
-
-        // Worktree indicator
-        let wt_indicator = if wt.is_main {
-            icons.main_worktree()
-        } else {
-            icons.worktree()
-        };
-
-        // Session count indicator
-        let session_count = sessions_by_worktree
-            .and_then(|sbw| sbw.get(&wt_idx))
-            .map(|s| s.len())
-            .unwrap_or(wt.session_count as usize);
-        let session_indicator = if session_count > 0 {
-            format!(" ({})", session_count)
-        } else {
-            String::new()
-        };
-
-        items.push(ListItem::new(Line::from(vec![
-            Span::styled(
-                icons.cursor(),
-                if is_cursor {
-                    wt_style
+    // Rows follow `sidebar_sort`, not the order of the worktrees and sessions
+    for (row, item) in app.sidebar_rows().into_iter().enumerate() {
+        let is_cursor = row == sidebar_cursor;
+        match item {
+            SidebarItem::Worktree(wt_idx) => {
+                let Some(wt) = app.worktrees().get(wt_idx) else {
+                    continue;
+                };
+                let is_expanded = expanded_worktrees
+                    .map(|e| e.contains(&wt_idx))
+                    .unwrap_or(false);
+
+                // Worktree row style
+                let wt_style = if is_cursor && is_focused {
+                    theme.selection_style()
+                } else if is_cursor {
+                    theme.selection_unfocused_style()
+                } else {
+                    theme.normal_style()
+                };
+
+                // Expand indicator
+                let expand_char = if is_expanded {
+                    icons.collapse()
+                } else {
+                    icons.expand()
+                };
+
+                // Worktree indicator
+                let wt_indicator = if wt.is_main {
+                    icons.main_worktree()
+                } else {
+                    icons.worktree()
+                };
+
+                // Session count indicator (all sessions, not just the loaded pages)
+                let session_count = sessions_by_worktree
+                    .and_then(|sbw| sbw.get(&wt_idx))
+                    .filter(|_| !has_more_sessions(wt_idx))
+                    .map(|s| s.len())
+                    .unwrap_or(wt.session_count as usize);
+                let session_indicator = if session_count > 0 {
+                    format!(" ({})", session_count)
                 } else {
-                    Style::default()
-                },
-            ),
-            Span::styled(
-                format!(" {} ", expand_char),
-                Style::default().fg(theme.text_tertiary),
-            ),
-            Span::styled(
-                format!("{} ", wt_indicator),
-                Style::default().fg(theme.neon_cyan),
-            ),
-            Span::styled(&wt.branch, wt_style),
-            Span::styled(session_indicator, Style::default().fg(theme.neon_green)),
-        ])));
-        cursor_pos += 1;
-
-        // Render sessions if expanded
-        if is_expanded {
-            if let Some(sessions) = sessions_by_worktree.and_then(|sbw| sbw.get(&wt_idx)) {
-                for session in sessions.iter() {
-                    let is_session_cursor = cursor_pos == sidebar_cursor;
-                    let is_active = app.terminal.active_session_id.as_ref() == Some(&session.id);
-
-                    let s_style = if is_session_cursor && is_focused {
-                        theme.selection_style()
-                    } else if is_session_cursor {
-                        theme.selection_unfocused_style()
-                    } else {
-                        theme.normal_style()
-                    };
-
-                    let active_indicator = if is_active {
-                        icons.active_indicator()
-                    } else {
-                        " "
-                    };
-                    let status_icon = if session.status == 1 {
-                        icons.running()
-                    } else {
-                        icons.stopped()
-                    };
-
-                    items.push(ListItem::new(Line::from(vec![
-                        Span::styled(
-                            icons.cursor(),
-                            if is_session_cursor {
-                                s_style
-                            } else {
-                                Style::default()
-                            },
-                        ),
-                        Span::raw("     "), // Indent for nesting
-                        Span::styled(
-                            format!("{} ", active_indicator),
-                            Style::default().fg(theme.neon_green),
-                        ),
-                        Span::styled(
-                            format!("{} ", status_icon),
-                            Style::default().fg(if session.status == 1 {
-                                theme.success
-                            } else {
-                                theme.text_disabled
-                            }),
-                        ),
-                        Span::styled(&session.name, s_style),
-                    ])));
-                    cursor_pos += 1;
+                    String::new()
+                };
+
+                // Dirty / ahead-behind indicators ("?" when the daemon gave up computing them)
+                let mut status_spans = Vec::new();
+                if !wt.status_known {
+                    status_spans.push(Span::styled(" ?", Style::default().fg(theme.text_disabled)));
+                } else {
+                    if wt.dirty {
+                        status_spans.push(Span::styled(
+                            format!(" {}", icons.worktree_dirty()),
+                            Style::default().fg(theme.warning),
+                        ));
+                    }
+                    if wt.ahead > 0 {
+                        status_spans.push(Span::styled(
+                            format!(" {}{}", icons.ahead(), wt.ahead),
+                            Style::default().fg(theme.neon_cyan),
+                        ));
+                    }
+                    if wt.behind > 0 {
+                        status_spans.push(Span::styled(
+                            format!(" {}{}", icons.behind(), wt.behind),
+                            Style::default().fg(theme.neon_magenta),
+                        ));
+                    }
+                    if let Some(stats) = wt.diff_stats.filter(|s| s.files_changed > 0) {
+                        status_spans.push(Span::styled(
+                            format!(" +{}", stats.insertions),
+                            Style::default().fg(theme.diff_add),
+                        ));
+                        status_spans.push(Span::styled(
+                            format!(" \u{2212}{}", stats.deletions),
+                            Style::default().fg(theme.diff_del),
+                        ));
+                    }
                 }
+
+                let mut wt_spans = vec![
+                    Span::styled(
+                        icons.cursor(),
+                        if is_cursor {
+                            wt_style
+                        } else {
+                            Style::default()
+                        },
+                    ),
+                    Span::styled(
+                        format!(" {} ", expand_char),
+                        Style::default().fg(theme.text_tertiary),
+                    ),
+                    Span::styled(
+                        format!("{} ", wt_indicator),
+                        Style::default().fg(theme.neon_cyan),
+                    ),
+                ];
+                let mut suffix = vec![Span::styled(
+                    session_indicator,
+                    Style::default().fg(theme.neon_green),
+                )];
+                suffix.extend(status_spans);
+                let branch_width =
+                    inner_width.saturating_sub(spans_width(&wt_spans) + spans_width(&suffix));
+                wt_spans.push(Span::styled(
+                    truncate_to_width(&wt.branch, branch_width),
+                    wt_style,
+                ));
+                wt_spans.extend(suffix);
+                items.push(ListItem::new(Line::from(wt_spans)));
             }
+            SidebarItem::Session(wt_idx, s_idx) => {
+                let Some(session) = sessions_by_worktree
+                    .and_then(|sbw| sbw.get(&wt_idx))
+                    .and_then(|sessions| sessions.get(s_idx))
+                else {
+                    continue;
+                };
+                let is_active = app.terminal.active_session_id.as_ref() == Some(&session.id);
+
+                let s_style = if is_cursor && is_focused {
+                    theme.selection_style()
+                } else if is_cursor {
+                    theme.selection_unfocused_style()
+                } else {
+                    theme.normal_style()
+                };
+
+                let active_indicator = if is_active {
+                    icons.active_indicator()
+                } else {
+                    " "
+                };
+                let status_icon = if session.status == 1 {
+                    icons.running()
+                } else {
+                    icons.stopped()
+                };
+
+                let mut spans = vec![
+                    Span::styled(
+                        icons.cursor(),
+                        if is_cursor { s_style } else { Style::default() },
+                    ),
+                    Span::raw("     "), // Indent for nesting
+                    Span::styled(
+                        format!("{} ", active_indicator),
+                        Style::default().fg(theme.neon_green),
+                    ),
+                    Span::styled(
+                        format!("{} ", status_icon),
+                        Style::default().fg(if session.status == 1 {
+                            theme.success
+                        } else {
+                            theme.text_disabled
+                        }),
+                    ),
+                ];
+                let mut badges = Vec::new();
+                if app.sidebar.unread.contains_key(&session.id) {
+                    badges.push(Span::styled(
+                        format!(" {}", icons.unread()),
+                        Style::default().fg(theme.neon_magenta),
+                    ));
+                }
+                if session.pinned {
+                    badges.push(Span::styled(
+                        format!(" {}", icons.pinned()),
+                        Style::default().fg(theme.neon_yellow),
+                    ));
+                }
+                if session.status == 1 && session.memory_kb > 0 {
+                    badges.push(Span::styled(
+                        format!(" {}", format_memory(session.memory_kb)),
+                        Style::default().fg(theme.text_disabled),
+                    ));
+                }
+                let name_width =
+                    inner_width.saturating_sub(spans_width(&spans) + spans_width(&badges));
+                spans.push(Span::styled(
+                    truncate_to_width(&session.name, name_width),
+                    s_style,
+                ));
+                spans.extend(badges);
+                // The title only gets the columns left over
+                if app.config.options.show_session_titles {
+                    if let Some(title) = &session.title {
+                        let title_width = inner_width.saturating_sub(spans_width(&spans) + 3);
+                        if title_width > 0 {
+                            spans.push(Span::styled(
+                                format!(" · {}", truncate_to_width(title, title_width)),
+                                Style::default().fg(theme.text_disabled),
+                            ));
+                        }
+                    }
+                }
+                items.push(ListItem::new(Line::from(spans)));
+            }
+            SidebarItem::LoadMore(_) => {
+                let more_style = if is_cursor && is_focused {
+                    theme.selection_style()
+                } else if is_cursor {
+                    theme.selection_unfocused_style()
+                } else {
+                    Style::default().fg(theme.text_tertiary)
+                };
+                items.push(ListItem::new(Line::from(vec![
+                    Span::styled(
+                        icons.cursor(),
+                        if is_cursor {
+                            more_style
+                        } else {
+                            Style::default()
+                        },
+                    ),
+                    Span::raw("       "),
+                    Span::styled("Load more…", more_style),
+                ])));
+            }
+            SidebarItem::None => {}
         }
     }
 
@@ -185,3 +298,16 @@ pub fn draw_sidebar_tree(f: &mut Frame, area: Rect, app: &App) {
 
     f.render_widget(list, area);
 }
+
+/// Compact memory size for the sidebar (e.g. `512K`, `64M`, `1.2G`)
+pub fn format_memory(kb: u64) -> String {
+    const MB: u64 = 1024;
+    const GB: u64 = 1024 * 1024;
+    if kb >= GB {
+        format!("{:.1}G", kb as f64 / GB as f64)
+    } else if kb >= MB {
+        format!("{}M", kb / MB)
+    } else {
+        format!("{}K", kb)
+    }
+}
@@ -0,0 +1,74 @@
+//! Session clock shown in the status bar, e.g. "up 1h23m · idle 4m"
+
+use amux_proto::daemon::{SessionInfo, SessionStatus};
+
+/// Uptime of a running session and time since its last output
+///
+/// Uptime counts from the last start; `None` when neither is known.
+pub fn session_clock(session: &SessionInfo, now: u64) -> Option<String> {
+    let running = session.status == SessionStatus::Running as i32;
+    let mut parts = Vec::new();
+    if running && session.started_at > 0 {
+        parts.push(format!(
+            "up {}",
+            format_duration(now.saturating_sub(session.started_at))
+        ));
+    }
+    if session.last_output_at > 0 {
+        parts.push(format!(
+            "idle {}",
+            format_duration(now.saturating_sub(session.last_output_at))
+        ));
+    }
+    (!parts.is_empty()).then(|| parts.join(" \u{b7} "))
+}
+
+/// Two most significant units of a duration (e.g. "45s", "4m", "1h23m", "2d5h")
+pub fn format_duration(secs: u64) -> String {
+    let (days, hours, mins) = (secs / 86_400, secs % 86_400 / 3600, secs % 3600 / 60);
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m", mins),
+        3600..=86_399 if mins == 0 => format!("{}h", hours),
+        3600..=86_399 => format!("{}h{}m", hours, mins),
+        _ if hours == 0 => format!("{}d", days),
+        _ => format!("{}d{}h", days, hours),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "0s");
+        assert_eq!(format_duration(59), "59s");
+        assert_eq!(format_duration(4 * 60 + 10), "4m");
+        assert_eq!(format_duration(3600), "1h");
+        assert_eq!(format_duration(3600 + 23 * 60), "1h23m");
+        assert_eq!(format_duration(2 * 86_400 + 5 * 3600 + 60), "2d5h");
+        assert_eq!(format_duration(86_400), "1d");
+    }
+
+    #[test]
+    fn test_session_clock() {
+        let mut session = SessionInfo {
+            status: SessionStatus::Running as i32,
+            started_at: 1000,
+            last_output_at: 1000 + 83 * 60 - 240,
+            ..Default::default()
+        };
+        let now = 1000 + 83 * 60;
+        assert_eq!(
+            session_clock(&session, now).as_deref(),
+            Some("up 1h23m \u{b7} idle 4m")
+        );
+
+        // Stopped sessions have no uptime
+        session.status = SessionStatus::Stopped as i32;
+        assert_eq!(session_clock(&session, now).as_deref(), Some("idle 4m"));
+        session.last_output_at = 0;
+        assert_eq!(session_clock(&session, now), None);
+    }
+}
@@ -1,5 +1,7 @@
 //! Tab bar view - branch tabs and status bar
 
+pub mod clock;
 pub mod render;
+pub mod title;
 
 pub use render::{draw_status_bar, draw_tab_bar};
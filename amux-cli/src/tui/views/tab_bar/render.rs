@@ -1,8 +1,10 @@
 //! Tab bar and status bar rendering
 
+use super::clock::session_clock;
+use super::title::{format_tab_title, TabTitleValues};
 use crate::tui::app::App;
 use crate::tui::icons::box_drawing;
-use crate::tui::state::{Focus, InputMode, PrefixMode, TerminalMode};
+use crate::tui::state::{Focus, InputMode, PrefixMode, SidebarItem, TerminalMode};
 use amux_config::actions::Action;
 use amux_config::keybind::BindingContext;
 use ratatui::{
@@ -18,16 +20,29 @@ pub fn draw_tab_bar(f: &mut Frame, area: Rect, app: &App) {
     let theme = &app.theme;
 
     let titles: Vec<Line> = app
-        .repos()
-        .iter()
+        .repos_ordered()
         .enumerate()
-        .map(|(i, repo)| {
-            let num = if i < 9 {
-                format!("{}:", i + 1)
-            } else {
-                String::new()
+        .map(|(i, state)| {
+            let repo = &state.info;
+            let values = TabTitleValues {
+                // Only the first 9 tabs have a switch key
+                index: (i < 9).then_some(i + 1),
+                dirty_count: state.worktrees.iter().filter(|w| w.dirty).count(),
+                provider: state
+                    .current_session()
+                    .and_then(|s| s.provider.as_deref())
+                    .unwrap_or_default(),
             };
-            Line::from(format!("{}{}", num, repo.name))
+            let title = format_tab_title(&app.config.options.tab_title_format, repo, &values);
+            let mut spans = vec![Span::raw(title)];
+            let unread = app.sidebar.unread_in_repo(&repo.id);
+            if unread > 0 {
+                spans.push(Span::styled(
+                    format!(" {}{}", app.icons.unread(), unread),
+                    Style::default().fg(theme.neon_magenta),
+                ));
+            }
+            Line::from(spans)
         })
         .collect();
 
@@ -59,6 +74,59 @@ pub fn draw_tab_bar(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(tabs, area);
 }
 
+/// Diff stats of the worktree under the sidebar cursor, e.g.
+/// "feature: 3 files changed, +120 −45"
+fn selected_worktree_stats(app: &App) -> Option<Line<'static>> {
+    let SidebarItem::Worktree(wt_idx) = app.current_sidebar_item() else {
+        return None;
+    };
+    let wt = app.current_repo()?.worktrees.get(wt_idx)?;
+    let stats = wt.diff_stats?;
+    let theme = &app.theme;
+    let files = if stats.files_changed == 1 {
+        "1 file changed".to_string()
+    } else {
+        format!("{} files changed", stats.files_changed)
+    };
+    Some(Line::from(vec![
+        Span::styled(
+            format!(" {}: {}, ", wt.branch, files),
+            Style::default().fg(theme.text_secondary),
+        ),
+        Span::styled(
+            format!("+{}", stats.insertions),
+            Style::default().fg(theme.diff_add),
+        ),
+        Span::styled(
+            format!(" \u{2212}{} ", stats.deletions),
+            Style::default().fg(theme.diff_del),
+        ),
+    ]))
+}
+
+/// Clock of the session under the sidebar cursor, or else of the one in
+/// the terminal, e.g. "up 1h23m · idle 4m"
+fn selected_session_clock(app: &App) -> Option<Line<'static>> {
+    let session = match app.current_sidebar_item() {
+        SidebarItem::Session(wt_idx, s_idx) => app
+            .current_repo()?
+            .sessions_by_worktree
+            .get(&wt_idx)?
+            .get(s_idx)?,
+        // Worktree rows show their diff stats instead
+        SidebarItem::Worktree(_) => return None,
+        SidebarItem::LoadMore(_) | SidebarItem::None => app.active_session()?,
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let clock = session_clock(session, now)?;
+    Some(Line::styled(
+        format!(" {} ", clock),
+        Style::default().fg(app.theme.text_secondary),
+    ))
+}
+
 /// Helper to format key binding for display
 fn key(app: &App, action: Action, context: BindingContext) -> String {
     app.keybinds.key_display(action, context)
@@ -72,7 +140,7 @@ pub fn draw_status_bar(f: &mut Frame, area: Rect, app: &App) {
     if app.prefix_mode == PrefixMode::WaitingForCommand {
         let ctx = BindingContext::Prefix;
         let prefix_help = format!(
-            "Prefix: {} Branches | {} Sessions | {} Terminal | {} Normal | {} New | {} Add | {} Delete | {} Refresh | {} Fullscreen | [1-9] Repo | {} Quit",
+            "Prefix: {} Branches | {} Sessions | {} Terminal | {} Normal | {} New | {} Add | {} Delete | {} Refresh | {} Fullscreen | [1-9] Recent | {} Quit",
             key(app, Action::FocusBranches, ctx),
             key(app, Action::FocusSessions, ctx),
             key(app, Action::FocusTerminal, ctx),
@@ -100,15 +168,21 @@ pub fn draw_status_bar(f: &mut Frame, area: Rect, app: &App) {
         return;
     }
 
-    // CreateSessionInput mode - show input prompt in status bar
-    if let InputMode::CreateSessionInput { .. } = &app.input_mode {
+    // Session name and command line inputs - show input prompt in status bar
+    let prompt = match &app.input_mode {
+        InputMode::CreateSessionInput { .. } => {
+            Some("Session name (Enter=create, Shift+Enter=system prompt, Esc=cancel): ")
+        }
+        InputMode::CommandLine => Some(":"),
+        _ => None,
+    };
+    if let Some(prompt) = prompt {
         // Use Unicode-safe methods to get cursor-split text
         let before = app.text_input.text_before_cursor();
         let at_cursor = app.text_input.char_at_cursor();
         let after = app.text_input.text_after_cursor();
 
         // Build the prompt with cursor indicator
-        let prompt = "Session name (Enter=create, Esc=cancel): ";
         let line = Line::from(vec![
             Span::styled(prompt, Style::default().fg(theme.neon_cyan)),
             Span::styled(before, Style::default().fg(theme.text_primary)),
@@ -157,13 +231,14 @@ pub fn draw_status_bar(f: &mut Frame, area: Rect, app: &App) {
             Focus::GitStatus => {
                 let ctx = BindingContext::GitStatus;
                 format!(
-                    "{} Move | {} Expand | {} Stage | {} Unstage | {} Stage All | {} Unstage All | {} Refresh | {} Diff | {} Back",
+                    "{} Move | {} Expand | {} Stage | {} Unstage | {} Stage All | {} Unstage All | {} Ship | {} Refresh | {} Diff | {} Back",
                     format!("{}/{}", key(app, Action::MoveUp, ctx), key(app, Action::MoveDown, ctx)).replace("[]", ""),
                     key(app, Action::ToggleExpand, ctx),
                     key(app, Action::StageFile, ctx),
                     key(app, Action::UnstageFile, ctx),
                     key(app, Action::StageAll, ctx),
                     key(app, Action::UnstageAll, ctx),
+                    key(app, Action::QuickShip, ctx),
                     key(app, Action::RefreshStatus, ctx),
                     key(app, Action::ToggleDiffView, ctx),
                     key(app, Action::FocusSidebar, ctx),
@@ -173,13 +248,14 @@ pub fn draw_status_bar(f: &mut Frame, area: Rect, app: &App) {
                 TerminalMode::Normal => {
                     let ctx = BindingContext::TerminalNormal;
                     format!(
-                        "{} Prefix | {} Scroll | {} Page | {} Top/Bottom | {} Insert | {} Full | {} Diff | {} Exit",
+                        "{} Prefix | {} Scroll | {} Page | {} Top/Bottom | {} Insert | {} Full | {} Link | {} Diff | {} Exit",
                         app.keybinds.prefix_key_display(),
                         format!("{}/{}", key(app, Action::ScrollUp, ctx), key(app, Action::ScrollDown, ctx)).replace("[]", ""),
                         format!("{}/{}", key(app, Action::ScrollHalfPageUp, ctx), key(app, Action::ScrollHalfPageDown, ctx)).replace("[]", ""),
                         format!("{}/{}", key(app, Action::ScrollTop, ctx), key(app, Action::ScrollBottom, ctx)).replace("[]", ""),
                         key(app, Action::InsertMode, ctx),
                         key(app, Action::ToggleFullscreen, ctx),
+                        key(app, Action::OpenLink, ctx),
                         key(app, Action::ToggleDiffView, ctx),
                         key(app, Action::ExitTerminal, ctx),
                     )
@@ -195,7 +271,7 @@ pub fn draw_status_bar(f: &mut Frame, area: Rect, app: &App) {
             Focus::DiffFiles => {
                 let ctx = BindingContext::Diff;
                 format!(
-                    "{} Nav | {} Expand | {} Add | {} Edit | {} Del | {} Jump | {} Send | {} Back",
+                    "{} Nav | {} Expand | {} Add | {} Edit | {} Del | {} Jump | {} Send | {} Reviews | {} Back",
                     format!(
                         "{}/{}",
                         key(app, Action::MoveUp, ctx),
@@ -213,6 +289,30 @@ pub fn draw_status_bar(f: &mut Frame, area: Rect, app: &App) {
                     )
                     .replace("[]", ""),
                     key(app, Action::SubmitReviewClaude, ctx),
+                    key(app, Action::ShowReviews, ctx),
+                    key(app, Action::BackToTerminal, ctx),
+                )
+            }
+            Focus::Conflicts => {
+                let ctx = BindingContext::Conflicts;
+                format!(
+                    "{} Move | {} File | {} Ours | {} Theirs | {} Both | {} Write & Stage | {} Back",
+                    format!(
+                        "{}/{}",
+                        key(app, Action::MoveUp, ctx),
+                        key(app, Action::MoveDown, ctx)
+                    )
+                    .replace("[]", ""),
+                    format!(
+                        "{}/{}",
+                        key(app, Action::PrevFile, ctx),
+                        key(app, Action::NextFile, ctx)
+                    )
+                    .replace("[]", ""),
+                    key(app, Action::ResolveOurs, ctx),
+                    key(app, Action::ResolveTheirs, ctx),
+                    key(app, Action::ResolveBoth, ctx),
+                    key(app, Action::WriteResolution, ctx),
                     key(app, Action::BackToTerminal, ctx),
                 )
             }
@@ -220,13 +320,30 @@ pub fn draw_status_bar(f: &mut Frame, area: Rect, app: &App) {
         (help, theme.text_tertiary)
     };
 
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded);
+    if let Some(stats) = selected_worktree_stats(app) {
+        block = block.title(stats.right_aligned());
+    }
+    if let Some(clock) = selected_session_clock(app) {
+        block = block.title(clock.right_aligned());
+    }
+    if app.sidebar.hidden {
+        block = block.title(Line::from(" [no-sidebar] ").right_aligned());
+    }
+    if let Some(recording) = &app.macro_recording {
+        block = block.title(
+            Line::styled(
+                format!(" recording @{} ", recording.register),
+                Style::default().fg(theme.neon_magenta),
+            )
+            .right_aligned(),
+        );
+    }
     let paragraph = Paragraph::new(message)
         .style(Style::default().fg(color))
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded),
-        );
+        .block(block);
 
     f.render_widget(paragraph, area);
 }
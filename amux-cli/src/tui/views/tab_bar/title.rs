@@ -0,0 +1,88 @@
+//! Repo tab titles built from `options.tab_title_format`
+
+use amux_proto::daemon::RepoInfo;
+
+/// Values for the tokens of a tab title that don't come from `RepoInfo`
+pub struct TabTitleValues<'a> {
+    /// 1-based tab position, None past the 9 tabs the `1`-`9` keys reach
+    pub index: Option<usize>,
+    /// Worktrees with uncommitted changes
+    pub dirty_count: usize,
+    /// Provider of the repo's selected session (empty for none)
+    pub provider: &'a str,
+}
+
+/// Replace the `{token}`s of `template`; unknown tokens become empty
+///
+/// Substitution works on whole tokens, so names with wide or multi-byte
+/// characters are copied intact and the tab widget measures them by display
+/// width.
+pub fn format_tab_title(template: &str, repo: &RepoInfo, values: &TabTitleValues) -> String {
+    let mut title = String::with_capacity(template.len() + repo.name.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        title.push_str(&rest[..start]);
+        match &rest[start + 1..start + len] {
+            "index" => {
+                if let Some(index) = values.index {
+                    title.push_str(&index.to_string());
+                }
+            }
+            "name" => title.push_str(&repo.name),
+            "session_count" => title.push_str(&repo.session_count.to_string()),
+            "dirty_count" => title.push_str(&values.dirty_count.to_string()),
+            "provider" => title.push_str(values.provider),
+            _ => {}
+        }
+        rest = &rest[start + len + 1..];
+    }
+    title.push_str(rest);
+    title
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unicode_width::UnicodeWidthStr;
+
+    fn repo(name: &str) -> RepoInfo {
+        RepoInfo {
+            name: name.to_string(),
+            session_count: 3,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_format_tab_title() {
+        let values = TabTitleValues {
+            index: Some(2),
+            dirty_count: 1,
+            provider: "claude",
+        };
+        let amux = repo("amux");
+        assert_eq!(format_tab_title("{index}:{name}", &amux, &values), "2:amux");
+        assert_eq!(
+            format_tab_title("{name} [{session_count}]", &amux, &values),
+            "amux [3]"
+        );
+        assert_eq!(
+            format_tab_title("{name} {dirty_count}* {provider}", &amux, &values),
+            "amux 1* claude"
+        );
+        assert_eq!(format_tab_title("{bogus}{name}{", &amux, &values), "amux{");
+        let tenth = TabTitleValues {
+            index: None,
+            ..values
+        };
+        assert_eq!(format_tab_title("{index} {name}", &amux, &tenth), " amux");
+
+        let wide = format_tab_title("{index}:{name}", &repo("项目"), &values);
+        assert_eq!(wide, "2:项目");
+        assert_eq!(wide.width(), 6);
+    }
+}
@@ -47,6 +47,11 @@ pub fn handle_insert_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncActi
                 | KeyCode::Null
         )
     {
+        // Ctrl+Alt+` asks for a subdirectory to open a new shell in
+        if key.modifiers.contains(KeyModifiers::ALT) {
+            app.start_shell_directory();
+            return None;
+        }
         tracing::info!("SwitchToShell triggered by {:?}", key.code);
         return Some(AsyncAction::SwitchToShell);
     }
@@ -64,6 +69,10 @@ pub fn handle_insert_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncActi
 fn execute_terminal_insert_action(app: &mut App, action: Action) -> Option<AsyncAction> {
     match action {
         Action::SwitchToShell => Some(AsyncAction::SwitchToShell),
+        Action::ShellInDirectory => {
+            app.start_shell_directory();
+            None
+        }
 
         // Exit insert mode
         Action::NormalMode => {
@@ -92,12 +101,10 @@ pub fn handle_terminal_normal_mode_sync(app: &mut App, key: KeyEvent) {
     // Fallback for keys not in keybinds (Esc, BackTab for special navigation)
     match key.code {
         // Exit fullscreen (Esc in Normal mode stays in Normal, but exits fullscreen if active)
-        KeyCode::Esc => {
-            if app.terminal.fullscreen {
-                app.terminal.fullscreen = false;
-            }
-            // Esc in Normal mode: stay in Normal mode (like Claude Code)
-            // User can use Tab/Shift+Tab or Prefix+s/w to navigate away
+        // Esc in Normal mode: stay in Normal mode (like Claude Code)
+        // User can use Tab/Shift+Tab or Prefix+s/w to navigate away
+        KeyCode::Esc if app.terminal.fullscreen => {
+            app.terminal.fullscreen = false;
         }
 
         // Shift+Tab: go back to sidebar
@@ -115,6 +122,7 @@ fn execute_terminal_normal_action(app: &mut App, action: Action) {
         Action::ToggleFullscreen => app.toggle_fullscreen(),
 
         Action::InsertMode => app.enter_insert_mode(),
+        Action::OpenInExternalTerminal => app.open_in_external_terminal(),
 
         Action::ScrollUp => app.scroll_up(1),
         Action::ScrollDown => app.scroll_down(1),
@@ -125,6 +133,8 @@ fn execute_terminal_normal_action(app: &mut App, action: Action) {
         Action::ScrollTop => app.scroll_to_top(),
         Action::ScrollBottom => app.scroll_to_bottom(),
 
+        Action::OpenLink => app.open_hyperlink_at_cursor(),
+
         // Unhandled or context-inappropriate actions
         _ => {}
     }
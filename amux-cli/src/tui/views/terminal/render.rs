@@ -4,6 +4,8 @@
 //! that converts vt100::Screen to ratatui widgets, similar to tui-term crate.
 
 use crate::tui::app::App;
+use crate::tui::hyperlink::HyperlinkSpan;
+use crate::tui::selection::{self, Selection};
 use crate::tui::state::{Focus, TerminalMode};
 use crate::tui::theme::TerminalMode as ThemeTerminalMode;
 use ratatui::{
@@ -17,11 +19,26 @@ use ratatui::{
 /// Simple PseudoTerminal widget that renders vt100::Screen
 struct PseudoTerminal<'a> {
     screen: &'a vt100::Screen,
+    hyperlinks: Vec<&'a HyperlinkSpan>,
+    selection: Option<Selection>,
 }
 
 impl<'a> PseudoTerminal<'a> {
-    fn new(screen: &'a vt100::Screen) -> Self {
-        Self { screen }
+    fn new(
+        screen: &'a vt100::Screen,
+        hyperlinks: &'a [HyperlinkSpan],
+        selection: Option<Selection>,
+    ) -> Self {
+        // Drop spans whose text has scrolled away or been overwritten
+        let hyperlinks = hyperlinks
+            .iter()
+            .filter(|span| span.is_visible(screen))
+            .collect();
+        Self {
+            screen,
+            hyperlinks,
+            selection,
+        }
     }
 }
 
@@ -40,8 +57,19 @@ impl Widget for PseudoTerminal<'_> {
                 }
 
                 if let Some(cell) = self.screen.cell(row, col) {
+                    // Covered by the wide character before it; drawing a space
+                    // here would overwrite that character's right half
+                    if cell.is_wide_continuation() {
+                        buf[(x, y)].reset();
+                        buf[(x, y)].set_skip(true);
+                        continue;
+                    }
+
+                    // A wide character in the last column has no room for its
+                    // right half and would spill past the pane
                     let ch = cell.contents();
-                    let display_char = if ch.is_empty() { " " } else { ch };
+                    let clipped = cell.is_wide() && col + 1 >= area.width;
+                    let display_char = if ch.is_empty() || clipped { " " } else { ch };
 
                     let mut style = Style::default();
 
@@ -64,12 +92,18 @@ impl Widget for PseudoTerminal<'_> {
                     if cell.italic() {
                         style = style.add_modifier(Modifier::ITALIC);
                     }
-                    if cell.underline() {
+                    if cell.underline() || self.hyperlinks.iter().any(|s| s.contains(row, col)) {
                         style = style.add_modifier(Modifier::UNDERLINED);
                     }
                     if cell.inverse() {
                         style = style.add_modifier(Modifier::REVERSED);
                     }
+                    if self
+                        .selection
+                        .is_some_and(|s| selection::contains(s, row, col))
+                    {
+                        style = style.add_modifier(Modifier::REVERSED);
+                    }
 
                     // Render cursor with reversed style
                     if cursor_visible && row == cursor_row && col == cursor_col {
@@ -95,6 +129,24 @@ fn vt100_color_to_ratatui(color: vt100::Color) -> Color {
     }
 }
 
+/// Border title, followed by the window title the session's program set
+fn pane_title(label: &str, app: &App) -> String {
+    let mut badge = String::new();
+    if app
+        .active_session()
+        .is_some_and(|s| s.system_prompt.is_some())
+    {
+        badge.push_str(" [system prompt]");
+    }
+    if app.attached_externally() {
+        badge.push_str(" [attached externally]");
+    }
+    match app.active_session().and_then(|s| s.title.as_deref()) {
+        Some(title) => format!(" {}{} — {} ", label, badge, title),
+        None => format!(" {}{} ", label, badge),
+    }
+}
+
 /// Draw terminal preview/interaction area
 pub fn draw_terminal(f: &mut Frame, area: Rect, app: &App) {
     let theme = &app.theme;
@@ -107,16 +159,17 @@ pub fn draw_terminal(f: &mut Frame, area: Rect, app: &App) {
 
     let border_style = theme.terminal_border_style(theme_mode, is_terminal_focused);
 
-    let title = if is_terminal_focused {
+    let label = if is_terminal_focused {
         match app.terminal.mode {
-            TerminalMode::Insert => " Terminal [INSERT] ",
-            TerminalMode::Normal => " Terminal [NORMAL] ",
+            TerminalMode::Insert => "Terminal [INSERT]",
+            TerminalMode::Normal => "Terminal [NORMAL]",
         }
     } else if app.terminal.active_session_id.is_some() {
-        " Terminal [Preview] "
+        "Terminal [Preview]"
     } else {
-        " Terminal [No session] "
+        "Terminal [No session]"
     };
+    let title = pane_title(label, app);
 
     let block = Block::default()
         .borders(Borders::ALL)
@@ -130,7 +183,11 @@ pub fn draw_terminal(f: &mut Frame, area: Rect, app: &App) {
     // Render terminal content using PseudoTerminal widget
     if app.terminal.active_session_id.is_some() {
         if let Ok(parser) = app.terminal.parser.lock() {
-            let pseudo_term = PseudoTerminal::new(parser.screen());
+            let pseudo_term = PseudoTerminal::new(
+                parser.screen(),
+                &app.terminal.hyperlinks,
+                app.terminal.selection,
+            );
             f.render_widget(pseudo_term, inner);
         }
     } else {
@@ -153,10 +210,11 @@ pub fn draw_terminal_fullscreen(f: &mut Frame, area: Rect, app: &App) {
 
     let border_style = theme.terminal_border_style(theme_mode, true);
 
-    let title = match app.terminal.mode {
-        TerminalMode::Insert => " Terminal [INSERT - FULLSCREEN] ",
-        TerminalMode::Normal => " Terminal [NORMAL - FULLSCREEN] ",
+    let label = match app.terminal.mode {
+        TerminalMode::Insert => "Terminal [INSERT - FULLSCREEN]",
+        TerminalMode::Normal => "Terminal [NORMAL - FULLSCREEN]",
     };
+    let title = pane_title(label, app);
 
     let block = Block::default()
         .borders(Borders::ALL)
@@ -169,7 +227,52 @@ pub fn draw_terminal_fullscreen(f: &mut Frame, area: Rect, app: &App) {
 
     // Render terminal content using PseudoTerminal widget
     if let Ok(parser) = app.terminal.parser.lock() {
-        let pseudo_term = PseudoTerminal::new(parser.screen());
+        let pseudo_term = PseudoTerminal::new(
+            parser.screen(),
+            &app.terminal.hyperlinks,
+            app.terminal.selection,
+        );
         f.render_widget(pseudo_term, inner);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Render `input` on a `cols`-wide screen into a buffer of the same size
+    ///
+    /// Inputs end in `ESC[?25l` so the cursor doesn't restyle a cell.
+    fn render(input: &str, cols: u16) -> Buffer {
+        let mut parser = vt100::Parser::new(1, cols, 0);
+        parser.process(input.as_bytes());
+        let area = Rect::new(0, 0, cols, 1);
+        let mut buf = Buffer::empty(area);
+        PseudoTerminal::new(parser.screen(), &[], None).render(area, &mut buf);
+        buf
+    }
+
+    #[test]
+    fn test_wide_cells_skip_continuation() {
+        let buf = render("a中b😀c\x1b[?25l", 8);
+        let symbols: Vec<&str> = (0..8).map(|x| buf[(x, 0)].symbol()).collect();
+        assert_eq!(symbols, vec!["a", "中", " ", "b", "😀", " ", "c", " "]);
+        let skipped: Vec<bool> = (0..8).map(|x| buf[(x, 0)].skip).collect();
+        assert_eq!(
+            skipped,
+            vec![false, false, true, false, false, true, false, false]
+        );
+    }
+
+    #[test]
+    fn test_wide_cell_at_right_edge_is_clipped() {
+        // The screen is wider than the pane, so "中" starts in its last column
+        let mut parser = vt100::Parser::new(1, 4, 0);
+        parser.process("ab中\x1b[?25l".as_bytes());
+        let area = Rect::new(0, 0, 3, 1);
+        let mut buf = Buffer::empty(area);
+        PseudoTerminal::new(parser.screen(), &[], None).render(area, &mut buf);
+        let symbols: Vec<&str> = (0..3).map(|x| buf[(x, 0)].symbol()).collect();
+        assert_eq!(symbols, vec!["a", "b", " "]);
+    }
+}
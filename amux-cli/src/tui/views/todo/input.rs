@@ -10,7 +10,7 @@ use crate::tui::input::utils::{
 };
 use crate::tui::state::{AsyncAction, InputMode};
 use crate::tui::widgets::virtual_list::VirtualList;
-use amux_config::Action;
+use amux_config::{Action, Confirmation};
 use crossterm::event::{KeyCode, KeyEvent};
 
 /// Handle TODO popup mode (main TODO list view)
@@ -129,9 +129,14 @@ fn execute_todo_action(app: &mut App, action: Action) -> Option<AsyncAction> {
             None
         }
 
+        Action::Undo => Some(AsyncAction::Undo),
+
         Action::DeleteTodo => {
             if let Some(&item_idx) = app.todo.display_order.get(app.todo.cursor) {
                 if let Some(item) = app.todo.items.get(item_idx).cloned() {
+                    if !app.config.options.confirms(Confirmation::DeleteTodo) {
+                        return Some(AsyncAction::DeleteTodo { todo_id: item.id });
+                    }
                     app.save_focus();
                     app.input_mode = InputMode::ConfirmDeleteTodo {
                         todo_id: item.id.clone(),
@@ -168,7 +173,7 @@ pub fn handle_add_todo_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncAc
                 return None;
             }
 
-            let title = app.text_input.content().to_string();
+            let title = app.text_input.content();
             app.text_input.clear();
 
             // Extract parent_id before changing mode
@@ -204,7 +209,7 @@ pub fn handle_edit_todo_mode_sync(app: &mut App, key: KeyEvent) -> Option<AsyncA
                 return None;
             }
 
-            let title = app.text_input.content().to_string();
+            let title = app.text_input.content();
             app.text_input.clear();
 
             if let InputMode::EditTodo { todo_id } =
@@ -236,7 +241,7 @@ pub fn handle_edit_todo_description_mode_sync(app: &mut App, key: KeyEvent) -> O
             let description = if app.text_input.is_empty() {
                 None
             } else {
-                Some(app.text_input.content().to_string())
+                Some(app.text_input.content())
             };
             app.text_input.clear();
 
@@ -265,6 +270,11 @@ pub fn handle_confirm_delete_todo_sync(app: &mut App, key: KeyEvent) -> Option<A
         None
     };
 
+    if key.code == KeyCode::Char('a') {
+        app.stop_confirming(Confirmation::DeleteTodo);
+        return todo_id.map(|id| AsyncAction::DeleteTodo { todo_id: id });
+    }
+
     handle_confirmation_with_enter(
         app,
         &key,
@@ -2,12 +2,13 @@
 
 use crate::tui::app::App;
 use crate::tui::state::InputMode;
+use crate::tui::widgets::helpers::truncate_to_width;
 use amux_config::actions::Action;
 use amux_config::keybind::BindingContext;
 use amux_proto::daemon::TodoItem;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     widgets::{Block, BorderType, Borders, List, ListItem, Paragraph},
     Frame,
 };
@@ -44,13 +45,14 @@ fn get_input_title(app: &App) -> &'static str {
         InputMode::AddTodo { parent_id: Some(_) } => "Add Child TODO:",
         InputMode::AddTodo { parent_id: None } => "Add TODO:",
         InputMode::EditTodo { .. } => "Edit Title:",
-        InputMode::EditTodoDescription { .. } => "Edit Description:",
+        InputMode::EditTodoDescription { .. } => "Edit Description (Alt+Enter=save):",
         _ => "",
     }
 }
 
 /// Draw TODO popup (main TODO list)
 pub fn draw_todo_popup(f: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     // Create centered popup (70% width, 80% height)
     let popup_width = (area.width * 70) / 100;
     let popup_height = (area.height * 80) / 100;
@@ -65,7 +67,7 @@ pub fn draw_todo_popup(f: &mut Frame, area: Rect, app: &App) {
 
     // Clear background
     let background = Block::default()
-        .style(Style::default().bg(Color::Black))
+        .style(Style::default().bg(theme.bg_level0))
         .borders(Borders::NONE);
     f.render_widget(background, area);
 
@@ -79,7 +81,7 @@ pub fn draw_todo_popup(f: &mut Frame, area: Rect, app: &App) {
     let block = Block::default()
         .title(title)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow));
+        .border_style(Style::default().fg(theme.neon_yellow));
     f.render_widget(block, popup_area);
 
     // Inner area for content
@@ -93,12 +95,23 @@ pub fn draw_todo_popup(f: &mut Frame, area: Rect, app: &App) {
     // Determine if we need an input area
     let in_input_mode = is_todo_input_mode(app);
 
+    // The input box grows with the wrapped text, up to 6 lines
+    let input_rows = if in_input_mode {
+        app.text_input
+            .wrap(inner.width.saturating_sub(2) as usize)
+            .lines
+            .len()
+            .min(6) as u16
+    } else {
+        1
+    };
+
     // Split into list area and input/help area
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Min(0),    // TODO list
-            Constraint::Length(3), // Input area or help text
+            Constraint::Min(0),                 // TODO list
+            Constraint::Length(input_rows + 2), // Input area or help text
         ])
         .split(inner);
 
@@ -116,12 +129,12 @@ pub fn draw_todo_popup(f: &mut Frame, area: Rect, app: &App) {
 
             let style = if display_idx == app.todo.cursor {
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(theme.neon_yellow)
                     .add_modifier(Modifier::BOLD)
             } else if item.completed {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(theme.text_disabled)
             } else {
-                Style::default().fg(Color::White)
+                Style::default().fg(theme.text_primary)
             };
 
             let text = if let Some(desc) = &item.description {
@@ -149,21 +162,14 @@ pub fn draw_todo_popup(f: &mut Frame, area: Rect, app: &App) {
             .title(input_title)
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(Color::Cyan));
+            .border_style(Style::default().fg(theme.neon_cyan));
 
         let input_inner = input_block.inner(chunks[1]);
         f.render_widget(input_block, chunks[1]);
 
-        // Input text
-        let input =
-            Paragraph::new(app.text_input.content()).style(Style::default().fg(Color::Yellow));
-        f.render_widget(input, input_inner);
-
-        // Cursor
-        f.set_cursor_position((
-            input_inner.x + app.text_input.cursor_display_offset() as u16,
-            input_inner.y,
-        ));
+        // Input text and cursor
+        app.text_input
+            .render_wrapped(f, input_inner, Style::default().fg(theme.neon_yellow));
     } else {
         // Draw help text
         let help_text = format!(
@@ -183,7 +189,7 @@ pub fn draw_todo_popup(f: &mut Frame, area: Rect, app: &App) {
             key(app, Action::ClosePopup),
         );
         let help = Paragraph::new(help_text)
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.text_disabled))
             .block(
                 Block::default()
                     .borders(Borders::ALL)
@@ -213,7 +219,8 @@ pub fn draw_edit_description_overlay(f: &mut Frame, area: Rect, app: &App) {
 }
 
 /// Draw confirm delete TODO overlay
-pub fn draw_confirm_delete_todo_overlay(f: &mut Frame, area: Rect, title: &str) {
+pub fn draw_confirm_delete_todo_overlay(f: &mut Frame, area: Rect, app: &App, title: &str) {
+    let theme = &app.theme;
     let popup_width = 50.min(area.width.saturating_sub(4));
     let popup_height = 5;
     let x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
@@ -221,22 +228,18 @@ pub fn draw_confirm_delete_todo_overlay(f: &mut Frame, area: Rect, title: &str)
     let popup_area = Rect::new(x, y, popup_width, popup_height);
 
     let text = format!(
-        "Delete \"{}\"?\n\n[y] Yes  [n] No",
-        if title.len() > 30 {
-            format!("{}...", &title[..27])
-        } else {
-            title.to_string()
-        }
+        "Delete \"{}\"?\n\n[y] Yes  [a] Always  [n] No",
+        truncate_to_width(title, 30)
     );
 
     let confirm = Paragraph::new(text)
-        .style(Style::default().bg(Color::Black))
+        .style(Style::default().fg(theme.text_primary).bg(theme.bg_level0))
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(Color::Red).bg(Color::Black))
-                .style(Style::default().bg(Color::Black))
+                .border_style(Style::default().fg(theme.error).bg(theme.bg_level0))
+                .style(Style::default().bg(theme.bg_level0))
                 .title(" Confirm Delete "),
         );
 
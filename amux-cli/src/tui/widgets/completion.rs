@@ -0,0 +1,365 @@
+//! Completion popup driven by a TextInput
+//!
+//! The owner of the input fetches candidates for the word ending at the
+//! cursor (directory entries, branch names, ...) and opens the popup with
+//! them. While it is open, Tab/Down and S-Tab/Up move the selection, Enter
+//! accepts it into the input and Esc dismisses it; any other key is left to
+//! the input, after which `refilter` narrows the candidates to what was
+//! typed.
+//!
+//! Candidates opened with `open` match by prefix; `suggest` shows fuzzy
+//! matches as the user types, best first.
+
+use super::super::theme::Theme;
+use super::TextInput;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+/// Rows of candidates shown at once
+const MAX_VISIBLE: usize = 8;
+
+#[derive(Debug, Default)]
+pub struct Completion {
+    /// Byte offset in the input where the completed word starts
+    start: usize,
+    /// Everything the source offered
+    candidates: Vec<String>,
+    /// Indices into `candidates` matching the typed word
+    matches: Vec<usize>,
+    selected: usize,
+    open: bool,
+    /// Match the typed characters in order rather than as a prefix
+    fuzzy: bool,
+}
+
+impl Completion {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the popup is showing
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Offer `candidates` for the word starting at byte `start` of the input
+    ///
+    /// A single match is accepted right away. Returns false, leaving the
+    /// popup closed, if none match what was typed.
+    pub fn open(&mut self, input: &mut TextInput, start: usize, candidates: Vec<String>) -> bool {
+        self.start = start;
+        self.candidates = candidates;
+        self.open = true;
+        self.fuzzy = false;
+        self.refilter(input);
+        if self.matches.len() == 1 {
+            self.accept(input);
+            return true;
+        }
+        self.open
+    }
+
+    /// Show `candidates` fuzzily matching the word starting at byte `start`
+    ///
+    /// Unlike `open`, nothing is accepted; the popup stays closed when
+    /// nothing matches or a candidate is exactly what was typed.
+    pub fn suggest(&mut self, input: &TextInput, start: usize, candidates: Vec<String>) {
+        let typed = input.content();
+        if candidates
+            .iter()
+            .any(|c| Some(c.as_str()) == typed.get(start..))
+        {
+            self.close();
+            return;
+        }
+        self.start = start;
+        self.candidates = candidates;
+        self.open = true;
+        self.fuzzy = true;
+        self.selected = 0;
+        self.refilter(input);
+    }
+
+    /// Close the popup without changing the input
+    pub fn close(&mut self) {
+        self.open = false;
+        self.candidates.clear();
+        self.matches.clear();
+        self.selected = 0;
+    }
+
+    /// Narrow the candidates to those starting with the typed word
+    ///
+    /// Closes the popup if nothing matches or the word was deleted.
+    pub fn refilter(&mut self, input: &TextInput) {
+        if !self.open {
+            return;
+        }
+        let content = input.content();
+        let Some(word) = content.get(self.start..) else {
+            self.close();
+            return;
+        };
+        self.matches = if self.fuzzy {
+            let mut scored: Vec<(usize, usize)> = self
+                .candidates
+                .iter()
+                .enumerate()
+                .filter_map(|(i, candidate)| fuzzy_score(word, candidate).map(|s| (s, i)))
+                .collect();
+            scored.sort_by_key(|&(score, i)| (score, self.candidates[i].len()));
+            scored.into_iter().map(|(_, i)| i).collect()
+        } else {
+            self.candidates
+                .iter()
+                .enumerate()
+                .filter(|(_, candidate)| candidate.starts_with(word))
+                .map(|(i, _)| i)
+                .collect()
+        };
+        self.selected = self.selected.min(self.matches.len().saturating_sub(1));
+        if self.matches.is_empty() {
+            self.close();
+        }
+    }
+
+    /// Handle a key while the popup is open
+    ///
+    /// Returns false for keys the popup leaves to the input, which the
+    /// caller should follow with `refilter`.
+    pub fn handle_key(&mut self, key: &KeyEvent, input: &mut TextInput) -> bool {
+        if !self.open {
+            return false;
+        }
+        match key.code {
+            KeyCode::Tab | KeyCode::Down => self.move_selection(true),
+            KeyCode::BackTab | KeyCode::Up => self.move_selection(false),
+            KeyCode::Enter => self.accept(input),
+            KeyCode::Esc => self.close(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// The highlighted candidate
+    pub fn selected(&self) -> Option<&str> {
+        self.matches
+            .get(self.selected)
+            .map(|&i| self.candidates[i].as_str())
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        let len = self.matches.len();
+        if len == 0 {
+            return;
+        }
+        self.selected = if down {
+            (self.selected + 1) % len
+        } else {
+            (self.selected + len - 1) % len
+        };
+    }
+
+    /// Replace the typed word with the selected candidate and close
+    fn accept(&mut self, input: &mut TextInput) {
+        if let Some(candidate) = self.selected() {
+            let content = input.content();
+            let head = content.get(..self.start).unwrap_or(&content);
+            input.set_content(format!("{}{}", head, candidate));
+        }
+        self.close();
+    }
+
+    /// Draw the popup under `anchor`, the row holding the input
+    pub fn render(&self, f: &mut Frame, anchor: Rect, theme: &Theme) {
+        if !self.open {
+            return;
+        }
+        let area = f.area();
+        let first = self.selected.saturating_sub(MAX_VISIBLE - 1);
+        let shown: Vec<&str> = self
+            .matches
+            .iter()
+            .skip(first)
+            .take(MAX_VISIBLE)
+            .map(|&i| self.candidates[i].as_str())
+            .collect();
+
+        let width = shown.iter().map(|c| c.width()).max().unwrap_or(0) as u16 + 4;
+        let width = width.min(area.width);
+        let height = (shown.len() as u16 + 2).min(area.height);
+        let x = anchor.x.min(area.right().saturating_sub(width));
+        // Below the input, or above it when there's no room
+        let y = if anchor.bottom() + height <= area.bottom() {
+            anchor.bottom()
+        } else {
+            anchor.y.saturating_sub(height)
+        };
+        let popup = Rect::new(x, y, width, height);
+
+        let items: Vec<ListItem> = shown
+            .iter()
+            .enumerate()
+            .map(|(i, candidate)| {
+                let style = if first + i == self.selected {
+                    Style::default()
+                        .fg(theme.neon_yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text_primary)
+                };
+                ListItem::new(format!(" {}", candidate)).style(style)
+            })
+            .collect();
+        let list = List::new(items).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.text_tertiary))
+                .style(Style::default().bg(theme.bg_level0)),
+        );
+        f.render_widget(Clear, popup);
+        f.render_widget(list, popup);
+    }
+}
+
+/// How well `candidate` matches the typed `word`, lower is better
+///
+/// The characters of `word` must appear in `candidate` in order, ignoring
+/// case. Each character skipped before or between them costs one.
+fn fuzzy_score(word: &str, candidate: &str) -> Option<usize> {
+    let mut chars = candidate.chars().flat_map(char::to_lowercase);
+    let mut score = 0;
+    for wanted in word.chars().flat_map(char::to_lowercase) {
+        loop {
+            if chars.next()? == wanted {
+                break;
+            }
+            score += 1;
+        }
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn candidates() -> Vec<String> {
+        vec!["api/".to_string(), "app/".to_string(), "web/".to_string()]
+    }
+
+    #[test]
+    fn test_single_match_is_accepted() {
+        let mut input = TextInput::with_content("packages/w");
+        let mut completion = Completion::new();
+
+        assert!(completion.open(&mut input, 9, candidates()));
+        assert!(!completion.is_open());
+        assert_eq!(input.content(), "packages/web/");
+    }
+
+    #[test]
+    fn test_no_match_stays_closed() {
+        let mut input = TextInput::with_content("x");
+        let mut completion = Completion::new();
+
+        assert!(!completion.open(&mut input, 0, candidates()));
+        assert!(!completion.is_open());
+        assert_eq!(input.content(), "x");
+    }
+
+    #[test]
+    fn test_cycle_and_accept() {
+        let mut input = TextInput::with_content("a");
+        let mut completion = Completion::new();
+        assert!(completion.open(&mut input, 0, candidates()));
+        assert_eq!(completion.selected(), Some("api/"));
+
+        assert!(completion.handle_key(&key(KeyCode::Tab), &mut input));
+        assert_eq!(completion.selected(), Some("app/"));
+        assert!(completion.handle_key(&key(KeyCode::Tab), &mut input));
+        assert_eq!(completion.selected(), Some("api/"));
+        assert!(completion.handle_key(&key(KeyCode::Up), &mut input));
+        assert_eq!(completion.selected(), Some("app/"));
+
+        assert!(completion.handle_key(&key(KeyCode::Enter), &mut input));
+        assert!(!completion.is_open());
+        assert_eq!(input.content(), "app/");
+    }
+
+    #[test]
+    fn test_typing_filters_and_esc_dismisses() {
+        let mut input = TextInput::with_content("a");
+        let mut completion = Completion::new();
+        completion.open(&mut input, 0, candidates());
+
+        assert!(!completion.handle_key(&key(KeyCode::Char('p')), &mut input));
+        input.insert('p');
+        input.insert('p');
+        completion.refilter(&input);
+        assert_eq!(completion.selected(), Some("app/"));
+
+        assert!(completion.handle_key(&key(KeyCode::Esc), &mut input));
+        assert!(!completion.is_open());
+        assert_eq!(input.content(), "app");
+
+        // Closed, keys go to the input
+        assert!(!completion.handle_key(&key(KeyCode::Enter), &mut input));
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert_eq!(fuzzy_score("", "main"), Some(0));
+        assert_eq!(fuzzy_score("feat", "feature/login"), Some(0));
+        assert_eq!(fuzzy_score("fl", "feature/login"), Some(7));
+        assert_eq!(fuzzy_score("LOGIN", "feature/login"), Some(8));
+        assert_eq!(fuzzy_score("lf", "feature/login"), None);
+    }
+
+    #[test]
+    fn test_suggest_ranks_fuzzy_matches() {
+        let branches = vec![
+            "fix/login".to_string(),
+            "feature/login".to_string(),
+            "main".to_string(),
+        ];
+        let mut input = TextInput::with_content("flog");
+        let mut completion = Completion::new();
+
+        completion.suggest(&input, 0, branches.clone());
+        assert!(completion.is_open());
+        assert_eq!(completion.selected(), Some("fix/login"));
+        assert!(completion.handle_key(&key(KeyCode::Tab), &mut input));
+        assert_eq!(completion.selected(), Some("feature/login"));
+        assert!(completion.handle_key(&key(KeyCode::Enter), &mut input));
+        assert_eq!(input.content(), "feature/login");
+
+        // Nothing to suggest for a name typed in full
+        completion.suggest(&input, 0, branches);
+        assert!(!completion.is_open());
+    }
+
+    #[test]
+    fn test_deleting_the_word_closes() {
+        let mut input = TextInput::with_content("src/a");
+        let mut completion = Completion::new();
+        completion.open(&mut input, 4, candidates());
+        assert!(completion.is_open());
+
+        input.set_content("src");
+        completion.refilter(&input);
+        assert!(!completion.is_open());
+    }
+}
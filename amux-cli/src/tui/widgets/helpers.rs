@@ -1,12 +1,15 @@
-//! Word-level diff highlighting utilities
+//! Word-level diff highlighting and display width utilities
 
 use super::super::highlight::Highlighter;
+use super::super::theme::Theme;
 use amux_proto::daemon::{DiffLine, LineType};
 use ratatui::{
     style::{Color, Modifier, Style},
     text::Span,
 };
 use std::sync::OnceLock;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 /// Global highlighter instance (lazy initialized)
 pub fn get_highlighter() -> &'static Highlighter {
@@ -21,9 +24,40 @@ pub enum DiffToken {
     Changed(String),
 }
 
+/// Lines whose token counts multiply past this fall back to whitespace
+/// separated words, keeping the LCS table small
+const MAX_TOKEN_PAIRS: usize = 250_000;
+
 /// Compute word-level diff between two strings
 /// Returns tokens for the "new" side (addition line)
+///
+/// Lines are split into runs of word characters, with every other character
+/// (punctuation, whitespace, wide CJK characters) a token of its own, so
+/// `foo.bar()` to `foo.baz()` only marks `baz`. The tokens then cover the
+/// whole new line; adjacent ones of the same kind are merged.
 pub fn compute_word_diff(old_line: &str, new_line: &str) -> Vec<DiffToken> {
+    let old_tokens = split_diff_tokens(old_line);
+    let new_tokens = split_diff_tokens(new_line);
+    if old_tokens.len() * new_tokens.len() > MAX_TOKEN_PAIRS {
+        return compute_whitespace_word_diff(old_line, new_line);
+    }
+    if old_tokens.is_empty() || new_tokens.is_empty() {
+        return vec![DiffToken::Changed(new_line.to_string())];
+    }
+
+    let mut result: Vec<DiffToken> = Vec::new();
+    for token in diff_against_lcs(&old_tokens, &new_tokens) {
+        match (result.last_mut(), token) {
+            (Some(DiffToken::Same(text)), DiffToken::Same(next))
+            | (Some(DiffToken::Changed(text)), DiffToken::Changed(next)) => text.push_str(&next),
+            (_, token) => result.push(token),
+        }
+    }
+    result
+}
+
+/// Word diff over whitespace separated words, for very long lines
+fn compute_whitespace_word_diff(old_line: &str, new_line: &str) -> Vec<DiffToken> {
     let old_words: Vec<&str> = old_line.split_whitespace().collect();
     let new_words: Vec<&str> = new_line.split_whitespace().collect();
 
@@ -31,29 +65,32 @@ pub fn compute_word_diff(old_line: &str, new_line: &str) -> Vec<DiffToken> {
         // If either is empty, everything is changed
         return vec![DiffToken::Changed(new_line.to_string())];
     }
+    diff_against_lcs(&old_words, &new_words)
+}
 
-    // Simple LCS-based word diff
+/// Mark the new tokens outside the longest common subsequence as changed
+fn diff_against_lcs(old_tokens: &[&str], new_tokens: &[&str]) -> Vec<DiffToken> {
     let mut result = Vec::new();
-    let lcs = compute_lcs(&old_words, &new_words);
+    let lcs = compute_lcs(old_tokens, new_tokens);
 
     let mut old_idx = 0;
     let mut new_idx = 0;
     let mut lcs_idx = 0;
 
-    while new_idx < new_words.len() {
-        if lcs_idx < lcs.len() && new_idx < new_words.len() && new_words[new_idx] == lcs[lcs_idx] {
-            // This word is in LCS - it's the same
-            // Skip any old words that aren't in the match
-            while old_idx < old_words.len() && old_words[old_idx] != lcs[lcs_idx] {
+    while new_idx < new_tokens.len() {
+        if lcs_idx < lcs.len() && new_tokens[new_idx] == lcs[lcs_idx] {
+            // This token is in LCS - it's the same
+            // Skip any old tokens that aren't in the match
+            while old_idx < old_tokens.len() && old_tokens[old_idx] != lcs[lcs_idx] {
                 old_idx += 1;
             }
-            result.push(DiffToken::Same(new_words[new_idx].to_string()));
+            result.push(DiffToken::Same(new_tokens[new_idx].to_string()));
             new_idx += 1;
             old_idx += 1;
             lcs_idx += 1;
         } else {
-            // This word is not in LCS - it's changed/added
-            result.push(DiffToken::Changed(new_words[new_idx].to_string()));
+            // This token is not in LCS - it's changed/added
+            result.push(DiffToken::Changed(new_tokens[new_idx].to_string()));
             new_idx += 1;
         }
     }
@@ -61,6 +98,28 @@ pub fn compute_word_diff(old_line: &str, new_line: &str) -> Vec<DiffToken> {
     result
 }
 
+/// Split a line into runs of narrow word characters and single other
+/// graphemes, together covering the whole line
+fn split_diff_tokens(line: &str) -> Vec<&str> {
+    let is_word = |g: &str| g.width() == 1 && g.chars().all(|c| c.is_alphanumeric() || c == '_');
+    let mut tokens = Vec::new();
+    let mut word_start = None;
+    for (idx, grapheme) in line.grapheme_indices(true) {
+        if is_word(grapheme) {
+            word_start.get_or_insert(idx);
+            continue;
+        }
+        if let Some(start) = word_start.take() {
+            tokens.push(&line[start..idx]);
+        }
+        tokens.push(grapheme);
+    }
+    if let Some(start) = word_start {
+        tokens.push(&line[start..]);
+    }
+    tokens
+}
+
 /// Compute Longest Common Subsequence of word slices
 pub fn compute_lcs<'a>(a: &[&'a str], b: &[&'a str]) -> Vec<&'a str> {
     let m = a.len();
@@ -113,7 +172,7 @@ pub fn find_paired_deletion(lines: &[DiffLine], addition_idx: usize) -> Option<u
         match line_type {
             LineType::Deletion => return Some(i),
             LineType::Addition => continue, // Skip other additions
-            LineType::Context | LineType::Header | LineType::Unspecified => {
+            LineType::Context | LineType::Header | LineType::Collapsed | LineType::Unspecified => {
                 // Hit a context line, stop looking
                 return None;
             }
@@ -131,7 +190,7 @@ pub fn find_paired_addition(lines: &[DiffLine], deletion_idx: usize) -> Option<u
         match line_type {
             LineType::Addition => return Some(i),
             LineType::Deletion => continue, // Skip other deletions
-            LineType::Context | LineType::Header | LineType::Unspecified => {
+            LineType::Context | LineType::Header | LineType::Collapsed | LineType::Unspecified => {
                 // Hit a context line, stop looking
                 return None;
             }
@@ -142,6 +201,7 @@ pub fn find_paired_addition(lines: &[DiffLine], deletion_idx: usize) -> Option<u
 
 /// Render a diff line with word-level highlighting and syntax highlighting
 pub fn render_word_diff_line<'a>(
+    theme: &Theme,
     content: &str,
     paired_content: Option<&str>,
     is_addition: bool,
@@ -149,16 +209,11 @@ pub fn render_word_diff_line<'a>(
     is_focused: bool,
     file_path: &str,
 ) -> Vec<Span<'a>> {
+    // Changed words use the plain diff color, set off by bold and underline
     let base_color = if is_addition {
-        Color::Green
-    } else {
-        Color::Red
-    };
-
-    let highlight_color = if is_addition {
-        Color::LightGreen
+        theme.diff_add
     } else {
-        Color::LightRed
+        theme.diff_del
     };
 
     // Get syntax-highlighted spans first
@@ -168,18 +223,9 @@ pub fn render_word_diff_line<'a>(
     match paired_content {
         Some(paired) => {
             // We have a paired line, compute word diff
-            let tokens = if is_addition {
-                compute_word_diff(paired, content)
-            } else {
-                // For deletion, compute against the addition
-                compute_word_diff(content, paired)
-                    .into_iter()
-                    .map(|t| match t {
-                        DiffToken::Same(s) => DiffToken::Same(s),
-                        DiffToken::Changed(s) => DiffToken::Changed(s),
-                    })
-                    .collect()
-            };
+            // Tokens cover the shown line; for a deletion that is the old
+            // side, diffed against the addition the same way
+            let tokens = compute_word_diff(paired, content);
 
             // Build spans with word diff + syntax highlighting
             let mut spans = Vec::new();
@@ -218,17 +264,18 @@ pub fn render_word_diff_line<'a>(
                     let word_style = if is_changed {
                         if is_selected && is_focused {
                             Style::default()
-                                .fg(highlight_color)
+                                .fg(base_color)
                                 .add_modifier(Modifier::BOLD | Modifier::REVERSED)
                         } else {
                             Style::default()
-                                .fg(highlight_color)
+                                .fg(base_color)
                                 .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
                         }
                     } else {
                         // Use syntax highlighting color with diff tint
-                        let fg_color = syntax_style.and_then(|s| s.fg).unwrap_or(base_color);
-                        let tinted_color = tint_color(fg_color, is_addition);
+                        let tinted_color = syntax_style
+                            .and_then(|s| s.fg)
+                            .map_or(base_color, |fg| tint_color(fg, is_addition, base_color));
                         if is_selected && is_focused {
                             Style::default()
                                 .fg(tinted_color)
@@ -269,8 +316,9 @@ pub fn render_word_diff_line<'a>(
             // No paired line, apply syntax highlighting with diff tint
             let mut spans = Vec::new();
             for (style, text) in syntax_spans {
-                let fg_color = style.fg.unwrap_or(base_color);
-                let tinted_color = tint_color(fg_color, is_addition);
+                let tinted_color = style
+                    .fg
+                    .map_or(base_color, |fg| tint_color(fg, is_addition, base_color));
                 let final_style = if is_selected && is_focused {
                     Style::default()
                         .fg(tinted_color)
@@ -313,7 +361,9 @@ pub fn find_syntax_style_for_range(
 }
 
 /// Apply a green/red tint to a color for diff highlighting
-pub fn tint_color(color: Color, is_addition: bool) -> Color {
+///
+/// Non-RGB colors can't be tinted and are replaced by `fallback`.
+pub fn tint_color(color: Color, is_addition: bool, fallback: Color) -> Color {
     match color {
         Color::Rgb(r, g, b) => {
             if is_addition {
@@ -332,13 +382,304 @@ pub fn tint_color(color: Color, is_addition: bool) -> Color {
                 )
             }
         }
-        _ => {
-            // For non-RGB colors, use standard diff colors
-            if is_addition {
-                Color::Green
-            } else {
-                Color::Red
+        _ => fallback,
+    }
+}
+
+/// Byte ranges of the case-insensitive occurrences of `query` in `text`
+///
+/// Occurrences don't overlap; an empty query matches nothing.
+pub fn find_matches(text: &str, query: &str) -> Vec<(usize, usize)> {
+    let query: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches = Vec::new();
+    let mut from = 0;
+    for (start, _) in text.char_indices() {
+        if start < from {
+            continue;
+        }
+        if let Some(end) = match_at(text, start, &query) {
+            matches.push((start, end));
+            from = end;
+        }
+    }
+    matches
+}
+
+/// End of the match of the lowercased `query` at byte `start` of `text`
+fn match_at(text: &str, start: usize, query: &[char]) -> Option<usize> {
+    let mut remaining = query;
+    for (offset, c) in text[start..].char_indices() {
+        for lower in c.to_lowercase() {
+            let (first, rest) = remaining.split_first()?;
+            if *first != lower {
+                return None;
+            }
+            remaining = rest;
+        }
+        if remaining.is_empty() {
+            return Some(start + offset + c.len_utf8());
+        }
+    }
+    None
+}
+
+/// Restyle the case-insensitive occurrences of `query` across a line of spans
+pub fn highlight_matches<'a>(spans: Vec<Span<'a>>, query: &str, style: Style) -> Vec<Span<'a>> {
+    let text: String = spans.iter().map(|s| s.content.as_ref()).collect();
+    let matches = find_matches(&text, query);
+    if matches.is_empty() {
+        return spans;
+    }
+
+    let mut result = Vec::with_capacity(spans.len() + matches.len() * 2);
+    let mut span_start = 0;
+    for span in spans {
+        let span_end = span_start + span.content.len();
+        let mut cut = span_start;
+        for &(match_start, match_end) in &matches {
+            let start = match_start.clamp(span_start, span_end);
+            let end = match_end.clamp(span_start, span_end);
+            if start >= end {
+                continue;
+            }
+            if start > cut {
+                let part = span.content[cut - span_start..start - span_start].to_string();
+                result.push(Span::styled(part, span.style));
+            }
+            let part = span.content[start - span_start..end - span_start].to_string();
+            result.push(Span::styled(part, span.style.patch(style)));
+            cut = end;
+        }
+        if cut == span_start {
+            result.push(span);
+        } else if cut < span_end {
+            let part = span.content[cut - span_start..].to_string();
+            result.push(Span::styled(part, span.style));
+        }
+        span_start = span_end;
+    }
+    result
+}
+
+/// Cut `s` to at most `max_width` columns, ending in `…` if anything was cut
+///
+/// Cuts fall between grapheme clusters, so a wide character is never split
+/// and combining marks stay with their base.
+pub fn truncate_to_width(s: &str, max_width: usize) -> String {
+    if s.width() <= max_width {
+        return s.to_string();
+    }
+    let budget = max_width.saturating_sub(1);
+    let mut out = String::new();
+    let mut width = 0;
+    for grapheme in s.graphemes(true) {
+        width += grapheme.width();
+        if width > budget {
+            break;
+        }
+        out.push_str(grapheme);
+    }
+    if max_width > 0 {
+        out.push('…');
+    }
+    out
+}
+
+/// Like [`truncate_to_width`], but keeps the end of `s` (for paths)
+pub fn truncate_start_to_width(s: &str, max_width: usize) -> String {
+    if s.width() <= max_width {
+        return s.to_string();
+    }
+    let budget = max_width.saturating_sub(1);
+    let mut kept = Vec::new();
+    let mut width = 0;
+    for grapheme in s.graphemes(true).rev() {
+        width += grapheme.width();
+        if width > budget {
+            break;
+        }
+        kept.push(grapheme);
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+    std::iter::once("…").chain(kept.into_iter().rev()).collect()
+}
+
+/// `s` truncated and then padded with spaces to exactly `width` columns
+pub fn fit_to_width(s: &str, width: usize) -> String {
+    let mut out = truncate_to_width(s, width);
+    let pad = width.saturating_sub(out.width());
+    out.extend(std::iter::repeat_n(' ', pad));
+    out
+}
+
+/// Split `s` at its newlines and into rows of at most `width` columns
+///
+/// Rows break between grapheme clusters; a row is never empty unless its
+/// source line is.
+pub fn wrap_to_width(s: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut rows = Vec::new();
+    for line in s.lines() {
+        let mut row = String::new();
+        let mut row_width = 0;
+        for grapheme in line.graphemes(true) {
+            let w = grapheme.width();
+            if row_width + w > width && !row.is_empty() {
+                rows.push(std::mem::take(&mut row));
+                row_width = 0;
             }
+            row.push_str(grapheme);
+            row_width += w;
+        }
+        rows.push(row);
+    }
+    if rows.is_empty() {
+        rows.push(String::new());
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// ASCII, CJK (2 columns each), an emoji ZWJ sequence (2) and a combining accent
+    const MIXED: &str = "ab中文👩‍💻e\u{301}";
+
+    fn changed(tokens: &[DiffToken]) -> Vec<&str> {
+        tokens
+            .iter()
+            .filter_map(|t| match t {
+                DiffToken::Changed(text) => Some(text.as_str()),
+                DiffToken::Same(_) => None,
+            })
+            .collect()
+    }
+
+    fn joined(tokens: &[DiffToken]) -> String {
+        tokens
+            .iter()
+            .map(|t| match t {
+                DiffToken::Same(text) | DiffToken::Changed(text) => text.as_str(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_word_diff_next_to_punctuation() {
+        let tokens = compute_word_diff("let x = foo.bar();", "let x = foo.baz();");
+        assert_eq!(changed(&tokens), vec!["baz"]);
+        assert_eq!(joined(&tokens), "let x = foo.baz();");
+
+        let tokens = compute_word_diff("call(a, b)", "call(a, b, c)");
+        assert_eq!(changed(&tokens), vec![", c"]);
+
+        let tokens = compute_word_diff("x == y", "x != y");
+        assert_eq!(changed(&tokens), vec!["!"]);
+    }
+
+    #[test]
+    fn test_word_diff_cjk() {
+        let tokens = compute_word_diff("// 你好世界", "// 你好地球");
+        assert_eq!(changed(&tokens), vec!["地球"]);
+        // Tokens cover the line, so its width is kept
+        assert_eq!(joined(&tokens).width(), "// 你好地球".width());
+
+        let tokens = compute_word_diff(MIXED, "ab中文👩‍💻o\u{301}");
+        assert_eq!(changed(&tokens), vec!["o\u{301}"]);
+    }
+
+    #[test]
+    fn test_word_diff_falls_back_on_long_lines() {
+        let old = "a.b ".repeat(400);
+        let new = format!("{}c.d", old);
+        let tokens = compute_word_diff(&old, &new);
+        // Whole whitespace separated words, without the whitespace
+        assert_eq!(changed(&tokens), vec!["c.d"]);
+        assert_eq!(tokens.len(), 401);
+
+        assert_eq!(
+            compute_word_diff("", "new"),
+            vec![DiffToken::Changed("new".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_find_matches() {
+        assert_eq!(
+            find_matches("Foo foo FOO", "foo"),
+            vec![(0, 3), (4, 7), (8, 11)]
+        );
+        assert_eq!(find_matches("aaaa", "aa"), vec![(0, 2), (2, 4)]);
+        assert_eq!(find_matches("中文Ab中文", "ab"), vec![(6, 8)]);
+        assert!(find_matches("foo", "").is_empty());
+        assert!(find_matches("fo", "foo").is_empty());
+    }
+
+    #[test]
+    fn test_highlight_matches_across_spans() {
+        let marked = Style::default().add_modifier(Modifier::REVERSED);
+        let spans = vec![Span::raw("let na"), Span::raw("me = name;")];
+        let highlighted = highlight_matches(spans, "NAME", marked);
+        let parts: Vec<(&str, bool)> = highlighted
+            .iter()
+            .map(|s| (s.content.as_ref(), s.style == marked))
+            .collect();
+        assert_eq!(
+            parts,
+            vec![
+                ("let ", false),
+                ("na", true),
+                ("me", true),
+                (" = ", false),
+                ("name", true),
+                (";", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_truncate_to_width() {
+        assert_eq!(MIXED.width(), 9);
+        assert_eq!(truncate_to_width(MIXED, 9), MIXED);
+        assert_eq!(truncate_to_width(MIXED, 8), "ab中文…");
+        // "中" doesn't fit in the last column before the ellipsis
+        assert_eq!(truncate_to_width(MIXED, 4), "ab…");
+        assert_eq!(truncate_to_width(MIXED, 3), "ab…");
+        assert_eq!(truncate_to_width(MIXED, 1), "…");
+        assert_eq!(truncate_to_width(MIXED, 0), "");
+        for max in 0..12 {
+            assert!(truncate_to_width(MIXED, max).width() <= max);
         }
     }
+
+    #[test]
+    fn test_truncate_start_to_width() {
+        assert_eq!(truncate_start_to_width("src/中文/main.rs", 10), "…/main.rs");
+        assert_eq!(truncate_start_to_width("文件/目录.rs", 8), "…目录.rs");
+        assert_eq!(truncate_start_to_width(MIXED, 4), "…👩‍💻e\u{301}");
+        assert_eq!(truncate_start_to_width("short", 10), "short");
+    }
+
+    #[test]
+    fn test_fit_to_width() {
+        assert_eq!(fit_to_width("李雷", 6), "李雷  ");
+        assert_eq!(fit_to_width("Han Meimei", 6), "Han M…");
+        assert_eq!(fit_to_width("李雷韩梅梅", 5), "李雷…");
+        assert_eq!(fit_to_width("李雷韩梅梅", 5).width(), 5);
+    }
+
+    #[test]
+    fn test_wrap_to_width() {
+        assert_eq!(wrap_to_width("中文中文", 5), vec!["中文", "中文"]);
+        assert_eq!(wrap_to_width("ab中\n\ncd", 3), vec!["ab", "中", "", "cd"]);
+        assert_eq!(wrap_to_width("👩‍💻👩‍💻", 2), vec!["👩‍💻", "👩‍💻"]);
+        assert_eq!(wrap_to_width("", 10), vec![""]);
+    }
 }
@@ -1,6 +1,8 @@
+pub mod completion;
 pub mod helpers;
 pub mod text_input;
 pub mod virtual_list;
 
+pub use completion::Completion;
 pub use text_input::TextInput;
 pub use virtual_list::VirtualList;
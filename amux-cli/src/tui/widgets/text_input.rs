@@ -1,21 +1,53 @@
-//! TextInput: cursor-aware text buffer with Unicode support
+//! TextInput: cursor-aware multi-line text buffer with Unicode support
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use unicode_width::UnicodeWidthStr;
+use ratatui::{layout::Rect, style::Style, text::Line, widgets::Paragraph, Frame};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 /// A cursor-aware text input buffer with Unicode support.
 ///
 /// Handles:
-/// - Cursor position tracking (in char indices, not bytes)
+/// - Cursor position tracking as row and column (in char indices, not bytes)
 /// - Display width calculation for CJK/emoji characters
-/// - Navigation (left/right/home/end/ctrl+arrows)
+/// - Navigation (left/right/up/down/home/end/ctrl+arrows)
 /// - Insertion/deletion at cursor position
-#[derive(Debug, Clone, Default)]
+/// - Kill and yank of line parts (Ctrl+U/K/Y)
+///
+/// Inputs are single-line by default: Enter submits. Built with
+/// `multiline(true)`, Enter inserts a newline and Alt+Enter submits.
+#[derive(Debug, Clone)]
 pub struct TextInput {
-    /// The text content
-    buffer: String,
-    /// Cursor position as char index (not byte index)
-    cursor: usize,
+    /// Lines of text, never empty
+    lines: Vec<String>,
+    /// Cursor line
+    row: usize,
+    /// Cursor position in its line as char index (not byte index)
+    col: usize,
+    /// Whether Enter inserts a newline instead of submitting
+    multiline: bool,
+    /// Text removed by the last kill, inserted again by yank
+    kill_buffer: String,
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        Self {
+            lines: vec![String::new()],
+            row: 0,
+            col: 0,
+            multiline: false,
+            kill_buffer: String::new(),
+        }
+    }
+}
+
+/// Input text wrapped to a width, as laid out on screen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WrappedText {
+    /// Visual lines
+    pub lines: Vec<String>,
+    /// Cursor as (display column, visual line)
+    pub cursor: (usize, usize),
 }
 
 impl TextInput {
@@ -26,147 +58,240 @@ impl TextInput {
     /// Create with initial content, cursor at end
     #[allow(dead_code)]
     pub fn with_content(content: impl Into<String>) -> Self {
-        let buffer: String = content.into();
-        let cursor = buffer.chars().count();
-        Self { buffer, cursor }
+        let mut input = Self::default();
+        input.set_content(content);
+        input
     }
 
-    /// Get the buffer content
-    pub fn content(&self) -> &str {
-        &self.buffer
+    /// Builder: let Enter insert a newline instead of submitting
+    #[allow(dead_code)]
+    pub fn multiline(mut self, multiline: bool) -> Self {
+        self.multiline = multiline;
+        self
     }
 
-    /// Get cursor position (char index)
+    /// Switch between single-line and multi-line editing
+    pub fn set_multiline(&mut self, multiline: bool) {
+        self.multiline = multiline;
+    }
+
+    /// Whether Enter inserts a newline
+    pub fn is_multiline(&self) -> bool {
+        self.multiline
+    }
+
+    /// Get the buffer content, lines joined by '\n'
+    pub fn content(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    /// Get cursor position (char index into `content()`)
     #[allow(dead_code)]
     pub fn cursor_position(&self) -> usize {
-        self.cursor
+        self.lines[..self.row]
+            .iter()
+            .map(|line| line.chars().count() + 1)
+            .sum::<usize>()
+            + self.col
+    }
+
+    /// Get cursor line and column (char index in the line)
+    #[allow(dead_code)]
+    pub fn cursor_row_col(&self) -> (usize, usize) {
+        (self.row, self.col)
     }
 
-    /// Get text before cursor (Unicode-safe)
+    /// Get text of the cursor line before the cursor (Unicode-safe)
     pub fn text_before_cursor(&self) -> String {
-        self.buffer.chars().take(self.cursor).collect()
+        self.line().chars().take(self.col).collect()
     }
 
-    /// Get char at cursor (or None if at end)
+    /// Get char at cursor (or None at the end of the line)
     pub fn char_at_cursor(&self) -> Option<char> {
-        self.buffer.chars().nth(self.cursor)
+        self.line().chars().nth(self.col)
     }
 
-    /// Get text after cursor, excluding char at cursor (Unicode-safe)
+    /// Get text of the cursor line after the cursor, excluding char at cursor
     pub fn text_after_cursor(&self) -> String {
-        self.buffer.chars().skip(self.cursor + 1).collect()
+        self.line().chars().skip(self.col + 1).collect()
     }
 
-    /// Get display width of text before cursor (for terminal cursor positioning)
+    /// Get display width of the cursor line before the cursor (for terminal cursor positioning)
     pub fn cursor_display_offset(&self) -> usize {
-        let text_before_cursor: String = self.buffer.chars().take(self.cursor).collect();
-        UnicodeWidthStr::width(text_before_cursor.as_str())
+        UnicodeWidthStr::width(self.text_before_cursor().as_str())
     }
 
-    /// Get total display width
+    /// Get display width of the widest line
     #[allow(dead_code)]
     pub fn display_width(&self) -> usize {
-        UnicodeWidthStr::width(self.buffer.as_str())
+        self.lines
+            .iter()
+            .map(|line| UnicodeWidthStr::width(line.as_str()))
+            .max()
+            .unwrap_or(0)
     }
 
     /// Clear the buffer and reset cursor
     pub fn clear(&mut self) {
-        self.buffer.clear();
-        self.cursor = 0;
+        self.lines = vec![String::new()];
+        self.row = 0;
+        self.col = 0;
     }
 
     /// Set content (cursor goes to end)
     pub fn set_content(&mut self, content: impl Into<String>) {
-        self.buffer = content.into();
-        self.cursor = self.buffer.chars().count();
+        self.lines = content.into().split('\n').map(String::from).collect();
+        self.row = self.lines.len() - 1;
+        self.col = self.line().chars().count();
     }
 
-    /// Insert character at cursor position
+    /// Insert character at cursor position ('\n' splits the line)
     pub fn insert(&mut self, c: char) {
-        let byte_idx = self.cursor_to_byte_index();
-        self.buffer.insert(byte_idx, c);
-        self.cursor += 1;
+        if c == '\n' {
+            self.newline();
+            return;
+        }
+        let byte_idx = self.col_to_byte_index();
+        let row = self.row;
+        self.lines[row].insert(byte_idx, c);
+        self.col += 1;
     }
 
     /// Insert string at cursor position
-    #[allow(dead_code)]
     pub fn insert_str(&mut self, s: &str) {
-        let byte_idx = self.cursor_to_byte_index();
-        self.buffer.insert_str(byte_idx, s);
-        self.cursor += s.chars().count();
+        for (i, part) in s.split('\n').enumerate() {
+            if i > 0 {
+                self.newline();
+            }
+            let byte_idx = self.col_to_byte_index();
+            let row = self.row;
+            self.lines[row].insert_str(byte_idx, part);
+            self.col += part.chars().count();
+        }
     }
 
-    /// Delete character before cursor (Backspace)
+    /// Insert pasted text at cursor position
+    ///
+    /// Line endings are normalized; a single-line input gets spaces instead
+    /// of line breaks so the paste can't submit or split it.
+    pub fn paste(&mut self, text: &str) {
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        if self.multiline {
+            self.insert_str(&text);
+        } else {
+            self.insert_str(&text.trim_end_matches('\n').replace('\n', " "));
+        }
+    }
+
+    /// Split the line at the cursor
+    pub fn newline(&mut self) {
+        let byte_idx = self.col_to_byte_index();
+        let rest = self.lines[self.row].split_off(byte_idx);
+        self.lines.insert(self.row + 1, rest);
+        self.row += 1;
+        self.col = 0;
+    }
+
+    /// Delete character before cursor (Backspace), joining lines at a line start
     pub fn backspace(&mut self) -> bool {
-        if self.cursor > 0 {
-            self.cursor -= 1;
-            let byte_idx = self.cursor_to_byte_index();
-            let char_len = self.buffer[byte_idx..]
-                .chars()
-                .next()
-                .map_or(0, |c| c.len_utf8());
-            self.buffer.drain(byte_idx..byte_idx + char_len);
+        if self.col > 0 {
+            self.col -= 1;
+            self.remove_at_cursor();
+            true
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.line().chars().count();
+            self.lines[self.row].push_str(&line);
             true
         } else {
             false
         }
     }
 
-    /// Delete character at cursor (Delete key)
+    /// Delete character at cursor (Delete key), joining lines at a line end
     pub fn delete(&mut self) -> bool {
-        let char_count = self.buffer.chars().count();
-        if self.cursor < char_count {
-            let byte_idx = self.cursor_to_byte_index();
-            let char_len = self.buffer[byte_idx..]
-                .chars()
-                .next()
-                .map_or(0, |c| c.len_utf8());
-            self.buffer.drain(byte_idx..byte_idx + char_len);
+        if self.col < self.line().chars().count() {
+            self.remove_at_cursor();
+            true
+        } else if self.row + 1 < self.lines.len() {
+            let next = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&next);
             true
         } else {
             false
         }
     }
 
-    /// Move cursor left
+    /// Move cursor left, to the end of the previous line at a line start
     pub fn move_left(&mut self) -> bool {
-        if self.cursor > 0 {
-            self.cursor -= 1;
+        if self.col > 0 {
+            self.col -= 1;
+            true
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.col = self.line().chars().count();
             true
         } else {
             false
         }
     }
 
-    /// Move cursor right
+    /// Move cursor right, to the start of the next line at a line end
     pub fn move_right(&mut self) -> bool {
-        let char_count = self.buffer.chars().count();
-        if self.cursor < char_count {
-            self.cursor += 1;
+        if self.col < self.line().chars().count() {
+            self.col += 1;
+            true
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = 0;
             true
         } else {
             false
         }
     }
 
-    /// Move cursor to start (Home)
+    /// Move cursor to the previous line, keeping its display column
+    pub fn move_up(&mut self) -> bool {
+        if self.row == 0 {
+            return false;
+        }
+        let x = self.cursor_display_offset();
+        self.row -= 1;
+        self.col = col_at_width(self.line(), x);
+        true
+    }
+
+    /// Move cursor to the next line, keeping its display column
+    pub fn move_down(&mut self) -> bool {
+        if self.row + 1 >= self.lines.len() {
+            return false;
+        }
+        let x = self.cursor_display_offset();
+        self.row += 1;
+        self.col = col_at_width(self.line(), x);
+        true
+    }
+
+    /// Move cursor to start of line (Home)
     pub fn move_home(&mut self) {
-        self.cursor = 0;
+        self.col = 0;
     }
 
-    /// Move cursor to end (End)
+    /// Move cursor to end of line (End)
     pub fn move_end(&mut self) {
-        self.cursor = self.buffer.chars().count();
+        self.col = self.line().chars().count();
     }
 
     /// Move cursor to previous word boundary (Ctrl+Left)
     pub fn move_word_left(&mut self) -> bool {
-        if self.cursor == 0 {
+        let cursor = self.cursor_position();
+        if cursor == 0 {
             return false;
         }
 
-        let chars: Vec<char> = self.buffer.chars().collect();
-        let mut pos = self.cursor;
+        let chars: Vec<char> = self.content().chars().collect();
+        let mut pos = cursor;
 
         // Skip trailing whitespace/punctuation
         while pos > 0 && !chars[pos - 1].is_alphanumeric() {
@@ -178,8 +303,8 @@ impl TextInput {
             pos -= 1;
         }
 
-        if pos != self.cursor {
-            self.cursor = pos;
+        if pos != cursor {
+            self.set_cursor_position(pos);
             true
         } else {
             false
@@ -188,14 +313,15 @@ impl TextInput {
 
     /// Move cursor to next word boundary (Ctrl+Right)
     pub fn move_word_right(&mut self) -> bool {
-        let chars: Vec<char> = self.buffer.chars().collect();
+        let chars: Vec<char> = self.content().chars().collect();
         let len = chars.len();
+        let cursor = self.cursor_position();
 
-        if self.cursor >= len {
+        if cursor >= len {
             return false;
         }
 
-        let mut pos = self.cursor;
+        let mut pos = cursor;
 
         // Skip current word
         while pos < len && chars[pos].is_alphanumeric() {
@@ -207,39 +333,197 @@ impl TextInput {
             pos += 1;
         }
 
-        if pos != self.cursor {
-            self.cursor = pos;
+        if pos != cursor {
+            self.set_cursor_position(pos);
             true
         } else {
             false
         }
     }
 
-    /// Convert char index to byte index
-    fn cursor_to_byte_index(&self) -> usize {
-        self.buffer
-            .char_indices()
-            .nth(self.cursor)
+    /// Cut the line before the cursor (Ctrl+U)
+    pub fn kill_to_line_start(&mut self) -> bool {
+        if self.col == 0 {
+            return false;
+        }
+        let byte_idx = self.col_to_byte_index();
+        let row = self.row;
+        self.kill_buffer = self.lines[row].drain(..byte_idx).collect();
+        self.col = 0;
+        true
+    }
+
+    /// Cut the line after the cursor, or the line break at its end (Ctrl+K)
+    pub fn kill_to_line_end(&mut self) -> bool {
+        let byte_idx = self.col_to_byte_index();
+        if byte_idx < self.line().len() {
+            let row = self.row;
+            self.kill_buffer = self.lines[row].split_off(byte_idx);
+            true
+        } else if self.delete() {
+            self.kill_buffer = "\n".to_string();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Insert the last killed text at the cursor (Ctrl+Y)
+    pub fn yank(&mut self) -> bool {
+        if self.kill_buffer.is_empty() {
+            return false;
+        }
+        let text = self.kill_buffer.clone();
+        self.insert_str(&text);
+        true
+    }
+
+    /// Lay the text out in lines of at most `width` columns
+    ///
+    /// Lines break after the last space that fits, or mid-word when a word
+    /// is wider than `width`.
+    pub fn wrap(&self, width: usize) -> WrappedText {
+        let width = width.max(1);
+        let mut lines = Vec::new();
+        let mut cursor = (0, 0);
+
+        for (row, line) in self.lines.iter().enumerate() {
+            let chars: Vec<char> = line.chars().collect();
+            let segments = wrap_segments(&chars, width);
+            for (i, &(start, end)) in segments.iter().enumerate() {
+                let last = i + 1 == segments.len();
+                if row == self.row && self.col >= start && (self.col < end || last) {
+                    let x: usize = chars[start..self.col]
+                        .iter()
+                        .map(|c| c.width().unwrap_or(0))
+                        .sum();
+                    cursor = (x, lines.len());
+                }
+                lines.push(chars[start..end].iter().collect());
+            }
+            // A cursor after a full last segment goes to a line of its own
+            if row == self.row && cursor.0 >= width {
+                cursor = (0, lines.len());
+                lines.push(String::new());
+            }
+        }
+
+        WrappedText { lines, cursor }
+    }
+
+    /// Draw the text wrapped to `area` and place the terminal cursor
+    ///
+    /// Scrolls so that the cursor line stays visible.
+    pub fn render_wrapped(&self, f: &mut Frame, area: Rect, style: Style) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        let wrapped = self.wrap(area.width as usize);
+        let (x, y) = wrapped.cursor;
+        let scroll = y.saturating_sub(area.height as usize - 1);
+        let lines: Vec<Line> = wrapped
+            .lines
+            .into_iter()
+            .skip(scroll)
+            .take(area.height as usize)
+            .map(Line::from)
+            .collect();
+        f.render_widget(Paragraph::new(lines).style(style), area);
+        f.set_cursor_position((area.x + x as u16, area.y + (y - scroll) as u16));
+    }
+
+    /// Move the cursor to a char index into `content()`
+    fn set_cursor_position(&mut self, mut pos: usize) {
+        for (row, line) in self.lines.iter().enumerate() {
+            let len = line.chars().count();
+            if pos <= len || row + 1 == self.lines.len() {
+                self.row = row;
+                self.col = pos.min(len);
+                return;
+            }
+            pos -= len + 1;
+        }
+    }
+
+    /// The cursor line
+    fn line(&self) -> &str {
+        &self.lines[self.row]
+    }
+
+    /// Remove the char at the cursor
+    fn remove_at_cursor(&mut self) {
+        let byte_idx = self.col_to_byte_index();
+        let row = self.row;
+        let char_len = self.lines[row][byte_idx..]
+            .chars()
+            .next()
+            .map_or(0, |c| c.len_utf8());
+        self.lines[row].drain(byte_idx..byte_idx + char_len);
+    }
+
+    /// Convert the cursor column to a byte index into its line
+    fn col_to_byte_index(&self) -> usize {
+        let line = self.line();
+        line.char_indices()
+            .nth(self.col)
             .map(|(i, _)| i)
-            .unwrap_or(self.buffer.len())
+            .unwrap_or(line.len())
     }
 
     /// Check if buffer is empty
     pub fn is_empty(&self) -> bool {
-        self.buffer.is_empty()
+        self.lines.len() == 1 && self.lines[0].is_empty()
     }
 
     /// Get trimmed content
-    pub fn trim(&self) -> &str {
-        self.buffer.trim()
+    pub fn trim(&self) -> String {
+        self.content().trim().to_string()
+    }
+}
+
+/// Char index in `line` closest to display column `x`, not splitting wide chars
+fn col_at_width(line: &str, x: usize) -> usize {
+    let mut width = 0;
+    for (i, c) in line.chars().enumerate() {
+        width += c.width().unwrap_or(0);
+        if width > x {
+            return i;
+        }
+    }
+    line.chars().count()
+}
+
+/// Char ranges of `chars` filling lines of `width` columns
+fn wrap_segments(chars: &[char], width: usize) -> Vec<(usize, usize)> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    let mut used = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        let w = chars[i].width().unwrap_or(0);
+        if used + w > width && i > start {
+            // Break after the last space in the line, if any
+            let end = chars[start..i]
+                .iter()
+                .rposition(|c| *c == ' ')
+                .map_or(i, |p| start + p + 1);
+            segments.push((start, end));
+            start = end;
+            used = chars[start..i].iter().map(|c| c.width().unwrap_or(0)).sum();
+            continue;
+        }
+        used += w;
+        i += 1;
     }
+    segments.push((start, chars.len()));
+    segments
 }
 
 /// Result of text input handling
 pub enum TextInputResult {
     /// User pressed Esc - cancel input
     Cancel,
-    /// User pressed Enter - submit with current buffer content
+    /// User pressed Enter (Alt+Enter when multi-line) - submit with current buffer content
     Submit,
     /// Input was handled (character added/removed), no action needed
     Handled,
@@ -251,22 +535,37 @@ pub enum TextInputResult {
 ///
 /// Supports:
 /// - Esc: cancel
-/// - Enter: submit
+/// - Enter: submit, or insert newline when multi-line
+/// - Alt+Enter / Ctrl+Enter: submit when multi-line
 /// - Shift+Enter: insert newline
 /// - Backspace: delete before cursor
 /// - Delete: delete at cursor
 /// - Left/Right: move cursor
-/// - Home/End: jump to start/end
+/// - Up/Down: move between lines when multi-line
+/// - Home/End: jump to start/end of line
 /// - Ctrl+Left/Right: move by word
+/// - Ctrl+U/Ctrl+K: cut to start/end of line
+/// - Ctrl+Y: paste the last cut
 /// - Char: insert at cursor
 pub fn handle_text_input(key: &KeyEvent, input: &mut TextInput) -> TextInputResult {
-    // Shift+Enter: insert newline
-    if key.code == KeyCode::Enter && key.modifiers.contains(KeyModifiers::SHIFT) {
-        input.insert('\n');
-        return TextInputResult::Handled;
+    if key.code == KeyCode::Enter {
+        // Shift+Enter: insert newline
+        if key.modifiers.contains(KeyModifiers::SHIFT) {
+            input.newline();
+            return TextInputResult::Handled;
+        }
+        if input.is_multiline()
+            && !key
+                .modifiers
+                .intersects(KeyModifiers::ALT | KeyModifiers::CONTROL)
+        {
+            input.newline();
+            return TextInputResult::Handled;
+        }
+        return TextInputResult::Submit;
     }
 
-    // Handle Ctrl+arrow keys for word navigation
+    // Handle Ctrl+arrow keys for word navigation and Ctrl+U/K/Y for kill/yank
     if key.modifiers.contains(KeyModifiers::CONTROL) {
         match key.code {
             KeyCode::Left => {
@@ -277,13 +576,24 @@ pub fn handle_text_input(key: &KeyEvent, input: &mut TextInput) -> TextInputResu
                 input.move_word_right();
                 return TextInputResult::Handled;
             }
+            KeyCode::Char('u') => {
+                input.kill_to_line_start();
+                return TextInputResult::Handled;
+            }
+            KeyCode::Char('k') => {
+                input.kill_to_line_end();
+                return TextInputResult::Handled;
+            }
+            KeyCode::Char('y') => {
+                input.yank();
+                return TextInputResult::Handled;
+            }
             _ => {}
         }
     }
 
     match key.code {
         KeyCode::Esc => TextInputResult::Cancel,
-        KeyCode::Enter => TextInputResult::Submit,
         KeyCode::Backspace => {
             input.backspace();
             TextInputResult::Handled
@@ -300,6 +610,14 @@ pub fn handle_text_input(key: &KeyEvent, input: &mut TextInput) -> TextInputResu
             input.move_right();
             TextInputResult::Handled
         }
+        KeyCode::Up if input.is_multiline() => {
+            input.move_up();
+            TextInputResult::Handled
+        }
+        KeyCode::Down if input.is_multiline() => {
+            input.move_down();
+            TextInputResult::Handled
+        }
         KeyCode::Home => {
             input.move_home();
             TextInputResult::Handled
@@ -497,4 +815,160 @@ mod tests {
         handle_text_input(&ctrl_left, &mut input);
         assert_eq!(input.cursor_position(), 0);
     }
+
+    #[test]
+    fn test_text_input_emoji_cursor_math() {
+        let mut input = TextInput::with_content("a😀b");
+        assert_eq!(input.cursor_position(), 3);
+        assert_eq!(input.cursor_display_offset(), 4);
+
+        input.move_left();
+        assert_eq!(input.char_at_cursor(), Some('b'));
+        assert_eq!(input.cursor_display_offset(), 3);
+        input.backspace();
+        assert_eq!(input.content(), "ab");
+        assert_eq!(input.cursor_display_offset(), 1);
+    }
+
+    #[test]
+    fn test_text_input_newline_and_join() {
+        let mut input = TextInput::with_content("中文abc");
+        input.move_home();
+        input.move_right();
+        input.move_right();
+        input.newline();
+        assert_eq!(input.content(), "中文\nabc");
+        assert_eq!(input.cursor_row_col(), (1, 0));
+        assert_eq!(input.cursor_position(), 3);
+
+        input.backspace();
+        assert_eq!(input.content(), "中文abc");
+        assert_eq!(input.cursor_row_col(), (0, 2));
+        assert_eq!(input.cursor_display_offset(), 4);
+
+        input.insert('\n');
+        input.move_end();
+        input.move_left();
+        input.move_left();
+        input.move_left();
+        input.move_left();
+        assert_eq!(input.cursor_row_col(), (0, 2));
+        input.delete();
+        assert_eq!(input.content(), "中文abc");
+    }
+
+    #[test]
+    fn test_text_input_up_down_keep_display_column() {
+        let mut input = TextInput::with_content("中文字\nabcdef\n😀");
+        input.move_up();
+        assert_eq!(input.cursor_row_col(), (1, 2));
+        input.move_end();
+        input.move_left();
+        input.move_left();
+        // Column 4 lands after two wide chars
+        input.move_up();
+        assert_eq!(input.cursor_row_col(), (0, 2));
+        assert_eq!(input.cursor_display_offset(), 4);
+        // Column 4 is past the emoji line
+        input.move_down();
+        input.move_down();
+        assert_eq!(input.cursor_row_col(), (2, 1));
+        assert!(!input.move_down());
+    }
+
+    #[test]
+    fn test_text_input_word_navigation_across_lines() {
+        let mut input = TextInput::with_content("hello\nworld");
+        input.move_word_left();
+        assert_eq!(input.cursor_row_col(), (1, 0));
+        input.move_word_left();
+        assert_eq!(input.cursor_row_col(), (0, 0));
+        input.move_word_right();
+        assert_eq!(input.cursor_row_col(), (1, 0));
+    }
+
+    #[test]
+    fn test_text_input_kill_and_yank() {
+        let mut input = TextInput::with_content("hello 世界");
+        input.move_left();
+        input.kill_to_line_start();
+        assert_eq!(input.content(), "界");
+        input.move_end();
+        input.yank();
+        assert_eq!(input.content(), "界hello 世");
+
+        let mut input = TextInput::with_content("one\ntwo");
+        input.set_cursor_position(1);
+        input.kill_to_line_end();
+        assert_eq!(input.content(), "o\ntwo");
+        // At a line end the line break is cut
+        input.kill_to_line_end();
+        assert_eq!(input.content(), "otwo");
+        input.yank();
+        assert_eq!(input.content(), "o\ntwo");
+    }
+
+    #[test]
+    fn test_text_input_paste() {
+        let mut input = TextInput::new();
+        input.paste("git commit\r\n-m fix\n");
+        assert_eq!(input.content(), "git commit -m fix");
+
+        let mut input = TextInput::new().multiline(true);
+        input.paste("line1\r\nline2");
+        assert_eq!(input.content(), "line1\nline2");
+        assert_eq!(input.cursor_row_col(), (1, 5));
+    }
+
+    #[test]
+    fn test_text_input_wrap() {
+        let input = TextInput::with_content("hello world foo");
+        let wrapped = input.wrap(8);
+        assert_eq!(wrapped.lines, vec!["hello ", "world ", "foo"]);
+        assert_eq!(wrapped.cursor, (3, 2));
+
+        // Wide chars don't straddle a line
+        let input = TextInput::with_content("中文字😀");
+        let wrapped = input.wrap(5);
+        assert_eq!(wrapped.lines, vec!["中文", "字😀"]);
+        assert_eq!(wrapped.cursor, (4, 1));
+
+        // A cursor after a full line moves to the next one
+        let mut input = TextInput::with_content("abcd\nx");
+        input.move_up();
+        input.move_end();
+        let wrapped = input.wrap(4);
+        assert_eq!(wrapped.lines, vec!["abcd", "", "x"]);
+        assert_eq!(wrapped.cursor, (0, 1));
+    }
+
+    #[test]
+    fn test_handle_text_input_multiline_enter() {
+        let mut input = TextInput::with_content("line1").multiline(true);
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::empty());
+        assert!(matches!(
+            handle_text_input(&enter, &mut input),
+            TextInputResult::Handled
+        ));
+        assert_eq!(input.content(), "line1\n");
+
+        let alt_enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::ALT);
+        assert!(matches!(
+            handle_text_input(&alt_enter, &mut input),
+            TextInputResult::Submit
+        ));
+
+        let up = KeyEvent::new(KeyCode::Up, KeyModifiers::empty());
+        assert!(matches!(
+            handle_text_input(&up, &mut input),
+            TextInputResult::Handled
+        ));
+        assert_eq!(input.cursor_row_col(), (0, 0));
+
+        let mut single = TextInput::new();
+        assert!(matches!(
+            handle_text_input(&up, &mut single),
+            TextInputResult::Unhandled
+        ));
+    }
 }
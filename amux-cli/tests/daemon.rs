@@ -0,0 +1,138 @@
+//! The client against an in-process daemon
+
+use amux_daemon::providers::MockProvider;
+use amux_daemon::testing::{TestDaemon, MAIN_BRANCH};
+use amux_proto::daemon::event;
+use std::path::Path;
+
+#[tokio::test]
+async fn test_session_lifecycle() {
+    // The helper is only built along with the daemon's binaries
+    if MockProvider::helper_path().is_err() {
+        return;
+    }
+    let daemon = TestDaemon::start().await;
+    let (repo_id, _repo) = daemon.add_repo().await;
+    let mut events = daemon.subscribe().await;
+    let mut client = daemon.client.clone();
+
+    let session = client
+        .create_session(
+            &repo_id,
+            MAIN_BRANCH,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some("mock"),
+            &[],
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(session.provider.as_deref(), Some("mock"));
+    let created = events
+        .expect(|event| match event {
+            event::Event::SessionCreated(e) => e.session,
+            _ => None,
+        })
+        .await;
+    assert_eq!(created.id, session.id);
+
+    client.destroy_session(&session.id).await.unwrap();
+    let destroyed = events
+        .expect(|event| match event {
+            event::Event::SessionDestroyed(e) => Some(e),
+            _ => None,
+        })
+        .await;
+    assert_eq!(destroyed.session_id, session.id);
+    assert_eq!(destroyed.repo_id, repo_id);
+    assert!(daemon.state.read().await.sessions.is_empty());
+}
+
+#[tokio::test]
+async fn test_worktree_and_git_status_events() {
+    let daemon = TestDaemon::start().await;
+    let (repo_id, _repo) = daemon.add_repo().await;
+    let mut events = daemon.subscribe().await;
+    let mut client = daemon.client.clone();
+
+    let worktree = client
+        .create_worktree(&repo_id, "feature", Some(MAIN_BRANCH))
+        .await
+        .unwrap();
+    let added = events
+        .expect(|event| match event {
+            event::Event::WorktreeAdded(e) => e.worktree,
+            _ => None,
+        })
+        .await;
+    assert_eq!(added.branch, "feature");
+    assert_eq!(added.path, worktree.path);
+
+    std::fs::write(Path::new(&worktree.path).join("new.txt"), "new\n").unwrap();
+    events
+        .expect(|event| match event {
+            event::Event::GitStatusChanged(e) if e.branch == "feature" => Some(e),
+            _ => None,
+        })
+        .await;
+
+    client.remove_worktree(&repo_id, "feature").await.unwrap();
+    let removed = events
+        .expect(|event| match event {
+            event::Event::WorktreeRemoved(e) => Some(e),
+            _ => None,
+        })
+        .await;
+    assert_eq!(removed.repo_id, repo_id);
+    assert_eq!(removed.branch, "feature");
+}
+
+#[tokio::test]
+async fn test_remove_repo_detaching_sessions() {
+    if MockProvider::helper_path().is_err() {
+        return;
+    }
+    let daemon = TestDaemon::start().await;
+    let (repo_id, _repo) = daemon.add_repo().await;
+    let mut client = daemon.client.clone();
+
+    let session = client
+        .create_session(
+            &repo_id,
+            MAIN_BRANCH,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some("mock"),
+            &[],
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    // Running sessions block the removal unless they're detached
+    assert!(client.remove_repo(&repo_id, false).await.is_err());
+    client.remove_repo(&repo_id, true).await.unwrap();
+    assert!(client.list_repos().await.unwrap().is_empty());
+
+    let sessions = client.list_sessions(None, None).await.unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].id, session.id);
+    assert_eq!(sessions[0].repo_id, "");
+    assert!(client
+        .list_sessions(Some(&repo_id), None)
+        .await
+        .unwrap()
+        .is_empty());
+    client.destroy_session(&session.id).await.unwrap();
+}
@@ -0,0 +1,162 @@
+//! `#[derive(Partial)]` for the amux-config structs
+//!
+//! Deriving `Partial` on a struct `Foo` with named fields generates:
+//!
+//! - `PartialFoo`, with each field wrapped in `Option`
+//! - `impl From<Foo> for PartialFoo`, setting every field
+//! - `Foo::apply_partial(&mut self, partial: PartialFoo)`, overwriting only
+//!   the fields that are `Some`
+//!
+//! A field marked `#[partial(nested)]` holds a struct deriving `Partial`
+//! itself. Its partial field is `Option<PartialBar>`, and applying it goes
+//! through `Bar::apply_partial`, so only the nested fields set are
+//! overwritten. Other fields are replaced whole, maps and lists included.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Field, Fields, Type};
+
+#[proc_macro_derive(Partial, attributes(partial))]
+pub fn derive_partial(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "Partial can't be derived for generic structs",
+        ));
+    }
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "Partial can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(
+            &data.fields,
+            "Partial needs a struct with named fields",
+        ));
+    };
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let partial = format_ident!("Partial{}", name);
+    let doc = format!(
+        "[`{}`] with every field optional, for [`{}::apply_partial`]",
+        name, name
+    );
+
+    let mut partial_fields = Vec::new();
+    let mut from_fields = Vec::new();
+    let mut apply_fields = Vec::new();
+    for field in &fields.named {
+        let ident = &field.ident;
+        let field_vis = &field.vis;
+        let docs = field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("doc"));
+        if is_nested(field)? {
+            let ty = partial_type(&field.ty)?;
+            partial_fields.push(quote! {
+                #(#docs)*
+                #field_vis #ident: ::core::option::Option<#ty>
+            });
+            from_fields.push(quote! {
+                #ident: ::core::option::Option::Some(value.#ident.into())
+            });
+            apply_fields.push(quote! {
+                if let ::core::option::Option::Some(value) = partial.#ident {
+                    self.#ident.apply_partial(value);
+                }
+            });
+        } else {
+            let ty = &field.ty;
+            partial_fields.push(quote! {
+                #(#docs)*
+                #field_vis #ident: ::core::option::Option<#ty>
+            });
+            from_fields.push(quote! {
+                #ident: ::core::option::Option::Some(value.#ident)
+            });
+            apply_fields.push(quote! {
+                if let ::core::option::Option::Some(value) = partial.#ident {
+                    self.#ident = value;
+                }
+            });
+        }
+    }
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, Default)]
+        #vis struct #partial {
+            #(#partial_fields,)*
+        }
+
+        impl ::core::convert::From<#name> for #partial {
+            fn from(value: #name) -> Self {
+                Self {
+                    #(#from_fields,)*
+                }
+            }
+        }
+
+        impl #name {
+            /// Overwrite the fields set in `partial`, leaving the others alone
+            #vis fn apply_partial(&mut self, partial: #partial) {
+                #(#apply_fields)*
+            }
+        }
+    })
+}
+
+/// Whether the field is marked `#[partial(nested)]`
+fn is_nested(field: &Field) -> syn::Result<bool> {
+    let mut nested = false;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("partial"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("nested") {
+                nested = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `nested`"))
+            }
+        })?;
+    }
+    Ok(nested)
+}
+
+/// `Bar` (or `path::Bar`) as `PartialBar` (or `path::PartialBar`)
+fn partial_type(original: &Type) -> syn::Result<Type> {
+    let mut ty = original.clone();
+    let last = match &mut ty {
+        Type::Path(path) => path.path.segments.last_mut(),
+        _ => None,
+    };
+    let Some(last) = last else {
+        return Err(Error::new_spanned(
+            original,
+            "nested field must be a struct type",
+        ));
+    };
+    if !last.arguments.is_empty() {
+        return Err(Error::new_spanned(
+            &last.arguments,
+            "nested field can't have generic arguments",
+        ));
+    }
+    last.ident = format_ident!("Partial{}", last.ident);
+    Ok(ty)
+}
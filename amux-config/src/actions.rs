@@ -9,7 +9,7 @@ use std::str::FromStr;
 ///
 /// These represent high-level commands that can be bound to keys.
 /// The actual execution is done in the TUI layer.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Action {
     // Navigation
     FocusSidebar,
@@ -41,19 +41,48 @@ pub enum Action {
     ScrollTop,
     ScrollBottom,
 
+    // Links
+    OpenLink,
+
     // Session management
     CreateSession,
     SelectProviderAndCreate, // Show provider selection form before creating session
     RenameSession,
     DeleteCurrent,
     SwitchToShell,
-    SwitchRepo(usize), // Switch to repo by index
+    ShellInDirectory, // New shell session in a subdirectory of the worktree
+    RunCommand,       // Run a command line in a new session of the worktree
+    BroadcastInput,   // Type a line into every running session of the worktree
+    SuspendToShell,   // Leave the TUI for a login shell in the worktree until it exits
+    SearchSessions,
+    SearchHistory,                // Search the output history of the repo's sessions
+    OpenInExternalTerminal,       // Attach the session from a new terminal window
+    RecentSessions,               // List recently shown sessions to switch back to
+    SwitchToRecentSession(usize), // Switch to the nth recent session before the current one
+    SwitchRepo(usize),            // Switch to repo by index
+    MoveRepoLeft,                 // Move current repo one tab to the left
+    MoveRepoRight,                // Move current repo one tab to the right
+    CopyToClipboard(CopyTarget),
 
     // Worktree
     AddWorktree,
+    RenameBranch,
+    CycleDeleteAction, // Cycle the worktree's default session delete action
+    TogglePinSession,
+    MoveSessionUp,     // Within its worktree
+    MoveSessionDown,   // Within its worktree
+    ExportTranscript,  // Write the session transcript to ~/.amux/exports/
+    ShowSessionInfo,   // Popup with the session's details
+    ViewSessionFile,   // Read-only view of the provider's session file
+    ToggleCheckpoints, // Checkpoint worktrees when sessions start in this repo
+    RestoreCheckpoint, // Restore the worktree to the session's checkpoint
+    AddRepo,
+    RemoveRepo,
+    CleanupWorktrees, // Prune worktrees deleted outside amux and their sessions
 
     // Diff
     ToggleDiffView,
+    ToggleRightPanel, // Swap terminal and diff, keeping the diff's state
     PrevFile,
     NextFile,
     AddComment,
@@ -62,6 +91,12 @@ pub enum Action {
     NextComment,
     PrevComment,
     SubmitReviewClaude,
+    ShowReviews,     // List past reviews of the worktree to reopen one
+    SelectDiffBase,  // Pick the ref the worktree is compared against
+    DiffAgainstBase, // Toggle between worktree changes and the branch vs its base
+    ToggleBlame,     // Show who last touched each line of the file
+    OpenInEditor,    // Open the file at the cursor's line in the editor
+    SearchInDiff,    // Search the diff lines as they are typed (n/N then cycle the matches)
 
     // Git status
     StageFile,
@@ -71,6 +106,18 @@ pub enum Action {
     ToggleOrOpen,
     GitPush,
     GitPull,
+    GitFetch,
+    QuickShip,
+    StashSave,
+    StashPop,
+    ToggleIgnored, // Show or hide files matched by .gitignore
+
+    // Merge conflicts
+    ShowConflicts,
+    ResolveOurs,
+    ResolveTheirs,
+    ResolveBoth,
+    WriteResolution, // Write the resolved file and stage it
 
     // TODO
     AddTodo,
@@ -92,11 +139,17 @@ pub enum Action {
     RefreshTodos,
     ToggleFullscreen,
     ExitFullscreen,
+    GrowSidebar,   // Widen the sidebar (persisted)
+    ShrinkSidebar, // Narrow the sidebar (persisted)
+    ToggleSidebar, // Hide/show the sidebar (not persisted)
     ExitTerminal,
     BackToTerminal,
     ToggleTreeView,
     OpenTodo,
     ClosePopup,
+    Undo,        // Restore the last destroyed session, deleted comment or TODO
+    RecordMacro, // Start recording actions into a register, or stop recording
+    ReplayMacro, // Replay the actions recorded in a register
     Quit,
     ShowHelp,
 
@@ -110,125 +163,236 @@ pub enum Action {
     EnterCommandMode,
 
     // Special
-    Noop,           // Do nothing
-    SendToTerminal, // Forward to PTY
+    Noop,             // Do nothing
+    SendToTerminal,   // Forward to PTY
+    SendText(String), // Type the text into the session's terminal
+}
+
+/// Declares the actions without a parameter and their names, the canonical
+/// one first
+///
+/// Generates [`ACTION_NAMES`], the parsing of those names and
+/// [`Action::name`], whose match must cover every variant.
+macro_rules! action_names {
+    ($($variant:ident $(($($arg:tt)*))? => $name:literal $(| $alias:literal)*,)*) => {
+        /// Every name (aliases included) of the actions without a parameter, as
+        /// written in `[bindings]` tables
+        ///
+        /// `switch-repo-N`, `switch-recent-session-N` and `send-text:<text>` take
+        /// a parameter and aren't listed.
+        pub const ACTION_NAMES: &[&str] = &[$($name, $($alias,)*)*];
+
+        impl Action {
+            /// Action without a parameter called `name` (lowercase)
+            fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    $($name $(| $alias)* => Some(Action::$variant $(($($arg)*))?),)*
+                    _ => None,
+                }
+            }
+
+            /// Name the action parses back from, e.g. `"move-down"` or
+            /// `"send-text:ls\\n"`
+            ///
+            /// Aliases come out as the canonical name.
+            pub fn name(&self) -> String {
+                match self {
+                    $(Action::$variant $(($($arg)*))? => $name.to_string(),)*
+                    Action::SwitchRepo(idx) => format!("switch-repo-{}", idx),
+                    Action::SwitchToRecentSession(idx) => {
+                        format!("switch-recent-session-{}", idx)
+                    }
+                    Action::SendText(text) => format!("send-text:{}", escape(text)),
+                }
+            }
+        }
+    };
+}
+
+action_names! {
+    // Navigation
+    FocusSidebar => "focus-sidebar" | "focus-worktree",
+    FocusSessions => "focus-sessions",
+    FocusBranches => "focus-branches",
+    FocusTerminal => "focus-terminal",
+    FocusGitStatus => "focus-git-status" | "focus-git",
+    FocusDiff => "focus-diff",
+    FocusNext => "focus-next",
+    FocusPrev => "focus-prev",
+
+    // Movement
+    MoveUp => "move-up" | "up",
+    MoveDown => "move-down" | "down",
+    GotoTop => "goto-top" | "top",
+    GotoBottom => "goto-bottom" | "bottom",
+    ToggleExpand => "toggle-expand" | "expand",
+    Select => "select",
+
+    // Terminal modes
+    InsertMode => "insert-mode" | "insert",
+    NormalMode => "normal-mode" | "terminal-normal-mode",
+
+    // Scrolling
+    ScrollUp => "scroll-up",
+    ScrollDown => "scroll-down",
+    ScrollHalfPageUp => "scroll-half-page-up",
+    ScrollHalfPageDown => "scroll-half-page-down",
+    ScrollTop => "scroll-top",
+    ScrollBottom => "scroll-bottom",
+
+    // Links
+    OpenLink => "open-link",
+
+    // Session management
+    CreateSession => "create-session" | "new-session",
+    SelectProviderAndCreate => "select-provider-and-create" | "select-provider",
+    RenameSession => "rename-session",
+    DeleteCurrent => "delete-current" | "delete",
+    SwitchToShell => "switch-to-shell",
+    ShellInDirectory => "shell-in-directory",
+    RunCommand => "run-command",
+    BroadcastInput => "broadcast-input",
+    SuspendToShell => "suspend-to-shell",
+    SearchSessions => "search-sessions" | "search",
+    SearchHistory => "search-history",
+    OpenInExternalTerminal => "open-in-external-terminal",
+    RecentSessions => "recent-sessions",
+    MoveRepoLeft => "move-repo-left",
+    MoveRepoRight => "move-repo-right",
+
+    // Clipboard
+    CopyToClipboard(CopyTarget::Auto) => "copy" | "copy-to-clipboard" | "yank",
+    CopyToClipboard(CopyTarget::BranchName) => "copy-branch-name" | "copy-branch",
+    CopyToClipboard(CopyTarget::SessionId) => "copy-session-id",
+    CopyToClipboard(CopyTarget::SessionName) => "copy-session-name",
+    CopyToClipboard(CopyTarget::FilePath) => "copy-file-path" | "copy-path",
+    CopyToClipboard(CopyTarget::CommitSha) => "copy-commit-sha" | "copy-sha",
+    CopyToClipboard(CopyTarget::DiffLineContent) => "copy-diff-line" | "copy-line",
+    CopyToClipboard(CopyTarget::TerminalSelection) => "copy-selection",
+
+    // Worktree
+    AddWorktree => "add-worktree",
+    RenameBranch => "rename-branch",
+    CycleDeleteAction => "cycle-delete-action",
+    TogglePinSession => "toggle-pin-session",
+    MoveSessionUp => "move-session-up",
+    MoveSessionDown => "move-session-down",
+    ExportTranscript => "export-transcript",
+    ShowSessionInfo => "show-session-info",
+    ViewSessionFile => "view-session-file",
+    ToggleCheckpoints => "toggle-checkpoints",
+    RestoreCheckpoint => "restore-checkpoint",
+    AddRepo => "add-repo",
+    RemoveRepo => "remove-repo",
+    CleanupWorktrees => "cleanup-worktrees",
+
+    // Diff
+    ToggleDiffView => "toggle-diff-view" | "diff",
+    ToggleRightPanel => "toggle-right-panel",
+    PrevFile => "prev-file",
+    NextFile => "next-file",
+    AddComment => "add-comment",
+    EditComment => "edit-comment",
+    DeleteComment => "delete-comment",
+    NextComment => "next-comment",
+    PrevComment => "prev-comment",
+    SubmitReviewClaude => "submit-review-claude",
+    ShowReviews => "show-reviews",
+    SelectDiffBase => "select-diff-base",
+    DiffAgainstBase => "diff-against-base",
+    ToggleBlame => "toggle-blame",
+    OpenInEditor => "open-in-editor",
+    SearchInDiff => "search-in-diff" | "search-diff",
+
+    // Git status
+    StageFile => "stage-file" | "stage",
+    UnstageFile => "unstage-file" | "unstage",
+    StageAll => "stage-all",
+    UnstageAll => "unstage-all",
+    ToggleOrOpen => "toggle-or-open",
+    GitPush => "git-push" | "push",
+    GitPull => "git-pull" | "pull",
+    GitFetch => "git-fetch" | "fetch",
+    QuickShip => "quick-ship" | "commit-and-push",
+    StashSave => "stash-save" | "stash",
+    StashPop => "stash-pop",
+    ToggleIgnored => "toggle-ignored",
+    // Merge conflicts
+    ShowConflicts => "show-conflicts",
+    ResolveOurs => "resolve-ours",
+    ResolveTheirs => "resolve-theirs",
+    ResolveBoth => "resolve-both",
+    WriteResolution => "write-resolution",
+    // TODO
+    AddTodo => "add-todo",
+    AddChildTodo => "add-child-todo",
+    EditTodoTitle => "edit-title" | "edit-todo-title",
+    EditTodoDescription => "edit-description" | "edit-todo-description",
+    DeleteTodo => "delete-todo",
+    ToggleTodoComplete => "toggle-complete" | "toggle-todo-complete",
+    MoveTodoDown => "move-todo-down",
+    MoveTodoUp => "move-todo-up",
+    IndentTodo => "indent-todo",
+    DedentTodo => "dedent-todo",
+    ToggleShowCompleted => "toggle-completed" | "toggle-show-completed",
+
+    // General
+    RefreshAll => "refresh-all" | "refresh",
+    RefreshDiff => "refresh-diff",
+    RefreshStatus => "refresh-status",
+    RefreshTodos => "refresh-todos",
+    ToggleFullscreen => "toggle-fullscreen" | "fullscreen",
+    ExitFullscreen => "exit-fullscreen",
+    GrowSidebar => "grow-sidebar",
+    ShrinkSidebar => "shrink-sidebar",
+    ToggleSidebar => "toggle-sidebar",
+    ExitTerminal => "exit-terminal",
+    BackToTerminal => "back-to-terminal",
+    ToggleTreeView => "toggle-tree-view",
+    OpenTodo => "open-todo",
+    ClosePopup => "close-popup",
+    Undo => "undo",
+    RecordMacro => "record-macro",
+    ReplayMacro => "replay-macro",
+    Quit => "quit" | "exit",
+    ShowHelp => "show-help" | "help" | "?",
+
+    // Dialog
+    Submit => "submit",
+    Cancel => "cancel",
+    Confirm => "confirm",
+    InsertNewline => "insert-newline",
+
+    // Command mode
+    EnterCommandMode => "command-mode" | ":",
+
+    // Special
+    Noop => "noop" | "none",
+    SendToTerminal => "send-to-terminal",
 }
 
 impl Action {
     #[allow(clippy::should_implement_trait)]
     /// Parse action from string (case-insensitive, supports aliases)
     pub fn from_str(s: &str) -> Option<Self> {
-        match s.trim().to_lowercase().as_str() {
-            // Navigation
-            "focus-sidebar" | "focus-worktree" => Some(Action::FocusSidebar),
-            "focus-sessions" => Some(Action::FocusSessions),
-            "focus-branches" => Some(Action::FocusBranches),
-            "focus-terminal" => Some(Action::FocusTerminal),
-            "focus-git-status" | "focus-git" => Some(Action::FocusGitStatus),
-            "focus-diff" => Some(Action::FocusDiff),
-            "focus-next" => Some(Action::FocusNext),
-            "focus-prev" => Some(Action::FocusPrev),
-
-            // Movement
-            "move-up" | "up" => Some(Action::MoveUp),
-            "move-down" | "down" => Some(Action::MoveDown),
-            "goto-top" | "top" => Some(Action::GotoTop),
-            "goto-bottom" | "bottom" => Some(Action::GotoBottom),
-            "toggle-expand" | "expand" => Some(Action::ToggleExpand),
-            "select" => Some(Action::Select),
-
-            // Terminal modes
-            "insert-mode" | "insert" => Some(Action::InsertMode),
-            "normal-mode" | "terminal-normal-mode" => Some(Action::NormalMode),
-
-            // Scrolling
-            "scroll-up" => Some(Action::ScrollUp),
-            "scroll-down" => Some(Action::ScrollDown),
-            "scroll-half-page-up" => Some(Action::ScrollHalfPageUp),
-            "scroll-half-page-down" => Some(Action::ScrollHalfPageDown),
-            "scroll-top" => Some(Action::ScrollTop),
-            "scroll-bottom" => Some(Action::ScrollBottom),
-
-            // Session management
-            "create-session" | "new-session" => Some(Action::CreateSession),
-            "select-provider-and-create" | "select-provider" => {
-                Some(Action::SelectProviderAndCreate)
+        // Send text keeps its case (send-text:<text> or SendText:<text>)
+        if let Some((name, text)) = s.trim_start().split_once(':') {
+            if matches!(name.to_lowercase().as_str(), "send-text" | "sendtext") {
+                return Some(Action::SendText(unescape(text)));
             }
-            "rename-session" => Some(Action::RenameSession),
-            "delete-current" | "delete" => Some(Action::DeleteCurrent),
-            "switch-to-shell" => Some(Action::SwitchToShell),
-
-            // Switch repo with dynamic index (switch-repo-0, switch-repo-1, etc.)
-            s if s.starts_with("switch-repo-") => s
-                .strip_prefix("switch-repo-")
-                .and_then(|idx_str| idx_str.parse::<usize>().ok())
-                .map(Action::SwitchRepo),
-
-            // Worktree
-            "add-worktree" => Some(Action::AddWorktree),
-
-            // Diff
-            "toggle-diff-view" | "diff" => Some(Action::ToggleDiffView),
-            "prev-file" => Some(Action::PrevFile),
-            "next-file" => Some(Action::NextFile),
-            "add-comment" => Some(Action::AddComment),
-            "edit-comment" => Some(Action::EditComment),
-            "delete-comment" => Some(Action::DeleteComment),
-            "next-comment" => Some(Action::NextComment),
-            "prev-comment" => Some(Action::PrevComment),
-            "submit-review-claude" => Some(Action::SubmitReviewClaude),
-
-            // Git status
-            "stage-file" | "stage" => Some(Action::StageFile),
-            "unstage-file" | "unstage" => Some(Action::UnstageFile),
-            "stage-all" => Some(Action::StageAll),
-            "unstage-all" => Some(Action::UnstageAll),
-            "toggle-or-open" => Some(Action::ToggleOrOpen),
-            "git-push" | "push" => Some(Action::GitPush),
-            "git-pull" | "pull" => Some(Action::GitPull),
-            // TODO
-            "add-todo" => Some(Action::AddTodo),
-            "add-child-todo" => Some(Action::AddChildTodo),
-            "edit-title" | "edit-todo-title" => Some(Action::EditTodoTitle),
-            "edit-description" | "edit-todo-description" => Some(Action::EditTodoDescription),
-            "delete-todo" => Some(Action::DeleteTodo),
-            "toggle-complete" | "toggle-todo-complete" => Some(Action::ToggleTodoComplete),
-            "move-todo-down" => Some(Action::MoveTodoDown),
-            "move-todo-up" => Some(Action::MoveTodoUp),
-            "indent-todo" => Some(Action::IndentTodo),
-            "dedent-todo" => Some(Action::DedentTodo),
-            "toggle-completed" | "toggle-show-completed" => Some(Action::ToggleShowCompleted),
-
-            // General
-            "refresh-all" | "refresh" => Some(Action::RefreshAll),
-            "refresh-diff" => Some(Action::RefreshDiff),
-            "refresh-status" => Some(Action::RefreshStatus),
-            "refresh-todos" => Some(Action::RefreshTodos),
-            "toggle-fullscreen" | "fullscreen" => Some(Action::ToggleFullscreen),
-            "exit-fullscreen" => Some(Action::ExitFullscreen),
-            "exit-terminal" => Some(Action::ExitTerminal),
-            "back-to-terminal" => Some(Action::BackToTerminal),
-            "toggle-tree-view" => Some(Action::ToggleTreeView),
-            "open-todo" => Some(Action::OpenTodo),
-            "close-popup" => Some(Action::ClosePopup),
-            "quit" | "exit" => Some(Action::Quit),
-            "show-help" | "help" | "?" => Some(Action::ShowHelp),
-
-            // Dialog
-            "submit" => Some(Action::Submit),
-            "cancel" => Some(Action::Cancel),
-            "confirm" => Some(Action::Confirm),
-            "insert-newline" => Some(Action::InsertNewline),
-
-            // Command mode
-            "command-mode" | ":" => Some(Action::EnterCommandMode),
-
-            // Special
-            "noop" | "none" => Some(Action::Noop),
-            "send-to-terminal" => Some(Action::SendToTerminal),
-
-            _ => None,
         }
+
+        let s = s.trim().to_lowercase();
+        if let Some(action) = Action::from_name(&s) {
+            return Some(action);
+        }
+        // Actions with an index (switch-repo-0, switch-recent-session-1, ...)
+        if let Some(idx) = s.strip_prefix("switch-recent-session-") {
+            return idx.parse().ok().map(Action::SwitchToRecentSession);
+        }
+        s.strip_prefix("switch-repo-")
+            .and_then(|idx| idx.parse().ok())
+            .map(Action::SwitchRepo)
     }
 
     /// Get display name for this action
@@ -256,14 +420,48 @@ impl Action {
             Action::ScrollHalfPageDown => "Scroll Half Page Down",
             Action::ScrollTop => "Scroll Top",
             Action::ScrollBottom => "Scroll Bottom",
+            Action::OpenLink => "Open Link",
             Action::CreateSession => "Create Session",
             Action::SelectProviderAndCreate => "Select Provider",
             Action::RenameSession => "Rename Session",
             Action::DeleteCurrent => "Delete Current",
             Action::SwitchToShell => "Switch to Shell",
+            Action::ShellInDirectory => "Shell in Directory",
+            Action::RunCommand => "Run Command",
+            Action::BroadcastInput => "Broadcast Input",
+            Action::SuspendToShell => "Suspend to Shell",
+            Action::SearchSessions => "Search Sessions",
+            Action::SearchHistory => "Search History",
+            Action::OpenInExternalTerminal => "Open in External Terminal",
+            Action::RecentSessions => "Recent Sessions",
+            Action::SwitchToRecentSession(_) => "Switch to Recent Session",
             Action::SwitchRepo(_) => "Switch Repository",
+            Action::MoveRepoLeft => "Move Repository Left",
+            Action::MoveRepoRight => "Move Repository Right",
+            Action::CopyToClipboard(CopyTarget::BranchName) => "Copy Branch Name",
+            Action::CopyToClipboard(CopyTarget::SessionId) => "Copy Session ID",
+            Action::CopyToClipboard(CopyTarget::SessionName) => "Copy Session Name",
+            Action::CopyToClipboard(CopyTarget::FilePath) => "Copy File Path",
+            Action::CopyToClipboard(CopyTarget::CommitSha) => "Copy Commit SHA",
+            Action::CopyToClipboard(CopyTarget::DiffLineContent) => "Copy Diff Line",
+            Action::CopyToClipboard(CopyTarget::TerminalSelection) => "Copy Selection",
+            Action::CopyToClipboard(CopyTarget::Auto) => "Copy to Clipboard",
             Action::AddWorktree => "Add Worktree",
+            Action::RenameBranch => "Rename Branch",
+            Action::CycleDeleteAction => "Cycle Delete Action",
+            Action::TogglePinSession => "Pin/Unpin Session",
+            Action::MoveSessionUp => "Move Session Up",
+            Action::MoveSessionDown => "Move Session Down",
+            Action::ExportTranscript => "Export Transcript",
+            Action::ShowSessionInfo => "Session Info",
+            Action::ViewSessionFile => "View Session File",
+            Action::ToggleCheckpoints => "Toggle Checkpoints",
+            Action::RestoreCheckpoint => "Restore to Session Start",
+            Action::AddRepo => "Add Repository",
+            Action::RemoveRepo => "Remove Repository",
+            Action::CleanupWorktrees => "Clean Up Worktrees",
             Action::ToggleDiffView => "Toggle Diff View",
+            Action::ToggleRightPanel => "Toggle Right Panel",
             Action::PrevFile => "Previous File",
             Action::NextFile => "Next File",
             Action::AddComment => "Add Comment",
@@ -272,6 +470,12 @@ impl Action {
             Action::NextComment => "Next Comment",
             Action::PrevComment => "Previous Comment",
             Action::SubmitReviewClaude => "Submit Review to Claude",
+            Action::ShowReviews => "Show Reviews",
+            Action::SelectDiffBase => "Select Diff Base",
+            Action::DiffAgainstBase => "Diff Against Base",
+            Action::ToggleBlame => "Toggle Blame",
+            Action::OpenInEditor => "Open in Editor",
+            Action::SearchInDiff => "Search in Diff",
             Action::StageFile => "Stage File",
             Action::UnstageFile => "Unstage File",
             Action::StageAll => "Stage All",
@@ -279,6 +483,16 @@ impl Action {
             Action::ToggleOrOpen => "Toggle or Open",
             Action::GitPush => "Git Push",
             Action::GitPull => "Git Pull",
+            Action::GitFetch => "Git Fetch",
+            Action::QuickShip => "Commit & Push",
+            Action::StashSave => "Stash Changes",
+            Action::StashPop => "Pop Stash",
+            Action::ToggleIgnored => "Toggle Ignored Files",
+            Action::ShowConflicts => "Show Conflicts",
+            Action::ResolveOurs => "Resolve with Ours",
+            Action::ResolveTheirs => "Resolve with Theirs",
+            Action::ResolveBoth => "Resolve with Both",
+            Action::WriteResolution => "Write Resolution",
             Action::AddTodo => "Add Todo",
             Action::AddChildTodo => "Add Child Todo",
             Action::EditTodoTitle => "Edit Todo Title",
@@ -295,12 +509,18 @@ impl Action {
             Action::RefreshStatus => "Refresh Status",
             Action::RefreshTodos => "Refresh Todos",
             Action::ToggleFullscreen => "Toggle Fullscreen",
+            Action::GrowSidebar => "Grow Sidebar",
+            Action::ShrinkSidebar => "Shrink Sidebar",
+            Action::ToggleSidebar => "Toggle Sidebar",
             Action::ExitFullscreen => "Exit Fullscreen",
             Action::ExitTerminal => "Exit Terminal",
             Action::BackToTerminal => "Back to Terminal",
             Action::ToggleTreeView => "Toggle Tree View",
             Action::OpenTodo => "Open Todo",
             Action::ClosePopup => "Close Popup",
+            Action::Undo => "Undo",
+            Action::RecordMacro => "Record Macro",
+            Action::ReplayMacro => "Replay Macro",
             Action::Quit => "Quit",
             Action::ShowHelp => "Show Help",
             Action::Submit => "Submit",
@@ -310,8 +530,68 @@ impl Action {
             Action::EnterCommandMode => "Enter Command Mode",
             Action::Noop => "No Operation",
             Action::SendToTerminal => "Send to Terminal",
+            Action::SendText(_) => "Send Text",
+        }
+    }
+}
+
+/// Inverse of [`unescape`], for writing send-text strings back
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\x1b' => out.push_str("\\e"),
+            '\\' => out.push_str("\\\\"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Expand `\n`, `\r`, `\t`, `\e` and `\\` in a send-text string
+///
+/// Other backslashes are kept as they are.
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some('e') => out.push('\x1b'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
         }
     }
+    out
+}
+
+/// Value copied by `Action::CopyToClipboard`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CopyTarget {
+    /// Resolved from focus: session or branch in the sidebar, file or line in
+    /// diff, the selection (else the session) in the terminal
+    Auto,
+    BranchName,
+    SessionId,
+    SessionName,
+    FilePath,
+    CommitSha,
+    DiffLineContent,
+    /// Text selected by double/triple-clicking the terminal
+    TerminalSelection,
 }
 
 impl FromStr for Action {
@@ -321,3 +601,62 @@ impl FromStr for Action {
         Action::from_str(s).ok_or_else(|| format!("Unknown action: {}", s))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_action_names_parse() {
+        for name in ACTION_NAMES {
+            assert!(Action::from_str(name).is_some(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_send_text() {
+        assert_eq!(
+            Action::from_str("SendText:/review this file\\n"),
+            Some(Action::SendText("/review this file\n".to_string()))
+        );
+        assert_eq!(
+            Action::from_str("send-text:a\\tb\\\\n\\q"),
+            Some(Action::SendText("a\tb\\n\\q".to_string()))
+        );
+        assert_eq!(
+            Action::from_str("send-text:"),
+            Some(Action::SendText(String::new()))
+        );
+        assert_eq!(Action::from_str("send-text"), None);
+    }
+
+    #[test]
+    fn test_switch_recent_session() {
+        assert_eq!(
+            Action::from_str("switch-recent-session-2"),
+            Some(Action::SwitchToRecentSession(2))
+        );
+        assert_eq!(Action::from_str("switch-recent-session-x"), None);
+    }
+
+    #[test]
+    fn test_action_name_round_trip() {
+        for name in ACTION_NAMES {
+            let action = Action::from_str(name).unwrap();
+            assert_eq!(Action::from_str(&action.name()), Some(action), "{}", name);
+        }
+        assert_eq!(Action::from_str("expand").unwrap().name(), "toggle-expand");
+
+        for action in [
+            Action::SwitchRepo(3),
+            Action::SwitchToRecentSession(0),
+            Action::SendText("a\\n\tb\n".to_string()),
+        ] {
+            assert_eq!(Action::from_str(&action.name()), Some(action));
+        }
+        assert_eq!(
+            Action::SendText("ls\n".to_string()).name(),
+            "send-text:ls\\n"
+        );
+    }
+}
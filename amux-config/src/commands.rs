@@ -5,10 +5,11 @@
 //! - `:bind [context] <key> <action>`
 //! - `:unbind [context] <key>`
 //! - `:source <file>`
+//! - `:provider list | default <name> | model <provider> <model>`
 //!
 //! Full implementation in Phase 4.
 
-use crate::Result;
+use crate::{BindingContext, ConfigError, Options, Result};
 
 /// Runtime command executed during application
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -45,6 +46,9 @@ pub enum RuntimeCommand {
     },
     ShowOptions,
 
+    // Provider commands
+    Provider(ProviderCommand),
+
     // Execution
     Exec {
         action: String,
@@ -54,6 +58,17 @@ pub enum RuntimeCommand {
     Help,
 }
 
+/// What `:provider` does
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProviderCommand {
+    /// Show the providers, installed or not
+    List,
+    /// Make `name` the provider of new sessions
+    Default { name: String },
+    /// Set the model new sessions of `provider` use
+    Model { provider: String, model: String },
+}
+
 impl RuntimeCommand {
     /// Parse command from input string
     pub fn parse(input: &str) -> Result<Self> {
@@ -69,15 +84,27 @@ impl RuntimeCommand {
 
         match parts[0] {
             "set" | "set-option" => {
-                if parts.len() < 3 {
+                // Accept both `set opt value` and `set opt=value`
+                let (option, value) = match parts.get(1).and_then(|p| p.split_once('=')) {
+                    Some((option, first)) => {
+                        let rest = parts[2..].join(" ");
+                        (
+                            option.to_string(),
+                            format!("{} {}", first, rest).trim().to_string(),
+                        )
+                    }
+                    None => (
+                        parts.get(1).unwrap_or(&"").to_string(),
+                        parts.get(2..).unwrap_or(&[]).join(" "),
+                    ),
+                };
+                if option.is_empty() || value.is_empty() {
                     return Err(crate::ConfigError::Custom(
                         "Usage: set <option> <value>".to_string(),
                     ));
                 }
-                Ok(RuntimeCommand::Set {
-                    option: parts[1].to_string(),
-                    value: parts[2..].join(" "),
-                })
+                validate_option_name(&option)?;
+                Ok(RuntimeCommand::Set { option, value })
             }
             "bind" | "bind-key" => {
                 if parts.len() < 3 {
@@ -93,7 +120,7 @@ impl RuntimeCommand {
                     })
                 } else {
                     Ok(RuntimeCommand::Bind {
-                        context: Some(parts[1].to_string()),
+                        context: Some(parse_context(parts[1])?),
                         key: parts[2].to_string(),
                         action: parts[3..].join(" "),
                     })
@@ -107,7 +134,7 @@ impl RuntimeCommand {
                 }
                 Ok(RuntimeCommand::Unbind {
                     context: if parts.len() > 2 {
-                        Some(parts[1].to_string())
+                        Some(parse_context(parts[1])?)
                     } else {
                         None
                     },
@@ -149,6 +176,25 @@ impl RuntimeCommand {
                     action: parts[1..].join(" "),
                 })
             }
+            "provider" | "providers" => {
+                let command = match parts[1..] {
+                    [] | ["list"] => ProviderCommand::List,
+                    ["default", name] => ProviderCommand::Default {
+                        name: name.to_string(),
+                    },
+                    ["model", provider, model] => ProviderCommand::Model {
+                        provider: provider.to_string(),
+                        model: model.to_string(),
+                    },
+                    _ => {
+                        return Err(crate::ConfigError::Custom(
+                            "Usage: provider list | default <name> | model <provider> <model>"
+                                .to_string(),
+                        ))
+                    }
+                };
+                Ok(RuntimeCommand::Provider(command))
+            }
             "help" | "?" => Ok(RuntimeCommand::Help),
             _ => Err(crate::ConfigError::Custom(format!(
                 "Unknown command: {}",
@@ -158,9 +204,141 @@ impl RuntimeCommand {
     }
 }
 
+/// Canonical name of a binding context given to `:bind`/`:unbind`
+fn parse_context(name: &str) -> Result<String> {
+    BindingContext::from_name(name)
+        .map(|context| context.name().to_string())
+        .ok_or_else(|| {
+            ConfigError::Custom(format!(
+                "Unknown context '{}' (one of: {})",
+                name,
+                BindingContext::all().join(", ")
+            ))
+        })
+}
+
+/// Reject option names `:set` doesn't know, suggesting the closest match
+///
+/// Only the first part of a dotted path is checked here; the rest is
+/// checked against the option's value when it is set.
+fn validate_option_name(option: &str) -> Result<()> {
+    let option = option.split('.').next().unwrap_or(option);
+    let known = Options::field_names();
+    if known.contains(&option) {
+        return Ok(());
+    }
+
+    let message = match suggest_option_name(option, known) {
+        Some(suggestion) => format!(
+            "Unknown option '{}'. Did you mean '{}'?",
+            option, suggestion
+        ),
+        None => format!("Unknown option '{}'", option),
+    };
+    Err(ConfigError::InvalidOption(message))
+}
+
+/// Set the value at the dotted `path` of `table` from its `:set` text form
+///
+/// The key must exist already, and `value` is parsed as the type its
+/// current value has. Errors name the offending path part or value.
+pub(crate) fn set_table_value(table: &mut toml::Table, path: &str, value: &str) -> Result<()> {
+    let parts: Vec<&str> = path.split('.').collect();
+    let Some((key, parents)) = parts.split_last() else {
+        return Err(unknown_path_part(path, path));
+    };
+    let mut current = table;
+    for (i, part) in parents.iter().enumerate() {
+        current = match current.get_mut(*part) {
+            Some(toml::Value::Table(sub)) => sub,
+            // Only tables have keys below them
+            Some(_) => return Err(unknown_path_part(parts[i + 1], path)),
+            None => return Err(unknown_path_part(part, path)),
+        };
+    }
+
+    let existing = current
+        .get(*key)
+        .ok_or_else(|| unknown_path_part(key, path))?;
+    let parsed = parse_like(existing, value).map_err(|token| {
+        ConfigError::InvalidOption(format!("Invalid value '{}' for {}", token, path))
+    })?;
+    current.insert(key.to_string(), parsed);
+    Ok(())
+}
+
+fn unknown_path_part(part: &str, path: &str) -> ConfigError {
+    if part == path {
+        ConfigError::InvalidOption(format!("Unknown option '{}'", path))
+    } else {
+        ConfigError::InvalidOption(format!("Unknown option '{}' in '{}'", part, path))
+    }
+}
+
+/// Parse `value` as the same type as `existing`
+///
+/// Array elements are comma-separated and parsed like the array's first
+/// element (strings when it is empty). Fails with the offending token.
+fn parse_like(existing: &toml::Value, value: &str) -> std::result::Result<toml::Value, String> {
+    let parsed = match existing {
+        toml::Value::Boolean(_) => match value.to_ascii_lowercase().as_str() {
+            "on" | "true" | "yes" | "1" => Some(toml::Value::Boolean(true)),
+            "off" | "false" | "no" | "0" => Some(toml::Value::Boolean(false)),
+            _ => None,
+        },
+        toml::Value::Integer(_) => value.parse().ok().map(toml::Value::Integer),
+        toml::Value::Float(_) => value.parse().ok().map(toml::Value::Float),
+        toml::Value::Array(items) => {
+            let element = items
+                .first()
+                .cloned()
+                .unwrap_or_else(|| toml::Value::String(String::new()));
+            return value
+                .split(',')
+                .map(str::trim)
+                .filter(|token| !token.is_empty())
+                .map(|token| parse_like(&element, token))
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map(toml::Value::Array);
+        }
+        // A table is set one key at a time
+        toml::Value::Table(_) => None,
+        _ => Some(toml::Value::String(value.trim_matches('"').to_string())),
+    };
+    parsed.ok_or_else(|| value.to_string())
+}
+
+/// Find the closest known option name within edit distance 2
+pub fn suggest_option_name(input: &str, known: &[&'static str]) -> Option<&'static str> {
+    known
+        .iter()
+        .map(|name| (levenshtein(input, name), *name))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, name)| name)
+}
+
+/// Levenshtein edit distance between two strings (by char)
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut row = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            row[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(row[j] + 1);
+        }
+        prev = row;
+    }
+
+    prev[b.len()]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Confirmation;
 
     #[test]
     fn test_parse_set_command() {
@@ -186,4 +364,231 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_parse_bind_with_context() {
+        let cmd = RuntimeCommand::parse(":bind Git_Status j move-down").unwrap();
+        assert_eq!(
+            cmd,
+            RuntimeCommand::Bind {
+                context: Some("git-status".to_string()),
+                key: "j".to_string(),
+                action: "move-down".to_string()
+            }
+        );
+        assert!(RuntimeCommand::parse(":bind nowhere j move-down").is_err());
+    }
+
+    #[test]
+    fn test_parse_set_with_equals() {
+        let cmd = RuntimeCommand::parse(":set worktree_status_timeout_ms=100").unwrap();
+        assert_eq!(
+            cmd,
+            RuntimeCommand::Set {
+                option: "worktree_status_timeout_ms".to_string(),
+                value: "100".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_set_unknown_option() {
+        let err = RuntimeCommand::parse(":set mouse_enabeld true").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid option: Unknown option 'mouse_enabeld'. Did you mean 'mouse_enabled'?"
+        );
+
+        let err = RuntimeCommand::parse(":set nonsense=1").unwrap_err();
+        assert!(
+            matches!(err, ConfigError::InvalidOption(msg) if msg == "Unknown option 'nonsense'")
+        );
+    }
+
+    #[test]
+    fn test_parse_provider_command() {
+        let parse = |input| RuntimeCommand::parse(input).unwrap();
+        assert_eq!(
+            parse(":provider"),
+            RuntimeCommand::Provider(ProviderCommand::List)
+        );
+        assert_eq!(
+            parse(":provider default codex"),
+            RuntimeCommand::Provider(ProviderCommand::Default {
+                name: "codex".to_string()
+            })
+        );
+        assert_eq!(
+            parse(":provider model claude opus"),
+            RuntimeCommand::Provider(ProviderCommand::Model {
+                provider: "claude".to_string(),
+                model: "opus".to_string()
+            })
+        );
+        assert!(RuntimeCommand::parse(":provider default").is_err());
+        assert!(RuntimeCommand::parse(":provider model claude").is_err());
+        assert!(RuntimeCommand::parse(":provider remove claude").is_err());
+    }
+
+    #[test]
+    fn test_provider_settings() {
+        let available: &[crate::types::AvailableProvider] =
+            &[("claude", &["sonnet", "opus"]), ("aider", &["gpt-4o"])];
+        let mut providers = crate::types::ProvidersConfig::default();
+
+        providers.set_default("aider", available).unwrap();
+        assert_eq!(providers.default, "aider");
+        providers.set_model("claude", "opus", available).unwrap();
+        assert_eq!(providers.claude.model, "opus");
+
+        let err = providers.set_default("codex", available).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid option: Provider 'codex' not found. Available providers: claude, aider"
+        );
+        let err = providers
+            .set_model("claude", "haiku", available)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid option: Invalid model 'haiku' for provider 'claude'. \
+             Available models: sonnet, opus"
+        );
+        // Script providers have no model setting in the config
+        assert!(providers.set_model("aider", "gpt-4o", available).is_err());
+
+        assert_eq!(
+            providers.list_lines(available),
+            [
+                "* aider (available)",
+                "  claude (available, model opus)",
+                "  codex (unavailable, model o4-mini)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_suggest_option_name() {
+        let known = &["open_links", "mouse_enabled"];
+        assert_eq!(suggest_option_name("open_link", known), Some("open_links"));
+        assert_eq!(suggest_option_name("opn_lnk", known), None);
+    }
+
+    #[test]
+    fn test_option_field_names_match_config_keys() {
+        let value = toml::Value::try_from(Options::default()).unwrap();
+        let mut keys: Vec<&str> = value
+            .as_table()
+            .unwrap()
+            .keys()
+            .map(|k| k.as_str())
+            .collect();
+        let mut names = Options::field_names().to_vec();
+        keys.sort_unstable();
+        names.sort_unstable();
+        assert_eq!(keys, names);
+    }
+
+    #[test]
+    fn test_set_applies_typed_values() {
+        let mut options = Options::default();
+        options.set("worktree_status_timeout_ms", "100").unwrap();
+        assert_eq!(options.worktree_status_timeout_ms, 100);
+        options.set("quit_behavior", "stop").unwrap();
+        assert_eq!(options.quit_behavior, crate::QuitBehavior::Stop);
+
+        assert!(options.set("quit_behavior", "explode").is_err());
+        assert!(options.set("worktree_status_timeout_ms", "-1").is_err());
+        assert!(options.set("mouse_enabled", "maybe").is_err());
+        assert!(options.set("nonsense", "1").is_err());
+        // Failed sets leave the options untouched
+        assert_eq!(options.quit_behavior, crate::QuitBehavior::Stop);
+    }
+
+    #[test]
+    fn test_parse_set_dotted_option() {
+        let cmd = RuntimeCommand::parse(":set theme.border_focused #00ffff").unwrap();
+        assert_eq!(
+            cmd,
+            RuntimeCommand::Set {
+                option: "theme.border_focused".to_string(),
+                value: "#00ffff".to_string()
+            }
+        );
+
+        let err = RuntimeCommand::parse(":set themes.border_focused #00ffff").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid option: Unknown option 'themes'. Did you mean 'theme'?"
+        );
+    }
+
+    #[test]
+    fn test_set_table_value_nested_and_arrays() {
+        let mut table: toml::Table = toml::from_str(
+            r##"
+            fallback = ["claude"]
+            limits = [1, 2]
+            [colors]
+            border = "#333333"
+            "##,
+        )
+        .unwrap();
+
+        set_table_value(&mut table, "fallback", "claude, codex").unwrap();
+        assert_eq!(
+            table["fallback"],
+            toml::Value::Array(vec!["claude".into(), "codex".into()])
+        );
+        set_table_value(&mut table, "limits", "3,4").unwrap();
+        assert_eq!(
+            table["limits"],
+            toml::Value::Array(vec![3.into(), 4.into()])
+        );
+        set_table_value(&mut table, "colors.border", "#00ffff").unwrap();
+        assert_eq!(table["colors"]["border"].as_str(), Some("#00ffff"));
+
+        let err = set_table_value(&mut table, "limits", "5,x").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid option: Invalid value 'x' for limits"
+        );
+        let err = set_table_value(&mut table, "colors.borders", "#fff").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid option: Unknown option 'borders' in 'colors.borders'"
+        );
+        let err = set_table_value(&mut table, "fallback.first", "codex").unwrap_err();
+        assert!(
+            matches!(err, ConfigError::InvalidOption(msg) if msg == "Unknown option 'first' in 'fallback.first'")
+        );
+        assert!(set_table_value(&mut table, "colors", "#fff").is_err());
+
+        // Failed sets leave the table untouched
+        assert_eq!(
+            table["limits"],
+            toml::Value::Array(vec![3.into(), 4.into()])
+        );
+    }
+
+    #[test]
+    fn test_set_reenables_confirmation() {
+        let mut options = Options::default();
+        assert!(options.confirms(Confirmation::DeleteSession));
+        options.set_confirms(Confirmation::DeleteSession, false);
+        assert!(!options.confirms(Confirmation::DeleteSession));
+        assert!(options.confirms(Confirmation::DeleteTodo));
+
+        let RuntimeCommand::Set { option, value } =
+            RuntimeCommand::parse(":set confirm_delete_session on").unwrap()
+        else {
+            panic!("expected a set command");
+        };
+        assert_eq!(option, Confirmation::DeleteSession.option_name());
+        options.set(&option, &value).unwrap();
+        assert!(options.confirms(Confirmation::DeleteSession));
+
+        options.set("confirm_delete_todo", "off").unwrap();
+        assert!(!options.confirms(Confirmation::DeleteTodo));
+    }
 }
@@ -2,10 +2,13 @@
 
 use crate::defaults;
 use crate::keybind::KeybindMap;
-use crate::types::Config;
-use crate::{parser, Result};
+use crate::types::{binding_pairs, BindingMap, Config};
+use crate::{parser, writer, ConfigError, Result};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
+pub use crate::writer::save_with_edit;
+
 /// Get the default ccm config directory
 pub fn config_dir() -> PathBuf {
     dirs::home_dir()
@@ -18,28 +21,31 @@ pub fn config_file() -> PathBuf {
     config_dir().join("config.toml")
 }
 
-/// Load configuration from file, or return defaults if not found
+/// Load configuration from file, creating a commented one if not found
+///
+/// Files named by `source` are loaded too, see [`parser::load_from_file`].
 pub fn load_or_default() -> Result<Config> {
     let config_path = config_file();
+    if !config_path.exists() {
+        // First run: leave a starter file to edit, which loads to defaults
+        if let Err(e) = writer::create_minimal_file(&config_path) {
+            eprintln!("Warning: Failed to create config file: {}", e);
+            return Ok(defaults::default_config());
+        }
+    }
 
-    match std::fs::read_to_string(&config_path) {
-        Ok(content) => {
-            // Try to parse the user's config
-            match parser::parse_toml(&content) {
-                Ok(mut config) => {
-                    // Merge with defaults to fill in missing bindings
-                    config.merge_with_defaults();
-                    Ok(config)
-                }
-                Err(e) => {
-                    eprintln!("Warning: Failed to parse config file: {}", e);
-                    eprintln!("Using default configuration");
-                    Ok(defaults::default_config())
-                }
-            }
+    // Try to parse the user's config
+    match parser::load_from_file(&config_path) {
+        Ok(mut config) => {
+            // Merge with defaults to fill in missing bindings
+            config.merge_with_defaults();
+            Ok(config)
         }
-        Err(_) => {
-            // Config file doesn't exist, use defaults
+        // Defaults would drop whatever the newer version's settings do
+        Err(e @ ConfigError::VersionMismatch { .. }) => Err(e),
+        Err(e) => {
+            eprintln!("Warning: Failed to parse config file: {}", e);
+            eprintln!("Using default configuration");
             Ok(defaults::default_config())
         }
     }
@@ -56,11 +62,40 @@ impl Config {
         load_or_default()
     }
 
+    /// Parse and validate a TOML config
+    pub fn load_from_str(content: &str) -> Result<Self> {
+        parser::parse_toml(content)
+    }
+
     /// Load from a specific file
     pub fn load_from_file(path: &Path) -> Result<Self> {
         load_from_file(path)
     }
 
+    /// Save to the default config file, keeping its comments
+    pub fn save(&self) -> Result<()> {
+        writer::save_default(self)
+    }
+
+    /// Serialize to pretty-printed JSON (for editor integrations)
+    #[cfg(feature = "json")]
+    pub fn to_json_string(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parse and validate a config serialized by [`Config::to_json_string`]
+    #[cfg(feature = "json")]
+    pub fn from_json_str(s: &str) -> Result<Self> {
+        parser::parse_json(s)
+    }
+
+    /// JSON Schema of the config file: every option with its type and
+    /// default, and the action names bindings accept
+    #[cfg(feature = "json")]
+    pub fn json_schema() -> serde_json::Value {
+        crate::schema::schema()
+    }
+
     /// Build a KeybindMap from this config
     pub fn to_keybind_map(&self) -> Result<KeybindMap> {
         KeybindMap::from_bindings(&self.bindings, &self.prefix.key)
@@ -88,9 +123,7 @@ impl Config {
             self.bindings.prefix = default_bindings.prefix;
         } else {
             // Merge: add missing defaults
-            for (k, v) in default_bindings.prefix {
-                self.bindings.prefix.entry(k).or_insert(v);
-            }
+            merge_binding_map(&mut self.bindings.prefix, &default_bindings.prefix);
         }
 
         // Similar for other contexts
@@ -105,6 +138,7 @@ impl Config {
         );
         merge_binding_map(&mut self.bindings.diff, &default_bindings.diff);
         merge_binding_map(&mut self.bindings.git_status, &default_bindings.git_status);
+        merge_binding_map(&mut self.bindings.conflicts, &default_bindings.conflicts);
         merge_binding_map(&mut self.bindings.todo, &default_bindings.todo);
         merge_binding_map(
             &mut self.bindings.dialog_text,
@@ -118,12 +152,17 @@ impl Config {
 }
 
 /// Merge default bindings into user bindings
-fn merge_binding_map(
-    user_map: &mut std::collections::HashMap<String, String>,
-    defaults: &std::collections::HashMap<String, String>,
-) {
+///
+/// Keys the user bound through an `action = [keys]` entry keep that binding.
+fn merge_binding_map(user_map: &mut BindingMap, defaults: &BindingMap) {
+    let user_keys: HashSet<String> = binding_pairs(user_map)
+        .into_iter()
+        .map(|(key, _)| key.to_string())
+        .collect();
     for (k, v) in defaults {
-        user_map.entry(k.clone()).or_insert(v.clone());
+        if !user_keys.contains(k) {
+            user_map.entry(k.clone()).or_insert(v.clone());
+        }
     }
 }
 
@@ -143,7 +182,7 @@ mod tests {
         assert!(!config.bindings.prefix.is_empty());
         assert_eq!(
             config.bindings.prefix.get("s"),
-            Some(&"focus-sessions".to_string())
+            Some(&"focus-sessions".into())
         );
     }
 
@@ -151,13 +190,187 @@ mod tests {
     fn test_config_has_sidebar_bindings() {
         let config = defaults::default_config();
         assert!(!config.bindings.sidebar.is_empty());
+        assert_eq!(config.bindings.sidebar.get("j"), Some(&"move-down".into()));
+        assert_eq!(config.bindings.sidebar.get("k"), Some(&"move-up".into()));
+    }
+
+    #[test]
+    fn test_key_arrays_replace_default_keys() {
+        let mut config =
+            parser::parse_toml("[bindings.sidebar]\nquit = [\"j\", \"C-q\"]\n").unwrap();
+        config.merge_with_defaults();
+        let map = config.to_keybind_map().unwrap();
+        let resolve = |key| map.resolve(key, crate::BindingContext::Sidebar);
+        assert_eq!(resolve("j"), Some(crate::Action::Quit));
+        assert_eq!(resolve("C-q"), Some(crate::Action::Quit));
+        assert_eq!(resolve("k"), Some(crate::Action::MoveUp));
+    }
+
+    #[test]
+    fn test_bindings_merge_and_remove() {
+        use crate::types::{BindingValue, Bindings};
+        use crate::BindingContext::{Diff, Sidebar};
+
+        let mut bindings = Bindings::default();
+        bindings.sidebar.insert(
+            "move-up".to_string(),
+            BindingValue::Keys(vec!["k".to_string(), "C-p".to_string()]),
+        );
+        bindings.sidebar.insert("q".to_string(), "quit".into());
+
+        let mut overlay = Bindings::default();
+        overlay.insert(Sidebar, "Ctrl-p", "move-down");
+        overlay.insert(Sidebar, "q", "none");
+        overlay.insert(Diff, "n", "next-file");
+        bindings.merge(&overlay);
+
         assert_eq!(
-            config.bindings.sidebar.get("j"),
-            Some(&"move-down".to_string())
+            bindings.sidebar.get("move-up"),
+            Some(&BindingValue::Keys(vec!["k".to_string()]))
         );
+        assert_eq!(bindings.sidebar.get("Ctrl-p"), Some(&"move-down".into()));
+        assert_eq!(bindings.sidebar.get("q"), Some(&"none".into()));
+        assert_eq!(bindings.diff.get("n"), Some(&"next-file".into()));
+
+        assert!(bindings.remove(Sidebar, "k"));
+        assert!(!bindings.sidebar.contains_key("move-up"));
+        assert!(!bindings.remove(Sidebar, "k"));
+    }
+
+    #[test]
+    fn test_quit_behavior_parsing() {
+        let config: Config = toml::from_str("[options]\nquit_behavior = \"stop\"\n").unwrap();
+        assert_eq!(config.options.quit_behavior, crate::QuitBehavior::Stop);
+
+        let config: Config = toml::from_str("[options]\n").unwrap();
+        assert_eq!(config.options.quit_behavior, crate::QuitBehavior::Detach);
+    }
+
+    #[test]
+    fn test_ime_integration_parsing() {
+        let config: Config = toml::from_str("[options]\nime_integration = \"ibus\"\n").unwrap();
+        assert_eq!(config.options.ime_integration, crate::ImeIntegration::Ibus);
+
+        let config: Config = toml::from_str("[options]\n").unwrap();
+        assert_eq!(config.options.ime_integration, crate::ImeIntegration::None);
+        assert!(toml::from_str::<Config>("[options]\nime_integration = \"uim\"\n").is_err());
+    }
+
+    #[test]
+    fn test_sidebar_sort_parsing() {
+        let config: Config = toml::from_str("[options]\nsidebar_sort = \"status\"\n").unwrap();
+        assert_eq!(config.options.sidebar_sort, crate::SidebarSort::Status);
+        assert!(config.options.sidebar_pin_main);
+
+        let config: Config = toml::from_str("[options]\n").unwrap();
+        assert_eq!(config.options.sidebar_sort, crate::SidebarSort::None);
+        assert!(toml::from_str::<Config>("[options]\nsidebar_sort = \"size\"\n").is_err());
+    }
+
+    #[test]
+    fn test_startup_action_parsing() {
+        let parse = |value: &str| {
+            toml::from_str::<Config>(&format!("[options]\nstartup_action = \"{}\"\n", value))
+                .map(|c| c.options.startup_action)
+        };
+        assert_eq!(
+            parse("last-session").unwrap(),
+            crate::StartupAction::LastSession
+        );
+        assert_eq!(
+            parse("session:review").unwrap(),
+            crate::StartupAction::SpecificSession("review".to_string())
+        );
+        assert!(parse("session:").is_err());
+        assert!(parse("resume").is_err());
+
+        let config: Config = toml::from_str("[options]\n").unwrap();
+        assert_eq!(config.options.startup_action, crate::StartupAction::None);
+    }
+
+    #[test]
+    fn test_sidebar_width_percent_validated() {
+        let config = parser::parse_toml("[ui]\nsidebar_width_percent = 40\n").unwrap();
+        assert_eq!(config.ui.sidebar_width_percent, 40);
+        assert_eq!(defaults::default_config().ui.sidebar_width_percent, 25);
+
+        for bad in ["5", "61"] {
+            let toml = format!("[ui]\nsidebar_width_percent = {}\n", bad);
+            assert!(parser::parse_toml(&toml).is_err());
+        }
+    }
+
+    #[test]
+    fn test_tab_title_format_validated() {
+        let config =
+            parser::parse_toml("[options]\ntab_title_format = \"{name} [{session_count}]\"\n")
+                .unwrap();
+        assert_eq!(config.options.tab_title_format, "{name} [{session_count}]");
         assert_eq!(
-            config.bindings.sidebar.get("k"),
-            Some(&"move-up".to_string())
+            defaults::default_config().options.tab_title_format,
+            "{index}:{name}"
+        );
+
+        let err = parser::parse_toml("[options]\ntab_title_format = \"{index} {branch}\"\n");
+        assert!(
+            matches!(err, Err(crate::ConfigError::ValidationError(msg)) if msg.contains("{branch}"))
+        );
+    }
+
+    #[test]
+    fn test_apply_partial() {
+        use crate::types::{PartialConfig, PartialOptions, PartialProvidersConfig};
+
+        let mut config = defaults::default_config();
+        config.apply_partial(PartialConfig {
+            source: Some(vec!["extra.toml".to_string()]),
+            options: Some(PartialOptions {
+                theme: Some("nord".to_string()),
+                ..Default::default()
+            }),
+            providers: Some(PartialProvidersConfig {
+                default: Some("codex".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        assert_eq!(config.source, ["extra.toml"]);
+        assert_eq!(config.options.theme, "nord");
+        assert_eq!(config.providers.default, "codex");
+        // Fields left out, nested ones included, are kept
+        assert!(config.options.tree_view_enabled);
+        assert_eq!(config.providers.claude.model, "sonnet");
+        assert_eq!(config.prefix.key, "C-s");
+
+        // A partial made from a whole config sets everything
+        let mut other = defaults::default_config();
+        other.prefix.key = "C-a".to_string();
+        other.options.mouse_enabled = false;
+        config.apply_partial(other.into());
+        assert_eq!(config.prefix.key, "C-a");
+        assert!(!config.options.mouse_enabled);
+        assert_eq!(
+            config.options.theme,
+            defaults::default_config().options.theme
+        );
+        assert!(config.source.is_empty());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_round_trip() {
+        let config = defaults::default_config();
+        let json = config.to_json_string().unwrap();
+        let parsed = Config::from_json_str(&json).unwrap();
+        assert_eq!(parsed.prefix.key, config.prefix.key);
+        assert_eq!(parsed.bindings.sidebar, config.bindings.sidebar);
+        assert_eq!(parsed.options.quit_behavior, config.options.quit_behavior);
+
+        let bad = json.replace(
+            "\"sidebar_width_percent\": 25",
+            "\"sidebar_width_percent\": 90",
         );
+        assert!(Config::from_json_str(&bad).is_err());
+        assert!(Config::from_json_str("{").is_err());
     }
 }
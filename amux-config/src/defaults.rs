@@ -1,30 +1,64 @@
 //! Default configuration that matches current hardcoded behavior
 
 use crate::types::{
-    Bindings, ClaudeConfig, CodexConfig, Config, Options, PrefixConfig, ProvidersConfig, UiConfig,
+    BindingMap, BindingValue, Bindings, ClaudeConfig, CodexConfig, Config, HighlightConfig,
+    ImeIntegration, Options, PrefixConfig, ProvidersConfig, PullStrategy, QuitBehavior,
+    SidebarSort, StartupAction, UiConfig,
 };
 use std::collections::HashMap;
 
 /// Get default configuration matching current amux keybindings
 pub fn default_config() -> Config {
     Config {
+        min_version: None,
         prefix: PrefixConfig {
             key: "C-s".to_string(),
         },
         options: Options {
             tree_view_enabled: true,
             git_panel_enabled: true,
-            mouse_enabled: false,
+            show_ignored: false,
+            mouse_enabled: true,
             fullscreen_on_connect: false,
             show_completed_todos: false,
+            show_session_titles: true,
+            session_page_size: 50,
+            open_links: true,
+            tab_title_format: "{index}:{name}".to_string(),
+            auto_start_daemon: false,
+            sidebar_sort: SidebarSort::None,
+            sidebar_pin_main: true,
+            startup_action: StartupAction::None,
+            theme: "dark".to_string(),
+            force_truecolor: false,
+            prefix_timeout_ms: 2000,
+            worktree_status_timeout_ms: 500,
+            git_refresh_debounce_ms: 500,
+            diff_collapse_context: 8,
+            resource_monitor_interval_secs: 10,
+            quit_behavior: QuitBehavior::Detach,
+            pull_strategy: PullStrategy::Rebase,
+            ime_integration: ImeIntegration::None,
+            external_terminal_command: String::new(),
+            editor_command: String::new(),
+            confirm_delete_session: true,
+            confirm_delete_branch: true,
+            confirm_delete_todo: true,
+            confirm_quit_running_sessions: true,
+            confirm_quit_dirty: false,
+            trash_ttl_secs: 30,
+            allow_broadcast: false,
+            word_delimiters: " \t\n\"'`()[]{}|&;:<>".to_string(),
         },
         ui: UiConfig {
             show_borders: true,
             sidebar_width: 30,
+            sidebar_width_percent: 25,
             terminal_rows: 24,
             terminal_cols: 80,
             terminal_scrollback: 10000,
         },
+        highlight: HighlightConfig::default(),
         bindings: default_bindings(),
         providers: ProvidersConfig {
             default: "claude".to_string(),
@@ -46,19 +80,27 @@ pub fn default_config() -> Config {
 /// Get default key bindings matching current amux behavior
 pub fn default_bindings() -> Bindings {
     Bindings {
-        global: default_global_bindings(),
-        prefix: default_prefix_bindings(),
-        sidebar: default_sidebar_bindings(),
-        terminal_normal: default_terminal_normal_bindings(),
-        terminal_insert: default_terminal_insert_bindings(),
-        diff: default_diff_bindings(),
-        git_status: default_git_status_bindings(),
-        todo: default_todo_bindings(),
-        dialog_text: default_dialog_text_bindings(),
-        dialog_confirm: default_dialog_confirm_bindings(),
+        global: actions(default_global_bindings()),
+        prefix: actions(default_prefix_bindings()),
+        sidebar: actions(default_sidebar_bindings()),
+        terminal_normal: actions(default_terminal_normal_bindings()),
+        terminal_insert: actions(default_terminal_insert_bindings()),
+        diff: actions(default_diff_bindings()),
+        git_status: actions(default_git_status_bindings()),
+        conflicts: actions(default_conflicts_bindings()),
+        todo: actions(default_todo_bindings()),
+        dialog_text: actions(default_dialog_text_bindings()),
+        dialog_confirm: actions(default_dialog_confirm_bindings()),
     }
 }
 
+/// Key-to-action entries of a binding table
+fn actions(map: HashMap<String, String>) -> BindingMap {
+    map.into_iter()
+        .map(|(key, action)| (key, BindingValue::Action(action)))
+        .collect()
+}
+
 fn default_global_bindings() -> HashMap<String, String> {
     HashMap::new() // No global bindings by default
 }
@@ -72,7 +114,12 @@ fn default_prefix_bindings() -> HashMap<String, String> {
     map.insert("t".to_string(), "focus-terminal".to_string());
     map.insert("n".to_string(), "create-session".to_string());
     map.insert("N".to_string(), "select-provider-and-create".to_string());
+    map.insert("~".to_string(), "shell-in-directory".to_string());
+    map.insert("$".to_string(), "suspend-to-shell".to_string());
     map.insert("a".to_string(), "add-worktree".to_string());
+    map.insert("A".to_string(), "add-repo".to_string());
+    map.insert("X".to_string(), "remove-repo".to_string());
+    map.insert("c".to_string(), "cleanup-worktrees".to_string());
     map.insert("d".to_string(), "delete-current".to_string());
     map.insert("r".to_string(), "refresh-all".to_string());
     map.insert("f".to_string(), "toggle-fullscreen".to_string());
@@ -82,13 +129,24 @@ fn default_prefix_bindings() -> HashMap<String, String> {
     map.insert("g".to_string(), "focus-git-status".to_string());
     map.insert("v".to_string(), "focus-diff".to_string());
     map.insert("o".to_string(), "open-todo".to_string());
+    map.insert("p".to_string(), "toggle-right-panel".to_string());
+    map.insert("/".to_string(), "search-history".to_string());
+    map.insert("u".to_string(), "undo".to_string());
+    map.insert("m".to_string(), "record-macro".to_string());
+    map.insert("@".to_string(), "replay-macro".to_string());
+    map.insert(":".to_string(), "command-mode".to_string());
     map.insert("q".to_string(), "quit".to_string());
+    map.insert("R".to_string(), "recent-sessions".to_string());
 
-    // Repo switching 1-9
+    // Recent session switching 1-9 (repos are switched with 1-9 in the sidebar)
     for i in 1..=9 {
-        // Will be handled specially in keybind resolution
-        map.insert(i.to_string(), format!("switch-repo-{}", i - 1));
+        map.insert(i.to_string(), format!("switch-recent-session-{}", i - 1));
     }
+    map.insert("<".to_string(), "move-repo-left".to_string());
+    map.insert(">".to_string(), "move-repo-right".to_string());
+    map.insert("H".to_string(), "shrink-sidebar".to_string());
+    map.insert("L".to_string(), "grow-sidebar".to_string());
+    map.insert("S".to_string(), "toggle-sidebar".to_string());
 
     map
 }
@@ -106,6 +164,8 @@ fn default_sidebar_bindings() -> HashMap<String, String> {
     map.insert("7".to_string(), "switch-repo-6".to_string());
     map.insert("8".to_string(), "switch-repo-7".to_string());
     map.insert("9".to_string(), "switch-repo-8".to_string());
+    map.insert("<".to_string(), "move-repo-left".to_string());
+    map.insert(">".to_string(), "move-repo-right".to_string());
 
     map.insert("Tab".to_string(), "focus-next".to_string());
     map.insert("S-Tab".to_string(), "focus-prev".to_string());
@@ -122,11 +182,32 @@ fn default_sidebar_bindings() -> HashMap<String, String> {
     map.insert("g".to_string(), "focus-git-status".to_string());
     map.insert("n".to_string(), "create-session".to_string());
     map.insert("N".to_string(), "select-provider-and-create".to_string());
+    map.insert("!".to_string(), "run-command".to_string());
+    map.insert("~".to_string(), "shell-in-directory".to_string());
+    map.insert("B".to_string(), "broadcast-input".to_string());
     map.insert("a".to_string(), "add-worktree".to_string());
     map.insert("d".to_string(), "delete-current".to_string());
     map.insert("x".to_string(), "delete-current".to_string());
     map.insert("R".to_string(), "rename-session".to_string());
+    map.insert("E".to_string(), "export-transcript".to_string());
+    map.insert("i".to_string(), "show-session-info".to_string());
+    map.insert("V".to_string(), "view-session-file".to_string());
+    map.insert("S".to_string(), "toggle-checkpoints".to_string());
+    map.insert("U".to_string(), "restore-checkpoint".to_string());
+    map.insert("O".to_string(), "open-in-external-terminal".to_string());
+    map.insert("b".to_string(), "rename-branch".to_string());
+    map.insert("D".to_string(), "cycle-delete-action".to_string());
+    map.insert("p".to_string(), "toggle-pin-session".to_string());
+    map.insert("K".to_string(), "move-session-up".to_string());
+    map.insert("J".to_string(), "move-session-down".to_string());
+    map.insert("/".to_string(), "search-sessions".to_string());
+    map.insert("C".to_string(), "quick-ship".to_string());
+    map.insert("y".to_string(), "copy".to_string());
+    map.insert("Y".to_string(), "copy-commit-sha".to_string());
+    map.insert("F".to_string(), "git-fetch".to_string());
+    map.insert("M".to_string(), "git-pull".to_string());
     map.insert("r".to_string(), "refresh-all".to_string());
+    map.insert("u".to_string(), "undo".to_string());
     map.insert("q".to_string(), "quit".to_string());
 
     // Diff toggle
@@ -157,6 +238,10 @@ fn default_terminal_normal_bindings() -> HashMap<String, String> {
     map.insert("g".to_string(), "scroll-top".to_string());
     map.insert("G".to_string(), "scroll-bottom".to_string());
 
+    map.insert("o".to_string(), "open-link".to_string());
+    map.insert("O".to_string(), "open-in-external-terminal".to_string());
+    map.insert("y".to_string(), "copy".to_string());
+
     map.insert("S-Tab".to_string(), "exit-terminal".to_string());
 
     map
@@ -168,6 +253,7 @@ fn default_terminal_insert_bindings() -> HashMap<String, String> {
     // From ccm-cli/src/tui/input/terminal.rs - Insert mode
     // Most keys are forwarded to PTY, but we intercept these special ones:
     map.insert("C-`".to_string(), "switch-to-shell".to_string());
+    map.insert("C-M-`".to_string(), "shell-in-directory".to_string());
     // Prefix key is handled separately (C-s) and always intercepted
 
     map
@@ -191,13 +277,22 @@ fn default_diff_bindings() -> HashMap<String, String> {
     map.insert("c".to_string(), "add-comment".to_string());
     map.insert("C".to_string(), "edit-comment".to_string());
     map.insert("x".to_string(), "delete-comment".to_string());
+    map.insert("u".to_string(), "undo".to_string());
 
     map.insert("n".to_string(), "next-comment".to_string());
     map.insert("N".to_string(), "prev-comment".to_string());
+    map.insert("/".to_string(), "search-in-diff".to_string());
 
     map.insert("S".to_string(), "submit-review-claude".to_string());
+    map.insert("R".to_string(), "show-reviews".to_string());
+
+    map.insert("y".to_string(), "copy".to_string());
 
     map.insert("r".to_string(), "refresh-diff".to_string());
+    map.insert("b".to_string(), "select-diff-base".to_string());
+    map.insert("m".to_string(), "diff-against-base".to_string());
+    map.insert("B".to_string(), "toggle-blame".to_string());
+    map.insert("e".to_string(), "open-in-editor".to_string());
 
     map.insert("f".to_string(), "toggle-fullscreen".to_string());
     map.insert("z".to_string(), "toggle-fullscreen".to_string());
@@ -228,6 +323,15 @@ fn default_git_status_bindings() -> HashMap<String, String> {
 
     map.insert("p".to_string(), "git-pull".to_string());
     map.insert("P".to_string(), "git-push".to_string());
+    map.insert("C".to_string(), "quick-ship".to_string());
+    map.insert("z".to_string(), "stash-save".to_string());
+    map.insert("Z".to_string(), "stash-pop".to_string());
+    map.insert("m".to_string(), "show-conflicts".to_string());
+    map.insert("e".to_string(), "open-in-editor".to_string());
+    map.insert("i".to_string(), "toggle-ignored".to_string());
+
+    map.insert("y".to_string(), "copy".to_string());
+    map.insert("Y".to_string(), "copy-commit-sha".to_string());
 
     map.insert("r".to_string(), "refresh-status".to_string());
 
@@ -238,6 +342,31 @@ fn default_git_status_bindings() -> HashMap<String, String> {
     map
 }
 
+fn default_conflicts_bindings() -> HashMap<String, String> {
+    let mut map = HashMap::new();
+
+    // Move between conflict hunks and conflicted files
+    map.insert("j".to_string(), "move-down".to_string());
+    map.insert("Down".to_string(), "move-down".to_string());
+    map.insert("k".to_string(), "move-up".to_string());
+    map.insert("Up".to_string(), "move-up".to_string());
+    map.insert("{".to_string(), "prev-file".to_string());
+    map.insert("}".to_string(), "next-file".to_string());
+
+    map.insert("o".to_string(), "resolve-ours".to_string());
+    map.insert("t".to_string(), "resolve-theirs".to_string());
+    map.insert("b".to_string(), "resolve-both".to_string());
+    map.insert("w".to_string(), "write-resolution".to_string());
+    map.insert("Enter".to_string(), "write-resolution".to_string());
+
+    map.insert("r".to_string(), "refresh-diff".to_string());
+
+    map.insert("Esc".to_string(), "back-to-terminal".to_string());
+    map.insert("q".to_string(), "back-to-terminal".to_string());
+
+    map
+}
+
 fn default_todo_bindings() -> HashMap<String, String> {
     let mut map = HashMap::new();
 
@@ -268,6 +397,8 @@ fn default_todo_bindings() -> HashMap<String, String> {
 
     map.insert("H".to_string(), "toggle-show-completed".to_string());
 
+    map.insert("u".to_string(), "undo".to_string());
+
     map.insert("r".to_string(), "refresh-todos".to_string());
 
     map.insert("Esc".to_string(), "close-popup".to_string());
@@ -1,37 +1,58 @@
 //! Key binding pattern parsing and resolution
 
-use crate::{actions::Action, types::Bindings, ConfigError, Result};
+use crate::types::{binding_pairs, BindingMap, Bindings, UNBOUND};
+use crate::{actions::Action, ConfigError, Result};
 use std::collections::HashMap;
 
 /// Represents a parsed key binding context
 ///
 /// These contexts determine which keybindings are active based on the
-/// current state of the application.
+/// current state of the application. Each one is configured by the
+/// `[bindings.<name>]` table of the same name (with `_` instead of `-`),
+/// where `<name>` is [`BindingContext::name`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum BindingContext {
-    /// Global bindings (always checked)
+    /// Global bindings, checked when the current context has no binding
+    /// for a key
     Global,
-    /// Prefix-based bindings (when prefix key is active)
+    /// Commands after the prefix key (e.g. `C-s`), in any context
     Prefix,
-    /// Sidebar/navigation context
+    /// Sidebar focused: repo worktrees and their sessions
     Sidebar,
-    /// Terminal normal mode (read-only, vim-like)
+    /// Terminal focused in normal mode (read-only, vim-like scrolling)
     TerminalNormal,
-    /// Terminal insert mode (forward to PTY mostly)
+    /// Terminal focused in insert mode; unbound keys go to the PTY
     TerminalInsert,
-    /// Diff view context
+    /// Diff view focused (file list with inline expanded diffs)
     Diff,
-    /// Git status panel context
+    /// Git status panel focused
     GitStatus,
-    /// TODO popup context
+    /// Merge conflict view focused
+    Conflicts,
+    /// TODO popup open
     Todo,
-    /// Text input dialog context
+    /// A dialog with a text field is open (names, messages, searches)
     DialogText,
-    /// Confirmation dialog context
+    /// A yes/no confirmation dialog is open
     DialogConfirm,
 }
 
 impl BindingContext {
+    /// Every context, in the order of the `[bindings]` tables
+    pub const ALL: [BindingContext; 11] = [
+        BindingContext::Global,
+        BindingContext::Prefix,
+        BindingContext::Sidebar,
+        BindingContext::TerminalNormal,
+        BindingContext::TerminalInsert,
+        BindingContext::Diff,
+        BindingContext::GitStatus,
+        BindingContext::Conflicts,
+        BindingContext::Todo,
+        BindingContext::DialogText,
+        BindingContext::DialogConfirm,
+    ];
+
     #[allow(clippy::should_implement_trait)]
     /// Get all context names for display
     pub fn all() -> &'static [&'static str] {
@@ -43,6 +64,7 @@ impl BindingContext {
             "terminal-insert",
             "diff",
             "git-status",
+            "conflicts",
             "todo",
             "dialog-text",
             "dialog-confirm",
@@ -50,8 +72,15 @@ impl BindingContext {
     }
 
     #[allow(clippy::should_implement_trait)]
-    /// Parse context from string
+    /// Parse context from string (same as [`BindingContext::from_name`])
     pub fn from_str(s: &str) -> Option<Self> {
+        Self::from_name(s)
+    }
+
+    /// Parse a context name as written in `:bind <context> <key> <action>`
+    ///
+    /// Case-insensitive; `_` may be used instead of `-` as in the TOML tables.
+    pub fn from_name(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "global" => Some(BindingContext::Global),
             "prefix" => Some(BindingContext::Prefix),
@@ -60,6 +89,7 @@ impl BindingContext {
             "terminal-insert" | "terminal_insert" => Some(BindingContext::TerminalInsert),
             "diff" => Some(BindingContext::Diff),
             "git-status" | "git_status" => Some(BindingContext::GitStatus),
+            "conflicts" => Some(BindingContext::Conflicts),
             "todo" => Some(BindingContext::Todo),
             "dialog-text" | "dialog_text" => Some(BindingContext::DialogText),
             "dialog-confirm" | "dialog_confirm" => Some(BindingContext::DialogConfirm),
@@ -67,7 +97,7 @@ impl BindingContext {
         }
     }
 
-    /// Get canonical name for this context
+    /// Get canonical name for this context (e.g. `"git-status"`)
     pub fn name(&self) -> &'static str {
         match self {
             BindingContext::Global => "global",
@@ -77,6 +107,7 @@ impl BindingContext {
             BindingContext::TerminalInsert => "terminal-insert",
             BindingContext::Diff => "diff",
             BindingContext::GitStatus => "git-status",
+            BindingContext::Conflicts => "conflicts",
             BindingContext::Todo => "todo",
             BindingContext::DialogText => "dialog-text",
             BindingContext::DialogConfirm => "dialog-confirm",
@@ -84,17 +115,27 @@ impl BindingContext {
     }
 }
 
+/// Modifiers in canonical order, with the short form used by `Display`
+const MODIFIERS: [(&str, &str); 3] = [("Ctrl", "C"), ("Alt", "A"), ("Shift", "S")];
+
 /// Represents a parsed key pattern like "C-s" or "S-Tab"
 ///
 /// # Format
 /// - "C-x" or "Ctrl-x" - Control key
 /// - "S-x" or "Shift-x" - Shift key
 /// - "A-x" or "Alt-x" - Alt key
-/// - "M-x" or "Meta-x" - Meta/Super key
+/// - "M-x" or "Meta-x" - same as Alt (terminals send Meta as Alt)
 /// - Single chars: "a", "j", "k", "1", etc.
 /// - Special keys: "Enter", "Esc", "Tab", "Space", "Backspace", "Up", "Down", etc.
+///
+/// Patterns are kept in canonical form, so every spelling of a key compares
+/// equal and `Display` writes the one the TUI produces for a key press:
+/// modifiers in the order `C-A-S`, key aliases replaced (`Return` is
+/// `Enter`, `BackTab` is `S-Tab`), and Shift folded into single characters
+/// (`S-a` is `A`, `S-?` is `?`).
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct KeyPattern {
+    /// Canonical modifier names joined with `+` (e.g. "Ctrl+Shift")
     pub modifiers: String,
     pub key: String,
 }
@@ -118,100 +159,143 @@ impl KeyPattern {
         // Split by hyphen to extract modifiers and key
         let parts: Vec<&str> = s.split('-').collect();
 
-        if parts.is_empty() {
-            return Err(ConfigError::InvalidKeyPattern(format!(
-                "Invalid key pattern: {}",
-                s
-            )));
-        }
-
-        // Check if we have modifiers
         // We only look for modifiers at the beginning, separated by hyphens
         // e.g., "C-s", "S-Tab", "C-S-x", but not "s-C" or "Tab-C"
         let mut modifiers = Vec::new();
         let mut key_idx = 0;
-
-        // Scan for modifiers at the beginning
-        // Modifiers are only recognized in key positions before the actual key
         for (i, part) in parts.iter().enumerate() {
-            // Only recognize as modifier if there are more parts after it (i.e., followed by -key)
-            // Single uppercase letters are only modifiers if they're not the last part
-            // Examples: "C-s" (C is modifier), but "C" alone is a key
+            // Only a modifier if followed by -key: "C-s" (C is modifier), but "C" alone is a key
             let has_following_parts = i + 1 < parts.len();
-
-            let is_modifier = has_following_parts
-                && matches!(
-                    *part,
-                    "C" | "S" | "A" | "M" | "CTRL" | "SHIFT" | "ALT" | "META"
-                );
-
-            if is_modifier {
-                match *part {
-                    "C" | "CTRL" => modifiers.push("Ctrl"),
-                    "S" | "SHIFT" => modifiers.push("Shift"),
-                    "A" | "ALT" => modifiers.push("Alt"),
-                    "M" | "META" => modifiers.push("Meta"),
-                    _ => {} // shouldn't happen
+            match Self::modifier_name(part) {
+                Some(modifier) if has_following_parts => {
+                    modifiers.push(modifier);
+                    key_idx = i + 1;
                 }
-                key_idx = i + 1;
-            } else {
-                // First non-modifier is the start of the key
-                break;
+                _ => break,
             }
         }
 
-        if key_idx >= parts.len() {
+        // Remaining parts are the key (join them back with hyphens); "C--" is Ctrl+minus
+        let key = parts[key_idx..].join("-");
+        if key.is_empty() {
             return Err(ConfigError::InvalidKeyPattern(format!(
                 "Invalid key pattern: {} (missing key after modifiers)",
                 s
             )));
         }
 
-        // Remaining parts are the key (join them back with hyphens)
-        let key = parts[key_idx..].join("-");
+        let has = |name| modifiers.contains(&name);
+        Self::new(has("Ctrl"), has("Alt"), has("Shift"), &key)
+    }
 
-        // Validate key
-        if !Self::is_valid_key(&key) {
+    /// Build a pattern in canonical form from its modifiers and key name
+    ///
+    /// `key` may be any name [`KeyPattern::parse`] accepts, aliases included.
+    pub fn new(ctrl: bool, alt: bool, shift: bool, key: &str) -> Result<Self> {
+        let Some(mut key) = Self::canonical_key(key) else {
             return Err(ConfigError::InvalidKeyPattern(format!(
                 "Invalid key: {} (not a recognized key)",
                 key
             )));
+        };
+
+        let mut shift = shift;
+        if key == "BackTab" {
+            key = "Tab".to_string();
+            shift = true;
+        } else if key.len() == 1 {
+            // The character already says whether Shift was held
+            if shift {
+                key = key.to_ascii_uppercase();
+            }
+            shift = false;
         }
 
-        let modifiers = modifiers.join("+");
+        let held = [ctrl, alt, shift];
+        let modifiers: Vec<&str> = MODIFIERS
+            .iter()
+            .zip(held)
+            .filter(|(_, held)| *held)
+            .map(|((name, _), _)| *name)
+            .collect();
+        Ok(KeyPattern {
+            modifiers: modifiers.join("+"),
+            key,
+        })
+    }
+
+    /// Whether Ctrl is part of the pattern
+    pub fn ctrl(&self) -> bool {
+        self.has_modifier("Ctrl")
+    }
+
+    /// Whether Alt (or Meta) is part of the pattern
+    pub fn alt(&self) -> bool {
+        self.has_modifier("Alt")
+    }
+
+    /// Whether Shift is part of the pattern; never true for single characters
+    pub fn shift(&self) -> bool {
+        self.has_modifier("Shift")
+    }
+
+    fn has_modifier(&self, name: &str) -> bool {
+        self.modifiers.split('+').any(|m| m == name)
+    }
 
-        Ok(KeyPattern { modifiers, key })
+    /// Canonical name of a modifier token, if `part` is one
+    fn modifier_name(part: &str) -> Option<&'static str> {
+        match part {
+            "C" => Some("Ctrl"),
+            "S" => Some("Shift"),
+            "A" | "M" => Some("Alt"),
+            _ => match part.to_ascii_lowercase().as_str() {
+                "ctrl" => Some("Ctrl"),
+                "shift" => Some("Shift"),
+                "alt" | "meta" => Some("Alt"),
+                _ => None,
+            },
+        }
     }
 
-    /// Check if a key string is valid
-    fn is_valid_key(key: &str) -> bool {
-        match key {
+    /// Canonical name of a key, or None if it is not a recognized key
+    fn canonical_key(key: &str) -> Option<String> {
+        // Single character keys (letters, numbers, symbols)
+        let mut chars = key.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return c.is_ascii_graphic().then(|| key.to_string());
+        }
+
+        let name = match key {
             // Special keys
-            "Enter" | "Return" | "Esc" | "Escape" | "Tab" | "Space" | "Backspace" | "Back" => true,
-            "Up" | "Down" | "Left" | "Right" => true,
-            "Home" | "End" | "PageUp" | "PageDown" | "Page_Up" | "Page_Down" => true,
-            "Delete" | "Insert" => true,
+            "Enter" | "Return" => "Enter",
+            "Esc" | "Escape" => "Esc",
+            "Tab" => "Tab",
+            "BackTab" => "BackTab",
+            "Space" => "Space",
+            "Backspace" | "Back" => "Backspace",
+            "Up" | "Down" | "Left" | "Right" => key,
+            "Home" | "End" | "Delete" | "Insert" => key,
+            "PageUp" | "Page_Up" => "PageUp",
+            "PageDown" | "Page_Down" => "PageDown",
             // Function keys
-            k if k.starts_with('F') && k.len() <= 3 => {
-                k[1..].parse::<u8>().is_ok() && k[1..].parse::<u8>().unwrap() <= 24
-            }
-            // Single character keys (letters, numbers, symbols)
-            k if k.len() == 1 => {
-                let c = k.chars().next().unwrap();
-                c.is_ascii_alphanumeric() || c.is_ascii_punctuation()
+            k => {
+                let n: u8 = k.strip_prefix('F')?.parse().ok()?;
+                return (1..=24).contains(&n).then(|| format!("F{}", n));
             }
-            _ => false,
-        }
+        };
+        Some(name.to_string())
     }
 }
 
 impl std::fmt::Display for KeyPattern {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.modifiers.is_empty() {
-            write!(f, "{}", self.key)
-        } else {
-            write!(f, "{}-{}", self.modifiers.replace("+", "-"), self.key)
+        for (name, short) in MODIFIERS {
+            if self.has_modifier(name) {
+                write!(f, "{}-", short)?;
+            }
         }
+        write!(f, "{}", self.key)
     }
 }
 
@@ -239,6 +323,7 @@ impl KeybindMap {
         map.load_context_bindings(BindingContext::TerminalInsert, &bindings.terminal_insert)?;
         map.load_context_bindings(BindingContext::Diff, &bindings.diff)?;
         map.load_context_bindings(BindingContext::GitStatus, &bindings.git_status)?;
+        map.load_context_bindings(BindingContext::Conflicts, &bindings.conflicts)?;
         map.load_context_bindings(BindingContext::Todo, &bindings.todo)?;
         map.load_context_bindings(BindingContext::DialogText, &bindings.dialog_text)?;
         map.load_context_bindings(BindingContext::DialogConfirm, &bindings.dialog_confirm)?;
@@ -250,20 +335,24 @@ impl KeybindMap {
     fn load_context_bindings(
         &mut self,
         context: BindingContext,
-        bindings: &HashMap<String, String>,
+        bindings: &BindingMap,
     ) -> Result<()> {
         let mut context_bindings = HashMap::new();
 
-        for (key_str, action_str) in bindings {
+        for (key_str, action_str) in binding_pairs(bindings) {
+            if action_str == UNBOUND {
+                continue;
+            }
+
             // Skip invalid key patterns
-            if KeyPattern::parse(key_str).is_err() {
+            let Ok(pattern) = KeyPattern::parse(key_str) else {
                 eprintln!("Warning: Invalid key pattern in config: {}", key_str);
                 continue;
-            }
+            };
 
-            // Skip invalid actions
+            // Skip invalid actions; keys are stored in canonical form
             if let Some(action) = Action::from_str(action_str) {
-                context_bindings.insert(key_str.clone(), action);
+                context_bindings.insert(pattern.to_string(), action);
             } else {
                 eprintln!("Warning: Invalid action in config: {}", action_str);
             }
@@ -275,19 +364,23 @@ impl KeybindMap {
 
     /// Resolve a key pattern to an action in a specific context
     ///
+    /// `key_str` may use any spelling [`KeyPattern::parse`] accepts.
     /// Returns None if no binding found.
     pub fn resolve(&self, key_str: &str, context: BindingContext) -> Option<Action> {
+        let key_str = KeyPattern::parse(key_str).ok()?.to_string();
+        let key_str = key_str.as_str();
+
         // Check context-specific bindings first
         if let Some(bindings) = self.bindings.get(&context) {
             if let Some(action) = bindings.get(key_str) {
-                return Some(*action);
+                return Some(action.clone());
             }
         }
 
         // Check global bindings as fallback
         if let Some(bindings) = self.bindings.get(&BindingContext::Global) {
             if let Some(action) = bindings.get(key_str) {
-                return Some(*action);
+                return Some(action.clone());
             }
         }
 
@@ -316,6 +409,27 @@ impl KeybindMap {
         self.bindings.get(&context)
     }
 
+    /// Every binding as (context, key, action), sorted by context name then key
+    ///
+    /// For listings like the help overlay and completions; lookups should
+    /// use [`KeybindMap::resolve`].
+    pub fn describe_all(&self) -> Vec<(BindingContext, KeyPattern, Action)> {
+        let mut all: Vec<(BindingContext, KeyPattern, Action)> = self
+            .bindings
+            .iter()
+            .flat_map(|(context, bindings)| {
+                bindings.iter().filter_map(|(key_str, action)| {
+                    // Keys were validated and canonicalized on load
+                    KeyPattern::parse(key_str)
+                        .ok()
+                        .map(|pattern| (*context, pattern, action.clone()))
+                })
+            })
+            .collect();
+        all.sort_by_cached_key(|(context, pattern, _)| (context.name(), pattern.to_string()));
+        all
+    }
+
     /// Get all contexts that have bindings
     pub fn contexts_with_bindings(&self) -> Vec<BindingContext> {
         self.bindings
@@ -363,5 +477,139 @@ impl KeybindMap {
     }
 }
 
-// Tests temporarily disabled due to module test compilation issues
-// Will be verified through integration tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::BindingValue;
+
+    #[test]
+    fn test_context_names_round_trip() {
+        let names: Vec<&str> = BindingContext::ALL.iter().map(|c| c.name()).collect();
+        assert_eq!(names, BindingContext::all());
+        for context in BindingContext::ALL {
+            assert_eq!(BindingContext::from_name(context.name()), Some(context));
+        }
+        assert_eq!(
+            BindingContext::from_name("Git_Status"),
+            Some(BindingContext::GitStatus)
+        );
+        assert_eq!(BindingContext::from_name("nowhere"), None);
+    }
+
+    #[test]
+    fn test_describe_all_sorted() {
+        let bindings = Bindings {
+            sidebar: HashMap::from([
+                ("k".to_string(), "move-up".into()),
+                ("j".to_string(), "move-down".into()),
+            ]),
+            diff: HashMap::from([("r".to_string(), "refresh-diff".into())]),
+            ..Default::default()
+        };
+        let map = KeybindMap::from_bindings(&bindings, "C-s").unwrap();
+
+        let described: Vec<(&str, String, Action)> = map
+            .describe_all()
+            .into_iter()
+            .map(|(c, p, a)| (c.name(), p.to_string(), a))
+            .collect();
+        assert_eq!(
+            described,
+            vec![
+                ("diff", "r".to_string(), Action::RefreshDiff),
+                ("sidebar", "j".to_string(), Action::MoveDown),
+                ("sidebar", "k".to_string(), Action::MoveUp),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_canonical_form() {
+        let cases = [
+            ("j", "j"),
+            ("C-s", "C-s"),
+            ("Ctrl-s", "C-s"),
+            ("CTRL-s", "C-s"),
+            ("C-S-Left", "C-S-Left"),
+            ("S-C-Left", "C-S-Left"),
+            ("Shift-Ctrl-Alt-Delete", "C-A-S-Delete"),
+            ("M-Enter", "A-Enter"),
+            ("Meta-Return", "A-Enter"),
+            ("F12", "F12"),
+            ("S-F12", "S-F12"),
+            ("F", "F"),
+            ("S-a", "A"),
+            ("C-S-s", "C-S"),
+            ("S-?", "?"),
+            ("S-Tab", "S-Tab"),
+            ("BackTab", "S-Tab"),
+            ("Tab", "Tab"),
+            ("S-Space", "S-Space"),
+            ("Page_Down", "PageDown"),
+            ("Back", "Backspace"),
+            ("-", "-"),
+            ("C--", "C--"),
+        ];
+        for (input, canonical) in cases {
+            let pattern = KeyPattern::parse(input).unwrap();
+            assert_eq!(pattern.to_string(), canonical, "parsing {}", input);
+            // Display round-trips
+            assert_eq!(KeyPattern::parse(canonical).unwrap(), pattern);
+        }
+
+        for invalid in ["", "C-", "F0", "F25", "Tab-C", "Hyper-x", "é"] {
+            assert!(KeyPattern::parse(invalid).is_err(), "parsing {}", invalid);
+        }
+
+        let pattern = KeyPattern::parse("S-M-C-x").unwrap();
+        assert!(pattern.ctrl() && pattern.alt() && !pattern.shift());
+        assert_eq!(pattern.key, "X");
+    }
+
+    #[test]
+    fn test_resolve_any_spelling() {
+        let bindings = Bindings {
+            sidebar: HashMap::from([
+                ("S-C-Left".to_string(), "move-up".into()),
+                ("Meta-Return".to_string(), "quit".into()),
+            ]),
+            ..Default::default()
+        };
+        let map = KeybindMap::from_bindings(&bindings, "Ctrl-s").unwrap();
+        assert_eq!(
+            map.resolve("C-S-Left", BindingContext::Sidebar),
+            Some(Action::MoveUp)
+        );
+        assert_eq!(
+            map.resolve("A-Enter", BindingContext::Sidebar),
+            Some(Action::Quit)
+        );
+        assert_eq!(map.resolve("Left", BindingContext::Sidebar), None);
+        assert!(map.is_prefix_key("C-s"));
+        assert_eq!(map.prefix_key_display(), "[C-s]");
+    }
+
+    #[test]
+    fn test_action_bound_to_several_keys() {
+        let bindings = Bindings {
+            sidebar: HashMap::from([
+                (
+                    "move-up".to_string(),
+                    BindingValue::Keys(vec!["k".to_string(), "Up".to_string()]),
+                ),
+                ("k".to_string(), "quit".into()),
+            ]),
+            ..Default::default()
+        };
+        let map = KeybindMap::from_bindings(&bindings, "C-s").unwrap();
+        assert_eq!(
+            map.resolve("Up", BindingContext::Sidebar),
+            Some(Action::MoveUp)
+        );
+        // A key-to-action entry wins over the same key in an array
+        assert_eq!(
+            map.resolve("k", BindingContext::Sidebar),
+            Some(Action::Quit)
+        );
+    }
+}
@@ -15,29 +15,49 @@
 //! - [`actions`] - Action enum and command parsing
 //! - [`keybind`] - Key pattern parsing and keybind resolution
 //! - [`commands`] - Runtime command parsing and validation
+//! - [`macros`] - Keyboard macros kept in `~/.amux/macros.toml`
+//! - `schema` - JSON Schema of the config file (`json` feature)
 
 pub mod actions;
 pub mod commands;
 pub mod config;
 pub mod defaults;
 pub mod keybind;
+pub mod macros;
 pub mod parser;
+#[cfg(feature = "json")]
+pub mod schema;
 pub mod types;
 pub mod writer;
 
 // Re-export commonly used types
 pub use keybind::{BindingContext, KeyPattern, KeybindMap};
 pub use types::Config;
-pub use types::{Bindings, Options, UiConfig};
+pub use types::{
+    AvailableProvider, BindingMap, BindingValue, Bindings, Confirmation, HighlightConfig,
+    ImeIntegration, Options, PartialConfig, PartialOptions, PullStrategy, QuitBehavior,
+    SidebarSort, StartupAction, UiConfig,
+};
 
-pub use actions::Action;
-pub use commands::RuntimeCommand;
+pub use actions::{Action, CopyTarget};
+pub use commands::{ProviderCommand, RuntimeCommand};
+pub use macros::{MacroStep, Macros};
 
 // Terminal size defaults (for use outside config context)
 pub const DEFAULT_TERMINAL_ROWS: u16 = 24;
 pub const DEFAULT_TERMINAL_COLS: u16 = 80;
 pub const DEFAULT_SCROLLBACK: usize = 10000;
 
+/// Line number of a review comment on a whole file rather than one line
+pub const FILE_COMMENT_LINE: i32 = -1;
+
+/// Allowed range for `ui.sidebar_width_percent`
+pub const SIDEBAR_WIDTH_PERCENT_RANGE: std::ops::RangeInclusive<u16> = 10..=60;
+
+/// Tokens `options.tab_title_format` may contain
+pub const TAB_TITLE_TOKENS: &[&str] =
+    &["index", "name", "session_count", "dirty_count", "provider"];
+
 /// Errors that can occur during config operations
 #[derive(thiserror::Error, Debug)]
 pub enum ConfigError {
@@ -50,6 +70,13 @@ pub enum ConfigError {
     #[error("TOML serialization error: {0}")]
     TomlSerialize(#[from] toml::ser::Error),
 
+    #[error("TOML editing error: {0}")]
+    TomlEdit(#[from] toml_edit::TomlError),
+
+    #[cfg(feature = "json")]
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
     #[error("Invalid key pattern: {0}")]
     InvalidKeyPattern(String),
 
@@ -65,6 +92,9 @@ pub enum ConfigError {
     #[error("Circular source file dependency detected: {0}")]
     CircularDependency(String),
 
+    #[error("Config requires amux {required} or newer, this is {current}")]
+    VersionMismatch { required: String, current: String },
+
     #[error("Invalid option: {0}")]
     InvalidOption(String),
 
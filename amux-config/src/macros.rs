@@ -0,0 +1,170 @@
+//! Keyboard macros kept in `~/.amux/macros.toml`
+//!
+//! A macro is the list of actions recorded into a register, each with the
+//! context it was resolved in, so it replays the same commands after keys
+//! are rebound. Registers are `a`-`z` and `0`-`9`; the file has one array
+//! of `"<context>:<action>"` steps per register:
+//!
+//! ```toml
+//! a = ["sidebar:move-down", "prefix:focus-diff", "diff:toggle-expand"]
+//! ```
+
+use crate::actions::Action;
+use crate::config::config_dir;
+use crate::keybind::BindingContext;
+use crate::{ConfigError, Result};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Recorded macros by register
+pub type Macros = BTreeMap<char, Vec<MacroStep>>;
+
+/// One recorded action and the context it was resolved in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacroStep {
+    pub context: BindingContext,
+    pub action: Action,
+}
+
+impl MacroStep {
+    /// Parse a `"<context>:<action>"` step
+    pub fn parse(s: &str) -> Result<Self> {
+        let (context, action) = s.split_once(':').ok_or_else(|| {
+            ConfigError::InvalidAction(format!("{} (expected context:action)", s))
+        })?;
+        let context = BindingContext::from_name(context)
+            .ok_or_else(|| ConfigError::InvalidAction(format!("{} (unknown context)", s)))?;
+        let action =
+            Action::from_str(action).ok_or_else(|| ConfigError::InvalidAction(s.into()))?;
+        Ok(MacroStep { context, action })
+    }
+}
+
+impl fmt::Display for MacroStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.context.name(), self.action.name())
+    }
+}
+
+/// Whether `c` names a register
+pub fn is_register(c: char) -> bool {
+    c.is_ascii_lowercase() || c.is_ascii_digit()
+}
+
+/// Get the default macros file path
+pub fn macros_file() -> PathBuf {
+    config_dir().join("macros.toml")
+}
+
+/// Load the macros file (empty if it doesn't exist)
+pub fn load_macros() -> Result<Macros> {
+    load_macros_from(&macros_file())
+}
+
+/// Write the macros file
+pub fn save_macros(macros: &Macros) -> Result<()> {
+    save_macros_to(&macros_file(), macros)
+}
+
+/// Load macros from a specific file (empty if it doesn't exist)
+pub fn load_macros_from(path: &Path) -> Result<Macros> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => parse_macros(&content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Macros::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Write macros to a specific file, creating its directory
+pub fn save_macros_to(path: &Path, macros: &Macros) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, format_macros(macros)?)?;
+    Ok(())
+}
+
+/// Parse the contents of a macros file
+pub fn parse_macros(content: &str) -> Result<Macros> {
+    let table: BTreeMap<String, Vec<String>> = toml::from_str(content)?;
+    let mut macros = Macros::new();
+    for (name, steps) in table {
+        let register = match name.chars().collect::<Vec<_>>()[..] {
+            [c] if is_register(c) => c,
+            _ => {
+                return Err(ConfigError::ValidationError(format!(
+                    "Invalid macro register: {}",
+                    name
+                )))
+            }
+        };
+        let steps = steps
+            .iter()
+            .map(|step| MacroStep::parse(step))
+            .collect::<Result<Vec<_>>>()?;
+        macros.insert(register, steps);
+    }
+    Ok(macros)
+}
+
+/// Macros as the TOML of a macros file
+pub fn format_macros(macros: &Macros) -> Result<String> {
+    let table: BTreeMap<String, Vec<String>> = macros
+        .iter()
+        .map(|(register, steps)| {
+            let steps = steps.iter().map(MacroStep::to_string).collect();
+            (register.to_string(), steps)
+        })
+        .collect();
+    Ok(toml::to_string(&table)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(context: BindingContext, action: Action) -> MacroStep {
+        MacroStep { context, action }
+    }
+
+    #[test]
+    fn test_macros_round_trip() {
+        let mut macros = Macros::new();
+        macros.insert(
+            'a',
+            vec![
+                step(BindingContext::Sidebar, Action::MoveDown),
+                step(BindingContext::Prefix, Action::FocusDiff),
+                step(
+                    BindingContext::TerminalNormal,
+                    Action::SendText("git status\n".to_string()),
+                ),
+            ],
+        );
+        macros.insert('1', Vec::new());
+
+        let content = format_macros(&macros).unwrap();
+        assert!(content.contains("\"sidebar:move-down\""), "{}", content);
+        assert_eq!(parse_macros(&content).unwrap(), macros);
+    }
+
+    #[test]
+    fn test_parse_macros_errors() {
+        assert_eq!(
+            parse_macros("b = [\"git-status:stage\"]").unwrap()[&'b'],
+            [step(BindingContext::GitStatus, Action::StageFile)]
+        );
+        assert!(parse_macros("ab = []").is_err());
+        assert!(parse_macros("A = []").is_err());
+        assert!(parse_macros("a = [\"move-down\"]").is_err());
+        assert!(parse_macros("a = [\"nowhere:move-down\"]").is_err());
+        assert!(parse_macros("a = [\"sidebar:fly\"]").is_err());
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let path = std::env::temp_dir().join("amux-no-such-dir/macros.toml");
+        assert!(load_macros_from(&path).unwrap().is_empty());
+    }
+}
@@ -1,8 +1,8 @@
 //! TOML configuration parsing and validation
 
-use crate::types::Config;
+use crate::types::{Bindings, Config};
 use crate::{ConfigError, Result};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Parse config from TOML string
 pub fn parse_toml(content: &str) -> Result<Config> {
@@ -11,23 +11,228 @@ pub fn parse_toml(content: &str) -> Result<Config> {
     Ok(config)
 }
 
-/// Load config from a TOML file
+/// Parse config from JSON string (same fields as the TOML file)
+#[cfg(feature = "json")]
+pub fn parse_json(content: &str) -> Result<Config> {
+    let config: Config = serde_json::from_str(content)?;
+    validate_config(&config)?;
+    Ok(config)
+}
+
+/// Load config from a TOML file and the files its `source` list names
+///
+/// Each sourced file is deep-merged over what was loaded before it: tables
+/// merge key by key, other values replace. Its `[bindings]` are layered
+/// with [`Bindings::merge`], so rebinding a key in any spelling replaces
+/// the earlier binding. A source may be a file, a
+/// directory (every `.toml` in it, sorted by name) or a pattern with `*`
+/// and `?` in its last component. Relative paths are resolved against the
+/// file that names them, `~/` against the home directory.
 pub fn load_from_file(path: &Path) -> Result<Config> {
+    let mut merged = toml::Table::new();
+    let mut bindings = Bindings::default();
+    let sources = load_layer(path, &mut Vec::new(), &mut merged, &mut bindings)?;
+
+    let mut config: Config = toml::Value::Table(merged).try_into()?;
+    // Only the main file's list; sourced files' lists were followed above
+    config.source = sources;
+    config.bindings = bindings;
+    validate_config(&config)?;
+    Ok(config)
+}
+
+/// Merge `path` into `merged` and `bindings`, then the files it sources;
+/// returns its `source` list
+///
+/// `loading` holds the files being loaded, to catch a file sourcing itself
+/// through any chain of sources.
+fn load_layer(
+    path: &Path,
+    loading: &mut Vec<PathBuf>,
+    merged: &mut toml::Table,
+    bindings: &mut Bindings,
+) -> Result<Vec<String>> {
+    let canonical = path.canonicalize()?;
+    if loading.contains(&canonical) {
+        return Err(ConfigError::CircularDependency(path.display().to_string()));
+    }
+
     let content = std::fs::read_to_string(path)?;
-    parse_toml(&content)
+    let mut table: toml::Table = toml::from_str(&content)?;
+    let sources: Vec<String> = match table.remove("source") {
+        Some(value) => value.try_into()?,
+        None => Vec::new(),
+    };
+    if let Some(value) = table.remove("bindings") {
+        bindings.merge(&value.try_into()?);
+    }
+    deep_merge(merged, table);
+
+    loading.push(canonical);
+    let base = path.parent().unwrap_or(Path::new("."));
+    for source in &sources {
+        for file in expand_source(source, base)? {
+            load_layer(&file, loading, merged, bindings)?;
+        }
+    }
+    loading.pop();
+
+    Ok(sources)
+}
+
+/// Merge `overlay` into `base`: tables key by key, anything else replaced
+fn deep_merge(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(table)) => {
+                deep_merge(base_table, table)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Files a `source` entry names, in load order
+///
+/// A pattern or directory matching no files is only a warning.
+fn expand_source(source: &str, base: &Path) -> Result<Vec<PathBuf>> {
+    let path = match source.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+        None => base.join(source),
+    };
+
+    let (dir, pattern) = if source.ends_with('/') || path.is_dir() {
+        (path.as_path(), "*.toml")
+    } else {
+        match path.file_name().and_then(|name| name.to_str()) {
+            Some(name) if name.contains(['*', '?']) => {
+                (path.parent().unwrap_or(Path::new(".")), name)
+            }
+            _ => return Ok(vec![path]),
+        }
+    };
+
+    let mut files: Vec<PathBuf> = match std::fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry.file_type().is_ok_and(|t| !t.is_dir())
+                    && entry
+                        .file_name()
+                        .to_str()
+                        .is_some_and(|name| wildcard_match(pattern, name))
+            })
+            .map(|entry| entry.path())
+            .collect(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e.into()),
+    };
+    files.sort();
+
+    if files.is_empty() {
+        eprintln!("Warning: No config files match source: {}", source);
+    }
+    Ok(files)
+}
+
+/// Match a file name against `*` (any run) and `?` (any one char)
+///
+/// Like the shell, wildcards don't match a leading `.` (editor swap files
+/// and the like stay out of `conf.d/*`).
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    fn matches(pattern: &[char], name: &[char]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some(('*', rest)) => (0..=name.len()).any(|skip| matches(rest, &name[skip..])),
+            Some(('?', rest)) => !name.is_empty() && matches(rest, &name[1..]),
+            Some((c, rest)) => name.first() == Some(c) && matches(rest, &name[1..]),
+        }
+    }
+
+    if name.starts_with('.') && !pattern.starts_with('.') {
+        return false;
+    }
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    matches(&pattern, &name)
 }
 
 /// Validate configuration for consistency
 fn validate_config(config: &Config) -> Result<()> {
+    if let Some(required) = &config.min_version {
+        check_min_version(required, env!("CARGO_PKG_VERSION"))?;
+    }
+
     // Check prefix key is valid
     crate::keybind::KeyPattern::parse(&config.prefix.key)?;
 
     // Validate that all key patterns are valid
     validate_bindings(&config.bindings)?;
 
+    let percent = config.ui.sidebar_width_percent;
+    if !crate::SIDEBAR_WIDTH_PERCENT_RANGE.contains(&percent) {
+        return Err(ConfigError::ValidationError(format!(
+            "ui.sidebar_width_percent must be between {} and {}, got {}",
+            crate::SIDEBAR_WIDTH_PERCENT_RANGE.start(),
+            crate::SIDEBAR_WIDTH_PERCENT_RANGE.end(),
+            percent
+        )));
+    }
+
+    for token in template_tokens(&config.options.tab_title_format) {
+        if !crate::TAB_TITLE_TOKENS.contains(&token) {
+            return Err(ConfigError::ValidationError(format!(
+                "options.tab_title_format: unknown token {{{}}}, expected one of {}",
+                token,
+                crate::TAB_TITLE_TOKENS.join(", ")
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Fail if `current` is older than the `required` version
+fn check_min_version(required: &str, current: &str) -> Result<()> {
+    let parsed = parse_version(required).ok_or_else(|| {
+        ConfigError::ValidationError(format!(
+            "min_version: expected a version like \"1.2.3\", got \"{}\"",
+            required
+        ))
+    })?;
+    if parse_version(current).is_some_and(|current| current < parsed) {
+        return Err(ConfigError::VersionMismatch {
+            required: required.to_string(),
+            current: current.to_string(),
+        });
+    }
     Ok(())
 }
 
+/// `MAJOR.MINOR.PATCH` as numbers; pre-release and build suffixes are
+/// ignored, and missing minor or patch numbers count as 0
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().unwrap_or(Some(0))?;
+    let patch = parts.next().unwrap_or(Some(0))?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
+}
+
+/// Names of the `{token}`s in a format string
+pub fn template_tokens(template: &str) -> impl Iterator<Item = &str> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(token, _)| token))
+}
+
 /// Validate all bindings
 fn validate_bindings(bindings: &crate::types::Bindings) -> Result<()> {
     // Collect all binding maps to validate
@@ -39,13 +244,14 @@ fn validate_bindings(bindings: &crate::types::Bindings) -> Result<()> {
         ("terminal-insert", &bindings.terminal_insert),
         ("diff", &bindings.diff),
         ("git-status", &bindings.git_status),
+        ("conflicts", &bindings.conflicts),
         ("todo", &bindings.todo),
         ("dialog-text", &bindings.dialog_text),
         ("dialog-confirm", &bindings.dialog_confirm),
     ];
 
     for (context_name, binding_map) in all_bindings {
-        for (key_str, action_str) in binding_map {
+        for (key_str, action_str) in crate::types::binding_pairs(binding_map) {
             // Validate key pattern
             if let Err(e) = crate::keybind::KeyPattern::parse(key_str) {
                 eprintln!(
@@ -67,5 +273,123 @@ fn validate_bindings(bindings: &crate::types::Bindings) -> Result<()> {
     Ok(())
 }
 
-// Tests temporarily disabled due to module test compilation issues
-// Will be verified through integration tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_version() {
+        assert!(check_min_version("0.3", "0.3.0").is_ok());
+        assert!(check_min_version("0.2.9", "0.3.0").is_ok());
+        assert!(check_min_version("0.3.0-beta.1", "0.3.0").is_ok());
+        assert!(matches!(
+            check_min_version("0.10.0", "0.9.5"),
+            Err(ConfigError::VersionMismatch { required, current })
+                if required == "0.10.0" && current == "0.9.5"
+        ));
+        assert!(matches!(
+            check_min_version("latest", "0.3.0"),
+            Err(ConfigError::ValidationError(_))
+        ));
+
+        let future = "min_version = \"999.0.0\"\n";
+        assert!(matches!(
+            parse_toml(future),
+            Err(ConfigError::VersionMismatch { .. })
+        ));
+        let current = format!("min_version = \"{}\"\n", env!("CARGO_PKG_VERSION"));
+        assert!(parse_toml(&current).is_ok());
+    }
+
+    /// Fresh directory under the system temp dir for one test
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("amux-source-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_source_directory_and_glob() {
+        let dir = temp_dir("glob");
+        std::fs::create_dir(dir.join("conf.d")).unwrap();
+        std::fs::write(
+            dir.join("config.toml"),
+            "source = [\"conf.d/\", \"extra/*.toml\", \"local-*.toml\"]\n\
+             [options]\ntree_view_enabled = false\nauto_start_daemon = true\n\
+             [bindings.sidebar]\n\"j\" = \"move-down\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("conf.d/10-a.toml"),
+            "[options]\ntree_view_enabled = true\n[bindings.sidebar]\n\"z\" = \"quit\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("conf.d/20-b.toml"),
+            "[bindings.sidebar]\n\"z\" = \"move-up\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("conf.d/.swap.toml"), "not toml").unwrap();
+        std::fs::write(dir.join("conf.d/notes.txt"), "not toml").unwrap();
+        std::fs::write(dir.join("local-1.toml"), "[prefix]\nkey = \"C-a\"\n").unwrap();
+
+        let config = load_from_file(&dir.join("config.toml")).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        // Later files win, tables merge key by key
+        assert!(config.options.tree_view_enabled);
+        assert!(config.options.auto_start_daemon);
+        assert_eq!(config.bindings.sidebar.get("j"), Some(&"move-down".into()));
+        assert_eq!(config.bindings.sidebar.get("z"), Some(&"move-up".into()));
+        assert_eq!(config.prefix.key, "C-a");
+        // "extra/*.toml" matched nothing, which is only a warning
+        assert_eq!(config.source.len(), 3);
+    }
+
+    #[test]
+    fn test_source_layers_bindings() {
+        let dir = temp_dir("bindings");
+        std::fs::write(
+            dir.join("config.toml"),
+            "source = [\"keys.toml\"]\n\
+             [bindings.sidebar]\nmove-up = [\"k\", \"C-p\"]\n\"x\" = \"quit\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("keys.toml"),
+            "[bindings.sidebar]\n\"Ctrl-p\" = \"move-down\"\n\"x\" = \"none\"\n",
+        )
+        .unwrap();
+
+        let mut config = load_from_file(&dir.join("config.toml")).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+        config.merge_with_defaults();
+        let map = config.to_keybind_map().unwrap();
+        let resolve = |key| map.resolve(key, crate::BindingContext::Sidebar);
+
+        assert_eq!(resolve("k"), Some(crate::Action::MoveUp));
+        assert_eq!(resolve("C-p"), Some(crate::Action::MoveDown));
+        assert_eq!(resolve("x"), None);
+    }
+
+    #[test]
+    fn test_source_cycle() {
+        let dir = temp_dir("cycle");
+        std::fs::write(dir.join("a.toml"), "source = [\"b.toml\"]\n").unwrap();
+        std::fs::write(dir.join("b.toml"), "source = [\"./*.toml\"]\n").unwrap();
+
+        let result = load_from_file(&dir.join("a.toml"));
+        std::fs::remove_dir_all(&dir).ok();
+        assert!(matches!(result, Err(ConfigError::CircularDependency(_))));
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("*.toml", "vim.toml"));
+        assert!(wildcard_match("?0-*.toml", "10-keys.toml"));
+        assert!(!wildcard_match("*.toml", "vim.toml.bak"));
+        assert!(!wildcard_match("*.toml", ".vim.toml"));
+        assert!(wildcard_match(".*.toml", ".vim.toml"));
+    }
+}
@@ -0,0 +1,200 @@
+//! JSON Schema of the config file, for editor completion
+//!
+//! Built by walking [`default_config`] serialized, so every option is
+//! listed with the type and default it really has. Descriptions come from
+//! the starter config ([`MINIMAL_OPTIONS`]), action names from
+//! [`ACTION_NAMES`].
+
+use crate::actions::ACTION_NAMES;
+use crate::defaults::default_config;
+use crate::writer::MINIMAL_OPTIONS;
+use serde_json::{json, Map, Value};
+
+/// Options taking one of a fixed set of strings: table, key and values
+const ENUM_OPTIONS: &[(&str, &str, &[&str])] = &[
+    ("options", "quit_behavior", &["detach", "stop", "destroy"]),
+    ("options", "pull_strategy", &["merge", "rebase", "ff-only"]),
+    ("options", "ime_integration", &["none", "fcitx5", "ibus"]),
+    (
+        "options",
+        "sidebar_sort",
+        &["none", "name", "recent", "status"],
+    ),
+];
+
+/// Actions taking a parameter: name prefix and a regex for the parameter
+const PARAMETER_ACTIONS: &[(&str, &str)] = &[
+    ("switch-repo-", "[0-9]+"),
+    ("switch-recent-session-", "[0-9]+"),
+    ("send-text:", ".*"),
+];
+
+/// JSON Schema (draft 2020-12) describing the config file
+pub fn schema() -> Value {
+    let Ok(Value::Object(tables)) = serde_json::to_value(default_config()) else {
+        unreachable!("the default config serializes to an object");
+    };
+
+    let mut properties = Map::new();
+    properties.insert(
+        "min_version".to_string(),
+        json!({
+            "type": "string",
+            "description": "Oldest amux version this config is meant for; older versions refuse to load it",
+        }),
+    );
+    for (name, value) in tables {
+        let schema = match name.as_str() {
+            "bindings" => bindings_schema(&value),
+            "source" => json!({
+                "type": "array",
+                "items": { "type": "string" },
+                "default": value,
+                "description": "Files, directories or patterns loaded after this file",
+            }),
+            _ => value_schema(&name, &value),
+        };
+        properties.insert(name, schema);
+    }
+
+    let mut actions: Vec<Value> = vec![json!({ "enum": ACTION_NAMES })];
+    actions.extend(PARAMETER_ACTIONS.iter().map(|(prefix, parameter)| {
+        json!({ "type": "string", "pattern": format!("^{}{}$", prefix, parameter) })
+    }));
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "amux configuration",
+        "type": "object",
+        "properties": properties,
+        "$defs": {
+            "action": { "anyOf": actions },
+            "bindings": {
+                "type": "object",
+                "description": "\"key\" = \"action\", or action = [\"key\", ...]",
+                "additionalProperties": {
+                    "anyOf": [
+                        { "$ref": "#/$defs/action" },
+                        { "type": "array", "items": { "type": "string" } },
+                    ],
+                },
+            },
+        },
+    })
+}
+
+/// Schema of `[bindings]`: one binding table per context
+fn bindings_schema(defaults: &Value) -> Value {
+    let contexts: Map<String, Value> = defaults
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(context, bindings)| {
+            let schema = json!({ "$ref": "#/$defs/bindings", "default": bindings });
+            (context.clone(), schema)
+        })
+        .collect();
+    json!({ "type": "object", "properties": contexts })
+}
+
+/// Schema of the value at `path` (e.g. "options.theme") in the default config
+fn value_schema(path: &str, value: &Value) -> Value {
+    let mut schema = match value {
+        Value::Object(fields) if !fields.is_empty() => {
+            let properties: Map<String, Value> = fields
+                .iter()
+                .map(|(key, value)| {
+                    let path = format!("{}.{}", path, key);
+                    (key.clone(), value_schema(&path, value))
+                })
+                .collect();
+            return json!({ "type": "object", "properties": properties });
+        }
+        Value::Object(_) => json!({ "type": "object" }),
+        Value::Bool(_) => json!({ "type": "boolean" }),
+        Value::Number(n) if n.is_f64() => json!({ "type": "number" }),
+        Value::Number(_) => json!({ "type": "integer" }),
+        Value::String(_) => json!({ "type": "string" }),
+        Value::Array(items) => match items.first().map(|item| value_schema(path, item)) {
+            Some(Value::Object(mut item)) => {
+                item.remove("default");
+                json!({ "type": "array", "items": item })
+            }
+            _ => json!({ "type": "array" }),
+        },
+        // Unset optional values
+        Value::Null => json!({}),
+    };
+
+    let fields = schema.as_object_mut().expect("schemas are objects");
+    if !value.is_null() {
+        fields.insert("default".to_string(), value.clone());
+    }
+    if let Some((_, _, values)) = ENUM_OPTIONS
+        .iter()
+        .find(|(t, k, _)| path == format!("{}.{}", t, k))
+    {
+        fields.insert("enum".to_string(), json!(values));
+    }
+    if let Some((_, _, description)) = MINIMAL_OPTIONS
+        .iter()
+        .find(|(t, k, _)| path == format!("{}.{}", t, k))
+    {
+        fields.insert("description".to_string(), json!(description));
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keybind::BindingContext;
+    use crate::types::{binding_pairs, Options};
+
+    #[test]
+    fn test_schema_lists_every_option() {
+        let schema = schema();
+        let options = &schema["properties"]["options"]["properties"];
+        for name in Options::field_names() {
+            assert!(options[name]["type"].is_string(), "{}", name);
+        }
+        assert_eq!(options["session_page_size"]["type"], "integer");
+        assert_eq!(options["session_page_size"]["default"], 50);
+        assert_eq!(
+            schema["properties"]["prefix"]["properties"]["key"]["default"],
+            "C-s"
+        );
+        assert!(schema["properties"]["prefix"]["properties"]["key"]["description"].is_string());
+    }
+
+    #[test]
+    fn test_enum_options_accept_their_values() {
+        for (table, key, values) in ENUM_OPTIONS {
+            assert_eq!(*table, "options");
+            for value in *values {
+                let mut options = Options::default();
+                assert!(options.set(key, value).is_ok(), "{} = {}", key, value);
+            }
+            assert!(Options::default().set(key, "bogus").is_err(), "{}", key);
+        }
+        let schema = schema();
+        assert_eq!(
+            schema["properties"]["options"]["properties"]["quit_behavior"]["enum"],
+            json!(["detach", "stop", "destroy"])
+        );
+    }
+
+    #[test]
+    fn test_default_bindings_use_known_actions() {
+        let config = default_config();
+        for context in BindingContext::ALL {
+            for (key, action) in binding_pairs(config.bindings.map(context)) {
+                let known = ACTION_NAMES.contains(&action)
+                    || PARAMETER_ACTIONS
+                        .iter()
+                        .any(|(prefix, _)| action.starts_with(prefix));
+                assert!(known, "{} = {} in {}", key, action, context.name());
+            }
+        }
+    }
+}
@@ -1,29 +1,51 @@
 //! Configuration data structures
 
+use crate::commands;
+use crate::keybind::{BindingContext, KeyPattern};
+use crate::{ConfigError, Result};
+use amux_config_derive::Partial;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Top-level configuration structure
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+///
+/// [`PartialConfig`] mirrors it with every field optional; the `prefix`,
+/// `options`, `ui` and `providers` tables are partial in turn.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Partial)]
 pub struct Config {
+    /// Oldest amux version this config is meant for (e.g. "0.3.0")
+    ///
+    /// Older versions refuse to load it instead of ignoring what they
+    /// don't know.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_version: Option<String>,
+
     /// Prefix key configuration
     #[serde(default)]
+    #[partial(nested)]
     pub prefix: PrefixConfig,
 
     /// Global options (UI, behavior, features)
     #[serde(default)]
+    #[partial(nested)]
     pub options: Options,
 
     /// UI-specific settings
     #[serde(default)]
+    #[partial(nested)]
     pub ui: UiConfig,
 
+    /// Syntax highlighting overrides
+    #[serde(default)]
+    pub highlight: HighlightConfig,
+
     /// All key bindings organized by context
     #[serde(default)]
     pub bindings: Bindings,
 
     /// AI provider configuration
     #[serde(default)]
+    #[partial(nested)]
     pub providers: ProvidersConfig,
 
     /// Source files to load (for modularity)
@@ -32,7 +54,7 @@ pub struct Config {
 }
 
 /// AI Provider configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Partial)]
 pub struct ProvidersConfig {
     /// Default provider to use when creating sessions
     #[serde(default = "default_provider")]
@@ -40,15 +62,17 @@ pub struct ProvidersConfig {
 
     /// Claude provider settings
     #[serde(default)]
+    #[partial(nested)]
     pub claude: ClaudeConfig,
 
     /// Codex provider settings
     #[serde(default)]
+    #[partial(nested)]
     pub codex: CodexConfig,
 }
 
 /// Claude provider configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Partial)]
 pub struct ClaudeConfig {
     /// Whether Claude provider is enabled
     #[serde(default = "default_true")]
@@ -64,7 +88,7 @@ pub struct ClaudeConfig {
 }
 
 /// OpenAI Codex provider configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Partial)]
 pub struct CodexConfig {
     /// Whether Codex provider is enabled
     #[serde(default = "default_true")]
@@ -129,8 +153,97 @@ impl Default for CodexConfig {
     }
 }
 
+/// An installed provider and its models, as the daemon lists them
+pub type AvailableProvider<'a> = (&'a str, &'a [&'a str]);
+
+impl ProvidersConfig {
+    /// Default model set for `provider`, for the providers with a section
+    pub fn model(&self, provider: &str) -> Option<&str> {
+        match provider {
+            "claude" => Some(&self.claude.model),
+            "codex" => Some(&self.codex.model),
+            _ => None,
+        }
+    }
+
+    /// Apply `:provider default <name>`
+    pub fn set_default(&mut self, name: &str, available: &[AvailableProvider]) -> Result<()> {
+        if !available.iter().any(|(provider, _)| *provider == name) {
+            return Err(provider_not_found(name, available));
+        }
+        self.default = name.to_string();
+        Ok(())
+    }
+
+    /// Apply `:provider model <provider> <model>`
+    pub fn set_model(
+        &mut self,
+        provider: &str,
+        model: &str,
+        available: &[AvailableProvider],
+    ) -> Result<()> {
+        let Some((_, models)) = available.iter().find(|(name, _)| *name == provider) else {
+            return Err(provider_not_found(provider, available));
+        };
+        if !models.contains(&model) {
+            return Err(ConfigError::InvalidOption(format!(
+                "Invalid model '{}' for provider '{}'. Available models: {}",
+                model,
+                provider,
+                models.join(", ")
+            )));
+        }
+        let slot = match provider {
+            "claude" => &mut self.claude.model,
+            "codex" => &mut self.codex.model,
+            _ => {
+                return Err(ConfigError::InvalidOption(format!(
+                    "Provider '{}' has no model setting",
+                    provider
+                )))
+            }
+        };
+        *slot = model.to_string();
+        Ok(())
+    }
+
+    /// Lines of `:provider list`: the configured and installed providers,
+    /// the default marked with `*`
+    pub fn list_lines(&self, available: &[AvailableProvider]) -> Vec<String> {
+        let mut names: Vec<&str> = available.iter().map(|(name, _)| *name).collect();
+        names.extend(["claude", "codex"]);
+        names.sort_unstable();
+        names.dedup();
+
+        names
+            .into_iter()
+            .map(|name| {
+                let marker = if name == self.default { '*' } else { ' ' };
+                let status = if available.iter().any(|(provider, _)| *provider == name) {
+                    "available"
+                } else {
+                    "unavailable"
+                };
+                match self.model(name) {
+                    Some(model) => format!("{} {} ({}, model {})", marker, name, status, model),
+                    None => format!("{} {} ({})", marker, name, status),
+                }
+            })
+            .collect()
+    }
+}
+
+fn provider_not_found(name: &str, available: &[AvailableProvider]) -> ConfigError {
+    let names: Vec<&str> = available.iter().map(|(name, _)| *name).collect();
+    ConfigError::InvalidOption(format!(
+        "Provider '{}' not found. Available providers: {}",
+        name,
+        names.join(", ")
+    ))
+}
+
 /// Prefix key configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Partial)]
 pub struct PrefixConfig {
     /// Prefix key string (e.g., "C-s", "C-a")
     #[serde(default = "default_prefix_key")]
@@ -138,7 +251,7 @@ pub struct PrefixConfig {
 }
 
 /// Global options for application behavior
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Partial)]
 pub struct Options {
     /// Enable tree view for sessions
     #[serde(default = "default_true")]
@@ -148,8 +261,13 @@ pub struct Options {
     #[serde(default = "default_true")]
     pub git_panel_enabled: bool,
 
-    /// Enable mouse support
+    /// List files matched by .gitignore in the git status panel
     #[serde(default)]
+    pub show_ignored: bool,
+
+    /// Capture the mouse: click to select, right-click for a context menu,
+    /// wheel to scroll (off leaves selection to the host terminal)
+    #[serde(default = "default_true")]
     pub mouse_enabled: bool,
 
     /// Fullscreen when connecting to session
@@ -159,10 +277,278 @@ pub struct Options {
     /// Show completed TODOs by default
     #[serde(default)]
     pub show_completed_todos: bool,
+
+    /// Show the window title set by each session's program in the sidebar
+    #[serde(default = "default_true")]
+    pub show_session_titles: bool,
+
+    /// Sessions listed per worktree before a "Load more" row
+    #[serde(default = "default_session_page_size")]
+    pub session_page_size: u32,
+
+    /// Track OSC 8 hyperlinks in terminal output and allow opening them
+    #[serde(default = "default_true")]
+    pub open_links: bool,
+
+    /// Repo tab title, with `{index}`, `{name}`, `{session_count}`,
+    /// `{dirty_count}` and `{provider}` replaced (`{index}` is empty past
+    /// the 9th tab)
+    #[serde(default = "default_tab_title_format")]
+    pub tab_title_format: String,
+
+    /// Spawn `amux-daemon` when connecting finds it not running
+    #[serde(default)]
+    pub auto_start_daemon: bool,
+
+    /// Order of the sidebar's worktrees and sessions
+    #[serde(default)]
+    pub sidebar_sort: SidebarSort,
+
+    /// Keep the main worktree at the top of the sidebar whatever the sort
+    #[serde(default = "default_true")]
+    pub sidebar_pin_main: bool,
+
+    /// What the TUI does once loaded: `none`, `last-session`,
+    /// `first-running-session`, `new-session` or `session:<id or name>`
+    #[serde(default)]
+    pub startup_action: StartupAction,
+
+    /// Color theme: `dark`, `light`, or a theme file (a bare name is
+    /// `~/.amux/themes/<name>.toml`)
+    #[serde(default = "default_theme")]
+    pub theme: String,
+
+    /// Draw RGB colors even if the terminal doesn't announce true color
+    /// (otherwise they are reduced to 256 or 16 colors)
+    #[serde(default)]
+    pub force_truecolor: bool,
+
+    /// Ms to wait for a command key after the prefix before leaving prefix
+    /// mode (0 waits indefinitely)
+    #[serde(default = "default_prefix_timeout_ms")]
+    pub prefix_timeout_ms: u64,
+
+    /// Time budget (ms) for computing worktree dirty/ahead/behind indicators
+    /// and diff stats; indicators that miss it are shown as unknown
+    #[serde(default = "default_worktree_status_timeout_ms")]
+    pub worktree_status_timeout_ms: u64,
+
+    /// Minimum ms between automatic git status refreshes of the current
+    /// worktree (0 disables debouncing; very low values can flicker while
+    /// files change in bursts)
+    #[serde(default = "default_git_refresh_debounce_ms")]
+    pub git_refresh_debounce_ms: u64,
+
+    /// Unchanged runs in file diffs longer than this many lines are collapsed
+    /// into an expandable row, as are the lines between hunks (0 disables)
+    #[serde(default = "default_diff_collapse_context")]
+    pub diff_collapse_context: usize,
+
+    /// Seconds between session CPU/memory samples in the daemon (0 disables)
+    #[serde(default = "default_resource_monitor_interval_secs")]
+    pub resource_monitor_interval_secs: u64,
+
+    /// What to do with running sessions when the TUI exits
+    #[serde(default)]
+    pub quit_behavior: QuitBehavior,
+
+    /// How pulling integrates upstream changes into a worktree
+    #[serde(default)]
+    pub pull_strategy: PullStrategy,
+
+    /// Input method to switch off while the TUI runs and back on at exit
+    #[serde(default)]
+    pub ime_integration: ImeIntegration,
+
+    /// Command that opens a session in a new terminal window, run with
+    /// `sh -c` after replacing `{session_id}` (empty disables the action)
+    #[serde(default)]
+    pub external_terminal_command: String,
+
+    /// Command that opens a file in an editor, run with `sh -c` in the
+    /// worktree after replacing `{file}` and `{line}` (empty uses
+    /// `$VISUAL`, then `$EDITOR`, then `vi`, as `<editor> +{line} {file}`)
+    #[serde(default)]
+    pub editor_command: String,
+
+    /// Ask before deleting a session
+    #[serde(default = "default_true")]
+    pub confirm_delete_session: bool,
+
+    /// Ask before deleting a worktree's branch after removing the worktree
+    /// (when off, the branch is deleted too)
+    #[serde(default = "default_true")]
+    pub confirm_delete_branch: bool,
+
+    /// Ask before deleting a TODO
+    #[serde(default = "default_true")]
+    pub confirm_delete_todo: bool,
+
+    /// Ask before quitting while sessions are running
+    #[serde(default = "default_true")]
+    pub confirm_quit_running_sessions: bool,
+
+    /// Ask before quitting while worktrees have uncommitted changes
+    #[serde(default)]
+    pub confirm_quit_dirty: bool,
+
+    /// Seconds a destroyed session, comment or TODO can be restored with undo
+    #[serde(default = "default_trash_ttl_secs")]
+    pub trash_ttl_secs: u64,
+
+    /// Allow typing one line into every running session of a worktree at
+    /// once (off by default)
+    #[serde(default)]
+    pub allow_broadcast: bool,
+
+    /// Characters that end a word selected by double-clicking the terminal
+    /// (whitespace always does)
+    #[serde(default = "default_word_delimiters")]
+    pub word_delimiters: String,
+}
+
+/// Confirmation dialogs that the `[a] always` choice can turn off
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Confirmation {
+    DeleteSession,
+    DeleteBranch,
+    DeleteTodo,
+}
+
+impl Confirmation {
+    /// The `[options]` key controlling this dialog
+    pub fn option_name(self) -> &'static str {
+        match self {
+            Confirmation::DeleteSession => "confirm_delete_session",
+            Confirmation::DeleteBranch => "confirm_delete_branch",
+            Confirmation::DeleteTodo => "confirm_delete_todo",
+        }
+    }
+}
+
+/// Session handling when the TUI quits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuitBehavior {
+    /// Keep sessions running in the daemon, only disconnect streams
+    #[default]
+    Detach,
+    /// Stop sessions (kill PTY, keep metadata)
+    Stop,
+    /// Destroy sessions (delete data)
+    Destroy,
+}
+
+/// Strategy for pulling upstream changes into a worktree
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PullStrategy {
+    Merge,
+    #[default]
+    Rebase,
+    FfOnly,
+}
+
+/// Order of the sidebar rows
+///
+/// Pinned sessions stay above the others of their worktree.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SidebarSort {
+    /// As the daemon lists them
+    #[default]
+    None,
+    /// Worktrees by branch, sessions by name
+    Name,
+    /// Newest sessions first, and worktrees by their newest session
+    Recent,
+    /// Running sessions first, and worktrees running one, then dirty ones
+    Status,
+}
+
+/// What the TUI does once loaded
+///
+/// Written as a string; `SpecificSession` is `session:<id or name>`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum StartupAction {
+    /// Stay in the sidebar
+    #[default]
+    None,
+    /// Open the session shown last
+    LastSession,
+    /// Open the first running session
+    FirstRunningSession,
+    /// Open the session with this ID or name
+    SpecificSession(String),
+    /// Start a session in the selected worktree
+    NewSession,
+}
+
+/// Prefix of `StartupAction::SpecificSession`
+const SPECIFIC_SESSION_PREFIX: &str = "session:";
+
+impl TryFrom<String> for StartupAction {
+    type Error = String;
+
+    fn try_from(value: String) -> std::result::Result<Self, Self::Error> {
+        match value.as_str() {
+            "none" => Ok(Self::None),
+            "last-session" => Ok(Self::LastSession),
+            "first-running-session" => Ok(Self::FirstRunningSession),
+            "new-session" => Ok(Self::NewSession),
+            _ => match value.strip_prefix(SPECIFIC_SESSION_PREFIX) {
+                Some(session) if !session.is_empty() => {
+                    Ok(Self::SpecificSession(session.to_string()))
+                }
+                _ => Err(format!(
+                    "unknown startup action '{}' (expected none, last-session, \
+                     first-running-session, new-session or session:<id or name>)",
+                    value
+                )),
+            },
+        }
+    }
+}
+
+impl From<StartupAction> for String {
+    fn from(action: StartupAction) -> Self {
+        match action {
+            StartupAction::None => "none".to_string(),
+            StartupAction::LastSession => "last-session".to_string(),
+            StartupAction::FirstRunningSession => "first-running-session".to_string(),
+            StartupAction::SpecificSession(session) => {
+                format!("{}{}", SPECIFIC_SESSION_PREFIX, session)
+            }
+            StartupAction::NewSession => "new-session".to_string(),
+        }
+    }
+}
+
+/// Input method framework the TUI switches off while it runs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImeIntegration {
+    /// Leave the input method alone
+    #[default]
+    None,
+    /// Close and reopen it with `fcitx5-remote`
+    Fcitx5,
+    /// Switch to the plain keyboard engine with `ibus engine`, then back
+    Ibus,
+}
+
+/// Syntax highlighting configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HighlightConfig {
+    /// Syntax name by file extension or file name (e.g. `tf = "HCL"`),
+    /// used before detection; `.gitattributes` `linguist-language` wins
+    #[serde(default)]
+    pub extensions: HashMap<String, String>,
 }
 
 /// UI-specific configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Partial)]
 pub struct UiConfig {
     /// Show UI borders
     #[serde(default = "default_true")]
@@ -172,6 +558,10 @@ pub struct UiConfig {
     #[serde(default = "default_sidebar_width")]
     pub sidebar_width: u16,
 
+    /// Sidebar share of the screen width, in percent (10-60)
+    #[serde(default = "default_sidebar_width_percent")]
+    pub sidebar_width_percent: u16,
+
     /// Default terminal rows
     #[serde(default = "default_terminal_rows")]
     pub terminal_rows: u16,
@@ -186,47 +576,184 @@ pub struct UiConfig {
 }
 
 /// All key bindings organized by context
+///
+/// Each table maps a key to an action (`"k" = "move-up"`) or an action to
+/// several keys (`move-up = ["k", "Up"]`).
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Bindings {
     /// Global key bindings (no prefix, no context)
     #[serde(default)]
-    pub global: HashMap<String, String>,
+    pub global: BindingMap,
 
     /// Prefix-based bindings (require prefix key first)
     #[serde(default)]
-    pub prefix: HashMap<String, String>,
+    pub prefix: BindingMap,
 
     /// Sidebar/navigation context
     #[serde(default)]
-    pub sidebar: HashMap<String, String>,
+    pub sidebar: BindingMap,
 
     /// Terminal normal mode (read-only, vim-like)
     #[serde(default)]
-    pub terminal_normal: HashMap<String, String>,
+    pub terminal_normal: BindingMap,
 
     /// Terminal insert mode (forward to PTY mostly)
     #[serde(default)]
-    pub terminal_insert: HashMap<String, String>,
+    pub terminal_insert: BindingMap,
 
     /// Diff view context
     #[serde(default)]
-    pub diff: HashMap<String, String>,
+    pub diff: BindingMap,
 
     /// Git status panel context
     #[serde(default)]
-    pub git_status: HashMap<String, String>,
+    pub git_status: BindingMap,
+
+    /// Merge conflict view context
+    #[serde(default)]
+    pub conflicts: BindingMap,
 
     /// TODO popup context
     #[serde(default)]
-    pub todo: HashMap<String, String>,
+    pub todo: BindingMap,
 
     /// Text input dialog context
     #[serde(default)]
-    pub dialog_text: HashMap<String, String>,
+    pub dialog_text: BindingMap,
 
     /// Confirmation dialog context
     #[serde(default)]
-    pub dialog_confirm: HashMap<String, String>,
+    pub dialog_confirm: BindingMap,
+}
+
+impl Bindings {
+    /// The table of one context
+    pub fn map(&self, context: BindingContext) -> &BindingMap {
+        match context {
+            BindingContext::Global => &self.global,
+            BindingContext::Prefix => &self.prefix,
+            BindingContext::Sidebar => &self.sidebar,
+            BindingContext::TerminalNormal => &self.terminal_normal,
+            BindingContext::TerminalInsert => &self.terminal_insert,
+            BindingContext::Diff => &self.diff,
+            BindingContext::GitStatus => &self.git_status,
+            BindingContext::Conflicts => &self.conflicts,
+            BindingContext::Todo => &self.todo,
+            BindingContext::DialogText => &self.dialog_text,
+            BindingContext::DialogConfirm => &self.dialog_confirm,
+        }
+    }
+
+    /// The table of one context, for editing
+    pub fn map_mut(&mut self, context: BindingContext) -> &mut BindingMap {
+        match context {
+            BindingContext::Global => &mut self.global,
+            BindingContext::Prefix => &mut self.prefix,
+            BindingContext::Sidebar => &mut self.sidebar,
+            BindingContext::TerminalNormal => &mut self.terminal_normal,
+            BindingContext::TerminalInsert => &mut self.terminal_insert,
+            BindingContext::Diff => &mut self.diff,
+            BindingContext::GitStatus => &mut self.git_status,
+            BindingContext::Conflicts => &mut self.conflicts,
+            BindingContext::Todo => &mut self.todo,
+            BindingContext::DialogText => &mut self.dialog_text,
+            BindingContext::DialogConfirm => &mut self.dialog_confirm,
+        }
+    }
+
+    /// Bind `key` to `action` in `context`, replacing any binding of the key
+    ///
+    /// `action` may be [`UNBOUND`] to leave the key unbound even where
+    /// defaults would bind it.
+    pub fn insert(&mut self, context: BindingContext, key: &str, action: &str) {
+        self.remove(context, key);
+        self.map_mut(context)
+            .insert(key.to_string(), BindingValue::Action(action.to_string()));
+    }
+
+    /// Drop every binding of `key` in `context`; returns whether there was one
+    ///
+    /// Keys are compared in canonical form, so `C-a` also drops `Ctrl-a`.
+    /// Defaults merged in later may bind the key again; bind it to
+    /// [`UNBOUND`] to prevent that.
+    pub fn remove(&mut self, context: BindingContext, key: &str) -> bool {
+        let mut removed = false;
+        self.map_mut(context).retain(|name, value| match value {
+            BindingValue::Action(_) => {
+                let matches = same_key(name, key);
+                removed |= matches;
+                !matches
+            }
+            BindingValue::Keys(keys) => {
+                let len = keys.len();
+                keys.retain(|k| !same_key(k, key));
+                removed |= keys.len() != len;
+                !keys.is_empty()
+            }
+        });
+        removed
+    }
+
+    /// Layer `other` over these bindings
+    ///
+    /// Each of its bindings replaces the binding of the same key here; keys
+    /// it doesn't mention keep their binding.
+    pub fn merge(&mut self, other: &Bindings) {
+        for context in BindingContext::ALL {
+            for (key, action) in binding_pairs(other.map(context)) {
+                self.insert(context, key, action);
+            }
+        }
+    }
+}
+
+/// Action name that unbinds a key (`"j" = "none"`)
+pub const UNBOUND: &str = "none";
+
+/// Whether two key spellings name the same key
+fn same_key(a: &str, b: &str) -> bool {
+    match (KeyPattern::parse(a), KeyPattern::parse(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Entries of one `[bindings.<context>]` table
+pub type BindingMap = HashMap<String, BindingValue>;
+
+/// Value of a binding table entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BindingValue {
+    /// The entry's name is a key, bound to this action
+    Action(String),
+    /// The entry's name is an action, bound to each of these keys
+    Keys(Vec<String>),
+}
+
+impl From<&str> for BindingValue {
+    fn from(action: &str) -> Self {
+        BindingValue::Action(action.to_string())
+    }
+}
+
+/// `(key, action)` pairs of a binding table
+///
+/// Key-to-action entries come after the action-to-keys ones, so they win
+/// when a key is bound both ways.
+pub fn binding_pairs(map: &BindingMap) -> Vec<(&str, &str)> {
+    let mut pairs = Vec::new();
+    for (action, value) in map {
+        if let BindingValue::Keys(keys) = value {
+            pairs.extend(keys.iter().map(|key| (key.as_str(), action.as_str())));
+        }
+    }
+    for (key, value) in map {
+        if let BindingValue::Action(action) = value {
+            pairs.push((key.as_str(), action.as_str()));
+        }
+    }
+    pairs
 }
 
 // Default value helper functions
@@ -238,10 +765,54 @@ fn default_prefix_key() -> String {
     "C-s".to_string()
 }
 
+fn default_tab_title_format() -> String {
+    "{index}:{name}".to_string()
+}
+
+fn default_session_page_size() -> u32 {
+    50
+}
+
+fn default_theme() -> String {
+    "dark".to_string()
+}
+
+fn default_prefix_timeout_ms() -> u64 {
+    2000
+}
+
+fn default_worktree_status_timeout_ms() -> u64 {
+    500
+}
+
+fn default_git_refresh_debounce_ms() -> u64 {
+    500
+}
+
+fn default_diff_collapse_context() -> usize {
+    8
+}
+
+fn default_resource_monitor_interval_secs() -> u64 {
+    10
+}
+
+fn default_trash_ttl_secs() -> u64 {
+    30
+}
+
+fn default_word_delimiters() -> String {
+    " \t\n\"'`()[]{}|&;:<>".to_string()
+}
+
 fn default_sidebar_width() -> u16 {
     30
 }
 
+fn default_sidebar_width_percent() -> u16 {
+    25
+}
+
 fn default_terminal_rows() -> u16 {
     24
 }
@@ -267,18 +838,129 @@ impl Default for Options {
         Self {
             tree_view_enabled: default_true(),
             git_panel_enabled: default_true(),
-            mouse_enabled: false,
+            show_ignored: false,
+            mouse_enabled: default_true(),
             fullscreen_on_connect: false,
             show_completed_todos: false,
+            show_session_titles: default_true(),
+            session_page_size: default_session_page_size(),
+            open_links: default_true(),
+            tab_title_format: default_tab_title_format(),
+            auto_start_daemon: false,
+            sidebar_sort: SidebarSort::default(),
+            sidebar_pin_main: default_true(),
+            startup_action: StartupAction::default(),
+            theme: default_theme(),
+            force_truecolor: false,
+            prefix_timeout_ms: default_prefix_timeout_ms(),
+            worktree_status_timeout_ms: default_worktree_status_timeout_ms(),
+            git_refresh_debounce_ms: default_git_refresh_debounce_ms(),
+            diff_collapse_context: default_diff_collapse_context(),
+            resource_monitor_interval_secs: default_resource_monitor_interval_secs(),
+            quit_behavior: QuitBehavior::default(),
+            pull_strategy: PullStrategy::default(),
+            ime_integration: ImeIntegration::default(),
+            external_terminal_command: String::new(),
+            editor_command: String::new(),
+            confirm_delete_session: default_true(),
+            confirm_delete_branch: default_true(),
+            confirm_delete_todo: default_true(),
+            confirm_quit_running_sessions: default_true(),
+            confirm_quit_dirty: false,
+            trash_ttl_secs: default_trash_ttl_secs(),
+            allow_broadcast: false,
+            word_delimiters: default_word_delimiters(),
         }
     }
 }
 
+impl Options {
+    /// Option names accepted by `:set`, matching the `[options]` keys
+    pub fn field_names() -> &'static [&'static str] {
+        &[
+            "tree_view_enabled",
+            "git_panel_enabled",
+            "show_ignored",
+            "mouse_enabled",
+            "fullscreen_on_connect",
+            "show_completed_todos",
+            "show_session_titles",
+            "session_page_size",
+            "open_links",
+            "tab_title_format",
+            "auto_start_daemon",
+            "sidebar_sort",
+            "sidebar_pin_main",
+            "startup_action",
+            "theme",
+            "force_truecolor",
+            "prefix_timeout_ms",
+            "worktree_status_timeout_ms",
+            "git_refresh_debounce_ms",
+            "diff_collapse_context",
+            "resource_monitor_interval_secs",
+            "quit_behavior",
+            "pull_strategy",
+            "ime_integration",
+            "external_terminal_command",
+            "editor_command",
+            "confirm_delete_session",
+            "confirm_delete_branch",
+            "confirm_delete_todo",
+            "confirm_quit_running_sessions",
+            "confirm_quit_dirty",
+            "trash_ttl_secs",
+            "allow_broadcast",
+            "word_delimiters",
+        ]
+    }
+
+    /// Whether to show the given confirmation dialog
+    pub fn confirms(&self, confirmation: Confirmation) -> bool {
+        match confirmation {
+            Confirmation::DeleteSession => self.confirm_delete_session,
+            Confirmation::DeleteBranch => self.confirm_delete_branch,
+            Confirmation::DeleteTodo => self.confirm_delete_todo,
+        }
+    }
+
+    /// Turn the given confirmation dialog on or off
+    pub fn set_confirms(&mut self, confirmation: Confirmation, ask: bool) {
+        match confirmation {
+            Confirmation::DeleteSession => self.confirm_delete_session = ask,
+            Confirmation::DeleteBranch => self.confirm_delete_branch = ask,
+            Confirmation::DeleteTodo => self.confirm_delete_todo = ask,
+        }
+    }
+
+    /// Apply `:set <option> <value>`
+    ///
+    /// The value is checked against the option's type; booleans also accept
+    /// `on`/`off` and `yes`/`no`. Keys of table-valued options are set with
+    /// a dotted path, and arrays take comma-separated elements.
+    pub fn set(&mut self, option: &str, value: &str) -> Result<()> {
+        let mut table = match toml::Value::try_from(&*self)? {
+            toml::Value::Table(table) => table,
+            _ => {
+                return Err(ConfigError::Custom(
+                    "Options did not serialize to a table".into(),
+                ))
+            }
+        };
+        commands::set_table_value(&mut table, option, value)?;
+        *self = toml::Value::Table(table).try_into().map_err(|_| {
+            ConfigError::InvalidOption(format!("Invalid value '{}' for {}", value, option))
+        })?;
+        Ok(())
+    }
+}
+
 impl Default for UiConfig {
     fn default() -> Self {
         Self {
             show_borders: default_true(),
             sidebar_width: default_sidebar_width(),
+            sidebar_width_percent: default_sidebar_width_percent(),
             terminal_rows: default_terminal_rows(),
             terminal_cols: default_terminal_cols(),
             terminal_scrollback: default_scrollback(),
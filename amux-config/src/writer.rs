@@ -2,12 +2,11 @@
 //!
 //! Handles saving configuration back to TOML files while preserving
 //! formatting and comments where possible.
-//!
-//! Full implementation in Phase 5.
 
 use crate::types::Config;
-use crate::Result;
+use crate::{parser, ConfigError, Result};
 use std::path::Path;
+use toml_edit::{Document, Item, TableLike};
 
 /// Save configuration to a file
 pub fn save_to_file(config: &Config, path: &Path) -> Result<()> {
@@ -19,19 +18,271 @@ pub fn save_to_file(config: &Config, path: &Path) -> Result<()> {
 /// Save configuration to the default config file
 pub fn save_default(config: &Config) -> Result<()> {
     let config_path = crate::config::config_file();
+    create_parent_dir(&config_path)?;
+    save_with_edit(config, &config_path)
+}
+
+/// Write the starter config from [`write_minimal_config`] to `path`
+pub fn create_minimal_file(path: &Path) -> Result<()> {
+    create_parent_dir(path)?;
+    std::fs::write(path, write_minimal_config())?;
+    Ok(())
+}
+
+fn create_parent_dir(path: &Path) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        if !dir.exists() {
+            std::fs::create_dir_all(dir)?;
+        }
+    }
+    Ok(())
+}
+
+/// Options written to the starter config: table, key and what it does
+pub(crate) const MINIMAL_OPTIONS: &[(&str, &str, &str)] = &[
+    (
+        "prefix",
+        "key",
+        "Key that starts prefix commands, like tmux",
+    ),
+    (
+        "options",
+        "theme",
+        "`dark`, `light`, or a theme file in ~/.amux/themes",
+    ),
+    (
+        "options",
+        "mouse_enabled",
+        "Click, right-click menus and wheel scrolling",
+    ),
+    (
+        "options",
+        "tree_view_enabled",
+        "Show sessions as a tree under their worktree",
+    ),
+    ("options", "git_panel_enabled", "Show the git status panel"),
+    (
+        "options",
+        "fullscreen_on_connect",
+        "Hide the sidebar when entering a session",
+    ),
+    (
+        "options",
+        "auto_start_daemon",
+        "Start amux-daemon when it isn't running",
+    ),
+    (
+        "options",
+        "quit_behavior",
+        "Sessions on exit: `detach`, `stop` or `destroy`",
+    ),
+    (
+        "options",
+        "pull_strategy",
+        "How pulls integrate upstream: `merge`, `rebase` or `ff-only`",
+    ),
+    (
+        "options",
+        "prefix_timeout_ms",
+        "Ms to wait for a key after the prefix (0 waits)",
+    ),
+    (
+        "options",
+        "confirm_delete_session",
+        "Ask before deleting a session",
+    ),
+    (
+        "options",
+        "ime_integration",
+        "Input method to switch off: `none`, `fcitx5` or `ibus`",
+    ),
+    (
+        "options",
+        "external_terminal_command",
+        "Open sessions in a new window, e.g.\n# \"wezterm start -- amux attach {session_id}\"",
+    ),
+    (
+        "ui",
+        "sidebar_width_percent",
+        "Sidebar share of the screen width (10-60)",
+    ),
+    (
+        "ui",
+        "terminal_scrollback",
+        "Lines of scrollback kept per session",
+    ),
+    (
+        "providers",
+        "default",
+        "Provider for new sessions: `claude` or `codex`",
+    ),
+];
+
+/// A commented starter config for first runs
+///
+/// Every option is commented out with its default value, so the file loads
+/// to the defaults until something is uncommented.
+pub fn write_minimal_config() -> String {
+    let defaults =
+        to_table(&crate::defaults::default_config()).expect("default config serializes to a table");
+
+    let mut out = String::from(
+        "# amux configuration\n\
+         #\n\
+         # Uncomment a line to change it; everything else keeps its default.\n\
+         # See CONFIG_GUIDE.md for all options, actions and key syntax.\n",
+    );
+    out.push_str(&format!(
+        "\n# Refuse to load in older amux versions instead of ignoring new settings\n\
+         # min_version = \"{}\"\n",
+        env!("CARGO_PKG_VERSION")
+    ));
+    let mut current = "";
+    for (table, key, comment) in MINIMAL_OPTIONS {
+        if *table != current {
+            out.push_str(&format!("\n[{}]\n", table));
+            current = table;
+        }
+        let value = defaults
+            .get(*table)
+            .and_then(|t| t.get(*key))
+            .expect("starter option exists in the default config");
+        out.push_str(&format!("# {}\n# {} = {}\n", comment, key, value));
+    }
+    out.push_str(
+        "\n[bindings.sidebar]\n\
+         # A key bound to an action, or an action bound to several keys\n\
+         # \"J\" = \"move-down\"\n\
+         # move-up = [\"k\", \"Up\"]\n\
+         # Remove a default binding\n\
+         # \"d\" = \"none\"\n",
+    );
+    out
+}
+
+/// Save configuration by editing the existing file in place
+///
+/// The file is compared with `config` as it would be loaded (defaults
+/// merged in), and only keys whose values differ are written, so comments,
+/// ordering and values left at their defaults survive. Falls back to a full
+/// rewrite when the file doesn't exist yet.
+pub fn save_with_edit(config: &Config, original_path: &Path) -> Result<()> {
+    let content = match std::fs::read_to_string(original_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return save_to_file(config, original_path);
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    // Values that came from sourced files count as already on disk
+    let mut on_disk = parser::load_from_file(original_path)?;
+    on_disk.merge_with_defaults();
 
-    // Ensure config directory exists
-    let config_dir = config_path.parent().unwrap();
-    if !config_dir.exists() {
-        std::fs::create_dir_all(config_dir)?;
+    let edited = edit_document(config, &content, &on_disk)?;
+    std::fs::write(original_path, edited)?;
+    Ok(())
+}
+
+/// Apply the differences between `on_disk` and `config` to the document text
+///
+/// `on_disk` is the config `content` loads to, defaults merged in.
+fn edit_document(config: &Config, content: &str, on_disk: &Config) -> Result<String> {
+    let mut doc: Document = content.parse()?;
+
+    let old = to_table(on_disk)?;
+    let new = to_table(config)?;
+    apply_changes(doc.as_table_mut(), &old, &new)?;
+
+    Ok(doc.to_string())
+}
+
+fn to_table(config: &Config) -> Result<toml::Table> {
+    match toml::Value::try_from(config)? {
+        toml::Value::Table(table) => Ok(table),
+        _ => Err(ConfigError::Custom(
+            "Config did not serialize to a table".to_string(),
+        )),
+    }
+}
+
+/// Update, insert and remove keys of `table` so it matches `new`
+fn apply_changes(table: &mut dyn TableLike, old: &toml::Table, new: &toml::Table) -> Result<()> {
+    for (key, new_value) in new {
+        let old_value = old.get(key);
+        if old_value == Some(new_value) {
+            continue;
+        }
+
+        // Recurse into tables so untouched siblings keep their formatting
+        if let (toml::Value::Table(new_sub), Some(toml::Value::Table(old_sub))) =
+            (new_value, old_value)
+        {
+            let item = table.entry(key).or_insert_with(implicit_table);
+            if let Some(sub) = item.as_table_like_mut() {
+                apply_changes(sub, old_sub, new_sub)?;
+                continue;
+            }
+        }
+
+        if let toml::Value::Table(_) = new_value {
+            let doc: Document = toml::to_string(new_value)?.parse()?;
+            table.insert(key, Item::Table(doc.as_table().clone()));
+            continue;
+        }
+
+        let mut value: toml_edit::Value = new_value.to_string().parse()?;
+        match table.get_mut(key) {
+            Some(Item::Value(existing)) => {
+                // Keep the spacing and trailing comment of the old value
+                *value.decor_mut() = existing.decor().clone();
+                *existing = value;
+            }
+            _ => {
+                table.insert(key, Item::Value(value));
+            }
+        }
+    }
+
+    // Keys dropped from the config (e.g. unbound keys)
+    for key in old.keys().filter(|key| !new.contains_key(*key)) {
+        table.remove(key);
     }
 
-    save_to_file(config, &config_path)
+    Ok(())
+}
+
+fn implicit_table() -> Item {
+    let mut table = toml_edit::Table::new();
+    table.set_implicit(true);
+    Item::Table(table)
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::defaults;
+    use crate::types::BindingValue;
+
+    const ANNOTATED: &str = r#"# My amux config
+[prefix]
+key = "C-s" # tmux muscle memory
+
+[options]
+# Keep the git panel, I use it a lot
+git_panel_enabled = true
+mouse_enabled = false # trackpad is flaky
+
+[bindings.sidebar]
+# Vim-style navigation
+"j" = "move-down"
+"#;
+
+    fn load(content: &str) -> Config {
+        let mut config = parser::parse_toml(content).unwrap();
+        config.merge_with_defaults();
+        config
+    }
 
     #[test]
     fn test_serialization() {
@@ -41,4 +292,111 @@ mod tests {
         assert!(toml_str.contains("[options]"));
         assert!(toml_str.contains("[bindings"));
     }
+
+    #[test]
+    fn test_unchanged_config_round_trips_verbatim() {
+        let config = load(ANNOTATED);
+        assert_eq!(
+            edit_document(&config, ANNOTATED, &load(ANNOTATED)).unwrap(),
+            ANNOTATED
+        );
+    }
+
+    #[test]
+    fn test_edit_preserves_comments() {
+        let mut config = load(ANNOTATED);
+        config.options.mouse_enabled = true;
+        config.options.open_links = false;
+        config
+            .bindings
+            .sidebar
+            .insert("J".to_string(), "move-down".into());
+        config
+            .bindings
+            .diff
+            .insert("C-x".to_string(), "quit".into());
+
+        let edited = edit_document(&config, ANNOTATED, &load(ANNOTATED)).unwrap();
+        assert!(edited.starts_with("# My amux config\n"));
+        assert!(edited.contains(r#"key = "C-s" # tmux muscle memory"#));
+        assert!(edited.contains("# Keep the git panel, I use it a lot\n"));
+        assert!(edited.contains("mouse_enabled = true # trackpad is flaky"));
+        assert!(edited.contains("open_links = false"));
+        assert!(edited.contains("# Vim-style navigation\n"));
+        assert!(edited.contains(r#"J = "move-down""#));
+        // Defaults that weren't in the file stay out of it
+        assert!(!edited.contains("tree_view_enabled"));
+
+        let reloaded = load(&edited);
+        assert!(reloaded.options.mouse_enabled);
+        assert!(!reloaded.options.open_links);
+        assert_eq!(reloaded.bindings.diff.get("C-x"), Some(&"quit".into()));
+    }
+
+    #[test]
+    fn test_key_arrays_round_trip() {
+        let content = "[bindings.sidebar]\nmove-up = [\"k\", \"Up\"] # both work\n";
+        let mut config = load(content);
+        assert_eq!(
+            edit_document(&config, content, &load(content)).unwrap(),
+            content
+        );
+
+        config.bindings.sidebar.insert(
+            "move-up".to_string(),
+            BindingValue::Keys(vec!["k".to_string(), "w".to_string()]),
+        );
+        let edited = edit_document(&config, content, &load(content)).unwrap();
+        assert!(edited.contains(r#"move-up = ["k", "w"] # both work"#));
+        assert_eq!(
+            load(&edited).bindings.sidebar.get("move-up"),
+            config.bindings.sidebar.get("move-up")
+        );
+    }
+
+    #[test]
+    fn test_nested_keys_are_edited_in_place() {
+        let content = "[highlight.extensions]\ntf = \"HCL\" # terraform\nh = \"C\"\n";
+        let mut config = load(content);
+        config
+            .highlight
+            .extensions
+            .insert("tf".to_string(), "Terraform".to_string());
+        config.highlight.extensions.remove("h");
+
+        let edited = edit_document(&config, content, &load(content)).unwrap();
+        assert_eq!(
+            edited,
+            "[highlight.extensions]\ntf = \"Terraform\" # terraform\n"
+        );
+    }
+
+    #[test]
+    fn test_minimal_config_loads_to_defaults() {
+        let content = write_minimal_config();
+        assert!(content.contains("# key = \"C-s\""));
+        assert!(content.contains("# quit_behavior = \"detach\""));
+        assert!(content.contains("[bindings.sidebar]"));
+
+        let defaults = defaults::default_config();
+        assert_eq!(
+            to_table(&load(&content)).unwrap(),
+            to_table(&defaults).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_save_with_edit_writes_file() {
+        let path = std::env::temp_dir().join(format!("amux-writer-{}.toml", std::process::id()));
+        std::fs::write(&path, ANNOTATED).unwrap();
+
+        let mut config = load(ANNOTATED);
+        config.prefix.key = "C-a".to_string();
+        save_with_edit(&config, &path).unwrap();
+
+        let saved = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert!(saved.contains(r#"key = "C-a" # tmux muscle memory"#));
+        assert!(saved.contains("# Vim-style navigation"));
+    }
 }
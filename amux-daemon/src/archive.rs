@@ -0,0 +1,245 @@
+//! Portable workspace archives
+//!
+//! An archive is a `.tar.gz` laid out like the data directory: `repos.json`,
+//! `sessions/<id>/meta.json` and `sessions/<id>/history.bin`, plus a
+//! `manifest.json` recording the daemon version that wrote it. Archives are
+//! read into memory rather than unpacked, so entry paths never reach the
+//! filesystem.
+
+use crate::persistence::SessionMeta;
+use crate::repo::Repo;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{self, Read, Write};
+use std::path::{Component, Path};
+
+const MANIFEST_FILE: &str = "manifest.json";
+const REPOS_FILE: &str = "repos.json";
+const SESSIONS_DIR: &str = "sessions";
+const META_FILE: &str = "meta.json";
+const HISTORY_FILE: &str = "history.bin";
+
+/// Archive metadata
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub daemon_version: String,
+    /// Unix time the archive was written
+    pub created_at: u64,
+}
+
+impl Manifest {
+    /// Manifest for an archive written now by this daemon
+    pub fn current() -> Self {
+        Self {
+            daemon_version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// A session's metadata and terminal history
+pub struct ArchivedSession {
+    pub meta: SessionMeta,
+    pub history: Vec<u8>,
+}
+
+/// Contents of a workspace archive
+pub struct Workspace {
+    pub manifest: Manifest,
+    pub repos: Vec<Repo>,
+    pub sessions: Vec<ArchivedSession>,
+}
+
+/// Write `workspace` as a gzipped tarball
+pub fn write<W: Write>(writer: W, workspace: &Workspace) -> io::Result<()> {
+    let mut tar = tar::Builder::new(GzEncoder::new(writer, Compression::default()));
+
+    append(
+        &mut tar,
+        MANIFEST_FILE,
+        &serde_json::to_vec_pretty(&workspace.manifest)?,
+    )?;
+    append(
+        &mut tar,
+        REPOS_FILE,
+        &serde_json::to_vec_pretty(&workspace.repos)?,
+    )?;
+    for session in &workspace.sessions {
+        let dir = format!("{}/{}", SESSIONS_DIR, session.meta.id);
+        append(
+            &mut tar,
+            &format!("{}/{}", dir, META_FILE),
+            &serde_json::to_vec_pretty(&session.meta)?,
+        )?;
+        if !session.history.is_empty() {
+            append(
+                &mut tar,
+                &format!("{}/{}", dir, HISTORY_FILE),
+                &session.history,
+            )?;
+        }
+    }
+
+    tar.into_inner()?.finish()?.flush()
+}
+
+/// Read a workspace written by `write`
+///
+/// Sessions without metadata, or whose metadata ID doesn't match their
+/// directory, are left out.
+pub fn read<R: Read>(reader: R) -> io::Result<Workspace> {
+    let mut tar = tar::Archive::new(GzDecoder::new(reader));
+    let mut manifest = None;
+    let mut repos = Vec::new();
+    let mut metas: BTreeMap<String, SessionMeta> = BTreeMap::new();
+    let mut histories: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path()?.into_owned();
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+
+        let parts: Vec<&str> = path
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(part) => part.to_str(),
+                _ => None,
+            })
+            .collect();
+        match parts.as_slice() {
+            [MANIFEST_FILE] => manifest = Some(serde_json::from_slice(&data)?),
+            [REPOS_FILE] => repos = serde_json::from_slice(&data)?,
+            [SESSIONS_DIR, id, META_FILE] if is_session_id(id) => {
+                metas.insert(id.to_string(), serde_json::from_slice(&data)?);
+            }
+            [SESSIONS_DIR, id, HISTORY_FILE] if is_session_id(id) => {
+                histories.insert(id.to_string(), data);
+            }
+            _ => {}
+        }
+    }
+
+    let manifest = manifest.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not an amux workspace archive (no manifest)",
+        )
+    })?;
+    let sessions = metas
+        .into_iter()
+        .filter(|(id, meta)| *id == meta.id)
+        .map(|(id, meta)| ArchivedSession {
+            history: histories.remove(&id).unwrap_or_default(),
+            meta,
+        })
+        .collect();
+
+    Ok(Workspace {
+        manifest,
+        repos,
+        sessions,
+    })
+}
+
+fn append<W: Write>(tar: &mut tar::Builder<W>, path: &str, data: &[u8]) -> io::Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    tar.append_data(&mut header, path, data)
+}
+
+/// Whether `id` can name a session directory
+fn is_session_id(id: &str) -> bool {
+    matches!(
+        Path::new(id).components().collect::<Vec<_>>().as_slice(),
+        [Component::Normal(_)]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn meta(id: &str) -> SessionMeta {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": "fix-login",
+            "repo_id": "r1",
+            "branch": "main",
+            "worktree_path": "/tmp/r1",
+            "created_at": 1,
+            "updated_at": 1,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_archive_round_trip() {
+        let workspace = Workspace {
+            manifest: Manifest::current(),
+            repos: vec![Repo {
+                id: "r1".to_string(),
+                name: "r1".to_string(),
+                path: PathBuf::from("/tmp/r1"),
+                delete_actions: HashMap::new(),
+                checkpoints: false,
+                env: Default::default(),
+                worktree_env: HashMap::new(),
+            }],
+            sessions: vec![
+                ArchivedSession {
+                    meta: meta("s1"),
+                    history: b"\x1b[1m$ ls\x1b[0m\r\n".to_vec(),
+                },
+                ArchivedSession {
+                    meta: meta("s2"),
+                    history: Vec::new(),
+                },
+            ],
+        };
+
+        let mut bytes = Vec::new();
+        write(&mut bytes, &workspace).unwrap();
+        let read_back = read(&bytes[..]).unwrap();
+
+        assert_eq!(read_back.manifest, workspace.manifest);
+        assert_eq!(read_back.repos.len(), 1);
+        assert_eq!(read_back.repos[0].id, "r1");
+        assert_eq!(read_back.sessions.len(), 2);
+        assert_eq!(read_back.sessions[0].meta.id, "s1");
+        assert_eq!(read_back.sessions[0].history, workspace.sessions[0].history);
+        assert!(read_back.sessions[1].history.is_empty());
+    }
+
+    #[test]
+    fn test_read_rejects_foreign_archives() {
+        let mut bytes = Vec::new();
+        let mut tar = tar::Builder::new(GzEncoder::new(&mut bytes, Compression::default()));
+        append(&mut tar, "notes.txt", b"hello").unwrap();
+        tar.into_inner().unwrap().finish().unwrap();
+
+        let err = read(&bytes[..]).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_is_session_id() {
+        assert!(is_session_id("5f0c2a"));
+        assert!(!is_session_id(".."));
+        assert!(!is_session_id("a/b"));
+        assert!(!is_session_id(""));
+    }
+}
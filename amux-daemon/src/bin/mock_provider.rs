@@ -0,0 +1,70 @@
+//! Helper process for the daemon's `MockProvider`
+//!
+//! Reads a script from `AMUX_MOCK_SCRIPT` and emits predictable output, so
+//! tests can exercise sessions without a real AI CLI installed. The script is
+//! JSON with a `kind` of `echo`, `delay` (`ms`), `lines` (`lines`) or `fail`
+//! (`message`); a missing variable means `echo`.
+
+use serde_json::Value;
+use std::io::{BufRead, Write};
+use std::time::Duration;
+
+fn main() {
+    let script: Value = match std::env::var("AMUX_MOCK_SCRIPT") {
+        Ok(raw) => match serde_json::from_str(&raw) {
+            Ok(script) => script,
+            Err(e) => fail(&format!("invalid AMUX_MOCK_SCRIPT: {}", e)),
+        },
+        Err(_) => serde_json::json!({ "kind": "echo" }),
+    };
+
+    match script["kind"].as_str() {
+        Some("echo") => echo(),
+        Some("delay") => {
+            let ms = script["ms"].as_u64().unwrap_or(0);
+            std::thread::sleep(Duration::from_millis(ms));
+            echo();
+        }
+        Some("lines") => {
+            let mut out = std::io::stdout().lock();
+            for line in script["lines"].as_array().into_iter().flatten() {
+                writeln!(out, "{}", line.as_str().unwrap_or_default()).ok();
+            }
+        }
+        Some("fail") => fail(script["message"].as_str().unwrap_or("mock failure")),
+        other => fail(&format!("unknown mock script kind: {:?}", other)),
+    }
+}
+
+/// Print the session banner, then echo input until `exit` or EOF
+fn echo() {
+    let mut out = std::io::stdout().lock();
+    let mut args = std::env::args().skip(1);
+    while let (Some(flag), Some(value)) = (args.next(), args.next()) {
+        let label = match flag.as_str() {
+            "--name" => "Provider",
+            "--model" => "Model",
+            "--session-id" => "Session",
+            "--resume" => "Resumed session",
+            "--prompt" => "Prompt",
+            _ => continue,
+        };
+        writeln!(out, "{}: {}", label, value).ok();
+    }
+    writeln!(out, "Mock AI ready. Type 'exit' to quit.").ok();
+    out.flush().ok();
+
+    for line in std::io::stdin().lock().lines() {
+        let Ok(line) = line else { break };
+        if line.trim() == "exit" {
+            break;
+        }
+        writeln!(out, "Mock response to: {}", line).ok();
+        out.flush().ok();
+    }
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(1);
+}
@@ -0,0 +1,181 @@
+//! Git blame of committed files
+//!
+//! Blame walks the file's history, so results are cached per worktree, file
+//! and HEAD commit; a new commit (or checkout) naturally misses the cache.
+
+use crate::error::GitError;
+use git2::{BlameOptions, Oid, Repository};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Who last touched a line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameLine {
+    pub commit_id: String,
+    pub author: String,
+    pub summary: String,
+    pub content: String,
+}
+
+/// Git blame operations
+pub struct BlameOps;
+
+impl BlameOps {
+    /// SHA of the worktree's HEAD commit
+    pub fn head_id(worktree_path: &Path) -> Result<String, GitError> {
+        let repo = Repository::open(worktree_path)?;
+        let head = repo.head()?.peel_to_commit()?;
+        Ok(head.id().to_string())
+    }
+
+    /// Blame every line of `file` as committed at `head`
+    pub fn blame_file(
+        worktree_path: &Path,
+        file: &str,
+        head: &str,
+    ) -> Result<Vec<BlameLine>, GitError> {
+        let repo = Repository::open(worktree_path)?;
+        let head = repo.find_commit(Oid::from_str(head)?)?;
+        let blob = head
+            .tree()?
+            .get_path(Path::new(file))
+            .ok()
+            .and_then(|entry| repo.find_blob(entry.id()).ok())
+            .ok_or_else(|| GitError::NotInHead(file.to_string()))?;
+        let content = String::from_utf8_lossy(blob.content());
+
+        let mut opts = BlameOptions::new();
+        opts.newest_commit(head.id());
+        let blame = repo.blame_file(Path::new(file), Some(&mut opts))?;
+
+        // Many lines share a commit; look each one up once
+        let mut commits: HashMap<Oid, (String, String)> = HashMap::new();
+        let mut lines = Vec::new();
+        for (idx, text) in content.lines().enumerate() {
+            let Some(hunk) = blame.get_line(idx + 1) else {
+                lines.push(BlameLine {
+                    commit_id: String::new(),
+                    author: String::new(),
+                    summary: String::new(),
+                    content: text.to_string(),
+                });
+                continue;
+            };
+            let oid = hunk.final_commit_id();
+            let (author, summary) = commits.entry(oid).or_insert_with(|| {
+                let author = hunk
+                    .final_signature()
+                    .name()
+                    .unwrap_or_default()
+                    .to_string();
+                let summary = repo
+                    .find_commit(oid)
+                    .ok()
+                    .and_then(|c| c.summary().map(str::to_string))
+                    .unwrap_or_default();
+                (author, summary)
+            });
+            lines.push(BlameLine {
+                commit_id: oid.to_string(),
+                author: author.clone(),
+                summary: summary.clone(),
+                content: text.to_string(),
+            });
+        }
+        Ok(lines)
+    }
+}
+
+/// Worktree path, file and HEAD SHA the blame was computed for
+pub type BlameKey = (PathBuf, String, String);
+
+/// Maximum number of cached blames (oldest are dropped first)
+const BLAME_CACHE_CAPACITY: usize = 32;
+
+/// Recently computed blames
+#[derive(Default)]
+pub struct BlameCache {
+    entries: HashMap<BlameKey, Arc<Vec<BlameLine>>>,
+    order: VecDeque<BlameKey>,
+}
+
+impl BlameCache {
+    pub fn get(&self, key: &BlameKey) -> Option<Arc<Vec<BlameLine>>> {
+        self.entries.get(key).cloned()
+    }
+
+    pub fn insert(&mut self, key: BlameKey, lines: Arc<Vec<BlameLine>>) {
+        if self.entries.insert(key.clone(), lines).is_none() {
+            self.order.push_back(key);
+        }
+        while self.order.len() > BLAME_CACHE_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn commit(repo: &Repository, dir: &Path, content: &str, author: &str, message: &str) {
+        fs::write(dir.join("file.txt"), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("file.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now(author, "test@test.com").unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<_> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_blame_file_at_head() {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        commit(&repo, dir.path(), "one\ntwo\n", "Alice", "Add file\n\nBody");
+        commit(&repo, dir.path(), "one\n2\nthree\n", "Bob", "Change two");
+        // Uncommitted edits don't show up
+        fs::write(dir.path().join("file.txt"), "edited\n").unwrap();
+
+        let head = BlameOps::head_id(dir.path()).unwrap();
+        let lines = BlameOps::blame_file(dir.path(), "file.txt", &head).unwrap();
+        let summary: Vec<_> = lines
+            .iter()
+            .map(|l| (l.content.as_str(), l.author.as_str(), l.summary.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("one", "Alice", "Add file"),
+                ("2", "Bob", "Change two"),
+                ("three", "Bob", "Change two"),
+            ]
+        );
+        assert_eq!(lines[1].commit_id, head);
+
+        assert!(matches!(
+            BlameOps::blame_file(dir.path(), "missing.txt", &head),
+            Err(GitError::NotInHead(_))
+        ));
+    }
+
+    #[test]
+    fn test_blame_cache_drops_oldest() {
+        let mut cache = BlameCache::default();
+        let key = |i: usize| (PathBuf::from("/wt"), format!("f{}", i), "head".to_string());
+        for i in 0..=BLAME_CACHE_CAPACITY {
+            cache.insert(key(i), Arc::new(Vec::new()));
+        }
+        assert!(cache.get(&key(0)).is_none());
+        assert!(cache.get(&key(1)).is_some());
+        assert!(cache.get(&key(BLAME_CACHE_CAPACITY)).is_some());
+    }
+}
@@ -0,0 +1,296 @@
+//! Worktree checkpoints taken when a session starts
+//!
+//! With checkpoints enabled for a repo, creating a non-shell session records
+//! the worktree as a commit on `refs/amux/checkpoints/<session id>`. Its tree
+//! holds the tracked and untracked (not ignored) files as they are on disk,
+//! its parent is HEAD; neither the index nor the worktree is touched.
+//!
+//! Restoring moves the branch back to that HEAD and puts the files back, so
+//! commits and changes made since are dropped (the commits stay reachable
+//! from the reflog). It is refused once the worktree is on another branch,
+//! which the reset would move instead. The ref is deleted once its session
+//! is gone for good.
+
+use crate::error::GitError;
+use crate::git::GitOps;
+use git2::build::CheckoutBuilder;
+use git2::{Commit, IndexAddOption, Oid, Repository, ResetType, Signature};
+use std::path::Path;
+
+const REF_PREFIX: &str = "refs/amux/checkpoints/";
+const SESSION_TRAILER: &str = "Session: ";
+const NAME_TRAILER: &str = "Session-Name: ";
+const BRANCH_TRAILER: &str = "Branch: ";
+
+/// A session's checkpoint
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checkpoint {
+    pub session_id: String,
+    pub session_name: String,
+    pub branch: String,
+    pub oid: Oid,
+    /// HEAD when the checkpoint was taken (None on an unborn branch)
+    pub base: Option<Oid>,
+    /// Unix time the checkpoint was taken
+    pub time: i64,
+}
+
+fn ref_name(session_id: &str) -> String {
+    format!("{}{}", REF_PREFIX, session_id)
+}
+
+/// Record the worktree at `worktree_path` as the session's checkpoint
+pub fn create(
+    worktree_path: &Path,
+    session_id: &str,
+    session_name: &str,
+    branch: &str,
+) -> Result<Checkpoint, GitError> {
+    let repo = GitOps::open(worktree_path)?;
+
+    // Stage everything in memory only; the index file is re-read afterwards
+    let mut index = repo.index()?;
+    index.add_all(["*"].iter(), IndexAddOption::DEFAULT, None)?;
+    index.update_all(["*"].iter(), None)?;
+    let tree_oid = index.write_tree();
+    index.read(true)?;
+    let tree = repo.find_tree(tree_oid?)?;
+
+    let base = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+    let parents: Vec<&Commit> = base.iter().collect();
+    let sig = repo
+        .signature()
+        .or_else(|_| Signature::now("amux", "amux@localhost"))?;
+    let message = format!(
+        "amux checkpoint: {}\n\n{}{}\n{}{}\n{}{}\n",
+        session_name,
+        SESSION_TRAILER,
+        session_id,
+        NAME_TRAILER,
+        session_name,
+        BRANCH_TRAILER,
+        branch
+    );
+    let oid = repo.commit(None, &sig, &sig, &message, &tree, &parents)?;
+    repo.reference(&ref_name(session_id), oid, true, "amux checkpoint")?;
+
+    let commit = repo.find_commit(oid)?;
+    parse(&commit).ok_or_else(|| GitError::Custom("unreadable checkpoint".to_string()))
+}
+
+/// Checkpoints of a repository, optionally only those of `branch`, newest first
+pub fn list(repo: &Repository, branch: Option<&str>) -> Result<Vec<Checkpoint>, GitError> {
+    let mut checkpoints = Vec::new();
+    for reference in repo.references_glob(&format!("{}*", REF_PREFIX))? {
+        let Ok(commit) = reference?.peel_to_commit() else {
+            continue;
+        };
+        if let Some(checkpoint) = parse(&commit) {
+            if branch.is_none_or(|b| b == checkpoint.branch) {
+                checkpoints.push(checkpoint);
+            }
+        }
+    }
+    checkpoints.sort_by_key(|c| std::cmp::Reverse(c.time));
+    Ok(checkpoints)
+}
+
+/// Put the worktree back to the session's checkpoint
+pub fn restore(worktree_path: &Path, session_id: &str) -> Result<Checkpoint, GitError> {
+    let repo = GitOps::open(worktree_path)?;
+    let commit = repo
+        .find_reference(&ref_name(session_id))
+        .and_then(|r| r.peel_to_commit())
+        .map_err(|_| GitError::Custom(format!("no checkpoint for session {}", session_id)))?;
+    let checkpoint =
+        parse(&commit).ok_or_else(|| GitError::Custom("unreadable checkpoint".to_string()))?;
+    let current = head_branch(&repo)?;
+    if current != checkpoint.branch {
+        return Err(GitError::WrongBranch {
+            expected: checkpoint.branch,
+            actual: current,
+        });
+    }
+    let base = commit.parent(0).ok();
+
+    if let Some(base) = &base {
+        repo.reset(base.as_object(), ResetType::Hard, None)?;
+    }
+    // Files created since aren't in the checkpoint tree and are removed
+    let mut checkout = CheckoutBuilder::new();
+    checkout.force().remove_untracked(true);
+    repo.checkout_tree(commit.tree()?.as_object(), Some(&mut checkout))?;
+    // Leave the restored changes unstaged, as they were
+    if let Some(base) = &base {
+        repo.reset(base.as_object(), ResetType::Mixed, None)?;
+    }
+
+    Ok(checkpoint)
+}
+
+/// Branch HEAD points at, unborn or not; "detached HEAD" when it's detached
+fn head_branch(repo: &Repository) -> Result<String, GitError> {
+    let head = repo.find_reference("HEAD")?;
+    Ok(head
+        .symbolic_target()
+        .map(|target| target.strip_prefix("refs/heads/").unwrap_or(target))
+        .unwrap_or("detached HEAD")
+        .to_string())
+}
+
+/// Delete the session's checkpoint, if it has one
+pub fn prune(repo_path: &Path, session_id: &str) -> Result<(), GitError> {
+    let repo = GitOps::open(repo_path)?;
+    let result = match repo.find_reference(&ref_name(session_id)) {
+        Ok(mut reference) => reference.delete(),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(()),
+        Err(e) => Err(e),
+    };
+    Ok(result?)
+}
+
+fn parse(commit: &Commit) -> Option<Checkpoint> {
+    let message = commit.message()?;
+    let trailer = |prefix: &str| {
+        message
+            .lines()
+            .find_map(|l| l.strip_prefix(prefix))
+            .map(str::to_string)
+    };
+    Some(Checkpoint {
+        session_id: trailer(SESSION_TRAILER)?,
+        session_name: trailer(NAME_TRAILER).unwrap_or_default(),
+        branch: trailer(BRANCH_TRAILER)?,
+        oid: commit.id(),
+        base: commit.parent_id(0).ok(),
+        time: commit.time().seconds(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_test_repo() -> (TempDir, Repository) {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@test.com").unwrap();
+
+        (dir, repo)
+    }
+
+    fn commit_all(repo: &Repository, message: &str) -> Oid {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"].iter(), IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = repo.signature().unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_create_and_restore() {
+        let (dir, repo) = create_test_repo();
+        let path = dir.path();
+        fs::write(path.join(".gitignore"), "target/\n").unwrap();
+        fs::write(path.join("a.txt"), "a\n").unwrap();
+        let base = commit_all(&repo, "initial");
+
+        // Uncommitted and untracked work present when the session starts
+        fs::write(path.join("a.txt"), "a edited\n").unwrap();
+        fs::write(path.join("notes.txt"), "notes\n").unwrap();
+        let branch = head_branch(&repo).unwrap();
+        let checkpoint = create(path, "s1", "claude-1", &branch).unwrap();
+        assert_eq!(checkpoint.base, Some(base));
+        assert_eq!(checkpoint.session_name, "claude-1");
+        // Creating it leaves the index alone
+        assert!(repo
+            .index()
+            .unwrap()
+            .get_path(Path::new("notes.txt"), 0)
+            .is_none());
+
+        // The session commits, edits, creates and deletes files
+        fs::write(path.join("a.txt"), "rewritten\n").unwrap();
+        commit_all(&repo, "sweeping edit");
+        fs::write(path.join("new.txt"), "new\n").unwrap();
+        fs::remove_file(path.join("notes.txt")).unwrap();
+        fs::create_dir(path.join("target")).unwrap();
+        fs::write(path.join("target/out"), "build\n").unwrap();
+
+        let restored = restore(path, "s1").unwrap();
+        assert_eq!(restored.oid, checkpoint.oid);
+        assert_eq!(repo.head().unwrap().target(), Some(base));
+        assert_eq!(
+            fs::read_to_string(path.join("a.txt")).unwrap(),
+            "a edited\n"
+        );
+        assert_eq!(
+            fs::read_to_string(path.join("notes.txt")).unwrap(),
+            "notes\n"
+        );
+        assert!(!path.join("new.txt").exists());
+        // Ignored files are left alone
+        assert!(path.join("target/out").exists());
+        // Restored changes are unstaged, untracked files stay untracked
+        let mut index = repo.index().unwrap();
+        index.read(true).unwrap();
+        assert!(index.get_path(Path::new("notes.txt"), 0).is_none());
+        assert!(index.get_path(Path::new("a.txt"), 0).is_some());
+    }
+
+    #[test]
+    fn test_list_and_prune() {
+        let (dir, repo) = create_test_repo();
+        fs::write(dir.path().join("a.txt"), "a\n").unwrap();
+        commit_all(&repo, "initial");
+
+        create(dir.path(), "s1", "claude-1", "main").unwrap();
+        create(dir.path(), "s2", "claude-2", "feature").unwrap();
+
+        let all = list(&repo, None).unwrap();
+        assert_eq!(all.len(), 2);
+        let main = list(&repo, Some("main")).unwrap();
+        assert_eq!(main.len(), 1);
+        assert_eq!(main[0].session_id, "s1");
+
+        prune(dir.path(), "s1").unwrap();
+        prune(dir.path(), "missing").unwrap();
+        assert!(list(&repo, Some("main")).unwrap().is_empty());
+        assert!(restore(dir.path(), "s1").is_err());
+    }
+
+    #[test]
+    fn test_restore_refuses_other_branch() {
+        let (dir, repo) = create_test_repo();
+        let path = dir.path();
+        fs::write(path.join("a.txt"), "a\n").unwrap();
+        let base = commit_all(&repo, "initial");
+        let branch = head_branch(&repo).unwrap();
+        create(path, "s1", "claude-1", &branch).unwrap();
+
+        // The user moves to another branch and commits there
+        let commit = repo.find_commit(base).unwrap();
+        repo.branch("other", &commit, false).unwrap();
+        repo.set_head("refs/heads/other").unwrap();
+        fs::write(path.join("a.txt"), "other\n").unwrap();
+        let other = commit_all(&repo, "work on other");
+
+        assert!(matches!(
+            restore(path, "s1"),
+            Err(GitError::WrongBranch { actual, .. }) if actual == "other"
+        ));
+        assert_eq!(repo.head().unwrap().target(), Some(other));
+        assert_eq!(fs::read_to_string(path.join("a.txt")).unwrap(), "other\n");
+    }
+}
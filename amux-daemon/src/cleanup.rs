@@ -0,0 +1,89 @@
+//! Removal of stale worktrees and sessions
+//!
+//! A worktree directory deleted outside amux leaves its git admin entry
+//! behind, along with sessions pointing at the missing path. Cleanup prunes
+//! those git entries and drops the sessions (and their persisted data). It
+//! runs for every repo at startup and on demand through `CleanupWorktrees`.
+
+use crate::git::GitOps;
+use crate::persistence;
+use crate::session::Session;
+use crate::state::AppState;
+
+/// What a cleanup removed
+#[derive(Default)]
+pub struct CleanupReport {
+    /// Branches of the pruned git worktrees
+    pub pruned_branches: Vec<String>,
+    /// Sessions whose worktree no longer exists
+    pub removed_sessions: Vec<Session>,
+    /// Entries that couldn't be cleaned up
+    pub errors: Vec<String>,
+}
+
+impl CleanupReport {
+    /// Number of stale worktrees and sessions removed
+    pub fn removed_count(&self) -> u32 {
+        (self.pruned_branches.len() + self.removed_sessions.len()) as u32
+    }
+}
+
+/// Prune stale worktrees of a repo and remove sessions left without one
+pub fn cleanup_repo(state: &mut AppState, repo_id: &str) -> CleanupReport {
+    let mut report = CleanupReport::default();
+
+    if let Some(repo) = state.repos.get(repo_id) {
+        match GitOps::open(&repo.path).and_then(|git_repo| GitOps::prune_worktrees(&git_repo)) {
+            Ok((pruned, errors)) => {
+                report.pruned_branches = pruned;
+                report.errors = errors;
+            }
+            Err(e) => report.errors.push(format!("{}: {}", repo.name, e)),
+        }
+    }
+
+    let stale: Vec<String> = state
+        .sessions
+        .values()
+        .filter(|s| s.repo_id == repo_id && !s.worktree_path.exists())
+        .map(|s| s.id.clone())
+        .collect();
+    for id in stale {
+        let Some(mut session) = state.sessions.remove(&id) else {
+            continue;
+        };
+        tracing::info!(
+            "Removing orphaned session {} (worktree not found: {:?})",
+            session.id,
+            session.worktree_path
+        );
+        if let Err(e) = session.stop() {
+            report.errors.push(format!("{}: {}", session.name, e));
+        }
+        if let Err(e) = persistence::delete_session_data(&session.id) {
+            report.errors.push(format!("{}: {}", session.name, e));
+        }
+        if session.checkpoint.is_some() {
+            state.prune_checkpoint(repo_id, &session.id);
+        }
+        report.removed_sessions.push(session);
+    }
+
+    // Close the gaps left in the affected worktrees' ordering
+    let mut branches: Vec<String> = report
+        .removed_sessions
+        .iter()
+        .map(|s| s.branch.clone())
+        .collect();
+    branches.sort();
+    branches.dedup();
+    for branch in branches {
+        for id in state.renumber_sessions(repo_id, &branch, None) {
+            if let Some(session) = state.sessions.get(&id) {
+                let _ = persistence::save_session_meta(session);
+            }
+        }
+    }
+
+    report
+}
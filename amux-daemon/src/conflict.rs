@@ -0,0 +1,245 @@
+//! Merge conflict marker parsing and resolution
+//!
+//! A conflicted file interleaves unchanged text with hunks of the form
+//!
+//! ```text
+//! <<<<<<< ours-label
+//! our lines
+//! ||||||| base-label     (diff3 style only)
+//! base lines
+//! =======
+//! their lines
+//! >>>>>>> theirs-label
+//! ```
+//!
+//! Files are split into lines without their `\n` terminator; the `\r` of
+//! CRLF files stays part of the line, so writing a file back keeps its line
+//! endings. A hunk that is never closed is kept as plain text.
+
+/// Unchanged text or a conflict hunk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Region {
+    Common(Vec<String>),
+    Conflict(ConflictHunk),
+}
+
+/// One `<<<<<<<` ... `>>>>>>>` block
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConflictHunk {
+    pub ours_label: String,
+    pub ours: Vec<String>,
+    /// Common ancestor lines (diff3 conflict style)
+    pub base: Vec<String>,
+    pub theirs_label: String,
+    pub theirs: Vec<String>,
+}
+
+/// How to resolve a conflict hunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Ours,
+    Theirs,
+    /// Our lines followed by theirs
+    Both,
+}
+
+/// A file split into regions
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConflictedFile {
+    pub regions: Vec<Region>,
+    trailing_newline: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Ours,
+    Base,
+    Theirs,
+}
+
+/// Label after a marker of seven `c`s, or `None` if `line` isn't that marker
+fn marker_label(line: &str, c: char) -> Option<&str> {
+    let line = line.strip_suffix('\r').unwrap_or(line);
+    let mut chars = line.chars();
+    if !chars.by_ref().take(7).eq(std::iter::repeat_n(c, 7)) {
+        return None;
+    }
+    let rest = chars.as_str();
+    if rest.is_empty() {
+        Some("")
+    } else {
+        rest.strip_prefix(' ')
+    }
+}
+
+impl ConflictedFile {
+    /// Split file content into unchanged regions and conflict hunks
+    pub fn parse(content: &str) -> Self {
+        let trailing_newline = content.ends_with('\n');
+        let body = content.strip_suffix('\n').unwrap_or(content);
+
+        let mut regions = Vec::new();
+        let mut common: Vec<String> = Vec::new();
+        let mut open: Option<(ConflictHunk, Section)> = None;
+        // Raw lines of the open hunk, restored if it's never closed
+        let mut raw: Vec<String> = Vec::new();
+
+        for line in body.split('\n').filter(|_| !content.is_empty()) {
+            let Some((hunk, section)) = open.as_mut() else {
+                match marker_label(line, '<') {
+                    Some(label) => {
+                        let hunk = ConflictHunk {
+                            ours_label: label.to_string(),
+                            ..Default::default()
+                        };
+                        open = Some((hunk, Section::Ours));
+                        raw = vec![line.to_string()];
+                    }
+                    None => common.push(line.to_string()),
+                }
+                continue;
+            };
+            raw.push(line.to_string());
+
+            match section {
+                Section::Ours | Section::Base if marker_label(line, '=') == Some("") => {
+                    *section = Section::Theirs;
+                }
+                Section::Ours if marker_label(line, '|').is_some() => *section = Section::Base,
+                Section::Ours => hunk.ours.push(line.to_string()),
+                Section::Base => hunk.base.push(line.to_string()),
+                Section::Theirs => match marker_label(line, '>') {
+                    Some(label) => {
+                        hunk.theirs_label = label.to_string();
+                        if let Some((hunk, _)) = open.take() {
+                            if !common.is_empty() {
+                                regions.push(Region::Common(std::mem::take(&mut common)));
+                            }
+                            regions.push(Region::Conflict(hunk));
+                        }
+                    }
+                    None => hunk.theirs.push(line.to_string()),
+                },
+            }
+        }
+
+        if open.is_some() {
+            common.append(&mut raw);
+        }
+        if !common.is_empty() {
+            regions.push(Region::Common(common));
+        }
+
+        Self {
+            regions,
+            trailing_newline,
+        }
+    }
+
+    /// Number of conflict hunks
+    pub fn conflict_count(&self) -> usize {
+        self.regions
+            .iter()
+            .filter(|r| matches!(r, Region::Conflict(_)))
+            .count()
+    }
+
+    /// File content with each hunk resolved, one resolution per hunk in order
+    ///
+    /// Returns `None` if the number of resolutions doesn't match the hunks.
+    pub fn resolve(&self, resolutions: &[Resolution]) -> Option<String> {
+        if resolutions.len() != self.conflict_count() {
+            return None;
+        }
+
+        let mut resolutions = resolutions.iter();
+        let mut lines: Vec<&str> = Vec::new();
+        for region in &self.regions {
+            match region {
+                Region::Common(common) => lines.extend(common.iter().map(String::as_str)),
+                Region::Conflict(hunk) => {
+                    let resolution = resolutions.next()?;
+                    if matches!(resolution, Resolution::Ours | Resolution::Both) {
+                        lines.extend(hunk.ours.iter().map(String::as_str));
+                    }
+                    if matches!(resolution, Resolution::Theirs | Resolution::Both) {
+                        lines.extend(hunk.theirs.iter().map(String::as_str));
+                    }
+                }
+            }
+        }
+
+        let mut content = lines.join("\n");
+        if self.trailing_newline && !lines.is_empty() {
+            content.push('\n');
+        }
+        Some(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFLICTED: &str = "fn main() {\n\
+<<<<<<< HEAD\n    println!(\"ours\");\n=======\n    println!(\"theirs\");\n    println!(\"more\");\n>>>>>>> feature\n\
+}\n\
+<<<<<<< HEAD\n=======\n// theirs only\n>>>>>>> feature\n";
+
+    #[test]
+    fn test_parse_conflict_hunks() {
+        let file = ConflictedFile::parse(CONFLICTED);
+        assert_eq!(file.conflict_count(), 2);
+        assert_eq!(file.regions.len(), 4);
+        assert_eq!(file.regions[0], Region::Common(vec!["fn main() {".into()]));
+        let Region::Conflict(hunk) = &file.regions[1] else {
+            panic!("expected a conflict");
+        };
+        assert_eq!(hunk.ours_label, "HEAD");
+        assert_eq!(hunk.ours, vec!["    println!(\"ours\");"]);
+        assert_eq!(hunk.theirs.len(), 2);
+        assert_eq!(hunk.theirs_label, "feature");
+        let Region::Conflict(empty_ours) = &file.regions[3] else {
+            panic!("expected a conflict");
+        };
+        assert!(empty_ours.ours.is_empty());
+    }
+
+    #[test]
+    fn test_parse_diff3_crlf_and_unterminated() {
+        let file = ConflictedFile::parse(
+            "<<<<<<< ours\r\na\r\n||||||| base\r\no\r\n=======\r\nb\r\n>>>>>>> theirs\r\n",
+        );
+        let Region::Conflict(hunk) = &file.regions[0] else {
+            panic!("expected a conflict");
+        };
+        assert_eq!(hunk.ours, vec!["a\r"]);
+        assert_eq!(hunk.base, vec!["o\r"]);
+        assert_eq!(hunk.theirs, vec!["b\r"]);
+        assert_eq!(
+            file.resolve(&[Resolution::Theirs]).unwrap(),
+            "b\r\n".to_string()
+        );
+
+        // Not a marker without the space, and an open hunk stays text
+        let text = "<<<<<<<<x\n<<<<<<< HEAD\na\n=======\n";
+        let file = ConflictedFile::parse(text);
+        assert_eq!(file.conflict_count(), 0);
+        assert_eq!(file.resolve(&[]).unwrap(), text);
+    }
+
+    #[test]
+    fn test_resolve_hunks() {
+        let file = ConflictedFile::parse(CONFLICTED);
+        assert_eq!(file.resolve(&[Resolution::Ours]), None);
+        assert_eq!(
+            file.resolve(&[Resolution::Ours, Resolution::Theirs])
+                .unwrap(),
+            "fn main() {\n    println!(\"ours\");\n}\n// theirs only\n"
+        );
+        assert_eq!(
+            file.resolve(&[Resolution::Both, Resolution::Ours]).unwrap(),
+            "fn main() {\n    println!(\"ours\");\n    println!(\"theirs\");\n    println!(\"more\");\n}\n"
+        );
+    }
+}
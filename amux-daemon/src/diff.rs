@@ -1,7 +1,7 @@
 //! Git diff operations
 
 use crate::error::GitError;
-use git2::{Delta, Diff, DiffOptions, Repository, Status, StatusOptions};
+use git2::{Delta, Diff, DiffOptions, Repository, Status, StatusOptions, Tree};
 use std::path::Path;
 
 /// Information about a changed file
@@ -13,6 +13,14 @@ pub struct DiffFileInfo {
     pub deletions: i32,
 }
 
+/// Files changed and lines added/removed, like `git diff --shortstat`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiffStats {
+    pub files_changed: u32,
+    pub insertions: u32,
+    pub deletions: u32,
+}
+
 /// File change status
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum FileStatus {
@@ -45,24 +53,23 @@ pub enum LineType {
 pub struct DiffOps;
 
 impl DiffOps {
-    /// Get list of changed files in worktree (vs HEAD)
-    pub fn get_diff_files(worktree_path: &Path) -> Result<Vec<DiffFileInfo>, GitError> {
+    /// Get list of changed files in worktree (vs `base_ref`, HEAD if `None`)
+    pub fn get_diff_files(
+        worktree_path: &Path,
+        base_ref: Option<&str>,
+    ) -> Result<Vec<DiffFileInfo>, GitError> {
         let repo = Repository::open(worktree_path)?;
         let mut files = Vec::new();
 
-        // Get HEAD tree for comparison
-        let head_tree = match repo.head() {
-            Ok(head) => Some(head.peel_to_tree()?),
-            Err(_) => None, // New repo with no commits
-        };
+        let base_tree = Self::base_tree(&repo, base_ref)?;
 
-        // Get diff between HEAD and working directory (including staged)
+        // Get diff between the base and working directory (including staged)
         let mut diff_opts = DiffOptions::new();
         diff_opts.include_untracked(false); // Handle untracked separately
         diff_opts.recurse_untracked_dirs(false);
 
         let diff =
-            repo.diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut diff_opts))?;
+            repo.diff_tree_to_workdir_with_index(base_tree.as_ref(), Some(&mut diff_opts))?;
 
         // Collect file stats from diff
         Self::collect_diff_files(&diff, &mut files)?;
@@ -76,6 +83,26 @@ impl DiffOps {
         Ok(files)
     }
 
+    /// Size of the uncommitted changes against HEAD, staged or not
+    ///
+    /// Untracked files count with all their lines added.
+    pub fn worktree_stats(repo: &Repository) -> Result<DiffStats, GitError> {
+        let head_tree = Self::base_tree(repo, None)?;
+        let mut diff_opts = DiffOptions::new();
+        diff_opts
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .show_untracked_content(true);
+        let diff =
+            repo.diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut diff_opts))?;
+        let stats = diff.stats()?;
+        Ok(DiffStats {
+            files_changed: stats.files_changed() as u32,
+            insertions: stats.insertions() as u32,
+            deletions: stats.deletions() as u32,
+        })
+    }
+
     /// Collect files from a git2 Diff
     fn collect_diff_files(diff: &Diff, files: &mut Vec<DiffFileInfo>) -> Result<(), GitError> {
         for delta_idx in 0..diff.deltas().len() {
@@ -157,8 +184,114 @@ impl DiffOps {
         Ok(())
     }
 
-    /// Get diff content for a specific file
-    pub fn get_file_diff(worktree_path: &Path, file_path: &str) -> Result<Vec<DiffLine>, GitError> {
+    /// Tree the worktree is compared against
+    ///
+    /// `None` means HEAD, or no tree at all in a repo without commits.
+    fn base_tree<'r>(
+        repo: &'r Repository,
+        base_ref: Option<&str>,
+    ) -> Result<Option<Tree<'r>>, GitError> {
+        match base_ref {
+            Some(base_ref) => repo
+                .revparse_single(base_ref)
+                .and_then(|object| object.peel_to_tree())
+                .map(Some)
+                .map_err(|_| GitError::InvalidRef(base_ref.to_string())),
+            None => match repo.head() {
+                Ok(head) => Ok(Some(head.peel_to_tree()?)),
+                Err(_) => Ok(None), // New repo with no commits
+            },
+        }
+    }
+
+    /// Get list of files changed on the branch since it forked from `base_ref`
+    ///
+    /// Like `git diff <base_ref>...HEAD`: committed changes only, against the
+    /// merge base. `None` picks `default_branch_base`. Returns the base used.
+    pub fn get_branch_diff_files(
+        worktree_path: &Path,
+        base_ref: Option<&str>,
+    ) -> Result<(String, Vec<DiffFileInfo>), GitError> {
+        let repo = Repository::open(worktree_path)?;
+        let base_ref = match base_ref {
+            Some(base_ref) => base_ref.to_string(),
+            None => Self::default_branch_base(&repo).ok_or(GitError::NoBaseBranch)?,
+        };
+
+        let mut files = Vec::new();
+        let diff = Self::branch_diff(&repo, &base_ref, None)?;
+        Self::collect_diff_files(&diff, &mut files)?;
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok((base_ref, files))
+    }
+
+    /// Get diff content for a file changed on the branch since `base_ref`
+    pub fn get_branch_file_diff(
+        worktree_path: &Path,
+        base_ref: &str,
+        file_path: &str,
+    ) -> Result<Vec<DiffLine>, GitError> {
+        let repo = Repository::open(worktree_path)?;
+        let mut lines = Vec::new();
+        let diff = Self::branch_diff(&repo, base_ref, Some(file_path))?;
+        Self::collect_diff_lines(&diff, &mut lines)?;
+        Ok(lines)
+    }
+
+    /// Base a branch diff uses by default: HEAD's upstream, else `main`,
+    /// else `master` (local, then on `origin`)
+    fn default_branch_base(repo: &Repository) -> Option<String> {
+        let upstream = repo
+            .head()
+            .ok()
+            .filter(|head| head.is_branch())
+            .and_then(|head| {
+                let branch = git2::Branch::wrap(head);
+                let upstream = branch.upstream().ok()?;
+                upstream.name().ok().flatten().map(str::to_string)
+            });
+        upstream.or_else(|| {
+            ["main", "master", "origin/main", "origin/master"]
+                .into_iter()
+                .find(|name| repo.revparse_single(name).is_ok())
+                .map(str::to_string)
+        })
+    }
+
+    /// Diff HEAD against its merge base with `base_ref`
+    fn branch_diff<'r>(
+        repo: &'r Repository,
+        base_ref: &str,
+        pathspec: Option<&str>,
+    ) -> Result<Diff<'r>, GitError> {
+        let invalid = || GitError::InvalidRef(base_ref.to_string());
+        let base = repo
+            .revparse_single(base_ref)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(|_| invalid())?;
+        let head = repo.head()?.peel_to_commit()?;
+        let merge_base = repo
+            .merge_base(base.id(), head.id())
+            .and_then(|id| repo.find_commit(id))
+            .map_err(|_| invalid())?;
+
+        let mut diff_opts = DiffOptions::new();
+        if let Some(path) = pathspec {
+            diff_opts.pathspec(path);
+        }
+        Ok(repo.diff_tree_to_tree(
+            Some(&merge_base.tree()?),
+            Some(&head.tree()?),
+            Some(&mut diff_opts),
+        )?)
+    }
+
+    /// Get diff content for a specific file (vs `base_ref`, HEAD if `None`)
+    pub fn get_file_diff(
+        worktree_path: &Path,
+        file_path: &str,
+        base_ref: Option<&str>,
+    ) -> Result<Vec<DiffLine>, GitError> {
         let repo = Repository::open(worktree_path)?;
         let mut lines = Vec::new();
 
@@ -174,22 +307,133 @@ impl DiffOps {
             }
         }
 
-        // Get HEAD tree
-        let head_tree = match repo.head() {
-            Ok(head) => Some(head.peel_to_tree()?),
-            Err(_) => None,
-        };
+        let base_tree = Self::base_tree(&repo, base_ref)?;
 
         // Get diff for this specific file
         let mut diff_opts = DiffOptions::new();
         diff_opts.pathspec(file_path);
 
         let diff =
-            repo.diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut diff_opts))?;
+            repo.diff_tree_to_workdir_with_index(base_tree.as_ref(), Some(&mut diff_opts))?;
 
-        // Iterate through patches
+        Self::collect_diff_lines(&diff, &mut lines)?;
+
+        Ok(lines)
+    }
+
+    /// Get list of files changed by a commit-ish (e.g. `stash@{0}`)
+    ///
+    /// The revision is compared against its first parent. For stashes, files
+    /// saved from the untracked tree are reported as untracked.
+    pub fn get_revision_diff_files(
+        worktree_path: &Path,
+        revision: &str,
+    ) -> Result<Vec<DiffFileInfo>, GitError> {
+        let repo = Repository::open(worktree_path)?;
+        let mut files = Vec::new();
+
+        let (diff, untracked) = Self::revision_diffs(&repo, revision, None)?;
+        Self::collect_diff_files(&diff, &mut files)?;
+        if let Some(untracked) = untracked {
+            let start = files.len();
+            Self::collect_diff_files(&untracked, &mut files)?;
+            for file in &mut files[start..] {
+                file.status = FileStatus::Untracked;
+            }
+        }
+
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
+
+    /// Get diff content for a file changed by a commit-ish
+    pub fn get_revision_file_diff(
+        worktree_path: &Path,
+        revision: &str,
+        file_path: &str,
+    ) -> Result<Vec<DiffLine>, GitError> {
+        let repo = Repository::open(worktree_path)?;
+        let mut lines = Vec::new();
+
+        let (diff, untracked) = Self::revision_diffs(&repo, revision, Some(file_path))?;
+        Self::collect_diff_lines(&diff, &mut lines)?;
+        if let Some(untracked) = untracked {
+            Self::collect_diff_lines(&untracked, &mut lines)?;
+        }
+
+        Ok(lines)
+    }
+
+    /// Lines `start..start + count` (1-based) of a file as in the worktree, or
+    /// as in `revision` when given
+    pub fn get_file_lines(
+        worktree_path: &Path,
+        file_path: &str,
+        revision: Option<&str>,
+        start: usize,
+        count: usize,
+    ) -> Result<Vec<String>, GitError> {
+        let content = match revision {
+            Some(revision) => {
+                let repo = Repository::open(worktree_path)?;
+                let tree = repo.revparse_single(revision)?.peel_to_tree()?;
+                let blob = tree
+                    .get_path(Path::new(file_path))?
+                    .to_object(&repo)?
+                    .peel_to_blob()?;
+                String::from_utf8_lossy(blob.content()).to_string()
+            }
+            None => {
+                let bytes = std::fs::read(worktree_path.join(file_path))?;
+                String::from_utf8_lossy(&bytes).to_string()
+            }
+        };
+
+        Ok(content
+            .lines()
+            .skip(start.saturating_sub(1))
+            .take(count)
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Diff a revision against its first parent, plus its untracked tree for stashes
+    fn revision_diffs<'r>(
+        repo: &'r Repository,
+        revision: &str,
+        pathspec: Option<&str>,
+    ) -> Result<(Diff<'r>, Option<Diff<'r>>), GitError> {
+        let commit = repo.revparse_single(revision)?.peel_to_commit()?;
+        let base = match commit.parent(0) {
+            Ok(parent) => Some(parent.tree()?),
+            Err(_) => None,
+        };
+
+        let options = || {
+            let mut diff_opts = DiffOptions::new();
+            if let Some(path) = pathspec {
+                diff_opts.pathspec(path);
+            }
+            diff_opts
+        };
+
+        let diff =
+            repo.diff_tree_to_tree(base.as_ref(), Some(&commit.tree()?), Some(&mut options()))?;
+        // Stashes made with untracked files keep them in a third parent
+        let untracked = match commit.parent(2) {
+            Ok(parent) => {
+                Some(repo.diff_tree_to_tree(None, Some(&parent.tree()?), Some(&mut options()))?)
+            }
+            Err(_) => None,
+        };
+
+        Ok((diff, untracked))
+    }
+
+    /// Collect hunk headers and lines from every patch in a diff
+    fn collect_diff_lines(diff: &Diff, lines: &mut Vec<DiffLine>) -> Result<(), GitError> {
         for delta_idx in 0..diff.deltas().len() {
-            if let Ok(Some(patch)) = git2::Patch::from_diff(&diff, delta_idx) {
+            if let Ok(Some(patch)) = git2::Patch::from_diff(diff, delta_idx) {
                 // Iterate through hunks
                 for hunk_idx in 0..patch.num_hunks() {
                     let (hunk, _) = patch.hunk(hunk_idx)?;
@@ -228,7 +472,7 @@ impl DiffOps {
             }
         }
 
-        Ok(lines)
+        Ok(())
     }
 
     /// Get diff for an untracked file (show all lines as additions)
@@ -284,7 +528,7 @@ mod tests {
     #[test]
     fn test_empty_repo() {
         let (dir, _repo) = create_test_repo();
-        let files = DiffOps::get_diff_files(dir.path()).unwrap();
+        let files = DiffOps::get_diff_files(dir.path(), None).unwrap();
         assert!(files.is_empty());
     }
 
@@ -295,7 +539,7 @@ mod tests {
         // Create an untracked file
         fs::write(dir.path().join("test.txt"), "hello\n").unwrap();
 
-        let files = DiffOps::get_diff_files(dir.path()).unwrap();
+        let files = DiffOps::get_diff_files(dir.path(), None).unwrap();
         assert_eq!(files.len(), 1);
         assert_eq!(files[0].path, "test.txt");
         assert_eq!(files[0].status, FileStatus::Untracked);
@@ -322,9 +566,155 @@ mod tests {
         // Modify the file
         fs::write(&file_path, "modified\n").unwrap();
 
-        let files = DiffOps::get_diff_files(dir.path()).unwrap();
+        let files = DiffOps::get_diff_files(dir.path(), None).unwrap();
         assert_eq!(files.len(), 1);
         assert_eq!(files[0].path, "test.txt");
         assert_eq!(files[0].status, FileStatus::Modified);
     }
+
+    #[test]
+    fn test_revision_diff_of_stash() {
+        let (dir, mut repo) = create_test_repo();
+        let file_path = dir.path().join("test.txt");
+        fs::write(&file_path, "original\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("test.txt")).unwrap();
+        index.write().unwrap();
+        let sig = repo.signature().unwrap();
+        {
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            repo.commit(Some("HEAD"), &sig, &sig, "Initial", &tree, &[])
+                .unwrap();
+        }
+
+        fs::write(&file_path, "stashed\n").unwrap();
+        fs::write(dir.path().join("new.txt"), "new\n").unwrap();
+        repo.stash_save(&sig, "wip", Some(git2::StashFlags::INCLUDE_UNTRACKED))
+            .unwrap();
+
+        let files = DiffOps::get_revision_diff_files(dir.path(), "stash@{0}").unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "new.txt");
+        assert_eq!(files[0].status, FileStatus::Untracked);
+        assert_eq!(files[1].status, FileStatus::Modified);
+
+        let lines = DiffOps::get_revision_file_diff(dir.path(), "stash@{0}", "test.txt").unwrap();
+        assert!(lines
+            .iter()
+            .any(|l| l.line_type == LineType::Addition && l.content == "stashed"));
+    }
+
+    #[test]
+    fn test_get_file_lines() {
+        let (dir, repo) = create_test_repo();
+        let file_path = dir.path().join("test.txt");
+        fs::write(&file_path, "one\ntwo\nthree\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("test.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = repo.signature().unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Initial", &tree, &[])
+            .unwrap();
+        fs::write(&file_path, "one\nTWO\nthree\nfour\n").unwrap();
+
+        let lines = DiffOps::get_file_lines(dir.path(), "test.txt", None, 2, 2).unwrap();
+        assert_eq!(lines, vec!["TWO", "three"]);
+        // Clamped at the end of the file
+        let lines = DiffOps::get_file_lines(dir.path(), "test.txt", None, 4, 10).unwrap();
+        assert_eq!(lines, vec!["four"]);
+        let lines = DiffOps::get_file_lines(dir.path(), "test.txt", Some("HEAD"), 2, 1).unwrap();
+        assert_eq!(lines, vec!["two"]);
+    }
+
+    #[test]
+    fn test_diff_against_base_ref() {
+        let (dir, repo) = create_test_repo();
+        let sig = repo.signature().unwrap();
+        let commit_file = |name: &str, content: &str, message: &str| {
+            fs::write(dir.path().join(name), content).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new(name)).unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parent = repo.head().ok().map(|h| h.peel_to_commit().unwrap());
+            let parents: Vec<_> = parent.iter().collect();
+            repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+                .unwrap()
+        };
+
+        let base = commit_file("a.txt", "one\n", "Initial");
+        repo.tag_lightweight("v1", &repo.find_object(base, None).unwrap(), false)
+            .unwrap();
+        commit_file("b.txt", "two\n", "Second");
+
+        // Clean against HEAD, but b.txt is new since the tag
+        assert!(DiffOps::get_diff_files(dir.path(), None)
+            .unwrap()
+            .is_empty());
+        for base_ref in ["v1", &base.to_string()] {
+            let files = DiffOps::get_diff_files(dir.path(), Some(base_ref)).unwrap();
+            assert_eq!(files.len(), 1);
+            assert_eq!(files[0].path, "b.txt");
+            assert_eq!(files[0].status, FileStatus::Added);
+        }
+        let lines = DiffOps::get_file_diff(dir.path(), "b.txt", Some("v1")).unwrap();
+        assert!(lines
+            .iter()
+            .any(|l| l.line_type == LineType::Addition && l.content == "two"));
+
+        assert!(matches!(
+            DiffOps::get_diff_files(dir.path(), Some("no-such-ref")),
+            Err(GitError::InvalidRef(r)) if r == "no-such-ref"
+        ));
+    }
+
+    #[test]
+    fn test_branch_diff_since_merge_base() {
+        let (dir, repo) = create_test_repo();
+        let sig = repo.signature().unwrap();
+        let commit_file = |name: &str, content: &str| {
+            fs::write(dir.path().join(name), content).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new(name)).unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parent = repo.head().ok().map(|h| h.peel_to_commit().unwrap());
+            let parents: Vec<_> = parent.iter().collect();
+            repo.commit(Some("HEAD"), &sig, &sig, name, &tree, &parents)
+                .unwrap()
+        };
+
+        let fork = commit_file("a.txt", "one\n");
+        let fork = repo.find_commit(fork).unwrap();
+        repo.branch("main", &fork, true).unwrap();
+        repo.branch("feature", &fork, true).unwrap();
+        repo.set_head("refs/heads/feature").unwrap();
+        commit_file("b.txt", "two\n");
+
+        // A later commit on main is not part of the branch's changes
+        repo.set_head("refs/heads/main").unwrap();
+        commit_file("c.txt", "three\n");
+        repo.set_head("refs/heads/feature").unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        // Uncommitted edits aren't either
+        fs::write(dir.path().join("a.txt"), "edited\n").unwrap();
+
+        let (base, files) = DiffOps::get_branch_diff_files(dir.path(), None).unwrap();
+        assert_eq!(base, "main");
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "b.txt");
+        assert_eq!(files[0].status, FileStatus::Added);
+
+        let lines = DiffOps::get_branch_file_diff(dir.path(), "main", "b.txt").unwrap();
+        assert!(lines
+            .iter()
+            .any(|l| l.line_type == LineType::Addition && l.content == "two"));
+
+        assert!(matches!(
+            DiffOps::get_branch_diff_files(dir.path(), Some("no-such-ref")),
+            Err(GitError::InvalidRef(_))
+        ));
+    }
 }
@@ -0,0 +1,155 @@
+//! Extra environment variables for session processes
+//!
+//! A repo holds an `env` map for all its sessions and one per worktree,
+//! set through `SetRepoEnv`. When a session's process is spawned the
+//! worktree's variables are layered over the repo's, and `${VAR}` in their
+//! values is expanded from the daemon's environment.
+
+use crate::error::EnvError;
+use crate::repo::Repo;
+
+/// Variables sessions may not override: the terminal type amux emulates
+/// and the dynamic loader's injection hooks
+const BLOCKED_VARS: &[&str] = &[
+    "TERM",
+    "LD_PRELOAD",
+    "LD_LIBRARY_PATH",
+    "LD_AUDIT",
+    "DYLD_INSERT_LIBRARIES",
+    "DYLD_LIBRARY_PATH",
+    "DYLD_FRAMEWORK_PATH",
+];
+
+/// Check a variable before it's stored
+///
+/// Variables the value references don't need to be set yet; that is only
+/// checked when a session starts.
+pub fn validate(name: &str, value: &str) -> Result<(), EnvError> {
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(EnvError::InvalidName(name.to_string()));
+    }
+    if BLOCKED_VARS.contains(&name) {
+        return Err(EnvError::Blocked(name.to_string()));
+    }
+    expand(name, value, |_| Some(String::new())).map(|_| ())
+}
+
+/// Replace each `${VAR}` in the value of `name` with `lookup(VAR)`
+///
+/// A `$` not followed by `{` is kept as is.
+pub fn expand(
+    name: &str,
+    value: &str,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<String, EnvError> {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find('}')
+            .ok_or_else(|| EnvError::Unterminated(name.to_string()))?;
+        let var = &after[..end];
+        let expanded = lookup(var).ok_or_else(|| EnvError::Undefined {
+            name: name.to_string(),
+            var: var.to_string(),
+        })?;
+        out.push_str(&expanded);
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Variables to set for a session in `branch` of `repo`, expanded
+pub fn session_env(repo: &Repo, branch: &str) -> Result<Vec<(String, String)>, EnvError> {
+    let mut vars = repo.env.clone();
+    if let Some(worktree) = repo.worktree_env.get(branch) {
+        vars.extend(worktree.clone());
+    }
+    vars.into_iter()
+        .filter(|(name, _)| {
+            // repos.json may have been edited by hand
+            let blocked = BLOCKED_VARS.contains(&name.as_str());
+            if blocked {
+                tracing::warn!("Ignoring blocked environment variable {}", name);
+            }
+            !blocked
+        })
+        .map(|(name, value)| {
+            let value = expand(&name, &value, |var| std::env::var(var).ok())?;
+            Ok((name, value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(var: &str) -> Option<String> {
+        (var == "HOME").then(|| "/home/me".to_string())
+    }
+
+    #[test]
+    fn test_expand() {
+        assert_eq!(
+            expand("P", "${HOME}/bin:$PATH", lookup).unwrap(),
+            "/home/me/bin:$PATH"
+        );
+        assert_eq!(expand("P", "plain", lookup).unwrap(), "plain");
+        assert!(matches!(
+            expand("P", "${NOPE}", lookup),
+            Err(EnvError::Undefined { var, .. }) if var == "NOPE"
+        ));
+        assert!(matches!(
+            expand("P", "${HOME", lookup),
+            Err(EnvError::Unterminated(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate("ANTHROPIC_BASE_URL", "http://localhost:8080").is_ok());
+        assert!(validate("PATH", "${HOME}/bin:${PATH}").is_ok());
+        assert!(matches!(
+            validate("LD_PRELOAD", "x.so"),
+            Err(EnvError::Blocked(_))
+        ));
+        assert!(matches!(validate("1X", ""), Err(EnvError::InvalidName(_))));
+        assert!(matches!(validate("A=B", ""), Err(EnvError::InvalidName(_))));
+        assert!(matches!(
+            validate("A", "${"),
+            Err(EnvError::Unterminated(_))
+        ));
+    }
+
+    #[test]
+    fn test_session_env_layers_worktree_over_repo() {
+        let mut repo: Repo =
+            serde_json::from_str(r#"{"id":"a","name":"a","path":"/tmp/a"}"#).unwrap();
+        repo.env.insert("A".to_string(), "repo".to_string());
+        repo.env.insert("B".to_string(), "repo".to_string());
+        repo.env.insert("TERM".to_string(), "dumb".to_string());
+        repo.worktree_env
+            .entry("dev".to_string())
+            .or_default()
+            .insert("B".to_string(), "worktree".to_string());
+
+        let vars = session_env(&repo, "dev").unwrap();
+        assert_eq!(
+            vars,
+            vec![
+                ("A".to_string(), "repo".to_string()),
+                ("B".to_string(), "worktree".to_string()),
+            ]
+        );
+        assert_eq!(session_env(&repo, "main").unwrap()[1].1, "repo");
+    }
+}
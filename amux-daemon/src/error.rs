@@ -6,6 +6,9 @@ use thiserror::Error;
 /// Errors that can occur in repository operations
 #[derive(Debug, Error)]
 pub enum RepoError {
+    #[error("path does not exist: {0}")]
+    PathNotFound(PathBuf),
+
     #[error("path is not a git repository: {0}")]
     NotAGitRepo(PathBuf),
 
@@ -50,6 +53,12 @@ pub enum GitError {
     #[error("cannot delete branch '{branch}': {reason}")]
     CannotDeleteBranch { branch: String, reason: String },
 
+    #[error("cannot rename branch '{branch}': {reason}")]
+    CannotRenameBranch { branch: String, reason: String },
+
+    #[error("branch '{0}' already exists")]
+    BranchExists(String),
+
     #[error("worktree already exists at: {0}")]
     WorktreeExists(PathBuf),
 
@@ -65,6 +74,49 @@ pub enum GitError {
     #[error("git command failed: {0}")]
     CommandFailed(String),
 
+    #[error("nothing to commit")]
+    NothingToCommit,
+
+    #[error("{hook} hook failed: {output}")]
+    HookFailed { hook: String, output: String },
+
+    #[error("push rejected: {0}")]
+    PushRejected(String),
+
+    #[error("authentication failed: {0}")]
+    AuthFailed(String),
+
+    #[error("worktree has uncommitted changes")]
+    DirtyWorktree,
+
+    #[error("no local changes to stash")]
+    NothingToStash,
+
+    #[error("stash@{{{0}}} not found for this branch")]
+    StashNotFound(usize),
+
+    #[error("'{0}' is not a valid ref")]
+    InvalidRef(String),
+
+    #[error("worktree is on {actual}, not {expected}")]
+    WrongBranch { expected: String, actual: String },
+
+    #[error("no upstream, main or master branch to compare against")]
+    NoBaseBranch,
+
+    #[error("'{0}' is not committed at HEAD")]
+    NotInHead(String),
+
+    #[error("{0} file(s) have unresolved merge conflicts")]
+    UnresolvedConflicts(usize),
+
+    #[error("{path} has {found} conflict(s), expected {expected}; reload it")]
+    ConflictsChanged {
+        path: String,
+        expected: usize,
+        found: usize,
+    },
+
     #[error("{0}")]
     Custom(String),
 }
@@ -98,6 +150,9 @@ pub enum SessionError {
 
     #[error("failed to parse session metadata: {0}")]
     ParseMeta(#[source] serde_json::Error),
+
+    #[error("not a directory inside the worktree: {0}")]
+    InvalidCwd(String),
 }
 
 /// Errors that can occur in PTY operations
@@ -125,6 +180,22 @@ pub enum PtyError {
     ProcessExited,
 }
 
+/// Errors in the extra environment variables of sessions
+#[derive(Debug, Error)]
+pub enum EnvError {
+    #[error("invalid environment variable name: {0:?}")]
+    InvalidName(String),
+
+    #[error("environment variable {0} can't be set for sessions")]
+    Blocked(String),
+
+    #[error("unterminated ${{ in the value of {0}")]
+    Unterminated(String),
+
+    #[error("the value of {name} uses ${{{var}}}, which is not set in the daemon's environment")]
+    Undefined { name: String, var: String },
+}
+
 /// Errors that can occur in persistence operations
 #[derive(Debug, Error)]
 pub enum PersistenceError {
@@ -174,6 +245,9 @@ pub enum DaemonError {
     #[error(transparent)]
     Persistence(#[from] PersistenceError),
 
+    #[error(transparent)]
+    Env(#[from] EnvError),
+
     #[error("internal error: {0}")]
     Internal(String),
 }
@@ -184,18 +258,34 @@ impl From<DaemonError> for tonic::Status {
         match &err {
             DaemonError::Repo(RepoError::NotFound(_))
             | DaemonError::Session(SessionError::NotFound(_))
-            | DaemonError::Git(GitError::BranchNotFound(_)) => {
-                tonic::Status::not_found(err.to_string())
-            }
+            | DaemonError::Git(GitError::BranchNotFound(_))
+            | DaemonError::Git(GitError::StashNotFound(_))
+            | DaemonError::Git(GitError::NotInHead(_))
+            | DaemonError::Git(GitError::NoBaseBranch) => tonic::Status::not_found(err.to_string()),
             DaemonError::Repo(RepoError::AlreadyExists(_))
             | DaemonError::Session(SessionError::AlreadyRunning(_))
-            | DaemonError::Git(GitError::WorktreeExists(_)) => {
+            | DaemonError::Git(GitError::WorktreeExists(_))
+            | DaemonError::Git(GitError::BranchExists(_)) => {
                 tonic::Status::already_exists(err.to_string())
             }
             DaemonError::Repo(RepoError::NotAGitRepo(_))
-            | DaemonError::Git(GitError::CannotDeleteBranch { .. }) => {
+            | DaemonError::Repo(RepoError::PathNotFound(_))
+            | DaemonError::Git(GitError::CannotDeleteBranch { .. })
+            | DaemonError::Git(GitError::CannotRenameBranch { .. })
+            | DaemonError::Git(GitError::NothingToStash)
+            | DaemonError::Git(GitError::InvalidRef(_))
+            | DaemonError::Session(SessionError::InvalidCwd(_))
+            | DaemonError::Env(EnvError::InvalidName(_))
+            | DaemonError::Env(EnvError::Blocked(_))
+            | DaemonError::Env(EnvError::Unterminated(_)) => {
                 tonic::Status::invalid_argument(err.to_string())
             }
+            DaemonError::Env(EnvError::Undefined { .. })
+            | DaemonError::Git(GitError::UnresolvedConflicts(_))
+            | DaemonError::Git(GitError::ConflictsChanged { .. })
+            | DaemonError::Git(GitError::WrongBranch { .. }) => {
+                tonic::Status::failed_precondition(err.to_string())
+            }
             _ => tonic::Status::internal(err.to_string()),
         }
     }
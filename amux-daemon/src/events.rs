@@ -1,8 +1,10 @@
 //! Event broadcasting system for real-time updates
 
+use crate::resource_monitor::ResourceUsage;
 use amux_proto::daemon::{
-    Event, GitStatusChangedEvent, SessionCreatedEvent, SessionDestroyedEvent,
-    SessionNameUpdatedEvent, SessionStatusChangedEvent, WorktreeAddedEvent, WorktreeInfo,
+    Event, GitStatusChangedEvent, SessionActivityEvent, SessionAttachChangedEvent,
+    SessionCreatedEvent, SessionDestroyedEvent, SessionNameUpdatedEvent, SessionStatusChangedEvent,
+    SessionTitleChangedEvent, SessionUsageUpdatedEvent, WorktreeAddedEvent, WorktreeInfo,
     WorktreeRemovedEvent,
 };
 use std::sync::Arc;
@@ -29,6 +31,11 @@ impl EventBroadcaster {
         self.sender.subscribe()
     }
 
+    /// Number of clients subscribed to events
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+
     /// Broadcast an event to all subscribers
     pub fn broadcast(&self, event: Event) {
         // Ignore send errors (no subscribers is fine)
@@ -77,6 +84,61 @@ impl EventBroadcaster {
         });
     }
 
+    /// Emit a session title changed event
+    pub fn emit_session_title_changed(&self, session_id: String, title: String) {
+        self.broadcast(Event {
+            event: Some(amux_proto::daemon::event::Event::SessionTitleChanged(
+                SessionTitleChangedEvent { session_id, title },
+            )),
+        });
+    }
+
+    /// Emit a session resource usage sample
+    pub fn emit_session_usage_updated(&self, session_id: String, usage: ResourceUsage) {
+        self.broadcast(Event {
+            event: Some(amux_proto::daemon::event::Event::SessionUsageUpdated(
+                SessionUsageUpdatedEvent {
+                    session_id,
+                    memory_kb: usage.memory_kb,
+                    cpu_percent: usage.cpu_percent,
+                },
+            )),
+        });
+    }
+
+    /// Emit a change in the number of clients attached to a session
+    pub fn emit_session_attach_changed(&self, session_id: String, attach_count: u32) {
+        self.broadcast(Event {
+            event: Some(amux_proto::daemon::event::Event::SessionAttachChanged(
+                SessionAttachChangedEvent {
+                    session_id,
+                    attach_count,
+                },
+            )),
+        });
+    }
+
+    /// Emit output activity of a session: `bytes` written since its last event
+    pub fn emit_session_activity(
+        &self,
+        session_id: String,
+        repo_id: String,
+        branch: String,
+        bytes: u64,
+    ) {
+        self.broadcast(Event {
+            event: Some(amux_proto::daemon::event::Event::SessionActivity(
+                SessionActivityEvent {
+                    session_id,
+                    repo_id,
+                    branch,
+                    bytes,
+                    at: chrono::Utc::now().timestamp(),
+                },
+            )),
+        });
+    }
+
     /// Emit a session status changed event
     #[allow(dead_code)]
     pub fn emit_session_status_changed(
@@ -4,6 +4,7 @@
 //! changes that affect git status and emit events for real-time UI updates.
 
 use crate::events::EventBroadcaster;
+use crate::worktree_status::WorktreeStatusCache;
 use anyhow::Result;
 use notify::{RecursiveMode, Watcher};
 use notify_debouncer_full::{new_debouncer, DebouncedEvent, Debouncer, FileIdMap};
@@ -31,6 +32,7 @@ impl GitFileWatcher {
         branch: String,
         worktree_path: PathBuf,
         events: EventBroadcaster,
+        status_cache: WorktreeStatusCache,
     ) -> Result<Self> {
         let repo_id_clone = repo_id.clone();
         let branch_clone = branch.clone();
@@ -49,6 +51,14 @@ impl GitFileWatcher {
                             debounced_events.len()
                         );
 
+                        // Ref updates (commit, fetch, push) move ahead/behind counts
+                        if debounced_events
+                            .iter()
+                            .any(|e| Self::touches_refs(&e.event))
+                        {
+                            status_cache.invalidate_repo(&repo_id_clone);
+                        }
+
                         // Filter out non-relevant events
                         let relevant_events: Vec<_> = debounced_events
                             .iter()
@@ -63,6 +73,7 @@ impl GitFileWatcher {
                                 relevant_events.len(),
                                 debounced_events.len()
                             );
+                            status_cache.invalidate(&repo_id_clone, &branch_clone);
                             events.emit_git_status_changed(
                                 repo_id_clone.clone(),
                                 branch_clone.clone(),
@@ -134,6 +145,14 @@ impl GitFileWatcher {
         Ok(watcher)
     }
 
+    /// Check if a notify event touches git refs
+    fn touches_refs(event: &notify::Event) -> bool {
+        event.paths.iter().any(|path| {
+            let path_str = path.to_string_lossy();
+            path_str.contains(".git/refs/") || path_str.contains(".git/packed-refs")
+        })
+    }
+
     /// Check if a notify event is relevant for git status
     ///
     /// This filters out temporary files, IDE files, git internals, etc.
@@ -181,14 +200,16 @@ impl GitFileWatcher {
 pub struct WatcherManager {
     watchers: Arc<Mutex<HashMap<String, GitFileWatcher>>>,
     events: EventBroadcaster,
+    status_cache: WorktreeStatusCache,
 }
 
 impl WatcherManager {
     /// Create a new watcher manager
-    pub fn new(events: EventBroadcaster) -> Self {
+    pub fn new(events: EventBroadcaster, status_cache: WorktreeStatusCache) -> Self {
         Self {
             watchers: Arc::new(Mutex::new(HashMap::new())),
             events,
+            status_cache,
         }
     }
 
@@ -203,7 +224,13 @@ impl WatcherManager {
     ) -> Result<()> {
         let key = format!("{}/{}", repo_id, branch);
 
-        let watcher = GitFileWatcher::new(repo_id, branch, worktree_path, self.events.clone())?;
+        let watcher = GitFileWatcher::new(
+            repo_id,
+            branch,
+            worktree_path,
+            self.events.clone(),
+            self.status_cache.clone(),
+        )?;
 
         self.watchers.lock().await.insert(key, watcher);
         Ok(())
@@ -217,6 +244,17 @@ impl WatcherManager {
         }
     }
 
+    /// Stop watching every worktree of a repo
+    pub async fn unwatch_repo(&self, repo_id: &str) {
+        self.watchers.lock().await.retain(|key, watcher| {
+            let keep = watcher.repo_id != repo_id;
+            if !keep {
+                debug!("Stopped watching {}", key);
+            }
+            keep
+        });
+    }
+
     /// Stop all watchers
     #[allow(dead_code)]
     pub async fn stop_all(&self) {
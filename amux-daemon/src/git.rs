@@ -1,7 +1,10 @@
 //! Git operations wrapper
 
+use crate::conflict::{ConflictedFile, Resolution};
+use crate::diff::{DiffOps, DiffStats};
 use crate::error::GitError;
 use git2::Repository;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
@@ -85,6 +88,35 @@ impl GitOps {
         Ok(result)
     }
 
+    /// List branches (local first, then remote-tracking) and tags, each sorted
+    pub fn list_refs(repo: &Repository) -> Result<(Vec<String>, Vec<String>), GitError> {
+        let mut branches = Self::list_branches(repo)?;
+        branches.sort();
+
+        let mut remotes = Vec::new();
+        for branch in repo.branches(Some(git2::BranchType::Remote))? {
+            let (branch, _) = branch?;
+            if let Some(name) = branch.name()? {
+                // Skip symbolic refs like "origin/HEAD"
+                if !name.ends_with("/HEAD") {
+                    remotes.push(name.to_string());
+                }
+            }
+        }
+        remotes.sort();
+        branches.extend(remotes);
+
+        let mut tags: Vec<String> = repo
+            .tag_names(None)?
+            .iter()
+            .flatten()
+            .map(String::from)
+            .collect();
+        tags.sort();
+
+        Ok((branches, tags))
+    }
+
     /// Get current branch name
     pub fn current_branch(repo: &Repository) -> Result<String, GitError> {
         let head = repo.head()?;
@@ -163,9 +195,7 @@ impl GitOps {
             .and_then(|n| n.to_str())
             .unwrap_or("unknown");
 
-        let amux_dir = dirs::home_dir()
-            .ok_or_else(|| GitError::Custom("Cannot find home directory".to_string()))?
-            .join(".amux")
+        let amux_dir = crate::state::AppState::data_dir()
             .join("repos")
             .join(repo_name);
 
@@ -247,6 +277,34 @@ impl GitOps {
         Ok(())
     }
 
+    /// Prune worktrees whose working directory no longer exists
+    /// (`git worktree prune`). Locked worktrees are kept. Returns the branches
+    /// of the pruned worktrees and the errors of those that couldn't be.
+    pub fn prune_worktrees(repo: &Repository) -> Result<(Vec<String>, Vec<String>), GitError> {
+        let mut pruned = Vec::new();
+        let mut errors = Vec::new();
+
+        for name in repo.worktrees()?.iter().flatten() {
+            let wt = match repo.find_worktree(name) {
+                Ok(wt) => wt,
+                Err(e) => {
+                    errors.push(format!("{}: {}", name, e.message()));
+                    continue;
+                }
+            };
+            if wt.validate().is_ok() || !wt.is_prunable(None).unwrap_or(false) {
+                continue;
+            }
+            let branch = Self::worktree_branch(&wt).unwrap_or_else(|_| name.to_string());
+            match wt.prune(None) {
+                Ok(()) => pruned.push(branch),
+                Err(e) => errors.push(format!("{}: {}", name, e.message())),
+            }
+        }
+
+        Ok((pruned, errors))
+    }
+
     /// Find worktree path for a branch
     pub fn find_worktree_path(repo: &Repository, branch: &str) -> Option<PathBuf> {
         let worktrees = Self::list_worktrees(repo).ok()?;
@@ -287,6 +345,102 @@ impl GitOps {
         branch_ref.delete()?;
         Ok(())
     }
+
+    /// Rename a local branch, moving its worktree along with it
+    ///
+    /// A worktree in the amux layout (directory named after the branch) is
+    /// moved to the directory for the new name, and its git admin entry is
+    /// renamed to match. Returns the worktree path after the rename, if the
+    /// branch has a worktree.
+    pub fn rename_branch(
+        repo: &Repository,
+        old: &str,
+        new: &str,
+    ) -> Result<Option<PathBuf>, GitError> {
+        let cannot = |reason: &str| GitError::CannotRenameBranch {
+            branch: old.to_string(),
+            reason: reason.to_string(),
+        };
+
+        if !git2::Branch::name_is_valid(new)? {
+            return Err(cannot(&format!("'{}' is not a valid branch name", new)));
+        }
+        if repo.find_branch(new, git2::BranchType::Local).is_ok() {
+            return Err(GitError::BranchExists(new.to_string()));
+        }
+        let mut branch_ref = repo
+            .find_branch(old, git2::BranchType::Local)
+            .map_err(|_| GitError::BranchNotFound(old.to_string()))?;
+
+        let worktrees = Self::list_worktrees(repo)?;
+        if worktrees.iter().any(|wt| wt.is_main && wt.branch == old)
+            || Self::default_branch(repo).as_deref() == Some(old)
+        {
+            return Err(cannot("it is the default branch"));
+        }
+
+        // libgit2 also repoints the HEAD of the worktree that has it checked out
+        branch_ref.rename(new, false)?;
+
+        let Some(wt) = worktrees.into_iter().find(|wt| wt.branch == old) else {
+            return Ok(None);
+        };
+        let old_name = old.replace('/', "-");
+        let new_name = new.replace('/', "-");
+        if old_name == new_name || repo.find_worktree(&old_name).is_err() {
+            return Ok(Some(wt.path));
+        }
+
+        let new_path = match wt.path.file_name() {
+            Some(name) if name == old_name.as_str() => wt.path.with_file_name(&new_name),
+            _ => wt.path.clone(),
+        };
+        Self::move_worktree(repo, &old_name, &new_name, &wt.path, &new_path)?;
+        Ok(Some(new_path))
+    }
+
+    /// Move a linked worktree and rename its admin dir under `.git/worktrees`
+    fn move_worktree(
+        repo: &Repository,
+        old_name: &str,
+        new_name: &str,
+        old_path: &Path,
+        new_path: &Path,
+    ) -> Result<(), GitError> {
+        let admin_dir = repo.path().join("worktrees");
+        let (old_admin, new_admin) = (admin_dir.join(old_name), admin_dir.join(new_name));
+        if new_admin.exists() {
+            return Err(GitError::WorktreeExists(new_admin));
+        }
+        if new_path != old_path && new_path.exists() {
+            return Err(GitError::WorktreeExists(new_path.to_path_buf()));
+        }
+
+        if new_path != old_path {
+            std::fs::rename(old_path, new_path)?;
+        }
+        std::fs::rename(&old_admin, &new_admin)?;
+
+        // Both sides of the link store absolute paths to each other
+        std::fs::write(
+            new_path.join(".git"),
+            format!("gitdir: {}\n", new_admin.display()),
+        )?;
+        std::fs::write(
+            new_admin.join("gitdir"),
+            format!("{}\n", new_path.join(".git").display()),
+        )?;
+        Ok(())
+    }
+
+    /// Branch `origin/HEAD` points to, if the remote has one
+    fn default_branch(repo: &Repository) -> Option<String> {
+        let head = repo.find_reference("refs/remotes/origin/HEAD").ok()?;
+        let target = head.symbolic_target()?;
+        target
+            .strip_prefix("refs/remotes/origin/")
+            .map(str::to_string)
+    }
 }
 
 /// Information about a worktree
@@ -297,6 +451,69 @@ pub struct WorktreeInfo {
     pub is_main: bool,
 }
 
+/// How `pull_worktree` integrates upstream changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PullStrategy {
+    Merge,
+    Rebase,
+    FfOnly,
+}
+
+/// Result of `pull_worktree`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PullOutcome {
+    /// HEAD moved; carries a short description (e.g. "abc1234..def5678")
+    Updated(String),
+    UpToDate,
+    /// Merge/rebase stopped with these conflicted paths
+    Conflicts(Vec<String>),
+}
+
+/// `PullOutcome` plus what happened to stashed local changes, if any
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullReport {
+    pub outcome: PullOutcome,
+    pub stash_note: Option<String>,
+}
+
+/// A stash entry made on a worktree's branch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StashEntry {
+    /// Position in the repository-wide stash list (`stash@{index}`)
+    pub index: usize,
+    /// Stash message without the "On <branch>:" prefix
+    pub message: String,
+    pub oid: git2::Oid,
+    /// Unix timestamp of the stash commit
+    pub time: i64,
+}
+
+/// Result of applying or popping a stash
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StashApplyOutcome {
+    Applied,
+    /// Stash could not be applied cleanly; it is kept in the stash list
+    Conflicts(Vec<String>),
+}
+
+/// A worktree file with unresolved merge conflicts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictFileInfo {
+    pub path: String,
+    /// Conflict marker hunks left in the file (0 if the markers were
+    /// removed but the file isn't staged yet)
+    pub conflicts: usize,
+}
+
+/// Dirty flag, upstream divergence and size of the changes of a worktree
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WorktreeSummary {
+    pub dirty: bool,
+    pub ahead: u32,
+    pub behind: u32,
+    pub diff_stats: DiffStats,
+}
+
 /// File status in git
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GitFileStatus {
@@ -305,6 +522,9 @@ pub enum GitFileStatus {
     Deleted,
     Renamed,
     Untracked,
+    Ignored,
+    SubmoduleModified,
+    SubmoduleUninitialized,
 }
 
 /// A file with its git status
@@ -320,23 +540,49 @@ pub struct GitStatusResult {
     pub staged: Vec<GitStatusFile>,
     pub unstaged: Vec<GitStatusFile>,
     pub untracked: Vec<GitStatusFile>,
+    pub ignored: Vec<GitStatusFile>,
+    pub submodules: Vec<GitStatusFile>,
 }
 
 impl GitOps {
     /// Get the git status for a repository (worktree)
-    pub fn get_status(repo: &Repository) -> Result<GitStatusResult, GitError> {
+    ///
+    /// Ignored files are only listed when `include_ignored` is set; ignored
+    /// directories are reported once rather than file by file. Submodules are
+    /// reported separately instead of as modified or untracked paths.
+    pub fn get_status(
+        repo: &Repository,
+        include_ignored: bool,
+    ) -> Result<GitStatusResult, GitError> {
         let mut opts = git2::StatusOptions::new();
         opts.include_untracked(true)
             .recurse_untracked_dirs(true)
-            .include_ignored(false);
+            .include_ignored(include_ignored)
+            .recurse_ignored_dirs(false);
 
         let statuses = repo.statuses(Some(&mut opts))?;
-        let mut result = GitStatusResult::default();
+        let submodule_paths: HashSet<String> = repo
+            .submodules()?
+            .iter()
+            .map(|s| s.path().to_string_lossy().into_owned())
+            .collect();
+        let mut result = GitStatusResult {
+            submodules: Self::submodule_status(repo)?,
+            ..Default::default()
+        };
 
         for entry in statuses.iter() {
             let path = entry.path().unwrap_or("").to_string();
             let status = entry.status();
 
+            if status.is_ignored() {
+                result.ignored.push(GitStatusFile {
+                    path,
+                    status: GitFileStatus::Ignored,
+                });
+                continue;
+            }
+
             // Check INDEX status (staged)
             if status.is_index_new() {
                 result.staged.push(GitStatusFile {
@@ -360,6 +606,11 @@ impl GitOps {
                 });
             }
 
+            // Submodule working trees are covered by `submodules`
+            if submodule_paths.contains(&path) {
+                continue;
+            }
+
             // Check WT status (unstaged/untracked)
             if status.is_wt_new() {
                 result.untracked.push(GitStatusFile {
@@ -387,6 +638,33 @@ impl GitOps {
         Ok(result)
     }
 
+    /// Submodules whose checkout is modified or missing (clean ones are omitted)
+    fn submodule_status(repo: &Repository) -> Result<Vec<GitStatusFile>, GitError> {
+        let mut result = Vec::new();
+        for submodule in repo.submodules()? {
+            let Some(name) = submodule.name() else {
+                continue;
+            };
+            let status = repo.submodule_status(name, git2::SubmoduleIgnore::None)?;
+            let status = if status.is_wd_uninitialized() {
+                GitFileStatus::SubmoduleUninitialized
+            } else if status.is_wd_modified()
+                || status.contains(git2::SubmoduleStatus::WD_INDEX_MODIFIED)
+                || status.is_wd_wd_modified()
+                || status.is_wd_untracked()
+            {
+                GitFileStatus::SubmoduleModified
+            } else {
+                continue;
+            };
+            result.push(GitStatusFile {
+                path: submodule.path().to_string_lossy().into_owned(),
+                status,
+            });
+        }
+        Ok(result)
+    }
+
     /// Stage a file (add to index)
     pub fn stage_file(repo: &Repository, path: &str) -> Result<(), GitError> {
         let mut index = repo.index()?;
@@ -473,6 +751,718 @@ impl GitOps {
         Ok(())
     }
 
+    /// Commit id a branch points at (`HEAD` for a detached main worktree)
+    pub fn branch_head_sha(repo: &Repository, branch: &str) -> Option<String> {
+        let spec = if branch == "HEAD" {
+            "HEAD".to_string()
+        } else {
+            format!("refs/heads/{}", branch)
+        };
+        let commit = repo.revparse_single(&spec).ok()?.peel_to_commit().ok()?;
+        Some(commit.id().to_string())
+    }
+
+    /// Summarize a worktree: uncommitted changes and commits ahead/behind upstream
+    ///
+    /// Ahead/behind are zero when the branch has no upstream.
+    pub fn worktree_summary(worktree_path: &Path) -> Result<WorktreeSummary, GitError> {
+        let repo = Self::open(worktree_path)?;
+
+        let mut opts = git2::StatusOptions::new();
+        opts.include_untracked(true)
+            .recurse_untracked_dirs(false)
+            .include_ignored(false);
+        let dirty = !repo.statuses(Some(&mut opts))?.is_empty();
+
+        let (ahead, behind) = Self::upstream_ahead_behind(&repo)?.unwrap_or((0, 0));
+        let diff_stats = if dirty {
+            DiffOps::worktree_stats(&repo)?
+        } else {
+            DiffStats::default()
+        };
+
+        Ok(WorktreeSummary {
+            dirty,
+            ahead: ahead as u32,
+            behind: behind as u32,
+            diff_stats,
+        })
+    }
+
+    /// Count commits HEAD is ahead/behind its upstream, if one is configured
+    fn upstream_ahead_behind(repo: &Repository) -> Result<Option<(usize, usize)>, GitError> {
+        let head = match repo.head() {
+            Ok(head) if head.is_branch() => head,
+            // Unborn or detached HEAD has no upstream
+            _ => return Ok(None),
+        };
+        let Some(name) = head.shorthand() else {
+            return Ok(None);
+        };
+        let branch = repo.find_branch(name, git2::BranchType::Local)?;
+        let Ok(upstream) = branch.upstream() else {
+            return Ok(None);
+        };
+
+        match (head.target(), upstream.get().target()) {
+            (Some(local), Some(remote)) => Ok(Some(repo.graph_ahead_behind(local, remote)?)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Commit the index with a message, running the pre-commit hook first
+    ///
+    /// Returns the new commit id; an in-progress merge is concluded with a
+    /// merge commit. Fails with `NothingToCommit` when the index matches HEAD
+    /// and with `UnresolvedConflicts` while it has conflicts.
+    pub fn commit_index(repo: &Repository, message: &str) -> Result<git2::Oid, GitError> {
+        let mut index = repo.index()?;
+        if index.has_conflicts() {
+            let paths = Self::conflicted_paths(&index)?;
+            return Err(GitError::UnresolvedConflicts(paths.len()));
+        }
+        let tree_id = index.write_tree()?;
+        let parent = match repo.head() {
+            Ok(head) => Some(head.peel_to_commit()?),
+            // Unborn branch - first commit
+            Err(e) if e.code() == git2::ErrorCode::UnbornBranch => None,
+            Err(e) => return Err(e.into()),
+        };
+        // Concluding a merge: MERGE_HEAD commits become extra parents
+        let merge_heads: Vec<git2::Oid> = if repo.state() == git2::RepositoryState::Merge {
+            std::fs::read_to_string(repo.path().join("MERGE_HEAD"))?
+                .lines()
+                .filter_map(|line| git2::Oid::from_str(line.trim()).ok())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        if merge_heads.is_empty() && parent.as_ref().map(|p| p.tree_id()) == Some(tree_id) {
+            return Err(GitError::NothingToCommit);
+        }
+
+        Self::run_hook(repo, "pre-commit", &[], None)?;
+        // The hook may have modified the index
+        index.read(true)?;
+        let tree = repo.find_tree(index.write_tree()?)?;
+
+        let sig = repo.signature()?;
+        let merged = merge_heads
+            .iter()
+            .map(|oid| repo.find_commit(*oid))
+            .collect::<Result<Vec<_>, _>>()?;
+        let parents: Vec<&git2::Commit> = parent.iter().chain(&merged).collect();
+        let oid = repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)?;
+        if !merged.is_empty() {
+            repo.cleanup_state()?;
+        }
+        Ok(oid)
+    }
+
+    /// Push a branch with libgit2, setting its upstream if missing
+    ///
+    /// `remote_name` falls back to the configured push remote
+    /// (`branch.<name>.pushRemote`, `remote.pushDefault`, `branch.<name>.remote`),
+    /// then "origin". Credentials come from ssh-agent, git credential helpers or
+    /// the default system credentials.
+    pub fn push_branch(
+        repo: &Repository,
+        branch: &str,
+        remote_name: Option<&str>,
+    ) -> Result<String, GitError> {
+        let config = repo.config()?;
+        let remote_name = match remote_name.filter(|r| !r.is_empty()) {
+            Some(name) => name.to_string(),
+            None => [
+                format!("branch.{}.pushRemote", branch),
+                "remote.pushDefault".to_string(),
+                format!("branch.{}.remote", branch),
+            ]
+            .iter()
+            .find_map(|key| config.get_string(key).ok())
+            .unwrap_or_else(|| "origin".to_string()),
+        };
+        let mut remote = repo.find_remote(&remote_name)?;
+        let url = remote.url().unwrap_or_default().to_string();
+        let refspec = format!("refs/heads/{0}:refs/heads/{0}", branch);
+
+        let local_oid = repo.refname_to_id(&format!("refs/heads/{}", branch))?;
+        let remote_oid = repo
+            .refname_to_id(&format!("refs/remotes/{}/{}", remote_name, branch))
+            .unwrap_or_else(|_| git2::Oid::zero());
+        let hook_input = format!(
+            "refs/heads/{0} {1} refs/heads/{0} {2}\n",
+            branch, local_oid, remote_oid
+        );
+        Self::run_hook(repo, "pre-push", &[&remote_name, &url], Some(&hook_input))?;
+
+        let mut rejection: Option<String> = None;
+        let mut callbacks = Self::credential_callbacks(config);
+        callbacks.push_update_reference(|_refname, status| {
+            if let Some(msg) = status {
+                rejection = Some(msg.to_string());
+            }
+            Ok(())
+        });
+
+        let mut opts = git2::PushOptions::new();
+        opts.remote_callbacks(callbacks);
+        remote
+            .push(&[&refspec], Some(&mut opts))
+            .map_err(|e| match e.code() {
+                git2::ErrorCode::NotFastForward => {
+                    GitError::PushRejected("non-fast-forward".to_string())
+                }
+                _ => Self::remote_error(e),
+            })?;
+        drop(opts);
+        if let Some(reason) = rejection {
+            return Err(GitError::PushRejected(reason));
+        }
+
+        // Create the upstream if missing
+        let mut local = repo.find_branch(branch, git2::BranchType::Local)?;
+        if local.upstream().is_err() {
+            let mut config = repo.config()?;
+            config.set_str(&format!("branch.{}.remote", branch), &remote_name)?;
+            config.set_str(
+                &format!("branch.{}.merge", branch),
+                &format!("refs/heads/{}", branch),
+            )?;
+            // Fall back to libgit2 if the config write didn't take
+            if local.upstream().is_err() {
+                let _ = local.set_upstream(Some(&format!("{}/{}", remote_name, branch)));
+            }
+        }
+
+        Ok(format!("Pushed {} to {}", branch, remote_name))
+    }
+
+    /// Remote callbacks that authenticate via ssh-agent, credential helpers
+    /// or default system credentials, trying each at most once
+    fn credential_callbacks<'a>(config: git2::Config) -> git2::RemoteCallbacks<'a> {
+        let mut tried_agent = false;
+        let mut tried_helper = false;
+        let mut callbacks = git2::RemoteCallbacks::new();
+        callbacks.credentials(move |url, username, allowed| {
+            if allowed.contains(git2::CredentialType::USERNAME) {
+                return git2::Cred::username(username.unwrap_or("git"));
+            }
+            if allowed.contains(git2::CredentialType::SSH_KEY) && !tried_agent {
+                tried_agent = true;
+                return git2::Cred::ssh_key_from_agent(username.unwrap_or("git"));
+            }
+            if allowed.contains(git2::CredentialType::USER_PASS_PLAINTEXT) && !tried_helper {
+                tried_helper = true;
+                return git2::Cred::credential_helper(&config, url, username);
+            }
+            if allowed.contains(git2::CredentialType::DEFAULT) {
+                return git2::Cred::default();
+            }
+            Err(git2::Error::new(
+                git2::ErrorCode::Auth,
+                git2::ErrorClass::None,
+                "no usable credentials (tried ssh-agent and credential helpers)",
+            ))
+        });
+        callbacks
+    }
+
+    /// Classify errors from talking to a remote
+    fn remote_error(e: git2::Error) -> GitError {
+        let is_auth = e.code() == git2::ErrorCode::Auth
+            || (matches!(e.class(), git2::ErrorClass::Ssh | git2::ErrorClass::Http)
+                && e.message().to_lowercase().contains("auth"));
+        if is_auth {
+            GitError::AuthFailed(e.message().to_string())
+        } else {
+            GitError::Git(e)
+        }
+    }
+
+    /// Fetch every remote of a repository using their configured refspecs
+    ///
+    /// Returns the names of the fetched remotes.
+    pub fn fetch_all(repo: &Repository) -> Result<Vec<String>, GitError> {
+        let names: Vec<String> = repo.remotes()?.iter().flatten().map(String::from).collect();
+        for name in &names {
+            Self::fetch_remote(repo, name)?;
+        }
+        Ok(names)
+    }
+
+    /// Fetch a single remote using its configured refspecs
+    fn fetch_remote(repo: &Repository, name: &str) -> Result<(), GitError> {
+        let mut remote = repo.find_remote(name)?;
+        let mut opts = git2::FetchOptions::new();
+        opts.remote_callbacks(Self::credential_callbacks(repo.config()?));
+        remote
+            .fetch(&[] as &[&str], Some(&mut opts), None)
+            .map_err(Self::remote_error)
+    }
+
+    /// Fetch the branch's upstream and integrate it into the worktree
+    ///
+    /// Refuses to run on a dirty worktree unless `stash_first` is set, in
+    /// which case local changes are stashed and re-applied afterwards. On
+    /// conflicts the merge/rebase is left in progress for the user to resolve.
+    pub fn pull_worktree(
+        repo: &mut Repository,
+        strategy: PullStrategy,
+        stash_first: bool,
+    ) -> Result<PullReport, GitError> {
+        let branch_name = Self::current_branch(repo)?;
+        let branch = repo.find_branch(&branch_name, git2::BranchType::Local)?;
+        let upstream_name = branch
+            .upstream()
+            .ok()
+            .and_then(|u| u.get().name().map(String::from))
+            .ok_or_else(|| GitError::Custom(format!("branch '{}' has no upstream", branch_name)))?;
+        drop(branch);
+
+        let remote = repo
+            .branch_upstream_remote(&format!("refs/heads/{}", branch_name))?
+            .as_str()
+            .map(String::from)
+            .unwrap_or_else(|| "origin".to_string());
+        Self::fetch_remote(repo, &remote)?;
+
+        let dirty = Self::has_tracked_changes(repo)?;
+        if dirty && !stash_first {
+            return Err(GitError::DirtyWorktree);
+        }
+        // Stashes saved meanwhile shift ours, so it's found by its oid
+        let stash = if dirty {
+            let sig = repo.signature()?;
+            Some(repo.stash_save(&sig, "amux: stash before pull", None)?)
+        } else {
+            None
+        };
+
+        let outcome = match Self::integrate_upstream(repo, &upstream_name, strategy) {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                // Nothing changed, so put local changes back
+                if let Some(index) = stash.and_then(|oid| Self::stash_index(repo, oid)) {
+                    let _ = repo.stash_pop(index, None);
+                }
+                return Err(e);
+            }
+        };
+
+        let stash_note = match stash.map(|oid| Self::stash_index(repo, oid)) {
+            None => None,
+            Some(None) => Some("stash of local changes was dropped".to_string()),
+            Some(Some(index)) if matches!(outcome, PullOutcome::Conflicts(_)) => {
+                Some(format!("local changes kept in stash@{{{}}}", index))
+            }
+            Some(Some(index)) => repo
+                .stash_pop(index, None)
+                .err()
+                .map(|e| format!("local changes kept in stash@{{{}}}: {}", index, e.message())),
+        };
+        Ok(PullReport {
+            outcome,
+            stash_note,
+        })
+    }
+
+    /// Current index of the stash entry with `oid`
+    fn stash_index(repo: &mut Repository, oid: git2::Oid) -> Option<usize> {
+        let mut found = None;
+        repo.stash_foreach(|index, _, stash_oid| {
+            if *stash_oid == oid {
+                found = Some(index);
+            }
+            found.is_none()
+        })
+        .ok()?;
+        found
+    }
+
+    /// Merge, rebase or fast-forward HEAD onto an upstream ref
+    fn integrate_upstream(
+        repo: &Repository,
+        upstream_ref: &str,
+        strategy: PullStrategy,
+    ) -> Result<PullOutcome, GitError> {
+        let upstream = repo.find_reference(upstream_ref)?;
+        let theirs = repo.reference_to_annotated_commit(&upstream)?;
+        let (analysis, _) = repo.merge_analysis(&[&theirs])?;
+
+        if analysis.is_up_to_date() {
+            return Ok(PullOutcome::UpToDate);
+        }
+
+        let mut head = repo.head()?;
+        let old = head.target();
+        if analysis.is_fast_forward() {
+            let target = repo.find_object(theirs.id(), None)?;
+            repo.checkout_tree(&target, Some(git2::build::CheckoutBuilder::new().safe()))?;
+            head.set_target(theirs.id(), "pull: fast-forward")?;
+            return Ok(PullOutcome::Updated(Self::range_summary(old, theirs.id())));
+        }
+
+        let sig = repo.signature()?;
+        match strategy {
+            PullStrategy::FfOnly => Err(GitError::Custom(
+                "cannot fast-forward: branch has diverged from upstream".to_string(),
+            )),
+            PullStrategy::Merge => {
+                repo.merge(&[&theirs], None, None)?;
+                let mut index = repo.index()?;
+                if index.has_conflicts() {
+                    return Ok(PullOutcome::Conflicts(Self::conflicted_paths(&index)?));
+                }
+
+                let tree = repo.find_tree(index.write_tree()?)?;
+                let ours = head.peel_to_commit()?;
+                let theirs_commit = repo.find_commit(theirs.id())?;
+                let message = format!(
+                    "Merge {} into {}",
+                    upstream_ref,
+                    head.shorthand().unwrap_or("HEAD")
+                );
+                let new = repo.commit(
+                    Some("HEAD"),
+                    &sig,
+                    &sig,
+                    &message,
+                    &tree,
+                    &[&ours, &theirs_commit],
+                )?;
+                repo.cleanup_state()?;
+                Ok(PullOutcome::Updated(Self::range_summary(old, new)))
+            }
+            PullStrategy::Rebase => {
+                let ours = repo.reference_to_annotated_commit(&head)?;
+                let mut rebase = repo.rebase(Some(&ours), Some(&theirs), None, None)?;
+                while let Some(op) = rebase.next() {
+                    op?;
+                    let index = repo.index()?;
+                    if index.has_conflicts() {
+                        return Ok(PullOutcome::Conflicts(Self::conflicted_paths(&index)?));
+                    }
+                    match rebase.commit(None, &sig, None) {
+                        Ok(_) => {}
+                        // Change already upstream - skip the now-empty commit
+                        Err(e) if e.code() == git2::ErrorCode::Applied => {}
+                        Err(e) => return Err(e.into()),
+                    }
+                }
+                rebase.finish(Some(&sig))?;
+                let new = repo.head()?.target();
+                Ok(PullOutcome::Updated(match new {
+                    Some(new) => Self::range_summary(old, new),
+                    None => "rebased".to_string(),
+                }))
+            }
+        }
+    }
+
+    /// Stash local changes of a worktree
+    ///
+    /// Untracked files are included when `include_untracked` is set.
+    pub fn stash_save(
+        repo: &mut Repository,
+        message: &str,
+        include_untracked: bool,
+    ) -> Result<StashEntry, GitError> {
+        let branch = Self::current_branch(repo)?;
+        let sig = repo.signature()?;
+        let flags = if include_untracked {
+            git2::StashFlags::INCLUDE_UNTRACKED
+        } else {
+            git2::StashFlags::DEFAULT
+        };
+        let message = Some(message.trim()).filter(|m| !m.is_empty());
+        match repo.stash_save2(&sig, message, Some(flags)) {
+            Ok(_) => {}
+            Err(e) if e.code() == git2::ErrorCode::NotFound => {
+                return Err(GitError::NothingToStash)
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        Self::stash_list(repo, &branch)?
+            .into_iter()
+            .find(|s| s.index == 0)
+            .ok_or(GitError::StashNotFound(0))
+    }
+
+    /// List stashes made on `branch`
+    ///
+    /// `refs/stash` is shared by all worktrees of a repository, so entries are
+    /// matched on the branch name git records in the stash message.
+    pub fn stash_list(repo: &mut Repository, branch: &str) -> Result<Vec<StashEntry>, GitError> {
+        let wip_prefix = format!("WIP on {}: ", branch);
+        let on_prefix = format!("On {}: ", branch);
+
+        let mut raw = Vec::new();
+        repo.stash_foreach(|index, message, oid| {
+            raw.push((index, message.to_string(), *oid));
+            true
+        })?;
+
+        let mut entries = Vec::new();
+        for (index, message, oid) in raw {
+            let message = if let Some(rest) = message.strip_prefix(&on_prefix) {
+                rest.to_string()
+            } else if let Some(rest) = message.strip_prefix(&wip_prefix) {
+                format!("WIP {}", rest)
+            } else {
+                continue;
+            };
+            let time = repo.find_commit(oid)?.time().seconds();
+            entries.push(StashEntry {
+                index,
+                message,
+                oid,
+                time,
+            });
+        }
+        Ok(entries)
+    }
+
+    /// Apply a stash of the worktree's branch, dropping it afterwards if `pop`
+    ///
+    /// A stash that conflicts with the worktree is left in place and the
+    /// conflicting paths are reported.
+    pub fn stash_apply(
+        repo: &mut Repository,
+        index: usize,
+        pop: bool,
+    ) -> Result<StashApplyOutcome, GitError> {
+        let entry = Self::find_stash(repo, index)?;
+
+        let mut opts = git2::StashApplyOptions::new();
+        opts.reinstantiate_index();
+        let result = if pop {
+            repo.stash_pop(index, Some(&mut opts))
+        } else {
+            repo.stash_apply(index, Some(&mut opts))
+        };
+
+        match result {
+            Ok(()) => {
+                let index = repo.index()?;
+                if index.has_conflicts() {
+                    return Ok(StashApplyOutcome::Conflicts(Self::conflicted_paths(
+                        &index,
+                    )?));
+                }
+                Ok(StashApplyOutcome::Applied)
+            }
+            Err(e)
+                if matches!(
+                    e.code(),
+                    git2::ErrorCode::Conflict | git2::ErrorCode::MergeConflict
+                ) =>
+            {
+                let index = repo.index()?;
+                let files = if index.has_conflicts() {
+                    Self::conflicted_paths(&index)?
+                } else {
+                    Self::stash_overlap(repo, entry.oid)?
+                };
+                Ok(StashApplyOutcome::Conflicts(files))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Drop a stash of the worktree's branch
+    pub fn stash_drop(repo: &mut Repository, index: usize) -> Result<(), GitError> {
+        Self::find_stash(repo, index)?;
+        repo.stash_drop(index)?;
+        Ok(())
+    }
+
+    /// Files with unresolved merge conflicts
+    ///
+    /// Lists paths the index marks as conflicted plus modified files that
+    /// still contain conflict markers (e.g. staged-over conflicts).
+    pub fn list_conflicts(repo: &Repository) -> Result<Vec<ConflictFileInfo>, GitError> {
+        let workdir = repo.workdir().ok_or(GitError::NoWorkdir)?;
+        let mut opts = git2::StatusOptions::new();
+        opts.include_untracked(false).include_ignored(false);
+
+        let mut files = Vec::new();
+        for entry in repo.statuses(Some(&mut opts))?.iter() {
+            let status = entry.status();
+            if !status.is_conflicted() && !status.is_wt_modified() {
+                continue;
+            }
+            let Some(path) = entry.path() else {
+                continue;
+            };
+            // Unreadable or binary files have no markers to resolve
+            let conflicts = std::fs::read_to_string(workdir.join(path))
+                .map(|content| ConflictedFile::parse(&content).conflict_count())
+                .unwrap_or(0);
+            if status.is_conflicted() || conflicts > 0 {
+                files.push(ConflictFileInfo {
+                    path: path.to_string(),
+                    conflicts,
+                });
+            }
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
+
+    /// Parse the conflict hunks of a worktree file
+    pub fn file_conflicts(repo: &Repository, path: &str) -> Result<ConflictedFile, GitError> {
+        let workdir = repo.workdir().ok_or(GitError::NoWorkdir)?;
+        let content = std::fs::read_to_string(workdir.join(path))?;
+        Ok(ConflictedFile::parse(&content))
+    }
+
+    /// Resolve every conflict hunk of a file, write it and stage it
+    ///
+    /// Fails with `ConflictsChanged` if the file no longer has one hunk per
+    /// resolution (it was edited since the hunks were read).
+    pub fn resolve_conflicts(
+        repo: &Repository,
+        path: &str,
+        resolutions: &[Resolution],
+    ) -> Result<(), GitError> {
+        let file = Self::file_conflicts(repo, path)?;
+        let resolved = file
+            .resolve(resolutions)
+            .ok_or_else(|| GitError::ConflictsChanged {
+                path: path.to_string(),
+                expected: resolutions.len(),
+                found: file.conflict_count(),
+            })?;
+
+        let workdir = repo.workdir().ok_or(GitError::NoWorkdir)?;
+        std::fs::write(workdir.join(path), resolved)?;
+        // Adding the path clears its conflict entries from the index
+        Self::stage_file(repo, path)
+    }
+
+    /// Find a stash by index, refusing stashes made on other branches
+    fn find_stash(repo: &mut Repository, index: usize) -> Result<StashEntry, GitError> {
+        let branch = Self::current_branch(repo)?;
+        Self::stash_list(repo, &branch)?
+            .into_iter()
+            .find(|s| s.index == index)
+            .ok_or(GitError::StashNotFound(index))
+    }
+
+    /// Paths changed by a stash that also have local changes
+    fn stash_overlap(repo: &Repository, stash: git2::Oid) -> Result<Vec<String>, GitError> {
+        let commit = repo.find_commit(stash)?;
+        let base = commit.parent(0)?.tree()?;
+        let diff = repo.diff_tree_to_tree(Some(&base), Some(&commit.tree()?), None)?;
+        let stashed: std::collections::HashSet<_> = diff
+            .deltas()
+            .filter_map(|d| d.new_file().path().map(|p| p.to_string_lossy().to_string()))
+            .collect();
+
+        let mut opts = git2::StatusOptions::new();
+        opts.include_untracked(true).include_ignored(false);
+        let mut paths: Vec<String> = repo
+            .statuses(Some(&mut opts))?
+            .iter()
+            .filter_map(|e| e.path().map(String::from))
+            .filter(|p| stashed.contains(p))
+            .collect();
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// Check for uncommitted changes to tracked files
+    fn has_tracked_changes(repo: &Repository) -> Result<bool, GitError> {
+        let mut opts = git2::StatusOptions::new();
+        opts.include_untracked(false).include_ignored(false);
+        Ok(!repo.statuses(Some(&mut opts))?.is_empty())
+    }
+
+    fn conflicted_paths(index: &git2::Index) -> Result<Vec<String>, GitError> {
+        let mut paths = Vec::new();
+        for conflict in index.conflicts()? {
+            let conflict = conflict?;
+            let entry = conflict.our.or(conflict.their).or(conflict.ancestor);
+            if let Some(entry) = entry {
+                paths.push(String::from_utf8_lossy(&entry.path).to_string());
+            }
+        }
+        paths.sort();
+        paths.dedup();
+        Ok(paths)
+    }
+
+    fn range_summary(old: Option<git2::Oid>, new: git2::Oid) -> String {
+        match old {
+            Some(old) => format!("{:.7}..{:.7}", old, new),
+            None => format!("{:.7}", new),
+        }
+    }
+
+    /// Directory containing git hooks (honors core.hooksPath and worktrees)
+    fn hooks_dir(repo: &Repository) -> Option<PathBuf> {
+        if let Ok(path) = repo.config().and_then(|c| c.get_path("core.hooksPath")) {
+            return Some(match repo.workdir() {
+                Some(workdir) if path.is_relative() => workdir.join(path),
+                _ => path,
+            });
+        }
+
+        let git_dir = repo.path();
+        if repo.is_worktree() {
+            // Hooks live in the common dir, see .git/worktrees/<name>/commondir
+            let common = std::fs::read_to_string(git_dir.join("commondir")).ok()?;
+            let common = git_dir.join(common.trim());
+            return Some(common.join("hooks"));
+        }
+        Some(git_dir.join("hooks"))
+    }
+
+    /// Run a git hook if it exists and is executable
+    fn run_hook(
+        repo: &Repository,
+        name: &str,
+        args: &[&str],
+        stdin: Option<&str>,
+    ) -> Result<(), GitError> {
+        let Some(hook) = Self::hooks_dir(repo).map(|d| d.join(name)) else {
+            return Ok(());
+        };
+        if !is_executable(&hook) {
+            return Ok(());
+        }
+
+        let workdir = repo.workdir().ok_or(GitError::NoWorkdir)?;
+        let mut child = Command::new(&hook)
+            .args(args)
+            .current_dir(workdir)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()?;
+        if let Some(mut pipe) = child.stdin.take() {
+            use std::io::Write;
+            // The hook may exit without reading stdin
+            let _ = pipe.write_all(stdin.unwrap_or_default().as_bytes());
+        }
+        let output = child.wait_with_output()?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            let mut text = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            if text.is_empty() {
+                text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            }
+            Err(GitError::HookFailed {
+                hook: name.to_string(),
+                output: text,
+            })
+        }
+    }
+
     /// Push current branch to remote using system git
     pub fn push(workdir: &Path) -> Result<String, GitError> {
         let output = Command::new("git")
@@ -499,20 +1489,487 @@ impl GitOps {
             Err(GitError::CommandFailed(stderr.trim().to_string()))
         }
     }
+}
 
-    /// Pull (fetch + rebase) from remote using system git
-    pub fn pull(workdir: &Path) -> Result<String, GitError> {
-        let output = Command::new("git")
-            .args(["pull", "--rebase"])
-            .current_dir(workdir)
-            .output()?;
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
 
-        if output.status.success() {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            Ok(stdout.trim().to_string())
-        } else {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            Err(GitError::CommandFailed(stderr.trim().to_string()))
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_test_repo() -> (TempDir, Repository) {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@test.com").unwrap();
+
+        (dir, repo)
+    }
+
+    fn commit_file(repo: &Repository, dir: &Path, name: &str, content: &str) -> git2::Oid {
+        fs::write(dir.join(name), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(name)).unwrap();
+        index.write().unwrap();
+
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = repo.signature().unwrap();
+        let parents: Vec<_> = repo
+            .head()
+            .ok()
+            .and_then(|h| h.peel_to_commit().ok())
+            .into_iter()
+            .collect();
+        let parent_refs: Vec<_> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, "commit", &tree, &parent_refs)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_worktree_summary_dirty() {
+        let (dir, repo) = create_test_repo();
+        commit_file(&repo, dir.path(), "a.txt", "a\n");
+
+        let summary = GitOps::worktree_summary(dir.path()).unwrap();
+        assert_eq!(summary, WorktreeSummary::default());
+
+        fs::write(dir.path().join("b.txt"), "b\n").unwrap();
+        let summary = GitOps::worktree_summary(dir.path()).unwrap();
+        assert!(summary.dirty);
+    }
+
+    #[test]
+    fn test_worktree_summary_diff_stats() {
+        let (dir, repo) = create_test_repo();
+        commit_file(&repo, dir.path(), "a.txt", "one\ntwo\nthree\n");
+
+        fs::write(dir.path().join("a.txt"), "one\n2\nthree\nfour\n").unwrap();
+        fs::write(dir.path().join("b.txt"), "b\nb\n").unwrap();
+        let summary = GitOps::worktree_summary(dir.path()).unwrap();
+        assert_eq!(
+            summary.diff_stats,
+            DiffStats {
+                files_changed: 2,
+                insertions: 4,
+                deletions: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_get_status_ignored_files() {
+        let (dir, repo) = create_test_repo();
+        commit_file(&repo, dir.path(), ".gitignore", "target/\n*.log\n");
+        fs::create_dir(dir.path().join("target")).unwrap();
+        fs::write(dir.path().join("target/out.bin"), "x").unwrap();
+        fs::write(dir.path().join("debug.log"), "x").unwrap();
+        fs::write(dir.path().join("new.txt"), "x").unwrap();
+
+        let status = GitOps::get_status(&repo, false).unwrap();
+        let untracked: Vec<_> = status.untracked.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(untracked, vec!["new.txt"]);
+        assert!(status.ignored.is_empty());
+
+        let status = GitOps::get_status(&repo, true).unwrap();
+        let mut ignored: Vec<_> = status.ignored.iter().map(|f| f.path.as_str()).collect();
+        ignored.sort();
+        assert_eq!(ignored, vec!["debug.log", "target/"]);
+        assert_eq!(status.untracked.len(), 1);
+    }
+
+    #[test]
+    fn test_worktree_summary_ahead_behind() {
+        let (dir, repo) = create_test_repo();
+        let base = commit_file(&repo, dir.path(), "a.txt", "a\n");
+        let branch = GitOps::current_branch(&repo).unwrap();
+
+        // Fake an upstream pointing at the base commit
+        repo.reference("refs/remotes/origin/main", base, true, "test")
+            .unwrap();
+        let mut config = repo.config().unwrap();
+        config
+            .set_str(&format!("branch.{}.remote", branch), "origin")
+            .unwrap();
+        config
+            .set_str(&format!("branch.{}.merge", branch), "refs/heads/main")
+            .unwrap();
+        config
+            .set_str("remote.origin.url", "https://example.com/repo.git")
+            .unwrap();
+        config
+            .set_str("remote.origin.fetch", "+refs/heads/*:refs/remotes/origin/*")
+            .unwrap();
+
+        commit_file(&repo, dir.path(), "b.txt", "b\n");
+        commit_file(&repo, dir.path(), "c.txt", "c\n");
+
+        let summary = GitOps::worktree_summary(dir.path()).unwrap();
+        assert!(!summary.dirty);
+        assert_eq!(summary.ahead, 2);
+        assert_eq!(summary.behind, 0);
+    }
+
+    #[test]
+    fn test_commit_index_nothing_to_commit() {
+        let (dir, repo) = create_test_repo();
+        commit_file(&repo, dir.path(), "a.txt", "a\n");
+
+        let err = GitOps::commit_index(&repo, "empty").unwrap_err();
+        assert!(matches!(err, GitError::NothingToCommit));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_commit_index_pre_commit_hook_failure() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (dir, repo) = create_test_repo();
+        commit_file(&repo, dir.path(), "a.txt", "a\n");
+
+        let hook = repo.path().join("hooks").join("pre-commit");
+        fs::create_dir_all(hook.parent().unwrap()).unwrap();
+        fs::write(&hook, "#!/bin/sh\necho 'lint failed' >&2\nexit 1\n").unwrap();
+        fs::set_permissions(&hook, fs::Permissions::from_mode(0o755)).unwrap();
+
+        fs::write(dir.path().join("b.txt"), "b\n").unwrap();
+        GitOps::stage_all(&repo).unwrap();
+        match GitOps::commit_index(&repo, "add b").unwrap_err() {
+            GitError::HookFailed { hook, output } => {
+                assert_eq!(hook, "pre-commit");
+                assert_eq!(output, "lint failed");
+            }
+            e => panic!("unexpected error: {}", e),
         }
     }
+
+    #[test]
+    fn test_commit_and_push_sets_upstream() {
+        let (dir, repo) = create_test_repo();
+        commit_file(&repo, dir.path(), "a.txt", "a\n");
+        let branch = GitOps::current_branch(&repo).unwrap();
+
+        let remote_dir = TempDir::new().unwrap();
+        Repository::init_bare(remote_dir.path()).unwrap();
+        repo.remote("origin", remote_dir.path().to_str().unwrap())
+            .unwrap();
+
+        fs::write(dir.path().join("b.txt"), "b\n").unwrap();
+        GitOps::stage_all(&repo).unwrap();
+        let oid = GitOps::commit_index(&repo, "add b").unwrap();
+        GitOps::push_branch(&repo, &branch, None).unwrap();
+
+        let remote = Repository::open_bare(remote_dir.path()).unwrap();
+        assert_eq!(
+            remote
+                .refname_to_id(&format!("refs/heads/{}", branch))
+                .unwrap(),
+            oid
+        );
+        let local = repo.find_branch(&branch, git2::BranchType::Local).unwrap();
+        assert!(local.upstream().is_ok());
+        assert_eq!(GitOps::worktree_summary(dir.path()).unwrap().ahead, 0);
+    }
+
+    /// Upstream repo pushed to a bare remote plus a clone tracking it
+    fn create_cloned_pair() -> (TempDir, Repository, TempDir, Repository, TempDir, String) {
+        let (up_dir, upstream) = create_test_repo();
+        commit_file(&upstream, up_dir.path(), "a.txt", "a\n");
+        let branch = GitOps::current_branch(&upstream).unwrap();
+
+        let remote_dir = TempDir::new().unwrap();
+        Repository::init_bare(remote_dir.path()).unwrap();
+        upstream
+            .remote("origin", remote_dir.path().to_str().unwrap())
+            .unwrap();
+        GitOps::push_branch(&upstream, &branch, None).unwrap();
+
+        let clone_dir = TempDir::new().unwrap();
+        let clone =
+            Repository::clone(remote_dir.path().to_str().unwrap(), clone_dir.path()).unwrap();
+        let mut config = clone.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@test.com").unwrap();
+
+        (up_dir, upstream, remote_dir, clone, clone_dir, branch)
+    }
+
+    #[test]
+    fn test_pull_worktree_fast_forward_and_up_to_date() {
+        let (up_dir, upstream, _remote, mut clone, clone_dir, branch) = create_cloned_pair();
+
+        let report = GitOps::pull_worktree(&mut clone, PullStrategy::FfOnly, false).unwrap();
+        assert_eq!(report.outcome, PullOutcome::UpToDate);
+
+        commit_file(&upstream, up_dir.path(), "b.txt", "b\n");
+        GitOps::push_branch(&upstream, &branch, None).unwrap();
+
+        let report = GitOps::pull_worktree(&mut clone, PullStrategy::FfOnly, false).unwrap();
+        assert!(matches!(report.outcome, PullOutcome::Updated(_)));
+        assert!(clone_dir.path().join("b.txt").exists());
+    }
+
+    #[test]
+    fn test_pull_worktree_conflicts_and_dirty() {
+        let (up_dir, upstream, _remote, mut clone, clone_dir, branch) = create_cloned_pair();
+
+        commit_file(&upstream, up_dir.path(), "a.txt", "upstream\n");
+        GitOps::push_branch(&upstream, &branch, None).unwrap();
+
+        // Uncommitted change blocks the pull
+        fs::write(clone_dir.path().join("a.txt"), "local\n").unwrap();
+        let err = GitOps::pull_worktree(&mut clone, PullStrategy::Merge, false).unwrap_err();
+        assert!(matches!(err, GitError::DirtyWorktree));
+
+        // Diverging commit conflicts, and ff-only refuses outright
+        commit_file(&clone, clone_dir.path(), "a.txt", "local\n");
+        assert!(GitOps::pull_worktree(&mut clone, PullStrategy::FfOnly, false).is_err());
+        let report = GitOps::pull_worktree(&mut clone, PullStrategy::Merge, false).unwrap();
+        assert_eq!(
+            report.outcome,
+            PullOutcome::Conflicts(vec!["a.txt".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_pull_worktree_stash_first() {
+        let (up_dir, upstream, _remote, mut clone, clone_dir, branch) = create_cloned_pair();
+        commit_file(&upstream, up_dir.path(), "b.txt", "b\n");
+        GitOps::push_branch(&upstream, &branch, None).unwrap();
+
+        // An older stash stays put while ours is popped
+        fs::write(clone_dir.path().join("a.txt"), "older\n").unwrap();
+        GitOps::stash_save(&mut clone, "older", false).unwrap();
+        fs::write(clone_dir.path().join("a.txt"), "local\n").unwrap();
+
+        let report = GitOps::pull_worktree(&mut clone, PullStrategy::FfOnly, true).unwrap();
+        assert!(matches!(report.outcome, PullOutcome::Updated(_)));
+        assert_eq!(report.stash_note, None);
+        assert_eq!(
+            fs::read_to_string(clone_dir.path().join("a.txt")).unwrap(),
+            "local\n"
+        );
+        let stashes = GitOps::stash_list(&mut clone, &branch).unwrap();
+        assert_eq!(stashes.len(), 1);
+        assert_eq!(stashes[0].message, "older");
+    }
+
+    #[test]
+    fn test_stash_index() {
+        let (dir, mut repo) = create_test_repo();
+        commit_file(&repo, dir.path(), "a.txt", "a\n");
+        let sig = repo.signature().unwrap();
+        fs::write(dir.path().join("a.txt"), "first\n").unwrap();
+        let first = repo.stash_save(&sig, "first", None).unwrap();
+        fs::write(dir.path().join("a.txt"), "second\n").unwrap();
+        let second = repo.stash_save(&sig, "second", None).unwrap();
+
+        assert_eq!(GitOps::stash_index(&mut repo, second), Some(0));
+        assert_eq!(GitOps::stash_index(&mut repo, first), Some(1));
+        repo.stash_drop(1).unwrap();
+        assert_eq!(GitOps::stash_index(&mut repo, first), None);
+    }
+
+    #[test]
+    fn test_pull_worktree_rebase_diverged() {
+        let (up_dir, upstream, _remote, mut clone, clone_dir, branch) = create_cloned_pair();
+
+        let upstream_head = commit_file(&upstream, up_dir.path(), "b.txt", "b\n");
+        GitOps::push_branch(&upstream, &branch, None).unwrap();
+        commit_file(&clone, clone_dir.path(), "c.txt", "c\n");
+
+        let report = GitOps::pull_worktree(&mut clone, PullStrategy::Rebase, false).unwrap();
+        assert!(matches!(report.outcome, PullOutcome::Updated(_)));
+
+        let head = clone.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.parent_id(0).unwrap(), upstream_head);
+        assert!(clone_dir.path().join("b.txt").exists());
+        assert!(clone_dir.path().join("c.txt").exists());
+    }
+
+    #[test]
+    fn test_stash_save_list_and_pop() {
+        let (dir, mut repo) = create_test_repo();
+        commit_file(&repo, dir.path(), "a.txt", "a\n");
+        let branch = GitOps::current_branch(&repo).unwrap();
+
+        let err = GitOps::stash_save(&mut repo, "", false).unwrap_err();
+        assert!(matches!(err, GitError::NothingToStash));
+
+        fs::write(dir.path().join("a.txt"), "changed\n").unwrap();
+        fs::write(dir.path().join("new.txt"), "new\n").unwrap();
+        let entry = GitOps::stash_save(&mut repo, "wip a", true).unwrap();
+        assert_eq!(entry.index, 0);
+        assert_eq!(entry.message, "wip a");
+        assert!(!dir.path().join("new.txt").exists());
+
+        let stashes = GitOps::stash_list(&mut repo, &branch).unwrap();
+        assert_eq!(stashes.len(), 1);
+        assert!(GitOps::stash_list(&mut repo, "other").unwrap().is_empty());
+
+        let outcome = GitOps::stash_apply(&mut repo, 0, true).unwrap();
+        assert_eq!(outcome, StashApplyOutcome::Applied);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("a.txt")).unwrap(),
+            "changed\n"
+        );
+        assert!(dir.path().join("new.txt").exists());
+        assert!(GitOps::stash_list(&mut repo, &branch).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_stash_pop_conflict_keeps_stash() {
+        let (dir, mut repo) = create_test_repo();
+        commit_file(&repo, dir.path(), "a.txt", "a\n");
+        let branch = GitOps::current_branch(&repo).unwrap();
+
+        fs::write(dir.path().join("a.txt"), "stashed\n").unwrap();
+        GitOps::stash_save(&mut repo, "", false).unwrap();
+        fs::write(dir.path().join("a.txt"), "local\n").unwrap();
+
+        let outcome = GitOps::stash_apply(&mut repo, 0, true).unwrap();
+        assert_eq!(
+            outcome,
+            StashApplyOutcome::Conflicts(vec!["a.txt".to_string()])
+        );
+        assert_eq!(GitOps::stash_list(&mut repo, &branch).unwrap().len(), 1);
+        assert!(matches!(
+            GitOps::stash_drop(&mut repo, 3).unwrap_err(),
+            GitError::StashNotFound(3)
+        ));
+    }
+
+    #[test]
+    fn test_resolve_merge_conflicts_then_commit() {
+        let (dir, repo) = create_test_repo();
+        let base = commit_file(&repo, dir.path(), "a.txt", "line\n");
+        let main = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        repo.branch("other", &repo.find_commit(base).unwrap(), false)
+            .unwrap();
+        repo.set_head("refs/heads/other").unwrap();
+        let theirs = commit_file(&repo, dir.path(), "a.txt", "theirs\n");
+        repo.set_head(&format!("refs/heads/{}", main)).unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        commit_file(&repo, dir.path(), "a.txt", "ours\n");
+
+        let theirs = repo.find_annotated_commit(theirs).unwrap();
+        repo.merge(&[&theirs], None, None).unwrap();
+
+        let conflicts = GitOps::list_conflicts(&repo).unwrap();
+        assert_eq!(
+            conflicts,
+            vec![ConflictFileInfo {
+                path: "a.txt".to_string(),
+                conflicts: 1,
+            }]
+        );
+        assert!(matches!(
+            GitOps::commit_index(&repo, "merge").unwrap_err(),
+            GitError::UnresolvedConflicts(1)
+        ));
+        assert!(matches!(
+            GitOps::resolve_conflicts(&repo, "a.txt", &[]).unwrap_err(),
+            GitError::ConflictsChanged { found: 1, .. }
+        ));
+
+        GitOps::resolve_conflicts(&repo, "a.txt", &[Resolution::Both]).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.path().join("a.txt")).unwrap(),
+            "ours\ntheirs\n"
+        );
+        assert!(GitOps::list_conflicts(&repo).unwrap().is_empty());
+        let merge = GitOps::commit_index(&repo, "merge").unwrap();
+        assert_eq!(repo.find_commit(merge).unwrap().parent_count(), 2);
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_prune_worktrees_removes_deleted_directories() {
+        let (dir, repo) = create_test_repo();
+        commit_file(&repo, dir.path(), "a.txt", "a\n");
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+
+        let wt_root = TempDir::new().unwrap();
+        for name in ["kept", "gone"] {
+            let branch_ref = repo.branch(name, &head, false).unwrap().into_reference();
+            let mut opts = git2::WorktreeAddOptions::new();
+            opts.reference(Some(&branch_ref));
+            repo.worktree(name, &wt_root.path().join(name), Some(&opts))
+                .unwrap();
+        }
+        fs::remove_dir_all(wt_root.path().join("gone")).unwrap();
+
+        let (pruned, errors) = GitOps::prune_worktrees(&repo).unwrap();
+        assert_eq!(pruned, vec!["gone".to_string()]);
+        assert!(errors.is_empty());
+
+        let branches: Vec<_> = GitOps::list_worktrees(&repo)
+            .unwrap()
+            .into_iter()
+            .filter(|wt| !wt.is_main)
+            .map(|wt| wt.branch)
+            .collect();
+        assert_eq!(branches, vec!["kept".to_string()]);
+    }
+
+    #[test]
+    fn test_rename_branch_moves_worktree() {
+        let (dir, repo) = create_test_repo();
+        commit_file(&repo, dir.path(), "a.txt", "a\n");
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let main = repo.head().unwrap().shorthand().unwrap().to_string();
+        repo.branch("feature/old", &head, false).unwrap();
+        repo.branch("taken", &head, false).unwrap();
+
+        let wt_root = TempDir::new().unwrap();
+        let old_path = wt_root.path().join("feature-old");
+        let branch_ref = repo
+            .find_branch("feature/old", git2::BranchType::Local)
+            .unwrap()
+            .into_reference();
+        let mut opts = git2::WorktreeAddOptions::new();
+        opts.reference(Some(&branch_ref));
+        repo.worktree("feature-old", &old_path, Some(&opts))
+            .unwrap();
+
+        assert!(matches!(
+            GitOps::rename_branch(&repo, "feature/old", "taken"),
+            Err(GitError::BranchExists(_))
+        ));
+        assert!(matches!(
+            GitOps::rename_branch(&repo, &main, "renamed"),
+            Err(GitError::CannotRenameBranch { .. })
+        ));
+
+        let new_path = GitOps::rename_branch(&repo, "feature/old", "feature/new")
+            .unwrap()
+            .unwrap();
+        assert_eq!(new_path, wt_root.path().join("feature-new"));
+        assert!(!old_path.exists());
+        assert!(repo
+            .find_branch("feature/old", git2::BranchType::Local)
+            .is_err());
+
+        // The moved worktree is still a valid checkout of the renamed branch
+        let wt_repo = Repository::open(&new_path).unwrap();
+        assert_eq!(wt_repo.head().unwrap().shorthand(), Some("feature/new"));
+        let wt = repo.find_worktree("feature-new").unwrap();
+        assert!(wt.validate().is_ok());
+    }
 }
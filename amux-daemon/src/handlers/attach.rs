@@ -3,11 +3,14 @@
 use crate::error::{DaemonError, SessionError};
 use crate::events::EventBroadcaster;
 use crate::persistence;
-use crate::session::SessionStatus;
+use crate::session::{Session, SessionStatus};
 use crate::state::SharedState;
+use crate::terminal_title::{TerminalTitle, TitleChange};
 use amux_proto::daemon::*;
 use std::pin::Pin;
-use tokio::sync::mpsc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc, oneshot};
 use tokio_stream::{wrappers::ReceiverStream, Stream, StreamExt};
 use tonic::{Response, Status, Streaming};
 
@@ -32,7 +35,9 @@ pub async fn attach_session(
 
     // Verify session exists and start if needed (handles restored sessions)
     {
-        let mut state = state.write().await;
+        let mut state_guard = state.write().await;
+        // Borrow fields separately: the session mutably, repos for its env
+        let state = &mut *state_guard;
         // Clone the registry Arc before getting mutable borrow on session
         let registry = state.provider_registry.clone();
         let session = state.sessions.get_mut(&session_id).ok_or_else(|| {
@@ -44,7 +49,12 @@ pub async fn attach_session(
         // Start session if not running
         if session.status() == SessionStatus::Stopped {
             tracing::info!("Starting stopped session: {}", session_id);
-            session.start(&registry).map_err(|e| {
+            let env = match state.repos.get(&session.repo_id) {
+                Some(repo) => crate::env::session_env(repo, &session.branch)
+                    .map_err(|e| Status::from(DaemonError::from(e)))?,
+                None => Vec::new(),
+            };
+            session.start(&registry, &env).map_err(|e| {
                 Status::from(DaemonError::Session(SessionError::Start(e.to_string())))
             })?;
 
@@ -66,82 +76,65 @@ pub async fn attach_session(
         }
     }
 
+    // Subscribe to the session's output, starting its PTY reader if it
+    // isn't running yet. History is captured under the same lock so nothing
+    // is lost or sent twice between the snapshot and the subscription.
+    let (history, mut output_rx, attach_count) = {
+        let mut state_guard = state.write().await;
+        let session = state_guard.sessions.get_mut(&session_id).ok_or_else(|| {
+            Status::from(DaemonError::Session(SessionError::NotFound(
+                session_id.clone(),
+            )))
+        })?;
+
+        let output_rx = session_output(&state, &events, session).subscribe();
+        (
+            session.get_screen_state(),
+            output_rx,
+            session.attach_count(),
+        )
+    };
+    events.emit_session_attach_changed(session_id.clone(), attach_count);
+
     // Create output channel
     let (tx, rx) = mpsc::channel(32);
-    let state_clone = state.clone();
-    let session_id_clone = session_id.clone();
-
-    // Send history buffer first
-    {
-        let state = state_clone.read().await;
-        if let Some(session) = state.sessions.get(&session_id_clone) {
-            let history = session.get_screen_state();
-            if !history.is_empty() {
-                let output = AttachOutput { data: history };
-                let _ = tx.send(Ok(output)).await;
-            }
-        }
+    if !history.is_empty() {
+        let _ = tx.send(Ok(AttachOutput { data: history })).await;
     }
 
-    // Spawn task to read from PTY and send to client
+    // Forward session output to this client until it goes away
+    let (input_done_tx, mut input_done_rx) = oneshot::channel::<()>();
+    let state_clone = state.clone();
+    let session_id_clone = session_id.clone();
     tokio::spawn(async move {
-        let mut buf = [0u8; 4096];
-        let mut save_counter = 0u32;
-        let mut name_check_counter = 0u32;
         loop {
-            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
-
-            // Periodically try to update session name from Claude's first message
-            name_check_counter += 1;
-            if name_check_counter >= 50 {
-                // Check every ~0.5 seconds
-                name_check_counter = 0;
-                let mut state = state_clone.write().await;
-                if let Some(session) = state.sessions.get_mut(&session_id_clone) {
-                    if !session.name_updated_from_provider {
-                        session.update_name_from_provider();
-                        if session.name_updated_from_provider {
-                            // Save updated metadata
-                            let _ = persistence::save_session_meta(session);
-                        }
-                    }
-                }
-            }
-
-            let state = state_clone.read().await;
-            if let Some(session) = state.sessions.get(&session_id_clone) {
-                match session.read(&mut buf) {
-                    Ok(n) if n > 0 => {
-                        // Store output in session buffer
-                        session.process_output(&buf[..n]);
-
-                        let output = AttachOutput {
-                            data: buf[..n].to_vec(),
-                        };
-                        if tx.send(Ok(output)).await.is_err() {
-                            // Client disconnected, save history before exit
-                            let _ = persistence::save_session_history(session);
+            tokio::select! {
+                received = output_rx.recv() => match received {
+                    Ok(data) => {
+                        if tx.send(Ok(AttachOutput { data })).await.is_err() {
                             break;
                         }
-
-                        // Periodically save history (every ~1 second of output)
-                        save_counter += 1;
-                        if save_counter >= 100 {
-                            save_counter = 0;
-                            let _ = persistence::save_session_history(session);
-                        }
                     }
-                    Ok(_) => {}
-                    Err(_) => {
-                        // PTY error, save history before exit
-                        let _ = persistence::save_session_history(session);
-                        break;
+                    // A slow client misses some output rather than stalling the others
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::debug!(
+                            "Attach client of {} lagged, skipped {} chunks",
+                            session_id_clone,
+                            skipped
+                        );
                     }
-                }
-            } else {
-                break;
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                // The client closed its input stream (detached)
+                _ = &mut input_done_rx => break,
             }
         }
+
+        drop(output_rx);
+        let state = state_clone.read().await;
+        if let Some(session) = state.sessions.get(&session_id_clone) {
+            events.emit_session_attach_changed(session_id_clone.clone(), session.attach_count());
+        }
     });
 
     // Spawn task to read from client and write to PTY
@@ -178,7 +171,167 @@ pub async fn attach_session(
                 }
             }
         }
+        let _ = input_done_tx.send(());
     });
 
     Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
 }
+
+/// Chunks buffered per client before it starts missing output
+const OUTPUT_CHANNEL_CAPACITY: usize = 256;
+
+/// Minimum interval between activity events of a session
+const ACTIVITY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Output channel of a running session, starting its PTY reader if needed
+pub fn session_output(
+    state: &SharedState,
+    events: &EventBroadcaster,
+    session: &mut Session,
+) -> broadcast::Sender<Vec<u8>> {
+    if let Some(output) = &session.output {
+        return output.clone();
+    }
+    let (output, _) = broadcast::channel(OUTPUT_CHANNEL_CAPACITY);
+    session.output = Some(output.clone());
+    spawn_output_reader(
+        state.clone(),
+        events.clone(),
+        session.id.clone(),
+        output.clone(),
+    );
+    output
+}
+
+/// Report a held-back title change at `due`, whether or not more output comes
+fn flush_title_at(
+    title: Arc<std::sync::Mutex<TerminalTitle>>,
+    events: EventBroadcaster,
+    session_id: String,
+    due: Instant,
+) {
+    tokio::spawn(async move {
+        tokio::time::sleep_until(due.into()).await;
+        let change = title
+            .lock()
+            .ok()
+            .and_then(|mut title| title.take_change(Instant::now().max(due)));
+        if let Some(TitleChange::Report(title)) = change {
+            events.emit_session_title_changed(session_id, title);
+        }
+    });
+}
+
+/// Read the session's PTY and broadcast its output to attached clients
+///
+/// Keeps reading with no client attached, so background sessions report
+/// activity and their history stays complete. The PTY closing or the
+/// session going away stops it.
+fn spawn_output_reader(
+    state: SharedState,
+    events: EventBroadcaster,
+    session_id: String,
+    output: broadcast::Sender<Vec<u8>>,
+) {
+    tokio::spawn(async move {
+        let mut buf = [0u8; 4096];
+        let mut save_counter = 0u32;
+        let mut name_check_counter = 0u32;
+        let mut last_activity: Option<Instant> = None;
+        let mut activity_bytes = 0u64;
+        loop {
+            tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+
+            // Periodically try to update session name from Claude's first message
+            name_check_counter += 1;
+            if name_check_counter >= 50 {
+                // Check every ~0.5 seconds
+                name_check_counter = 0;
+                let mut state = state.write().await;
+                if let Some(session) = state.sessions.get_mut(&session_id) {
+                    if !session.name_updated_from_provider {
+                        session.update_name_from_provider();
+                        if session.name_updated_from_provider {
+                            // Save updated metadata
+                            let _ = persistence::save_session_meta(session);
+                        }
+                    }
+                }
+            }
+
+            let (pty_closed, stopped) = {
+                let state = state.read().await;
+                let Some(session) = state.sessions.get(&session_id) else {
+                    break;
+                };
+                let pty_closed = match session.read(&mut buf) {
+                    Ok(n) if n > 0 => {
+                        // Store output in session buffer
+                        if session.process_output(&buf[..n]) {
+                            let _ = persistence::save_session_meta(session);
+                        }
+                        let _ = output.send(buf[..n].to_vec());
+
+                        match session.take_title_change() {
+                            Some(TitleChange::Report(title)) => {
+                                events.emit_session_title_changed(session_id.clone(), title);
+                            }
+                            Some(TitleChange::FlushAt(due)) => flush_title_at(
+                                session.title.clone(),
+                                events.clone(),
+                                session_id.clone(),
+                                due,
+                            ),
+                            None => {}
+                        }
+
+                        // Output between events is counted toward the next one
+                        activity_bytes += n as u64;
+                        if last_activity.is_none_or(|last| last.elapsed() >= ACTIVITY_INTERVAL) {
+                            last_activity = Some(Instant::now());
+                            events.emit_session_activity(
+                                session_id.clone(),
+                                session.repo_id.clone(),
+                                session.branch.clone(),
+                                std::mem::take(&mut activity_bytes),
+                            );
+                        }
+
+                        // Periodically save history (every ~1 second of output)
+                        save_counter += 1;
+                        if save_counter >= 100 {
+                            save_counter = 0;
+                            let _ = persistence::save_session_history(session);
+                        }
+                        false
+                    }
+                    Ok(_) => false,
+                    Err(_) => true, // PTY closed
+                };
+                (pty_closed, session.pid().is_none())
+            };
+            if !pty_closed && !stopped {
+                continue;
+            }
+
+            // Re-check under the write lock, where stopped sessions are restarted
+            let mut state = state.write().await;
+            let Some(session) = state.sessions.get_mut(&session_id) else {
+                break;
+            };
+            if !pty_closed && session.pid().is_some() {
+                continue;
+            }
+            let _ = persistence::save_session_history(session);
+            if session
+                .output
+                .as_ref()
+                .is_some_and(|current| current.same_channel(&output))
+            {
+                // Dropping the stored sender closes the remaining clients' streams
+                session.output = None;
+            }
+            break;
+        }
+    });
+}
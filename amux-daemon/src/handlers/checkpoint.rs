@@ -0,0 +1,82 @@
+//! Worktree checkpoint handlers
+
+use super::get_repo_and_open_git;
+use crate::checkpoint::{self, Checkpoint};
+use crate::error::{DaemonError, SessionError};
+use crate::events::EventBroadcaster;
+use crate::state::SharedState;
+use amux_proto::daemon::*;
+use tonic::{Response, Status};
+
+/// List a repo's checkpoints, optionally only those of one branch
+pub async fn list_checkpoints(
+    state: &SharedState,
+    req: ListCheckpointsRequest,
+) -> Result<Response<ListCheckpointsResponse>, Status> {
+    let (_repo, git_repo) = get_repo_and_open_git(state, &req.repo_id).await?;
+
+    let checkpoints = checkpoint::list(&git_repo, req.branch.as_deref())
+        .map_err(|e| Status::from(DaemonError::from(e)))?;
+
+    Ok(Response::new(ListCheckpointsResponse {
+        checkpoints: checkpoints.into_iter().map(checkpoint_to_proto).collect(),
+    }))
+}
+
+/// Put a session's worktree back to its checkpoint
+pub async fn restore_checkpoint(
+    state: &SharedState,
+    events: &EventBroadcaster,
+    req: RestoreCheckpointRequest,
+) -> Result<Response<CheckpointInfo>, Status> {
+    let (worktree_path, repo_id, branch) = {
+        let state = state.read().await;
+        let session = state.sessions.get(&req.session_id).ok_or_else(|| {
+            Status::from(DaemonError::Session(SessionError::NotFound(
+                req.session_id.clone(),
+            )))
+        })?;
+        // A running session could write files back over the restore
+        let running = state
+            .sessions
+            .values()
+            .filter(|s| {
+                s.repo_id == session.repo_id
+                    && s.branch == session.branch
+                    && s.status() == crate::session::SessionStatus::Running
+            })
+            .count();
+        if running > 0 {
+            return Err(Status::failed_precondition(format!(
+                "{} session(s) running in the worktree, stop them first",
+                running
+            )));
+        }
+        (
+            session.worktree_path.clone(),
+            session.repo_id.clone(),
+            session.branch.clone(),
+        )
+    };
+
+    let session_id = req.session_id;
+    let restored =
+        tokio::task::spawn_blocking(move || checkpoint::restore(&worktree_path, &session_id))
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .map_err(|e| Status::from(DaemonError::from(e)))?;
+
+    events.emit_git_status_changed(repo_id, branch);
+    Ok(Response::new(checkpoint_to_proto(restored)))
+}
+
+fn checkpoint_to_proto(checkpoint: Checkpoint) -> CheckpointInfo {
+    CheckpointInfo {
+        session_id: checkpoint.session_id,
+        session_name: checkpoint.session_name,
+        branch: checkpoint.branch,
+        commit: checkpoint.oid.to_string(),
+        base: checkpoint.base.map(|oid| oid.to_string()),
+        created_at: checkpoint.time,
+    }
+}
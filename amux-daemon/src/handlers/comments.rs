@@ -1,15 +1,24 @@
 //! Review/Comment operations handlers
 
 use crate::git::GitOps;
-use crate::review::{CommentLineType, ReviewOps};
+use crate::review::{CommentLineType, LineComment, Review, ReviewOps};
 use crate::state::SharedState;
+use amux_config::FILE_COMMENT_LINE;
 use amux_proto::daemon::*;
+use chrono::{DateTime, Utc};
 use tonic::{Response, Status};
 
 /// Create a line comment
 pub async fn create_line_comment(
     req: CreateLineCommentRequest,
 ) -> Result<Response<LineCommentInfo>, Status> {
+    if req.line_number < FILE_COMMENT_LINE {
+        return Err(Status::invalid_argument(format!(
+            "Invalid line number: {}",
+            req.line_number
+        )));
+    }
+
     let comment = ReviewOps::create_comment(
         &req.repo_id,
         &req.branch,
@@ -103,6 +112,40 @@ pub async fn delete_line_comment(
     Err(Status::not_found("Comment not found"))
 }
 
+/// Restore a deleted line comment
+pub async fn restore_line_comment(
+    req: RestoreLineCommentRequest,
+) -> Result<Response<LineCommentInfo>, Status> {
+    let info = req
+        .comment
+        .ok_or_else(|| Status::invalid_argument("Missing comment"))?;
+
+    let comment = ReviewOps::restore_comment(
+        &info.repo_id,
+        &info.branch,
+        LineComment {
+            id: info.id,
+            file_path: info.file_path,
+            line_number: info.line_number,
+            line_type: CommentLineType::from(info.line_type),
+            comment: info.comment,
+            created_at: DateTime::from_timestamp(info.created_at, 0).unwrap_or_else(Utc::now),
+        },
+    )
+    .map_err(|e| Status::internal(e.to_string()))?;
+
+    Ok(Response::new(LineCommentInfo {
+        id: comment.id,
+        repo_id: info.repo_id,
+        branch: info.branch,
+        file_path: comment.file_path,
+        line_number: comment.line_number,
+        line_type: i32::from(comment.line_type),
+        comment: comment.comment,
+        created_at: comment.created_at.timestamp(),
+    }))
+}
+
 /// List line comments
 pub async fn list_line_comments(
     req: ListLineCommentsRequest,
@@ -128,3 +171,77 @@ pub async fn list_line_comments(
         comments: comment_infos,
     }))
 }
+
+/// Helper to convert a stored comment to proto
+fn comment_info(repo_id: &str, branch: &str, c: LineComment) -> LineCommentInfo {
+    LineCommentInfo {
+        id: c.id,
+        repo_id: repo_id.to_string(),
+        branch: branch.to_string(),
+        file_path: c.file_path,
+        line_number: c.line_number,
+        line_type: i32::from(c.line_type),
+        comment: c.comment,
+        created_at: c.created_at.timestamp(),
+    }
+}
+
+/// Save the branch's comments as a review
+pub async fn save_review(req: SaveReviewRequest) -> Result<Response<ReviewInfo>, Status> {
+    let review = ReviewOps::save_review(&req.repo_id, &req.branch, &req.base_ref, req.title)
+        .map_err(|e| Status::failed_precondition(e.to_string()))?;
+
+    Ok(Response::new(review_info(
+        &req.repo_id,
+        &req.branch,
+        review,
+    )))
+}
+
+/// List the reviews submitted on a branch, newest first
+pub async fn list_reviews(
+    req: ListReviewsRequest,
+) -> Result<Response<ListReviewsResponse>, Status> {
+    let reviews = ReviewOps::load_reviews(&req.repo_id, &req.branch)
+        .map_err(|e| Status::internal(e.to_string()))?;
+
+    Ok(Response::new(ListReviewsResponse {
+        reviews: reviews
+            .reviews
+            .into_iter()
+            .rev()
+            .map(|r| review_info(&req.repo_id, &req.branch, r))
+            .collect(),
+    }))
+}
+
+/// Put a past review's comments back on its branch
+pub async fn open_review(
+    req: OpenReviewRequest,
+) -> Result<Response<ListLineCommentsResponse>, Status> {
+    let comments = ReviewOps::open_review(&req.repo_id, &req.branch, &req.review_id)
+        .map_err(|e| Status::not_found(e.to_string()))?;
+
+    Ok(Response::new(ListLineCommentsResponse {
+        comments: comments
+            .into_iter()
+            .map(|c| comment_info(&req.repo_id, &req.branch, c))
+            .collect(),
+    }))
+}
+
+fn review_info(repo_id: &str, branch: &str, review: Review) -> ReviewInfo {
+    ReviewInfo {
+        id: review.id,
+        repo_id: repo_id.to_string(),
+        branch: branch.to_string(),
+        base_ref: review.base_ref,
+        title: review.title,
+        comments: review
+            .comments
+            .into_iter()
+            .map(|c| comment_info(repo_id, branch, c))
+            .collect(),
+        created_at: review.created_at.timestamp(),
+    }
+}
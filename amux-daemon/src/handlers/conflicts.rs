@@ -0,0 +1,116 @@
+//! Merge conflict handlers
+
+use super::get_worktree_path;
+use crate::conflict::{Region, Resolution};
+use crate::error::DaemonError;
+use crate::events::EventBroadcaster;
+use crate::git::{ConflictFileInfo, GitOps};
+use crate::state::SharedState;
+use amux_proto::daemon::{
+    ConflictFile, ConflictRegion, ConflictResolution, GetFileConflictsRequest,
+    GetFileConflictsResponse, ListConflictsRequest, ListConflictsResponse, ResolveConflictsRequest,
+    ResolveConflictsResponse,
+};
+use tonic::{Response, Status};
+
+/// List files of a worktree with unresolved merge conflicts
+pub async fn list_conflicts(
+    state: &SharedState,
+    req: ListConflictsRequest,
+) -> Result<Response<ListConflictsResponse>, Status> {
+    let worktree_path = get_worktree_path(state, &req.repo_id, &req.branch).await?;
+
+    let files = tokio::task::spawn_blocking(move || {
+        let wt_repo = GitOps::open(&worktree_path)?;
+        GitOps::list_conflicts(&wt_repo)
+    })
+    .await
+    .map_err(|e| Status::internal(e.to_string()))?
+    .map_err(|e| Status::from(DaemonError::from(e)))?;
+
+    Ok(Response::new(ListConflictsResponse {
+        files: files.into_iter().map(conflict_file_to_proto).collect(),
+    }))
+}
+
+/// Parse the conflict hunks of a file
+pub async fn get_file_conflicts(
+    state: &SharedState,
+    req: GetFileConflictsRequest,
+) -> Result<Response<GetFileConflictsResponse>, Status> {
+    let worktree_path = get_worktree_path(state, &req.repo_id, &req.branch).await?;
+    let wt_repo = GitOps::open(&worktree_path).map_err(|e| Status::from(DaemonError::from(e)))?;
+    let file = GitOps::file_conflicts(&wt_repo, &req.path)
+        .map_err(|e| Status::from(DaemonError::from(e)))?;
+
+    // The view shows lines as text; CRLF files keep their `\r` on disk only
+    let strip = |lines: Vec<String>| -> Vec<String> {
+        lines
+            .into_iter()
+            .map(|l| l.strip_suffix('\r').map(str::to_string).unwrap_or(l))
+            .collect()
+    };
+    let regions = file
+        .regions
+        .into_iter()
+        .map(|region| match region {
+            Region::Common(lines) => ConflictRegion {
+                lines: strip(lines),
+                ..Default::default()
+            },
+            Region::Conflict(hunk) => ConflictRegion {
+                is_conflict: true,
+                lines: Vec::new(),
+                ours_label: hunk.ours_label,
+                ours: strip(hunk.ours),
+                theirs_label: hunk.theirs_label,
+                theirs: strip(hunk.theirs),
+            },
+        })
+        .collect();
+
+    Ok(Response::new(GetFileConflictsResponse { regions }))
+}
+
+/// Resolve every hunk of a file, write it and stage it
+pub async fn resolve_conflicts(
+    state: &SharedState,
+    events: &EventBroadcaster,
+    req: ResolveConflictsRequest,
+) -> Result<Response<ResolveConflictsResponse>, Status> {
+    let resolutions = req
+        .resolutions
+        .iter()
+        .map(|&r| match ConflictResolution::try_from(r) {
+            Ok(ConflictResolution::Ours) => Some(Resolution::Ours),
+            Ok(ConflictResolution::Theirs) => Some(Resolution::Theirs),
+            Ok(ConflictResolution::Both) => Some(Resolution::Both),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| Status::invalid_argument("Every conflict needs a resolution"))?;
+
+    let worktree_path = get_worktree_path(state, &req.repo_id, &req.branch).await?;
+    let path = req.path;
+
+    let remaining = tokio::task::spawn_blocking(move || {
+        let wt_repo = GitOps::open(&worktree_path)?;
+        GitOps::resolve_conflicts(&wt_repo, &path, &resolutions)?;
+        GitOps::list_conflicts(&wt_repo)
+    })
+    .await
+    .map_err(|e| Status::internal(e.to_string()))?
+    .map_err(|e| Status::from(DaemonError::from(e)))?;
+
+    events.emit_git_status_changed(req.repo_id, req.branch);
+    Ok(Response::new(ResolveConflictsResponse {
+        remaining: remaining.into_iter().map(conflict_file_to_proto).collect(),
+    }))
+}
+
+fn conflict_file_to_proto(file: ConflictFileInfo) -> ConflictFile {
+    ConflictFile {
+        path: file.path,
+        conflict_count: file.conflicts as u32,
+    }
+}
@@ -0,0 +1,70 @@
+//! Daemon status handler
+
+use crate::events::EventBroadcaster;
+use crate::git::GitOps;
+use crate::persistence;
+use crate::session::SessionStatus;
+use crate::state::{AppState, SharedState};
+use amux_proto::daemon::{DaemonStatus, ExitedSession};
+use tonic::{Response, Status};
+
+/// Summary of the daemon and what it manages, with sessions whose process
+/// exited unnoticed
+pub async fn get_status(
+    state: &SharedState,
+    events: &EventBroadcaster,
+    started_at: u64,
+) -> Result<Response<DaemonStatus>, Status> {
+    let mut status = DaemonStatus {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        started_at,
+        socket_path: AppState::socket_path().to_string_lossy().into_owned(),
+        event_subscribers: events.subscriber_count() as u32,
+        ..Default::default()
+    };
+
+    let (repo_paths, session_ids) = {
+        let state = state.read().await;
+        for session in state.sessions.values() {
+            match session.status() {
+                SessionStatus::Running => status.running_sessions += 1,
+                SessionStatus::Trashed => status.trashed_sessions += 1,
+                SessionStatus::Stopped => status.stopped_sessions += 1,
+            }
+            if session.process_exited() {
+                status.exited_sessions.push(ExitedSession {
+                    id: session.id.clone(),
+                    name: session.name.clone(),
+                    branch: session.branch.clone(),
+                });
+            }
+        }
+        status.trashed_sessions += state.trash.len() as u32;
+        status.repo_count = state.repos.len() as u32;
+
+        let repo_paths: Vec<_> = state.repos.values().map(|r| r.path.clone()).collect();
+        let session_ids: Vec<String> = state
+            .sessions
+            .keys()
+            .chain(state.trash.keys())
+            .cloned()
+            .collect();
+        (repo_paths, session_ids)
+    };
+    status.exited_sessions.sort_by(|a, b| a.id.cmp(&b.id));
+
+    // Git and the disk are read without holding the state lock
+    status.worktree_count = repo_paths
+        .iter()
+        .filter_map(|path| GitOps::open(path).ok())
+        .filter_map(|repo| GitOps::list_worktrees(&repo).ok())
+        .map(|worktrees| worktrees.len() as u32)
+        .sum();
+    status.history_bytes = session_ids
+        .iter()
+        .filter_map(|id| std::fs::metadata(persistence::session_history_file(id)).ok())
+        .map(|meta| meta.len())
+        .sum();
+
+    Ok(Response::new(status))
+}
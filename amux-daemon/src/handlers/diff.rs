@@ -1,6 +1,7 @@
 //! Diff operations handlers
 
-use super::get_repo_and_open_git;
+use super::{get_repo_and_open_git, get_worktree_path};
+use crate::blame::BlameOps;
 use crate::diff::DiffOps;
 use crate::error::DaemonError;
 use crate::git::GitOps;
@@ -20,29 +21,54 @@ pub async fn get_diff_files(
         Status::not_found(format!("Worktree not found for branch: {}", req.branch))
     })?;
 
-    // Get diff files
-    let diff_files =
-        DiffOps::get_diff_files(&worktree_path).map_err(|e| Status::from(DaemonError::from(e)))?;
+    // Get diff files, either of the worktree or of a given revision
+    let diff_files = match req.revision.as_deref() {
+        Some(revision) => DiffOps::get_revision_diff_files(&worktree_path, revision),
+        None => DiffOps::get_diff_files(&worktree_path, req.base_ref.as_deref()),
+    }
+    .map_err(|e| Status::from(DaemonError::from(e)))?;
 
-    let files = diff_files
-        .into_iter()
-        .map(|f| DiffFileInfo {
-            path: f.path,
-            status: match f.status {
-                crate::diff::FileStatus::Modified => FileStatus::Modified as i32,
-                crate::diff::FileStatus::Added => FileStatus::Added as i32,
-                crate::diff::FileStatus::Deleted => FileStatus::Deleted as i32,
-                crate::diff::FileStatus::Renamed => FileStatus::Renamed as i32,
-                crate::diff::FileStatus::Untracked => FileStatus::Untracked as i32,
-            },
-            additions: f.additions,
-            deletions: f.deletions,
-        })
-        .collect();
+    let files = diff_files.into_iter().map(diff_file_info).collect();
 
     Ok(Response::new(GetDiffFilesResponse { files }))
 }
 
+fn diff_file_info(f: crate::diff::DiffFileInfo) -> DiffFileInfo {
+    DiffFileInfo {
+        path: f.path,
+        status: match f.status {
+            crate::diff::FileStatus::Modified => FileStatus::Modified as i32,
+            crate::diff::FileStatus::Added => FileStatus::Added as i32,
+            crate::diff::FileStatus::Deleted => FileStatus::Deleted as i32,
+            crate::diff::FileStatus::Renamed => FileStatus::Renamed as i32,
+            crate::diff::FileStatus::Untracked => FileStatus::Untracked as i32,
+        },
+        additions: f.additions,
+        deletions: f.deletions,
+    }
+}
+
+/// Get the files a branch changed since it forked from its base
+pub async fn get_branch_diff(
+    state: &SharedState,
+    req: GetBranchDiffRequest,
+) -> Result<Response<GetBranchDiffResponse>, Status> {
+    let (_repo, git_repo) = get_repo_and_open_git(state, &req.repo_id).await?;
+
+    let worktree_path = GitOps::find_worktree_path(&git_repo, &req.branch).ok_or_else(|| {
+        Status::not_found(format!("Worktree not found for branch: {}", req.branch))
+    })?;
+
+    let (base_ref, diff_files) =
+        DiffOps::get_branch_diff_files(&worktree_path, req.base_ref.as_deref())
+            .map_err(|e| Status::from(DaemonError::from(e)))?;
+
+    Ok(Response::new(GetBranchDiffResponse {
+        files: diff_files.into_iter().map(diff_file_info).collect(),
+        base_ref,
+    }))
+}
+
 /// Get diff lines for a specific file
 pub async fn get_file_diff(
     state: &SharedState,
@@ -56,8 +82,16 @@ pub async fn get_file_diff(
     })?;
 
     // Get diff for file
-    let diff_lines = DiffOps::get_file_diff(&worktree_path, &req.file_path)
-        .map_err(|e| Status::from(DaemonError::from(e)))?;
+    let diff_lines = match (req.revision.as_deref(), req.base_ref.as_deref()) {
+        (Some(revision), _) => {
+            DiffOps::get_revision_file_diff(&worktree_path, revision, &req.file_path)
+        }
+        (None, Some(base_ref)) if req.branch_diff => {
+            DiffOps::get_branch_file_diff(&worktree_path, base_ref, &req.file_path)
+        }
+        (None, base_ref) => DiffOps::get_file_diff(&worktree_path, &req.file_path, base_ref),
+    }
+    .map_err(|e| Status::from(DaemonError::from(e)))?;
 
     let lines = diff_lines
         .into_iter()
@@ -71,6 +105,7 @@ pub async fn get_file_diff(
             content: l.content,
             old_lineno: l.old_lineno,
             new_lineno: l.new_lineno,
+            hidden_lines: 0,
         })
         .collect();
 
@@ -79,3 +114,80 @@ pub async fn get_file_diff(
         lines,
     }))
 }
+
+/// Get a range of a file's lines, to expand context hidden in a diff
+pub async fn get_file_lines(
+    state: &SharedState,
+    req: GetFileLinesRequest,
+) -> Result<Response<GetFileLinesResponse>, Status> {
+    let worktree_path = get_worktree_path(state, &req.repo_id, &req.branch).await?;
+    if req.start < 1 || req.count < 0 {
+        return Err(Status::invalid_argument(format!(
+            "Invalid line range: {} (+{})",
+            req.start, req.count
+        )));
+    }
+
+    let lines = DiffOps::get_file_lines(
+        &worktree_path,
+        &req.file_path,
+        req.revision.as_deref(),
+        req.start as usize,
+        req.count as usize,
+    )
+    .map_err(|e| Status::from(DaemonError::from(e)))?;
+
+    Ok(Response::new(GetFileLinesResponse { lines }))
+}
+
+/// List branches and tags usable as a diff base
+pub async fn list_refs(
+    state: &SharedState,
+    req: ListRefsRequest,
+) -> Result<Response<ListRefsResponse>, Status> {
+    let (_repo, git_repo) = get_repo_and_open_git(state, &req.repo_id).await?;
+    let (branches, tags) =
+        GitOps::list_refs(&git_repo).map_err(|e| Status::from(DaemonError::from(e)))?;
+    Ok(Response::new(ListRefsResponse { branches, tags }))
+}
+
+/// Blame a file as committed at the worktree's HEAD (cached per HEAD)
+pub async fn get_blame(
+    state: &SharedState,
+    req: GetBlameRequest,
+) -> Result<Response<GetBlameResponse>, Status> {
+    let worktree_path = get_worktree_path(state, &req.repo_id, &req.branch).await?;
+    let head = BlameOps::head_id(&worktree_path).map_err(|e| Status::from(DaemonError::from(e)))?;
+    let key = (worktree_path.clone(), req.file_path.clone(), head.clone());
+
+    let cached = state.read().await.blame_cache.get(&key);
+    let lines = match cached {
+        Some(lines) => lines,
+        None => {
+            let file = req.file_path.clone();
+            let commit = head.clone();
+            let lines = tokio::task::spawn_blocking(move || {
+                BlameOps::blame_file(&worktree_path, &file, &commit)
+            })
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .map_err(|e| Status::from(DaemonError::from(e)))?;
+            let lines = std::sync::Arc::new(lines);
+            state.write().await.blame_cache.insert(key, lines.clone());
+            lines
+        }
+    };
+
+    Ok(Response::new(GetBlameResponse {
+        head,
+        lines: lines
+            .iter()
+            .map(|l| BlameLine {
+                commit_id: l.commit_id.clone(),
+                author: l.author.clone(),
+                summary: l.summary.clone(),
+                content: l.content.clone(),
+            })
+            .collect(),
+    }))
+}
@@ -17,6 +17,7 @@ pub async fn subscribe_events(
     req: SubscribeEventsRequest,
 ) -> Result<Response<SubscribeEventsStream>, Status> {
     let repo_filter = req.repo_id;
+    let include_activity = req.include_activity;
 
     // Subscribe to event broadcaster
     let mut event_rx = events.subscribe();
@@ -31,6 +32,8 @@ pub async fn subscribe_events(
                 Ok(event) => {
                     // Apply repo_id filter if specified
                     let should_send = match (&repo_filter, &event.event) {
+                        // Activity events only go to clients that asked for them
+                        (_, Some(event::Event::SessionActivity(_))) if !include_activity => false,
                         (None, _) => true, // No filter, send all
                         (Some(filter_repo_id), Some(event::Event::SessionCreated(e))) => e
                             .session
@@ -40,10 +43,16 @@ pub async fn subscribe_events(
                         (Some(filter_repo_id), Some(event::Event::SessionDestroyed(e))) => {
                             &e.repo_id == filter_repo_id
                         }
-                        // Name/status updates don't have repo_id, send all for now
+                        // Name/status/title updates don't have repo_id, send all for now
                         // TUI can filter client-side if needed
                         (Some(_), Some(event::Event::SessionNameUpdated(_))) => true,
                         (Some(_), Some(event::Event::SessionStatusChanged(_))) => true,
+                        (Some(_), Some(event::Event::SessionTitleChanged(_))) => true,
+                        (Some(_), Some(event::Event::SessionUsageUpdated(_))) => true,
+                        (Some(_), Some(event::Event::SessionAttachChanged(_))) => true,
+                        (Some(filter_repo_id), Some(event::Event::SessionActivity(e))) => {
+                            &e.repo_id == filter_repo_id
+                        }
                         // Worktree events
                         (Some(filter_repo_id), Some(event::Event::WorktreeAdded(e))) => e
                             .worktree
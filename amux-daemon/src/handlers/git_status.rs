@@ -1,14 +1,16 @@
 //! Git status operations handlers
 
 use super::get_repo_and_open_git;
-use crate::error::DaemonError;
-use crate::git::GitOps;
+use crate::error::{DaemonError, GitError};
+use crate::git::{GitOps, PullOutcome as GitPullOutcome, PullStrategy as GitPullStrategy};
 use crate::state::SharedState;
 use amux_proto::daemon::{
-    Empty, FileStatus, GetGitStatusRequest, GetGitStatusResponse, GitPullRequest, GitPullResponse,
-    GitPushRequest, GitPushResponse, StageAllRequest, StageFileRequest, UnstageAllRequest,
-    UnstageFileRequest,
+    CommitAndPushRequest, CommitAndPushResponse, Empty, FetchRequest, FetchResponse, FileStatus,
+    GetGitStatusRequest, GetGitStatusResponse, GitPushRequest, GitPushResponse, PipelineStep,
+    PipelineStepResult, PullOutcome, PullStrategy, PullWorktreeRequest, PullWorktreeResponse,
+    StageAllRequest, StageFileRequest, StepFailure, UnstageAllRequest, UnstageFileRequest,
 };
+use std::path::PathBuf;
 use tonic::{Response, Status};
 
 /// Get git status for a worktree
@@ -27,8 +29,8 @@ pub async fn get_git_status(
     let wt_repo = GitOps::open(&worktree_path).map_err(|e| Status::from(DaemonError::from(e)))?;
 
     // Get git status
-    let status_result =
-        GitOps::get_status(&wt_repo).map_err(|e| Status::from(DaemonError::from(e)))?;
+    let status_result = GitOps::get_status(&wt_repo, req.include_ignored)
+        .map_err(|e| Status::from(DaemonError::from(e)))?;
 
     // Convert to proto types
     let to_proto_file = |f: crate::git::GitStatusFile| -> amux_proto::daemon::GitStatusFile {
@@ -40,6 +42,13 @@ pub async fn get_git_status(
                 crate::git::GitFileStatus::Deleted => FileStatus::Deleted as i32,
                 crate::git::GitFileStatus::Renamed => FileStatus::Renamed as i32,
                 crate::git::GitFileStatus::Untracked => FileStatus::Untracked as i32,
+                crate::git::GitFileStatus::Ignored => FileStatus::Ignored as i32,
+                crate::git::GitFileStatus::SubmoduleModified => {
+                    FileStatus::SubmoduleModified as i32
+                }
+                crate::git::GitFileStatus::SubmoduleUninitialized => {
+                    FileStatus::SubmoduleUninitialized as i32
+                }
             },
         }
     };
@@ -60,6 +69,16 @@ pub async fn get_git_status(
             .into_iter()
             .map(to_proto_file)
             .collect(),
+        ignored: status_result
+            .ignored
+            .into_iter()
+            .map(to_proto_file)
+            .collect(),
+        submodules: status_result
+            .submodules
+            .into_iter()
+            .map(to_proto_file)
+            .collect(),
     }))
 }
 
@@ -173,11 +192,11 @@ pub async fn git_push(
     }
 }
 
-/// Pull from remote (fetch + rebase)
-pub async fn git_pull(
+/// Stage all, commit and push a worktree, reporting each step
+pub async fn commit_and_push(
     state: &SharedState,
-    req: GitPullRequest,
-) -> Result<Response<GitPullResponse>, Status> {
+    req: CommitAndPushRequest,
+) -> Result<Response<CommitAndPushResponse>, Status> {
     let (_repo, git_repo) = get_repo_and_open_git(state, &req.repo_id).await?;
 
     // Find worktree path for the branch
@@ -185,15 +204,228 @@ pub async fn git_pull(
         Status::not_found(format!("Worktree not found for branch: {}", req.branch))
     })?;
 
-    // Pull using system git
-    match GitOps::pull(&worktree_path) {
-        Ok(msg) => Ok(Response::new(GitPullResponse {
+    if req.message.trim().is_empty() {
+        return Err(Status::invalid_argument("Commit message cannot be empty"));
+    }
+
+    // Hooks and network push can take a while
+    let response = tokio::task::spawn_blocking(move || run_commit_and_push(worktree_path, req))
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?;
+
+    Ok(Response::new(response))
+}
+
+fn run_commit_and_push(worktree_path: PathBuf, req: CommitAndPushRequest) -> CommitAndPushResponse {
+    let mut response = CommitAndPushResponse::default();
+
+    let wt_repo = match GitOps::open(&worktree_path) {
+        Ok(repo) => repo,
+        Err(e) => {
+            response
+                .steps
+                .push(step_result(PipelineStep::Stage, Err(e)));
+            return response;
+        }
+    };
+
+    // Staging everything would mark conflicted files as resolved
+    let staged = match GitOps::list_conflicts(&wt_repo) {
+        Ok(conflicts) if !conflicts.is_empty() => {
+            Err(GitError::UnresolvedConflicts(conflicts.len()))
+        }
+        Ok(_) => GitOps::stage_all(&wt_repo).map(|_| "Staged all changes".to_string()),
+        Err(e) => Err(e),
+    };
+    if !push_step(&mut response, PipelineStep::Stage, staged) {
+        return response;
+    }
+
+    let committed = GitOps::commit_index(&wt_repo, req.message.trim()).map(|oid| {
+        response.commit_id = oid.to_string();
+        format!("Committed {:.7}", oid)
+    });
+    if !push_step(&mut response, PipelineStep::Commit, committed) {
+        return response;
+    }
+
+    let pushed = GitOps::push_branch(&wt_repo, &req.branch, Some(&req.push_remote));
+    response.success = push_step(&mut response, PipelineStep::Push, pushed);
+    response
+}
+
+/// Record a step result, returning whether it succeeded
+fn push_step(
+    response: &mut CommitAndPushResponse,
+    step: PipelineStep,
+    result: Result<String, GitError>,
+) -> bool {
+    let result = step_result(step, result);
+    let success = result.success;
+    response.steps.push(result);
+    success
+}
+
+fn step_result(step: PipelineStep, result: Result<String, GitError>) -> PipelineStepResult {
+    match result {
+        Ok(message) => PipelineStepResult {
+            step: step as i32,
             success: true,
-            message: msg,
-        })),
-        Err(e) => Ok(Response::new(GitPullResponse {
+            message,
+            failure: StepFailure::None as i32,
+        },
+        Err(e) => {
+            let failure = match &e {
+                GitError::AuthFailed(_) => StepFailure::Auth,
+                GitError::PushRejected(reason) if is_non_fast_forward(reason) => {
+                    StepFailure::NonFastForward
+                }
+                GitError::HookFailed { .. } => StepFailure::Hook,
+                GitError::NothingToCommit => StepFailure::NothingToCommit,
+                GitError::UnresolvedConflicts(_) => StepFailure::Conflicts,
+                _ => StepFailure::Other,
+            };
+            PipelineStepResult {
+                step: step as i32,
+                success: false,
+                message: e.to_string(),
+                failure: failure as i32,
+            }
+        }
+    }
+}
+
+/// Whether the remote rejected a push because the branch moved on
+///
+/// Other rejections (protected branches, server hooks) aren't fixed by
+/// pulling first.
+fn is_non_fast_forward(reason: &str) -> bool {
+    let reason = reason.to_ascii_lowercase();
+    reason.contains("non-fast-forward") || reason.contains("fetch first")
+}
+
+/// Fetch all remotes of a repository
+pub async fn fetch(
+    state: &SharedState,
+    req: FetchRequest,
+) -> Result<Response<FetchResponse>, Status> {
+    let (repo, git_repo) = get_repo_and_open_git(state, &req.repo_id).await?;
+    drop(git_repo);
+
+    // Network I/O - keep it off the async runtime
+    let result = tokio::task::spawn_blocking(move || {
+        let git_repo = GitOps::open(&repo.path)?;
+        GitOps::fetch_all(&git_repo)
+    })
+    .await
+    .map_err(|e| Status::internal(e.to_string()))?;
+
+    Ok(Response::new(match result {
+        Ok(remotes) if remotes.is_empty() => FetchResponse {
+            success: true,
+            message: "No remotes configured".to_string(),
+        },
+        Ok(remotes) => FetchResponse {
+            success: true,
+            message: format!("Fetched {}", remotes.join(", ")),
+        },
+        Err(e) => FetchResponse {
             success: false,
             message: e.to_string(),
-        })),
+        },
+    }))
+}
+
+/// Fetch and integrate upstream changes into a worktree
+pub async fn pull_worktree(
+    state: &SharedState,
+    req: PullWorktreeRequest,
+) -> Result<Response<PullWorktreeResponse>, Status> {
+    let (_repo, git_repo) = get_repo_and_open_git(state, &req.repo_id).await?;
+
+    // Find worktree path for the branch
+    let worktree_path = GitOps::find_worktree_path(&git_repo, &req.branch).ok_or_else(|| {
+        Status::not_found(format!("Worktree not found for branch: {}", req.branch))
+    })?;
+    drop(git_repo);
+
+    let strategy = match PullStrategy::try_from(req.strategy).unwrap_or(PullStrategy::Unspecified) {
+        PullStrategy::Rebase => GitPullStrategy::Rebase,
+        PullStrategy::FfOnly => GitPullStrategy::FfOnly,
+        PullStrategy::Merge | PullStrategy::Unspecified => GitPullStrategy::Merge,
+    };
+    let stash_first = req.stash_first;
+
+    let result = tokio::task::spawn_blocking(move || {
+        let mut wt_repo = GitOps::open(&worktree_path)?;
+        GitOps::pull_worktree(&mut wt_repo, strategy, stash_first)
+    })
+    .await
+    .map_err(|e| Status::internal(e.to_string()))?;
+
+    let response = match result {
+        Ok(report) => {
+            let stash_note = report
+                .stash_note
+                .map(|n| format!(" ({})", n))
+                .unwrap_or_default();
+            match report.outcome {
+                GitPullOutcome::Updated(range) => PullWorktreeResponse {
+                    outcome: PullOutcome::Updated as i32,
+                    message: format!("Updated {}{}", range, stash_note),
+                    conflicted_files: vec![],
+                },
+                GitPullOutcome::UpToDate => PullWorktreeResponse {
+                    outcome: PullOutcome::UpToDate as i32,
+                    message: format!("Already up to date{}", stash_note),
+                    conflicted_files: vec![],
+                },
+                GitPullOutcome::Conflicts(files) => PullWorktreeResponse {
+                    outcome: PullOutcome::Conflicts as i32,
+                    message: format!("Conflicts in {} file(s){}", files.len(), stash_note),
+                    conflicted_files: files,
+                },
+            }
+        }
+        Err(GitError::DirtyWorktree) => PullWorktreeResponse {
+            outcome: PullOutcome::Dirty as i32,
+            message: GitError::DirtyWorktree.to_string(),
+            conflicted_files: vec![],
+        },
+        Err(e) => PullWorktreeResponse {
+            outcome: PullOutcome::Failed as i32,
+            message: e.to_string(),
+            conflicted_files: vec![],
+        },
+    };
+
+    Ok(Response::new(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_failure(reason: &str) -> i32 {
+        step_result(
+            PipelineStep::Push,
+            Err(GitError::PushRejected(reason.to_string())),
+        )
+        .failure
+    }
+
+    #[test]
+    fn test_push_rejections() {
+        let non_fast_forward = StepFailure::NonFastForward as i32;
+        assert_eq!(push_failure("non-fast-forward"), non_fast_forward);
+        assert_eq!(push_failure("rejected (fetch first)"), non_fast_forward);
+        assert_eq!(
+            push_failure("pre-receive hook declined"),
+            StepFailure::Other as i32
+        );
+        assert_eq!(
+            push_failure("protected branch hook declined"),
+            StepFailure::Other as i32
+        );
     }
 }
@@ -4,14 +4,19 @@
 //! The main CcmDaemonService delegates to these handlers.
 
 pub mod attach;
+pub mod checkpoint;
 pub mod comments;
+pub mod conflicts;
+pub mod daemon;
 pub mod diff;
 pub mod events;
 pub mod git_status;
 pub mod provider;
 pub mod repo;
 pub mod session;
+pub mod stash;
 pub mod todo;
+pub mod workspace;
 pub mod worktree;
 
 use crate::error::{DaemonError, RepoError};
@@ -43,8 +48,8 @@ pub async fn get_repo_and_open_git(
         );
         // Remove from state and save
         let mut state_guard = state.write().await;
-        state_guard.repos.remove(repo_id);
-        let repos: Vec<_> = state_guard.repos.values().cloned().collect();
+        state_guard.remove_repo(repo_id);
+        let repos = state_guard.ordered_repos();
         drop(state_guard);
         let _ = repo_mod::save_repos(&repos);
 
@@ -59,3 +64,14 @@ pub async fn get_repo_and_open_git(
 
     Ok((repo, git_repo))
 }
+
+/// Helper to find the worktree path of a repo's branch
+pub async fn get_worktree_path(
+    state: &SharedState,
+    repo_id: &str,
+    branch: &str,
+) -> Result<std::path::PathBuf, Status> {
+    let (_repo, git_repo) = get_repo_and_open_git(state, repo_id).await?;
+    GitOps::find_worktree_path(&git_repo, branch)
+        .ok_or_else(|| Status::not_found(format!("Worktree not found for branch: {}", branch)))
+}
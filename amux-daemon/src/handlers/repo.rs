@@ -1,9 +1,16 @@
 //! Repository management handlers
 
-use crate::error::{DaemonError, RepoError};
+use crate::env;
+use crate::error::{DaemonError, GitError, RepoError};
+use crate::git::GitOps;
+use crate::persistence;
 use crate::repo::{self, Repo};
+use crate::repo_stats::{ExpensiveStats, RepoStatsOps};
+use crate::session::SessionStatus;
 use crate::state::SharedState;
 use amux_proto::daemon::*;
+use std::path::PathBuf;
+use std::time::Instant;
 use tonic::{Response, Status};
 
 /// Add a new repository
@@ -12,6 +19,11 @@ pub async fn add_repo(
     req: AddRepoRequest,
 ) -> Result<Response<RepoInfo>, Status> {
     let path = std::path::PathBuf::from(&req.path);
+    if !path.exists() {
+        return Err(Status::from(DaemonError::Repo(RepoError::PathNotFound(
+            path,
+        ))));
+    }
 
     // If the path is a worktree, resolve to main repository
     let path = crate::git::GitOps::find_main_repo_path(&path).unwrap_or(path);
@@ -32,12 +44,13 @@ pub async fn add_repo(
         name: repo.name.clone(),
         path: repo.path.to_string_lossy().to_string(),
         session_count: 0,
+        checkpoints: repo.checkpoints,
     };
 
-    state.repos.insert(repo.id.clone(), repo);
+    state.insert_repo(repo);
 
     // Save to disk
-    let repos: Vec<_> = state.repos.values().cloned().collect();
+    let repos = state.ordered_repos();
     drop(state);
     repo::save_repos(&repos).map_err(|e| Status::from(DaemonError::from(e)))?;
 
@@ -49,8 +62,8 @@ pub async fn list_repos(state: &SharedState) -> Result<Response<ListReposRespons
     let state = state.read().await;
 
     let repos: Vec<RepoInfo> = state
-        .repos
-        .values()
+        .ordered_repos()
+        .iter()
         .map(|r| {
             let session_count = state
                 .sessions
@@ -63,6 +76,7 @@ pub async fn list_repos(state: &SharedState) -> Result<Response<ListReposRespons
                 name: r.name.clone(),
                 path: r.path.to_string_lossy().to_string(),
                 session_count,
+                checkpoints: r.checkpoints,
             }
         })
         .collect();
@@ -77,23 +91,229 @@ pub async fn remove_repo(
 ) -> Result<Response<Empty>, Status> {
     let mut state = state.write().await;
 
-    // Check if repo has active sessions
-    let has_sessions = state.sessions.values().any(|s| s.repo_id == req.id);
-    if has_sessions {
+    // Refuse while sessions are running unless they're detached; stopped ones
+    // are cleaned up as orphans on restart
+    let has_running = state
+        .sessions
+        .values()
+        .any(|s| s.repo_id == req.id && s.status() == SessionStatus::Running);
+    if has_running && !req.detach_sessions {
         return Err(Status::failed_precondition(
-            "Cannot remove repo with active sessions",
+            "Cannot remove repo with running sessions",
         ));
     }
 
     state
-        .repos
-        .remove(&req.id)
+        .remove_repo(&req.id)
         .ok_or_else(|| Status::from(DaemonError::Repo(RepoError::NotFound(req.id.clone()))))?;
 
+    // Detached sessions keep their process but no longer belong to a repo
+    if req.detach_sessions {
+        for session in state.sessions.values_mut() {
+            if session.repo_id == req.id {
+                session.repo_id.clear();
+                if let Err(e) = persistence::save_session_meta(session) {
+                    tracing::warn!("Failed to save session {}: {}", session.id, e);
+                }
+            }
+        }
+    }
+
+    // Save to disk
+    let repos = state.ordered_repos();
+    drop(state);
+    repo::save_repos(&repos).map_err(|e| Status::from(DaemonError::from(e)))?;
+
+    Ok(Response::new(Empty {}))
+}
+
+/// Reorder repositories (persisted as the repos.json order)
+pub async fn reorder_repos(
+    state: &SharedState,
+    req: ReorderReposRequest,
+) -> Result<Response<Empty>, Status> {
+    let mut state = state.write().await;
+    state.reorder_repos(&req.repo_ids);
+
+    // Save to disk
+    let repos = state.ordered_repos();
+    drop(state);
+    repo::save_repos(&repos).map_err(|e| Status::from(DaemonError::from(e)))?;
+
+    Ok(Response::new(Empty {}))
+}
+
+/// Turn worktree checkpoints on or off for a repository
+pub async fn set_repo_checkpoints(
+    state: &SharedState,
+    req: SetRepoCheckpointsRequest,
+) -> Result<Response<Empty>, Status> {
+    let mut state = state.write().await;
+    let repo = state
+        .repos
+        .get_mut(&req.repo_id)
+        .ok_or_else(|| Status::from(DaemonError::Repo(RepoError::NotFound(req.repo_id.clone()))))?;
+    repo.checkpoints = req.enabled;
+
+    // Save to disk
+    let repos = state.ordered_repos();
+    drop(state);
+    repo::save_repos(&repos).map_err(|e| Status::from(DaemonError::from(e)))?;
+
+    Ok(Response::new(Empty {}))
+}
+
+/// Set or unset an environment variable of a repo's or worktree's sessions
+pub async fn set_repo_env(
+    state: &SharedState,
+    req: SetRepoEnvRequest,
+) -> Result<Response<Empty>, Status> {
+    if let Some(value) = &req.value {
+        env::validate(&req.name, value).map_err(|e| Status::from(DaemonError::from(e)))?;
+    }
+
+    let mut state = state.write().await;
+    let repo = state
+        .repos
+        .get_mut(&req.repo_id)
+        .ok_or_else(|| Status::from(DaemonError::Repo(RepoError::NotFound(req.repo_id.clone()))))?;
+    let vars = match &req.branch {
+        Some(branch) => repo.worktree_env.entry(branch.clone()).or_default(),
+        None => &mut repo.env,
+    };
+    match req.value {
+        Some(value) => {
+            vars.insert(req.name, value);
+        }
+        None => {
+            vars.remove(&req.name);
+        }
+    }
+    repo.worktree_env.retain(|_, vars| !vars.is_empty());
+
     // Save to disk
-    let repos: Vec<_> = state.repos.values().cloned().collect();
+    let repos = state.ordered_repos();
     drop(state);
     repo::save_repos(&repos).map_err(|e| Status::from(DaemonError::from(e)))?;
 
     Ok(Response::new(Empty {}))
 }
+
+/// List the environment variables of a repo and its worktrees
+pub async fn list_repo_env(
+    state: &SharedState,
+    req: ListRepoEnvRequest,
+) -> Result<Response<ListRepoEnvResponse>, Status> {
+    let state = state.read().await;
+    let repo = state
+        .repos
+        .get(&req.repo_id)
+        .ok_or_else(|| Status::from(DaemonError::Repo(RepoError::NotFound(req.repo_id.clone()))))?;
+
+    let mut vars: Vec<EnvVar> = repo
+        .env
+        .iter()
+        .map(|(name, value)| EnvVar {
+            name: name.clone(),
+            value: value.clone(),
+            branch: None,
+        })
+        .collect();
+    let mut branches: Vec<_> = repo.worktree_env.iter().collect();
+    branches.sort_by_key(|(branch, _)| *branch);
+    for (branch, worktree) in branches {
+        vars.extend(worktree.iter().map(|(name, value)| EnvVar {
+            name: name.clone(),
+            value: value.clone(),
+            branch: Some(branch.clone()),
+        }));
+    }
+
+    Ok(Response::new(ListRepoEnvResponse { vars }))
+}
+
+/// Totals over a repo's worktrees and sessions
+///
+/// Commits ahead and disk usage are reused for a while, the other counts
+/// are current.
+pub async fn get_repo_stats(
+    state: &SharedState,
+    req: GetRepoStatsRequest,
+) -> Result<Response<RepoStats>, Status> {
+    let (_repo, git_repo) = super::get_repo_and_open_git(state, &req.repo_id).await?;
+    let worktrees: Vec<PathBuf> = GitOps::list_worktrees(&git_repo)
+        .map_err(|e| Status::from(DaemonError::from(e)))?
+        .into_iter()
+        .map(|w| w.path)
+        .collect();
+    drop(git_repo);
+
+    let (total_sessions, running_sessions, cached) = {
+        let state = state.read().await;
+        let sessions: Vec<_> = state
+            .sessions
+            .values()
+            .filter(|s| s.repo_id == req.repo_id)
+            .collect();
+        let running = sessions
+            .iter()
+            .filter(|s| s.status() == SessionStatus::Running)
+            .count();
+        let cached = state.repo_stats_cache.get(&req.repo_id, Instant::now());
+        (sessions.len() as u32, running as u32, cached)
+    };
+
+    let paths = worktrees.clone();
+    let total_uncommitted_changes = tokio::task::spawn_blocking(move || {
+        paths.iter().try_fold(0, |total, path| {
+            Ok::<_, GitError>(total + RepoStatsOps::uncommitted_changes(path)?)
+        })
+    })
+    .await
+    .map_err(|e| Status::internal(e.to_string()))?
+    .map_err(|e| Status::from(DaemonError::from(e)))?;
+
+    let expensive = match cached {
+        Some(stats) => stats,
+        None => {
+            let paths = worktrees.clone();
+            let stats = tokio::task::spawn_blocking(move || expensive_stats(&paths))
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?
+                .map_err(|e| Status::from(DaemonError::from(e)))?;
+            state
+                .write()
+                .await
+                .repo_stats_cache
+                .insert(&req.repo_id, stats, Instant::now());
+            stats
+        }
+    };
+
+    Ok(Response::new(RepoStats {
+        repo_id: req.repo_id,
+        worktree_count: worktrees.len() as u32,
+        total_sessions,
+        running_sessions,
+        total_commits_ahead: expensive.commits_ahead,
+        total_uncommitted_changes,
+        disk_usage_kb: expensive.disk_usage_kb,
+    }))
+}
+
+/// Commits ahead and disk usage summed over the worktrees
+///
+/// Worktrees inside another one are already counted in its size.
+fn expensive_stats(worktrees: &[PathBuf]) -> Result<ExpensiveStats, GitError> {
+    let mut stats = ExpensiveStats::default();
+    for path in worktrees {
+        stats.commits_ahead += RepoStatsOps::commits_ahead(path)?;
+        let nested = worktrees
+            .iter()
+            .any(|other| other != path && path.starts_with(other));
+        if !nested {
+            stats.disk_usage_kb += RepoStatsOps::disk_usage_kb(path);
+        }
+    }
+    Ok(stats)
+}
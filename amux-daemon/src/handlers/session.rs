@@ -1,15 +1,27 @@
 //! Session management handlers
 
 use super::get_repo_and_open_git;
+use crate::checkpoint;
 use crate::error::{DaemonError, SessionError};
 use crate::events::EventBroadcaster;
 use crate::git::GitOps;
+use crate::history_search::{self, LineMatch, Matcher};
 use crate::persistence;
-use crate::providers::ProviderRef;
-use crate::session::{self, Session, SessionStatus};
+use crate::providers::{self, ProviderRef};
+use crate::session::{self, Session, SessionKind, SessionStatus};
 use crate::state::SharedState;
+use crate::transcript;
+use crate::trash;
+use crate::workdir;
 use amux_config::{DEFAULT_TERMINAL_COLS, DEFAULT_TERMINAL_ROWS};
 use amux_proto::daemon::*;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::pin::Pin;
+use tokio::sync::mpsc;
+use tokio_stream::{wrappers::ReceiverStream, Stream};
 use tonic::{Response, Status};
 
 // Proto session status enum values
@@ -17,6 +29,7 @@ mod session_status {
     pub enum SessionStatus {
         Running = 1,
         Stopped = 2,
+        Trashed = 3,
     }
 }
 
@@ -38,15 +51,30 @@ pub async fn list_sessions(
         }
     }
 
+    let query = req
+        .query
+        .as_deref()
+        .map(str::trim)
+        .filter(|q| !q.is_empty());
+
     let state = state.read().await;
-    let sessions: Vec<SessionInfo> = state
+    let mut sessions: Vec<SessionInfo> = state
         .sessions
         .values()
         .filter(|s| {
             req.repo_id.as_ref().is_none_or(|id| &s.repo_id == id)
                 && req.branch.as_ref().is_none_or(|b| &s.branch == b)
         })
-        .map(|s| SessionInfo {
+        .filter_map(|s| {
+            // Linear scan over names and in-memory transcripts
+            let match_context = match query {
+                None => None,
+                Some(q) if s.name.to_lowercase().contains(&q.to_lowercase()) => None,
+                Some(q) => Some(s.find_in_transcript(q)?),
+            };
+            Some((s, match_context))
+        })
+        .map(|(s, match_context)| SessionInfo {
             id: s.id.clone(),
             name: s.name.clone(),
             repo_id: s.repo_id.clone(),
@@ -55,14 +83,66 @@ pub async fn list_sessions(
             status: match s.status() {
                 SessionStatus::Running => session_status::SessionStatus::Running as i32,
                 SessionStatus::Stopped => session_status::SessionStatus::Stopped as i32,
+                SessionStatus::Trashed => session_status::SessionStatus::Trashed as i32,
             },
             provider_session_id: s.provider_session_id().map(|s| s.to_string()),
             is_shell: Some(s.is_shell()),
             provider: Some(s.provider.clone()),
+            match_context,
+            title: s.title(),
+            memory_kb: s.usage.map_or(0, |u| u.memory_kb),
+            cpu_percent: s.usage.map_or(0.0, |u| u.cpu_percent),
+            attach_count: s.attach_count(),
+            pinned: s.pinned,
+            sort_key: s.sort_key,
+            system_prompt: s.system_prompt.clone(),
+            command: s.kind.command().to_vec(),
+            cwd: s.cwd.as_ref().map(|c| c.to_string_lossy().to_string()),
+            model: s.model.clone(),
+            created_at: s.created_at,
+            started_at: s.started_at,
+            last_output_at: s.last_output_at(),
         })
         .collect();
 
-    Ok(Response::new(ListSessionsResponse { sessions }))
+    // Each worktree's pinned sessions first, then by position
+    sessions.sort_by(|a, b| {
+        (&a.repo_id, &a.branch, !a.pinned, a.sort_key)
+            .cmp(&(&b.repo_id, &b.branch, !b.pinned, b.sort_key))
+    });
+
+    let offset = decode_page_token(&req.page_token)
+        .ok_or_else(|| Status::invalid_argument("Invalid page token"))?
+        .min(sessions.len());
+    let end = match req.page_size {
+        0 => sessions.len(),
+        size => (offset + size as usize).min(sessions.len()),
+    };
+    let next_page_token = if end < sessions.len() {
+        encode_page_token(end)
+    } else {
+        String::new()
+    };
+    let sessions = sessions.drain(offset..end).collect();
+
+    Ok(Response::new(ListSessionsResponse {
+        sessions,
+        next_page_token,
+    }))
+}
+
+/// Page token for the sessions starting at `offset`
+fn encode_page_token(offset: usize) -> String {
+    URL_SAFE_NO_PAD.encode(offset.to_string())
+}
+
+/// Offset of a page token (0 for the first page, None if malformed)
+fn decode_page_token(token: &str) -> Option<usize> {
+    if token.is_empty() {
+        return Some(0);
+    }
+    let bytes = URL_SAFE_NO_PAD.decode(token).ok()?;
+    String::from_utf8(bytes).ok()?.parse().ok()
 }
 
 /// Create a new session
@@ -72,53 +152,82 @@ pub async fn create_session(
     req: CreateSessionRequest,
 ) -> Result<Response<SessionInfo>, Status> {
     let (repo, git_repo) = get_repo_and_open_git(state, &req.repo_id).await?;
-
-    let mut state_guard = state.write().await;
+    // Fail before touching the worktree if a variable can't be expanded
+    let env = crate::env::session_env(&repo, &req.branch)
+        .map_err(|e| Status::from(DaemonError::from(e)))?;
 
     // Find or create worktree
     let worktree_path = match GitOps::find_worktree_path(&git_repo, &req.branch) {
         Some(path) => path,
         None => {
-            // Auto-create worktree (uses HEAD as base for new branch)
+            // Auto-create worktree (uses HEAD as base for new branch); a
+            // concurrent create for the branch may have made it meanwhile
             GitOps::create_worktree(&git_repo, &req.branch, &repo.path, None)
+                .or_else(|e| GitOps::find_worktree_path(&git_repo, &req.branch).ok_or(e))
                 .map_err(|e| Status::from(DaemonError::from(e)))?
         }
     };
 
+    let cwd = match req.cwd.filter(|c| !c.is_empty()) {
+        Some(_) if req.is_shell != Some(true) => {
+            return Err(Status::invalid_argument(
+                "A working directory can only be set for shell sessions",
+            ));
+        }
+        Some(cwd) => Some(
+            workdir::resolve(&worktree_path, &cwd)
+                .map_err(|e| Status::from(DaemonError::from(e)))?,
+        ),
+        None => None,
+    };
+
     // Create session with auto-generated provider session ID
     let id = session::generate_session_id();
+    let command = req.command;
+    let is_command = !command.is_empty();
     let is_shell = req.is_shell.unwrap_or(false);
     let prompt = req.prompt;
 
-    // Validate provider and model for non-shell sessions
-    let (provider, model) = if is_shell {
-        // Shell sessions don't use providers
-        ("shell".to_string(), None)
-    } else {
-        // Validate provider and model using cached registry
-        let provider_ref = ProviderRef::new(
-            &state_guard.provider_registry,
-            req.provider.as_deref(),
-            req.model.as_deref(),
-        )
-        .map_err(|e| Status::invalid_argument(e.to_string()))?;
-
-        (provider_ref.name, Some(provider_ref.model))
+    let (provider, model, name) = {
+        let state_guard = state.read().await;
+
+        // Validate provider and model for non-shell sessions
+        let (provider, model) = if is_command {
+            ("command".to_string(), None)
+        } else if is_shell {
+            // Shell sessions don't use providers
+            ("shell".to_string(), None)
+        } else {
+            // Validate provider and model using cached registry
+            let provider_ref = ProviderRef::new(
+                &state_guard.provider_registry,
+                req.provider.as_deref(),
+                req.model.as_deref(),
+            )
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+            (provider_ref.name, Some(provider_ref.model))
+        };
+
+        // Generate session name based on provider (e.g., claude-1, codex-2)
+        let existing_names: Vec<String> = state_guard
+            .sessions
+            .values()
+            .filter(|s| s.repo_id == req.repo_id && s.branch == req.branch)
+            .map(|s| s.name.clone())
+            .collect();
+
+        let name = req.name.filter(|n| !n.is_empty()).unwrap_or_else(|| {
+            if is_command {
+                // Named after its command line until renamed
+                command.join(" ")
+            } else {
+                session::generate_session_name(&provider, &existing_names)
+            }
+        });
+        (provider, model, name)
     };
 
-    // Generate session name based on provider (e.g., claude-1, codex-2)
-    let existing_names: Vec<String> = state_guard
-        .sessions
-        .values()
-        .filter(|s| s.repo_id == req.repo_id && s.branch == req.branch)
-        .map(|s| s.name.clone())
-        .collect();
-
-    let name = req
-        .name
-        .filter(|n| !n.is_empty())
-        .unwrap_or_else(|| session::generate_session_name(&provider, &existing_names));
-
     // Debug: log the provider being used
     tracing::info!(
         "Creating session with provider: {}, model: {:?}",
@@ -127,30 +236,82 @@ pub async fn create_session(
     );
 
     // Shell sessions don't need provider session ID
-    let provider_session_id = if is_shell {
+    let provider_session_id = if is_shell || is_command {
         None
     } else {
         Some(uuid::Uuid::new_v4().to_string())
     };
 
-    let mut session = Session::new(
-        id.clone(),
-        name.clone(),
-        req.repo_id.clone(),
-        req.branch.clone(),
-        worktree_path.clone(),
-        provider,
-        provider_session_id.clone(),
-        is_shell,
-        model,
-        prompt,
-    );
+    // Snapshot the worktree before the agent touches it. Staging a large
+    // worktree takes a while, so it runs off the runtime and without the
+    // state lock held.
+    let is_agent = !is_shell && !is_command;
+    let checkpoint = if is_agent && repo.checkpoints {
+        let (path, session_id, session_name, branch) = (
+            worktree_path.clone(),
+            id.clone(),
+            name.clone(),
+            req.branch.clone(),
+        );
+        let created = tokio::task::spawn_blocking(move || {
+            checkpoint::create(&path, &session_id, &session_name, &branch)
+        })
+        .await;
+        match created {
+            Ok(Ok(checkpoint)) => Some(checkpoint.oid.to_string()),
+            Ok(Err(e)) => {
+                tracing::warn!("Failed to checkpoint worktree for session {}: {}", id, e);
+                None
+            }
+            Err(e) => {
+                tracing::warn!("Checkpoint task for session {} failed: {}", id, e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let mut state_guard = state.write().await;
+
+    let mut session = if is_command {
+        Session::with_kind(
+            id.clone(),
+            name.clone(),
+            req.repo_id.clone(),
+            req.branch.clone(),
+            worktree_path.clone(),
+            provider,
+            SessionKind::Command { argv: command },
+            None,
+            None,
+        )
+    } else {
+        Session::new(
+            id.clone(),
+            name.clone(),
+            req.repo_id.clone(),
+            req.branch.clone(),
+            worktree_path.clone(),
+            provider,
+            provider_session_id.clone(),
+            is_shell,
+            model,
+            prompt,
+        )
+    };
+    session.sort_key = state_guard.next_sort_key(&req.repo_id, &req.branch);
+    session.cwd = cwd.filter(|c| !c.as_os_str().is_empty());
+    if is_agent {
+        session.system_prompt = req.system_prompt.filter(|p| !p.trim().is_empty());
+    }
+    session.checkpoint = checkpoint;
 
     // Start session with provided size (or defaults)
     let rows = req.rows.map(|r| r as u16).unwrap_or(DEFAULT_TERMINAL_ROWS);
     let cols = req.cols.map(|c| c as u16).unwrap_or(DEFAULT_TERMINAL_COLS);
     session
-        .start_with_size(&state_guard.provider_registry, rows, cols)
+        .start_with_size(&state_guard.provider_registry, &env, rows, cols)
         .map_err(|e| Status::from(DaemonError::Session(SessionError::Start(e.to_string()))))?;
 
     let info = SessionInfo {
@@ -163,6 +324,23 @@ pub async fn create_session(
         provider_session_id,
         is_shell: Some(session.is_shell()),
         provider: Some(session.provider.clone()),
+        match_context: None,
+        title: session.title(),
+        memory_kb: session.usage.map_or(0, |u| u.memory_kb),
+        cpu_percent: session.usage.map_or(0.0, |u| u.cpu_percent),
+        attach_count: session.attach_count(),
+        pinned: session.pinned,
+        sort_key: session.sort_key,
+        system_prompt: session.system_prompt.clone(),
+        command: session.kind.command().to_vec(),
+        cwd: session
+            .cwd
+            .as_ref()
+            .map(|c| c.to_string_lossy().to_string()),
+        model: session.model.clone(),
+        created_at: session.created_at,
+        started_at: session.started_at,
+        last_output_at: session.last_output_at(),
     };
 
     // Save session metadata to disk
@@ -170,7 +348,11 @@ pub async fn create_session(
         tracing::warn!("Failed to persist session metadata: {}", e);
     }
 
-    state_guard.sessions.insert(id, session);
+    state_guard.sessions.insert(id.clone(), session);
+    if let Some(session) = state_guard.sessions.get_mut(&id) {
+        // Read output from the start so activity is reported before any attach
+        super::attach::session_output(state, events, session);
+    }
 
     // Emit session created event
     events.emit_session_created(info.clone());
@@ -178,6 +360,247 @@ pub async fn create_session(
     Ok(Response::new(info))
 }
 
+/// Get live process stats for a session
+pub async fn get_session_stats(
+    state: &SharedState,
+    req: GetSessionStatsRequest,
+) -> Result<Response<SessionStats>, Status> {
+    let state = state.read().await;
+
+    let session = state.sessions.get(&req.session_id).ok_or_else(|| {
+        Status::from(DaemonError::Session(SessionError::NotFound(
+            req.session_id.clone(),
+        )))
+    })?;
+
+    let usage = session.usage.unwrap_or_default();
+    Ok(Response::new(SessionStats {
+        session_id: session.id.clone(),
+        status: match session.status() {
+            SessionStatus::Running => session_status::SessionStatus::Running as i32,
+            SessionStatus::Stopped => session_status::SessionStatus::Stopped as i32,
+            SessionStatus::Trashed => session_status::SessionStatus::Trashed as i32,
+        },
+        pid: session.pid().map(|pid| pid as u32),
+        memory_kb: usage.memory_kb,
+        cpu_percent: usage.cpu_percent,
+    }))
+}
+
+/// Type alias for the history search stream
+pub type SearchSessionHistoryStream =
+    Pin<Box<dyn Stream<Item = Result<HistoryMatch, Status>> + Send + 'static>>;
+
+/// Matches returned when the request sets no limit
+const DEFAULT_SEARCH_LIMIT: usize = 200;
+
+/// Search the output history of sessions, streaming matches as they're found
+///
+/// Sessions with output in memory are scanned from it (the bytes replayed on
+/// attach), others from their persisted history file. The scan stops once
+/// the client drops the stream.
+pub async fn search_session_history(
+    state: &SharedState,
+    req: SearchSessionHistoryRequest,
+) -> Result<Response<SearchSessionHistoryStream>, Status> {
+    let query = req.query.trim();
+    if query.is_empty() {
+        return Err(Status::invalid_argument("Search query is empty"));
+    }
+    let matcher = Matcher::new(query, req.regex)
+        .map_err(|e| Status::invalid_argument(format!("Invalid regex: {}", e)))?;
+    let mut remaining = match req.limit {
+        0 => DEFAULT_SEARCH_LIMIT,
+        limit => limit as usize,
+    };
+
+    // Snapshot the sessions so the state lock isn't held while scanning
+    let mut targets: Vec<(HistoryMatch, Vec<u8>)> = {
+        let state = state.read().await;
+        state
+            .sessions
+            .values()
+            .filter(|s| req.repo_id.as_ref().is_none_or(|id| &s.repo_id == id))
+            .map(|s| {
+                let target = HistoryMatch {
+                    session_id: s.id.clone(),
+                    session_name: s.name.clone(),
+                    repo_id: s.repo_id.clone(),
+                    branch: s.branch.clone(),
+                    ..Default::default()
+                };
+                (target, s.get_screen_state())
+            })
+            .collect()
+    };
+    targets
+        .sort_by(|(a, _), (b, _)| (&a.branch, &a.session_name).cmp(&(&b.branch, &b.session_name)));
+
+    let (tx, rx) = mpsc::channel::<Result<HistoryMatch, Status>>(32);
+    tokio::task::spawn_blocking(move || {
+        for (target, buffer) in targets {
+            let on_match = |m: LineMatch| {
+                let found = HistoryMatch {
+                    line: m.line,
+                    context_before: m.context_before,
+                    context_after: m.context_after,
+                    byte_offset: m.byte_offset,
+                    ..target.clone()
+                };
+                if tx.blocking_send(Ok(found)).is_err() {
+                    return false;
+                }
+                remaining -= 1;
+                remaining > 0
+            };
+            let cancelled = || tx.is_closed();
+
+            let finished = if buffer.is_empty() {
+                match File::open(persistence::session_history_file(&target.session_id)) {
+                    Ok(file) => {
+                        history_search::scan(BufReader::new(file), &matcher, cancelled, on_match)
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(true),
+                    Err(e) => Err(e),
+                }
+            } else {
+                history_search::scan(&buffer[..], &matcher, cancelled, on_match)
+            };
+            match finished {
+                Ok(true) => {}
+                // Limit reached or client gone
+                Ok(false) => break,
+                Err(e) => {
+                    tracing::warn!("Failed to search history of {}: {}", target.session_id, e);
+                }
+            }
+        }
+    });
+
+    Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+}
+
+/// Export a session's transcript as plain text or Markdown
+pub async fn export_session_transcript(
+    state: &SharedState,
+    req: ExportSessionTranscriptRequest,
+) -> Result<Response<SessionTranscript>, Status> {
+    let (name, history, conversation_source) = {
+        let state = state.read().await;
+        let session = state.sessions.get(&req.session_id).ok_or_else(|| {
+            Status::from(DaemonError::Session(SessionError::NotFound(
+                req.session_id.clone(),
+            )))
+        })?;
+        let conversation_source = session
+            .provider_session_id()
+            .filter(|_| session.provider == "claude")
+            .map(|id| (session.worktree_path.clone(), id.to_string()));
+        (
+            session.name.clone(),
+            session.get_screen_state(),
+            conversation_source,
+        )
+    };
+
+    let markdown = req.format == TranscriptFormat::Markdown as i32;
+    if markdown {
+        let messages = conversation_source
+            .and_then(|(worktree_path, id)| providers::read_conversation(&worktree_path, &id))
+            .filter(|messages| !messages.is_empty());
+        if let Some(messages) = messages {
+            return Ok(Response::new(SessionTranscript {
+                content: transcript::conversation_markdown(&name, &messages),
+                from_conversation: true,
+            }));
+        }
+    }
+
+    // Stopped sessions may only have their persisted history
+    let history = if history.is_empty() {
+        persistence::load_session_history(&req.session_id)
+            .map_err(|e| Status::internal(e.to_string()))?
+    } else {
+        history
+    };
+    let plain = transcript::plain_transcript(&history);
+    Ok(Response::new(SessionTranscript {
+        content: if markdown {
+            transcript::markdown_transcript(&name, &plain)
+        } else {
+            plain
+        },
+        from_conversation: false,
+    }))
+}
+
+/// Largest part of a provider session file sent to clients
+const MAX_PROVIDER_SESSION_FILE_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Read the file the session's provider keeps its conversation in
+///
+/// Files over `MAX_PROVIDER_SESSION_FILE_BYTES` are cut at the last line
+/// end before the limit.
+pub async fn get_provider_session_file(
+    state: &SharedState,
+    req: GetProviderSessionFileRequest,
+) -> Result<Response<ProviderSessionFile>, Status> {
+    let path = {
+        let state = state.read().await;
+        let session = state.sessions.get(&req.session_id).ok_or_else(|| {
+            Status::from(DaemonError::Session(SessionError::NotFound(
+                req.session_id.clone(),
+            )))
+        })?;
+        let provider_session_id = session.provider_session_id().ok_or_else(|| {
+            Status::failed_precondition("Session has no provider session".to_string())
+        })?;
+        let provider = state
+            .provider_registry
+            .get(&session.provider)
+            .ok_or_else(|| {
+                Status::failed_precondition(format!(
+                    "Provider '{}' is not installed",
+                    session.provider
+                ))
+            })?;
+        provider
+            .read_session_info(provider_session_id, &session.worktree_path)
+            .map_err(|e| Status::internal(e.to_string()))?
+            .and_then(|info| info.file)
+            .ok_or_else(|| {
+                Status::not_found(format!(
+                    "{} has no session file for this session",
+                    provider.display_name()
+                ))
+            })?
+    };
+
+    let file = File::open(&path).map_err(|e| Status::internal(e.to_string()))?;
+    let len = file
+        .metadata()
+        .map_err(|e| Status::internal(e.to_string()))?
+        .len();
+    let mut content = Vec::new();
+    file.take(MAX_PROVIDER_SESSION_FILE_BYTES)
+        .read_to_end(&mut content)
+        .map_err(|e| Status::internal(e.to_string()))?;
+    let truncated = len > MAX_PROVIDER_SESSION_FILE_BYTES;
+    if truncated {
+        let end = content
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+        content.truncate(end);
+    }
+
+    Ok(Response::new(ProviderSessionFile {
+        path: path.to_string_lossy().into_owned(),
+        content: String::from_utf8_lossy(&content).into_owned(),
+        truncated,
+    }))
+}
+
 /// Rename a session
 pub async fn rename_session(
     state: &SharedState,
@@ -210,10 +633,28 @@ pub async fn rename_session(
         status: match session.status() {
             SessionStatus::Running => session_status::SessionStatus::Running as i32,
             SessionStatus::Stopped => session_status::SessionStatus::Stopped as i32,
+            SessionStatus::Trashed => session_status::SessionStatus::Trashed as i32,
         },
         provider_session_id: session.provider_session_id().map(|s| s.to_string()),
         is_shell: Some(session.is_shell()),
         provider: Some(session.provider.clone()),
+        match_context: None,
+        title: session.title(),
+        memory_kb: session.usage.map_or(0, |u| u.memory_kb),
+        cpu_percent: session.usage.map_or(0.0, |u| u.cpu_percent),
+        attach_count: session.attach_count(),
+        pinned: session.pinned,
+        sort_key: session.sort_key,
+        system_prompt: session.system_prompt.clone(),
+        command: session.kind.command().to_vec(),
+        cwd: session
+            .cwd
+            .as_ref()
+            .map(|c| c.to_string_lossy().to_string()),
+        model: session.model.clone(),
+        created_at: session.created_at,
+        started_at: session.started_at,
+        last_output_at: session.last_output_at(),
     };
 
     // Emit session name updated event
@@ -222,6 +663,73 @@ pub async fn rename_session(
     Ok(Response::new(info))
 }
 
+/// Pin/unpin a session or move it within its worktree
+pub async fn update_session_meta(
+    state: &SharedState,
+    req: UpdateSessionMetaRequest,
+) -> Result<Response<SessionInfo>, Status> {
+    let mut state = state.write().await;
+
+    let session = state.sessions.get_mut(&req.session_id).ok_or_else(|| {
+        Status::from(DaemonError::Session(SessionError::NotFound(
+            req.session_id.clone(),
+        )))
+    })?;
+
+    if let Some(pinned) = req.pinned {
+        session.pinned = pinned;
+        if let Err(e) = persistence::save_session_meta(session) {
+            tracing::warn!("Failed to persist session metadata: {}", e);
+        }
+    }
+    let (repo_id, branch) = (session.repo_id.clone(), session.branch.clone());
+
+    if let Some(position) = req.sort_key {
+        let moved = Some((req.session_id.as_str(), position as usize));
+        for id in state.renumber_sessions(&repo_id, &branch, moved) {
+            if let Some(session) = state.sessions.get(&id) {
+                if let Err(e) = persistence::save_session_meta(session) {
+                    tracing::warn!("Failed to persist session metadata: {}", e);
+                }
+            }
+        }
+    }
+
+    let session = &state.sessions[&req.session_id];
+    Ok(Response::new(SessionInfo {
+        id: session.id.clone(),
+        name: session.name.clone(),
+        repo_id: session.repo_id.clone(),
+        branch: session.branch.clone(),
+        worktree_path: session.worktree_path.to_string_lossy().to_string(),
+        status: match session.status() {
+            SessionStatus::Running => session_status::SessionStatus::Running as i32,
+            SessionStatus::Stopped => session_status::SessionStatus::Stopped as i32,
+            SessionStatus::Trashed => session_status::SessionStatus::Trashed as i32,
+        },
+        provider_session_id: session.provider_session_id().map(|s| s.to_string()),
+        is_shell: Some(session.is_shell()),
+        provider: Some(session.provider.clone()),
+        match_context: None,
+        title: session.title(),
+        memory_kb: session.usage.map_or(0, |u| u.memory_kb),
+        cpu_percent: session.usage.map_or(0.0, |u| u.cpu_percent),
+        attach_count: session.attach_count(),
+        pinned: session.pinned,
+        sort_key: session.sort_key,
+        system_prompt: session.system_prompt.clone(),
+        command: session.kind.command().to_vec(),
+        cwd: session
+            .cwd
+            .as_ref()
+            .map(|c| c.to_string_lossy().to_string()),
+        model: session.model.clone(),
+        created_at: session.created_at,
+        started_at: session.started_at,
+        last_output_at: session.last_output_at(),
+    }))
+}
+
 /// Destroy a session
 pub async fn destroy_session(
     state: &SharedState,
@@ -245,9 +753,28 @@ pub async fn destroy_session(
         tracing::warn!("Failed to stop session {}: {}", req.session_id, e);
     }
 
-    // Delete persisted session data
-    if let Err(e) = persistence::delete_session_data(&req.session_id) {
-        tracing::warn!("Failed to delete session data: {}", e);
+    if req.trash {
+        // Keep the data around so the destroy can be undone
+        session.trashed_at = Some(trash::now_secs());
+        if let Err(e) = persistence::save_session(&session) {
+            tracing::warn!("Failed to persist trashed session: {}", e);
+        }
+        state.trash.insert(session_id.clone(), session);
+    } else {
+        // Delete persisted session data
+        if let Err(e) = persistence::delete_session_data(&req.session_id) {
+            tracing::warn!("Failed to delete session data: {}", e);
+        }
+        if session.checkpoint.is_some() {
+            state.prune_checkpoint(&repo_id, &session_id);
+        }
+    }
+
+    // Close the gap left in the worktree's ordering
+    for id in state.renumber_sessions(&repo_id, &branch, None) {
+        if let Some(session) = state.sessions.get(&id) {
+            let _ = persistence::save_session_meta(session);
+        }
     }
 
     // Emit session destroyed event
@@ -256,6 +783,69 @@ pub async fn destroy_session(
     Ok(Response::new(Empty {}))
 }
 
+/// Restore a session from the trash (stopped, at the end of its worktree)
+pub async fn restore_session(
+    state: &SharedState,
+    events: &EventBroadcaster,
+    req: RestoreSessionRequest,
+) -> Result<Response<SessionInfo>, Status> {
+    let mut state = state.write().await;
+
+    let mut session = state.trash.remove(&req.session_id).ok_or_else(|| {
+        Status::from(DaemonError::Session(SessionError::NotFound(
+            req.session_id.clone(),
+        )))
+    })?;
+
+    if !session.worktree_path.exists() {
+        let _ = persistence::delete_session_data(&session.id);
+        return Err(Status::failed_precondition(format!(
+            "Worktree no longer exists: {}",
+            session.worktree_path.display()
+        )));
+    }
+
+    session.trashed_at = None;
+    session.sort_key = state.next_sort_key(&session.repo_id, &session.branch);
+    if let Err(e) = persistence::save_session_meta(&session) {
+        tracing::warn!("Failed to persist session metadata: {}", e);
+    }
+
+    let info = SessionInfo {
+        id: session.id.clone(),
+        name: session.name.clone(),
+        repo_id: session.repo_id.clone(),
+        branch: session.branch.clone(),
+        worktree_path: session.worktree_path.to_string_lossy().to_string(),
+        status: session_status::SessionStatus::Stopped as i32,
+        provider_session_id: session.provider_session_id().map(|s| s.to_string()),
+        is_shell: Some(session.is_shell()),
+        provider: Some(session.provider.clone()),
+        match_context: None,
+        title: session.title(),
+        memory_kb: 0,
+        cpu_percent: 0.0,
+        attach_count: session.attach_count(),
+        pinned: session.pinned,
+        sort_key: session.sort_key,
+        system_prompt: session.system_prompt.clone(),
+        command: session.kind.command().to_vec(),
+        cwd: session
+            .cwd
+            .as_ref()
+            .map(|c| c.to_string_lossy().to_string()),
+        model: session.model.clone(),
+        created_at: session.created_at,
+        started_at: session.started_at,
+        last_output_at: session.last_output_at(),
+    };
+
+    state.sessions.insert(session.id.clone(), session);
+    events.emit_session_created(info.clone());
+
+    Ok(Response::new(info))
+}
+
 /// Stop a session (kill PTY but keep metadata)
 pub async fn stop_session(
     state: &SharedState,
@@ -274,6 +864,7 @@ pub async fn stop_session(
     let old_status = match session.status() {
         SessionStatus::Running => session_status::SessionStatus::Running as i32,
         SessionStatus::Stopped => session_status::SessionStatus::Stopped as i32,
+        SessionStatus::Trashed => session_status::SessionStatus::Trashed as i32,
     };
 
     // Stop session (kill PTY)
@@ -0,0 +1,124 @@
+//! Stash operation handlers
+
+use super::get_worktree_path;
+use crate::error::DaemonError;
+use crate::events::EventBroadcaster;
+use crate::git::{GitOps, StashApplyOutcome, StashEntry as GitStashEntry};
+use crate::state::SharedState;
+use amux_proto::daemon::{
+    Empty, StashApplyResponse, StashEntry, StashListRequest, StashListResponse, StashRefRequest,
+    StashSaveRequest,
+};
+use tonic::{Response, Status};
+
+/// Stash local changes of a worktree
+pub async fn stash_save(
+    state: &SharedState,
+    events: &EventBroadcaster,
+    req: StashSaveRequest,
+) -> Result<Response<StashEntry>, Status> {
+    let worktree_path = get_worktree_path(state, &req.repo_id, &req.branch).await?;
+    let (message, include_untracked) = (req.message, req.include_untracked);
+
+    let entry = tokio::task::spawn_blocking(move || {
+        let mut wt_repo = GitOps::open(&worktree_path)?;
+        GitOps::stash_save(&mut wt_repo, &message, include_untracked)
+    })
+    .await
+    .map_err(|e| Status::internal(e.to_string()))?
+    .map_err(|e| Status::from(DaemonError::from(e)))?;
+
+    events.emit_git_status_changed(req.repo_id, req.branch);
+    Ok(Response::new(stash_entry_to_proto(entry)))
+}
+
+/// List stashes made on a worktree's branch
+pub async fn stash_list(
+    state: &SharedState,
+    req: StashListRequest,
+) -> Result<Response<StashListResponse>, Status> {
+    let worktree_path = get_worktree_path(state, &req.repo_id, &req.branch).await?;
+    let branch = req.branch;
+
+    let entries = tokio::task::spawn_blocking(move || {
+        let mut wt_repo = GitOps::open(&worktree_path)?;
+        GitOps::stash_list(&mut wt_repo, &branch)
+    })
+    .await
+    .map_err(|e| Status::internal(e.to_string()))?
+    .map_err(|e| Status::from(DaemonError::from(e)))?;
+
+    Ok(Response::new(StashListResponse {
+        stashes: entries.into_iter().map(stash_entry_to_proto).collect(),
+    }))
+}
+
+/// Apply a stash, dropping it afterwards when `pop` is set
+pub async fn stash_apply(
+    state: &SharedState,
+    events: &EventBroadcaster,
+    req: StashRefRequest,
+    pop: bool,
+) -> Result<Response<StashApplyResponse>, Status> {
+    let worktree_path = get_worktree_path(state, &req.repo_id, &req.branch).await?;
+    let index = req.index as usize;
+
+    let outcome = tokio::task::spawn_blocking(move || {
+        let mut wt_repo = GitOps::open(&worktree_path)?;
+        GitOps::stash_apply(&mut wt_repo, index, pop)
+    })
+    .await
+    .map_err(|e| Status::internal(e.to_string()))?
+    .map_err(|e| Status::from(DaemonError::from(e)))?;
+
+    events.emit_git_status_changed(req.repo_id, req.branch);
+
+    let verb = if pop { "Popped" } else { "Applied" };
+    let response = match outcome {
+        StashApplyOutcome::Applied => StashApplyResponse {
+            success: true,
+            message: format!("{} stash@{{{}}}", verb, index),
+            conflicted_files: vec![],
+        },
+        StashApplyOutcome::Conflicts(files) => StashApplyResponse {
+            success: false,
+            message: format!(
+                "stash@{{{}}} conflicts in {} file(s); stash kept",
+                index,
+                files.len()
+            ),
+            conflicted_files: files,
+        },
+    };
+    Ok(Response::new(response))
+}
+
+/// Drop a stash
+pub async fn stash_drop(
+    state: &SharedState,
+    events: &EventBroadcaster,
+    req: StashRefRequest,
+) -> Result<Response<Empty>, Status> {
+    let worktree_path = get_worktree_path(state, &req.repo_id, &req.branch).await?;
+    let index = req.index as usize;
+
+    tokio::task::spawn_blocking(move || {
+        let mut wt_repo = GitOps::open(&worktree_path)?;
+        GitOps::stash_drop(&mut wt_repo, index)
+    })
+    .await
+    .map_err(|e| Status::internal(e.to_string()))?
+    .map_err(|e| Status::from(DaemonError::from(e)))?;
+
+    events.emit_git_status_changed(req.repo_id, req.branch);
+    Ok(Response::new(Empty {}))
+}
+
+fn stash_entry_to_proto(entry: GitStashEntry) -> StashEntry {
+    StashEntry {
+        index: entry.index as u32,
+        message: entry.message,
+        oid: entry.oid.to_string(),
+        created_at: entry.time,
+    }
+}
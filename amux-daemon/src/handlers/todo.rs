@@ -4,6 +4,7 @@ use crate::error::DaemonError;
 use crate::state::SharedState;
 use crate::todo::TodoOps;
 use amux_proto::daemon::*;
+use chrono::{DateTime, Utc};
 use tonic::{Response, Status};
 
 /// Helper to convert internal todo to proto TodoItem
@@ -21,6 +22,22 @@ fn to_proto_item(todo: crate::todo::TodoItem) -> TodoItem {
     }
 }
 
+/// Helper to convert a proto TodoItem back to an internal todo
+fn from_proto_item(item: TodoItem) -> crate::todo::TodoItem {
+    let timestamp = |secs| DateTime::from_timestamp(secs, 0).unwrap_or_else(Utc::now);
+    crate::todo::TodoItem {
+        id: item.id,
+        repo_id: item.repo_id,
+        title: item.title,
+        description: item.description,
+        completed: item.completed,
+        parent_id: item.parent_id,
+        order: item.order,
+        created_at: timestamp(item.created_at),
+        updated_at: timestamp(item.updated_at),
+    }
+}
+
 /// Helper to find repo_id for a todo item
 async fn find_repo_id_for_todo(state: &SharedState, todo_id: &str) -> Option<String> {
     let state = state.read().await;
@@ -66,14 +83,30 @@ pub async fn update_todo(
 pub async fn delete_todo(
     state: &SharedState,
     req: DeleteTodoRequest,
-) -> Result<Response<Empty>, Status> {
+) -> Result<Response<DeleteTodoResponse>, Status> {
     let repo_id = find_repo_id_for_todo(state, &req.todo_id)
         .await
         .ok_or_else(|| Status::not_found("TODO item not found"))?;
 
-    TodoOps::delete_todo(&repo_id, &req.todo_id).map_err(|e| Status::from(DaemonError::from(e)))?;
+    let deleted = TodoOps::delete_todo(&repo_id, &req.todo_id)
+        .map_err(|e| Status::from(DaemonError::from(e)))?;
+
+    Ok(Response::new(DeleteTodoResponse {
+        deleted: deleted.into_iter().map(to_proto_item).collect(),
+    }))
+}
+
+/// Restore deleted TODO items
+pub async fn restore_todos(
+    req: RestoreTodosRequest,
+) -> Result<Response<RestoreTodosResponse>, Status> {
+    let items = req.items.into_iter().map(from_proto_item).collect();
+    let restored = TodoOps::restore_todos(&req.repo_id, items)
+        .map_err(|e| Status::from(DaemonError::from(e)))?;
 
-    Ok(Response::new(Empty {}))
+    Ok(Response::new(RestoreTodosResponse {
+        items: restored.into_iter().map(to_proto_item).collect(),
+    }))
 }
 
 /// List all TODO items
@@ -0,0 +1,188 @@
+//! Workspace export/import handlers
+
+use crate::archive::{self, ArchivedSession, Manifest, Workspace};
+use crate::events::EventBroadcaster;
+use crate::git::GitOps;
+use crate::persistence::{self, SessionMeta};
+use crate::repo;
+use crate::session::Session;
+use crate::state::SharedState;
+use amux_proto::daemon::*;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use tonic::{Response, Status};
+
+// Proto session status enum value for imported sessions
+const SESSION_STATUS_STOPPED: i32 = 2;
+
+/// Write repos, session metadata and terminal history to an archive
+pub async fn export_workspace(
+    state: &SharedState,
+    req: ExportWorkspaceRequest,
+) -> Result<Response<ExportResult>, Status> {
+    let path = PathBuf::from(&req.output_path);
+    if !path.is_absolute() {
+        return Err(relative_path(&path));
+    }
+
+    let workspace = {
+        let state = state.read().await;
+        Workspace {
+            manifest: Manifest::current(),
+            repos: state.ordered_repos(),
+            sessions: state
+                .sessions
+                .values()
+                .filter(|s| s.kind.should_persist())
+                .map(|s| ArchivedSession {
+                    meta: SessionMeta::from_session(s),
+                    history: s.get_screen_state(),
+                })
+                .collect(),
+        }
+    };
+    let repo_count = workspace.repos.len() as u32;
+    let session_count = workspace.sessions.len() as u32;
+
+    let written = path.clone();
+    tokio::task::spawn_blocking(move || {
+        let file = File::create(&written)?;
+        archive::write(BufWriter::new(file), &workspace)
+    })
+    .await
+    .map_err(|e| Status::internal(e.to_string()))?
+    .map_err(|e| Status::internal(format!("Failed to write {}: {}", path.display(), e)))?;
+
+    Ok(Response::new(ExportResult {
+        path: path.to_string_lossy().to_string(),
+        repo_count,
+        session_count,
+    }))
+}
+
+/// Merge an archive into the current state
+///
+/// Repos and sessions already present are kept as they are. Repos whose path
+/// isn't a git repository on this machine are skipped, along with sessions
+/// whose repo or worktree is missing.
+pub async fn import_workspace(
+    state: &SharedState,
+    events: &EventBroadcaster,
+    req: ImportWorkspaceRequest,
+) -> Result<Response<ImportResult>, Status> {
+    let path = PathBuf::from(&req.archive_path);
+    if !path.is_absolute() {
+        return Err(relative_path(&path));
+    }
+
+    let read = path.clone();
+    let workspace = tokio::task::spawn_blocking(move || {
+        let file = File::open(&read)?;
+        archive::read(BufReader::new(file))
+    })
+    .await
+    .map_err(|e| Status::internal(e.to_string()))?
+    .map_err(|e| Status::invalid_argument(format!("Failed to read {}: {}", path.display(), e)))?;
+
+    let mut result = ImportResult {
+        daemon_version: workspace.manifest.daemon_version,
+        ..Default::default()
+    };
+    let mut state = state.write().await;
+
+    for r in workspace.repos {
+        if state.repos.contains_key(&r.id) {
+            continue;
+        }
+        if !GitOps::is_git_repo(&r.path) {
+            result
+                .skipped
+                .push(format!("repo {}: {} not found", r.name, r.path.display()));
+            continue;
+        }
+        state.insert_repo(r);
+        result.imported_repos += 1;
+    }
+    if result.imported_repos > 0 {
+        let repos = state.ordered_repos();
+        if let Err(e) = repo::save_repos(&repos) {
+            tracing::warn!("Failed to save imported repos: {}", e);
+        }
+    }
+
+    for archived in workspace.sessions {
+        let meta = archived.meta;
+        let skip_reason = if state.sessions.contains_key(&meta.id) {
+            Some("already exists".to_string())
+        } else if meta.trashed_at.is_some() {
+            Some("in the trash".to_string())
+        } else if !state.repos.contains_key(&meta.repo_id) {
+            Some("repo not imported".to_string())
+        } else if !meta.worktree_path.exists() {
+            Some(format!("{} not found", meta.worktree_path.display()))
+        } else {
+            None
+        };
+        if let Some(reason) = skip_reason {
+            result
+                .skipped
+                .push(format!("session {}: {}", meta.name, reason));
+            continue;
+        }
+
+        if !archived.history.is_empty() {
+            if let Err(e) = persistence::write_session_history(&meta.id, &archived.history) {
+                result.skipped.push(format!("session {}: {}", meta.name, e));
+                continue;
+            }
+        }
+        let mut session = Session::from_meta(meta);
+        if let Err(e) = session.load_history() {
+            tracing::warn!("Failed to load history for session {}: {}", session.id, e);
+        }
+        session.sort_key = state.next_sort_key(&session.repo_id, &session.branch);
+        if let Err(e) = persistence::save_session_meta(&session) {
+            tracing::warn!("Failed to persist session metadata: {}", e);
+        }
+
+        events.emit_session_created(SessionInfo {
+            id: session.id.clone(),
+            name: session.name.clone(),
+            repo_id: session.repo_id.clone(),
+            branch: session.branch.clone(),
+            worktree_path: session.worktree_path.to_string_lossy().to_string(),
+            status: SESSION_STATUS_STOPPED,
+            provider_session_id: session.provider_session_id().map(|s| s.to_string()),
+            is_shell: Some(session.is_shell()),
+            provider: Some(session.provider.clone()),
+            match_context: None,
+            title: session.title(),
+            memory_kb: 0,
+            cpu_percent: 0.0,
+            attach_count: 0,
+            pinned: session.pinned,
+            sort_key: session.sort_key,
+            system_prompt: session.system_prompt.clone(),
+            command: session.kind.command().to_vec(),
+            cwd: session
+                .cwd
+                .as_ref()
+                .map(|c| c.to_string_lossy().to_string()),
+            model: session.model.clone(),
+            created_at: session.created_at,
+            started_at: session.started_at,
+            last_output_at: session.last_output_at(),
+        });
+        state.sessions.insert(session.id.clone(), session);
+        result.imported_sessions += 1;
+    }
+
+    Ok(Response::new(result))
+}
+
+/// Archive paths must be absolute: the daemon's working directory is
+/// unrelated to the caller's
+fn relative_path(path: &Path) -> Status {
+    Status::invalid_argument(format!("Archive path must be absolute: {}", path.display()))
+}
@@ -1,22 +1,28 @@
 //! Worktree management handlers
 
 use super::get_repo_and_open_git;
-use crate::error::DaemonError;
+use crate::cleanup;
+use crate::error::{DaemonError, GitError, RepoError};
 use crate::events::EventBroadcaster;
 use crate::git::GitOps;
+use crate::persistence;
+use crate::repo as repo_mod;
 use crate::state::SharedState;
+use crate::workdir;
+use crate::worktree_status::WorktreeStatusCache;
 use amux_proto::daemon::*;
-use std::collections::HashSet;
+use futures::future::join_all;
+use std::collections::{HashMap, HashSet};
 use tonic::{Response, Status};
 
 /// List all worktrees for a repository
 pub async fn list_worktrees(
     state: &SharedState,
+    status_cache: &WorktreeStatusCache,
     req: ListWorktreesRequest,
 ) -> Result<Response<ListWorktreesResponse>, Status> {
-    let (_repo, git_repo) = get_repo_and_open_git(state, &req.repo_id).await?;
-
-    let state = state.read().await;
+    let (repo, git_repo) = get_repo_and_open_git(state, &req.repo_id).await?;
+    let delete_action = |branch: &str| repo.delete_actions.get(branch).map(|a| a.to_proto() as i32);
 
     // Get worktrees from git
     let git_worktrees =
@@ -25,13 +31,30 @@ pub async fn list_worktrees(
     // Get all branches
     let branches =
         GitOps::list_branches(&git_repo).map_err(|e| Status::from(DaemonError::from(e)))?;
+    let head_shas: HashMap<String, String> = git_worktrees
+        .iter()
+        .map(|wt| wt.branch.as_str())
+        .chain(branches.iter().map(|b| b.as_str()))
+        .filter_map(|b| GitOps::branch_head_sha(&git_repo, b).map(|sha| (b.to_string(), sha)))
+        .collect();
+    drop(git_repo);
+
+    // Compute (or fetch cached) dirty/ahead/behind before taking the state lock
+    let summaries = join_all(
+        git_worktrees
+            .iter()
+            .map(|wt| status_cache.get_or_compute(&req.repo_id, &wt.branch, wt.path.clone())),
+    )
+    .await;
+
+    let state = state.read().await;
 
     // Build response: first include all worktrees (including main), then other branches
     let mut worktrees: Vec<WorktreeInfo> = Vec::new();
     let mut seen_branches: HashSet<String> = HashSet::new();
 
     // First: add all branches that have worktrees (this ensures main worktree is always included)
-    for wt in &git_worktrees {
+    for (wt, summary) in git_worktrees.iter().zip(summaries) {
         let session_count = state
             .sessions
             .values()
@@ -44,6 +67,17 @@ pub async fn list_worktrees(
             path: wt.path.to_string_lossy().to_string(),
             is_main: wt.is_main,
             session_count,
+            dirty: summary.map(|s| s.dirty).unwrap_or(false),
+            ahead: summary.map(|s| s.ahead).unwrap_or(0),
+            behind: summary.map(|s| s.behind).unwrap_or(0),
+            status_known: summary.is_some(),
+            diff_stats: summary.map(|s| DiffStats {
+                files_changed: s.diff_stats.files_changed,
+                insertions: s.diff_stats.insertions,
+                deletions: s.diff_stats.deletions,
+            }),
+            head_sha: head_shas.get(&wt.branch).cloned().unwrap_or_default(),
+            default_delete_action: delete_action(&wt.branch),
         });
         seen_branches.insert(wt.branch.clone());
     }
@@ -59,10 +93,13 @@ pub async fn list_worktrees(
 
             worktrees.push(WorktreeInfo {
                 repo_id: req.repo_id.clone(),
+                head_sha: head_shas.get(&branch).cloned().unwrap_or_default(),
+                default_delete_action: delete_action(&branch),
                 branch,
                 path: String::new(), // No worktree path
                 is_main: false,
                 session_count,
+                ..Default::default()
             });
         }
     }
@@ -92,6 +129,7 @@ pub async fn create_worktree(
         path: wt_path.to_string_lossy().to_string(),
         is_main: false,
         session_count: 0,
+        ..Default::default()
     };
 
     // Emit worktree added event for multi-instance sync
@@ -132,6 +170,36 @@ pub async fn remove_worktree(
     Ok(Response::new(Empty {}))
 }
 
+/// Prune stale worktrees and remove sessions whose worktree is gone
+pub async fn cleanup_worktrees(
+    state: &SharedState,
+    events: &EventBroadcaster,
+    status_cache: &WorktreeStatusCache,
+    req: CleanupWorktreesRequest,
+) -> Result<Response<CleanupResult>, Status> {
+    // Fails (and drops the repo) if the repo itself is gone
+    get_repo_and_open_git(state, &req.repo_id).await?;
+
+    let report = cleanup::cleanup_repo(&mut *state.write().await, &req.repo_id);
+
+    for branch in &report.pruned_branches {
+        status_cache.invalidate(&req.repo_id, branch);
+        events.emit_worktree_removed(req.repo_id.clone(), branch.clone());
+    }
+    for session in &report.removed_sessions {
+        events.emit_session_destroyed(
+            session.id.clone(),
+            session.repo_id.clone(),
+            session.branch.clone(),
+        );
+    }
+
+    Ok(Response::new(CleanupResult {
+        removed_count: report.removed_count(),
+        errors: report.errors,
+    }))
+}
+
 /// Delete a branch
 pub async fn delete_branch(
     state: &SharedState,
@@ -142,5 +210,156 @@ pub async fn delete_branch(
     GitOps::delete_branch(&git_repo, &req.branch)
         .map_err(|e| Status::from(DaemonError::from(e)))?;
 
+    update_worktree_settings(state, &req.repo_id, |repo| {
+        repo.delete_actions.remove(&req.branch);
+        repo.worktree_env.remove(&req.branch);
+    })
+    .await?;
+
+    Ok(Response::new(Empty {}))
+}
+
+/// Rename a branch, moving its worktree and re-mapping its sessions
+pub async fn rename_branch(
+    state: &SharedState,
+    events: &EventBroadcaster,
+    req: RenameBranchRequest,
+) -> Result<Response<RenameBranchResponse>, Status> {
+    let (_repo, git_repo) = get_repo_and_open_git(state, &req.repo_id).await?;
+
+    let wt_path = GitOps::rename_branch(&git_repo, &req.old_branch, &req.new_branch)
+        .map_err(|e| Status::from(DaemonError::from(e)))?;
+
+    // Sessions are keyed by branch name, so point them at the new one
+    let mut session_count = 0;
+    {
+        let mut state = state.write().await;
+        for session in state
+            .sessions
+            .values_mut()
+            .filter(|s| s.repo_id == req.repo_id && s.branch == req.old_branch)
+        {
+            session_count += 1;
+            session.branch = req.new_branch.clone();
+            if let Some(path) = &wt_path {
+                session.worktree_path = path.clone();
+            }
+            if let Err(e) = persistence::save_session_meta(session) {
+                tracing::warn!(
+                    "Failed to persist session metadata after branch rename: {}",
+                    e
+                );
+            }
+        }
+    }
+
+    update_worktree_settings(state, &req.repo_id, |repo| {
+        if let Some(action) = repo.delete_actions.remove(&req.old_branch) {
+            repo.delete_actions.insert(req.new_branch.clone(), action);
+        }
+        if let Some(env) = repo.worktree_env.remove(&req.old_branch) {
+            repo.worktree_env.insert(req.new_branch.clone(), env);
+        }
+    })
+    .await?;
+
+    if let Some(path) = &wt_path {
+        events.emit_worktree_removed(req.repo_id.clone(), req.old_branch.clone());
+        events.emit_worktree_added(WorktreeInfo {
+            repo_id: req.repo_id,
+            branch: req.new_branch,
+            path: path.to_string_lossy().to_string(),
+            is_main: false,
+            session_count,
+            ..Default::default()
+        });
+    }
+
+    Ok(Response::new(RenameBranchResponse {
+        worktree_path: wt_path.map(|p| p.to_string_lossy().to_string()),
+    }))
+}
+
+/// Set or clear the default session delete action of a worktree
+pub async fn set_worktree_delete_action(
+    state: &SharedState,
+    req: SetWorktreeDeleteActionRequest,
+) -> Result<Response<Empty>, Status> {
+    let action = req
+        .action
+        .and_then(|a| DeleteAction::try_from(a).ok())
+        .and_then(repo_mod::DeleteAction::from_proto);
+
+    update_worktree_settings(state, &req.repo_id, |repo| match action {
+        Some(action) => {
+            repo.delete_actions.insert(req.branch.clone(), action);
+        }
+        None => {
+            repo.delete_actions.remove(&req.branch);
+        }
+    })
+    .await?;
+
     Ok(Response::new(Empty {}))
 }
+
+/// List the entries of a directory in a worktree
+pub async fn list_directory(
+    state: &SharedState,
+    req: ListDirectoryRequest,
+) -> Result<Response<ListDirectoryResponse>, Status> {
+    let (_, git_repo) = get_repo_and_open_git(state, &req.repo_id).await?;
+    let worktree_path = GitOps::find_worktree_path(&git_repo, &req.branch).ok_or_else(|| {
+        Status::from(DaemonError::Git(GitError::BranchNotFound(
+            req.branch.clone(),
+        )))
+    })?;
+
+    // Ignore rules are read from the worktree's own checkout
+    let worktree_repo = if req.respect_gitignore {
+        let repo = git2::Repository::open(&worktree_path)
+            .map_err(|e| Status::from(DaemonError::from(GitError::from(e))))?;
+        Some(repo)
+    } else {
+        None
+    };
+    let ignored = |path: &std::path::Path| {
+        worktree_repo
+            .as_ref()
+            .is_some_and(|repo| repo.is_path_ignored(path).unwrap_or(false))
+    };
+
+    let entries = workdir::list(&worktree_path, &req.path, ignored)
+        .map_err(|e| Status::from(DaemonError::from(e)))?
+        .into_iter()
+        .map(|entry| DirectoryEntry {
+            name: entry.name,
+            is_dir: entry.is_dir,
+        })
+        .collect();
+    Ok(Response::new(ListDirectoryResponse { entries }))
+}
+
+/// Modify a repo's per-worktree settings (delete actions, environment) and
+/// persist repos.json if they changed
+async fn update_worktree_settings(
+    state: &SharedState,
+    repo_id: &str,
+    update: impl FnOnce(&mut repo_mod::Repo),
+) -> Result<(), Status> {
+    let mut state = state.write().await;
+    let repo = state
+        .repos
+        .get_mut(repo_id)
+        .ok_or_else(|| Status::from(DaemonError::Repo(RepoError::NotFound(repo_id.to_string()))))?;
+
+    let before = (repo.delete_actions.clone(), repo.worktree_env.clone());
+    update(repo);
+    if (&repo.delete_actions, &repo.worktree_env) == (&before.0, &before.1) {
+        return Ok(());
+    }
+
+    let repos = state.ordered_repos();
+    drop(state);
+    repo_mod::save_repos(&repos).map_err(|e| Status::from(DaemonError::from(e)))
+}
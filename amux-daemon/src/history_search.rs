@@ -0,0 +1,204 @@
+//! Search of session output history
+//!
+//! History is scanned one raw line at a time, so a persisted history file
+//! is streamed from disk rather than loaded whole. Each raw line is stripped
+//! of escape sequences before matching; reported offsets point into the raw
+//! bytes, which is what attaching replays to the client.
+
+use crate::session::strip_ansi;
+use regex::{Regex, RegexBuilder};
+use std::collections::VecDeque;
+use std::io::{self, BufRead};
+
+/// Lines of context kept on each side of a match
+pub const CONTEXT_LINES: usize = 2;
+
+/// Longest matched or context line returned, in characters
+const MAX_LINE_CHARS: usize = 300;
+
+/// Case-insensitive matcher for a search query
+pub enum Matcher {
+    /// Plain substring (query already lowercased)
+    Literal(String),
+    Regex(Regex),
+}
+
+impl Matcher {
+    /// Build a matcher, compiling `query` when `regex` is set
+    pub fn new(query: &str, regex: bool) -> Result<Self, regex::Error> {
+        if regex {
+            RegexBuilder::new(query)
+                .case_insensitive(true)
+                .build()
+                .map(Matcher::Regex)
+        } else {
+            Ok(Matcher::Literal(query.to_lowercase()))
+        }
+    }
+
+    fn is_match(&self, line: &str) -> bool {
+        match self {
+            Matcher::Literal(query) => line.to_lowercase().contains(query),
+            Matcher::Regex(re) => re.is_match(line),
+        }
+    }
+}
+
+/// A matching history line with its surroundings
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineMatch {
+    pub line: String,
+    pub context_before: Vec<String>,
+    pub context_after: Vec<String>,
+    /// Offset of the raw line holding the match
+    pub byte_offset: u64,
+}
+
+/// Scan history for lines matching `matcher`
+///
+/// `on_match` returns false to stop the scan, `cancelled` is checked before
+/// each raw line. Returns whether the scan reached the end of the history.
+pub fn scan<R: BufRead>(
+    mut reader: R,
+    matcher: &Matcher,
+    mut cancelled: impl FnMut() -> bool,
+    mut on_match: impl FnMut(LineMatch) -> bool,
+) -> io::Result<bool> {
+    let mut raw = Vec::new();
+    let mut offset = 0u64;
+    let mut before: VecDeque<String> = VecDeque::with_capacity(CONTEXT_LINES);
+    // Matches still collecting lines after them
+    let mut pending: VecDeque<LineMatch> = VecDeque::new();
+
+    loop {
+        if cancelled() {
+            return Ok(false);
+        }
+        raw.clear();
+        let read = reader.read_until(b'\n', &mut raw)?;
+        if read == 0 {
+            break;
+        }
+
+        for line in strip_ansi(&raw).lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let line = truncate(line);
+
+            for m in pending.iter_mut() {
+                m.context_after.push(line.clone());
+            }
+            while pending
+                .front()
+                .is_some_and(|m| m.context_after.len() == CONTEXT_LINES)
+            {
+                if let Some(m) = pending.pop_front() {
+                    if !on_match(m) {
+                        return Ok(false);
+                    }
+                }
+            }
+
+            if matcher.is_match(&line) {
+                pending.push_back(LineMatch {
+                    line: line.clone(),
+                    context_before: before.iter().cloned().collect(),
+                    context_after: Vec::new(),
+                    byte_offset: offset,
+                });
+            }
+
+            if before.len() == CONTEXT_LINES {
+                before.pop_front();
+            }
+            before.push_back(line);
+        }
+        offset += read as u64;
+    }
+
+    // Matches near the end get whatever context followed them
+    for m in pending {
+        if !on_match(m) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+fn truncate(line: &str) -> String {
+    match line.char_indices().nth(MAX_LINE_CHARS) {
+        Some((idx, _)) => format!("{}…", &line[..idx]),
+        None => line.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(history: &[u8], matcher: &Matcher) -> Vec<LineMatch> {
+        let mut found = Vec::new();
+        let finished = scan(
+            history,
+            matcher,
+            || false,
+            |m| {
+                found.push(m);
+                true
+            },
+        );
+        assert!(matches!(finished, Ok(true)));
+        found
+    }
+
+    #[test]
+    fn test_scan_reports_context_and_offsets() {
+        let history =
+            b"one\r\ntwo\r\n\x1b[1mrun the migration\x1b[0m script\r\nthree\r\nfour\r\nfive\r\n";
+        let found = collect(history, &Matcher::new("MIGRATION", false).unwrap());
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].line, "run the migration script");
+        assert_eq!(found[0].context_before, vec!["one", "two"]);
+        assert_eq!(found[0].context_after, vec!["three", "four"]);
+        assert_eq!(found[0].byte_offset, 10);
+    }
+
+    #[test]
+    fn test_scan_regex_and_trailing_match() {
+        let history = b"error: 42\nok\nerror: 7";
+        let found = collect(history, &Matcher::new(r"error: \d+$", true).unwrap());
+
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].context_after, vec!["ok", "error: 7"]);
+        assert_eq!(found[1].line, "error: 7");
+        assert!(found[1].context_after.is_empty());
+        assert_eq!(found[1].byte_offset, 13);
+
+        assert!(Matcher::new("(unclosed", true).is_err());
+    }
+
+    #[test]
+    fn test_scan_stops_early() {
+        let history = b"a match\nanother match\n";
+        let matcher = Matcher::new("match", false).unwrap();
+
+        let mut count = 0;
+        let finished = scan(
+            &history[..],
+            &matcher,
+            || false,
+            |_| {
+                count += 1;
+                false
+            },
+        );
+        assert!(matches!(finished, Ok(false)));
+        assert_eq!(count, 1);
+
+        let finished = scan(&history[..], &matcher, || true, |_| true);
+        assert!(matches!(finished, Ok(false)));
+    }
+}
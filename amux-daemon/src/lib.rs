@@ -0,0 +1,36 @@
+//! Amux Daemon - Claude Code Manager Daemon
+//!
+//! The service and the state behind it; `main.rs` loads the persisted state
+//! and serves it on the daemon socket.
+
+mod archive;
+mod blame;
+mod checkpoint;
+pub mod cleanup;
+mod conflict;
+mod diff;
+mod env;
+pub mod error;
+pub mod events;
+mod file_watcher;
+mod git;
+mod handlers;
+mod history_search;
+pub mod persistence;
+pub mod providers;
+mod pty;
+pub mod repo;
+mod repo_stats;
+pub mod resource_monitor;
+mod review;
+pub mod server;
+pub mod session;
+pub mod state;
+mod terminal_title;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod todo;
+mod transcript;
+pub mod trash;
+mod workdir;
+pub mod worktree_status;
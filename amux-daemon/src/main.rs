@@ -1,24 +1,10 @@
 //! Amux Daemon - Claude Code Manager Daemon
 
-mod diff;
-pub mod error;
-mod events;
-mod file_watcher;
-mod git;
-mod handlers;
-mod persistence;
-pub mod providers;
-mod pty;
-mod repo;
-mod review;
-mod server;
-mod session;
-mod state;
-mod todo;
-
-use crate::events::EventBroadcaster;
-use crate::server::CcmDaemonService;
-use crate::state::{AppState, SharedState};
+use amux_daemon::events::EventBroadcaster;
+use amux_daemon::server::CcmDaemonService;
+use amux_daemon::state::{AppState, SharedState};
+use amux_daemon::worktree_status::WorktreeStatusCache;
+use amux_daemon::{cleanup, persistence, providers, repo, resource_monitor, session, trash};
 use amux_proto::daemon::ccm_daemon_server::CcmDaemonServer;
 use anyhow::Result;
 use std::sync::Arc;
@@ -53,7 +39,9 @@ async fn main() -> Result<()> {
     std::fs::write(&pid_file, std::process::id().to_string())?;
 
     // Initialize state
-    let state: SharedState = Arc::new(RwLock::new(AppState::new()));
+    let mut app_state = AppState::new();
+    app_state.provider_registry = Arc::new(providers::ProviderRegistry::discover());
+    let state: SharedState = Arc::new(RwLock::new(app_state));
 
     // Load persisted repos
     if let Ok(repos) = repo::load_repos() {
@@ -86,7 +74,7 @@ async fn main() -> Result<()> {
             }
 
             valid_repos.push(r.clone());
-            state_guard.repos.insert(r.id.clone(), r);
+            state_guard.insert_repo(r);
         }
         // Save cleaned repo list if any were removed
         if valid_repos.len() < original_count {
@@ -105,13 +93,13 @@ async fn main() -> Result<()> {
                 let _ = persistence::delete_session_data(&meta.id);
                 continue;
             }
-            // Check if worktree path still exists
-            if !meta.worktree_path.exists() {
-                info!(
-                    "Removing orphaned session {} (worktree not found: {:?})",
-                    meta.id, meta.worktree_path
-                );
+            // Nothing can restore a session trashed before the restart
+            if meta.trashed_at.is_some() {
+                info!("Purging trashed session {}", meta.id);
                 let _ = persistence::delete_session_data(&meta.id);
+                if meta.checkpoint.is_some() {
+                    state_guard.prune_checkpoint(&meta.repo_id, &meta.id);
+                }
                 continue;
             }
             let session = session::Session::from_meta(meta);
@@ -121,7 +109,32 @@ async fn main() -> Result<()> {
             }
             state_guard.sessions.insert(session.id.clone(), session);
         }
+
+        // Prune worktrees deleted while the daemon was down and their sessions
+        let repo_ids: Vec<String> = state_guard.repos.keys().cloned().collect();
+        for repo_id in repo_ids {
+            let report = cleanup::cleanup_repo(&mut state_guard, &repo_id);
+            for error in report.errors {
+                tracing::warn!("Worktree cleanup in repo {}: {}", repo_id, error);
+            }
+        }
         info!("Restored {} sessions", state_guard.sessions.len());
+
+        // Give sessions persisted before ordering existed distinct positions
+        let mut worktrees: Vec<(String, String)> = state_guard
+            .sessions
+            .values()
+            .map(|s| (s.repo_id.clone(), s.branch.clone()))
+            .collect();
+        worktrees.sort();
+        worktrees.dedup();
+        for (repo_id, branch) in worktrees {
+            for id in state_guard.renumber_sessions(&repo_id, &branch, None) {
+                if let Some(session) = state_guard.sessions.get(&id) {
+                    let _ = persistence::save_session_meta(session);
+                }
+            }
+        }
     }
 
     // Create event broadcaster (before background task so it can emit events)
@@ -157,14 +170,34 @@ async fn main() -> Result<()> {
         }
     });
 
+    let config = amux_config::Config::load_or_default().unwrap_or_default();
+
+    // Spawn background task sampling session CPU/memory usage
+    resource_monitor::spawn_monitor(
+        state.clone(),
+        events.clone(),
+        Duration::from_secs(config.options.resource_monitor_interval_secs),
+    );
+
+    // Spawn background task emptying the session trash
+    trash::spawn_purger(
+        state.clone(),
+        Duration::from_secs(config.options.trash_ttl_secs),
+    );
+
     // Create Unix socket listener
     let listener = UnixListener::bind(&socket_path)?;
     info!("Listening on {:?}", socket_path);
 
     let incoming = UnixListenerStream::new(listener);
 
+    // Worktree summary cache, bounded by the configured timeout
+    let status_cache = WorktreeStatusCache::new(Duration::from_millis(
+        config.options.worktree_status_timeout_ms,
+    ));
+
     // Create gRPC service
-    let service = CcmDaemonService::new(state, events);
+    let service = CcmDaemonService::new(state, events, status_cache);
 
     // Initialize file watchers for all existing worktrees
     if let Err(e) = service.initialize_watchers().await {
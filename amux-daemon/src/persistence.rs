@@ -17,6 +17,10 @@ pub struct SessionMeta {
     pub worktree_path: PathBuf,
     pub created_at: u64,
     pub updated_at: u64,
+    #[serde(default)]
+    pub started_at: u64,
+    #[serde(default)]
+    pub last_output_at: u64,
 
     // Provider fields
     #[serde(default = "default_provider")]
@@ -35,6 +39,28 @@ pub struct SessionMeta {
     pub is_shell: bool,
     #[serde(default)]
     pub model: Option<String>,
+
+    // Sidebar ordering within the worktree
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default)]
+    pub sort_key: u32,
+
+    // Set while the session is in the trash
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trashed_at: Option<u64>,
+
+    // Worktree checkpoint commit taken when the session was created
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checkpoint: Option<String>,
+
+    // System prompt given when the session was created, passed on every start
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
+
+    // Shell's directory relative to the worktree root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<PathBuf>,
 }
 
 fn default_provider() -> String {
@@ -56,7 +82,7 @@ impl SessionMeta {
                 ..
             } => (Some(provider_session_id.clone()), false),
             SessionKind::Shell => (None, true),
-            SessionKind::OneShot => (None, false),
+            SessionKind::OneShot | SessionKind::Command { .. } => (None, false),
         };
 
         Self {
@@ -65,14 +91,22 @@ impl SessionMeta {
             repo_id: session.repo_id.clone(),
             branch: session.branch.clone(),
             worktree_path: session.worktree_path.clone(),
-            created_at: now,
+            created_at: session.created_at,
             updated_at: now,
+            started_at: session.started_at,
+            last_output_at: session.last_output_at(),
             provider: session.provider.clone(),
             kind: Some(session.kind.clone()),
             provider_session_id,
             name_updated_from_provider: session.name_updated_from_provider,
             is_shell,
             model: session.model.clone(),
+            pinned: session.pinned,
+            sort_key: session.sort_key,
+            trashed_at: session.trashed_at,
+            checkpoint: session.checkpoint.clone(),
+            system_prompt: session.system_prompt.clone(),
+            cwd: session.cwd.clone(),
         }
     }
 }
@@ -148,6 +182,18 @@ pub fn save_session_history(session: &Session) -> Result<(), PersistenceError> {
     Ok(())
 }
 
+/// Write terminal history for a session that isn't loaded (e.g. imported)
+pub fn write_session_history(session_id: &str, history: &[u8]) -> Result<(), PersistenceError> {
+    let dir = session_dir(session_id);
+    std::fs::create_dir_all(&dir).map_err(PersistenceError::CreateDir)?;
+    let path = session_history_file(session_id);
+    std::fs::write(&path, history).map_err(|e| PersistenceError::WriteFile {
+        path: path.clone(),
+        source: e,
+    })?;
+    Ok(())
+}
+
 /// Load session metadata
 pub fn load_session_meta(session_id: &str) -> Result<SessionMeta, PersistenceError> {
     let path = session_meta_file(session_id);
@@ -217,7 +263,6 @@ pub fn delete_session_data(session_id: &str) -> Result<(), PersistenceError> {
 }
 
 /// Save session (metadata + history)
-#[allow(dead_code)]
 pub fn save_session(session: &Session) -> Result<(), PersistenceError> {
     save_session_meta(session)?;
     save_session_history(session)?;
@@ -3,11 +3,15 @@
 use super::{
     AiProvider, ProviderConfig, ProviderError, ProviderResult, ProviderSessionInfo, SessionMode,
 };
+use crate::state::AppState;
 use serde::Deserialize;
+use std::collections::hash_map::DefaultHasher;
 use std::ffi::CString;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
 
 /// Claude Code CLI provider
 pub struct ClaudeProvider {
@@ -86,6 +90,8 @@ impl AiProvider for ClaudeProvider {
                     );
                 }
 
+                push_system_prompt(&mut args, config)?;
+
                 // Add prompt if specified (as positional argument)
                 if let Some(ref prompt) = config.prompt {
                     args.push(
@@ -104,6 +110,9 @@ impl AiProvider for ClaudeProvider {
                     CString::new(session_id.as_str())
                         .map_err(|e| ProviderError::CommandBuild(e.to_string()))?,
                 );
+
+                // The CLI doesn't keep the system prompt of a resumed session
+                push_system_prompt(&mut args, config)?;
             }
 
             SessionMode::OneShot => {
@@ -119,6 +128,8 @@ impl AiProvider for ClaudeProvider {
                     );
                 }
 
+                push_system_prompt(&mut args, config)?;
+
                 // Add prompt as positional argument
                 if let Some(ref prompt) = config.prompt {
                     args.push(
@@ -137,12 +148,13 @@ impl AiProvider for ClaudeProvider {
         session_id: &str,
         worktree_path: &Path,
     ) -> ProviderResult<Option<ProviderSessionInfo>> {
-        match get_first_user_message(worktree_path, session_id) {
-            Some(description) => Ok(Some(ProviderSessionInfo {
-                description: Some(description),
-            })),
-            None => Ok(None),
-        }
+        let Some(file) = session_file(worktree_path, session_id).filter(|f| f.is_file()) else {
+            return Ok(None);
+        };
+        Ok(Some(ProviderSessionInfo {
+            description: get_first_user_message(worktree_path, session_id),
+            file: Some(file),
+        }))
     }
 
     fn available_models(&self) -> Vec<&str> {
@@ -162,6 +174,53 @@ impl AiProvider for ClaudeProvider {
     }
 }
 
+/// Add `--system-prompt-file` for the config's system prompt, if any
+fn push_system_prompt(args: &mut Vec<CString>, config: &ProviderConfig) -> ProviderResult<()> {
+    let Some(ref prompt) = config.system_prompt else {
+        return Ok(());
+    };
+    let path = write_system_prompt(prompt)?;
+    args.push(
+        CString::new("--system-prompt-file")
+            .map_err(|e| ProviderError::CommandBuild(e.to_string()))?,
+    );
+    args.push(
+        CString::new(path.to_string_lossy().as_bytes())
+            .map_err(|e| ProviderError::CommandBuild(e.to_string()))?,
+    );
+    Ok(())
+}
+
+/// Write a system prompt to `~/.amux/system-prompts/`, named after its content
+///
+/// The directory is private to the user (0700) and the file 0600, so no
+/// one else can read or swap the prompt before claude reads it.
+fn write_system_prompt(prompt: &str) -> ProviderResult<PathBuf> {
+    let mut hasher = DefaultHasher::new();
+    prompt.hash(&mut hasher);
+    let dir = AppState::data_dir().join("system-prompts");
+    let path = dir.join(format!("{:016x}.md", hasher.finish()));
+    write_private(&dir, &path, prompt)
+        .map_err(|e| ProviderError::CommandBuild(format!("Cannot write system prompt: {}", e)))?;
+    Ok(path)
+}
+
+fn write_private(dir: &Path, path: &Path, content: &str) -> std::io::Result<()> {
+    std::fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(dir)?;
+    // An existing directory keeps its mode otherwise
+    std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))?;
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    file.write_all(content.as_bytes())
+}
+
 // ============ Claude session file parsing (from claude_session.rs) ============
 
 #[derive(Deserialize)]
@@ -185,13 +244,94 @@ fn path_to_claude_folder(path: &Path) -> String {
     trimmed.replace('/', "-")
 }
 
-/// Get first user message from Claude session
-pub fn get_first_user_message(worktree_path: &Path, claude_session_id: &str) -> Option<String> {
+/// Path of a Claude session's JSONL file
+fn session_file(worktree_path: &Path, claude_session_id: &str) -> Option<std::path::PathBuf> {
     let folder = path_to_claude_folder(worktree_path);
     let claude_home = dirs::home_dir()?.join(".claude/projects").join(&folder);
-    let session_file = claude_home.join(format!("{}.jsonl", claude_session_id));
+    Some(claude_home.join(format!("{}.jsonl", claude_session_id)))
+}
+
+/// Who wrote a conversation message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    User,
+    Assistant,
+}
+
+/// A message of a Claude conversation
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversationMessage {
+    pub role: Role,
+    pub text: String,
+}
 
-    let file = File::open(&session_file).ok()?;
+/// Read the user and assistant messages of a Claude session, in order
+pub fn read_conversation(
+    worktree_path: &Path,
+    claude_session_id: &str,
+) -> Option<Vec<ConversationMessage>> {
+    let file = File::open(session_file(worktree_path, claude_session_id)?).ok()?;
+    Some(parse_conversation(BufReader::new(file)))
+}
+
+/// Parse the text messages of a Claude session file
+///
+/// Tool calls and results, meta entries and system reminders are skipped;
+/// consecutive messages of the same role (e.g. a streamed reply) are merged.
+fn parse_conversation(reader: impl BufRead) -> Vec<ConversationMessage> {
+    let mut messages: Vec<ConversationMessage> = Vec::new();
+
+    for line in reader.lines().map_while(Result::ok) {
+        let Ok(entry) = serde_json::from_str::<serde_json::Value>(&line) else {
+            continue;
+        };
+        let role = match entry["type"].as_str() {
+            Some("user") => Role::User,
+            Some("assistant") => Role::Assistant,
+            _ => continue,
+        };
+        if entry["isMeta"].as_bool() == Some(true) {
+            continue;
+        }
+
+        // Content is a plain string or a list of blocks
+        let content = &entry["message"]["content"];
+        let text = match content.as_str() {
+            Some(text) => text.to_string(),
+            None => content
+                .as_array()
+                .map(|blocks| {
+                    blocks
+                        .iter()
+                        .filter(|b| b["type"].as_str() == Some("text"))
+                        .filter_map(|b| b["text"].as_str())
+                        .collect::<Vec<_>>()
+                        .join("\n\n")
+                })
+                .unwrap_or_default(),
+        };
+        let text = text.trim();
+        if text.is_empty() || text.starts_with("<system-reminder>") {
+            continue;
+        }
+
+        match messages.last_mut() {
+            Some(last) if last.role == role => {
+                last.text.push_str("\n\n");
+                last.text.push_str(text);
+            }
+            _ => messages.push(ConversationMessage {
+                role,
+                text: text.to_string(),
+            }),
+        }
+    }
+    messages
+}
+
+/// Get first user message from Claude session
+pub fn get_first_user_message(worktree_path: &Path, claude_session_id: &str) -> Option<String> {
+    let file = File::open(session_file(worktree_path, claude_session_id)?).ok()?;
     let reader = BufReader::new(file);
 
     for line in reader.lines().map_while(Result::ok) {
@@ -218,3 +358,73 @@ pub fn get_first_user_message(worktree_path: &Path, claude_session_id: &str) ->
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args_of(config: &ProviderConfig) -> Vec<String> {
+        let (_, args) = ClaudeProvider::new().build_command(config).unwrap();
+        args.iter()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_system_prompt_file() {
+        let data_dir = crate::testing::shared_data_dir();
+        let mut config = ProviderConfig::resume("abc".to_string());
+        assert_eq!(args_of(&config), ["claude", "--resume", "abc"]);
+
+        config.system_prompt = Some("Answer like a pirate".to_string());
+        let args = args_of(&config);
+        assert_eq!(
+            args[..4],
+            ["claude", "--resume", "abc", "--system-prompt-file"]
+        );
+        let written = std::fs::read_to_string(&args[4]).unwrap();
+        assert_eq!(written, "Answer like a pirate");
+
+        // Neither the file nor its directory is readable by group or others
+        let path = Path::new(&args[4]);
+        assert!(path.starts_with(data_dir.path()));
+        let mode = |path: &Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(path), 0o600);
+        assert_eq!(mode(path.parent().unwrap()), 0o700);
+    }
+
+    #[test]
+    fn test_parse_conversation() {
+        let jsonl = [
+            r#"{"type":"summary","summary":"Migration"}"#,
+            r#"{"type":"user","message":{"role":"user","content":"Write the migration script"}}"#,
+            r#"{"type":"user","isMeta":true,"message":{"content":"<command-name>/clear</command-name>"}}"#,
+            r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Sure."},{"type":"tool_use","name":"Write","input":{}}]}}"#,
+            r#"{"type":"user","message":{"content":[{"type":"tool_result","content":"ok"}]}}"#,
+            r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Done."}]}}"#,
+            "not json",
+            r#"{"type":"user","message":{"content":"<system-reminder>ignored</system-reminder>"}}"#,
+            r#"{"type":"user","message":{"content":"Thanks"}}"#,
+        ]
+        .join("\n");
+
+        let messages = parse_conversation(jsonl.as_bytes());
+        assert_eq!(
+            messages,
+            vec![
+                ConversationMessage {
+                    role: Role::User,
+                    text: "Write the migration script".to_string(),
+                },
+                ConversationMessage {
+                    role: Role::Assistant,
+                    text: "Sure.\n\nDone.".to_string(),
+                },
+                ConversationMessage {
+                    role: Role::User,
+                    text: "Thanks".to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -1,52 +1,147 @@
 //! Mock provider for testing
 //!
 //! This module provides a mock AI provider that can be used in tests
-//! without requiring real CLI tools to be installed.
+//! without requiring real CLI tools to be installed. Sessions run the
+//! `amux-mock-provider` helper binary (built with the `testing` feature),
+//! which reads a [`MockScript`] from the `AMUX_MOCK_SCRIPT` environment
+//! variable and produces predictable output.
 
-use super::{AiProvider, ProviderConfig, ProviderResult, ProviderSessionInfo};
+use super::SessionMode;
+use super::{AiProvider, ProviderConfig, ProviderError, ProviderResult, ProviderSessionInfo};
 use std::ffi::CString;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Environment variable the helper reads its script from
+pub const MOCK_SCRIPT_ENV: &str = "AMUX_MOCK_SCRIPT";
+
+/// Overrides the location of the helper binary
+pub const MOCK_BIN_ENV: &str = "AMUX_MOCK_PROVIDER_BIN";
+
+/// File name of the helper binary
+const MOCK_BIN_NAME: &str = "amux-mock-provider";
+
+/// What the mock helper does once started
+#[derive(Debug, Clone, PartialEq)]
+pub enum MockScript {
+    /// Print a banner, then echo each line of input until `exit` or EOF
+    Echo,
+    /// Like `Echo`, but wait before printing the banner
+    Delay(Duration),
+    /// Print the lines and exit successfully
+    Lines(Vec<String>),
+    /// Print the message to stderr and exit with status 1
+    Fail(String),
+}
+
+impl MockScript {
+    /// Encode the script for `AMUX_MOCK_SCRIPT`
+    pub fn to_env_value(&self) -> String {
+        let value = match self {
+            MockScript::Echo => serde_json::json!({ "kind": "echo" }),
+            MockScript::Delay(delay) => {
+                serde_json::json!({ "kind": "delay", "ms": delay.as_millis() as u64 })
+            }
+            MockScript::Lines(lines) => serde_json::json!({ "kind": "lines", "lines": lines }),
+            MockScript::Fail(message) => {
+                serde_json::json!({ "kind": "fail", "message": message })
+            }
+        };
+        value.to_string()
+    }
+}
 
 /// A mock AI provider for testing
 ///
-/// This provider simulates an AI CLI tool by running a simple shell command
-/// that echoes output, allowing tests to verify session management and
+/// This provider simulates an AI CLI tool by running the mock helper with a
+/// configurable script, allowing tests to verify session management and
 /// PTY handling without requiring real AI tools.
 pub struct MockProvider {
     name: String,
     display_name: String,
     models: Vec<String>,
     default_model: String,
+    script: MockScript,
+    helper: Option<PathBuf>,
 }
 
 impl MockProvider {
-    /// Create a new mock provider with the given name
-    pub fn new(name: &str) -> Self {
+    /// Create a mock provider that runs the given script
+    pub fn new(script: MockScript) -> Self {
         Self {
-            name: name.to_string(),
-            display_name: format!("Mock {}", name),
-            models: vec!["mock-model-1".to_string(), "mock-model-2".to_string()],
-            default_model: "mock-model-1".to_string(),
+            name: "mock".to_string(),
+            display_name: "Mock".to_string(),
+            models: vec!["mock-fast".to_string(), "mock-slow".to_string()],
+            default_model: "mock-fast".to_string(),
+            script,
+            helper: None,
         }
     }
 
-    /// Create with custom models
+    /// Create a mock standing in for another provider, with custom models
     pub fn with_models(name: &str, models: Vec<String>, default_model: String) -> Self {
         Self {
             name: name.to_string(),
             display_name: format!("Mock {}", name),
             models,
             default_model,
+            script: MockScript::Echo,
+            helper: None,
+        }
+    }
+
+    /// Run the helper binary at `path` instead of looking it up
+    pub fn with_helper(mut self, path: impl Into<PathBuf>) -> Self {
+        self.helper = Some(path.into());
+        self
+    }
+
+    /// Script the helper runs
+    pub fn script(&self) -> &MockScript {
+        &self.script
+    }
+
+    /// Locate the helper binary
+    ///
+    /// Uses `AMUX_MOCK_PROVIDER_BIN` if set, otherwise looks next to the
+    /// running executable, which finds it both from the daemon and from test
+    /// binaries in `target/<profile>/deps`.
+    pub fn helper_path() -> ProviderResult<PathBuf> {
+        if let Some(path) = std::env::var_os(MOCK_BIN_ENV) {
+            return Ok(PathBuf::from(path));
+        }
+
+        let exe = std::env::current_exe().map_err(|e| {
+            ProviderError::CommandBuild(format!("cannot locate current executable: {}", e))
+        })?;
+        let mut dir = exe.parent().map(Path::to_path_buf).unwrap_or_default();
+        if dir.ends_with("deps") {
+            dir.pop();
+        }
+
+        let path = dir.join(MOCK_BIN_NAME);
+        if path.exists() {
+            Ok(path)
+        } else {
+            Err(ProviderError::CommandBuild(format!(
+                "mock provider helper not found at {} (set {})",
+                path.display(),
+                MOCK_BIN_ENV
+            )))
         }
     }
 }
 
 impl Default for MockProvider {
     fn default() -> Self {
-        Self::new("mock")
+        Self::new(MockScript::Echo)
     }
 }
 
+fn cstring(s: impl Into<Vec<u8>>) -> ProviderResult<CString> {
+    CString::new(s).map_err(|e| ProviderError::CommandBuild(e.to_string()))
+}
+
 impl AiProvider for MockProvider {
     fn name(&self) -> &str {
         &self.name
@@ -57,41 +152,46 @@ impl AiProvider for MockProvider {
     }
 
     fn build_command(&self, config: &ProviderConfig) -> ProviderResult<(CString, Vec<CString>)> {
-        // Build a simple command that simulates AI behavior
-        // Uses /bin/sh with a script that echoes responses
-        let cmd = CString::new("/bin/sh").expect("Failed to create CString");
-
+        // The PTY has no separate environment, so pass the script through env(1)
+        let helper = match &self.helper {
+            Some(path) => path.clone(),
+            None => Self::helper_path()?,
+        };
         let model = config.model.as_deref().unwrap_or(&self.default_model);
-        let prompt_msg = config
-            .prompt
-            .as_ref()
-            .map(|p| format!("Prompt: {}", p))
-            .unwrap_or_else(|| "No prompt".to_string());
-
-        // Create a simple interactive script that simulates an AI session
-        let script = format!(
-            r#"
-echo "[MockProvider: {}]"
-echo "Model: {}"
-echo "{}"
-echo "---"
-echo "Mock AI ready. Type 'exit' to quit."
-while read -r line; do
-    if [ "$line" = "exit" ]; then
-        exit 0
-    fi
-    echo "Mock response to: $line"
-done
-"#,
-            self.name, model, prompt_msg
-        );
 
-        let args = vec![
-            CString::new("-c").expect("Failed to create CString"),
-            CString::new(script).expect("Failed to create CString"),
+        let mut args = vec![
+            cstring("env")?,
+            cstring(format!(
+                "{}={}",
+                MOCK_SCRIPT_ENV,
+                self.script.to_env_value()
+            ))?,
+            cstring(helper.to_string_lossy().as_bytes())?,
+            cstring("--name")?,
+            cstring(self.name.as_str())?,
+            cstring("--model")?,
+            cstring(model)?,
         ];
 
-        Ok((cmd, args))
+        match &config.session_mode {
+            SessionMode::New {
+                session_id: Some(id),
+            } => {
+                args.push(cstring("--session-id")?);
+                args.push(cstring(id.as_str())?);
+            }
+            SessionMode::Resume { session_id } => {
+                args.push(cstring("--resume")?);
+                args.push(cstring(session_id.as_str())?);
+            }
+            _ => {}
+        }
+        if let Some(prompt) = &config.prompt {
+            args.push(cstring("--prompt")?);
+            args.push(cstring(prompt.as_str())?);
+        }
+
+        Ok((cstring("env")?, args))
     }
 
     fn read_session_info(
@@ -102,6 +202,7 @@ done
         // Mock providers don't have real session files
         Ok(Some(ProviderSessionInfo {
             description: Some("Mock session".to_string()),
+            file: None,
         }))
     }
 
@@ -126,37 +227,103 @@ done
 mod tests {
     use super::*;
 
+    fn args_of(provider: &MockProvider, config: &ProviderConfig) -> Vec<String> {
+        let (cmd, args) = provider.build_command(config).unwrap();
+        assert_eq!(cmd.to_str().unwrap(), "env");
+        args.iter()
+            .map(|a| a.to_str().unwrap().to_string())
+            .collect()
+    }
+
     #[test]
     fn test_mock_provider_default() {
         let provider = MockProvider::default();
         assert_eq!(provider.name(), "mock");
-        assert_eq!(provider.display_name(), "Mock mock");
+        assert_eq!(provider.display_name(), "Mock");
+        assert_eq!(provider.script(), &MockScript::Echo);
+        assert!(provider.supports_resume());
     }
 
     #[test]
     fn test_mock_provider_named() {
-        let provider = MockProvider::new("test-ai");
+        let provider = MockProvider::with_models("test-ai", vec!["m".to_string()], "m".into());
         assert_eq!(provider.name(), "test-ai");
         assert_eq!(provider.display_name(), "Mock test-ai");
     }
 
+    #[test]
+    fn test_mock_script_env_value() {
+        assert_eq!(MockScript::Echo.to_env_value(), r#"{"kind":"echo"}"#);
+        assert_eq!(
+            MockScript::Delay(Duration::from_millis(250)).to_env_value(),
+            r#"{"kind":"delay","ms":250}"#
+        );
+        assert_eq!(
+            MockScript::Lines(vec!["a".into(), "b".into()]).to_env_value(),
+            r#"{"kind":"lines","lines":["a","b"]}"#
+        );
+        assert_eq!(
+            MockScript::Fail("boom".into()).to_env_value(),
+            r#"{"kind":"fail","message":"boom"}"#
+        );
+    }
+
     #[test]
     fn test_mock_provider_build_command() {
-        let provider = MockProvider::default();
-        let config = ProviderConfig::default();
-        let result = provider.build_command(&config);
-        assert!(result.is_ok());
-        let (cmd, _args) = result.unwrap();
-        assert_eq!(cmd.to_str().unwrap(), "/bin/sh");
+        let provider = MockProvider::new(MockScript::Fail("no auth".into()))
+            .with_helper("/opt/mock/amux-mock-provider");
+
+        let args = args_of(&provider, &ProviderConfig::resume("abc".to_string()));
+        assert_eq!(
+            args,
+            [
+                "env",
+                r#"AMUX_MOCK_SCRIPT={"kind":"fail","message":"no auth"}"#,
+                "/opt/mock/amux-mock-provider",
+                "--name",
+                "mock",
+                "--model",
+                "mock-fast",
+                "--resume",
+                "abc",
+            ]
+        );
+
+        let config = ProviderConfig::one_shot(Some("mock-slow".into()), "hi".into());
+        let args = args_of(&provider, &config);
+        assert_eq!(args[6], "mock-slow");
+        assert_eq!(&args[7..], ["--prompt", "hi"]);
+    }
+
+    #[test]
+    fn test_mock_provider_runs_in_pty() {
+        // The helper is only built along with the integration tests
+        if MockProvider::helper_path().is_err() {
+            return;
+        }
+        let provider = MockProvider::new(MockScript::Lines(vec!["deterministic".into()]));
+        let (cmd, args) = provider.build_command(&ProviderConfig::default()).unwrap();
+        let pty = crate::pty::PtyProcess::spawn(Path::new("/tmp"), cmd, args, &[], 24, 80).unwrap();
+
+        let mut output = String::new();
+        let mut buf = [0u8; 1024];
+        for _ in 0..50 {
+            match pty.read(&mut buf) {
+                Ok(n) if n > 0 => output.push_str(&String::from_utf8_lossy(&buf[..n])),
+                _ => std::thread::sleep(Duration::from_millis(20)),
+            }
+            if output.contains("deterministic") {
+                break;
+            }
+        }
+        assert!(output.contains("deterministic"), "output: {:?}", output);
     }
 
     #[test]
     fn test_mock_provider_models() {
         let provider = MockProvider::default();
-        let models = provider.available_models();
-        assert_eq!(models.len(), 2);
-        assert!(models.contains(&"mock-model-1"));
-        assert!(models.contains(&"mock-model-2"));
+        assert_eq!(provider.available_models(), ["mock-fast", "mock-slow"]);
+        assert_eq!(provider.default_model(), "mock-fast");
     }
 
     #[test]
@@ -7,14 +7,16 @@ mod claude;
 mod codex;
 mod mock;
 mod registry;
+mod script;
 
-pub use claude::ClaudeProvider;
+pub use claude::{read_conversation, ClaudeProvider, ConversationMessage, Role};
 pub use codex::CodexProvider;
-pub use mock::MockProvider;
+pub use mock::{MockProvider, MockScript};
 pub use registry::ProviderRegistry;
+pub use script::ScriptProvider;
 
 use std::ffi::CString;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Session mode for AI provider
 #[derive(Debug, Clone)]
@@ -38,6 +40,8 @@ pub struct ProviderConfig {
     pub model: Option<String>,
     /// Initial prompt (for one-shot or new session)
     pub prompt: Option<String>,
+    /// System prompt, passed on every start (providers without one ignore it)
+    pub system_prompt: Option<String>,
 }
 
 impl Default for ProviderConfig {
@@ -46,6 +50,7 @@ impl Default for ProviderConfig {
             session_mode: SessionMode::New { session_id: None },
             model: None,
             prompt: None,
+            system_prompt: None,
         }
     }
 }
@@ -55,6 +60,8 @@ impl Default for ProviderConfig {
 pub struct ProviderSessionInfo {
     /// First user message or session description
     pub description: Option<String>,
+    /// File the provider keeps the session in
+    pub file: Option<PathBuf>,
 }
 
 /// Result type for provider operations
@@ -209,6 +216,7 @@ impl ProviderConfig {
             session_mode: SessionMode::Shell,
             model: None,
             prompt: None,
+            system_prompt: None,
         }
     }
 
@@ -218,6 +226,7 @@ impl ProviderConfig {
             session_mode: SessionMode::New { session_id },
             model,
             prompt: None,
+            system_prompt: None,
         }
     }
 
@@ -227,6 +236,7 @@ impl ProviderConfig {
             session_mode: SessionMode::Resume { session_id },
             model: None,
             prompt: None,
+            system_prompt: None,
         }
     }
 
@@ -236,6 +246,7 @@ impl ProviderConfig {
             session_mode: SessionMode::OneShot,
             model,
             prompt: Some(prompt),
+            system_prompt: None,
         }
     }
 }
@@ -1,10 +1,22 @@
 //! Provider registry for managing available AI providers
 
-use super::{AiProvider, ClaudeProvider, CodexProvider, ProviderError, ProviderResult};
+#[cfg(any(test, feature = "testing"))]
+use super::MockProvider;
 #[cfg(test)]
-use super::{MockProvider, ProviderRef};
+use super::ProviderRef;
+use super::{
+    AiProvider, ClaudeProvider, CodexProvider, ProviderError, ProviderResult, ScriptProvider,
+};
+use crate::state::AppState;
 use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Preferred default provider, when installed
+const PREFERRED_DEFAULT: &str = "claude";
 
 /// Registry of available AI providers
 pub struct ProviderRegistry {
@@ -39,6 +51,71 @@ impl ProviderRegistry {
         }
     }
 
+    /// Registry of the providers installed on this machine
+    ///
+    /// Built-in providers are registered when their CLI is found in `$PATH`,
+    /// user scripts from `~/.amux/providers/` when they describe themselves
+    /// (see [`ScriptProvider`]). With none found, debug builds with the
+    /// `testing` feature (which builds its helper) fall back to the mock
+    /// provider so the daemon stays usable in development.
+    pub fn discover() -> Self {
+        let path_var = std::env::var_os("PATH").unwrap_or_default();
+        #[allow(unused_mut)]
+        let mut registry = Self::discover_in(&path_var, &AppState::data_dir().join("providers"));
+
+        if registry.providers.is_empty() {
+            warn!("No AI providers found in PATH or ~/.amux/providers");
+            #[cfg(all(debug_assertions, feature = "testing"))]
+            {
+                registry.register(Arc::new(super::MockProvider::default()));
+                registry.choose_default();
+            }
+        }
+        info!("Providers: {}", registry.list_providers().join(", "));
+        registry
+    }
+
+    fn discover_in(path_var: &OsStr, scripts_dir: &Path) -> Self {
+        let mut registry = Self {
+            providers: HashMap::new(),
+            default_provider: PREFERRED_DEFAULT.to_string(),
+        };
+
+        let builtins: [Arc<dyn AiProvider>; 2] = [
+            Arc::new(ClaudeProvider::new()),
+            Arc::new(CodexProvider::new()),
+        ];
+        for provider in builtins {
+            if find_in_path(provider.name(), path_var).is_some() {
+                registry.register(provider);
+            }
+        }
+
+        for script in executables(scripts_dir) {
+            match ScriptProvider::load(&script) {
+                Ok(provider) if registry.has_provider(provider.name()) => warn!(
+                    "Skipping provider script {}: '{}' is already registered",
+                    script.display(),
+                    provider.name()
+                ),
+                Ok(provider) => registry.register(Arc::new(provider)),
+                Err(e) => warn!("Skipping provider script {}: {}", script.display(), e),
+            }
+        }
+
+        registry.choose_default();
+        registry
+    }
+
+    /// Default to the preferred provider if registered, else the first by name
+    fn choose_default(&mut self) {
+        if self.providers.contains_key(PREFERRED_DEFAULT) {
+            self.default_provider = PREFERRED_DEFAULT.to_string();
+        } else if let Some(name) = self.providers.keys().min() {
+            self.default_provider = name.clone();
+        }
+    }
+
     /// Register a new provider
     pub fn register(&mut self, provider: Arc<dyn AiProvider>) {
         self.providers.insert(provider.name().to_string(), provider);
@@ -107,7 +184,7 @@ impl ProviderRegistry {
     ///
     /// This is useful for unit tests that don't need real AI CLI tools.
     /// The mock providers simulate AI behavior without external dependencies.
-    #[cfg(test)]
+    #[cfg(any(test, feature = "testing"))]
     pub fn test_registry() -> Self {
         let mut providers: HashMap<String, Arc<dyn AiProvider>> = HashMap::new();
 
@@ -150,9 +227,103 @@ impl ProviderRegistry {
     }
 }
 
+/// Executable file named `command` in a directory of `path_var`
+fn find_in_path(command: &str, path_var: &OsStr) -> Option<PathBuf> {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    which::which_in(command, Some(path_var), cwd).ok()
+}
+
+/// Executable files of `dir`, hidden ones skipped, sorted by name
+fn executables(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .map(|entry| entry.path())
+        .filter(|path| is_executable(path))
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn is_executable(path: &Path) -> bool {
+    std::fs::metadata(path)
+        .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs;
+
+    fn write_executable(dir: &Path, name: &str, contents: &str) {
+        let path = dir.join(name);
+        fs::write(&path, contents).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn test_discover_only_installed() {
+        let bin = tempfile::tempdir().unwrap();
+        let scripts = tempfile::tempdir().unwrap();
+        write_executable(bin.path(), "codex", "");
+        // Not executable, so not installed
+        fs::write(bin.path().join("claude"), "").unwrap();
+
+        let registry = ProviderRegistry::discover_in(bin.path().as_os_str(), scripts.path());
+        assert_eq!(registry.list_providers(), ["codex"]);
+        assert_eq!(registry.default_provider_name(), "codex");
+
+        write_executable(bin.path(), "claude", "");
+        let path_var = std::env::join_paths([Path::new("/nonexistent"), bin.path()]).unwrap();
+        let registry = ProviderRegistry::discover_in(&path_var, scripts.path());
+        assert!(registry.has_provider("claude"));
+        assert!(registry.has_provider("codex"));
+        assert_eq!(registry.default_provider_name(), "claude");
+    }
+
+    #[test]
+    fn test_discover_scripts() {
+        let bin = tempfile::tempdir().unwrap();
+        let scripts = tempfile::tempdir().unwrap();
+        write_executable(bin.path(), "codex", "");
+        let describe = |json: &str| format!("#!/bin/sh\necho '{}'\n", json);
+        write_executable(
+            scripts.path(),
+            "aider",
+            &describe(r#"{"models": ["gpt-4o"]}"#),
+        );
+        write_executable(scripts.path(), "broken", &describe("not json"));
+        // Loses to the built-in provider of the same name
+        write_executable(
+            scripts.path(),
+            "my-codex",
+            &describe(r#"{"name": "codex", "models": ["x"]}"#),
+        );
+        fs::write(scripts.path().join("README"), "").unwrap();
+
+        let registry = ProviderRegistry::discover_in(bin.path().as_os_str(), scripts.path());
+        let mut names = registry.list_providers();
+        names.sort();
+        assert_eq!(names, ["aider", "codex"]);
+        assert_eq!(
+            registry.get("codex").unwrap().display_name(),
+            "OpenAI Codex"
+        );
+        assert_eq!(registry.default_provider_name(), "aider");
+    }
+
+    #[test]
+    fn test_discover_nothing() {
+        let empty = tempfile::tempdir().unwrap();
+        let registry = ProviderRegistry::discover_in(
+            empty.path().as_os_str(),
+            &empty.path().join("providers"),
+        );
+        assert!(registry.list_providers().is_empty());
+    }
 
     #[test]
     fn test_default_registry() {
@@ -0,0 +1,317 @@
+//! User-added providers, run as scripts from `~/.amux/providers/`
+//!
+//! Each executable file in the directory is a provider. At discovery the
+//! script is run as `<script> describe` and prints its metadata as JSON:
+//!
+//! ```json
+//! {"name": "aider", "display_name": "Aider", "models": ["gpt-4o"],
+//!  "default_model": "gpt-4o", "supports_resume": false}
+//! ```
+//!
+//! Only `models` is required; the name defaults to the file name. A script
+//! that doesn't answer within [`DESCRIBE_TIMEOUT`] is killed. Sessions
+//! then run `<script> new [--session-id ID]`, `<script> resume ID` or
+//! `<script> one-shot`, followed by `--model`, `--prompt` and
+//! `--system-prompt` when set.
+
+use super::{
+    AiProvider, ProviderConfig, ProviderError, ProviderResult, ProviderSessionInfo, SessionMode,
+};
+use serde::Deserialize;
+use std::ffi::CString;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::time::{Duration, Instant};
+
+/// How long `<script> describe` may run before the script is skipped
+const DESCRIBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// What a script prints for `describe`
+#[derive(Debug, Deserialize)]
+struct ScriptDescription {
+    name: Option<String>,
+    display_name: Option<String>,
+    models: Vec<String>,
+    default_model: Option<String>,
+    #[serde(default)]
+    supports_resume: bool,
+}
+
+/// A provider implemented by a user script
+#[derive(Debug)]
+pub struct ScriptProvider {
+    name: String,
+    display_name: String,
+    models: Vec<String>,
+    default_model: String,
+    supports_resume: bool,
+    path: PathBuf,
+}
+
+/// Run `<path> describe`, killing it after [`DESCRIBE_TIMEOUT`]
+fn describe(path: &Path) -> ProviderResult<Output> {
+    let cannot_run = |e: std::io::Error| {
+        ProviderError::InvalidConfig(format!("cannot run {}: {}", path.display(), e))
+    };
+    let mut child = Command::new(path)
+        .arg("describe")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(cannot_run)?;
+
+    let deadline = Instant::now() + DESCRIBE_TIMEOUT;
+    while child.try_wait().map_err(cannot_run)?.is_none() {
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(ProviderError::InvalidConfig(format!(
+                "{} describe didn't finish within {}s",
+                path.display(),
+                DESCRIBE_TIMEOUT.as_secs()
+            )));
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    child.wait_with_output().map_err(cannot_run)
+}
+
+fn cstring(s: impl Into<Vec<u8>>) -> ProviderResult<CString> {
+    CString::new(s).map_err(|e| ProviderError::CommandBuild(e.to_string()))
+}
+
+impl ScriptProvider {
+    /// Run `<path> describe` and build the provider from its answer
+    pub fn load(path: &Path) -> ProviderResult<Self> {
+        let output = describe(path)?;
+        if !output.status.success() {
+            return Err(ProviderError::InvalidConfig(format!(
+                "{} describe failed: {}",
+                path.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        let description: ScriptDescription =
+            serde_json::from_slice(&output.stdout).map_err(|e| {
+                ProviderError::InvalidConfig(format!(
+                    "{} describe printed invalid JSON: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+        Self::from_description(path, description)
+    }
+
+    fn from_description(path: &Path, description: ScriptDescription) -> ProviderResult<Self> {
+        let name = match description.name {
+            Some(name) => name,
+            None => path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+        };
+        let Some(first_model) = description.models.first() else {
+            return Err(ProviderError::InvalidConfig(format!(
+                "{} lists no models",
+                path.display()
+            )));
+        };
+        let default_model = description
+            .default_model
+            .unwrap_or_else(|| first_model.clone());
+        if !description.models.contains(&default_model) {
+            return Err(ProviderError::invalid_model(
+                &name,
+                &default_model,
+                &description
+                    .models
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>(),
+            ));
+        }
+
+        Ok(Self {
+            display_name: description.display_name.unwrap_or_else(|| name.clone()),
+            name,
+            models: description.models,
+            default_model,
+            supports_resume: description.supports_resume,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Path of the script
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AiProvider for ScriptProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    fn build_command(&self, config: &ProviderConfig) -> ProviderResult<(CString, Vec<CString>)> {
+        let cmd = cstring(self.path.to_string_lossy().as_bytes())?;
+        let mut args = vec![cmd.clone()];
+
+        match &config.session_mode {
+            SessionMode::Shell => {
+                return Err(ProviderError::InvalidConfig(
+                    "Shell mode should not use AiProvider".to_string(),
+                ));
+            }
+            SessionMode::New { session_id } => {
+                args.push(cstring("new")?);
+                if let Some(id) = session_id {
+                    args.push(cstring("--session-id")?);
+                    args.push(cstring(id.as_str())?);
+                }
+            }
+            SessionMode::Resume { session_id } => {
+                args.push(cstring("resume")?);
+                args.push(cstring(session_id.as_str())?);
+            }
+            SessionMode::OneShot => args.push(cstring("one-shot")?),
+        }
+
+        let options = [
+            ("--model", config.model.as_deref()),
+            ("--prompt", config.prompt.as_deref()),
+            ("--system-prompt", config.system_prompt.as_deref()),
+        ];
+        for (flag, value) in options {
+            if let Some(value) = value {
+                args.push(cstring(flag)?);
+                args.push(cstring(value)?);
+            }
+        }
+
+        Ok((cmd, args))
+    }
+
+    fn read_session_info(
+        &self,
+        _session_id: &str,
+        _worktree_path: &Path,
+    ) -> ProviderResult<Option<ProviderSessionInfo>> {
+        Ok(None)
+    }
+
+    fn available_models(&self) -> Vec<&str> {
+        self.models.iter().map(|s| s.as_str()).collect()
+    }
+
+    fn default_model(&self) -> &str {
+        &self.default_model
+    }
+
+    fn supports_resume(&self) -> bool {
+        self.supports_resume
+    }
+
+    fn has_local_sessions(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    /// Write an executable script that prints `describe_output` for `describe`
+    fn write_script(dir: &Path, file: &str, describe_output: &str) -> PathBuf {
+        let path = dir.join(file);
+        fs::write(
+            &path,
+            format!("#!/bin/sh\ncat <<'EOF'\n{}\nEOF\n", describe_output),
+        )
+        .unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    fn args_of(provider: &ScriptProvider, config: &ProviderConfig) -> Vec<String> {
+        let (_, args) = provider.build_command(config).unwrap();
+        args.iter()
+            .skip(1)
+            .map(|a| a.to_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_load_script() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_script(
+            dir.path(),
+            "aider",
+            r#"{"display_name": "Aider", "models": ["a", "b"], "default_model": "b"}"#,
+        );
+        let provider = ScriptProvider::load(&path).unwrap();
+        assert_eq!(provider.name(), "aider");
+        assert_eq!(provider.display_name(), "Aider");
+        assert_eq!(provider.available_models(), ["a", "b"]);
+        assert_eq!(provider.default_model(), "b");
+        assert!(!provider.supports_resume());
+        assert_eq!(provider.path(), path);
+    }
+
+    #[test]
+    fn test_load_rejects_bad_descriptions() {
+        let dir = tempfile::tempdir().unwrap();
+        let no_models = write_script(dir.path(), "empty", r#"{"models": []}"#);
+        assert!(ScriptProvider::load(&no_models).is_err());
+        let bad_default = write_script(
+            dir.path(),
+            "bad",
+            r#"{"models": ["a"], "default_model": "z"}"#,
+        );
+        assert!(ScriptProvider::load(&bad_default).is_err());
+        let not_json = write_script(dir.path(), "text", "hello");
+        assert!(ScriptProvider::load(&not_json).is_err());
+    }
+
+    #[test]
+    fn test_load_times_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stuck");
+        fs::write(&path, "#!/bin/sh\nsleep 30\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let started = Instant::now();
+        let err = ScriptProvider::load(&path).unwrap_err();
+        assert!(err.to_string().contains("didn't finish"), "{}", err);
+        assert!(started.elapsed() < DESCRIBE_TIMEOUT + Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_build_command() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_script(dir.path(), "tool", r#"{"name": "t", "models": ["m"]}"#);
+        let provider = ScriptProvider::load(&path).unwrap();
+        assert_eq!(provider.name(), "t");
+
+        let config = ProviderConfig::new_session(Some("abc".into()), Some("m".into()));
+        assert_eq!(
+            args_of(&provider, &config),
+            ["new", "--session-id", "abc", "--model", "m"]
+        );
+        assert_eq!(
+            args_of(&provider, &ProviderConfig::resume("abc".into())),
+            ["resume", "abc"]
+        );
+        assert_eq!(
+            args_of(&provider, &ProviderConfig::one_shot(None, "hi".into())),
+            ["one-shot", "--prompt", "hi"]
+        );
+        assert!(provider.build_command(&ProviderConfig::shell()).is_err());
+    }
+}
@@ -21,18 +21,26 @@ pub struct PtyProcess {
 
 impl PtyProcess {
     /// Spawn a new PTY process running user's shell
-    pub fn spawn_shell(working_dir: &Path, rows: u16, cols: u16) -> Result<Self, PtyError> {
+    pub fn spawn_shell(
+        working_dir: &Path,
+        env: &[(String, String)],
+        rows: u16,
+        cols: u16,
+    ) -> Result<Self, PtyError> {
         let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
         let cmd = CString::new(shell.clone()).unwrap();
         let args = vec![CString::new(shell).unwrap()];
-        Self::spawn(working_dir, cmd, args, rows, cols)
+        Self::spawn(working_dir, cmd, args, env, rows, cols)
     }
 
     /// Spawn a new PTY process with a specific command
+    ///
+    /// `env` is set over the daemon's environment in the child.
     pub fn spawn(
         working_dir: &Path,
         cmd: CString,
         args: Vec<CString>,
+        env: &[(String, String)],
         rows: u16,
         cols: u16,
     ) -> Result<Self, PtyError> {
@@ -91,6 +99,10 @@ impl PtyProcess {
                 // Change to working directory
                 std::env::set_current_dir(working_dir).ok();
 
+                for (name, value) in env {
+                    std::env::set_var(name, value);
+                }
+
                 // Set TERM environment variable for proper TTY detection
                 std::env::set_var("TERM", "xterm-256color");
 
@@ -159,7 +171,6 @@ impl PtyProcess {
     }
 
     /// Get the child PID
-    #[allow(dead_code)]
     pub fn pid(&self) -> Pid {
         self.child_pid
     }
@@ -190,6 +201,7 @@ mod tests {
             std::path::Path::new("/tmp"),
             cmd,
             args,
+            &[],
             DEFAULT_TERMINAL_ROWS,
             DEFAULT_TERMINAL_COLS,
         );
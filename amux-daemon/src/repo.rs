@@ -3,8 +3,10 @@
 use crate::error::RepoError;
 use crate::git::GitOps;
 use crate::state::AppState;
+use amux_proto::daemon::DeleteAction as ProtoDeleteAction;
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
 use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 
@@ -14,6 +16,44 @@ pub struct Repo {
     pub id: String,
     pub name: String,
     pub path: PathBuf,
+    /// Per-worktree default for deleting sessions, keyed by branch
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub delete_actions: HashMap<String, DeleteAction>,
+    /// Checkpoint the worktree when a non-shell session starts
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub checkpoints: bool,
+    /// Extra environment variables of all the repo's sessions
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// Per-worktree environment variables, keyed by branch, over `env`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub worktree_env: HashMap<String, BTreeMap<String, String>>,
+}
+
+/// What deleting a session does by default
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeleteAction {
+    Destroy,
+    Stop,
+}
+
+impl DeleteAction {
+    /// Convert from the proto enum; `Unspecified` means no setting
+    pub fn from_proto(action: ProtoDeleteAction) -> Option<Self> {
+        match action {
+            ProtoDeleteAction::Unspecified => None,
+            ProtoDeleteAction::Destroy => Some(Self::Destroy),
+            ProtoDeleteAction::Stop => Some(Self::Stop),
+        }
+    }
+
+    pub fn to_proto(self) -> ProtoDeleteAction {
+        match self {
+            Self::Destroy => ProtoDeleteAction::Destroy,
+            Self::Stop => ProtoDeleteAction::Stop,
+        }
+    }
 }
 
 impl Repo {
@@ -31,7 +71,15 @@ impl Repo {
         let id = Self::generate_id(&path);
         let name = GitOps::repo_name(&path);
 
-        Ok(Self { id, name, path })
+        Ok(Self {
+            id,
+            name,
+            path,
+            delete_actions: HashMap::new(),
+            checkpoints: false,
+            env: BTreeMap::new(),
+            worktree_env: HashMap::new(),
+        })
     }
 
     /// Generate a unique ID from path
@@ -96,6 +144,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_delete_actions_optional_in_repos_json() {
+        let repos: Vec<Repo> =
+            serde_json::from_str(r#"[{"id":"a","name":"a","path":"/tmp/a"}]"#).unwrap();
+        assert!(repos[0].delete_actions.is_empty());
+
+        let mut repo = repos[0].clone();
+        repo.delete_actions
+            .insert("scratch".to_string(), DeleteAction::Destroy);
+        let json = serde_json::to_string(&repo).unwrap();
+        assert!(json.contains(r#""delete_actions":{"scratch":"destroy"}"#));
+    }
+
     #[test]
     fn test_repo_name_extraction() {
         let name = GitOps::repo_name(Path::new("/home/user/projects/my-project"));
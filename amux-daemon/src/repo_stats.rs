@@ -0,0 +1,191 @@
+//! Aggregate figures of a repo and its worktrees
+//!
+//! Commits ahead and disk usage walk history and the worktree directories,
+//! so they are cached per repo for [`STATS_TTL`]; the cheap counts are taken
+//! on every request.
+
+use crate::error::GitError;
+use git2::{BranchType, Repository, StatusOptions};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// How long commits ahead and disk usage are reused
+pub const STATS_TTL: Duration = Duration::from_secs(30);
+
+/// The costly part of a repo's stats
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExpensiveStats {
+    /// Commits of the worktree branches missing from their upstreams
+    pub commits_ahead: u32,
+    /// Size of the worktree directories
+    pub disk_usage_kb: u64,
+}
+
+/// Repo stats operations
+pub struct RepoStatsOps;
+
+impl RepoStatsOps {
+    /// Commits of the worktree's branch that its upstream lacks
+    ///
+    /// Detached HEADs and branches without an upstream count none.
+    pub fn commits_ahead(worktree_path: &Path) -> Result<u32, GitError> {
+        let repo = Repository::open(worktree_path)?;
+        let head = match repo.head() {
+            Ok(head) if head.is_branch() => head,
+            _ => return Ok(0),
+        };
+        let Some(name) = head.shorthand() else {
+            return Ok(0);
+        };
+        let Ok(upstream) = repo.find_branch(name, BranchType::Local)?.upstream() else {
+            return Ok(0);
+        };
+        let (Some(local), Some(remote)) = (head.target(), upstream.get().target()) else {
+            return Ok(0);
+        };
+
+        let mut walk = repo.revwalk()?;
+        walk.push(local)?;
+        walk.hide(remote)?;
+        Ok(walk.count() as u32)
+    }
+
+    /// Changed and untracked files of a worktree
+    pub fn uncommitted_changes(worktree_path: &Path) -> Result<u32, GitError> {
+        let repo = Repository::open(worktree_path)?;
+        let mut opts = StatusOptions::new();
+        opts.include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .include_ignored(false);
+        let count = repo.statuses(Some(&mut opts))?.len();
+        Ok(count as u32)
+    }
+
+    /// Size of the files under `path` in KiB, symlinks not followed
+    pub fn disk_usage_kb(path: &Path) -> u64 {
+        dir_size(path).div_ceil(1024)
+    }
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// Recently computed expensive stats, per repo
+#[derive(Default)]
+pub struct RepoStatsCache {
+    entries: HashMap<String, (Instant, ExpensiveStats)>,
+}
+
+impl RepoStatsCache {
+    /// Stats computed less than [`STATS_TTL`] before `now`
+    pub fn get(&self, repo_id: &str, now: Instant) -> Option<ExpensiveStats> {
+        self.entries
+            .get(repo_id)
+            .filter(|(at, _)| now.duration_since(*at) < STATS_TTL)
+            .map(|(_, stats)| *stats)
+    }
+
+    pub fn insert(&mut self, repo_id: &str, stats: ExpensiveStats, now: Instant) {
+        self.entries.insert(repo_id.to_string(), (now, stats));
+    }
+
+    /// Forget a removed repo
+    pub fn remove(&mut self, repo_id: &str) {
+        self.entries.remove(repo_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn commit(repo: &Repository, dir: &Path, file: &str) -> git2::Oid {
+        fs::write(dir.join(file), file).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(file)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Test", "test@test.com").unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<_> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, file, &tree, &parents)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_commits_ahead_of_upstream() {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let base = commit(&repo, dir.path(), "a.txt");
+        assert_eq!(RepoStatsOps::commits_ahead(dir.path()).unwrap(), 0);
+
+        // Fake an upstream pointing at the base commit
+        let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+        repo.reference("refs/remotes/origin/main", base, true, "test")
+            .unwrap();
+        let mut config = repo.config().unwrap();
+        config
+            .set_str(&format!("branch.{}.remote", branch), "origin")
+            .unwrap();
+        config
+            .set_str(&format!("branch.{}.merge", branch), "refs/heads/main")
+            .unwrap();
+        config
+            .set_str("remote.origin.url", "https://example.com/repo.git")
+            .unwrap();
+        config
+            .set_str("remote.origin.fetch", "+refs/heads/*:refs/remotes/origin/*")
+            .unwrap();
+
+        commit(&repo, dir.path(), "b.txt");
+        commit(&repo, dir.path(), "c.txt");
+        assert_eq!(RepoStatsOps::commits_ahead(dir.path()).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_uncommitted_changes_and_disk_usage() {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        commit(&repo, dir.path(), "a.txt");
+        assert_eq!(RepoStatsOps::uncommitted_changes(dir.path()).unwrap(), 0);
+
+        fs::write(dir.path().join("a.txt"), "changed").unwrap();
+        fs::create_dir(dir.path().join("new")).unwrap();
+        fs::write(dir.path().join("new/b.txt"), vec![0u8; 4096]).unwrap();
+        assert_eq!(RepoStatsOps::uncommitted_changes(dir.path()).unwrap(), 2);
+
+        assert_eq!(RepoStatsOps::disk_usage_kb(&dir.path().join("new")), 4);
+        assert!(RepoStatsOps::disk_usage_kb(dir.path()) > 4);
+    }
+
+    #[test]
+    fn test_cache_expires() {
+        let mut cache = RepoStatsCache::default();
+        let now = Instant::now();
+        let stats = ExpensiveStats {
+            commits_ahead: 3,
+            disk_usage_kb: 10,
+        };
+        cache.insert("repo", stats, now);
+        assert_eq!(cache.get("repo", now + Duration::from_secs(1)), Some(stats));
+        assert_eq!(cache.get("repo", now + STATS_TTL), None);
+        assert_eq!(cache.get("other", now), None);
+
+        cache.remove("repo");
+        assert_eq!(cache.get("repo", now), None);
+    }
+}
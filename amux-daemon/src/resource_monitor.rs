@@ -0,0 +1,170 @@
+//! Session process CPU and memory monitoring
+//!
+//! A background task samples each running session's process from `/proc`
+//! (resident memory from `status`, CPU time from `stat`) and broadcasts the
+//! values. CPU usage is the share of one core used since the previous
+//! sample, so the first sample of a process reports 0%. On platforms without
+//! `/proc` no usage is reported.
+
+use crate::events::EventBroadcaster;
+use crate::state::SharedState;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Latest resource usage of a session's process
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ResourceUsage {
+    /// Resident set size (VmRSS)
+    pub memory_kb: u64,
+    /// CPU usage since the previous sample, 100 = one full core
+    pub cpu_percent: f32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CpuSample {
+    ticks: u64,
+    at: Instant,
+}
+
+/// Samples process usage, remembering CPU time between samples
+#[derive(Debug, Default)]
+pub struct ResourceMonitor {
+    samples: HashMap<i32, CpuSample>,
+}
+
+impl ResourceMonitor {
+    /// Sample a process, returning `None` if it can't be read
+    pub fn sample(&mut self, pid: i32, now: Instant) -> Option<ResourceUsage> {
+        let memory_kb = parse_vm_rss(&read_proc(pid, "status")?)?;
+        let ticks = parse_cpu_ticks(&read_proc(pid, "stat")?)?;
+
+        let current = CpuSample { ticks, at: now };
+        let cpu_percent = match self.samples.insert(pid, current) {
+            Some(prev) => cpu_percent(prev, current, clock_ticks_per_sec()),
+            None => 0.0,
+        };
+
+        Some(ResourceUsage {
+            memory_kb,
+            cpu_percent,
+        })
+    }
+
+    /// Forget processes that are no longer sampled
+    pub fn retain(&mut self, pids: &[i32]) {
+        self.samples.retain(|pid, _| pids.contains(pid));
+    }
+}
+
+/// Spawn the monitoring task; an interval of zero disables it
+pub fn spawn_monitor(state: SharedState, events: EventBroadcaster, interval: Duration) {
+    if interval.is_zero() {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut monitor = ResourceMonitor::default();
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let now = Instant::now();
+            let mut pids = Vec::new();
+
+            let mut state = state.write().await;
+            for session in state.sessions.values_mut() {
+                let Some(pid) = session.pid() else {
+                    session.usage = None;
+                    continue;
+                };
+                pids.push(pid);
+                session.usage = monitor.sample(pid, now);
+                if let Some(usage) = session.usage {
+                    events.emit_session_usage_updated(session.id.clone(), usage);
+                }
+            }
+            monitor.retain(&pids);
+        }
+    });
+}
+
+fn read_proc(pid: i32, file: &str) -> Option<String> {
+    std::fs::read_to_string(format!("/proc/{}/{}", pid, file)).ok()
+}
+
+fn clock_ticks_per_sec() -> u64 {
+    // SAFETY: sysconf has no preconditions
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if ticks > 0 {
+        ticks as u64
+    } else {
+        100
+    }
+}
+
+/// Parse `VmRSS` (in kB) from `/proc/<pid>/status`
+fn parse_vm_rss(status: &str) -> Option<u64> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|kb| kb.parse().ok())
+}
+
+/// Parse user + system CPU time (in clock ticks) from `/proc/<pid>/stat`
+fn parse_cpu_ticks(stat: &str) -> Option<u64> {
+    // The command name may contain spaces and parens, so skip past the last ')'
+    let mut fields = stat.get(stat.rfind(')')? + 1..)?.split_whitespace();
+    // Fields after the name start at `state` (3); utime is 14, stime 15
+    let utime: u64 = fields.nth(11)?.parse().ok()?;
+    let stime: u64 = fields.next()?.parse().ok()?;
+    Some(utime + stime)
+}
+
+fn cpu_percent(prev: CpuSample, current: CpuSample, ticks_per_sec: u64) -> f32 {
+    let elapsed = current.at.duration_since(prev.at).as_secs_f32();
+    if elapsed <= 0.0 || ticks_per_sec == 0 {
+        return 0.0;
+    }
+    let cpu_secs = current.ticks.saturating_sub(prev.ticks) as f32 / ticks_per_sec as f32;
+    cpu_secs / elapsed * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_files() {
+        let status = "Name:\tclaude\nVmPeak:\t  900000 kB\nVmRSS:\t   65536 kB\nThreads:\t12\n";
+        assert_eq!(parse_vm_rss(status), Some(65536));
+        assert_eq!(parse_vm_rss("Name:\tkthreadd\n"), None);
+
+        let stat = "4242 (node (my app)) S 1 4242 4242 0 -1 4194560 1200 0 0 0 250 50 0 0 20 0";
+        assert_eq!(parse_cpu_ticks(stat), Some(300));
+    }
+
+    #[test]
+    fn test_cpu_percent_between_samples() {
+        let start = Instant::now();
+        let prev = CpuSample {
+            ticks: 100,
+            at: start,
+        };
+        let current = CpuSample {
+            ticks: 150,
+            at: start + Duration::from_secs(1),
+        };
+        assert_eq!(cpu_percent(prev, current, 100), 50.0);
+        assert_eq!(cpu_percent(prev, prev, 100), 0.0);
+    }
+
+    #[test]
+    fn test_sample_own_process() {
+        let mut monitor = ResourceMonitor::default();
+        let pid = std::process::id() as i32;
+        if let Some(usage) = monitor.sample(pid, Instant::now()) {
+            assert!(usage.memory_kb > 0);
+            assert_eq!(usage.cpu_percent, 0.0);
+        }
+    }
+}
@@ -1,7 +1,9 @@
 //! Review/comment persistence module
 //!
 //! Stores line comments in ~/.amux/reviews/{repo_id}/{branch}/comments.json
+//! and submitted reviews (snapshots of those comments) in reviews.json
 
+use crate::state::AppState;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -12,9 +14,7 @@ use uuid::Uuid;
 
 /// Comment storage path: ~/.amux/reviews/{repo_id}/{branch}/comments.json
 fn get_review_dir(repo_id: &str, branch: &str) -> Result<PathBuf> {
-    let ccm_dir = dirs::home_dir()
-        .context("Failed to get home directory")?
-        .join(".amux")
+    let ccm_dir = AppState::data_dir()
         .join("reviews")
         .join(repo_id)
         .join(branch);
@@ -25,6 +25,10 @@ fn get_comments_file(repo_id: &str, branch: &str) -> Result<PathBuf> {
     Ok(get_review_dir(repo_id, branch)?.join("comments.json"))
 }
 
+fn get_reviews_file(repo_id: &str, branch: &str) -> Result<PathBuf> {
+    Ok(get_review_dir(repo_id, branch)?.join("reviews.json"))
+}
+
 /// Line type for a comment
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CommentLineType {
@@ -62,6 +66,7 @@ impl From<CommentLineType> for i32 {
 pub struct LineComment {
     pub id: String,
     pub file_path: String,
+    /// `FILE_COMMENT_LINE` for a comment on the whole file
     pub line_number: i32,
     pub line_type: CommentLineType,
     pub comment: String,
@@ -74,6 +79,23 @@ pub struct BranchComments {
     pub comments: Vec<LineComment>,
 }
 
+/// A submitted review
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Review {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub base_ref: String,
+    pub comments: Vec<LineComment>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Reviews submitted on a branch, oldest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BranchReviews {
+    pub reviews: Vec<Review>,
+}
+
 /// Operations for managing review comments
 pub struct ReviewOps;
 
@@ -168,6 +190,24 @@ impl ReviewOps {
         Ok(())
     }
 
+    /// Put back a deleted comment, keeping its ID unless it was reused
+    pub fn restore_comment(
+        repo_id: &str,
+        branch: &str,
+        mut comment: LineComment,
+    ) -> Result<LineComment> {
+        let mut comments = Self::load_comments(repo_id, branch)?;
+
+        if comments.comments.iter().any(|c| c.id == comment.id) {
+            comment.id = Uuid::new_v4().to_string();
+        }
+
+        comments.comments.push(comment.clone());
+        Self::save_comments(repo_id, branch, &comments)?;
+
+        Ok(comment)
+    }
+
     /// List comments for a repo/branch, optionally filtered by file
     pub fn list_comments(
         repo_id: &str,
@@ -188,6 +228,70 @@ impl ReviewOps {
         Ok(filtered)
     }
 
+    /// Load the reviews submitted on a repo/branch
+    pub fn load_reviews(repo_id: &str, branch: &str) -> Result<BranchReviews> {
+        let path = get_reviews_file(repo_id, branch)?;
+        if !path.exists() {
+            return Ok(BranchReviews::default());
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read reviews file: {:?}", path))?;
+        let reviews: BranchReviews = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse reviews file: {:?}", path))?;
+        Ok(reviews)
+    }
+
+    /// Record the branch's current comments as a review
+    pub fn save_review(
+        repo_id: &str,
+        branch: &str,
+        base_ref: &str,
+        title: Option<String>,
+    ) -> Result<Review> {
+        let comments = Self::load_comments(repo_id, branch)?.comments;
+        if comments.is_empty() {
+            anyhow::bail!("No comments to save");
+        }
+
+        let review = Review {
+            id: Uuid::new_v4().to_string(),
+            title,
+            base_ref: base_ref.to_string(),
+            comments,
+            created_at: Utc::now(),
+        };
+
+        let mut reviews = Self::load_reviews(repo_id, branch)?;
+        reviews.reviews.push(review.clone());
+        let dir = get_review_dir(repo_id, branch)?;
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create review directory: {:?}", dir))?;
+        let path = get_reviews_file(repo_id, branch)?;
+        fs::write(&path, serde_json::to_string_pretty(&reviews)?)
+            .with_context(|| format!("Failed to write reviews file: {:?}", path))?;
+
+        Ok(review)
+    }
+
+    /// Add a review's comments back to the branch, skipping those still there
+    pub fn open_review(repo_id: &str, branch: &str, review_id: &str) -> Result<Vec<LineComment>> {
+        let review = Self::load_reviews(repo_id, branch)?
+            .reviews
+            .into_iter()
+            .find(|r| r.id == review_id)
+            .context("Review not found")?;
+
+        let mut comments = Self::load_comments(repo_id, branch)?;
+        for comment in review.comments {
+            if !comments.comments.iter().any(|c| c.id == comment.id) {
+                comments.comments.push(comment);
+            }
+        }
+        Self::save_comments(repo_id, branch, &comments)?;
+
+        Ok(comments.comments)
+    }
+
     /// Get comments grouped by file
     #[allow(dead_code)]
     pub fn get_comments_by_file(
@@ -7,6 +7,7 @@ use crate::events::EventBroadcaster;
 use crate::file_watcher::WatcherManager;
 use crate::handlers;
 use crate::state::SharedState;
+use crate::worktree_status::WorktreeStatusCache;
 use amux_proto::daemon::ccm_daemon_server::CcmDaemon;
 use amux_proto::daemon::*;
 use tonic::{Request, Response, Status, Streaming};
@@ -16,15 +17,24 @@ pub struct CcmDaemonService {
     state: SharedState,
     events: EventBroadcaster,
     pub watcher_manager: WatcherManager,
+    status_cache: WorktreeStatusCache,
+    /// Unix time the service was created, for `GetStatus`
+    started_at: u64,
 }
 
 impl CcmDaemonService {
-    pub fn new(state: SharedState, events: EventBroadcaster) -> Self {
-        let watcher_manager = WatcherManager::new(events.clone());
+    pub fn new(
+        state: SharedState,
+        events: EventBroadcaster,
+        status_cache: WorktreeStatusCache,
+    ) -> Self {
+        let watcher_manager = WatcherManager::new(events.clone(), status_cache.clone());
         Self {
             state,
             events,
             watcher_manager,
+            status_cache,
+            started_at: crate::session::unix_now(),
         }
     }
 
@@ -81,6 +91,12 @@ impl CcmDaemonService {
 
 #[tonic::async_trait]
 impl CcmDaemon for CcmDaemonService {
+    // ============ Daemon ============
+
+    async fn get_status(&self, _request: Request<Empty>) -> Result<Response<DaemonStatus>, Status> {
+        handlers::daemon::get_status(&self.state, &self.events, self.started_at).await
+    }
+
     // ============ Provider Management ============
 
     async fn list_providers(
@@ -110,7 +126,49 @@ impl CcmDaemon for CcmDaemonService {
         &self,
         request: Request<RemoveRepoRequest>,
     ) -> Result<Response<Empty>, Status> {
-        handlers::repo::remove_repo(&self.state, request.into_inner()).await
+        let req = request.into_inner();
+        let repo_id = req.id.clone();
+        let result = handlers::repo::remove_repo(&self.state, req).await?;
+
+        // Its worktrees are no longer watched or cached
+        self.watcher_manager.unwatch_repo(&repo_id).await;
+        self.status_cache.invalidate_repo(&repo_id);
+        Ok(result)
+    }
+
+    async fn reorder_repos(
+        &self,
+        request: Request<ReorderReposRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        handlers::repo::reorder_repos(&self.state, request.into_inner()).await
+    }
+
+    async fn get_repo_stats(
+        &self,
+        request: Request<GetRepoStatsRequest>,
+    ) -> Result<Response<RepoStats>, Status> {
+        handlers::repo::get_repo_stats(&self.state, request.into_inner()).await
+    }
+
+    async fn set_repo_checkpoints(
+        &self,
+        request: Request<SetRepoCheckpointsRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        handlers::repo::set_repo_checkpoints(&self.state, request.into_inner()).await
+    }
+
+    async fn set_repo_env(
+        &self,
+        request: Request<SetRepoEnvRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        handlers::repo::set_repo_env(&self.state, request.into_inner()).await
+    }
+
+    async fn list_repo_env(
+        &self,
+        request: Request<ListRepoEnvRequest>,
+    ) -> Result<Response<ListRepoEnvResponse>, Status> {
+        handlers::repo::list_repo_env(&self.state, request.into_inner()).await
     }
 
     // ============ Worktree Management ============
@@ -119,7 +177,8 @@ impl CcmDaemon for CcmDaemonService {
         &self,
         request: Request<ListWorktreesRequest>,
     ) -> Result<Response<ListWorktreesResponse>, Status> {
-        handlers::worktree::list_worktrees(&self.state, request.into_inner()).await
+        handlers::worktree::list_worktrees(&self.state, &self.status_cache, request.into_inner())
+            .await
     }
 
     async fn create_worktree(
@@ -173,6 +232,73 @@ impl CcmDaemon for CcmDaemonService {
         handlers::worktree::delete_branch(&self.state, request.into_inner()).await
     }
 
+    async fn rename_branch(
+        &self,
+        request: Request<RenameBranchRequest>,
+    ) -> Result<Response<RenameBranchResponse>, Status> {
+        let req = request.into_inner();
+        let (repo_id, old_branch, new_branch) = (
+            req.repo_id.clone(),
+            req.old_branch.clone(),
+            req.new_branch.clone(),
+        );
+
+        let result = handlers::worktree::rename_branch(&self.state, &self.events, req).await?;
+
+        // Re-key the watcher and cached status under the new branch name
+        if let Some(path) = &result.get_ref().worktree_path {
+            self.watcher_manager
+                .unwatch_worktree(&repo_id, &old_branch)
+                .await;
+            self.status_cache.invalidate(&repo_id, &old_branch);
+            if let Err(e) = self
+                .watcher_manager
+                .watch_worktree(
+                    repo_id.clone(),
+                    new_branch.clone(),
+                    std::path::PathBuf::from(path),
+                )
+                .await
+            {
+                tracing::warn!(
+                    "Failed to start watcher for {}/{}: {}",
+                    repo_id,
+                    new_branch,
+                    e
+                );
+            }
+        }
+
+        Ok(result)
+    }
+
+    async fn set_worktree_delete_action(
+        &self,
+        request: Request<SetWorktreeDeleteActionRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        handlers::worktree::set_worktree_delete_action(&self.state, request.into_inner()).await
+    }
+
+    async fn list_directory(
+        &self,
+        request: Request<ListDirectoryRequest>,
+    ) -> Result<Response<ListDirectoryResponse>, Status> {
+        handlers::worktree::list_directory(&self.state, request.into_inner()).await
+    }
+
+    async fn cleanup_worktrees(
+        &self,
+        request: Request<CleanupWorktreesRequest>,
+    ) -> Result<Response<CleanupResult>, Status> {
+        handlers::worktree::cleanup_worktrees(
+            &self.state,
+            &self.events,
+            &self.status_cache,
+            request.into_inner(),
+        )
+        .await
+    }
+
     // ============ Session Management ============
 
     async fn list_sessions(
@@ -196,6 +322,13 @@ impl CcmDaemon for CcmDaemonService {
         handlers::session::rename_session(&self.state, &self.events, request.into_inner()).await
     }
 
+    async fn update_session_meta(
+        &self,
+        request: Request<UpdateSessionMetaRequest>,
+    ) -> Result<Response<SessionInfo>, Status> {
+        handlers::session::update_session_meta(&self.state, request.into_inner()).await
+    }
+
     async fn destroy_session(
         &self,
         request: Request<DestroySessionRequest>,
@@ -203,6 +336,13 @@ impl CcmDaemon for CcmDaemonService {
         handlers::session::destroy_session(&self.state, &self.events, request.into_inner()).await
     }
 
+    async fn restore_session(
+        &self,
+        request: Request<RestoreSessionRequest>,
+    ) -> Result<Response<SessionInfo>, Status> {
+        handlers::session::restore_session(&self.state, &self.events, request.into_inner()).await
+    }
+
     async fn stop_session(
         &self,
         request: Request<StopSessionRequest>,
@@ -210,6 +350,36 @@ impl CcmDaemon for CcmDaemonService {
         handlers::session::stop_session(&self.state, &self.events, request.into_inner()).await
     }
 
+    async fn get_session_stats(
+        &self,
+        request: Request<GetSessionStatsRequest>,
+    ) -> Result<Response<SessionStats>, Status> {
+        handlers::session::get_session_stats(&self.state, request.into_inner()).await
+    }
+
+    async fn export_session_transcript(
+        &self,
+        request: Request<ExportSessionTranscriptRequest>,
+    ) -> Result<Response<SessionTranscript>, Status> {
+        handlers::session::export_session_transcript(&self.state, request.into_inner()).await
+    }
+
+    async fn get_provider_session_file(
+        &self,
+        request: Request<GetProviderSessionFileRequest>,
+    ) -> Result<Response<ProviderSessionFile>, Status> {
+        handlers::session::get_provider_session_file(&self.state, request.into_inner()).await
+    }
+
+    type SearchSessionHistoryStream = handlers::session::SearchSessionHistoryStream;
+
+    async fn search_session_history(
+        &self,
+        request: Request<SearchSessionHistoryRequest>,
+    ) -> Result<Response<Self::SearchSessionHistoryStream>, Status> {
+        handlers::session::search_session_history(&self.state, request.into_inner()).await
+    }
+
     // ============ Events ============
 
     type SubscribeEventsStream = handlers::events::SubscribeEventsStream;
@@ -230,6 +400,13 @@ impl CcmDaemon for CcmDaemonService {
         handlers::diff::get_diff_files(&self.state, request.into_inner()).await
     }
 
+    async fn get_branch_diff(
+        &self,
+        request: Request<GetBranchDiffRequest>,
+    ) -> Result<Response<GetBranchDiffResponse>, Status> {
+        handlers::diff::get_branch_diff(&self.state, request.into_inner()).await
+    }
+
     async fn get_file_diff(
         &self,
         request: Request<GetFileDiffRequest>,
@@ -237,6 +414,27 @@ impl CcmDaemon for CcmDaemonService {
         handlers::diff::get_file_diff(&self.state, request.into_inner()).await
     }
 
+    async fn get_file_lines(
+        &self,
+        request: Request<GetFileLinesRequest>,
+    ) -> Result<Response<GetFileLinesResponse>, Status> {
+        handlers::diff::get_file_lines(&self.state, request.into_inner()).await
+    }
+
+    async fn list_refs(
+        &self,
+        request: Request<ListRefsRequest>,
+    ) -> Result<Response<ListRefsResponse>, Status> {
+        handlers::diff::list_refs(&self.state, request.into_inner()).await
+    }
+
+    async fn get_blame(
+        &self,
+        request: Request<GetBlameRequest>,
+    ) -> Result<Response<GetBlameResponse>, Status> {
+        handlers::diff::get_blame(&self.state, request.into_inner()).await
+    }
+
     // ============ Review/Comment Operations ============
 
     async fn create_line_comment(
@@ -260,6 +458,34 @@ impl CcmDaemon for CcmDaemonService {
         handlers::comments::delete_line_comment(&self.state, request.into_inner()).await
     }
 
+    async fn save_review(
+        &self,
+        request: Request<SaveReviewRequest>,
+    ) -> Result<Response<ReviewInfo>, Status> {
+        handlers::comments::save_review(request.into_inner()).await
+    }
+
+    async fn list_reviews(
+        &self,
+        request: Request<ListReviewsRequest>,
+    ) -> Result<Response<ListReviewsResponse>, Status> {
+        handlers::comments::list_reviews(request.into_inner()).await
+    }
+
+    async fn open_review(
+        &self,
+        request: Request<OpenReviewRequest>,
+    ) -> Result<Response<ListLineCommentsResponse>, Status> {
+        handlers::comments::open_review(request.into_inner()).await
+    }
+
+    async fn restore_line_comment(
+        &self,
+        request: Request<RestoreLineCommentRequest>,
+    ) -> Result<Response<LineCommentInfo>, Status> {
+        handlers::comments::restore_line_comment(request.into_inner()).await
+    }
+
     async fn list_line_comments(
         &self,
         request: Request<ListLineCommentsRequest>,
@@ -324,14 +550,157 @@ impl CcmDaemon for CcmDaemonService {
         &self,
         request: Request<GitPushRequest>,
     ) -> Result<Response<GitPushResponse>, Status> {
-        handlers::git_status::git_push(&self.state, request.into_inner()).await
+        let req = request.into_inner();
+        let (repo_id, branch) = (req.repo_id.clone(), req.branch.clone());
+        let result = handlers::git_status::git_push(&self.state, req).await;
+
+        // Pushing moves the upstream ref, so ahead/behind is stale
+        self.status_cache.invalidate(&repo_id, &branch);
+        result
+    }
+
+    async fn commit_and_push(
+        &self,
+        request: Request<CommitAndPushRequest>,
+    ) -> Result<Response<CommitAndPushResponse>, Status> {
+        let req = request.into_inner();
+        let (repo_id, branch) = (req.repo_id.clone(), req.branch.clone());
+        let result = handlers::git_status::commit_and_push(&self.state, req).await;
+
+        // Committing cleans the worktree and pushing moves the upstream
+        self.status_cache.invalidate(&repo_id, &branch);
+        result
+    }
+
+    async fn fetch(
+        &self,
+        request: Request<FetchRequest>,
+    ) -> Result<Response<FetchResponse>, Status> {
+        let req = request.into_inner();
+        let repo_id = req.repo_id.clone();
+        let result = handlers::git_status::fetch(&self.state, req).await;
+
+        // Fetching moves remote-tracking refs of every branch
+        self.status_cache.invalidate_repo(&repo_id);
+        result
+    }
+
+    async fn pull_worktree(
+        &self,
+        request: Request<PullWorktreeRequest>,
+    ) -> Result<Response<PullWorktreeResponse>, Status> {
+        let req = request.into_inner();
+        let repo_id = req.repo_id.clone();
+        let result = handlers::git_status::pull_worktree(&self.state, req).await;
+
+        // Pulling fetches (all branch upstreams may move) and updates HEAD
+        self.status_cache.invalidate_repo(&repo_id);
+        result
+    }
+
+    // ============ Conflict Operations ============
+
+    async fn list_conflicts(
+        &self,
+        request: Request<ListConflictsRequest>,
+    ) -> Result<Response<ListConflictsResponse>, Status> {
+        handlers::conflicts::list_conflicts(&self.state, request.into_inner()).await
     }
 
-    async fn git_pull(
+    async fn get_file_conflicts(
         &self,
-        request: Request<GitPullRequest>,
-    ) -> Result<Response<GitPullResponse>, Status> {
-        handlers::git_status::git_pull(&self.state, request.into_inner()).await
+        request: Request<GetFileConflictsRequest>,
+    ) -> Result<Response<GetFileConflictsResponse>, Status> {
+        handlers::conflicts::get_file_conflicts(&self.state, request.into_inner()).await
+    }
+
+    async fn resolve_conflicts(
+        &self,
+        request: Request<ResolveConflictsRequest>,
+    ) -> Result<Response<ResolveConflictsResponse>, Status> {
+        let req = request.into_inner();
+        let (repo_id, branch) = (req.repo_id.clone(), req.branch.clone());
+        let result = handlers::conflicts::resolve_conflicts(&self.state, &self.events, req).await;
+        self.status_cache.invalidate(&repo_id, &branch);
+        result
+    }
+
+    // ============ Stash Operations ============
+
+    async fn stash_save(
+        &self,
+        request: Request<StashSaveRequest>,
+    ) -> Result<Response<StashEntry>, Status> {
+        let req = request.into_inner();
+        let (repo_id, branch) = (req.repo_id.clone(), req.branch.clone());
+        let result = handlers::stash::stash_save(&self.state, &self.events, req).await;
+        self.status_cache.invalidate(&repo_id, &branch);
+        result
+    }
+
+    async fn stash_list(
+        &self,
+        request: Request<StashListRequest>,
+    ) -> Result<Response<StashListResponse>, Status> {
+        handlers::stash::stash_list(&self.state, request.into_inner()).await
+    }
+
+    async fn stash_pop(
+        &self,
+        request: Request<StashRefRequest>,
+    ) -> Result<Response<StashApplyResponse>, Status> {
+        let req = request.into_inner();
+        let (repo_id, branch) = (req.repo_id.clone(), req.branch.clone());
+        let result = handlers::stash::stash_apply(&self.state, &self.events, req, true).await;
+        self.status_cache.invalidate(&repo_id, &branch);
+        result
+    }
+
+    async fn stash_apply(
+        &self,
+        request: Request<StashRefRequest>,
+    ) -> Result<Response<StashApplyResponse>, Status> {
+        let req = request.into_inner();
+        let (repo_id, branch) = (req.repo_id.clone(), req.branch.clone());
+        let result = handlers::stash::stash_apply(&self.state, &self.events, req, false).await;
+        self.status_cache.invalidate(&repo_id, &branch);
+        result
+    }
+
+    async fn stash_drop(
+        &self,
+        request: Request<StashRefRequest>,
+    ) -> Result<Response<Empty>, Status> {
+        handlers::stash::stash_drop(&self.state, &self.events, request.into_inner()).await
+    }
+
+    // ============ Checkpoint Operations ============
+
+    async fn list_checkpoints(
+        &self,
+        request: Request<ListCheckpointsRequest>,
+    ) -> Result<Response<ListCheckpointsResponse>, Status> {
+        handlers::checkpoint::list_checkpoints(&self.state, request.into_inner()).await
+    }
+
+    async fn restore_checkpoint(
+        &self,
+        request: Request<RestoreCheckpointRequest>,
+    ) -> Result<Response<CheckpointInfo>, Status> {
+        let req = request.into_inner();
+        let repo_id = {
+            let state = self.state.read().await;
+            state
+                .sessions
+                .get(&req.session_id)
+                .map(|s| s.repo_id.clone())
+        };
+        let result = handlers::checkpoint::restore_checkpoint(&self.state, &self.events, req).await;
+        if let (Some(repo_id), Ok(info)) = (repo_id, &result) {
+            self.status_cache
+                .invalidate(&repo_id, &info.get_ref().branch);
+        }
+        result
     }
 
     // ============ TODO Operations ============
@@ -353,10 +722,17 @@ impl CcmDaemon for CcmDaemonService {
     async fn delete_todo(
         &self,
         request: Request<DeleteTodoRequest>,
-    ) -> Result<Response<Empty>, Status> {
+    ) -> Result<Response<DeleteTodoResponse>, Status> {
         handlers::todo::delete_todo(&self.state, request.into_inner()).await
     }
 
+    async fn restore_todos(
+        &self,
+        request: Request<RestoreTodosRequest>,
+    ) -> Result<Response<RestoreTodosResponse>, Status> {
+        handlers::todo::restore_todos(request.into_inner()).await
+    }
+
     async fn list_todos(
         &self,
         request: Request<ListTodosRequest>,
@@ -377,4 +753,20 @@ impl CcmDaemon for CcmDaemonService {
     ) -> Result<Response<TodoItem>, Status> {
         handlers::todo::reorder_todo(&self.state, request.into_inner()).await
     }
+
+    // ============ Workspace ============
+
+    async fn export_workspace(
+        &self,
+        request: Request<ExportWorkspaceRequest>,
+    ) -> Result<Response<ExportResult>, Status> {
+        handlers::workspace::export_workspace(&self.state, request.into_inner()).await
+    }
+
+    async fn import_workspace(
+        &self,
+        request: Request<ImportWorkspaceRequest>,
+    ) -> Result<Response<ImportResult>, Status> {
+        handlers::workspace::import_workspace(&self.state, &self.events, request.into_inner()).await
+    }
 }
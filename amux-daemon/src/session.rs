@@ -3,20 +3,30 @@
 use crate::persistence::{self, SessionMeta};
 use crate::providers::{AiProvider, ClaudeProvider, ProviderConfig, ProviderRegistry, SessionMode};
 use crate::pty::PtyProcess;
+use crate::resource_monitor::ResourceUsage;
+use crate::terminal_title::{TerminalTitle, TitleChange};
 use amux_config::{DEFAULT_SCROLLBACK, DEFAULT_TERMINAL_COLS, DEFAULT_TERMINAL_ROWS};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::ffi::CString;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::broadcast;
 
 /// Maximum raw buffer size (1MB)
 const MAX_RAW_BUFFER_SIZE: usize = 1024 * 1024;
 
+/// Resolution of `last_output_at`, so steady output doesn't rewrite the metadata
+const LAST_OUTPUT_RESOLUTION_SECS: u64 = 10;
+
 /// Session status
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SessionStatus {
     Running,
     Stopped,
+    Trashed,
 }
 
 /// Session kind - distinguishes interactive, one-shot, and shell sessions
@@ -38,12 +48,20 @@ pub enum SessionKind {
     OneShot,
     /// Plain shell session (no AI provider)
     Shell,
+    /// An arbitrary command line run in a PTY, tracked like a shell session
+    Command {
+        /// Program and its arguments
+        argv: Vec<String>,
+    },
 }
 
 impl SessionKind {
     /// Check if this session should be persisted
     pub fn should_persist(&self) -> bool {
-        matches!(self, SessionKind::Interactive { .. } | SessionKind::Shell)
+        matches!(
+            self,
+            SessionKind::Interactive { .. } | SessionKind::Shell | SessionKind::Command { .. }
+        )
     }
 
     /// Check if this is a shell session
@@ -51,6 +69,14 @@ impl SessionKind {
         matches!(self, SessionKind::Shell)
     }
 
+    /// Argv of a command session (empty for other kinds)
+    pub fn command(&self) -> &[String] {
+        match self {
+            SessionKind::Command { argv } => argv,
+            _ => &[],
+        }
+    }
+
     /// Get provider session ID if interactive
     pub fn provider_session_id(&self) -> Option<&str> {
         match self {
@@ -82,9 +108,21 @@ pub struct Session {
     pub name_updated_from_provider: bool, // Whether name was updated from provider's first message
     pub model: Option<String>, // Model to use (e.g., "haiku", "sonnet")
     pub prompt: Option<String>, // Initial prompt (only used on first start)
+    pub system_prompt: Option<String>, // System prompt passed on every start
+    pub pinned: bool,      // Listed before unpinned sessions of the worktree
+    pub sort_key: u32,     // Position among the worktree's sessions
+    pub trashed_at: Option<u64>, // Unix time the session was moved to the trash
+    pub checkpoint: Option<String>, // Worktree checkpoint commit taken at creation
+    pub cwd: Option<PathBuf>, // Shell's directory relative to the worktree root
+    pub created_at: u64,   // Unix time the session was created
+    pub started_at: u64,   // Unix time the process was last started (0 if never)
+    last_output_at: AtomicU64, // Unix time of the last output, see `LAST_OUTPUT_RESOLUTION_SECS`
     pub pty: Option<PtyProcess>,
     pub screen_buffer: Arc<Mutex<vt100::Parser>>,
     pub raw_output_buffer: Arc<Mutex<Vec<u8>>>,
+    pub title: Arc<Mutex<TerminalTitle>>, // Window title set via OSC 0/2
+    pub usage: Option<ResourceUsage>,     // Last CPU/memory sample of the process
+    pub output: Option<broadcast::Sender<Vec<u8>>>, // PTY output fan-out while clients are attached
 }
 
 impl Session {
@@ -127,6 +165,15 @@ impl Session {
             name_updated_from_provider: false,
             model,
             prompt,
+            system_prompt: None,
+            pinned: false,
+            sort_key: 0,
+            trashed_at: None,
+            checkpoint: None,
+            cwd: None,
+            created_at: unix_now(),
+            started_at: 0,
+            last_output_at: AtomicU64::new(0),
             pty: None,
             screen_buffer: Arc::new(Mutex::new(vt100::Parser::new(
                 DEFAULT_TERMINAL_ROWS,
@@ -134,6 +181,9 @@ impl Session {
                 DEFAULT_SCROLLBACK,
             ))),
             raw_output_buffer: Arc::new(Mutex::new(Vec::new())),
+            title: Arc::new(Mutex::new(TerminalTitle::default())),
+            usage: None,
+            output: None,
         }
     }
 
@@ -207,13 +257,25 @@ impl Session {
             name_updated_from_provider: meta.name_updated_from_provider,
             model: meta.model,
             prompt: None, // Prompt is only used on first start, not restored
-            pty: None,    // PTY will be started on demand
+            system_prompt: meta.system_prompt,
+            pinned: meta.pinned,
+            sort_key: meta.sort_key,
+            trashed_at: meta.trashed_at,
+            checkpoint: meta.checkpoint,
+            cwd: meta.cwd,
+            created_at: meta.created_at,
+            started_at: meta.started_at,
+            last_output_at: AtomicU64::new(meta.last_output_at),
+            pty: None, // PTY will be started on demand
             screen_buffer: Arc::new(Mutex::new(vt100::Parser::new(
                 DEFAULT_TERMINAL_ROWS,
                 DEFAULT_TERMINAL_COLS,
                 DEFAULT_SCROLLBACK,
             ))),
             raw_output_buffer: Arc::new(Mutex::new(Vec::new())),
+            title: Arc::new(Mutex::new(TerminalTitle::default())),
+            usage: None,
+            output: None,
         }
     }
 
@@ -229,6 +291,9 @@ impl Session {
             if let Ok(mut parser) = self.screen_buffer.lock() {
                 parser.process(&history);
             }
+            if let Ok(mut title) = self.title.lock() {
+                title.feed(&history);
+            }
         }
         Ok(())
     }
@@ -239,15 +304,28 @@ impl Session {
         Ok(persistence::save_session(self)?)
     }
 
+    /// Directory the session's process starts in
+    ///
+    /// Falls back to the worktree root if the shell's directory was removed.
+    pub fn working_dir(&self) -> PathBuf {
+        match &self.cwd {
+            Some(cwd) if self.worktree_path.join(cwd).is_dir() => self.worktree_path.join(cwd),
+            _ => self.worktree_path.clone(),
+        }
+    }
+
     /// Start the session (spawn PTY) with default size
-    pub fn start(&mut self, registry: &ProviderRegistry) -> Result<()> {
-        self.start_with_size(registry, DEFAULT_TERMINAL_ROWS, DEFAULT_TERMINAL_COLS)
+    pub fn start(&mut self, registry: &ProviderRegistry, env: &[(String, String)]) -> Result<()> {
+        self.start_with_size(registry, env, DEFAULT_TERMINAL_ROWS, DEFAULT_TERMINAL_COLS)
     }
 
     /// Start the session (spawn PTY) with specific terminal size
+    ///
+    /// `env` holds extra environment variables for the process.
     pub fn start_with_size(
         &mut self,
         registry: &ProviderRegistry,
+        env: &[(String, String)],
         rows: u16,
         cols: u16,
     ) -> Result<()> {
@@ -259,7 +337,21 @@ impl Session {
         let pty = match &self.kind {
             SessionKind::Shell => {
                 // Shell session - run plain shell (no provider)
-                PtyProcess::spawn_shell(&self.worktree_path, rows, cols)?
+                PtyProcess::spawn_shell(&self.working_dir(), env, rows, cols)?
+            }
+            SessionKind::Command { argv } => {
+                let args = argv
+                    .iter()
+                    .map(|arg| CString::new(arg.as_str()))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                let cmd = args
+                    .first()
+                    .cloned()
+                    .ok_or_else(|| anyhow::anyhow!("Empty command"))?;
+
+                tracing::info!("Spawning PTY with command {:?}", argv);
+
+                PtyProcess::spawn(&self.worktree_path, cmd, args, env, rows, cols)?
             }
             SessionKind::OneShot => {
                 // One-shot session with prompt
@@ -268,6 +360,7 @@ impl Session {
                     session_mode: SessionMode::OneShot,
                     model: self.model.clone(),
                     prompt,
+                    system_prompt: self.system_prompt.clone(),
                 };
 
                 // Get provider and build command
@@ -284,7 +377,7 @@ impl Session {
                     cols
                 );
 
-                PtyProcess::spawn(&self.worktree_path, cmd, args, rows, cols)?
+                PtyProcess::spawn(&self.worktree_path, cmd, args, env, rows, cols)?
             }
             SessionKind::Interactive {
                 provider_session_id,
@@ -315,6 +408,7 @@ impl Session {
                     session_mode,
                     model: self.model.clone(),
                     prompt,
+                    system_prompt: self.system_prompt.clone(),
                 };
 
                 // Get provider and build command
@@ -331,11 +425,12 @@ impl Session {
                     cols
                 );
 
-                PtyProcess::spawn(&self.worktree_path, cmd, args, rows, cols)?
+                PtyProcess::spawn(&self.worktree_path, cmd, args, env, rows, cols)?
             }
         };
 
         self.pty = Some(pty);
+        self.started_at = unix_now();
 
         // Mark interactive session as started for next time
         self.kind.mark_started();
@@ -353,12 +448,38 @@ impl Session {
 
     /// Get session status
     pub fn status(&self) -> SessionStatus {
+        if self.trashed_at.is_some() {
+            return SessionStatus::Trashed;
+        }
         match &self.pty {
             Some(pty) if pty.is_running() => SessionStatus::Running,
             _ => SessionStatus::Stopped,
         }
     }
 
+    /// Whether the process exited while the daemon still holds its PTY
+    ///
+    /// Nothing sends a status change when a process exits on its own, so
+    /// clients keep showing such a session as running until it is attached.
+    pub fn process_exited(&self) -> bool {
+        matches!(&self.pty, Some(pty) if !pty.is_running())
+    }
+
+    /// PID of the session's process while it is running
+    pub fn pid(&self) -> Option<i32> {
+        match &self.pty {
+            Some(pty) if pty.is_running() => Some(pty.pid().as_raw()),
+            _ => None,
+        }
+    }
+
+    /// Number of clients attached to the session's output
+    pub fn attach_count(&self) -> u32 {
+        self.output
+            .as_ref()
+            .map_or(0, |tx| tx.receiver_count() as u32)
+    }
+
     /// Read from PTY
     pub fn read(&self, buf: &mut [u8]) -> Result<usize> {
         match &self.pty {
@@ -394,12 +515,19 @@ impl Session {
     }
 
     /// Process output data (store in buffers)
-    pub fn process_output(&self, data: &[u8]) {
+    ///
+    /// Returns true when `last_output_at` moved, so it can be persisted.
+    pub fn process_output(&self, data: &[u8]) -> bool {
         // Update screen buffer
         if let Ok(mut parser) = self.screen_buffer.lock() {
             parser.process(data);
         }
 
+        // Track window title changes
+        if let Ok(mut title) = self.title.lock() {
+            title.feed(data);
+        }
+
         // Store raw output for history replay
         if let Ok(mut buffer) = self.raw_output_buffer.lock() {
             buffer.extend_from_slice(data);
@@ -409,6 +537,33 @@ impl Session {
                 buffer.drain(..excess);
             }
         }
+
+        self.record_output(unix_now())
+    }
+
+    /// Move the output clock to `now` if it is `LAST_OUTPUT_RESOLUTION_SECS` behind
+    fn record_output(&self, now: u64) -> bool {
+        let last = self.last_output_at.load(Ordering::Relaxed);
+        if now < last + LAST_OUTPUT_RESOLUTION_SECS {
+            return false;
+        }
+        self.last_output_at.store(now, Ordering::Relaxed);
+        true
+    }
+
+    /// Unix time of the last output (0 if none seen)
+    pub fn last_output_at(&self) -> u64 {
+        self.last_output_at.load(Ordering::Relaxed)
+    }
+
+    /// Window title last set by the program in this session
+    pub fn title(&self) -> Option<String> {
+        self.title.lock().ok()?.current().map(str::to_string)
+    }
+
+    /// Take a debounced title change, see `TerminalTitle::take_change`
+    pub fn take_title_change(&self) -> Option<TitleChange> {
+        self.title.lock().ok()?.take_change(Instant::now())
     }
 
     /// Get screen state (raw buffer for replay)
@@ -420,6 +575,16 @@ impl Session {
         }
     }
 
+    /// Find the first transcript line containing `query` (case-insensitive)
+    pub fn find_in_transcript(&self, query: &str) -> Option<String> {
+        let query = query.to_lowercase();
+        let raw = self.raw_output_buffer.lock().ok()?;
+        let text = strip_ansi(&raw);
+        text.lines()
+            .find(|line| line.to_lowercase().contains(&query))
+            .map(|line| line.trim().to_string())
+    }
+
     /// Update session name from provider's first user message
     pub fn update_name_from_provider(&mut self) {
         if self.name_updated_from_provider {
@@ -443,6 +608,61 @@ impl Session {
     }
 }
 
+/// Current unix time in seconds
+pub(crate) fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Strip escape sequences and control characters from raw PTY output
+///
+/// Carriage returns become line breaks so redrawn lines stay separate.
+pub fn strip_ansi(data: &[u8]) -> String {
+    let text = String::from_utf8_lossy(data);
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => match chars.next() {
+                // CSI: parameters until a final byte in @..~
+                Some('[') => {
+                    for c in chars.by_ref() {
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+                // OSC: until BEL or ST (ESC \)
+                Some(']') => {
+                    while let Some(c) = chars.next() {
+                        if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                            break;
+                        }
+                    }
+                }
+                // Charset designation takes one more byte
+                Some('(' | ')' | '*' | '+') => {
+                    chars.next();
+                }
+                // Other two-byte sequences (keypad mode, save cursor, ...)
+                _ => {}
+            },
+            '\r' => {
+                if chars.peek() != Some(&'\n') {
+                    out.push('\n');
+                }
+            }
+            '\n' | '\t' => out.push(c),
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
 /// Generate a unique session ID
 pub fn generate_session_id() -> String {
     uuid::Uuid::new_v4().to_string()
@@ -479,6 +699,12 @@ mod tests {
         assert!(uuid::Uuid::parse_str(&id).is_ok());
     }
 
+    #[test]
+    fn test_strip_ansi_removes_sequences() {
+        let raw = b"\x1b[1;32mgreen\x1b[0m text\r\n\x1b]0;title\x07next\x1b(B line\rredraw";
+        assert_eq!(strip_ansi(raw), "green text\nnext line\nredraw");
+    }
+
     #[test]
     fn test_generate_session_name_first_session() {
         let existing: Vec<String> = vec![];
@@ -514,4 +740,52 @@ mod tests {
         let name = generate_session_name("codex", &existing);
         assert_eq!(name, "codex-1");
     }
+
+    #[test]
+    fn test_command_session_meta_round_trip() {
+        let argv = vec!["cargo".to_string(), "watch".to_string()];
+        let session = Session::with_kind(
+            generate_session_id(),
+            "cargo watch".to_string(),
+            "repo".to_string(),
+            "main".to_string(),
+            PathBuf::from("/tmp"),
+            "command".to_string(),
+            SessionKind::Command { argv: argv.clone() },
+            None,
+            None,
+        );
+        assert!(session.kind.should_persist());
+
+        let json = serde_json::to_string(&SessionMeta::from_session(&session)).unwrap();
+        let restored = Session::from_meta(serde_json::from_str(&json).unwrap());
+        assert_eq!(restored.kind.command(), argv);
+        assert!(!restored.is_shell());
+    }
+
+    #[test]
+    fn test_output_clock_is_coarse_and_persisted() {
+        let mut session = Session::new(
+            generate_session_id(),
+            "shell".to_string(),
+            "repo".to_string(),
+            "main".to_string(),
+            PathBuf::from("/tmp"),
+            "claude".to_string(),
+            None,
+            true,
+            None,
+            None,
+        );
+        session.started_at = 900;
+        assert!(session.record_output(1000));
+        assert!(!session.record_output(1005));
+        assert_eq!(session.last_output_at(), 1000);
+        assert!(session.record_output(1010));
+
+        let json = serde_json::to_string(&SessionMeta::from_session(&session)).unwrap();
+        let restored = Session::from_meta(serde_json::from_str(&json).unwrap());
+        assert_eq!(restored.started_at, 900);
+        assert_eq!(restored.last_output_at(), 1010);
+    }
 }
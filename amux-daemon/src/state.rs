@@ -1,7 +1,10 @@
 //! Application state management
 
+use crate::blame::BlameCache;
+use crate::checkpoint;
 use crate::providers::ProviderRegistry;
 use crate::repo::Repo;
+use crate::repo_stats::RepoStatsCache;
 use crate::session::Session;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -11,14 +14,26 @@ use tokio::sync::RwLock;
 /// Shared application state
 pub type SharedState = Arc<RwLock<AppState>>;
 
+/// Data directory used instead of `~/.amux`, set by the test harness
+#[cfg(feature = "testing")]
+pub static DATA_DIR_OVERRIDE: std::sync::RwLock<Option<PathBuf>> = std::sync::RwLock::new(None);
+
 /// Application state containing repos and sessions
 pub struct AppState {
     /// Repos indexed by ID
     pub repos: HashMap<String, Repo>,
+    /// Repo IDs in display order (persisted as the order of repos.json)
+    pub repo_order: Vec<String>,
     /// Sessions indexed by ID
     pub sessions: HashMap<String, Session>,
+    /// Destroyed sessions that can still be restored, indexed by ID
+    pub trash: HashMap<String, Session>,
     /// Cached provider registry (created once, shared across handlers)
     pub provider_registry: Arc<ProviderRegistry>,
+    /// Recently computed file blames
+    pub blame_cache: BlameCache,
+    /// Recently computed commits ahead and disk usage of repos
+    pub repo_stats_cache: RepoStatsCache,
 }
 
 impl Default for AppState {
@@ -31,13 +46,124 @@ impl AppState {
     pub fn new() -> Self {
         Self {
             repos: HashMap::new(),
+            repo_order: Vec::new(),
             sessions: HashMap::new(),
+            trash: HashMap::new(),
             provider_registry: Arc::new(ProviderRegistry::new()),
+            blame_cache: BlameCache::default(),
+            repo_stats_cache: RepoStatsCache::default(),
+        }
+    }
+
+    /// Add a repo at the end of the display order
+    pub fn insert_repo(&mut self, repo: Repo) {
+        if !self.repo_order.contains(&repo.id) {
+            self.repo_order.push(repo.id.clone());
+        }
+        self.repos.insert(repo.id.clone(), repo);
+    }
+
+    /// Remove a repo and its place in the display order
+    pub fn remove_repo(&mut self, id: &str) -> Option<Repo> {
+        self.repo_order.retain(|r| r != id);
+        self.repo_stats_cache.remove(id);
+        self.repos.remove(id)
+    }
+
+    /// Repos in display order
+    pub fn ordered_repos(&self) -> Vec<Repo> {
+        self.repo_order
+            .iter()
+            .filter_map(|id| self.repos.get(id))
+            .cloned()
+            .collect()
+    }
+
+    /// Move the given repo IDs to the front of the display order
+    ///
+    /// Unknown IDs are ignored and repos not mentioned keep their relative
+    /// order after the listed ones.
+    pub fn reorder_repos(&mut self, ids: &[String]) {
+        let mut order: Vec<String> = Vec::with_capacity(self.repo_order.len());
+        for id in ids {
+            if self.repos.contains_key(id) && !order.contains(id) {
+                order.push(id.clone());
+            }
+        }
+        for id in &self.repo_order {
+            if !order.contains(id) {
+                order.push(id.clone());
+            }
+        }
+        self.repo_order = order;
+    }
+
+    /// Sort key for a session added at the end of its worktree
+    pub fn next_sort_key(&self, repo_id: &str, branch: &str) -> u32 {
+        self.sessions
+            .values()
+            .filter(|s| s.repo_id == repo_id && s.branch == branch)
+            .count() as u32
+    }
+
+    /// Renumber a worktree's sessions 0..n, optionally moving one to `position`
+    ///
+    /// Sessions keep their relative order (by sort key, then name). Returns
+    /// the IDs of sessions whose sort key changed.
+    pub fn renumber_sessions(
+        &mut self,
+        repo_id: &str,
+        branch: &str,
+        moved: Option<(&str, usize)>,
+    ) -> Vec<String> {
+        let mut ids: Vec<(u32, String, String)> = self
+            .sessions
+            .values()
+            .filter(|s| s.repo_id == repo_id && s.branch == branch)
+            .map(|s| (s.sort_key, s.name.clone(), s.id.clone()))
+            .collect();
+        ids.sort();
+        let mut ids: Vec<String> = ids.into_iter().map(|(_, _, id)| id).collect();
+
+        if let Some((id, position)) = moved {
+            if let Some(idx) = ids.iter().position(|i| i == id) {
+                let id = ids.remove(idx);
+                ids.insert(position.min(ids.len()), id);
+            }
+        }
+
+        let mut changed = Vec::new();
+        for (key, id) in ids.into_iter().enumerate() {
+            if let Some(session) = self.sessions.get_mut(&id) {
+                if session.sort_key != key as u32 {
+                    session.sort_key = key as u32;
+                    changed.push(id);
+                }
+            }
+        }
+        changed
+    }
+
+    /// Delete a session's worktree checkpoint once the session is gone for good
+    pub fn prune_checkpoint(&self, repo_id: &str, session_id: &str) {
+        let Some(repo) = self.repos.get(repo_id) else {
+            return;
+        };
+        if let Err(e) = checkpoint::prune(&repo.path, session_id) {
+            tracing::warn!(
+                "Failed to delete checkpoint of session {}: {}",
+                session_id,
+                e
+            );
         }
     }
 
     /// Get Amux data directory (~/.amux/)
     pub fn data_dir() -> PathBuf {
+        #[cfg(feature = "testing")]
+        if let Some(dir) = DATA_DIR_OVERRIDE.read().unwrap().clone() {
+            return dir;
+        }
         dirs::home_dir()
             .expect("Cannot find home directory")
             .join(".amux")
@@ -67,3 +193,87 @@ impl AppState {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo(id: &str) -> Repo {
+        Repo {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: PathBuf::from(format!("/tmp/{}", id)),
+            delete_actions: HashMap::new(),
+            checkpoints: false,
+            env: Default::default(),
+            worktree_env: HashMap::new(),
+        }
+    }
+
+    fn ids(state: &AppState) -> Vec<String> {
+        state.ordered_repos().into_iter().map(|r| r.id).collect()
+    }
+
+    #[test]
+    fn test_reorder_repos_keeps_unlisted_after() {
+        let mut state = AppState::new();
+        for id in ["a", "b", "c", "d"] {
+            state.insert_repo(repo(id));
+        }
+
+        state.reorder_repos(&["c".to_string(), "unknown".to_string(), "a".to_string()]);
+        assert_eq!(ids(&state), vec!["c", "a", "b", "d"]);
+
+        state.remove_repo("a");
+        assert_eq!(ids(&state), vec!["c", "b", "d"]);
+    }
+
+    fn add_session(state: &mut AppState, name: &str, branch: &str) {
+        let mut session = Session::new(
+            name.to_string(),
+            name.to_string(),
+            "repo".to_string(),
+            branch.to_string(),
+            PathBuf::from("/tmp/repo"),
+            "shell".to_string(),
+            None,
+            true,
+            None,
+            None,
+        );
+        session.sort_key = state.next_sort_key("repo", branch);
+        state.sessions.insert(session.id.clone(), session);
+    }
+
+    fn session_order(state: &AppState) -> Vec<String> {
+        let mut sessions: Vec<_> = state
+            .sessions
+            .values()
+            .filter(|s| s.branch == "main")
+            .collect();
+        sessions.sort_by_key(|s| s.sort_key);
+        sessions.iter().map(|s| s.id.clone()).collect()
+    }
+
+    #[test]
+    fn test_renumber_sessions_moves_and_compacts() {
+        let mut state = AppState::new();
+        for name in ["a", "b", "c", "d"] {
+            add_session(&mut state, name, "main");
+        }
+        add_session(&mut state, "other", "feature");
+        assert_eq!(state.sessions["d"].sort_key, 3);
+        assert_eq!(state.sessions["other"].sort_key, 0);
+
+        let changed = state.renumber_sessions("repo", "main", Some(("a", 2)));
+        assert_eq!(session_order(&state), vec!["b", "c", "a", "d"]);
+        assert_eq!(changed.len(), 3);
+
+        state.sessions.remove("c");
+        let changed = state.renumber_sessions("repo", "main", None);
+        assert_eq!(session_order(&state), vec!["b", "a", "d"]);
+        assert_eq!(changed, vec!["a".to_string(), "d".to_string()]);
+        assert_eq!(state.sessions["d"].sort_key, 2);
+        assert_eq!(state.sessions["other"].sort_key, 0);
+    }
+}
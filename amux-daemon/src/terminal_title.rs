@@ -0,0 +1,237 @@
+//! Window title tracking from OSC 0/2 sequences
+//!
+//! Programs set the terminal title with `ESC ] 0 ; title ST` or
+//! `ESC ] 2 ; title ST`, where ST is BEL or `ESC \`. A PTY read can end in
+//! the middle of a sequence, so the scanner is a byte-level state machine
+//! that carries its state over to the next chunk.
+
+use std::time::{Duration, Instant};
+
+/// Maximum title length kept, in characters
+pub const MAX_TITLE_LEN: usize = 120;
+
+/// Minimum interval between reported title changes
+pub const TITLE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Longest OSC body buffered while waiting for its terminator
+const MAX_OSC_LEN: usize = 4096;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum ScanState {
+    #[default]
+    Ground,
+    /// Saw ESC outside an OSC
+    Escape,
+    /// Inside `ESC ]`, collecting the body
+    Osc,
+    /// Saw ESC inside an OSC (possible `ESC \` terminator)
+    OscEscape,
+}
+
+/// Title change to report after a chunk of output
+#[derive(Debug, PartialEq, Eq)]
+pub enum TitleChange {
+    /// Report this title now; empty means the title was cleared
+    Report(String),
+    /// A change is held back; take it again at this instant
+    FlushAt(Instant),
+}
+
+/// Latest title set by the program running in a session
+#[derive(Debug, Default)]
+pub struct TerminalTitle {
+    state: ScanState,
+    body: Vec<u8>,
+    /// Body exceeded `MAX_OSC_LEN` and will be discarded
+    overflow: bool,
+    current: Option<String>,
+    reported: Option<String>,
+    last_report: Option<Instant>,
+    /// A held-back change was handed out as `FlushAt`
+    flush_scheduled: bool,
+}
+
+impl TerminalTitle {
+    /// Scan a chunk of PTY output for title changes
+    pub fn feed(&mut self, data: &[u8]) {
+        for &b in data {
+            match self.state {
+                ScanState::Ground => {
+                    if b == 0x1b {
+                        self.state = ScanState::Escape;
+                    }
+                }
+                ScanState::Escape => self.after_escape(b),
+                ScanState::Osc => match b {
+                    0x07 => self.finish_osc(),
+                    0x1b => self.state = ScanState::OscEscape,
+                    // CAN and SUB abort the sequence
+                    0x18 | 0x1a => self.state = ScanState::Ground,
+                    _ if self.body.len() < MAX_OSC_LEN => self.body.push(b),
+                    _ => self.overflow = true,
+                },
+                ScanState::OscEscape => {
+                    if b == b'\\' {
+                        self.finish_osc();
+                    } else {
+                        // Unterminated OSC interrupted by another escape
+                        self.after_escape(b);
+                    }
+                }
+            }
+        }
+    }
+
+    fn after_escape(&mut self, b: u8) {
+        self.state = match b {
+            b']' => {
+                self.body.clear();
+                self.overflow = false;
+                ScanState::Osc
+            }
+            0x1b => ScanState::Escape,
+            _ => ScanState::Ground,
+        };
+    }
+
+    fn finish_osc(&mut self) {
+        self.state = ScanState::Ground;
+        if self.overflow {
+            return;
+        }
+        let title = match self.body.as_slice() {
+            [b'0' | b'2', b';', title @ ..] => title,
+            _ => return,
+        };
+        self.current = sanitize_title(title);
+    }
+
+    /// Current title, if the program set one
+    pub fn current(&self) -> Option<&str> {
+        self.current.as_deref()
+    }
+
+    /// Take a title change that hasn't been reported yet
+    ///
+    /// Changes are reported at most once per `TITLE_DEBOUNCE`. The first
+    /// change held back in an interval comes out as `FlushAt` its end, so
+    /// the caller takes it then even if no more output arrives; until that
+    /// flush, further changes give None.
+    pub fn take_change(&mut self, now: Instant) -> Option<TitleChange> {
+        if self.current == self.reported {
+            self.flush_scheduled = false;
+            return None;
+        }
+        if let Some(due) = self.last_report.map(|last| last + TITLE_DEBOUNCE) {
+            if now < due {
+                if std::mem::replace(&mut self.flush_scheduled, true) {
+                    return None;
+                }
+                return Some(TitleChange::FlushAt(due));
+            }
+        }
+        self.flush_scheduled = false;
+        self.reported = self.current.clone();
+        self.last_report = Some(now);
+        Some(TitleChange::Report(
+            self.current.clone().unwrap_or_default(),
+        ))
+    }
+}
+
+/// Decode a title, dropping control characters and capping its length
+fn sanitize_title(raw: &[u8]) -> Option<String> {
+    let text = String::from_utf8_lossy(raw);
+    let text: String = text.chars().filter(|c| !c.is_control()).collect();
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    if text.chars().count() > MAX_TITLE_LEN {
+        let mut capped: String = text.chars().take(MAX_TITLE_LEN - 1).collect();
+        capped.push('…');
+        Some(capped)
+    } else {
+        Some(text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(chunks: &[&[u8]]) -> TerminalTitle {
+        let mut title = TerminalTitle::default();
+        for chunk in chunks {
+            title.feed(chunk);
+        }
+        title
+    }
+
+    #[test]
+    fn test_osc_title_terminators() {
+        assert_eq!(
+            scan(&[b"\x1b]0;vim main.rs\x07"]).current(),
+            Some("vim main.rs")
+        );
+        assert_eq!(scan(&[b"a\x1b]2;htop\x1b\\b"]).current(), Some("htop"));
+        // Icon name only (OSC 1) and other OSCs are ignored
+        assert_eq!(
+            scan(&[b"\x1b]1;icon\x07\x1b]8;;http://x\x07"]).current(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_osc_title_split_across_chunks() {
+        let title = scan(&[b"out\x1b", b"]", b"2;car", b"go test\x1b", b"\\more"]);
+        assert_eq!(title.current(), Some("cargo test"));
+
+        // Latest complete title wins, unfinished one is kept pending
+        let mut title = scan(&[b"\x1b]0;first\x07\x1b]0;sec"]);
+        assert_eq!(title.current(), Some("first"));
+        title.feed(b"ond\x07");
+        assert_eq!(title.current(), Some("second"));
+    }
+
+    #[test]
+    fn test_osc_title_capped_and_sanitized() {
+        let long = format!("\x1b]0;{}\x07", "x".repeat(500));
+        let title = scan(&[long.as_bytes()]);
+        assert_eq!(title.current().unwrap().chars().count(), MAX_TITLE_LEN);
+        assert_eq!(scan(&[b"\x1b]0;  a\tb  \x07"]).current(), Some("ab"));
+        assert_eq!(scan(&[b"\x1b]0;x\x07\x1b]0;\x07"]).current(), None);
+    }
+
+    #[test]
+    fn test_title_change_debounced() {
+        let start = Instant::now();
+        let report = |title: &str| Some(TitleChange::Report(title.to_string()));
+        let mut title = scan(&[b"\x1b]0;one\x07"]);
+        assert_eq!(title.take_change(start), report("one"));
+        assert_eq!(title.take_change(start), None);
+
+        // Held back until the interval ends, flushed only once
+        title.feed(b"\x1b]0;two\x07");
+        let due = start + TITLE_DEBOUNCE;
+        assert_eq!(
+            title.take_change(start + Duration::from_millis(100)),
+            Some(TitleChange::FlushAt(due))
+        );
+        title.feed(b"\x1b]0;three\x07");
+        assert_eq!(title.take_change(start + Duration::from_millis(200)), None);
+        assert_eq!(title.take_change(due), report("three"));
+
+        // Changed back before the flush: nothing to report
+        title.feed(b"\x1b]0;four\x07");
+        let due = due + TITLE_DEBOUNCE;
+        assert_eq!(
+            title.take_change(due - TITLE_DEBOUNCE),
+            Some(TitleChange::FlushAt(due))
+        );
+        title.feed(b"\x1b]0;three\x07");
+        assert_eq!(title.take_change(due), None);
+        title.feed(b"\x1b]0;five\x07");
+        assert_eq!(title.take_change(due), report("five"));
+    }
+}
@@ -0,0 +1,213 @@
+//! In-process daemon for tests
+//!
+//! [`TestDaemon`] serves the gRPC service on a Unix socket in a temporary
+//! directory and hands back a client connected to it. Providers are mocks:
+//! `claude` and `codex` from [`ProviderRegistry::test_registry`], plus a
+//! `mock` provider running a chosen [`MockScript`]. Everything the daemon
+//! keeps in `~/.amux` (repos.json, sessions, worktrees, reviews) goes to a
+//! temporary directory instead, shared by the daemons alive in the process
+//! (the data directory is process-wide) and deleted with the last of them.
+//!
+//! Only built with the `testing` feature.
+
+use crate::events::EventBroadcaster;
+use crate::providers::{MockProvider, MockScript, ProviderRegistry};
+use crate::server::CcmDaemonService;
+use crate::state::{AppState, SharedState, DATA_DIR_OVERRIDE};
+use crate::worktree_status::WorktreeStatusCache;
+use amux_cli::client::Client;
+use amux_proto::daemon::ccm_daemon_server::CcmDaemonServer;
+use amux_proto::daemon::{event, Event};
+use std::path::Path;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::net::UnixListener;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::UnixListenerStream;
+use tonic::transport::Server;
+use tonic::Streaming;
+
+/// How long [`EventStream::expect`] waits for a matching event
+pub const EVENT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Branch checked out in repos made by [`TestDaemon::add_repo`]
+pub const MAIN_BRANCH: &str = "main";
+
+/// Data directory of the daemons alive in this process
+static DATA_DIR: Mutex<Weak<TempDir>> = Mutex::new(Weak::new());
+
+/// The current data directory, or a new one if no daemon holds it
+///
+/// Also for unit tests of code writing under `AppState::data_dir()`.
+pub(crate) fn shared_data_dir() -> Arc<TempDir> {
+    let mut shared = DATA_DIR.lock().unwrap();
+    if let Some(dir) = shared.upgrade() {
+        return dir;
+    }
+    let dir = Arc::new(
+        tempfile::Builder::new()
+            .prefix("amux-test-data-")
+            .tempdir()
+            .expect("create test data directory"),
+    );
+    *DATA_DIR_OVERRIDE.write().unwrap() = Some(dir.path().to_path_buf());
+    *shared = Arc::downgrade(&dir);
+    dir
+}
+
+/// A daemon serving on a temporary socket, stopped when dropped
+pub struct TestDaemon {
+    /// Client connected to the daemon
+    pub client: Client,
+    /// The daemon's state, for checks the API doesn't expose
+    pub state: SharedState,
+    server: JoinHandle<()>,
+    _socket_dir: TempDir,
+    _data_dir: Arc<TempDir>,
+}
+
+impl TestDaemon {
+    /// Start a daemon whose `mock` provider echoes its input
+    pub async fn start() -> Self {
+        Self::with_script(MockScript::Echo).await
+    }
+
+    /// Start a daemon whose `mock` provider runs `script`
+    pub async fn with_script(script: MockScript) -> Self {
+        let data_dir = shared_data_dir();
+
+        let mut registry = ProviderRegistry::test_registry();
+        registry.register(Arc::new(MockProvider::new(script)));
+        let mut app_state = AppState::new();
+        app_state.provider_registry = Arc::new(registry);
+        let state: SharedState = Arc::new(RwLock::new(app_state));
+
+        let service = CcmDaemonService::new(
+            state.clone(),
+            EventBroadcaster::new(),
+            WorktreeStatusCache::new(Duration::from_millis(500)),
+        );
+
+        let socket_dir = tempfile::tempdir().expect("create socket directory");
+        let socket_path = socket_dir.path().join("daemon.sock");
+        let listener = UnixListener::bind(&socket_path).expect("bind test daemon socket");
+        let server = tokio::spawn(async move {
+            let result = Server::builder()
+                .add_service(CcmDaemonServer::new(service))
+                .serve_with_incoming(UnixListenerStream::new(listener))
+                .await;
+            if let Err(e) = result {
+                tracing::warn!("Test daemon stopped: {}", e);
+            }
+        });
+
+        let client = Client::connect_to(&socket_path)
+            .await
+            .expect("connect to test daemon");
+
+        Self {
+            client,
+            state,
+            server,
+            _socket_dir: socket_dir,
+            _data_dir: data_dir,
+        }
+    }
+
+    /// Subscribe to the events of all repos
+    ///
+    /// Events emitted after this returns are all received.
+    pub async fn subscribe(&self) -> EventStream {
+        let stream = self
+            .client
+            .clone()
+            .subscribe_events(None, false)
+            .await
+            .expect("subscribe to events");
+        EventStream { stream }
+    }
+
+    /// Register a new repo with one commit on [`MAIN_BRANCH`]
+    ///
+    /// Returns the repo ID and the directory holding the repo, which is
+    /// deleted when dropped.
+    pub async fn add_repo(&self) -> (String, TempDir) {
+        let dir = tempfile::Builder::new()
+            .prefix("amux-test-repo-")
+            .tempdir()
+            .expect("create repo directory");
+        init_repo(dir.path());
+        let repo = self
+            .client
+            .clone()
+            .add_repo(&dir.path().to_string_lossy())
+            .await
+            .expect("add repo");
+        (repo.id, dir)
+    }
+}
+
+impl Drop for TestDaemon {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+/// Events from [`TestDaemon::subscribe`]
+pub struct EventStream {
+    stream: Streaming<Event>,
+}
+
+impl EventStream {
+    /// Wait for the first event `pick` returns something for
+    ///
+    /// Events before it are skipped. Panics after [`EVENT_TIMEOUT`] or if
+    /// the stream ends.
+    pub async fn expect<T>(&mut self, mut pick: impl FnMut(event::Event) -> Option<T>) -> T {
+        let wait = async {
+            loop {
+                let event = self
+                    .stream
+                    .message()
+                    .await
+                    .expect("event stream failed")
+                    .expect("event stream ended");
+                if let Some(found) = event.event.and_then(&mut pick) {
+                    return found;
+                }
+            }
+        };
+        tokio::time::timeout(EVENT_TIMEOUT, wait)
+            .await
+            .expect("timed out waiting for event")
+    }
+}
+
+/// `git init` with a README committed on [`MAIN_BRANCH`]
+fn init_repo(path: &Path) {
+    let mut options = git2::RepositoryInitOptions::new();
+    options.initial_head(MAIN_BRANCH);
+    let repo = git2::Repository::init_opts(path, &options).expect("init repo");
+
+    std::fs::write(path.join("README.md"), "test\n").expect("write README");
+    let mut index = repo.index().expect("open index");
+    index
+        .add_path(Path::new("README.md"))
+        .expect("stage README");
+    index.write().expect("write index");
+    let tree = repo
+        .find_tree(index.write_tree().expect("write tree"))
+        .expect("find tree");
+    let signature = git2::Signature::now("amux", "amux@example.com").expect("signature");
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        "Initial commit",
+        &tree,
+        &[],
+    )
+    .expect("commit");
+}
@@ -171,8 +171,8 @@ impl TodoOps {
         Ok(updated)
     }
 
-    /// Delete a TODO item
-    pub fn delete_todo(repo_id: &str, todo_id: &str) -> Result<(), PersistenceError> {
+    /// Delete a TODO item and its descendants, returning the removed items
+    pub fn delete_todo(repo_id: &str, todo_id: &str) -> Result<Vec<TodoItem>, PersistenceError> {
         let mut todos = Self::load_todos(repo_id)?;
 
         // Find and collect all descendant IDs (recursive)
@@ -189,7 +189,11 @@ impl TodoOps {
         }
 
         // Remove all items
-        todos.items.retain(|item| !to_delete.contains(&item.id));
+        let (deleted, kept) = todos
+            .items
+            .into_iter()
+            .partition(|item| to_delete.contains(&item.id));
+        todos.items = kept;
         Self::save_todos(repo_id, &todos)?;
 
         info!(
@@ -197,7 +201,45 @@ impl TodoOps {
             to_delete.len(),
             repo_id
         );
-        Ok(())
+        Ok(deleted)
+    }
+
+    /// Put back deleted TODO items
+    ///
+    /// Items whose ID is already taken are skipped; items whose parent no
+    /// longer exists become top-level.
+    pub fn restore_todos(
+        repo_id: &str,
+        items: Vec<TodoItem>,
+    ) -> Result<Vec<TodoItem>, PersistenceError> {
+        let mut todos = Self::load_todos(repo_id)?;
+
+        let mut restored: Vec<TodoItem> = items
+            .into_iter()
+            .filter(|item| !todos.items.iter().any(|t| t.id == item.id))
+            .collect();
+        let ids: Vec<String> = todos
+            .items
+            .iter()
+            .chain(&restored)
+            .map(|item| item.id.clone())
+            .collect();
+        for item in &mut restored {
+            item.repo_id = repo_id.to_string();
+            if item.parent_id.as_ref().is_some_and(|p| !ids.contains(p)) {
+                item.parent_id = None;
+            }
+        }
+
+        todos.items.extend(restored.iter().cloned());
+        Self::save_todos(repo_id, &todos)?;
+
+        info!(
+            "Restored {} TODO item(s) in repo {}",
+            restored.len(),
+            repo_id
+        );
+        Ok(restored)
     }
 
     /// Toggle TODO completion status
@@ -0,0 +1,103 @@
+//! Session transcript export
+//!
+//! The plain transcript is the session's output history with escape
+//! sequences stripped. As Markdown, Claude sessions are rendered from the
+//! provider's conversation file when it can be read; other sessions get the
+//! plain transcript in a code block.
+
+use crate::providers::{ConversationMessage, Role};
+use crate::session::strip_ansi;
+
+/// Output history as text: escapes stripped, blank line runs collapsed
+pub fn plain_transcript(history: &[u8]) -> String {
+    let mut out = String::new();
+    let mut blank = true; // Skip leading blank lines
+    for line in strip_ansi(history).lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            if !blank {
+                out.push('\n');
+            }
+            blank = true;
+            continue;
+        }
+        out.push_str(line);
+        out.push('\n');
+        blank = false;
+    }
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    out
+}
+
+/// Plain transcript as a Markdown document
+pub fn markdown_transcript(title: &str, plain: &str) -> String {
+    let fence = code_fence(plain);
+    format!(
+        "# {}\n\n{}text\n{}\n{}\n",
+        title,
+        fence,
+        plain.trim_end_matches('\n'),
+        fence
+    )
+}
+
+/// Conversation as a Markdown document with a section per message
+pub fn conversation_markdown(title: &str, messages: &[ConversationMessage]) -> String {
+    let mut out = format!("# {}\n", title);
+    for message in messages {
+        let heading = match message.role {
+            Role::User => "User",
+            Role::Assistant => "Assistant",
+        };
+        out.push_str(&format!("\n## {}\n\n{}\n", heading, message.text));
+    }
+    out
+}
+
+/// Backtick fence longer than any backtick run in `text`
+fn code_fence(text: &str) -> String {
+    let longest = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat((longest + 1).max(3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_transcript() {
+        let history = b"\r\n\x1b[32m$ cargo test\x1b[0m   \r\n\r\n\r\n\r\nok\r\n\r\n";
+        assert_eq!(plain_transcript(history), "$ cargo test\n\nok\n");
+    }
+
+    #[test]
+    fn test_markdown_transcript_fence() {
+        assert_eq!(
+            markdown_transcript("build", "ok\n"),
+            "# build\n\n```text\nok\n```\n"
+        );
+        assert_eq!(
+            markdown_transcript("docs", "```rust\n```\n"),
+            "# docs\n\n````text\n```rust\n```\n````\n"
+        );
+    }
+
+    #[test]
+    fn test_conversation_markdown() {
+        let messages = vec![
+            ConversationMessage {
+                role: Role::User,
+                text: "Fix the bug".to_string(),
+            },
+            ConversationMessage {
+                role: Role::Assistant,
+                text: "Fixed.".to_string(),
+            },
+        ];
+        assert_eq!(
+            conversation_markdown("fix", &messages),
+            "# fix\n\n## User\n\nFix the bug\n\n## Assistant\n\nFixed.\n"
+        );
+    }
+}
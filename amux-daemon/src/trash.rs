@@ -0,0 +1,73 @@
+//! Trash for destroyed sessions
+//!
+//! A session destroyed with `trash` set is stopped and kept (metadata and
+//! history on disk, the session in `AppState::trash`) so the client can undo
+//! the destroy. A background task deletes the data of sessions that have been
+//! in the trash longer than the configured TTL. Sessions still marked as
+//! trashed when the daemon starts are deleted right away.
+
+use crate::persistence;
+use crate::state::SharedState;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How often the trash is checked for expired sessions
+const PURGE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Current Unix time in seconds
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Whether something trashed at `trashed_at` can no longer be restored
+pub fn is_expired(trashed_at: u64, now: u64, ttl: Duration) -> bool {
+    now.saturating_sub(trashed_at) >= ttl.as_secs()
+}
+
+/// Spawn the task deleting expired sessions from the trash
+pub fn spawn_purger(state: SharedState, ttl: Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(PURGE_INTERVAL);
+        loop {
+            ticker.tick().await;
+            let now = now_secs();
+
+            let mut state = state.write().await;
+            let expired: Vec<String> = state
+                .trash
+                .values()
+                .filter(|s| s.trashed_at.is_none_or(|at| is_expired(at, now, ttl)))
+                .map(|s| s.id.clone())
+                .collect();
+            for id in expired {
+                let Some(session) = state.trash.remove(&id) else {
+                    continue;
+                };
+                if let Err(e) = persistence::delete_session_data(&id) {
+                    tracing::warn!("Failed to delete trashed session {}: {}", id, e);
+                }
+                if session.checkpoint.is_some() {
+                    state.prune_checkpoint(&session.repo_id, &id);
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_expired() {
+        let ttl = Duration::from_secs(30);
+        assert!(!is_expired(100, 100, ttl));
+        assert!(!is_expired(100, 129, ttl));
+        assert!(is_expired(100, 130, ttl));
+        // Clock moved backwards
+        assert!(!is_expired(100, 50, ttl));
+        assert!(is_expired(100, 100, Duration::ZERO));
+    }
+}
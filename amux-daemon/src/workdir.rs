@@ -0,0 +1,146 @@
+//! Directories inside a worktree: where shell sessions start, and listings
+//! for completing paths
+//!
+//! Paths come from clients relative to the worktree root and must stay
+//! inside it: absolute paths and `..` are rejected outright, and symlinks
+//! leading out of the worktree are caught after resolving.
+
+use crate::error::SessionError;
+use std::path::{Component, Path, PathBuf};
+
+/// Normalize a directory given relative to a worktree
+///
+/// `.` components and trailing slashes are dropped; an empty result is the
+/// worktree root.
+pub fn normalize(dir: &str) -> Result<PathBuf, SessionError> {
+    let mut out = PathBuf::new();
+    for component in Path::new(dir).components() {
+        match component {
+            Component::Normal(part) => out.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Err(SessionError::InvalidCwd(dir.to_string()));
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Resolve `dir` to an existing directory inside `worktree`
+///
+/// Returns the normalized relative path.
+pub fn resolve(worktree: &Path, dir: &str) -> Result<PathBuf, SessionError> {
+    let relative = normalize(dir)?;
+    let invalid = || SessionError::InvalidCwd(dir.to_string());
+    let root = worktree.canonicalize().map_err(|_| invalid())?;
+    let target = worktree
+        .join(&relative)
+        .canonicalize()
+        .map_err(|_| invalid())?;
+    if !target.is_dir() || !target.starts_with(&root) {
+        return Err(invalid());
+    }
+    Ok(relative)
+}
+
+/// An entry of a listed directory
+#[derive(Debug, PartialEq, Eq)]
+pub struct DirEntry {
+    pub name: String,
+    pub is_dir: bool,
+}
+
+/// Entries of `dir` in `worktree`, directories first, then by name
+///
+/// `.git` is always left out, and so is every entry whose path relative
+/// to the worktree `ignored` returns true for.
+pub fn list(
+    worktree: &Path,
+    dir: &str,
+    ignored: impl Fn(&Path) -> bool,
+) -> Result<Vec<DirEntry>, SessionError> {
+    let relative = resolve(worktree, dir)?;
+    let entries = std::fs::read_dir(worktree.join(&relative))
+        .map_err(|_| SessionError::InvalidCwd(dir.to_string()))?;
+    let mut listed: Vec<DirEntry> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let is_dir = entry.path().is_dir();
+            let skip = name == ".git" || ignored(&relative.join(&name));
+            (!skip).then_some(DirEntry { name, is_dir })
+        })
+        .collect();
+    listed.sort_by(|a, b| (!a.is_dir, &a.name).cmp(&(!b.is_dir, &b.name)));
+    Ok(listed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize("packages/./api/").unwrap(),
+            PathBuf::from("packages/api")
+        );
+        assert_eq!(normalize("").unwrap(), PathBuf::new());
+        assert!(normalize("../other").is_err());
+        assert!(normalize("packages/../../other").is_err());
+        assert!(normalize("/etc").is_err());
+    }
+
+    #[test]
+    fn test_resolve() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("packages/api")).unwrap();
+        std::fs::write(root.join("packages/README.md"), "").unwrap();
+
+        assert_eq!(
+            resolve(root, "packages/api").unwrap(),
+            PathBuf::from("packages/api")
+        );
+        assert!(resolve(root, "packages/README.md").is_err());
+        assert!(resolve(root, "missing").is_err());
+    }
+
+    #[test]
+    fn test_list() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("packages/web")).unwrap();
+        std::fs::create_dir_all(root.join("packages/api")).unwrap();
+        std::fs::create_dir_all(root.join("packages/node_modules")).unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::write(root.join("packages/README.md"), "").unwrap();
+        std::fs::write(root.join(".env"), "").unwrap();
+
+        let entry = |name: &str, is_dir| DirEntry {
+            name: name.to_string(),
+            is_dir,
+        };
+        assert_eq!(
+            list(root, "", |_| false).unwrap(),
+            vec![entry("packages", true), entry(".env", false)]
+        );
+        assert_eq!(
+            list(root, "packages", |p| p.ends_with("node_modules")).unwrap(),
+            vec![
+                entry("api", true),
+                entry("web", true),
+                entry("README.md", false)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_rejects_symlink_escape() {
+        let outside = tempfile::tempdir().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        std::os::unix::fs::symlink(outside.path(), dir.path().join("out")).unwrap();
+
+        assert!(resolve(dir.path(), "out").is_err());
+    }
+}
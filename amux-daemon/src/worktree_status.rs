@@ -0,0 +1,137 @@
+//! Cached worktree summaries (dirty flag, ahead/behind upstream, diff stats)
+//!
+//! Summaries are computed lazily when worktrees are listed and dropped by the
+//! file watcher whenever the worktree changes. Computation runs on a blocking
+//! thread with a timeout so that very large repositories don't stall
+//! `ListWorktrees`; a summary that misses the deadline is reported as unknown
+//! and lands in the cache once the background computation finishes, unless
+//! the cache was invalidated while it ran.
+
+use crate::git::{GitOps, WorktreeSummary};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::debug;
+
+/// Shared cache of worktree summaries keyed by "repo_id/branch"
+#[derive(Clone)]
+pub struct WorktreeStatusCache {
+    entries: Arc<Mutex<Entries>>,
+    timeout: Duration,
+}
+
+#[derive(Default)]
+struct Entries {
+    summaries: HashMap<String, WorktreeSummary>,
+    /// Bumped by every invalidation, so computations started before one
+    /// don't store what they read
+    generation: u64,
+}
+
+impl WorktreeStatusCache {
+    /// Create a cache whose computations give up after `timeout`
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(Entries::default())),
+            timeout,
+        }
+    }
+
+    fn key(repo_id: &str, branch: &str) -> String {
+        format!("{}/{}", repo_id, branch)
+    }
+
+    /// Get the summary for a worktree, computing it if not cached
+    ///
+    /// Returns `None` when the summary couldn't be computed in time.
+    pub async fn get_or_compute(
+        &self,
+        repo_id: &str,
+        branch: &str,
+        worktree_path: PathBuf,
+    ) -> Option<WorktreeSummary> {
+        let key = Self::key(repo_id, branch);
+        let generation = {
+            let entries = self.entries.lock().ok()?;
+            if let Some(summary) = entries.summaries.get(&key) {
+                return Some(*summary);
+            }
+            entries.generation
+        };
+
+        let cache = self.clone();
+        let task_key = key.clone();
+        let task = tokio::task::spawn_blocking(move || {
+            let summary = GitOps::worktree_summary(&worktree_path).ok()?;
+            cache.store(task_key, summary, generation);
+            Some(summary)
+        });
+
+        match tokio::time::timeout(self.timeout, task).await {
+            Ok(Ok(summary)) => summary,
+            Ok(Err(_)) => None,
+            Err(_) => {
+                debug!("Worktree summary for {} timed out", key);
+                None
+            }
+        }
+    }
+
+    /// Cache a summary computed from the worktree as of `generation`
+    fn store(&self, key: String, summary: WorktreeSummary, generation: u64) {
+        if let Ok(mut entries) = self.entries.lock() {
+            if entries.generation == generation {
+                entries.summaries.insert(key, summary);
+            }
+        }
+    }
+
+    /// Drop the cached summary for a worktree
+    pub fn invalidate(&self, repo_id: &str, branch: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.summaries.remove(&Self::key(repo_id, branch));
+            entries.generation += 1;
+        }
+    }
+
+    /// Drop cached summaries for every worktree of a repo
+    ///
+    /// Used when refs change, since ahead/behind of any branch may move.
+    pub fn invalidate_repo(&self, repo_id: &str) {
+        let prefix = format!("{}/", repo_id);
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.summaries.retain(|key, _| !key.starts_with(&prefix));
+            entries.generation += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cached(cache: &WorktreeStatusCache, key: &str) -> Option<WorktreeSummary> {
+        cache.entries.lock().unwrap().summaries.get(key).copied()
+    }
+
+    #[test]
+    fn test_store_skips_results_older_than_invalidation() {
+        let cache = WorktreeStatusCache::new(Duration::from_millis(100));
+        let summary = WorktreeSummary {
+            dirty: true,
+            ..Default::default()
+        };
+
+        let generation = cache.entries.lock().unwrap().generation;
+        cache.invalidate("repo", "main");
+        cache.store("repo/main".to_string(), summary, generation);
+        assert_eq!(cached(&cache, "repo/main"), None);
+
+        let generation = cache.entries.lock().unwrap().generation;
+        cache.store("repo/main".to_string(), summary, generation);
+        assert_eq!(cached(&cache, "repo/main"), Some(summary));
+        cache.invalidate_repo("repo");
+        assert_eq!(cached(&cache, "repo/main"), None);
+    }
+}
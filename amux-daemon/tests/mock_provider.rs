@@ -0,0 +1,62 @@
+//! Output of the `amux-mock-provider` helper for each script kind
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn run(script: &str, args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_amux-mock-provider"))
+        .env("AMUX_MOCK_SCRIPT", script)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn test_echo_script() {
+    let output = run(
+        r#"{"kind":"echo"}"#,
+        &["--model", "mock-fast", "--resume", "abc"],
+        "hello\nexit\nignored\n",
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "Model: mock-fast\nResumed session: abc\nMock AI ready. Type 'exit' to quit.\n\
+         Mock response to: hello\n"
+    );
+}
+
+#[test]
+fn test_delay_script() {
+    let output = run(r#"{"kind":"delay","ms":20}"#, &[], "");
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("Mock AI ready."));
+}
+
+#[test]
+fn test_lines_script() {
+    let output = run(r#"{"kind":"lines","lines":["one","two"]}"#, &[], "");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "one\ntwo\n");
+}
+
+#[test]
+fn test_fail_script() {
+    let output = run(r#"{"kind":"fail","message":"no credentials"}"#, &[], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "no credentials\n");
+}
@@ -1,3 +1,5 @@
+// Event's oneof holds SessionInfo by value; prost decides the layout
+#[allow(clippy::large_enum_variant)]
 pub mod daemon {
     tonic::include_proto!("ccm.daemon");
 }
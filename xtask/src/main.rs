@@ -3,8 +3,14 @@ use std::os::unix::fs::PermissionsExt;
 use std::path::PathBuf;
 use std::process::Command;
 
-use anyhow::{bail, Context, Result};
+use amux_proto::daemon::ccm_daemon_client::CcmDaemonClient;
+use amux_proto::daemon::{ExportWorkspaceRequest, ImportWorkspaceRequest};
+use anyhow::{anyhow, bail, Context, Result};
 use clap::{Parser, Subcommand};
+use hyper_util::rt::TokioIo;
+use tokio::net::UnixStream;
+use tonic::transport::{Channel, Endpoint, Uri};
+use tower::service_fn;
 
 #[derive(Parser)]
 #[command(name = "xtask")]
@@ -22,6 +28,16 @@ enum Commands {
         #[arg(long)]
         path: Option<PathBuf>,
     },
+    /// Back up repos, sessions and terminal history to a .tar.gz (daemon must be running)
+    Export {
+        /// Archive to write
+        path: PathBuf,
+    },
+    /// Merge a backup into the running daemon (existing sessions are kept)
+    Import {
+        /// Archive to read
+        path: PathBuf,
+    },
 }
 
 fn main() -> Result<()> {
@@ -29,7 +45,72 @@ fn main() -> Result<()> {
 
     match cli.command {
         Commands::Install { path } => install(path),
+        Commands::Export { path } => export_workspace(path),
+        Commands::Import { path } => import_workspace(path),
+    }
+}
+
+fn export_workspace(path: PathBuf) -> Result<()> {
+    let output_path = std::path::absolute(&path)?.to_string_lossy().to_string();
+    let result = tokio::runtime::Runtime::new()?.block_on(async {
+        let mut client = connect_daemon().await?;
+        let response = client
+            .export_workspace(ExportWorkspaceRequest { output_path })
+            .await
+            .map_err(|status| anyhow!("{}", status.message()))?;
+        anyhow::Ok(response.into_inner())
+    })?;
+
+    println!(
+        "Exported {} repo(s) and {} session(s) to {}",
+        result.repo_count, result.session_count, result.path
+    );
+    Ok(())
+}
+
+fn import_workspace(path: PathBuf) -> Result<()> {
+    let archive_path = std::path::absolute(&path)?.to_string_lossy().to_string();
+    let result = tokio::runtime::Runtime::new()?.block_on(async {
+        let mut client = connect_daemon().await?;
+        let response = client
+            .import_workspace(ImportWorkspaceRequest { archive_path })
+            .await
+            .map_err(|status| anyhow!("{}", status.message()))?;
+        anyhow::Ok(response.into_inner())
+    })?;
+
+    println!(
+        "Imported {} repo(s) and {} session(s) (archive from amux-daemon {})",
+        result.imported_repos, result.imported_sessions, result.daemon_version
+    );
+    for skipped in &result.skipped {
+        println!("  Skipped {}", skipped);
     }
+    Ok(())
+}
+
+/// Connect to the running daemon's Unix socket
+async fn connect_daemon() -> Result<CcmDaemonClient<Channel>> {
+    let home = std::env::var("HOME").context("HOME environment variable not set")?;
+    let socket_path = PathBuf::from(home).join(".amux").join("daemon.sock");
+    if !socket_path.exists() {
+        bail!(
+            "amux-daemon is not running ({} not found)",
+            socket_path.display()
+        );
+    }
+
+    let channel = Endpoint::try_from("http://[::]:50051")?
+        .connect_with_connector(service_fn(move |_: Uri| {
+            let path = socket_path.clone();
+            async move {
+                let stream = UnixStream::connect(path).await?;
+                Ok::<_, std::io::Error>(TokioIo::new(stream))
+            }
+        }))
+        .await
+        .context("Failed to connect to amux-daemon")?;
+    Ok(CcmDaemonClient::new(channel))
 }
 
 fn install(custom_path: Option<PathBuf>) -> Result<()> {